@@ -0,0 +1,268 @@
+//! bridge-status 命令
+//!
+//! 查询 controller 内嵌 bridge host 的运行状态与已连接会话
+//!
+//! ```bash
+//! piper-cli bridge-status
+//! piper-cli bridge-status --endpoint tcp://10.0.0.2:18888 --tls-ca ca.pem \
+//!     --tls-client-cert client.pem --tls-client-key client-key.pem --json
+//! ```
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use piper_sdk::{
+    BridgeClientOptions, BridgeDeviceState, BridgeEndpoint, BridgeRole, BridgeSessionInfo,
+    BridgeStatus, BridgeTlsClientConfig, PiperBridgeClient,
+};
+use serde_json::{Value, json};
+use std::path::PathBuf;
+use tokio::task::spawn_blocking;
+
+/// Unix 平台默认的 bridge socket
+#[cfg(unix)]
+const DEFAULT_BRIDGE_ENDPOINT: Option<&str> = Some("/tmp/piper_bridge.sock");
+#[cfg(not(unix))]
+const DEFAULT_BRIDGE_ENDPOINT: Option<&str> = None;
+
+/// bridge 状态查询参数
+#[derive(Args, Debug)]
+pub struct BridgeStatusCommand {
+    /// bridge 地址：`unix:/path`、`/path` 或 `tcp://host:port`（TCP 需 TLS 参数）
+    #[arg(long)]
+    pub endpoint: Option<String>,
+
+    /// TCP-TLS：CA 证书 PEM
+    #[arg(long)]
+    pub tls_ca: Option<PathBuf>,
+
+    /// TCP-TLS：客户端证书 PEM
+    #[arg(long)]
+    pub tls_client_cert: Option<PathBuf>,
+
+    /// TCP-TLS：客户端私钥 PEM
+    #[arg(long)]
+    pub tls_client_key: Option<PathBuf>,
+
+    /// TCP-TLS：服务端证书名称
+    #[arg(long, default_value = "localhost")]
+    pub tls_server_name: String,
+
+    /// 以 JSON 输出
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl BridgeStatusCommand {
+    fn endpoint(&self) -> Result<BridgeEndpoint> {
+        let raw = self
+            .endpoint
+            .as_deref()
+            .or(DEFAULT_BRIDGE_ENDPOINT)
+            .context("当前平台没有默认 bridge 地址，请使用 --endpoint 指定")?;
+        raw.parse().with_context(|| format!("无效的 bridge 地址 {raw}"))
+    }
+
+    fn options(&self, endpoint: &BridgeEndpoint) -> Result<BridgeClientOptions> {
+        let tcp_tls = match endpoint {
+            BridgeEndpoint::Unix(_) => None,
+            BridgeEndpoint::TcpTls(_) => {
+                let (Some(ca), Some(cert), Some(key)) =
+                    (&self.tls_ca, &self.tls_client_cert, &self.tls_client_key)
+                else {
+                    bail!("TCP bridge 地址需要 --tls-ca、--tls-client-cert 和 --tls-client-key");
+                };
+                Some(BridgeTlsClientConfig {
+                    ca_cert_pem: ca.clone(),
+                    client_cert_pem: cert.clone(),
+                    client_key_pem: key.clone(),
+                    server_name: self.tls_server_name.clone(),
+                })
+            },
+        };
+        Ok(BridgeClientOptions {
+            tcp_tls,
+            ..Default::default()
+        })
+    }
+
+    pub async fn execute(&self) -> Result<()> {
+        let endpoint = self.endpoint()?;
+        let options = self.options(&endpoint)?;
+        let label = endpoint.to_string();
+
+        let (status, sessions) = spawn_blocking(move || -> Result<_> {
+            let mut client = PiperBridgeClient::connect(endpoint, options)
+                .with_context(|| format!("无法连接 bridge {label}"))?;
+            let status = client.get_status()?;
+            let sessions = client.list_sessions()?;
+            client.disconnect();
+            Ok((status, sessions))
+        })
+        .await??;
+
+        if self.json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&status_json(&status, &sessions))?
+            );
+        } else {
+            print_status(&status, &sessions);
+        }
+        Ok(())
+    }
+}
+
+fn device_state_name(state: BridgeDeviceState) -> &'static str {
+    match state {
+        BridgeDeviceState::Disconnected => "disconnected",
+        BridgeDeviceState::Connected => "connected",
+        BridgeDeviceState::Reconnecting => "reconnecting",
+    }
+}
+
+fn role_name(role: BridgeRole) -> &'static str {
+    match role {
+        BridgeRole::Observer => "observer",
+        BridgeRole::WriterCandidate => "writer-candidate",
+    }
+}
+
+fn status_json(status: &BridgeStatus, sessions: &[BridgeSessionInfo]) -> Value {
+    json!({
+        "device_state": device_state_name(status.device_state),
+        "health_score": status.health_score,
+        "rx_fps": f64::from(status.rx_fps_x1000) / 1000.0,
+        "tx_fps": f64::from(status.tx_fps_x1000) / 1000.0,
+        "ipc_in_fps": f64::from(status.ipc_in_fps_x1000) / 1000.0,
+        "ipc_out_fps": f64::from(status.ipc_out_fps_x1000) / 1000.0,
+        "rx_frame_count": status.rx_frame_count,
+        "tx_frame_count": status.tx_frame_count,
+        "usb_stall_count": status.usb_stall_count,
+        "can_bus_off_count": status.can_bus_off_count,
+        "can_error_passive_count": status.can_error_passive_count,
+        "cpu_usage_percent": status.cpu_usage_percent,
+        "queue_drop_count": status.queue_drop_count,
+        "inactive_enqueue_count": status.inactive_enqueue_count,
+        "session_replacement_discard_count": status.session_replacement_discard_count,
+        "send_throttle_count": status.send_throttle_count,
+        "session_count": status.session_count,
+        "sessions": sessions
+            .iter()
+            .map(|session| json!({
+                "session_id": session.session_id,
+                "role": role_name(session.role_granted),
+                "peer": session.peer,
+                "idle_ms": session.idle_ms,
+                "raw_frame_tap": session.raw_frame_tap,
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn print_status(status: &BridgeStatus, sessions: &[BridgeSessionInfo]) {
+    println!(
+        "🔌 设备: {} | 健康度: {}",
+        device_state_name(status.device_state),
+        status.health_score
+    );
+    println!(
+        "   RX: {} 帧 ({:.1} fps) | TX: {} 帧 ({:.1} fps)",
+        status.rx_frame_count,
+        f64::from(status.rx_fps_x1000) / 1000.0,
+        status.tx_frame_count,
+        f64::from(status.tx_fps_x1000) / 1000.0
+    );
+    println!(
+        "   USB stall: {} | Bus-off: {} | Error-passive: {}",
+        status.usb_stall_count, status.can_bus_off_count, status.can_error_passive_count
+    );
+    println!(
+        "   队列丢帧: {} | 限流拒绝: {} | 会话替换丢弃: {}",
+        status.queue_drop_count,
+        status.send_throttle_count,
+        status.session_replacement_discard_count
+    );
+    println!();
+    println!("👥 会话: {}", status.session_count);
+    println!(
+        "{:>6} {:<17} {:<32} {:>9} {:>6}",
+        "ID", "角色", "对端", "空闲ms", "抓帧"
+    );
+    for session in sessions {
+        println!(
+            "{:>6} {:<17} {:<32} {:>9} {:>6}",
+            session.session_id,
+            role_name(session.role_granted),
+            session.peer,
+            session.idle_ms,
+            if session.raw_frame_tap { "yes" } else { "no" }
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(endpoint: Option<&str>) -> BridgeStatusCommand {
+        BridgeStatusCommand {
+            endpoint: endpoint.map(str::to_string),
+            tls_ca: None,
+            tls_client_cert: None,
+            tls_client_key: None,
+            tls_server_name: "localhost".to_string(),
+            json: true,
+        }
+    }
+
+    #[test]
+    fn tcp_endpoint_requires_tls_material() {
+        let cmd = command(Some("tcp://127.0.0.1:18888"));
+        let endpoint = cmd.endpoint().unwrap();
+        assert!(cmd.options(&endpoint).is_err());
+
+        let mut cmd = cmd;
+        cmd.tls_ca = Some("ca.pem".into());
+        cmd.tls_client_cert = Some("client.pem".into());
+        cmd.tls_client_key = Some("client-key.pem".into());
+        let options = cmd.options(&endpoint).unwrap();
+        assert_eq!(options.tcp_tls.unwrap().server_name, "localhost");
+    }
+
+    #[test]
+    fn status_json_lists_sessions() {
+        let status = BridgeStatus {
+            device_state: BridgeDeviceState::Connected,
+            rx_fps_x1000: 1_500,
+            tx_fps_x1000: 0,
+            ipc_out_fps_x1000: 0,
+            ipc_in_fps_x1000: 0,
+            health_score: 100,
+            usb_stall_count: 0,
+            can_bus_off_count: 1,
+            can_error_passive_count: 0,
+            cpu_usage_percent: 0,
+            session_count: 1,
+            queue_drop_count: 0,
+            inactive_enqueue_count: 0,
+            session_replacement_discard_count: 0,
+            send_throttle_count: 2,
+            rx_frame_count: 42,
+            tx_frame_count: 3,
+        };
+        let sessions = [BridgeSessionInfo {
+            session_id: 7,
+            role_granted: BridgeRole::WriterCandidate,
+            peer: "unix://peer".to_string(),
+            idle_ms: 10,
+            raw_frame_tap: true,
+        }];
+
+        let json = status_json(&status, &sessions);
+        assert_eq!(json["device_state"], "connected");
+        assert_eq!(json["rx_fps"], 1.5);
+        assert_eq!(json["rx_frame_count"], 42);
+        assert_eq!(json["sessions"][0]["role"], "writer-candidate");
+        assert_eq!(json["sessions"][0]["peer"], "unix://peer");
+    }
+}
@@ -0,0 +1,151 @@
+//! 反馈帧解码命令
+//!
+//! 离线解释一帧原始 CAN 数据（例如 candump 日志中的一行），不需要连接机器人。
+//!
+//! ```bash
+//! piper-cli decode 2A5 00 00 46 50 FF FF FC 18
+//! piper-cli decode 0x2A1 0107010000000500
+//! piper-cli decode 2A5#00004650FFFFFC18 --json
+//! ```
+
+use crate::validation::{frame_from_parts, parse_can_id, parse_frame_literal, parse_hex_bytes};
+use anyhow::{Result, bail};
+use clap::Args;
+use piper_sdk::protocol::PiperFrame;
+use piper_tools::decode::decode_feedback;
+use serde_json::{Value, json};
+
+#[derive(Args, Debug, Clone)]
+pub struct DecodeCommand {
+    /// CAN ID（十六进制，可带 0x 前缀；也接受 candump 的 `ID#DATA` 形式）
+    pub id: String,
+
+    /// 数据字节（十六进制，可拆成多个参数，也可用空格/`.`/`:` 分隔）
+    pub data: Vec<String>,
+
+    /// 以 JSON 输出
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl DecodeCommand {
+    pub fn execute(&self) -> Result<()> {
+        let frame = self.parse_frame()?;
+        let Some(decoded) = decode_feedback(&frame) else {
+            bail!(
+                "{} is not a known feedback frame",
+                format_id(frame.raw_id(), frame.is_extended())
+            );
+        };
+
+        if self.json {
+            let output = json!({
+                "can_id": frame.raw_id(),
+                "extended": frame.is_extended(),
+                "data": frame.data(),
+                "name": decoded.name,
+                "decoded": Value::Object(decoded.fields),
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+            return Ok(());
+        }
+
+        println!(
+            "{} {}  [{}]",
+            format_id(frame.raw_id(), frame.is_extended()),
+            decoded.name,
+            format_bytes(frame.data())
+        );
+        let width = decoded.fields.keys().map(String::len).max().unwrap_or(0);
+        for (field, value) in &decoded.fields {
+            println!("  {field:<width$}  {}", format_value(value));
+        }
+        Ok(())
+    }
+
+    fn parse_frame(&self) -> Result<PiperFrame> {
+        let (id, mut data) = if self.id.contains('#') {
+            let frame = parse_frame_literal(&self.id)?;
+            (frame.id(), frame.data().to_vec())
+        } else {
+            (parse_can_id(&self.id)?, Vec::new())
+        };
+        for part in &self.data {
+            data.extend(parse_hex_bytes(part)?);
+        }
+
+        if data.is_empty() {
+            bail!("missing data bytes");
+        }
+        frame_from_parts(id, &data)
+    }
+}
+
+fn format_id(id: u32, extended: bool) -> String {
+    if extended {
+        format!("0x{id:08X}")
+    } else {
+        format!("0x{id:03X}")
+    }
+}
+
+fn format_bytes(data: &[u8]) -> String {
+    data.iter().map(|byte| format!("{byte:02X}")).collect::<Vec<_>>().join(" ")
+}
+
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Number(number) => match number.as_f64() {
+            Some(float) if number.is_f64() => format!("{float:.4}"),
+            _ => number.to_string(),
+        },
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(id: &str, data: &[&str]) -> DecodeCommand {
+        DecodeCommand {
+            id: id.to_string(),
+            data: data.iter().map(|part| part.to_string()).collect(),
+            json: false,
+        }
+    }
+
+    #[test]
+    fn parse_frame_accepts_split_and_packed_bytes() {
+        let split = command("2A5", &["00", "00", "46", "50", "FF", "FF", "FC", "18"]);
+        let packed = command("0x2A5", &["00004650FFFFFC18"]);
+        let candump = command("2A5#00004650FFFFFC18", &[]);
+
+        let expected = split.parse_frame().unwrap();
+        assert_eq!(expected.raw_id(), 0x2A5);
+        assert!(!expected.is_extended());
+        assert_eq!(
+            expected.data(),
+            &[0x00, 0x00, 0x46, 0x50, 0xFF, 0xFF, 0xFC, 0x18]
+        );
+        assert_eq!(packed.parse_frame().unwrap(), expected);
+        assert_eq!(candump.parse_frame().unwrap(), expected);
+    }
+
+    #[test]
+    fn parse_frame_rejects_malformed_input() {
+        assert!(command("2A5", &[]).parse_frame().is_err());
+        assert!(command("2A5", &["0"]).parse_frame().is_err());
+        assert!(command("2A5", &["000102030405060708"]).parse_frame().is_err());
+        assert!(command("zz", &["00"]).parse_frame().is_err());
+    }
+
+    #[test]
+    fn decoded_joint_feedback_is_known() {
+        let frame = command("2A5", &["00004650FFFFFC18"]).parse_frame().unwrap();
+        let decoded = decode_feedback(&frame).unwrap();
+        assert_eq!(decoded.name, "JointFeedback12");
+        assert_eq!(format_value(&decoded.fields["j1_deg"]), "18.0000");
+    }
+}
@@ -1,7 +1,9 @@
 //! 命令定义和实现
 
+pub mod bridge_status;
 pub mod collision_protection;
 pub mod config;
+pub mod decode;
 pub mod gravity;
 pub mod home;
 pub mod r#move;
@@ -11,11 +13,14 @@ pub mod record;
 pub mod replay;
 pub mod run;
 pub mod set_zero;
+pub mod stats;
 pub mod stop;
 pub mod teleop;
 
+pub use bridge_status::BridgeStatusCommand;
 pub use collision_protection::CollisionProtectionCommand;
 pub use config::ConfigCommand;
+pub use decode::DecodeCommand;
 pub use gravity::{GravityAction, GravityCommand};
 pub use home::HomeCommand;
 pub use r#move::MoveCommand;
@@ -25,5 +30,6 @@ pub use record::RecordCommand;
 pub use replay::ReplayCommand;
 pub use run::RunCommand;
 pub use set_zero::SetZeroCommand;
+pub use stats::StatsCommand;
 pub use stop::StopCommand;
 pub use teleop::{TeleopAction, TeleopCommand};
@@ -5,15 +5,15 @@
 use crate::commands::config::CliConfig;
 use crate::connection::{TargetArgs, client_builder, resolved_target_spec};
 use anyhow::{Context, Result};
-use clap::Args;
+use clap::{Args, ValueEnum};
 use piper_control::TargetSpec;
 use piper_sdk::can::CanId;
 use piper_sdk::client::state::{CapabilityMarker, Standby};
 use piper_sdk::client::{ConnectedPiper, MotionConnectedState, Piper};
 use piper_sdk::driver::ConnectionTarget;
-use piper_sdk::{RecordingConfig, RecordingMetadata, StopCondition};
+use piper_sdk::{RecordingConfig, RecordingMetadata, RecordingMode, StopCondition};
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
@@ -39,6 +39,23 @@ pub struct RecordCommand {
     #[arg(short, long, value_parser = parse_can_id_arg)]
     pub stop_on_id: Option<CanId>,
 
+    /// 最长录制时长（如 `90`、`30s`、`5m`、`2h`），与 --stop-on-id 同时生效
+    #[arg(long, value_parser = parse_duration_arg)]
+    pub max_duration: Option<Duration>,
+
+    /// 最多录制帧数（所有分段合计）
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_frames: Option<u64>,
+
+    /// 按时长（如 `10m`）或文件大小（如 `100MB`）分段，
+    /// 输出为 `<name>.000.bin`、`<name>.001.bin`…
+    #[arg(long, value_parser = parse_split_arg)]
+    pub split_every: Option<SplitPolicy>,
+
+    /// 机械臂故障时的处理方式
+    #[arg(long, value_enum, default_value_t = OnFault::Continue)]
+    pub on_fault: OnFault,
+
     /// 跳过确认提示
     #[arg(long)]
     pub force: bool,
@@ -65,6 +82,92 @@ fn parse_u32_arg(value: &str) -> std::result::Result<u32, String> {
     }
 }
 
+/// 分段策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitPolicy {
+    /// 每段录制时长
+    Duration(Duration),
+    /// 每段文件大小上限（字节）
+    Size(u64),
+}
+
+impl std::fmt::Display for SplitPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Duration(duration) => write!(f, "每 {:.1} 秒", duration.as_secs_f64()),
+            Self::Size(bytes) => write!(f, "每 {:.1} MB", *bytes as f64 / (1024.0 * 1024.0)),
+        }
+    }
+}
+
+/// 机械臂故障时的录制行为
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OnFault {
+    /// 继续录制
+    #[default]
+    Continue,
+    /// 结束录制
+    Stop,
+}
+
+/// 解析时长：纯数字按秒，支持 `ms` / `s` / `m` / `h` 后缀
+fn parse_duration_arg(value: &str) -> std::result::Result<Duration, String> {
+    let value = value.trim();
+    let split = value.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse().map_err(|_| format!("invalid duration '{value}'"))?;
+    let seconds = match unit.trim() {
+        "" | "s" | "sec" => number,
+        "ms" => number / 1000.0,
+        "m" | "min" => number * 60.0,
+        "h" => number * 3600.0,
+        other => return Err(format!("unknown duration unit '{other}' (use ms/s/m/h)")),
+    };
+    if !seconds.is_finite() || seconds <= 0.0 {
+        return Err(format!("duration '{value}' must be positive"));
+    }
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+/// 解析文件大小：`B` / `KB` / `MB` / `GB`（1 KB = 1024 B）
+fn parse_size_arg(value: &str) -> std::result::Result<u64, String> {
+    let value = value.trim();
+    let split = value.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse().map_err(|_| format!("invalid size '{value}'"))?;
+    let scale = match unit.trim().to_ascii_uppercase().as_str() {
+        "B" => 1.0,
+        "K" | "KB" | "KIB" => 1024.0,
+        "M" | "MB" | "MIB" => 1024.0 * 1024.0,
+        "G" | "GB" | "GIB" => 1024.0 * 1024.0 * 1024.0,
+        other => return Err(format!("unknown size unit '{other}' (use B/KB/MB/GB)")),
+    };
+    let bytes = number * scale;
+    if !bytes.is_finite() || bytes < 1.0 {
+        return Err(format!("size '{value}' must be positive"));
+    }
+    Ok(bytes as u64)
+}
+
+/// 以 `B` 结尾（如 `100MB`）按文件大小分段，否则按时长分段
+fn parse_split_arg(value: &str) -> std::result::Result<SplitPolicy, String> {
+    if value.trim().to_ascii_uppercase().ends_with('B') {
+        parse_size_arg(value).map(SplitPolicy::Size)
+    } else {
+        parse_duration_arg(value).map(SplitPolicy::Duration)
+    }
+}
+
+/// 分段文件路径：`capture.bin` → `capture.003.bin`
+fn segment_output_path(base: &Path, index: usize) -> PathBuf {
+    let stem = base.file_stem().map(|stem| stem.to_string_lossy()).unwrap_or_default();
+    let name = match base.extension() {
+        Some(ext) => format!("{stem}.{index:03}.{}", ext.to_string_lossy()),
+        None => format!("{stem}.{index:03}"),
+    };
+    base.with_file_name(name)
+}
+
 fn format_can_id_arg(id: CanId) -> String {
     let format = if id.is_standard() {
         "standard"
@@ -78,9 +181,118 @@ fn format_can_id_arg(id: CanId) -> String {
 enum RecordRunOutcome {
     Completed,
     StoppedByCondition,
+    StoppedByFault,
     InterruptedByUser,
 }
 
+/// 单个分段的结束原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SegmentEnd {
+    /// 达到分段阈值，切换到下一个文件
+    Rotate,
+    /// 整个录制结束
+    Finished(RecordRunOutcome),
+}
+
+/// 录制计划：输出路径、停止条件与分段策略
+#[derive(Debug, Clone)]
+struct RecordPlan {
+    output: PathBuf,
+    stop_on_id: Option<CanId>,
+    /// 总时长上限（--duration 与 --max-duration 中较小者）
+    deadline: Option<Duration>,
+    max_frames: Option<u64>,
+    split: Option<SplitPolicy>,
+    stop_on_fault: bool,
+}
+
+impl RecordPlan {
+    fn from_command(cmd: &RecordCommand) -> Self {
+        let loop_timeout_secs =
+            RecordCommand::effective_loop_timeout_secs(cmd.duration, cmd.stop_on_id);
+        let duration_limit =
+            (loop_timeout_secs > 0).then(|| Duration::from_secs(loop_timeout_secs));
+        let deadline = match (duration_limit, cmd.max_duration) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+
+        Self {
+            output: PathBuf::from(&cmd.output),
+            stop_on_id: cmd.stop_on_id,
+            deadline,
+            max_frames: cmd.max_frames,
+            split: cmd.split_every,
+            stop_on_fault: cmd.on_fault == OnFault::Stop,
+        }
+    }
+
+    /// 第 `index` 段的输出路径（不分段时即为 --output）
+    fn segment_path(&self, index: usize) -> PathBuf {
+        if self.split.is_some() {
+            segment_output_path(&self.output, index)
+        } else {
+            self.output.clone()
+        }
+    }
+
+    /// 交给 SDK 的分段停止条件；总时长与分段阈值由录制循环控制
+    fn segment_stop_condition(&self, recorded: u64) -> StopCondition {
+        if self.stop_on_id.is_none()
+            && let Some(max_frames) = self.max_frames
+        {
+            return StopCondition::FrameCount(max_frames.saturating_sub(recorded) as usize);
+        }
+        if self.split.is_some() {
+            return self.stop_on_id.map_or(StopCondition::Manual, StopCondition::OnCanId);
+        }
+        let deadline_secs =
+            self.deadline.map_or(0, |deadline| deadline.as_secs_f64().ceil() as u64);
+        RecordCommand::effective_stop_condition(deadline_secs, self.stop_on_id)
+    }
+
+    fn segment_config(&self, index: usize, recorded: u64) -> RecordingConfig {
+        let stop_condition = self.segment_stop_condition(recorded);
+        let mut notes = match stop_condition {
+            StopCondition::OnCanId(id) => {
+                format!("CLI recording, stop_on_id={}", format_can_id_arg(id))
+            },
+            StopCondition::Duration(seconds) => format!("CLI recording, duration={seconds}"),
+            StopCondition::Manual => "CLI recording, manual stop".to_string(),
+            StopCondition::FrameCount(count) => format!("CLI recording, frame_count={count}"),
+        };
+        if let Some(split) = self.split {
+            notes.push_str(&format!(", segment={index}, split_every={split}"));
+        }
+
+        RecordingConfig {
+            output_path: self.segment_path(index),
+            stop_condition,
+            // 元数据（接口、波特率、开始时间）由 SDK 按文件写入
+            metadata: RecordingMetadata {
+                notes,
+                operator: RecordCommand::current_operator_name(),
+            },
+            // 分段录制面向长时间采集，流式写盘保证文件大小可观测
+            mode: if self.split.is_some() {
+                RecordingMode::streaming()
+            } else {
+                Default::default()
+            },
+            compression: Default::default(),
+        }
+    }
+
+    /// 当前分段是否达到切换阈值
+    fn should_rotate(&self, segment_elapsed: Duration, segment_bytes: u64) -> bool {
+        match self.split {
+            Some(SplitPolicy::Duration(every)) => segment_elapsed >= every,
+            Some(SplitPolicy::Size(limit)) => segment_bytes >= limit,
+            None => false,
+        }
+    }
+}
+
 fn classify_recording_outcome(
     still_running: bool,
     stop_requested: bool,
@@ -122,7 +334,8 @@ impl RecordCommand {
     pub async fn execute(&self) -> Result<()> {
         // === 1. 参数验证 ===
 
-        let output_path = PathBuf::from(&self.output);
+        let plan = RecordPlan::from_command(self);
+        let output_path = plan.segment_path(0);
 
         // 🔴 P0 安全修复：验证输出路径
         let validator = PathValidator::new();
@@ -132,7 +345,7 @@ impl RecordCommand {
 
         // 检查文件是否已存在
         if output_path.exists() && !self.force {
-            println!("⚠️  文件已存在: {}", self.output);
+            println!("⚠️  文件已存在: {}", output_path.display());
             print!("是否覆盖? [y/N] ");
             use std::io::Write;
             std::io::stdout().flush()?;
@@ -166,6 +379,18 @@ impl RecordCommand {
         if let Some(stop_id) = self.stop_on_id {
             println!("🛑 停止条件: CAN ID {}", format_can_id_arg(stop_id));
         }
+        if let Some(max_duration) = self.max_duration {
+            println!("⏱️  最长时长: {:.1} 秒", max_duration.as_secs_f64());
+        }
+        if let Some(max_frames) = self.max_frames {
+            println!("🔢 最多帧数: {max_frames}");
+        }
+        if let Some(split) = self.split_every {
+            println!("✂️  分段: {split}（{} …）", output_path.display());
+        }
+        if plan.stop_on_fault {
+            println!("🛑 机械臂故障时停止录制");
+        }
         let config = CliConfig::load()?;
         let target_spec = resolved_target_spec(&config, self.target.target.as_ref());
         println!("🎯 target: {}", target_spec);
@@ -209,29 +434,18 @@ impl RecordCommand {
         // === 5. 使用 spawn_blocking 隔离 ===
 
         // 在专用线程中运行录制逻辑
-        let output_path = self.output.clone();
-        let duration = self.duration;
         let target = target_spec.clone().into_connection_target();
-        let stop_on_id = self.stop_on_id;
         let running_for_task = running.clone();
 
         println!("💡 提示: 按 Ctrl-C 停止录制");
         println!();
 
         // 在专用线程中运行录制逻辑
-        let task = spawn_blocking(move || {
-            Self::record_sync(
-                output_path,
-                duration,
-                target,
-                target_spec,
-                stop_on_id,
-                running_for_task,
-            )
-        });
+        let task =
+            spawn_blocking(move || Self::record_sync(plan, target, target_spec, running_for_task));
 
         let result: Result<
-            Result<(piper_sdk::RecordingStats, RecordRunOutcome), anyhow::Error>,
+            Result<(Vec<piper_sdk::RecordingStats>, RecordRunOutcome), anyhow::Error>,
             tokio::task::JoinError,
         > = task.await;
 
@@ -239,21 +453,33 @@ impl RecordCommand {
 
         match result {
             Ok(inner_result) => match inner_result {
-                Ok((stats, outcome)) => {
+                Ok((segments, outcome)) => {
                     println!();
                     match outcome {
                         RecordRunOutcome::Completed => println!("✅ 录制完成"),
                         RecordRunOutcome::StoppedByCondition => {
                             println!("✅ 录制已按停止条件结束");
                         },
+                        RecordRunOutcome::StoppedByFault => {
+                            println!("⚠️  机械臂故障，录制已结束");
+                        },
                         RecordRunOutcome::InterruptedByUser => {
                             println!("⚠️  录制被用户中断");
                         },
                     }
-                    println!("   📊 帧数: {}", stats.frame_count);
-                    println!("   ⏱️  时长: {:.2}s", stats.duration.as_secs_f64());
-                    println!("   ⚠️ 丢帧: {}", stats.dropped_frames);
-                    println!("   💾 已保存: {}", stats.output_path.display());
+                    let frames: usize = segments.iter().map(|stats| stats.frame_count).sum();
+                    let dropped: u64 = segments.iter().map(|stats| stats.dropped_frames).sum();
+                    let duration: Duration = segments.iter().map(|stats| stats.duration).sum();
+                    println!("   📊 帧数: {frames}");
+                    println!("   ⏱️  时长: {:.2}s", duration.as_secs_f64());
+                    println!("   ⚠️ 丢帧: {dropped}");
+                    for stats in &segments {
+                        println!(
+                            "   💾 已保存: {} ({} 帧)",
+                            stats.output_path.display(),
+                            stats.frame_count
+                        );
+                    }
                     Ok(())
                 },
                 Err(e) => Err(e.context("录制失败")),
@@ -277,13 +503,11 @@ impl RecordCommand {
     /// 3. 录制循环（阻塞 + 可取消）
     /// 4. 停止录制并保存（安全退出）
     fn record_sync(
-        output_path: String,
-        duration: u64,
+        plan: RecordPlan,
         target: ConnectionTarget,
         target_spec: TargetSpec,
-        stop_on_id: Option<CanId>,
         running: Arc<AtomicBool>,
-    ) -> Result<(piper_sdk::RecordingStats, RecordRunOutcome)> {
+    ) -> Result<(Vec<piper_sdk::RecordingStats>, RecordRunOutcome)> {
         // === 1. 连接到机器人 ===
 
         println!("⏳ 连接到机器人...");
@@ -295,90 +519,93 @@ impl RecordCommand {
         let standby = builder.build()?;
         println!("✅ 已连接");
 
-        // ⚠️ 缓冲区警告（Phase 1 限制）
-        if duration == 0 || duration > 180 {
+        // ⚠️ 缓冲区警告（Phase 1 限制；分段录制使用流式写盘，不受限制）
+        let long_capture = plan.deadline.is_none_or(|deadline| deadline.as_secs() > 180);
+        if plan.split.is_none() && long_capture {
             println!();
             println!("⚠️  注意：当前版本主要用于短时录制（< 3分钟）");
-            println!("   超过此时长可能导致数据丢失（缓冲区限制）");
+            println!("   超过此时长可能导致数据丢失（缓冲区限制），长时间录制请使用 --split-every");
             println!();
         }
 
-        // === 2. 映射停止条件 ===
-
-        // ✅ 优先级：stop_on_id > duration > manual
-        let stop_condition = Self::effective_stop_condition(duration, stop_on_id);
-        let loop_timeout_secs = Self::effective_loop_timeout_secs(duration, stop_on_id);
-
-        // === 3. 启动录制 ===
-
-        let metadata = RecordingMetadata {
-            notes: match stop_condition {
-                StopCondition::OnCanId(id) => {
-                    format!("CLI recording, stop_on_id={}", format_can_id_arg(id))
-                },
-                StopCondition::Duration(seconds) => {
-                    format!("CLI recording, duration={seconds}")
-                },
-                StopCondition::Manual => "CLI recording, manual stop".to_string(),
-                StopCondition::FrameCount(count) => {
-                    format!("CLI recording, frame_count={count}")
-                },
-            },
-            operator: Self::current_operator_name(),
-        };
-
-        let config = RecordingConfig {
-            output_path: PathBuf::from(&output_path),
-            stop_condition,
-            metadata,
-        };
+        // === 2. 启动录制 ===
 
         match standby {
             ConnectedPiper::Strict(MotionConnectedState::Standby(standby)) => {
-                Self::record_with_standby(standby, config, loop_timeout_secs, running)
+                Self::record_with_standby(standby, &plan, running)
             },
             ConnectedPiper::Soft(MotionConnectedState::Standby(standby)) => {
-                Self::record_with_standby(standby, config, loop_timeout_secs, running)
+                Self::record_with_standby(standby, &plan, running)
             },
             ConnectedPiper::Strict(MotionConnectedState::Maintenance(_))
             | ConnectedPiper::Soft(MotionConnectedState::Maintenance(_)) => Err(anyhow::anyhow!(
                 "机械臂当前不在确认全失能的 Standby，请先执行 stop"
             )),
-            ConnectedPiper::Monitor(standby) => {
-                Self::record_with_standby(standby, config, loop_timeout_secs, running)
-            },
+            ConnectedPiper::Monitor(standby) => Self::record_with_standby(standby, &plan, running),
         }
     }
 
+    /// 逐段录制：每段独立 `start_recording()` / `stop_recording()`，
+    /// 因此每个文件都带有完整的录制元数据
     fn record_with_standby<Capability>(
-        standby: Piper<Standby, Capability>,
-        config: RecordingConfig,
-        duration: u64,
+        mut standby: Piper<Standby, Capability>,
+        plan: &RecordPlan,
         running: Arc<AtomicBool>,
-    ) -> Result<(piper_sdk::RecordingStats, RecordRunOutcome)>
+    ) -> Result<(Vec<piper_sdk::RecordingStats>, RecordRunOutcome)>
     where
         Capability: CapabilityMarker,
     {
-        let (standby, handle) = standby.start_recording(config)?;
-        println!("🔴 开始录制...");
-        println!();
+        let session_start = Instant::now();
+        let fault_latched_at_start = standby.robot_fault().is_some();
+        if plan.stop_on_fault && fault_latched_at_start {
+            println!("⚠️  机械臂已处于故障锁存状态，--on-fault stop 将不会触发");
+        }
 
-        // === 4. 循环逻辑（封装为独立函数，防止 panic 导致数据丢失）🛡️ ===
+        let mut segments = Vec::new();
+        let mut recorded = 0u64;
 
-        let loop_result = Self::recording_loop(&handle, &running, duration);
+        loop {
+            let index = segments.len();
+            let (next, handle) = standby.start_recording(plan.segment_config(index, recorded))?;
+            standby = next;
+            if index == 0 {
+                println!("🔴 开始录制...");
+            } else {
+                println!();
+                println!("✂️  切换到分段 {}", handle.output_path().display());
+            }
+            println!();
 
-        // === 5. 无论循环如何结束，都尝试保存数据 🛡️ ===
+            // === 3. 循环逻辑（封装为独立函数，防止 panic 导致数据丢失）🛡️ ===
 
-        println!();
-        println!("⏳ 正在保存录制...");
+            let fault_detected =
+                || plan.stop_on_fault && !fault_latched_at_start && standby.robot_fault().is_some();
+            let loop_result = Self::recording_loop(
+                &handle,
+                &running,
+                plan,
+                session_start,
+                recorded,
+                fault_detected,
+            );
 
-        let (_standby, stats) = standby.stop_recording(handle)?;
+            // === 4. 无论循环如何结束，都尝试保存数据 🛡️ ===
+
+            println!();
+            println!("⏳ 正在保存录制...");
 
-        // === 6. 然后再处理循环的错误（如果有） ===
+            let (next, stats) = standby.stop_recording(handle)?;
+            standby = next;
+            recorded = recorded.saturating_add(stats.frame_count as u64);
+            segments.push(stats);
 
-        let outcome = loop_result?;
+            // === 5. 然后再处理循环的错误（如果有） ===
 
-        Ok((stats, outcome))
+            match loop_result? {
+                SegmentEnd::Rotate => {},
+                SegmentEnd::Finished(outcome) => return Ok((segments, outcome)),
+            }
+        }
     }
 
     /// 录制循环（独立函数，错误不会影响数据保存）🛡️
@@ -392,41 +619,63 @@ impl RecordCommand {
     fn recording_loop(
         handle: &piper_sdk::RecordingHandle,
         running: &Arc<AtomicBool>,
-        duration: u64,
-    ) -> Result<RecordRunOutcome> {
-        let start = Instant::now();
-        let timeout = if duration > 0 {
-            Some(Duration::from_secs(duration))
-        } else {
-            None
-        };
-
+        plan: &RecordPlan,
+        session_start: Instant,
+        recorded_before: u64,
+        fault_detected: impl Fn() -> bool,
+    ) -> Result<SegmentEnd> {
+        let segment_start = Instant::now();
         let mut ticks = 0usize;
 
         while running.load(Ordering::Relaxed) {
-            // 1. 检查超时（精度 100ms）
-            if matches!(timeout, Some(duration) if start.elapsed() >= duration) {
+            // 1. 检查总时长（精度 100ms）
+            if plan.deadline.is_some_and(|deadline| session_start.elapsed() >= deadline) {
                 println!();
                 println!("⏳ 录制时长已到");
-                return Ok(classify_recording_outcome(true, false, true));
+                return Ok(SegmentEnd::Finished(classify_recording_outcome(
+                    true, false, true,
+                )));
             }
 
-            // 2. ✅ 检查 OnCanId 停止条件（Driver 层检测到触发帧）
+            // 2. ✅ 检查 SDK 停止条件（OnCanId / FrameCount 由 Driver 层检测）
             if handle.is_stop_requested() {
                 println!();
-                println!("🛑 检测到停止触发帧");
-                return Ok(classify_recording_outcome(true, true, false));
+                println!("🛑 检测到停止条件");
+                return Ok(SegmentEnd::Finished(classify_recording_outcome(
+                    true, true, false,
+                )));
+            }
+
+            // 3. 总帧数上限（--stop-on-id 同时给出时由此处兜底）
+            let total_frames = recorded_before.saturating_add(handle.frame_count());
+            if plan.max_frames.is_some_and(|max_frames| total_frames >= max_frames) {
+                println!();
+                println!("🔢 已达到帧数上限");
+                return Ok(SegmentEnd::Finished(RecordRunOutcome::StoppedByCondition));
+            }
+
+            // 4. 机械臂故障
+            if fault_detected() {
+                println!();
+                println!("🚨 检测到机械臂故障");
+                return Ok(SegmentEnd::Finished(RecordRunOutcome::StoppedByFault));
             }
 
-            // 3. ⚡ 短暂休眠（提升 Ctrl-C 响应速度）
+            // 5. 分段阈值
+            let segment_bytes =
+                std::fs::metadata(handle.output_path()).map_or(0, |meta| meta.len());
+            if plan.should_rotate(segment_start.elapsed(), segment_bytes) {
+                return Ok(SegmentEnd::Rotate);
+            }
+
+            // 6. ⚡ 短暂休眠（提升 Ctrl-C 响应速度）
             std::thread::sleep(Duration::from_millis(100));
             ticks += 1;
 
-            // 3. 每 1 秒（10 次 100ms）刷新一次 UI
+            // 7. 每 1 秒（10 次 100ms）刷新一次 UI
             if ticks.is_multiple_of(10) {
                 // 显示进度（使用 SDK 暴露的 getter 方法）
-                let elapsed = start.elapsed().as_secs();
-                let current_count = handle.frame_count(); // ✅ 使用新增方法
+                let elapsed = session_start.elapsed().as_secs();
                 let dropped = handle.dropped_count();
 
                 // ⚠️ 丢帧警告（缓冲区即将满）
@@ -439,14 +688,16 @@ impl RecordCommand {
                     "\r🔴 正在录制... [{:02}:{:02}] | 帧数: {} | 丢帧: {}",
                     elapsed / 60,
                     elapsed % 60,
-                    current_count,
+                    total_frames,
                     dropped
                 );
                 std::io::stdout().flush()?;
             }
         }
 
-        Ok(classify_recording_outcome(false, false, false))
+        Ok(SegmentEnd::Finished(classify_recording_outcome(
+            false, false, false,
+        )))
     }
 }
 
@@ -466,6 +717,10 @@ mod tests {
             },
             duration: 10,
             stop_on_id: Some(CanId::standard(0x2A5).unwrap()),
+            max_duration: None,
+            max_frames: None,
+            split_every: None,
+            on_fault: OnFault::Continue,
             force: false,
         };
 
@@ -482,6 +737,10 @@ mod tests {
             target: TargetArgs::default(),
             duration: 0,
             stop_on_id: None,
+            max_duration: None,
+            max_frames: None,
+            split_every: None,
+            on_fault: OnFault::Continue,
             force: false,
         };
 
@@ -501,6 +760,10 @@ mod tests {
             },
             duration: 30,
             stop_on_id: None,
+            max_duration: None,
+            max_frames: None,
+            split_every: None,
+            on_fault: OnFault::Continue,
             force: true,
         };
 
@@ -551,6 +814,118 @@ mod tests {
         assert_eq!(RecordCommand::effective_loop_timeout_secs(30, None), 30);
     }
 
+    fn command(duration: u64) -> RecordCommand {
+        RecordCommand {
+            output: "capture.bin".to_string(),
+            target: TargetArgs::default(),
+            duration,
+            stop_on_id: None,
+            max_duration: None,
+            max_frames: None,
+            split_every: None,
+            on_fault: OnFault::Continue,
+            force: true,
+        }
+    }
+
+    #[test]
+    fn parses_split_duration_and_size() {
+        assert_eq!(
+            parse_split_arg("10m").unwrap(),
+            SplitPolicy::Duration(Duration::from_secs(600))
+        );
+        assert_eq!(
+            parse_split_arg("90").unwrap(),
+            SplitPolicy::Duration(Duration::from_secs(90))
+        );
+        assert_eq!(
+            parse_split_arg("100MB").unwrap(),
+            SplitPolicy::Size(100 * 1024 * 1024)
+        );
+        assert_eq!(
+            parse_split_arg("512kb").unwrap(),
+            SplitPolicy::Size(512 * 1024)
+        );
+        assert_eq!(
+            parse_duration_arg("250ms").unwrap(),
+            Duration::from_millis(250)
+        );
+        assert!(parse_split_arg("0s").is_err());
+        assert!(parse_split_arg("10parsecs").is_err());
+        assert!(parse_split_arg("MB").is_err());
+    }
+
+    #[test]
+    fn segment_paths_are_numbered_before_extension() {
+        assert_eq!(
+            segment_output_path(Path::new("out/capture.bin"), 3),
+            PathBuf::from("out/capture.003.bin")
+        );
+        assert_eq!(
+            segment_output_path(Path::new("capture"), 12),
+            PathBuf::from("capture.012")
+        );
+
+        let mut cmd = command(0);
+        assert_eq!(
+            RecordPlan::from_command(&cmd).segment_path(1),
+            PathBuf::from("capture.bin")
+        );
+        cmd.split_every = Some(SplitPolicy::Size(1024));
+        assert_eq!(
+            RecordPlan::from_command(&cmd).segment_path(1),
+            PathBuf::from("capture.001.bin")
+        );
+    }
+
+    #[test]
+    fn plan_combines_duration_limits_and_frame_budget() {
+        let mut cmd = command(60);
+        cmd.max_duration = Some(Duration::from_secs(30));
+        cmd.max_frames = Some(1_000);
+        let plan = RecordPlan::from_command(&cmd);
+        assert_eq!(plan.deadline, Some(Duration::from_secs(30)));
+        assert!(matches!(
+            plan.segment_stop_condition(400),
+            StopCondition::FrameCount(600)
+        ));
+
+        // --max-duration 在 --stop-on-id 下依然生效，--duration 则被覆盖
+        cmd.stop_on_id = Some(CanId::standard(0x2A5).unwrap());
+        cmd.max_duration = None;
+        assert_eq!(RecordPlan::from_command(&cmd).deadline, None);
+        cmd.max_duration = Some(Duration::from_secs(5));
+        let plan = RecordPlan::from_command(&cmd);
+        assert_eq!(plan.deadline, Some(Duration::from_secs(5)));
+        assert!(matches!(
+            plan.segment_stop_condition(0),
+            StopCondition::OnCanId(_)
+        ));
+    }
+
+    #[test]
+    fn split_plan_rotates_on_threshold_and_streams() {
+        let mut cmd = command(0);
+        cmd.split_every = Some(SplitPolicy::Duration(Duration::from_secs(60)));
+        let plan = RecordPlan::from_command(&cmd);
+        assert!(!plan.should_rotate(Duration::from_secs(59), u64::MAX));
+        assert!(plan.should_rotate(Duration::from_secs(60), 0));
+        assert!(matches!(
+            plan.segment_stop_condition(0),
+            StopCondition::Manual
+        ));
+
+        let config = plan.segment_config(2, 0);
+        assert_eq!(config.output_path, PathBuf::from("capture.002.bin"));
+        assert_eq!(config.mode, RecordingMode::streaming());
+        assert!(config.metadata.notes.contains("segment=2"));
+
+        cmd.split_every = Some(SplitPolicy::Size(4096));
+        let plan = RecordPlan::from_command(&cmd);
+        assert!(!plan.should_rotate(Duration::from_secs(3600), 4095));
+        assert!(plan.should_rotate(Duration::ZERO, 4096));
+    }
+
     #[test]
     fn current_operator_name_falls_back_to_username() {
         let user_backup = std::env::var("USER").ok();
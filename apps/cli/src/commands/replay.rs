@@ -7,7 +7,7 @@ use crate::connection::{TargetArgs, client_builder, resolved_target_spec};
 use anyhow::Result;
 use clap::Args;
 use piper_control::TargetSpec;
-use piper_sdk::client::state::{MotionCapability, Standby};
+use piper_sdk::client::state::{MotionCapability, ReplayOptions, Standby};
 use piper_sdk::client::{MotionConnectedPiper, MotionConnectedState, Piper};
 use piper_sdk::driver::ConnectionTarget;
 use std::fmt;
@@ -33,6 +33,14 @@ pub struct ReplayCommand {
     #[arg(short, long, default_value_t = 1.0)]
     pub speed: f64,
 
+    /// 回放轮数（用于耐久测试；录制首尾姿态应一致）
+    #[arg(long = "loop", default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    pub loop_count: u32,
+
+    /// 只回放指定 CAN ID 的 TX 帧（逗号分隔，支持 0x 前缀，例如 0x155,0x156,0x157）
+    #[arg(long, value_delimiter = ',', value_parser = parse_replay_id_arg)]
+    pub id_filter: Vec<u32>,
+
     #[command(flatten)]
     pub target: TargetArgs,
 
@@ -41,6 +49,15 @@ pub struct ReplayCommand {
     pub yes: bool,
 }
 
+fn parse_replay_id_arg(value: &str) -> std::result::Result<u32, String> {
+    let value = value.trim();
+    if let Some(hex) = value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        u32::from_str_radix(hex, 16).map_err(|err| format!("invalid hex CAN ID: {err}"))
+    } else {
+        value.parse::<u32>().map_err(|err| format!("invalid CAN ID: {err}"))
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ReplayRunOutcome {
     Completed,
@@ -91,6 +108,13 @@ impl ReplayCommand {
         println!();
         println!("📁 文件: {}", self.input);
         println!("⚡ 速度: {:.2}x", self.speed);
+        if self.loop_count > 1 {
+            println!("🔁 轮数: {}", self.loop_count);
+        }
+        if !self.id_filter.is_empty() {
+            let ids: Vec<String> = self.id_filter.iter().map(|id| format!("0x{id:03X}")).collect();
+            println!("🔎 CAN ID 过滤: {}", ids.join(", "));
+        }
 
         if self.speed > RECOMMENDED_SPEED_FACTOR {
             println!(
@@ -145,7 +169,7 @@ impl ReplayCommand {
         let config = CliConfig::load()?;
         let target_spec = resolved_target_spec(&config, self.target.target.as_ref());
         let input = self.input.clone();
-        let speed = self.speed;
+        let options = self.replay_options();
         let target = target_spec.clone().into_connection_target();
         let running_for_task = running.clone();
 
@@ -155,7 +179,7 @@ impl ReplayCommand {
 
         let result = spawn_blocking(move || {
             // ✅ 在专用 OS 线程中运行，不阻塞 Tokio Worker
-            Self::replay_sync(input, speed, target, target_spec, running_for_task)
+            Self::replay_sync(input, options, target, target_spec, running_for_task)
         })
         .await;
 
@@ -187,6 +211,15 @@ impl ReplayCommand {
         Ok(())
     }
 
+    fn replay_options(&self) -> ReplayOptions {
+        let options = ReplayOptions::new(self.speed).loop_count(self.loop_count);
+        if self.id_filter.is_empty() {
+            options
+        } else {
+            options.id_filter(self.id_filter.iter().copied())
+        }
+    }
+
     /// 同步回放实现（在专用线程中运行）
     ///
     /// 此方法在 spawn_blocking 的 OS 线程中执行，包含：
@@ -196,7 +229,7 @@ impl ReplayCommand {
     /// 4. 安全停止（如被取消）
     fn replay_sync(
         input: String,
-        speed: f64,
+        options: ReplayOptions,
         target: ConnectionTarget,
         target_spec: TargetSpec,
         running: Arc<AtomicBool>,
@@ -215,10 +248,10 @@ impl ReplayCommand {
 
         match standby {
             MotionConnectedPiper::Strict(MotionConnectedState::Standby(standby)) => {
-                Self::replay_with_standby(standby, &input, speed, &running)
+                Self::replay_with_standby(standby, &input, &options, &running)
             },
             MotionConnectedPiper::Soft(MotionConnectedState::Standby(standby)) => {
-                Self::replay_with_standby(standby, &input, speed, &running)
+                Self::replay_with_standby(standby, &input, &options, &running)
             },
             MotionConnectedPiper::Strict(MotionConnectedState::Maintenance(_))
             | MotionConnectedPiper::Soft(MotionConnectedState::Maintenance(_)) => {
@@ -232,7 +265,7 @@ impl ReplayCommand {
     fn replay_with_standby<Capability>(
         standby: Piper<Standby, Capability>,
         input: &str,
-        speed: f64,
+        options: &ReplayOptions,
         running: &Arc<AtomicBool>,
     ) -> Result<ReplayRunOutcome>
     where
@@ -246,7 +279,7 @@ impl ReplayCommand {
         println!();
 
         replay
-            .replay_recording_with_cancel(input, speed, running)
+            .replay_recording_with_options(input, options, running)
             .map_err(anyhow::Error::from)?;
 
         if running.load(Ordering::Acquire) {
@@ -273,6 +306,8 @@ mod tests {
                     iface: "can0".to_string(),
                 }),
             },
+            loop_count: 1,
+            id_filter: Vec::new(),
             yes: true,
         };
 
@@ -287,6 +322,8 @@ mod tests {
             input: "recording.bin".to_string(),
             speed: 1.0,
             target: TargetArgs::default(),
+            loop_count: 1,
+            id_filter: Vec::new(),
             yes: false,
         };
 
@@ -304,6 +341,8 @@ mod tests {
                     serial: "ABC123".to_string(),
                 }),
             },
+            loop_count: 1,
+            id_filter: Vec::new(),
             yes: false,
         };
 
@@ -325,6 +364,8 @@ mod tests {
                     iface: "vcan0".to_string(),
                 }),
             },
+            loop_count: 1,
+            id_filter: Vec::new(),
             yes: true,
         };
 
@@ -341,6 +382,8 @@ mod tests {
             input: "test.bin".to_string(),
             speed: max_speed,
             target: TargetArgs::default(),
+            loop_count: 1,
+            id_filter: Vec::new(),
             yes: true,
         };
 
@@ -354,6 +397,8 @@ mod tests {
             input: "test.bin".to_string(),
             speed: min_speed,
             target: TargetArgs::default(),
+            loop_count: 1,
+            id_filter: Vec::new(),
             yes: false,
         };
 
@@ -367,12 +412,47 @@ mod tests {
             input: "test.bin".to_string(),
             speed: recommended_speed,
             target: TargetArgs::default(),
+            loop_count: 1,
+            id_filter: Vec::new(),
             yes: false,
         };
 
         assert_eq!(cmd.speed, recommended_speed);
     }
 
+    #[test]
+    fn test_replay_command_builds_options_from_flags() {
+        let cmd = ReplayCommand {
+            input: "teach.bin".to_string(),
+            speed: 0.5,
+            loop_count: 3,
+            id_filter: vec![0x155, 0x156],
+            target: TargetArgs::default(),
+            yes: true,
+        };
+
+        assert_eq!(
+            cmd.replay_options(),
+            ReplayOptions::new(0.5).loop_count(3).id_filter([0x155, 0x156])
+        );
+        assert_eq!(
+            ReplayCommand {
+                id_filter: Vec::new(),
+                ..cmd
+            }
+            .replay_options()
+            .id_filter,
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_replay_id_arg_accepts_hex_and_decimal() {
+        assert_eq!(parse_replay_id_arg("0x155").unwrap(), 0x155);
+        assert_eq!(parse_replay_id_arg("341").unwrap(), 341);
+        assert!(parse_replay_id_arg("0xZZ").is_err());
+    }
+
     #[test]
     fn test_replay_run_outcome_display() {
         assert_eq!(ReplayRunOutcome::Completed.to_string(), "completed");
@@ -9,7 +9,7 @@ use crate::script::ScriptExecutor;
 
 #[derive(Args, Debug, Clone)]
 pub struct RunCommand {
-    /// 脚本文件路径
+    /// 脚本文件路径（JSON，或扩展名为 .toml 的 TOML 脚本）
     #[arg(short, long)]
     pub script: String,
 
@@ -19,6 +19,10 @@ pub struct RunCommand {
     /// 失败时继续执行
     #[arg(long)]
     pub continue_on_error: bool,
+
+    /// 只校验并打印计划，不连接机器人
+    #[arg(long)]
+    pub dry_run: bool,
 }
 
 impl RunCommand {
@@ -33,17 +37,42 @@ impl RunCommand {
         }
     }
 
+    fn print_plan(script: &crate::script::Script) {
+        println!("🧪 Dry run（不会移动机械臂）:");
+        let mut planned = std::time::Duration::ZERO;
+        for (index, command) in script.commands.iter().enumerate() {
+            let line = script
+                .command_lines
+                .get(index)
+                .map(|line| format!("  [第 {line} 行]"))
+                .unwrap_or_default();
+            println!("  {:>3}. {}{}", index + 1, command.describe(), line);
+            planned += command.planned_duration().unwrap_or_default();
+        }
+        println!();
+        println!(
+            "  计划耗时（仅计入带时长的步骤）: {:.2} 秒",
+            planned.as_secs_f64()
+        );
+    }
+
     pub async fn execute(&self) -> Result<()> {
         println!("📜 加载脚本: {}", self.script);
         let script = ScriptExecutor::load_script(&self.script)?;
         let config = CliConfig::load()?;
         let profile = config.control_profile(self.target.target.as_ref());
+        script.validate(&profile.safety)?;
 
         println!("📋 脚本: {}", script.name);
         println!("    {}", script.description);
         println!("    {} 个命令", script.commands.len());
         println!();
 
+        if self.dry_run {
+            Self::print_plan(&script);
+            return Ok(());
+        }
+
         let executor_config = crate::script::ScriptConfig {
             profile,
             continue_on_error: self.continue_on_error,
@@ -88,6 +117,7 @@ mod tests {
                 }),
             },
             continue_on_error: true,
+            dry_run: false,
         };
 
         assert_eq!(cmd.script, "test.json");
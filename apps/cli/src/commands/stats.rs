@@ -0,0 +1,304 @@
+//! stats 命令
+//!
+//! 统计录制文件或实时总线的每 ID 帧数、帧率、帧间隔与断流
+//!
+//! ```bash
+//! piper-cli stats recording.bin
+//! piper-cli stats live --duration 5 --json
+//! ```
+
+use crate::commands::config::CliConfig;
+use crate::connection::{TargetArgs, driver_builder, resolved_target};
+use anyhow::{Context, Result};
+use clap::Args;
+use piper_sdk::driver::FrameCallback;
+use piper_sdk::driver::recording::RecordedFrameEvent;
+use piper_sdk::protocol::PiperFrame;
+use piper_tools::PiperRecording;
+use piper_tools::statistics::{BusTimingAnalyzer, BusTimingReport, DEFAULT_DROPOUT_FACTOR};
+use serde_json::Value;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{Receiver, RecvTimeoutError, SyncSender, sync_channel};
+use std::time::{Duration, Instant};
+use tokio::task::spawn_blocking;
+
+/// 实时采集的帧队列容量
+const LIVE_QUEUE_CAPACITY: usize = 65_536;
+
+/// 统计命令参数
+#[derive(Args, Debug)]
+pub struct StatsCommand {
+    /// 录制文件路径，或 `live` 表示实时采集总线
+    pub source: String,
+
+    /// 实时采集时长（秒）
+    #[arg(short, long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+    pub duration: u64,
+
+    /// 断流判定倍数：帧间隔超过该 ID 中位间隔的倍数时计为一次断流
+    #[arg(long, default_value_t = DEFAULT_DROPOUT_FACTOR)]
+    pub dropout_factor: f64,
+
+    /// 以 JSON 输出
+    #[arg(long)]
+    pub json: bool,
+
+    #[command(flatten)]
+    pub target: TargetArgs,
+}
+
+/// 统计结果及来源
+struct StatsOutcome {
+    report: BusTimingReport,
+    /// 实时采集时因队列满丢弃的帧数
+    queue_dropped: u64,
+}
+
+impl StatsCommand {
+    fn is_live(&self) -> bool {
+        self.source.eq_ignore_ascii_case("live")
+    }
+
+    fn analyzer(&self) -> BusTimingAnalyzer {
+        BusTimingAnalyzer::new().with_dropout_factor(self.dropout_factor)
+    }
+
+    pub async fn execute(&self) -> Result<()> {
+        let outcome = if self.is_live() {
+            let config = CliConfig::load()?;
+            let target = resolved_target(&config, self.target.target.as_ref());
+            let duration = Duration::from_secs(self.duration);
+            let analyzer = self.analyzer();
+            if !self.json {
+                println!("⏳ 采集总线 {} 秒（Ctrl-C 提前结束）...", self.duration);
+            }
+
+            let running = Arc::new(AtomicBool::new(true));
+            let running_for_signal = running.clone();
+            tokio::spawn(async move {
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    running_for_signal.store(false, Ordering::SeqCst);
+                }
+            });
+
+            spawn_blocking(move || {
+                let piper = driver_builder(&target).build()?;
+                collect_live(&piper, analyzer, duration, &running)
+            })
+            .await??
+        } else {
+            let recording = PiperRecording::load(&self.source)
+                .with_context(|| format!("无法读取录制文件 {}", self.source))?;
+            StatsOutcome {
+                report: analyze_recording(&recording, self.analyzer()),
+                queue_dropped: 0,
+            }
+        };
+
+        if self.json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&self.json_output(&outcome)?)?
+            );
+        } else {
+            self.print_table(&outcome);
+        }
+        Ok(())
+    }
+
+    fn json_output(&self, outcome: &StatsOutcome) -> Result<Value> {
+        let mut output = serde_json::to_value(&outcome.report)?;
+        if let Value::Object(fields) = &mut output {
+            fields.insert("source".to_string(), Value::from(self.source.clone()));
+            fields.insert(
+                "queue_dropped".to_string(),
+                Value::from(outcome.queue_dropped),
+            );
+        }
+        Ok(output)
+    }
+
+    fn print_table(&self, outcome: &StatsOutcome) {
+        let report = &outcome.report;
+        println!("📊 来源: {}", self.source);
+        println!(
+            "   时长: {:.3}s | 帧数: {} | 总帧率: {:.1} fps | 带宽: {:.1} KB/s",
+            report.duration_us as f64 / 1e6,
+            report.total_frames,
+            report.fps,
+            report.bandwidth_bps / 1024.0
+        );
+        if report.out_of_order_frames > 0 {
+            println!("   ⚠️  时间戳回退: {} 帧", report.out_of_order_frames);
+        }
+        if outcome.queue_dropped > 0 {
+            println!("   ⚠️  采集队列丢帧: {} 帧", outcome.queue_dropped);
+        }
+        println!();
+        println!(
+            "{:<12} {:>8} {:>9} {:>9} {:>9} {:>9} {:>6} {:>6}",
+            "CAN ID", "帧数", "帧率Hz", "最小ms", "平均ms", "最大ms", "断流", "缺帧"
+        );
+        for stats in &report.per_id {
+            println!(
+                "{:<12} {:>8} {:>9.1} {:>9.3} {:>9.3} {:>9.3} {:>6} {:>6}",
+                format_id(stats.id.raw_id(), stats.id.is_extended()),
+                stats.frame_count,
+                stats.rate_hz,
+                stats.min_gap_us as f64 / 1000.0,
+                stats.mean_gap_us / 1000.0,
+                stats.max_gap_us as f64 / 1000.0,
+                stats.dropouts,
+                stats.missing_frames
+            );
+        }
+        println!();
+        match report.total_dropouts() {
+            0 => println!(
+                "✅ 未检测到断流（阈值: 中位间隔 × {}）",
+                report.dropout_factor
+            ),
+            dropouts => println!(
+                "⚠️  检测到 {dropouts} 次断流（阈值: 中位间隔 × {}）",
+                report.dropout_factor
+            ),
+        }
+    }
+}
+
+fn format_id(id: u32, extended: bool) -> String {
+    if extended {
+        format!("0x{id:08X}")
+    } else {
+        format!("0x{id:03X}")
+    }
+}
+
+fn analyze_recording(
+    recording: &PiperRecording,
+    mut analyzer: BusTimingAnalyzer,
+) -> BusTimingReport {
+    // 统一到会话相对时间轴，避免不同后端的时间戳混用
+    for (frame, timestamp_us) in recording.frames.iter().zip(recording.normalized_timestamps()) {
+        analyzer.add_frame_at(&frame.frame, timestamp_us);
+    }
+    analyzer.report()
+}
+
+/// 实时采集钩子：RX 线程中只做 `try_send`，时间戳统一使用主机接收时间
+struct LiveStatsHook {
+    tx: SyncSender<(PiperFrame, u64)>,
+    start: Instant,
+    dropped: Arc<AtomicU64>,
+}
+
+impl FrameCallback for LiveStatsHook {
+    fn on_frame(&self, event: RecordedFrameEvent) {
+        let timestamp_us = self.start.elapsed().as_micros().min(u128::from(u64::MAX)) as u64;
+        if self.tx.try_send((event.frame, timestamp_us)).is_err() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+fn collect_live(
+    piper: &piper_sdk::driver::Piper,
+    mut analyzer: BusTimingAnalyzer,
+    duration: Duration,
+    running: &AtomicBool,
+) -> Result<StatsOutcome> {
+    let (tx, rx) = sync_channel(LIVE_QUEUE_CAPACITY);
+    let dropped = Arc::new(AtomicU64::new(0));
+    let hook = Arc::new(LiveStatsHook {
+        tx,
+        start: Instant::now(),
+        dropped: dropped.clone(),
+    }) as Arc<dyn FrameCallback>;
+
+    let hooks = piper.hooks();
+    let handle = hooks
+        .write()
+        .map_err(|_| anyhow::anyhow!("hook manager lock poisoned"))?
+        .add_callback(hook);
+
+    let deadline = Instant::now() + duration;
+    while running.load(Ordering::SeqCst) && Instant::now() < deadline {
+        drain_into(&rx, &mut analyzer, Duration::from_millis(100));
+    }
+
+    if let Ok(mut hooks) = hooks.write() {
+        hooks.remove_callback(handle);
+    }
+    while let Ok((frame, timestamp_us)) = rx.try_recv() {
+        analyzer.add_frame_at(&frame, timestamp_us);
+    }
+
+    Ok(StatsOutcome {
+        report: analyzer.report(),
+        queue_dropped: dropped.load(Ordering::Relaxed),
+    })
+}
+
+fn drain_into(rx: &Receiver<(PiperFrame, u64)>, analyzer: &mut BusTimingAnalyzer, wait: Duration) {
+    match rx.recv_timeout(wait) {
+        Ok((frame, timestamp_us)) => analyzer.add_frame_at(&frame, timestamp_us),
+        Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => return,
+    }
+    while let Ok((frame, timestamp_us)) = rx.try_recv() {
+        analyzer.add_frame_at(&frame, timestamp_us);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use piper_tools::{RecordedFrameDirection, RecordingMetadata, TimestampedFrame};
+
+    fn command(source: &str) -> StatsCommand {
+        StatsCommand {
+            source: source.to_string(),
+            duration: 10,
+            dropout_factor: DEFAULT_DROPOUT_FACTOR,
+            json: true,
+            target: TargetArgs::default(),
+        }
+    }
+
+    #[test]
+    fn recording_stats_include_per_id_rows_and_source() {
+        let mut recording =
+            PiperRecording::new(RecordingMetadata::new("can0".to_string(), 1_000_000));
+        for (index, timestamp_us) in [0u64, 5_000, 10_000, 40_000].into_iter().enumerate() {
+            let frame = PiperFrame::new_standard(0x2A1, [index as u8])
+                .unwrap()
+                .with_timestamp_us(timestamp_us);
+            recording.add_frame(TimestampedFrame::new(
+                frame,
+                RecordedFrameDirection::Rx,
+                None,
+            ));
+        }
+
+        let cmd = command("capture.bin");
+        assert!(!cmd.is_live());
+        let outcome = StatsOutcome {
+            report: analyze_recording(&recording, cmd.analyzer()),
+            queue_dropped: 0,
+        };
+        assert_eq!(outcome.report.per_id.len(), 1);
+        assert_eq!(outcome.report.per_id[0].dropouts, 1);
+
+        let json = cmd.json_output(&outcome).unwrap();
+        assert_eq!(json["source"], "capture.bin");
+        assert_eq!(json["total_frames"], 4);
+        assert_eq!(json["per_id"][0]["id"], "standard:0x2A1");
+    }
+
+    #[test]
+    fn live_source_is_case_insensitive() {
+        assert!(command("LIVE").is_live());
+        assert_eq!(format_id(0x2A1, false), "0x2A1");
+        assert_eq!(format_id(0x2A1, true), "0x000002A1");
+    }
+}
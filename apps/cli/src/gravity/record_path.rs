@@ -392,6 +392,7 @@ mod tests {
             raw_feedback_timing: None,
             joint_pos: [0.1, 0.2, 0.3, 0.4, 0.5, 0.6],
             frame_valid_mask: 0b111,
+            consistency: piper_sdk::driver::StateConsistency::Consistent,
         };
 
         let row = build_path_sample_row(9, position, None).unwrap();
@@ -413,6 +414,7 @@ mod tests {
             raw_feedback_timing: None,
             joint_pos: [0.1, 0.2, 0.0, 0.0, 0.0, 0.0],
             frame_valid_mask: 0b001,
+            consistency: piper_sdk::driver::StateConsistency::Consistent,
         };
 
         let row = build_path_sample_row(9, position, None);
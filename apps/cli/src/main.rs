@@ -41,9 +41,10 @@ mod validation;
 
 use commands::config::CliConfig;
 use commands::{
-    CollisionProtectionCommand, ConfigCommand, GravityAction, GravityCommand, HomeCommand,
-    MoveCommand, ParkCommand, PositionCommand, RecordCommand, ReplayCommand, RunCommand,
-    SetZeroCommand, StopCommand, TeleopAction, TeleopCommand,
+    BridgeStatusCommand, CollisionProtectionCommand, ConfigCommand, DecodeCommand, GravityAction,
+    GravityCommand, HomeCommand, MoveCommand, ParkCommand, PositionCommand, RecordCommand,
+    ReplayCommand, RunCommand, SetZeroCommand, StatsCommand, StopCommand, TeleopAction,
+    TeleopCommand,
 };
 use connection::TargetArgs;
 use modes::oneshot::OneShotMode;
@@ -116,6 +117,10 @@ enum Commands {
         #[arg(short, long, default_value_t = 10)]
         frequency: u32,
 
+        /// 以终端仪表盘显示（原地刷新，按 e 急停）
+        #[arg(long)]
+        tui: bool,
+
         #[command(flatten)]
         target: TargetArgs,
     },
@@ -138,6 +143,25 @@ enum Commands {
         args: ReplayCommand,
     },
 
+    /// 解码反馈帧（离线，不连接机器人）
+    Decode {
+        #[command(flatten)]
+        args: DecodeCommand,
+    },
+
+    /// 总线统计（每 ID 帧率、帧间隔与断流），来源为录制文件或 `live`
+    Stats {
+        #[command(flatten)]
+        args: StatsCommand,
+    },
+
+    /// 查询 bridge host 状态与已连接会话
+    #[command(alias = "daemon-status")]
+    BridgeStatus {
+        #[command(flatten)]
+        args: BridgeStatusCommand,
+    },
+
     /// 双臂遥操作
     Teleop {
         #[command(subcommand)]
@@ -209,9 +233,17 @@ async fn main() -> Result<()> {
             args.execute(&config).await
         },
 
-        Commands::Monitor { frequency, target } => {
+        Commands::Monitor {
+            frequency,
+            tui,
+            target,
+        } => {
             let mut mode = OneShotMode::new().await?;
-            mode.monitor(frequency, target.target.as_ref()).await?;
+            if tui {
+                mode.monitor_tui(frequency, target.target.as_ref()).await?;
+            } else {
+                mode.monitor(frequency, target.target.as_ref()).await?;
+            }
             Ok(())
         },
 
@@ -229,6 +261,12 @@ async fn main() -> Result<()> {
             Ok(())
         },
 
+        Commands::Decode { args } => args.execute(),
+
+        Commands::Stats { args } => args.execute().await,
+
+        Commands::BridgeStatus { args } => args.execute().await,
+
         Commands::Teleop { action } => TeleopCommand { action: *action }.execute().await,
 
         Commands::Gravity { action } => GravityCommand { action }.execute().await,
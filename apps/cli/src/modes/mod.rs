@@ -4,5 +4,6 @@
 //! - One-shot 模式：每次命令独立连接
 //! - REPL 模式：交互式 Shell
 
+pub mod monitor_tui;
 pub mod oneshot;
 pub mod repl;
@@ -0,0 +1,457 @@
+//! TUI 监控面板（`monitor --tui`）
+//!
+//! 原地刷新的终端仪表盘，适合在 SSH 会话中查看：
+//! - 每个关节的位置 / 速度 / 力矩条形图
+//! - 驱动器电压、温度与状态位
+//! - 控制状态、故障锁存、链路健康与各反馈组 FPS
+//!
+//! 按键：`e` 急停，`q` / `Esc` / `Ctrl+C` 退出。
+
+use anyhow::Result;
+use piper_sdk::client::types::Joint;
+use piper_sdk::client::{DriverDiagnostics, JointState, LinkHealth, Observer};
+use piper_sdk::driver::{FpsResult, Piper as DriverPiper, RobotControlState};
+use piper_sdk::protocol::control::EmergencyStopCommand;
+use piper_sdk::protocol::{ControlMode, RobotStatus};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::symbols;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, LineGauge, Paragraph, Row, Table};
+use ratatui::{DefaultTerminal, Frame};
+use std::f64::consts::PI;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 位置条满量程（rad，仅用于条形图缩放）
+const POSITION_FULL_SCALE_RAD: f64 = PI;
+/// 速度条满量程（rad/s，仅用于条形图缩放）
+const VELOCITY_FULL_SCALE_RAD_S: f64 = PI;
+/// 力矩条满量程（N·m，仅用于条形图缩放）
+const EFFORT_FULL_SCALE_NM: f64 = 10.0;
+/// 急停命令等待 shutdown lane 确认的超时
+const EMERGENCY_STOP_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// 一次刷新读取到的状态
+struct DashboardSnapshot {
+    joints: std::result::Result<JointState, String>,
+    diagnostics: std::result::Result<[DriverDiagnostics; 6], String>,
+    control: RobotControlState,
+    robot_fault: Option<RobotStatus>,
+    link_health: LinkHealth,
+    fps: FpsResult,
+}
+
+impl DashboardSnapshot {
+    fn sample(driver: &DriverPiper, observer: &Observer) -> Self {
+        Self {
+            joints: observer.joint_state().map_err(|error| error.to_string()),
+            diagnostics: observer.driver_diagnostics().map_err(|error| error.to_string()),
+            control: driver.get_robot_control(),
+            robot_fault: driver.robot_fault(),
+            link_health: driver.link_health(),
+            fps: driver.get_fps(),
+        }
+    }
+}
+
+/// 运行 TUI 监控面板，直到用户退出
+///
+/// 阻塞调用；在 async 上下文中请放到 `spawn_blocking` 中执行。
+pub fn run(driver: Arc<DriverPiper>, frequency: u32) -> Result<()> {
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &driver, frequency);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(
+    terminal: &mut DefaultTerminal,
+    driver: &Arc<DriverPiper>,
+    frequency: u32,
+) -> Result<()> {
+    let observer: Observer = Observer::new(Arc::clone(driver));
+    let period = Duration::from_millis(1000 / u64::from(frequency.max(1)));
+    let mut message: Option<(String, bool)> = None;
+
+    loop {
+        let snapshot = DashboardSnapshot::sample(driver, &observer);
+        terminal.draw(|frame| render(frame, &snapshot, message.as_ref()))?;
+
+        let deadline = Instant::now() + period;
+        while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+            if !event::poll(remaining)? {
+                break;
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(());
+                },
+                KeyCode::Char('e') | KeyCode::Char('E') => {
+                    message = Some(match emergency_stop(driver) {
+                        Ok(()) => ("急停已发送".to_string(), true),
+                        Err(error) => (format!("急停发送失败: {error}"), false),
+                    });
+                    break;
+                },
+                _ => {},
+            }
+        }
+    }
+}
+
+fn emergency_stop(driver: &DriverPiper) -> Result<()> {
+    let frame = EmergencyStopCommand::emergency_stop().to_frame();
+    driver
+        .enqueue_shutdown(frame, Instant::now() + EMERGENCY_STOP_TIMEOUT)?
+        .wait()?;
+    Ok(())
+}
+
+fn render(frame: &mut Frame, snapshot: &DashboardSnapshot, message: Option<&(String, bool)>) {
+    let [header, joints, drivers, status, footer] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Length(8),
+        Constraint::Length(9),
+        Constraint::Min(5),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    render_header(frame, header, snapshot);
+    render_joints(frame, joints, snapshot);
+    render_drivers(frame, drivers, snapshot);
+    render_status(frame, status, snapshot);
+
+    let footer_line = match message {
+        Some((text, ok)) => Line::from(vec![
+            Span::raw(" [e] 急停  [q] 退出   "),
+            Span::styled(
+                text.as_str(),
+                Style::new().fg(if *ok { Color::Yellow } else { Color::Red }),
+            ),
+        ]),
+        None => Line::from(" [e] 急停  [q] 退出"),
+    };
+    frame.render_widget(Paragraph::new(footer_line), footer);
+}
+
+fn render_header(frame: &mut Frame, area: Rect, snapshot: &DashboardSnapshot) {
+    let control = &snapshot.control;
+    let line = Line::from(vec![
+        Span::raw(format!(
+            "模式 {:?}  状态 {:?}  ",
+            ControlMode::from(control.control_mode),
+            RobotStatus::from(control.robot_status),
+        )),
+        if control.is_enabled {
+            Span::styled("已使能", Style::new().fg(Color::Green))
+        } else {
+            Span::styled("未使能", Style::new().fg(Color::DarkGray))
+        },
+        Span::raw("  链路 "),
+        link_health_span(snapshot.link_health),
+    ]);
+    frame.render_widget(
+        Paragraph::new(line).block(Block::bordered().title(" Piper Monitor ")),
+        area,
+    );
+}
+
+fn render_joints(frame: &mut Frame, area: Rect, snapshot: &DashboardSnapshot) {
+    let title = match &snapshot.joints {
+        Ok(state) if state.stale => format!(" 关节（反馈过期 {:?}）", state.feedback_age),
+        Ok(_) => " 关节 ".to_string(),
+        Err(error) => format!(" 关节（{error}）"),
+    };
+    let block = Block::bordered().title(title);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let Ok(state) = &snapshot.joints else {
+        return;
+    };
+    let rows = Layout::vertical([Constraint::Length(1); 6]).split(inner);
+    for (index, row) in rows.iter().enumerate() {
+        let (position, velocity, effort) = state.joint(Joint::ALL[index]);
+        let [label, position_area, velocity_area, effort_area] = Layout::horizontal([
+            Constraint::Length(3),
+            Constraint::Ratio(1, 3),
+            Constraint::Ratio(1, 3),
+            Constraint::Ratio(1, 3),
+        ])
+        .areas(*row);
+
+        frame.render_widget(Paragraph::new(format!("J{}", index + 1)), label);
+        frame.render_widget(
+            gauge(
+                format!("{:>7.1}°", position.0.to_degrees()),
+                centered_ratio(position.0, POSITION_FULL_SCALE_RAD),
+                Color::Cyan,
+            ),
+            position_area,
+        );
+        frame.render_widget(
+            gauge(
+                format!("{:>6.2} rad/s", velocity.0),
+                centered_ratio(velocity.0, VELOCITY_FULL_SCALE_RAD_S),
+                Color::Green,
+            ),
+            velocity_area,
+        );
+        frame.render_widget(
+            gauge(
+                format!("{:>6.2} N·m", effort.0),
+                centered_ratio(effort.0, EFFORT_FULL_SCALE_NM),
+                Color::Magenta,
+            ),
+            effort_area,
+        );
+    }
+}
+
+fn gauge(label: String, ratio: f64, color: Color) -> LineGauge<'static> {
+    LineGauge::default()
+        .label(label)
+        .ratio(ratio)
+        .line_set(symbols::line::THICK)
+        .filled_style(Style::new().fg(color))
+        .unfilled_style(Style::new().fg(Color::DarkGray))
+}
+
+fn render_drivers(frame: &mut Frame, area: Rect, snapshot: &DashboardSnapshot) {
+    let header = Row::new(["关节", "电压 V", "驱动 ℃", "电机 ℃", "母线 A", "状态"])
+        .style(Style::new().add_modifier(Modifier::BOLD));
+    let rows: Vec<Row> = match &snapshot.diagnostics {
+        Ok(diagnostics) => diagnostics
+            .iter()
+            .map(|diag| {
+                let style = if diag.has_fault() {
+                    Style::new().fg(Color::Red)
+                } else if diag.is_overheating() || diag.is_voltage_low() {
+                    Style::new().fg(Color::Yellow)
+                } else {
+                    Style::new()
+                };
+                Row::new([
+                    diag.joint.to_string(),
+                    format!("{:.1}", diag.voltage),
+                    format!("{:.0}", diag.driver_temp_c),
+                    format!("{:.0}", diag.motor_temp_c),
+                    format!("{:.2}", diag.bus_current),
+                    driver_status_text(diag),
+                ])
+                .style(style)
+            })
+            .collect(),
+        Err(error) => vec![Row::new([error.clone()])],
+    };
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Min(10),
+        ],
+    )
+    .header(header)
+    .block(Block::bordered().title(" 驱动器 "));
+    frame.render_widget(table, area);
+}
+
+fn driver_status_text(diag: &DriverDiagnostics) -> String {
+    let status = diag.status;
+    let flags = [
+        (status.voltage_low(), "欠压"),
+        (status.motor_over_temp(), "电机过温"),
+        (status.driver_over_current(), "过流"),
+        (status.driver_over_temp(), "驱动过温"),
+        (status.collision_protection(), "碰撞保护"),
+        (status.driver_error(), "驱动错误"),
+        (status.stall_protection(), "堵转保护"),
+    ];
+    let active: Vec<&str> = flags.iter().filter(|(set, _)| *set).map(|(_, name)| *name).collect();
+    match (active.is_empty(), status.enabled()) {
+        (true, true) => "使能".to_string(),
+        (true, false) => "失能".to_string(),
+        (false, _) => active.join(" "),
+    }
+}
+
+fn render_status(frame: &mut Frame, area: Rect, snapshot: &DashboardSnapshot) {
+    let faults = fault_summary(
+        &snapshot.control,
+        snapshot.robot_fault,
+        snapshot.diagnostics.as_ref().ok(),
+        snapshot.link_health,
+    );
+    let [fault_area, fps_area] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(area);
+
+    let fault_lines: Vec<Line> = if faults.is_empty() {
+        vec![Line::styled("无故障", Style::new().fg(Color::Green))]
+    } else {
+        faults
+            .into_iter()
+            .map(|fault| Line::styled(fault, Style::new().fg(Color::Red)))
+            .collect()
+    };
+    frame.render_widget(
+        Paragraph::new(fault_lines).block(Block::bordered().title(" 故障 ")),
+        fault_area,
+    );
+
+    let fps = &snapshot.fps;
+    let fps_lines = vec![
+        Line::from(format!("关节位置   {:>7.1}", fps.joint_position)),
+        Line::from(format!("关节动态   {:>7.1}", fps.joint_dynamic)),
+        Line::from(format!("控制状态   {:>7.1}", fps.robot_control)),
+        Line::from(format!("末端位姿   {:>7.1}", fps.end_pose)),
+        Line::from(format!("夹爪       {:>7.1}", fps.gripper)),
+        Line::from(format!("驱动低速   {:>7.1}", fps.joint_driver_low_speed)),
+    ];
+    frame.render_widget(
+        Paragraph::new(fps_lines).block(Block::bordered().title(" 总线 FPS ")),
+        fps_area,
+    );
+}
+
+fn link_health_span(health: LinkHealth) -> Span<'static> {
+    match health {
+        LinkHealth::Healthy => Span::styled("正常", Style::new().fg(Color::Green)),
+        LinkHealth::Degraded { missed } => Span::styled(
+            format!("降级（丢失 {missed} 帧）"),
+            Style::new().fg(Color::Yellow),
+        ),
+        LinkHealth::Lost => Span::styled("断开", Style::new().fg(Color::Red)),
+    }
+}
+
+/// 把有符号值映射到条形图比例（0 位于中点）
+fn centered_ratio(value: f64, full_scale: f64) -> f64 {
+    if !value.is_finite() || full_scale <= 0.0 {
+        return 0.5;
+    }
+    ((value / full_scale).clamp(-1.0, 1.0) + 1.0) / 2.0
+}
+
+/// 1-based 关节编号列表（Bit 0-5 对应 J1-J6）
+fn joints_in_mask(mask: u8) -> String {
+    (0..6)
+        .filter(|bit| mask & (1 << bit) != 0)
+        .map(|bit| format!("J{}", bit + 1))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 汇总当前所有故障（为空表示无故障）
+fn fault_summary(
+    control: &RobotControlState,
+    robot_fault: Option<RobotStatus>,
+    diagnostics: Option<&[DriverDiagnostics; 6]>,
+    link_health: LinkHealth,
+) -> Vec<String> {
+    let mut faults = Vec::new();
+
+    if let Some(status) = robot_fault {
+        faults.push(format!("故障锁存: {status:?}（重新使能后清除）"));
+    }
+    let status = RobotStatus::from(control.robot_status);
+    if status.is_fault() && robot_fault != Some(status) {
+        faults.push(format!("机械臂状态: {status:?}"));
+    }
+    if control.fault_angle_limit_mask != 0 {
+        faults.push(format!(
+            "角度超限: {}",
+            joints_in_mask(control.fault_angle_limit_mask)
+        ));
+    }
+    if control.fault_comm_error_mask != 0 {
+        faults.push(format!(
+            "通信异常: {}",
+            joints_in_mask(control.fault_comm_error_mask)
+        ));
+    }
+    for diag in diagnostics.into_iter().flatten().filter(|diag| diag.has_fault()) {
+        faults.push(format!(
+            "{} 驱动器: {}",
+            diag.joint,
+            driver_status_text(diag)
+        ));
+    }
+    if link_health == LinkHealth::Lost {
+        faults.push("链路断开：超时未收到反馈".to_string());
+    }
+
+    faults
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use piper_sdk::protocol::feedback::DriverStatus;
+
+    fn diagnostics(status: u8) -> [DriverDiagnostics; 6] {
+        std::array::from_fn(|index| DriverDiagnostics {
+            joint: Joint::ALL[index],
+            voltage: 24.0,
+            driver_temp_c: 40.0,
+            motor_temp_c: 40.0,
+            bus_current: 0.5,
+            status: DriverStatus::from(if index == 2 { status } else { 0b0100_0000 }),
+            host_rx_mono_us: 1_000,
+        })
+    }
+
+    #[test]
+    fn centered_ratio_puts_zero_in_the_middle_and_clamps() {
+        assert_eq!(centered_ratio(0.0, PI), 0.5);
+        assert_eq!(centered_ratio(PI, PI), 1.0);
+        assert_eq!(centered_ratio(-10.0, PI), 0.0);
+        assert_eq!(centered_ratio(f64::NAN, PI), 0.5);
+    }
+
+    #[test]
+    fn fault_summary_is_empty_for_healthy_robot() {
+        let faults = fault_summary(
+            &RobotControlState::default(),
+            None,
+            Some(&diagnostics(0b0100_0000)),
+            LinkHealth::Healthy,
+        );
+        assert!(faults.is_empty(), "{faults:?}");
+    }
+
+    #[test]
+    fn fault_summary_lists_latched_fault_masks_and_driver_errors() {
+        let control = RobotControlState {
+            robot_status: 0x07,
+            fault_angle_limit_mask: 0b0000_0101,
+            ..RobotControlState::default()
+        };
+
+        let faults = fault_summary(
+            &control,
+            Some(RobotStatus::from(0x07)),
+            Some(&diagnostics(0b0010_0000)),
+            LinkHealth::Lost,
+        );
+
+        assert_eq!(faults.len(), 4, "{faults:?}");
+        assert!(faults[0].contains("Collision"));
+        assert_eq!(faults[1], "角度超限: J1 J3");
+        assert!(faults[2].contains("驱动错误"));
+        assert!(faults[3].contains("链路断开"));
+    }
+}
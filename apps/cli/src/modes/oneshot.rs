@@ -13,6 +13,7 @@ use tokio::signal;
 
 use crate::commands::config::CliConfig;
 use crate::connection::{driver_builder, resolved_target};
+use crate::modes::monitor_tui;
 
 const MONITOR_FEEDBACK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
 
@@ -173,6 +174,24 @@ impl OneShotMode {
         println!("✅ 已停止监控");
         Ok(())
     }
+
+    /// 以 TUI 仪表盘方式监控（阻塞直到用户按 q 退出）
+    pub async fn monitor_tui(
+        &mut self,
+        frequency: u32,
+        override_target: Option<&TargetSpec>,
+    ) -> Result<()> {
+        println!("⏳ 连接到机器人...");
+        let target = resolved_target(&self.config, override_target);
+        let piper = Arc::new(driver_builder(&target).build()?);
+
+        println!("⏳ 等待首帧反馈...");
+        prepare_monitor_startup(piper.as_ref(), MONITOR_FEEDBACK_TIMEOUT)?;
+
+        tokio::task::spawn_blocking(move || monitor_tui::run(piper, frequency)).await??;
+        println!("✅ 已停止监控");
+        Ok(())
+    }
 }
 
 fn print_end_pose(end_pose: &Observation<EndPose, PartialEndPose>) {
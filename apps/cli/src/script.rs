@@ -1,24 +1,79 @@
 //! 脚本系统
-
-use anyhow::{Context, Result};
+//!
+//! 脚本可以是 JSON（`commands` 数组）或 TOML（`[[steps]]` 表数组，按扩展名识别）。
+//! TOML 脚本在执行前会逐步校验，错误信息带行号：
+//!
+//! ```toml
+//! name = "pick cycle"
+//! description = "repeatable test sequence"
+//!
+//! [[steps]]
+//! type = "Waypoint"
+//! joints = [0.0, 0.4, -0.6, 0.0, 0.3, 0.0]
+//! duration_ms = 3000
+//! wait_for_arrival = true
+//!
+//! [[steps]]
+//! type = "Gripper"
+//! action = "close"
+//!
+//! [[steps]]
+//! type = "Pause"
+//! duration_ms = 500
+//! ```
+
+use anyhow::{Context, Result, bail};
 use piper_client::state::{DisableConfig, MotionCapability, Piper, Standby};
-use piper_client::{MotionConnectedPiper, MotionConnectedState};
+use piper_client::types::{Millimeter, NewtonMeter};
+use piper_client::{GripperCommander, MotionConnectedPiper, MotionConnectedState};
 use piper_control::{
-    ControlProfile, DEFAULT_PARK_SPEED_PERCENT, home_zero_blocking, move_to_joint_target_blocking,
+    ControlProfile, DEFAULT_PARK_SPEED_PERCENT, MotionExecutionOutcome,
+    active_move_to_joint_target_with_cancel, home_zero_blocking, move_to_joint_target_blocking,
     park_blocking, prepare_move, set_joint_zero_blocking,
 };
 use piper_sdk::driver::ConnectionTarget;
+use piper_tools::SafetyConfig;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
 
 use crate::connection::{client_builder, wait_for_initial_monitor_snapshot};
 use crate::parsing::normalize_joint_indices;
 
+/// 夹爪命令执行后、失能机械臂前的等待时间
+const GRIPPER_SETTLE: Duration = Duration::from_millis(800);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Script {
     pub name: String,
     pub description: String,
     pub commands: Vec<ScriptCommand>,
+    /// 每条命令在源文件中的行号（仅 TOML 脚本可用）
+    #[serde(skip)]
+    pub command_lines: Vec<usize>,
+}
+
+/// TOML 脚本的原始结构（保留每一步的位置信息）
+#[derive(Deserialize)]
+struct TomlScript {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(alias = "commands")]
+    steps: Vec<toml::Spanned<ScriptCommand>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GripperAction {
+    /// 完全张开
+    Open,
+    /// 完全闭合
+    Close,
+    /// 移动到 `position_mm`
+    Move,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +84,29 @@ pub enum ScriptCommand {
         #[serde(default)]
         force: bool,
     },
+    /// 带时间预算的关节航点（弧度）
+    ///
+    /// - `wait_for_arrival = true`：必须在 `duration_ms` 内到位（未设置时使用配置的等待超时）
+    /// - `wait_for_arrival = false`：运动 `duration_ms` 后直接进入下一步，不要求到位
+    Waypoint {
+        joints: Vec<f64>,
+        #[serde(default)]
+        duration_ms: Option<u64>,
+        #[serde(default = "default_wait_for_arrival")]
+        wait_for_arrival: bool,
+        #[serde(default)]
+        speed_percent: Option<u8>,
+        #[serde(default)]
+        force: bool,
+    },
+    Gripper {
+        action: GripperAction,
+        #[serde(default)]
+        position_mm: Option<f64>,
+        #[serde(default)]
+        effort_nm: Option<f64>,
+    },
+    #[serde(alias = "Pause")]
     Wait {
         duration_ms: u64,
     },
@@ -44,6 +122,243 @@ pub enum ScriptCommand {
     Stop,
 }
 
+fn default_wait_for_arrival() -> bool {
+    true
+}
+
+impl ScriptCommand {
+    /// 单行描述（用于 dry run 计划）
+    pub fn describe(&self) -> String {
+        match self {
+            ScriptCommand::Move { joints, force } => {
+                format!(
+                    "移动到 {}{}",
+                    format_joints(joints),
+                    if *force { "（force）" } else { "" }
+                )
+            },
+            ScriptCommand::Waypoint {
+                joints,
+                duration_ms,
+                wait_for_arrival,
+                speed_percent,
+                ..
+            } => {
+                let mut text = format!("航点 {}", format_joints(joints));
+                if let Some(duration_ms) = duration_ms {
+                    text.push_str(&format!("，预算 {duration_ms} ms"));
+                }
+                if let Some(speed_percent) = speed_percent {
+                    text.push_str(&format!("，速度 {speed_percent}%"));
+                }
+                text.push_str(if *wait_for_arrival {
+                    "，等待到位"
+                } else {
+                    "，不等待到位"
+                });
+                text
+            },
+            ScriptCommand::Gripper {
+                action,
+                position_mm,
+                effort_nm,
+            } => {
+                let mut text = match action {
+                    GripperAction::Open => "夹爪张开".to_string(),
+                    GripperAction::Close => "夹爪闭合".to_string(),
+                    GripperAction::Move => {
+                        format!("夹爪移动到 {:.1} mm", position_mm.unwrap_or_default())
+                    },
+                };
+                if let Some(effort_nm) = effort_nm {
+                    text.push_str(&format!("，扭矩 {effort_nm:.2} N·m"));
+                }
+                text
+            },
+            ScriptCommand::Wait { duration_ms } => format!("等待 {duration_ms} ms"),
+            ScriptCommand::Position => "查询位置".to_string(),
+            ScriptCommand::Home => "回零".to_string(),
+            ScriptCommand::Park => "停靠".to_string(),
+            ScriptCommand::SetZero { joints, .. } => match joints {
+                Some(joints) => format!("设置零点 {joints:?}"),
+                None => "设置零点（全部关节）".to_string(),
+            },
+            ScriptCommand::Stop => "急停".to_string(),
+        }
+    }
+
+    /// 该步骤的计划耗时（无法预估时为 `None`）
+    pub fn planned_duration(&self) -> Option<Duration> {
+        match self {
+            ScriptCommand::Waypoint { duration_ms, .. } => duration_ms.map(Duration::from_millis),
+            ScriptCommand::Wait { duration_ms } => Some(Duration::from_millis(*duration_ms)),
+            ScriptCommand::Gripper { .. } => Some(GRIPPER_SETTLE),
+            _ => None,
+        }
+    }
+
+    fn validate(&self, safety: &SafetyConfig) -> std::result::Result<(), String> {
+        match self {
+            ScriptCommand::Move { joints, .. } => validate_joints(joints, safety),
+            ScriptCommand::Waypoint {
+                joints,
+                duration_ms,
+                wait_for_arrival,
+                speed_percent,
+                ..
+            } => {
+                validate_joints(joints, safety)?;
+                if *duration_ms == Some(0) {
+                    return Err("duration_ms 必须大于 0".to_string());
+                }
+                if !wait_for_arrival && duration_ms.is_none() {
+                    return Err("wait_for_arrival = false 时必须设置 duration_ms".to_string());
+                }
+                if let Some(speed_percent) = speed_percent
+                    && !(1..=100).contains(speed_percent)
+                {
+                    return Err(format!(
+                        "speed_percent 必须在 1..=100 范围内，得到 {speed_percent}"
+                    ));
+                }
+                Ok(())
+            },
+            ScriptCommand::Gripper {
+                action,
+                position_mm,
+                effort_nm,
+            } => {
+                let max_travel = GripperCommander::MAX_TRAVEL.0;
+                let max_effort = GripperCommander::MAX_EFFORT.0;
+                match (action, position_mm) {
+                    (GripperAction::Move, None) => {
+                        return Err("action = \"move\" 需要设置 position_mm".to_string());
+                    },
+                    (GripperAction::Move, Some(position)) => {
+                        if !(0.0..=max_travel).contains(position) {
+                            return Err(format!(
+                                "position_mm 必须在 0..={max_travel} 范围内，得到 {position}"
+                            ));
+                        }
+                    },
+                    (_, Some(_)) => {
+                        return Err("position_mm 只能用于 action = \"move\"".to_string());
+                    },
+                    (_, None) => {},
+                }
+                if let Some(effort) = effort_nm
+                    && !(0.0..=max_effort).contains(effort)
+                {
+                    return Err(format!(
+                        "effort_nm 必须在 0..={max_effort} 范围内，得到 {effort}"
+                    ));
+                }
+                Ok(())
+            },
+            ScriptCommand::SetZero { joints, force } => {
+                if !force {
+                    return Err("set-zero 必须显式设置 force = true".to_string());
+                }
+                normalize_set_zero_joints(joints.as_deref())
+                    .map(|_| ())
+                    .map_err(|error| error.to_string())
+            },
+            ScriptCommand::Wait { .. }
+            | ScriptCommand::Position
+            | ScriptCommand::Home
+            | ScriptCommand::Park
+            | ScriptCommand::Stop => Ok(()),
+        }
+    }
+}
+
+fn validate_joints(joints: &[f64], safety: &SafetyConfig) -> std::result::Result<(), String> {
+    if joints.is_empty() || joints.len() > 6 {
+        return Err(format!("joints 需要 1 到 6 个值，得到 {}", joints.len()));
+    }
+    for (index, position) in joints.iter().copied().enumerate() {
+        if !position.is_finite() {
+            return Err(format!("J{} 目标不是有限数值", index + 1));
+        }
+        if !safety.check_joint_position(index, position) {
+            return Err(format!(
+                "J{} 目标 {:.3} rad ({:.1}°) 超出配置的关节限位",
+                index + 1,
+                position,
+                position.to_degrees()
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn format_joints(joints: &[f64]) -> String {
+    let degrees: Vec<String> =
+        joints.iter().map(|joint| format!("{:.1}°", joint.to_degrees())).collect();
+    format!("[{}]", degrees.join(", "))
+}
+
+/// 脚本校验问题
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptIssue {
+    /// 命令序号（从 0 开始）
+    pub index: usize,
+    /// 源文件行号（仅 TOML 脚本可用）
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for ScriptIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(
+                f,
+                "第 {} 行（命令 {}）: {}",
+                line,
+                self.index + 1,
+                self.message
+            ),
+            None => write!(f, "命令 {}: {}", self.index + 1, self.message),
+        }
+    }
+}
+
+impl Script {
+    /// 执行前校验所有命令，返回全部问题
+    pub fn issues(&self, safety: &SafetyConfig) -> Vec<ScriptIssue> {
+        self.commands
+            .iter()
+            .enumerate()
+            .filter_map(|(index, command)| {
+                command.validate(safety).err().map(|message| ScriptIssue {
+                    index,
+                    line: self.command_lines.get(index).copied(),
+                    message,
+                })
+            })
+            .collect()
+    }
+
+    /// 执行前校验；存在问题时返回列出全部问题的错误
+    pub fn validate(&self, safety: &SafetyConfig) -> Result<()> {
+        let issues = self.issues(safety);
+        if issues.is_empty() {
+            return Ok(());
+        }
+        let details: Vec<String> = issues.iter().map(|issue| format!("  {issue}")).collect();
+        bail!(
+            "脚本校验失败（{} 处）:\n{}",
+            issues.len(),
+            details.join("\n")
+        )
+    }
+}
+
+/// 把字节偏移转换为 1-based 行号
+fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].matches('\n').count() + 1
+}
+
 pub struct ScriptExecutor {
     config: ScriptConfig,
 }
@@ -78,9 +393,26 @@ impl ScriptExecutor {
         self
     }
 
-    pub fn load_script<P: AsRef<std::path::Path>>(path: P) -> Result<Script> {
+    pub fn load_script<P: AsRef<Path>>(path: P) -> Result<Script> {
+        let path = path.as_ref();
         let content = fs::read_to_string(path).context("读取脚本文件失败")?;
-        serde_json::from_str(&content).context("解析脚本 JSON 失败")
+        if path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("toml")) {
+            Self::parse_toml_script(&content)
+        } else {
+            serde_json::from_str(&content).context("解析脚本 JSON 失败")
+        }
+    }
+
+    pub fn parse_toml_script(content: &str) -> Result<Script> {
+        let raw: TomlScript = toml::from_str(content).context("解析脚本 TOML 失败")?;
+        let command_lines =
+            raw.steps.iter().map(|step| line_of(content, step.span().start)).collect();
+        Ok(Script {
+            name: raw.name,
+            description: raw.description,
+            commands: raw.steps.into_iter().map(toml::Spanned::into_inner).collect(),
+            command_lines,
+        })
     }
 
     #[allow(dead_code)]
@@ -250,6 +582,56 @@ impl ScriptExecutor {
                 .map_err(CommandFailure::lost_standby)?;
                 Ok(ExecutionOutcome::Continue(next))
             },
+            ScriptCommand::Waypoint {
+                joints,
+                duration_ms,
+                wait_for_arrival,
+                speed_percent,
+                force,
+            } => {
+                println!("  航点: {}", command.describe());
+                let positions =
+                    wait_for_initial_monitor_snapshot(|| standby.observer().joint_positions())
+                        .map_err(CommandFailure::lost_standby)?;
+                let current = std::array::from_fn(|index| positions[index].0);
+                let prepared =
+                    match prepare_move(current, joints, &self.config.profile.safety, *force) {
+                        Ok(prepared) => prepared,
+                        Err(error) => return Err(CommandFailure::recoverable(error, standby)),
+                    };
+                if prepared.requires_confirmation {
+                    return Err(CommandFailure::recoverable(
+                        anyhow::anyhow!("脚本中的大幅移动必须显式设置 force=true"),
+                        standby,
+                    ));
+                }
+                let next = run_waypoint(
+                    standby,
+                    &self.config.profile,
+                    prepared.effective_target,
+                    duration_ms.map(Duration::from_millis),
+                    *wait_for_arrival,
+                    *speed_percent,
+                )
+                .map_err(CommandFailure::lost_standby)?;
+                Ok(ExecutionOutcome::Continue(next))
+            },
+            ScriptCommand::Gripper {
+                action,
+                position_mm,
+                effort_nm,
+            } => {
+                println!("  {}", command.describe());
+                let next = run_gripper(
+                    standby,
+                    &self.config.profile,
+                    *action,
+                    *position_mm,
+                    *effort_nm,
+                )
+                .map_err(CommandFailure::lost_standby)?;
+                Ok(ExecutionOutcome::Continue(next))
+            },
             ScriptCommand::Wait { duration_ms } => {
                 println!("  等待: {} ms", duration_ms);
                 tokio::time::sleep(tokio::time::Duration::from_millis(*duration_ms)).await;
@@ -320,6 +702,75 @@ impl Default for ScriptExecutor {
     }
 }
 
+/// 使能位置模式执行一个航点，结束后回到 Standby
+fn run_waypoint<Capability>(
+    standby: Piper<Standby, Capability>,
+    profile: &ControlProfile,
+    target: [f64; 6],
+    duration: Option<Duration>,
+    wait_for_arrival: bool,
+    speed_percent: Option<u8>,
+) -> Result<Piper<Standby, Capability>>
+where
+    Capability: MotionCapability,
+{
+    let mut config = profile.position_mode_config();
+    if let Some(speed_percent) = speed_percent {
+        config.speed_percent = speed_percent;
+    }
+    let mut wait = profile.wait.clone();
+    let active = standby.enable_position_mode(config)?;
+    let started_at = Instant::now();
+
+    let outcome = match (duration, wait_for_arrival) {
+        (Some(duration), true) => {
+            wait.timeout = duration;
+            active_move_to_joint_target_with_cancel(&active, target, &wait, || false)?
+        },
+        (Some(duration), false) => {
+            wait.timeout = duration + profile.wait.timeout;
+            active_move_to_joint_target_with_cancel(&active, target, &wait, || {
+                started_at.elapsed() >= duration
+            })?
+        },
+        (None, _) => active_move_to_joint_target_with_cancel(&active, target, &wait, || false)?,
+    };
+    if outcome == MotionExecutionOutcome::Cancelled {
+        println!("    ⏱ 预算用尽，未等待到位");
+    }
+
+    active.disable(DisableConfig::default()).map_err(Into::into)
+}
+
+/// 使能位置模式执行一个夹爪动作，结束后回到 Standby
+fn run_gripper<Capability>(
+    standby: Piper<Standby, Capability>,
+    profile: &ControlProfile,
+    action: GripperAction,
+    position_mm: Option<f64>,
+    effort_nm: Option<f64>,
+) -> Result<Piper<Standby, Capability>>
+where
+    Capability: MotionCapability,
+{
+    let active = standby.enable_position_mode(profile.position_mode_config())?;
+    let effort = effort_nm.map(NewtonMeter).unwrap_or(GripperCommander::DEFAULT_EFFORT);
+    let gripper = active.gripper().with_effort(effort);
+    match action {
+        GripperAction::Open => gripper.open()?,
+        GripperAction::Close => gripper.close()?,
+        GripperAction::Move => {
+            let Some(position_mm) = position_mm else {
+                bail!("action = \"move\" 需要设置 position_mm");
+            };
+            gripper.set_position(Millimeter(position_mm), effort)?;
+        },
+    }
+    std::thread::sleep(GRIPPER_SETTLE);
+
+    active.disable(DisableConfig::default()).map_err(Into::into)
+}
+
 enum ExecutionOutcome<Capability> {
     Continue(Piper<Standby, Capability>),
     Stop,
@@ -383,6 +834,7 @@ mod tests {
                     force: true,
                 },
             ],
+            command_lines: Vec::new(),
         };
 
         let json = serde_json::to_string(&script).unwrap();
@@ -390,6 +842,100 @@ mod tests {
         assert_eq!(decoded.commands.len(), 3);
     }
 
+    const TOML_SCRIPT: &str = r#"
+name = "cycle"
+description = "waypoints"
+
+[[steps]]
+type = "Waypoint"
+joints = [0.0, 0.2, -0.2]
+duration_ms = 2000
+
+[[steps]]
+type = "Gripper"
+action = "move"
+position_mm = 40.0
+
+[[steps]]
+type = "Pause"
+duration_ms = 500
+
+[[steps]]
+type = "Waypoint"
+joints = [0.0, 9.0]
+wait_for_arrival = false
+"#;
+
+    #[test]
+    fn toml_script_records_step_lines() {
+        let script = ScriptExecutor::parse_toml_script(TOML_SCRIPT).unwrap();
+
+        assert_eq!(script.name, "cycle");
+        assert_eq!(script.commands.len(), 4);
+        assert_eq!(script.command_lines, vec![5, 10, 15, 19]);
+        assert!(matches!(
+            script.commands[0],
+            ScriptCommand::Waypoint {
+                duration_ms: Some(2000),
+                wait_for_arrival: true,
+                ..
+            }
+        ));
+        assert!(matches!(
+            script.commands[2],
+            ScriptCommand::Wait { duration_ms: 500 }
+        ));
+    }
+
+    #[test]
+    fn validation_reports_every_issue_with_its_line() {
+        let script = ScriptExecutor::parse_toml_script(TOML_SCRIPT).unwrap();
+
+        let issues = script.issues(&SafetyConfig::default_config());
+
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        assert_eq!(issues[0].index, 3);
+        assert_eq!(issues[0].line, Some(19));
+        assert!(issues[0].message.contains("J2"), "{}", issues[0].message);
+        assert!(issues[0].to_string().starts_with("第 19 行（命令 4）"));
+        assert!(script.validate(&SafetyConfig::default_config()).is_err());
+    }
+
+    #[test]
+    fn validation_rejects_inconsistent_waypoint_and_gripper_fields() {
+        let safety = SafetyConfig::default_config();
+        let untimed = ScriptCommand::Waypoint {
+            joints: vec![0.0],
+            duration_ms: None,
+            wait_for_arrival: false,
+            speed_percent: None,
+            force: false,
+        };
+        let missing_position = ScriptCommand::Gripper {
+            action: GripperAction::Move,
+            position_mm: None,
+            effort_nm: None,
+        };
+        let open = ScriptCommand::Gripper {
+            action: GripperAction::Open,
+            position_mm: None,
+            effort_nm: Some(1.0),
+        };
+
+        assert!(untimed.validate(&safety).is_err());
+        assert!(missing_position.validate(&safety).is_err());
+        assert!(open.validate(&safety).is_ok());
+    }
+
+    #[test]
+    fn toml_parse_errors_mention_the_line() {
+        let error =
+            ScriptExecutor::parse_toml_script("name = \"x\"\n\n[[steps]]\ntype = \"Teleport\"\n")
+                .unwrap_err();
+
+        assert!(format!("{error:#}").contains("line 4"), "{error:#}");
+    }
+
     #[test]
     fn normalize_set_zero_joints_defaults_to_all() {
         assert_eq!(
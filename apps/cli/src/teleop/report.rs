@@ -607,6 +607,7 @@ fn format_runtime_fault(fault: RuntimeFaultKind) -> &'static str {
         RuntimeFaultKind::TxExited => "tx_exited",
         RuntimeFaultKind::TransportError => "transport_error",
         RuntimeFaultKind::ManualFault => "manual_fault",
+        RuntimeFaultKind::WatchdogExpired => "watchdog_expired",
     }
 }
 
@@ -498,7 +498,7 @@ where
         cancel_signal.clone(),
     ) {
         Ok(summary) => summary,
-        Err(error) if io.cancel_requested() || cancel_signal.load(Ordering::SeqCst) => {
+        Err(_) if io.cancel_requested() || cancel_signal.load(Ordering::SeqCst) => {
             return Ok(TeleopExitStatus::Success);
         },
         Err(error) => return Err(error).context("raw-clock warmup failed"),
@@ -565,7 +565,7 @@ where
         cancel_signal.clone(),
     ) {
         Ok(summary) => summary,
-        Err(error) if io.cancel_requested() || cancel_signal.load(Ordering::SeqCst) => {
+        Err(_) if io.cancel_requested() || cancel_signal.load(Ordering::SeqCst) => {
             return Ok(TeleopExitStatus::Success);
         },
         Err(error) => return Err(error).context("post-confirmation raw-clock refresh failed"),
@@ -2,7 +2,8 @@
 //!
 //! 提供各种输入验证功能
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, anyhow, bail};
+use piper_sdk::protocol::{CanData, CanId, PiperFrame};
 use std::path::Path;
 
 /// 文件路径验证器
@@ -92,10 +93,202 @@ impl Default for PathValidator {
     }
 }
 
+/// 带位置的字符（位置从 1 开始，相对整个输入）
+type Positioned = (usize, char);
+
+fn positioned(text: &str) -> Vec<Positioned> {
+    text.chars().enumerate().map(|(index, c)| (index + 1, c)).collect()
+}
+
+fn trim_positioned(mut chars: &[Positioned]) -> &[Positioned] {
+    while let [(_, c), rest @ ..] = chars
+        && c.is_whitespace()
+    {
+        chars = rest;
+    }
+    while let [rest @ .., (_, c)] = chars
+        && c.is_whitespace()
+    {
+        chars = rest;
+    }
+    chars
+}
+
+fn strip_hex_prefix(chars: &[Positioned]) -> &[Positioned] {
+    match chars {
+        [(_, '0'), (_, 'x' | 'X'), rest @ ..] => rest,
+        _ => chars,
+    }
+}
+
+fn hex_digit(input: &str, (position, c): Positioned) -> Result<u32> {
+    c.to_digit(16)
+        .ok_or_else(|| anyhow!("invalid hex digit '{c}' at position {position} in '{input}'"))
+}
+
+fn text_of(chars: &[Positioned]) -> String {
+    chars.iter().map(|&(_, c)| c).collect()
+}
+
+/// 解析 CAN ID（十六进制，可带 `0x` 前缀）
+///
+/// 8 位十六进制（candump 写法）或超过 0x7FF 的值视为扩展帧 ID。
+pub fn parse_can_id(text: &str) -> Result<CanId> {
+    parse_can_id_chars(text, &positioned(text))
+}
+
+fn parse_can_id_chars(input: &str, chars: &[Positioned]) -> Result<CanId> {
+    let chars = trim_positioned(chars);
+    if chars.is_empty() {
+        bail!("missing CAN ID in '{input}'");
+    }
+    let digits = strip_hex_prefix(chars);
+    let Some(&(first, _)) = digits.first() else {
+        bail!("missing hex digits after '0x' in '{input}'");
+    };
+    if digits.len() > 8 {
+        bail!(
+            "CAN ID '{}' at position {first} in '{input}' has more than 8 hex digits",
+            text_of(digits)
+        );
+    }
+
+    let mut raw = 0u32;
+    for &digit in digits {
+        raw = (raw << 4) | hex_digit(input, digit)?;
+    }
+    if digits.len() == 8 || raw > 0x7FF {
+        CanId::extended(raw).map_err(|error| anyhow!("{error} in '{input}'"))
+    } else {
+        CanId::standard(raw).map_err(|error| anyhow!("{error} in '{input}'"))
+    }
+}
+
+/// 解析十六进制数据字节（可用空格/`.`/`:`/`,` 分隔，每段可带 `0x` 前缀）
+pub fn parse_hex_bytes(text: &str) -> Result<Vec<u8>> {
+    parse_hex_bytes_chars(text, &positioned(text))
+}
+
+fn parse_hex_bytes_chars(input: &str, chars: &[Positioned]) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    for token in chars.split(|&(_, c)| matches!(c, ' ' | '.' | ':' | ',')) {
+        let digits = strip_hex_prefix(token);
+        if let Some(&(position, _)) = digits.first()
+            && !digits.len().is_multiple_of(2)
+        {
+            bail!(
+                "odd number of hex digits in '{}' at position {position} in '{input}'",
+                text_of(digits)
+            );
+        }
+        for pair in digits.chunks(2) {
+            bytes.push((hex_digit(input, pair[0])? << 4 | hex_digit(input, pair[1])?) as u8);
+        }
+    }
+    Ok(bytes)
+}
+
+/// 由 CAN ID 与数据构造帧（数据最多 8 字节）
+pub fn frame_from_parts(id: CanId, data: &[u8]) -> Result<PiperFrame> {
+    if data.len() > 8 {
+        bail!("data has {} bytes; CAN frames carry at most 8", data.len());
+    }
+    let data = CanData::new(data)?;
+    Ok(match id {
+        CanId::Standard(id) => PiperFrame::standard(id, data),
+        CanId::Extended(id) => PiperFrame::extended(id, data),
+    })
+}
+
+/// 解析 candump 风格的帧字面量 `ID#DATA`（如 `2A5#00004650FFFFFC18`）
+///
+/// 错误信息指出出错字符在输入中的位置（从 1 开始）。
+pub fn parse_frame_literal(text: &str) -> Result<PiperFrame> {
+    let chars = positioned(text);
+    let Some(separator) = chars.iter().position(|&(_, c)| c == '#') else {
+        bail!("missing '#' separator in frame '{text}' (expected ID#DATA)");
+    };
+    let id = parse_can_id_chars(text, &chars[..separator])?;
+
+    let data = trim_positioned(&chars[separator + 1..]);
+    match data.first() {
+        Some(&(position, '#')) => {
+            bail!("CAN FD frame at position {position} in '{text}' is not supported")
+        },
+        Some(&(position, 'R' | 'r')) => {
+            bail!("remote frame at position {position} in '{text}' is not supported")
+        },
+        _ => {},
+    }
+    frame_from_parts(id, &parse_hex_bytes_chars(text, data)?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_can_id_detects_extended_ids() {
+        assert_eq!(
+            parse_can_id("0x2A1").unwrap(),
+            CanId::standard(0x2A1).unwrap()
+        );
+        assert_eq!(
+            parse_can_id(" 673 ").unwrap(),
+            CanId::standard(0x673).unwrap()
+        );
+        assert_eq!(
+            parse_can_id("800").unwrap(),
+            CanId::extended(0x800).unwrap()
+        );
+        assert_eq!(
+            parse_can_id("00000151").unwrap(),
+            CanId::extended(0x151).unwrap()
+        );
+
+        let error = parse_can_id("0x2G1").unwrap_err().to_string();
+        assert_eq!(error, "invalid hex digit 'G' at position 4 in '0x2G1'");
+        assert!(parse_can_id("0x").is_err());
+        assert!(parse_can_id("123456789").is_err());
+        assert!(parse_can_id("20000000").is_err());
+    }
+
+    #[test]
+    fn parse_frame_literal_reports_offending_position() {
+        let frame = parse_frame_literal("2A5#00.46:50").unwrap();
+        assert_eq!(
+            frame,
+            PiperFrame::new_standard(0x2A5, [0x00, 0x46, 0x50]).unwrap()
+        );
+        assert_eq!(
+            parse_frame_literal("2A8#").unwrap(),
+            PiperFrame::new_standard(0x2A8, [0u8; 0]).unwrap()
+        );
+
+        let error = |text: &str| parse_frame_literal(text).unwrap_err().to_string();
+        assert_eq!(
+            error("2A5#00Z1"),
+            "invalid hex digit 'Z' at position 7 in '2A5#00Z1'"
+        );
+        assert_eq!(
+            error("2A5#001"),
+            "odd number of hex digits in '001' at position 5 in '2A5#001'"
+        );
+        // 非 ASCII 字符不会导致切片越界 panic
+        assert_eq!(
+            error("2A5#0é"),
+            "invalid hex digit 'é' at position 6 in '2A5#0é'"
+        );
+        assert_eq!(
+            error("2A5#0é0"),
+            "odd number of hex digits in '0é0' at position 5 in '2A5#0é0'"
+        );
+        assert!(error("2A5##11").contains("CAN FD"));
+        assert!(error("151#R").contains("remote frame"));
+        assert!(error("2A5").contains("missing '#'"));
+        assert!(error("2A5#000102030405060708").contains("at most 8"));
+    }
+
     #[test]
     fn test_path_validator_exists() {
         let validator = PathValidator::new().must_exist();
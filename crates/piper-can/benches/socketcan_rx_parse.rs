@@ -0,0 +1,87 @@
+//! SocketCAN 接收帧解析基准
+//!
+//! 对比两条 `can_frame` 字节 -> `PiperFrame` 的解析路径：
+//! - `raw_bytes`：`parse_libc_can_frame_bytes` 直接按 can_id/dlc/data 字段构造（接收热路径）
+//! - `via_socketcan_frame`：先转换为 `socketcan::CanFrame`，再复制到 `PiperFrame`
+//!
+//! 运行：`cargo bench -p piper-can --features test-helpers --bench socketcan_rx_parse`
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use criterion::{Criterion, black_box};
+    use piper_can::PiperFrame;
+    use piper_can::socketcan::{ParsedSocketCanFrame, parse_libc_can_frame_bytes};
+    use socketcan::{EmbeddedFrame, Id};
+
+    const CLASSIC_CAN_MTU: usize = std::mem::size_of::<libc::can_frame>();
+
+    fn raw_frame_bytes(can_id: u32, dlc: u8, data: [u8; 8]) -> [u8; CLASSIC_CAN_MTU] {
+        let mut bytes = [0u8; CLASSIC_CAN_MTU];
+        bytes[..4].copy_from_slice(&can_id.to_ne_bytes());
+        bytes[4] = dlc;
+        bytes[8..16].copy_from_slice(&data);
+        bytes
+    }
+
+    fn parse_via_socketcan(bytes: &[u8; CLASSIC_CAN_MTU]) -> Option<PiperFrame> {
+        // SAFETY: `bytes` 恰为一个 `libc::can_frame` 大小，`read_unaligned` 不要求对齐
+        let raw: libc::can_frame = unsafe { std::ptr::read_unaligned(bytes.as_ptr().cast()) };
+        let socketcan::CanFrame::Data(frame) = socketcan::CanFrame::from(raw) else {
+            return None;
+        };
+        match frame.id() {
+            Id::Standard(id) => PiperFrame::new_standard(u32::from(id.as_raw()), frame.data()),
+            Id::Extended(id) => PiperFrame::new_extended(id.as_raw(), frame.data()),
+        }
+        .ok()
+    }
+
+    /// 一个反馈周期的典型帧：0x251-0x256 高速反馈 + 0x2A5-0x2A7 关节位置 + 扩展帧
+    fn feedback_cycle() -> Vec<[u8; CLASSIC_CAN_MTU]> {
+        let payload = [0x10, 0x21, 0x32, 0x43, 0x54, 0x65, 0x76, 0x87];
+        let mut frames: Vec<_> = (0x251..=0x256)
+            .chain(0x2A5..=0x2A7)
+            .map(|id| raw_frame_bytes(id, 8, payload))
+            .collect();
+        frames.push(raw_frame_bytes(
+            libc::CAN_EFF_FLAG | 0x01AB_CDEF,
+            4,
+            payload,
+        ));
+        frames
+    }
+
+    pub fn bench_rx_parse(c: &mut Criterion) {
+        let frames = feedback_cycle();
+        let mut group = c.benchmark_group("socketcan_rx_parse");
+
+        group.bench_function("raw_bytes", |b| {
+            b.iter(|| {
+                for bytes in &frames {
+                    let parsed = parse_libc_can_frame_bytes(black_box(bytes), CLASSIC_CAN_MTU, 0);
+                    if let ParsedSocketCanFrame::Data(frame) = parsed {
+                        black_box(frame);
+                    }
+                }
+            })
+        });
+
+        group.bench_function("via_socketcan_frame", |b| {
+            b.iter(|| {
+                for bytes in &frames {
+                    black_box(parse_via_socketcan(black_box(bytes)));
+                }
+            })
+        });
+
+        group.finish();
+    }
+}
+
+#[cfg(target_os = "linux")]
+criterion::criterion_group!(benches, linux::bench_rx_parse);
+#[cfg(target_os = "linux")]
+criterion::criterion_main!(benches);
+
+#[cfg(not(target_os = "linux"))]
+fn main() {}
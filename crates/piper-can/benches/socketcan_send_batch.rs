@@ -0,0 +1,60 @@
+//! SocketCAN 批量发送基准
+//!
+//! 对比一个轨迹突发（默认 6 帧）的两种发送方式：
+//! - `send_each`：逐帧 `send`，每帧一次 `write` 系统调用
+//! - `send_batch`：一次 `sendmmsg` 系统调用推送整批
+//!
+//! 需要 `vcan0`（`sudo ip link add dev vcan0 type vcan && sudo ip link set up vcan0`），
+//! 接口不存在时跳过。运行：`cargo bench -p piper-can --bench socketcan_send_batch`
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use criterion::{BenchmarkId, Criterion, black_box};
+    use piper_can::{CanAdapter, PiperFrame, SocketCanAdapter};
+
+    const INTERFACE: &str = "vcan0";
+
+    fn burst(len: usize) -> Vec<PiperFrame> {
+        (0..len)
+            .map(|index| PiperFrame::new_standard(0x155 + (index as u32 % 3), [index as u8; 8]))
+            .collect::<Result<_, _>>()
+            .expect("valid benchmark frames")
+    }
+
+    pub fn bench_send_batch(c: &mut Criterion) {
+        let mut adapter = match SocketCanAdapter::new(INTERFACE) {
+            Ok(adapter) => adapter,
+            Err(error) => {
+                eprintln!("Skipping socketcan_send_batch: {INTERFACE} unavailable ({error})");
+                return;
+            },
+        };
+
+        let mut group = c.benchmark_group("socketcan_send_batch");
+        for len in [6, 32] {
+            let frames = burst(len);
+            group.bench_with_input(BenchmarkId::new("send_each", len), &frames, |b, frames| {
+                b.iter(|| {
+                    for frame in frames {
+                        adapter.send(black_box(*frame)).expect("send");
+                    }
+                })
+            });
+            group.bench_with_input(BenchmarkId::new("send_batch", len), &frames, |b, frames| {
+                b.iter(|| {
+                    let sent = adapter.send_batch(black_box(frames)).expect("send_batch");
+                    assert_eq!(sent, frames.len());
+                })
+            });
+        }
+        group.finish();
+    }
+}
+
+#[cfg(target_os = "linux")]
+criterion::criterion_group!(benches, linux::bench_send_batch);
+#[cfg(target_os = "linux")]
+criterion::criterion_main!(benches);
+
+#[cfg(not(target_os = "linux"))]
+fn main() {}
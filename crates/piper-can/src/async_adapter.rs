@@ -0,0 +1,285 @@
+//! 异步 CAN 适配器（tokio）
+//!
+//! 为 async 应用提供非阻塞的收发接口，可在 `tokio::select!` 中与其他异步事件一起等待，
+//! 无需为 `receive` 单独占用一个线程。
+//!
+//! - [`AsyncCanAdapter`]: 异步收发 trait
+//! - [`BlockingAsyncAdapter`]: 把任意阻塞 [`CanAdapter`]（如 GS-USB）放入内部的
+//!   `spawn_blocking` 工作任务中
+//! - `AsyncSocketCanAdapter`（仅 Linux）: 基于 `AsyncFd` 的 SocketCAN 实现，不占用额外线程
+//!
+//! # 示例
+//!
+//! ```rust,ignore
+//! use piper_can::{AsyncCanAdapter, AsyncSocketCanAdapter};
+//!
+//! let mut can = AsyncSocketCanAdapter::new("can0")?;
+//! loop {
+//!     tokio::select! {
+//!         frame = can.receive() => handle(frame?),
+//!         _ = shutdown.recv() => break,
+//!     }
+//! }
+//! ```
+
+use crate::{CanAdapter, CanError, PiperFrame, ReceivedFrame};
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc as std_mpsc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+/// 异步 CAN 适配器
+///
+/// 与 [`CanAdapter`] 语义一致：`receive` 只返回有效数据帧，错误帧由实现过滤。
+pub trait AsyncCanAdapter {
+    /// 发送帧
+    fn send(&mut self, frame: PiperFrame) -> impl Future<Output = Result<(), CanError>> + Send;
+
+    /// 接收下一个有效数据帧
+    ///
+    /// 实现必须是取消安全的：在 `select!` 中被取消时不会丢失已到达的帧。
+    fn receive(&mut self) -> impl Future<Output = Result<ReceivedFrame, CanError>> + Send;
+}
+
+/// 工作任务的接收队列容量
+const RX_QUEUE_CAPACITY: usize = 256;
+
+/// 工作任务在两次检查发送请求之间的默认接收等待时间
+pub const DEFAULT_BLOCKING_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+struct TxRequest {
+    frame: PiperFrame,
+    reply: oneshot::Sender<Result<(), CanError>>,
+}
+
+/// 阻塞适配器的异步包装
+///
+/// 在 tokio 的阻塞线程池中运行一个工作任务（`spawn_blocking`），循环执行：
+/// 处理排队的发送请求 → 以 `poll_interval` 为超时接收一帧 → 转发到异步队列。
+/// 因此发送延迟最多为一个 `poll_interval`。
+///
+/// 接收队列满时丢弃新帧（不阻塞工作任务，以免拖慢发送），丢弃数量见
+/// [`dropped_rx_frames`](Self::dropped_rx_frames)。
+///
+/// 底层适配器返回致命错误（`BusOff`、`NotStarted`、致命设备错误）后工作任务退出；
+/// 之后的 `send` / `receive` 返回 `CanError::NotStarted`。
+pub struct BlockingAsyncAdapter {
+    tx_requests: std_mpsc::Sender<TxRequest>,
+    rx_frames: mpsc::Receiver<Result<ReceivedFrame, CanError>>,
+    stop: Arc<AtomicBool>,
+    dropped_rx_frames: Arc<AtomicU64>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl BlockingAsyncAdapter {
+    /// 启动工作任务（使用 [`DEFAULT_BLOCKING_POLL_INTERVAL`]）
+    ///
+    /// # Panics
+    ///
+    /// 必须在 tokio 运行时内调用。
+    pub fn spawn<A>(adapter: A) -> Self
+    where
+        A: CanAdapter + Send + 'static,
+    {
+        Self::spawn_with_poll_interval(adapter, DEFAULT_BLOCKING_POLL_INTERVAL)
+    }
+
+    /// 启动工作任务并指定接收等待时间
+    ///
+    /// # Panics
+    ///
+    /// 必须在 tokio 运行时内调用。
+    pub fn spawn_with_poll_interval<A>(mut adapter: A, poll_interval: Duration) -> Self
+    where
+        A: CanAdapter + Send + 'static,
+    {
+        let (tx_requests, tx_queue) = std_mpsc::channel::<TxRequest>();
+        let (rx_sender, rx_frames) = mpsc::channel(RX_QUEUE_CAPACITY);
+        let stop = Arc::new(AtomicBool::new(false));
+        let dropped_rx_frames = Arc::new(AtomicU64::new(0));
+
+        let worker_stop = stop.clone();
+        let worker_dropped = dropped_rx_frames.clone();
+        let worker = tokio::task::spawn_blocking(move || {
+            adapter.set_receive_timeout(poll_interval);
+            while !worker_stop.load(Ordering::Acquire) {
+                loop {
+                    match tx_queue.try_recv() {
+                        Ok(request) => {
+                            let _ = request.reply.send(adapter.send(request.frame));
+                        },
+                        Err(std_mpsc::TryRecvError::Empty) => break,
+                        Err(std_mpsc::TryRecvError::Disconnected) => return,
+                    }
+                }
+
+                let result = match adapter.receive_timeout(poll_interval) {
+                    Err(CanError::Timeout) => continue,
+                    result => result,
+                };
+                let fatal = result.as_ref().is_err_and(is_fatal_worker_error);
+                match rx_sender.try_send(result) {
+                    Ok(()) => {},
+                    Err(mpsc::error::TrySendError::Full(_)) => {
+                        worker_dropped.fetch_add(1, Ordering::Relaxed);
+                    },
+                    Err(mpsc::error::TrySendError::Closed(_)) => return,
+                }
+                if fatal {
+                    warn!("Blocking CAN adapter worker stopped after fatal error");
+                    return;
+                }
+            }
+        });
+
+        Self {
+            tx_requests,
+            rx_frames,
+            stop,
+            dropped_rx_frames,
+            worker: Some(worker),
+        }
+    }
+
+    /// 接收队列满导致丢弃的帧数
+    pub fn dropped_rx_frames(&self) -> u64 {
+        self.dropped_rx_frames.load(Ordering::Relaxed)
+    }
+
+    /// 停止工作任务并等待其退出
+    pub async fn shutdown(mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.await;
+        }
+    }
+}
+
+fn is_fatal_worker_error(error: &CanError) -> bool {
+    match error {
+        CanError::BusOff | CanError::NotStarted => true,
+        CanError::Device(device) => device.is_fatal(),
+        _ => false,
+    }
+}
+
+impl Drop for BlockingAsyncAdapter {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+    }
+}
+
+impl AsyncCanAdapter for BlockingAsyncAdapter {
+    async fn send(&mut self, frame: PiperFrame) -> Result<(), CanError> {
+        let (reply, response) = oneshot::channel();
+        self.tx_requests
+            .send(TxRequest { frame, reply })
+            .map_err(|_| CanError::NotStarted)?;
+        response.await.map_err(|_| CanError::NotStarted)?
+    }
+
+    async fn receive(&mut self) -> Result<ReceivedFrame, CanError> {
+        self.rx_frames.recv().await.unwrap_or(Err(CanError::NotStarted))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CanData, StandardCanId, TimestampProvenance};
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    struct ScriptedAdapter {
+        rx: VecDeque<Result<ReceivedFrame, CanError>>,
+        sent: Arc<Mutex<Vec<PiperFrame>>>,
+    }
+
+    impl CanAdapter for ScriptedAdapter {
+        fn send(&mut self, frame: PiperFrame) -> Result<(), CanError> {
+            self.sent.lock().unwrap().push(frame);
+            Ok(())
+        }
+
+        fn receive(&mut self) -> Result<ReceivedFrame, CanError> {
+            self.rx.pop_front().unwrap_or(Err(CanError::Timeout))
+        }
+
+        fn receive_timeout(&mut self, timeout: Duration) -> Result<ReceivedFrame, CanError> {
+            match self.rx.pop_front() {
+                Some(result) => result,
+                None => {
+                    std::thread::sleep(timeout);
+                    Err(CanError::Timeout)
+                },
+            }
+        }
+    }
+
+    fn frame(id: u32) -> PiperFrame {
+        PiperFrame::standard(
+            StandardCanId::new(id).unwrap(),
+            CanData::from_array([id as u8; 8]),
+        )
+    }
+
+    fn received(id: u32) -> Result<ReceivedFrame, CanError> {
+        Ok(ReceivedFrame::new(
+            frame(id),
+            TimestampProvenance::Userspace,
+        ))
+    }
+
+    #[tokio::test]
+    async fn blocking_adapter_forwards_frames_in_both_directions() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let mut adapter = BlockingAsyncAdapter::spawn(ScriptedAdapter {
+            rx: VecDeque::from([received(0x2A5), received(0x2A6)]),
+            sent: sent.clone(),
+        });
+
+        assert_eq!(adapter.receive().await.unwrap().frame.raw_id(), 0x2A5);
+        assert_eq!(adapter.receive().await.unwrap().frame.raw_id(), 0x2A6);
+
+        adapter.send(frame(0x155)).await.unwrap();
+        assert_eq!(sent.lock().unwrap().as_slice(), &[frame(0x155)]);
+
+        adapter.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn blocking_adapter_receive_is_usable_in_select() {
+        let mut adapter = BlockingAsyncAdapter::spawn(ScriptedAdapter {
+            rx: VecDeque::new(),
+            sent: Arc::new(Mutex::new(Vec::new())),
+        });
+
+        let timed_out = tokio::select! {
+            _ = adapter.receive() => false,
+            _ = tokio::time::sleep(Duration::from_millis(20)) => true,
+        };
+
+        assert!(timed_out, "idle bus must not resolve receive()");
+        adapter.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn blocking_adapter_stops_after_fatal_error() {
+        let mut adapter = BlockingAsyncAdapter::spawn(ScriptedAdapter {
+            rx: VecDeque::from([received(0x2A5), Err(CanError::BusOff)]),
+            sent: Arc::new(Mutex::new(Vec::new())),
+        });
+
+        assert!(adapter.receive().await.is_ok());
+        assert!(matches!(adapter.receive().await, Err(CanError::BusOff)));
+        assert!(matches!(adapter.receive().await, Err(CanError::NotStarted)));
+        assert!(matches!(
+            adapter.send(frame(0x155)).await,
+            Err(CanError::NotStarted)
+        ));
+    }
+}
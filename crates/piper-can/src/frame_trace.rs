@@ -0,0 +1,115 @@
+//! CAN 帧结构化追踪
+//!
+//! 收发路径以固定 target 和字段名输出 `trace` 级事件，供 `tracing` 订阅者
+//! 按 CAN ID / 方向过滤与聚合（例如 `RUST_LOG=piper_can::frame=trace`）。
+//!
+//! | 字段 | 含义 |
+//! |------|------|
+//! | `can.id` | 原始 CAN ID（不含 EFF 标志） |
+//! | `can.dlc` | 数据长度 |
+//! | `can.dir` | `"tx"` / `"rx"` |
+//! | `adapter.interface` | SocketCAN 接口名，或 GS-USB 序列号（无序列号时为 `"gs_usb"`） |
+//!
+//! 级别未启用时只有一次静态过滤判断，可以留在热路径上。
+
+use crate::PiperFrame;
+use tracing::Level;
+
+/// 帧事件使用的 target
+pub const TARGET: &str = "piper_can::frame";
+
+/// GS-USB 设备无序列号时使用的接口名
+pub(crate) const GS_USB_INTERFACE: &str = "gs_usb";
+
+/// 记录一帧发送
+#[inline]
+pub(crate) fn tx(interface: &str, frame: &PiperFrame) {
+    tracing::event!(
+        target: TARGET,
+        Level::TRACE,
+        can.id = frame.raw_id(),
+        can.dlc = frame.dlc(),
+        can.dir = "tx",
+        adapter.interface = interface,
+        "can frame"
+    );
+}
+
+/// 记录一帧接收
+#[inline]
+pub(crate) fn rx(interface: &str, frame: &PiperFrame) {
+    tracing::event!(
+        target: TARGET,
+        Level::TRACE,
+        can.id = frame.raw_id(),
+        can.dlc = frame.dlc(),
+        can.dir = "rx",
+        adapter.interface = interface,
+        "can frame"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt::Debug;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    type Fields = Vec<(String, String)>;
+
+    /// 收集事件字段的最小订阅者
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<Fields>>>);
+
+    struct FieldVisitor(Fields);
+
+    impl Visit for FieldVisitor {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0.push((field.name().to_string(), format!("{:?}", value)));
+        }
+    }
+
+    impl Subscriber for Capture {
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            metadata.target() == TARGET
+        }
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, event: &Event<'_>) {
+            let mut visitor = FieldVisitor(Vec::new());
+            event.record(&mut visitor);
+            self.0.lock().unwrap().push(visitor.0);
+        }
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn frame_events_carry_structured_fields() {
+        let capture = Capture::default();
+        let frame = PiperFrame::new_standard(0x2A5, [1, 2, 3]).unwrap();
+
+        tracing::subscriber::with_default(capture.clone(), || {
+            tx("vcan0", &frame);
+            rx(GS_USB_INTERFACE, &frame);
+        });
+
+        let events = capture.0.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        let field = |event: &[(String, String)], name: &str| {
+            event.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone()).unwrap()
+        };
+        assert_eq!(field(&events[0], "can.id"), "677");
+        assert_eq!(field(&events[0], "can.dlc"), "3");
+        assert_eq!(field(&events[0], "can.dir"), "\"tx\"");
+        assert_eq!(field(&events[0], "adapter.interface"), "\"vcan0\"");
+        assert_eq!(field(&events[1], "can.dir"), "\"rx\"");
+        assert_eq!(field(&events[1], "adapter.interface"), "\"gs_usb\"");
+    }
+}
@@ -304,6 +304,11 @@ impl GsUsbDevice {
         (device, harness)
     }
 
+    #[cfg(test)]
+    pub(crate) fn set_test_hw_timestamp(&mut self, enabled: bool) {
+        self.hw_timestamp = enabled;
+    }
+
     fn usb_timeout_from_deadline(deadline: Instant, now: Instant) -> Result<Duration, GsUsbError> {
         let Some(remaining) = deadline.checked_duration_since(now) else {
             return Err(GsUsbError::WriteTimeout);
@@ -492,6 +497,11 @@ impl GsUsbDevice {
         self.serial_number.as_deref()
     }
 
+    /// 结构化追踪中的 `adapter.interface`（序列号，缺省为 `"gs_usb"`）
+    pub(crate) fn trace_interface(&self) -> &str {
+        self.serial_number().unwrap_or(crate::frame_trace::GS_USB_INTERFACE)
+    }
+
     /// 设备 VID
     pub fn vendor_id(&self) -> u16 {
         self.vendor_id
@@ -670,12 +680,13 @@ impl GsUsbDevice {
         let mut flags = flags & capability.feature;
 
         // 4. 过滤 flags：只保留驱动支持的功能
-        // 我们的驱动支持 CAN 2.0 和硬件时间戳，不支持 CAN FD 等高级功能
+        // 我们的驱动支持 CAN 2.0、One-Shot 和硬件时间戳，不支持 CAN FD 等高级功能
         flags &= GS_CAN_MODE_LISTEN_ONLY
             | GS_CAN_MODE_LOOP_BACK
             | GS_CAN_MODE_NORMAL
+            | GS_CAN_MODE_ONE_SHOT
             | GS_CAN_MODE_HW_TIMESTAMP;
-        // 注意：不包含 GS_CAN_MODE_FD, GS_CAN_MODE_ONE_SHOT 等
+        // 注意：不包含 GS_CAN_MODE_FD 等
         // 因为我们只支持经典 CAN 2.0
 
         // 5. 记录是否启用硬件时间戳
@@ -782,6 +793,20 @@ impl GsUsbDevice {
         }
     }
 
+    /// 按设备约束校验后设置位定时（用于自定义采样点）
+    pub fn set_bit_timing(&mut self, timing: &DeviceBitTiming) -> Result<(), GsUsbError> {
+        self.ensure_interface_claimed()?;
+        let capability = self.device_capability()?;
+        timing.validate(&capability).map_err(GsUsbError::UnsupportedBitTiming)?;
+        self.set_timing(
+            timing.prop_seg,
+            timing.phase_seg1,
+            timing.phase_seg2,
+            timing.sjw,
+            timing.brp,
+        )
+    }
+
     /// 设置原始 CAN 位定时参数
     pub fn set_timing(
         &mut self,
@@ -812,6 +837,12 @@ impl GsUsbDevice {
         Ok(cap)
     }
 
+    /// 获取设备配置（通道数、固件/硬件版本）
+    pub fn device_config(&self) -> Result<DeviceInfo, GsUsbError> {
+        let data = self.control_in(GS_USB_BREQ_DEVICE_CONFIG, 0, 12)?;
+        Ok(DeviceInfo::unpack(&data))
+    }
+
     /// 发送原始 GS-USB 帧（Fire-and-Forget）
     ///
     /// 这条路径遵循 exact-write + fail-fast 语义：
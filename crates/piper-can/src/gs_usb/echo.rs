@@ -0,0 +1,166 @@
+//! GS-USB TX Echo 跟踪
+//!
+//! 每次发送分配一个轮转的 `echo_id`，并在固定大小的在途表中记录发送时刻与 CAN ID。
+//! RX 路径读到设备回传的 Echo 后按 `echo_id` 查表，得到 TX→Echo 的真实 USB 发送延迟；
+//! 槽位在 Echo 到达前被再次分配则计为丢失。
+//!
+//! 在途表全部由原子量构成，split 后 TX / RX 线程通过 `Arc` 共享，热路径不加锁。
+
+use crate::{TxEchoStats, monotonic_micros};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+
+/// 在途表容量（可用 `echo_id` 为 `1..=TX_ECHO_SLOTS`）
+///
+/// `0` 保留给不参与跟踪的发送，`0xFFFF_FFFF` 为 RX 帧标记。
+/// 上限与 Linux gs_usb 驱动的 TX 上下文数（10）一致：固件按 `echo_id` 索引 TX 上下文，
+/// 超出范围的 id 会越界。
+pub const TX_ECHO_SLOTS: u32 = 9;
+
+#[derive(Default)]
+struct Slot {
+    /// 发送时刻（`monotonic_micros`，0 表示空闲）
+    sent_us: AtomicU64,
+    /// 发送帧的 GS-USB `can_id`（含 EFF 标志）
+    can_id: AtomicU32,
+}
+
+/// TX Echo 在途表与延迟统计
+pub struct TxEchoTracker {
+    slots: Box<[Slot]>,
+    next: AtomicU32,
+    completed: AtomicU64,
+    lost: AtomicU64,
+    unmatched: AtomicU64,
+    last_latency_us: AtomicU64,
+    max_latency_us: AtomicU64,
+    total_latency_us: AtomicU64,
+}
+
+impl Default for TxEchoTracker {
+    fn default() -> Self {
+        Self {
+            slots: (0..TX_ECHO_SLOTS).map(|_| Slot::default()).collect(),
+            next: AtomicU32::new(0),
+            completed: AtomicU64::new(0),
+            lost: AtomicU64::new(0),
+            unmatched: AtomicU64::new(0),
+            last_latency_us: AtomicU64::new(0),
+            max_latency_us: AtomicU64::new(0),
+            total_latency_us: AtomicU64::new(0),
+        }
+    }
+}
+
+impl TxEchoTracker {
+    /// 为一次发送分配 `echo_id` 并记录发送元数据
+    pub fn allocate(&self, can_id: u32) -> u32 {
+        let echo_id = self.next.fetch_add(1, Ordering::Relaxed) % TX_ECHO_SLOTS + 1;
+        let slot = &self.slots[(echo_id - 1) as usize];
+        slot.can_id.store(can_id, Ordering::Relaxed);
+        if slot.sent_us.swap(monotonic_micros().max(1), Ordering::AcqRel) != 0 {
+            self.lost.fetch_add(1, Ordering::Relaxed);
+        }
+        echo_id
+    }
+
+    /// 发送失败时撤销分配（帧未进入设备，不会有 Echo）
+    pub fn cancel(&self, echo_id: u32) {
+        if let Some(slot) = self.slot(echo_id) {
+            slot.sent_us.store(0, Ordering::Release);
+        }
+    }
+
+    /// 处理一个 TX Echo；返回 TX→Echo 延迟（微秒）
+    ///
+    /// `echo_id` 不在跟踪范围（如 `0`）时直接忽略并返回 `None`。
+    pub fn complete(&self, echo_id: u32, can_id: u32) -> Option<u64> {
+        let slot = self.slot(echo_id)?;
+        let sent_us = slot.sent_us.swap(0, Ordering::AcqRel);
+        if sent_us == 0 || slot.can_id.load(Ordering::Relaxed) != can_id {
+            self.unmatched.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+
+        let latency_us = monotonic_micros().saturating_sub(sent_us);
+        self.completed.fetch_add(1, Ordering::Relaxed);
+        self.total_latency_us.fetch_add(latency_us, Ordering::Relaxed);
+        self.last_latency_us.store(latency_us, Ordering::Relaxed);
+        self.max_latency_us.fetch_max(latency_us, Ordering::Relaxed);
+        Some(latency_us)
+    }
+
+    /// 统计快照
+    pub fn stats(&self) -> TxEchoStats {
+        let completed = self.completed.load(Ordering::Relaxed);
+        TxEchoStats {
+            completed,
+            lost: self.lost.load(Ordering::Relaxed),
+            unmatched: self.unmatched.load(Ordering::Relaxed),
+            in_flight: self
+                .slots
+                .iter()
+                .filter(|slot| slot.sent_us.load(Ordering::Relaxed) != 0)
+                .count(),
+            last_latency_us: self.last_latency_us.load(Ordering::Relaxed),
+            max_latency_us: self.max_latency_us.load(Ordering::Relaxed),
+            mean_latency_us: self
+                .total_latency_us
+                .load(Ordering::Relaxed)
+                .checked_div(completed)
+                .unwrap_or(0),
+        }
+    }
+
+    fn slot(&self, echo_id: u32) -> Option<&Slot> {
+        echo_id.checked_sub(1).and_then(|index| self.slots.get(index as usize))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn echo_ids_rotate_and_skip_reserved_values() {
+        let tracker = TxEchoTracker::default();
+        let ids: Vec<u32> = (0..TX_ECHO_SLOTS + 1).map(|_| tracker.allocate(0x100)).collect();
+        assert_eq!(ids[0], 1);
+        assert_eq!(ids[TX_ECHO_SLOTS as usize - 1], TX_ECHO_SLOTS);
+        // 第一个槽位在 Echo 到达前被复用 -> 丢失
+        assert_eq!(ids[TX_ECHO_SLOTS as usize], 1);
+        let stats = tracker.stats();
+        assert_eq!(stats.lost, 1);
+        assert_eq!(stats.in_flight, TX_ECHO_SLOTS as usize);
+    }
+
+    #[test]
+    fn complete_matches_echo_id_and_can_id() {
+        let tracker = TxEchoTracker::default();
+        let first = tracker.allocate(0x151);
+        let second = tracker.allocate(0x152);
+
+        assert!(tracker.complete(first, 0x151).is_some());
+        // 重复 Echo、CAN ID 不符、未跟踪的 echo_id
+        assert!(tracker.complete(first, 0x151).is_none());
+        assert!(tracker.complete(second, 0x999).is_none());
+        assert!(tracker.complete(0, 0x151).is_none());
+
+        let stats = tracker.stats();
+        assert_eq!(stats.completed, 1);
+        assert_eq!(stats.unmatched, 2);
+        assert_eq!(stats.in_flight, 0);
+        assert_eq!(stats.lost, 0);
+    }
+
+    #[test]
+    fn cancel_frees_slot_without_counting_loss() {
+        let tracker = TxEchoTracker::default();
+        let echo_id = tracker.allocate(0x151);
+        tracker.cancel(echo_id);
+        assert_eq!(tracker.stats().in_flight, 0);
+        for _ in 0..TX_ECHO_SLOTS {
+            tracker.allocate(0x151);
+        }
+        assert_eq!(tracker.stats().lost, 0);
+    }
+}
@@ -50,6 +50,10 @@ pub enum GsUsbError {
     /// 不支持的波特率
     #[error("Unsupported bitrate {bitrate} for clock {clock_hz} Hz")]
     UnsupportedBitrate { bitrate: u32, clock_hz: u32 },
+
+    /// 位定时参数超出设备约束
+    #[error("Unsupported bit timing: {0}")]
+    UnsupportedBitTiming(String),
 }
 
 impl GsUsbError {
@@ -141,9 +141,42 @@ impl GsUsbFrame {
     }
 }
 
+/// GS-USB 32 位硬件时间戳扩展
+///
+/// 设备时间戳为 `u32` 微秒计数，约 71.6 分钟回绕一次。跟踪上一次的原始值，
+/// 检测到回退时累加回绕次数，输出单调递增的 `u64` 时间戳。
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct HwTimestampExtender {
+    wraps: u64,
+    last_low: Option<u32>,
+}
+
+impl HwTimestampExtender {
+    /// 扩展原始时间戳；`0` 表示设备未提供时间戳，原样返回
+    pub(crate) fn extend(&mut self, timestamp_low: u32) -> u64 {
+        if timestamp_low == 0 {
+            return 0;
+        }
+
+        if let Some(previous) = self.last_low
+            && timestamp_low < previous
+        {
+            self.wraps = self.wraps.saturating_add(1);
+        }
+
+        self.last_low = Some(timestamp_low);
+        (self.wraps << 32) | (timestamp_low as u64)
+    }
+
+    /// 已检测到的回绕次数
+    pub(crate) fn wrap_count(&self) -> u64 {
+        self.wraps
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::GsUsbFrame;
+    use super::{GsUsbFrame, HwTimestampExtender};
     use crate::gs_usb::protocol::*;
     use bytes::BytesMut;
 
@@ -502,4 +535,15 @@ mod tests {
         assert_eq!(frame.can_dlc, 0);
         assert_eq!(frame.timestamp_us, 0);
     }
+
+    #[test]
+    fn hw_timestamp_extender_accumulates_wraps() {
+        let mut extender = HwTimestampExtender::default();
+        assert_eq!(extender.extend(0xFFFF_FF00), 0xFFFF_FF00);
+        assert_eq!(extender.extend(0x0000_0010), (1 << 32) | 0x10);
+        assert_eq!(extender.extend(0x0000_0020), (1 << 32) | 0x20);
+        assert_eq!(extender.extend(0), 0);
+        assert_eq!(extender.extend(0x0000_0005), (2 << 32) | 0x05);
+        assert_eq!(extender.wrap_count(), 2);
+    }
 }
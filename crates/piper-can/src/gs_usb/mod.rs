@@ -4,6 +4,7 @@
 
 pub mod classify;
 pub mod device;
+pub mod echo;
 pub mod error;
 pub mod frame;
 pub mod protocol;
@@ -13,18 +14,100 @@ use crate::gs_usb::classify::parse_gs_usb_batch;
 use crate::gs_usb::device::{
     GS_USB_BATCH_FRAME_CAPACITY, GS_USB_READ_BUFFER_SIZE, GsUsbDevice, GsUsbDeviceSelector,
 };
-use crate::gs_usb::frame::GsUsbFrame;
+use crate::gs_usb::echo::TxEchoTracker;
+use crate::gs_usb::frame::{GsUsbFrame, HwTimestampExtender};
 use crate::gs_usb::protocol::*;
-use crate::gs_usb::split::{GsUsbRxAdapter, GsUsbTxAdapter};
+use crate::gs_usb::split::{GsUsbRxAdapter, GsUsbTxAdapter, tx_can_id};
 use crate::{
-    BackendCapability, CanAdapter, CanDeviceError, CanDeviceErrorKind, CanError, CanId, PiperFrame,
-    ReceivedFrame, SplittableAdapter, TimestampProvenance,
+    BackendCapability, CanAdapter, CanDeviceError, CanDeviceErrorKind, CanError, PiperFrame,
+    ReceivedFrame, SplittableAdapter, TimestampProvenance, TxEchoStats,
 };
 use std::collections::VecDeque;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tracing::{trace, warn};
 
+/// 接收队列溢出策略
+///
+/// 接收队列缓存单个 USB 包中解出的多余帧；队列满时按此策略处理新帧。
+/// 所有导致丢帧或报错的溢出都会计入 [`GsUsbCanAdapter::rx_overflow_count`]。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum RxQueueOverflowPolicy {
+    /// 不丢帧：允许队列暂时超出容量。队列非空时 `receive()` 不再读取 USB，
+    /// 由设备端缓冲承担背压
+    Block,
+    /// 丢弃最旧的帧（默认）
+    #[default]
+    DropOldest,
+    /// 丢弃新到达的帧
+    DropNewest,
+    /// 丢弃新到达的帧，并在本次接收返回 `CanError::BufferOverflow`
+    Error,
+}
+
+/// GS-USB 设备能力（`configure` 之后可用，见 [`GsUsbCanAdapter::capabilities`]）
+///
+/// GS-USB 的 feature 位与 `GS_CAN_MODE_*` 标志位一一对应。
+#[derive(Debug, Clone, Copy)]
+pub struct GsUsbCapabilities {
+    /// 设备声明支持的功能位（BT_CONST.feature）
+    pub feature: u32,
+    /// CAN 时钟频率（Hz）
+    pub fclk_can: u32,
+    /// 当前启动时实际生效的模式标志
+    pub effective_flags: u32,
+    /// 设备配置（固件/硬件版本、通道数）；设备不支持查询时为 `None`
+    pub device_info: Option<DeviceInfo>,
+}
+
+impl GsUsbCapabilities {
+    fn supports(&self, flag: u32) -> bool {
+        self.feature & flag != 0
+    }
+
+    /// 设备是否支持硬件时间戳
+    pub fn supports_hw_timestamp(&self) -> bool {
+        self.supports(GS_CAN_MODE_HW_TIMESTAMP)
+    }
+
+    /// 设备是否支持 Listen-Only 模式
+    pub fn supports_listen_only(&self) -> bool {
+        self.supports(GS_CAN_MODE_LISTEN_ONLY)
+    }
+
+    /// 设备是否支持 Loopback 模式
+    pub fn supports_loopback(&self) -> bool {
+        self.supports(GS_CAN_MODE_LOOP_BACK)
+    }
+
+    /// 设备是否支持 One-Shot 模式（本驱动不会启用该模式）
+    pub fn supports_one_shot(&self) -> bool {
+        self.supports(GS_CAN_MODE_ONE_SHOT)
+    }
+
+    /// 设备是否支持三重采样
+    pub fn supports_triple_sample(&self) -> bool {
+        self.supports(GS_CAN_MODE_TRIPLE_SAMPLE)
+    }
+
+    /// 设备是否声明支持 CAN FD
+    ///
+    /// 仅供探测：本驱动只收发经典 CAN 2.0 帧，不会启用 FD 模式与数据段波特率。
+    pub fn supports_fd(&self) -> bool {
+        self.supports(GS_CAN_MODE_FD)
+    }
+
+    /// 当前是否实际启用了硬件时间戳（为 `false` 时 `timestamp_us` 无硬件来源）
+    pub fn hw_timestamp_enabled(&self) -> bool {
+        self.effective_flags & GS_CAN_MODE_HW_TIMESTAMP != 0
+    }
+
+    /// 固件版本（如 `2.1`）
+    pub fn firmware_version(&self) -> Option<f32> {
+        self.device_info.map(|info| info.firmware_version())
+    }
+}
+
 /// GS-USB CAN 适配器
 ///
 /// 实现 `CanAdapter` trait，提供统一的 CAN 接口
@@ -49,11 +132,56 @@ pub struct GsUsbCanAdapter {
     realtime_mode: bool,
     /// 连续写超时计数（用于检测设备故障）
     consecutive_write_timeouts: u32,
+    /// 硬件时间戳回绕跟踪
+    timestamp_extender: HwTimestampExtender,
+    /// 接收队列容量上限
+    rx_queue_capacity: usize,
+    /// 接收队列溢出策略
+    rx_overflow_policy: RxQueueOverflowPolicy,
+    /// 接收队列溢出次数
+    rx_overflow_count: u64,
+    /// 设备拔出后的自动重开状态
+    reopen: AutoReopen,
+    /// 最近一次 `configure` 得到的设备能力
+    capabilities: Option<GsUsbCapabilities>,
+    /// 自定义位定时（`None` 时使用推荐表）
+    bit_timing: Option<DeviceBitTiming>,
+    /// TX Echo 在途表与延迟统计（split 后由 RX / TX 共享）
+    tx_echo: Arc<TxEchoTracker>,
+}
+
+/// 自动重开状态（见 [`GsUsbCanAdapter::set_auto_reopen`]）
+#[derive(Default)]
+struct AutoReopen {
+    /// 重开时使用的选择器（设备有序列号时固定为该序列号）
+    selector: GsUsbDeviceSelector,
+    /// 最近一次成功配置的波特率（`None` 表示尚未配置）
+    bitrate: Option<u32>,
+    /// 等待设备重新出现的时长（`None` 表示禁用）
+    timeout: Option<Duration>,
+    reconnect_callback: Option<Arc<dyn Fn() + Send + Sync>>,
+    stall_count_callback: Option<Arc<dyn Fn() + Send + Sync>>,
+    reopen_count: u64,
+    /// 测试用：替代真实的 USB 重新枚举
+    #[cfg(test)]
+    test_reopen: Option<fn() -> Result<(), CanError>>,
+}
+
+impl AutoReopen {
+    fn new(selector: &GsUsbDeviceSelector, serial_number: Option<&str>) -> Self {
+        let selector = match serial_number {
+            Some(sn) => GsUsbDeviceSelector::by_serial(sn),
+            None => selector.clone(),
+        };
+        Self {
+            selector,
+            ..Self::default()
+        }
+    }
 }
 
 impl GsUsbCanAdapter {
-    /// Maximum RX queue size to prevent unbounded memory growth
-    /// When exceeded, oldest frames are dropped
+    /// Default RX queue capacity to prevent unbounded memory growth
     const MAX_QUEUE_SIZE: usize = 256;
 
     /// 创建新的适配器（扫描并打开设备）
@@ -140,6 +268,7 @@ impl GsUsbCanAdapter {
             };
             CanError::Device(CanDeviceError::new(kind, message))
         })?;
+        let reopen = AutoReopen::new(&selector, device.serial_number());
         Ok(Self {
             device, // 保持为 GsUsbDevice，在 split 时包裹为 Arc
             started: false,
@@ -153,6 +282,14 @@ impl GsUsbCanAdapter {
             rx_batch_frames: Vec::with_capacity(GS_USB_BATCH_FRAME_CAPACITY),
             realtime_mode: false, // 默认非实时模式
             consecutive_write_timeouts: 0,
+            timestamp_extender: HwTimestampExtender::default(),
+            rx_queue_capacity: Self::MAX_QUEUE_SIZE,
+            rx_overflow_policy: RxQueueOverflowPolicy::default(),
+            rx_overflow_count: 0,
+            reopen,
+            capabilities: None,
+            bit_timing: None,
+            tx_echo: Arc::default(),
         })
     }
 
@@ -197,19 +334,79 @@ impl GsUsbCanAdapter {
         self.realtime_mode
     }
 
+    /// 设置接收队列容量（至少为 1，默认 256）
+    pub fn set_rx_queue_capacity(&mut self, capacity: usize) {
+        self.rx_queue_capacity = capacity.max(1);
+    }
+
+    /// 获取接收队列容量
+    pub fn rx_queue_capacity(&self) -> usize {
+        self.rx_queue_capacity
+    }
+
+    /// 设置接收队列溢出策略（默认 `DropOldest`）
+    pub fn set_rx_overflow_policy(&mut self, policy: RxQueueOverflowPolicy) {
+        self.rx_overflow_policy = policy;
+    }
+
+    /// 获取接收队列溢出策略
+    pub fn rx_overflow_policy(&self) -> RxQueueOverflowPolicy {
+        self.rx_overflow_policy
+    }
+
+    /// 接收队列溢出次数（`Block` 策略下为超出容量的入队次数）
+    pub fn rx_overflow_count(&self) -> u64 {
+        self.rx_overflow_count
+    }
+
     /// Push frame to RX queue with bounded size check
     ///
-    /// If the queue is full, drops the oldest frame to make room.
-    /// This prevents unbounded memory growth if consumer stops consuming.
-    fn push_to_rx_queue(&mut self, frame: ReceivedFrame) {
-        if self.rx_queue.len() >= Self::MAX_QUEUE_SIZE {
-            warn!(
-                "RX queue full ({} frames), dropping oldest frame",
-                Self::MAX_QUEUE_SIZE
-            );
-            self.rx_queue.pop_front();
+    /// When the queue is full the configured [`RxQueueOverflowPolicy`] decides
+    /// which frame is kept. Returns `true` if the `Error` policy rejected it.
+    fn push_to_rx_queue(&mut self, frame: ReceivedFrame) -> bool {
+        if self.rx_queue.len() < self.rx_queue_capacity {
+            self.rx_queue.push_back(frame);
+            return false;
+        }
+
+        self.rx_overflow_count = self.rx_overflow_count.saturating_add(1);
+        match self.rx_overflow_policy {
+            RxQueueOverflowPolicy::Block => {
+                self.rx_queue.push_back(frame);
+                false
+            },
+            RxQueueOverflowPolicy::DropOldest => {
+                warn!(
+                    "RX queue full ({} frames), dropping oldest frame",
+                    self.rx_queue_capacity
+                );
+                self.rx_queue.pop_front();
+                self.rx_queue.push_back(frame);
+                false
+            },
+            RxQueueOverflowPolicy::DropNewest => {
+                warn!(
+                    "RX queue full ({} frames), dropping newest frame",
+                    self.rx_queue_capacity
+                );
+                false
+            },
+            RxQueueOverflowPolicy::Error => true,
+        }
+    }
+
+    /// 将设备 32 位时间戳扩展为单调递增的 64 位时间戳（仅硬件时间戳模式）
+    fn extend_frame_timestamp(&mut self, frame: PiperFrame) -> PiperFrame {
+        if self.device.hw_timestamp_enabled() {
+            frame.with_timestamp_us(self.timestamp_extender.extend(frame.timestamp_us() as u32))
+        } else {
+            frame
         }
-        self.rx_queue.push_back(frame);
+    }
+
+    /// 硬件时间戳回绕次数（诊断用）
+    pub fn timestamp_wrap_count(&self) -> u64 {
+        self.timestamp_extender.wrap_count()
     }
 
     fn timestamp_provenance(&self) -> TimestampProvenance {
@@ -241,6 +438,8 @@ impl GsUsbCanAdapter {
             device,
             rx_timeout,
             mode,
+            timestamp_extender,
+            tx_echo,
             ..
         } = self;
         let device_arc = Arc::new(device);
@@ -251,8 +450,10 @@ impl GsUsbCanAdapter {
                 rx_timeout,
                 mode,
                 device_arc.hw_timestamp_enabled(),
-            ),
-            GsUsbTxAdapter::new(device_arc),
+            )
+            .with_timestamp_extender(timestamp_extender)
+            .with_tx_echo_tracker(tx_echo.clone()),
+            GsUsbTxAdapter::new(device_arc).with_tx_echo_tracker(tx_echo),
         ))
     }
 
@@ -260,7 +461,12 @@ impl GsUsbCanAdapter {
     ///
     /// - 会应用与 `receive()` 相同的 Echo 过滤与 overflow 检测逻辑
     /// - 返回的 Vec 可能为空（例如读到的都是 Echo 且被过滤，或读到空包）
+    /// - 启用自动重开时，设备拔出后会等待其重新出现并重试一次
     pub fn receive_batch_frames(&mut self) -> Result<Vec<ReceivedFrame>, CanError> {
+        self.with_auto_reopen(Self::receive_batch_frames_once)
+    }
+
+    fn receive_batch_frames_once(&mut self) -> Result<Vec<ReceivedFrame>, CanError> {
         if !self.started {
             return Err(CanError::NotStarted);
         }
@@ -283,32 +489,11 @@ impl GsUsbCanAdapter {
             Err(crate::gs_usb::error::GsUsbError::ReadTimeout) => {
                 return Err(CanError::Timeout);
             },
-            Err(e) => {
-                let kind = match e {
-                    crate::gs_usb::error::GsUsbError::Usb(rusb::Error::NoDevice) => {
-                        CanDeviceErrorKind::NoDevice
-                    },
-                    crate::gs_usb::error::GsUsbError::Usb(rusb::Error::Access) => {
-                        CanDeviceErrorKind::AccessDenied
-                    },
-                    crate::gs_usb::error::GsUsbError::Usb(rusb::Error::NotFound) => {
-                        CanDeviceErrorKind::NotFound
-                    },
-                    crate::gs_usb::error::GsUsbError::InvalidFrame(_) => {
-                        CanDeviceErrorKind::InvalidFrame
-                    },
-                    crate::gs_usb::error::GsUsbError::InvalidResponse { .. } => {
-                        CanDeviceErrorKind::InvalidResponse
-                    },
-                    _ => CanDeviceErrorKind::Backend,
-                };
-                return Err(CanError::Device(CanDeviceError::new(
-                    kind,
-                    format!("USB receive failed: {}", e),
-                )));
-            },
+            Err(e) => return Err(usb_receive_error(e)),
         }
 
+        self.track_tx_echoes();
+
         if self.rx_batch_frames.is_empty() {
             return Ok(Vec::new());
         }
@@ -317,7 +502,10 @@ impl GsUsbCanAdapter {
         self.rx_batch_frames.clear();
         let provenance = self.timestamp_provenance();
         out.map(|frames| {
-            frames.into_iter().map(|frame| ReceivedFrame::new(frame, provenance)).collect()
+            frames
+                .into_iter()
+                .map(|frame| ReceivedFrame::new(self.extend_frame_timestamp(frame), provenance))
+                .collect()
         })
     }
 
@@ -343,7 +531,152 @@ impl GsUsbCanAdapter {
     where
         F: Fn() + Send + Sync + 'static,
     {
-        self.device.set_stall_count_callback(callback);
+        let callback: Arc<dyn Fn() + Send + Sync> = Arc::new(callback);
+        self.reopen.stall_count_callback = Some(callback.clone());
+        self.device.set_stall_count_callback(move || callback());
+    }
+
+    /// 启用/禁用设备拔出后的自动重开
+    ///
+    /// 启用后，`send()`/`send_confirmed()`/`receive()`/`receive_batch_frames()` 遇到
+    /// `CanDeviceErrorKind::NoDevice` 时，会在 `timeout` 内轮询同一设备
+    /// （按序列号匹配，无序列号时使用原选择器）。设备重新出现后按上次的
+    /// 波特率与模式重新配置：接收操作会重试一次，超时则返回 `NoDevice` 错误；
+    /// 发送操作不会重放原帧（重开后它可能已过期），始终返回原 `NoDevice` 错误，
+    /// 由调用方决定是否重发。
+    ///
+    /// 注意：重开期间调用会阻塞最多 `timeout`；仅对已 `configure` 的适配器生效。
+    pub fn set_auto_reopen(&mut self, timeout: Option<Duration>) {
+        self.reopen.timeout = timeout;
+    }
+
+    /// 设置设备重开成功后的回调
+    pub fn set_reconnect_callback<F>(&mut self, callback: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.reopen.reconnect_callback = Some(Arc::new(callback));
+    }
+
+    /// 设备自动重开成功的次数
+    pub fn reopen_count(&self) -> u64 {
+        self.reopen.reopen_count
+    }
+
+    /// 设备拔出且自动重开可用（已启用并 configure 过）
+    fn should_auto_reopen(&self, error: &CanDeviceError) -> bool {
+        error.kind == CanDeviceErrorKind::NoDevice
+            && self.reopen.timeout.is_some()
+            && self.reopen.bitrate.is_some()
+    }
+
+    /// 执行可安全重试的操作（接收、重置）；设备拔出且启用自动重开时，重开后重试一次
+    fn with_auto_reopen<T>(
+        &mut self,
+        mut op: impl FnMut(&mut Self) -> Result<T, CanError>,
+    ) -> Result<T, CanError> {
+        match op(self) {
+            Err(CanError::Device(error)) if self.should_auto_reopen(&error) => {
+                warn!(
+                    "GS-USB device disconnected ({}), waiting for it to reappear",
+                    error.message
+                );
+                self.reopen_device()?;
+                op(self)
+            },
+            result => result,
+        }
+    }
+
+    /// 执行发送类操作；设备拔出且启用自动重开时只重开设备，不重发
+    ///
+    /// 重开可能阻塞到配置的 `timeout`，届时原帧（运动或配置命令）可能早已过期，
+    /// 自动重放会在数秒后驱动机械臂。因此无论重开是否成功都返回原 `NoDevice` 错误，
+    /// 由调用方决定是否重发。
+    fn send_with_auto_reopen(
+        &mut self,
+        op: impl FnOnce(&mut Self) -> Result<(), CanError>,
+    ) -> Result<(), CanError> {
+        match op(self) {
+            Err(CanError::Device(error)) if self.should_auto_reopen(&error) => {
+                warn!(
+                    "GS-USB device disconnected during send ({}), reopening without resending",
+                    error.message
+                );
+                if let Err(reopen_error) = self.reopen_device() {
+                    warn!("GS-USB device reopen failed: {}", reopen_error);
+                }
+                Err(CanError::Device(error))
+            },
+            result => result,
+        }
+    }
+
+    /// 停止设备后按上次配置重新启动（见 `CanAdapter::reset`）
+    fn restart(&mut self) -> Result<(), CanError> {
+        let Some(bitrate) = self.reopen.bitrate else {
+            return Err(CanError::NotStarted);
+        };
+        let _ = self.device.stop();
+        self.started = false;
+        self.consecutive_write_timeouts = 0;
+        // 设备时间戳计数器随重新启动归零
+        self.timestamp_extender = HwTimestampExtender::default();
+        self.configure_with_mode(bitrate, self.mode)
+    }
+
+    /// 轮询等待设备重新出现，重新打开并按上次配置启动
+    fn reopen_device(&mut self) -> Result<(), CanError> {
+        const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+        let (Some(timeout), Some(bitrate)) = (self.reopen.timeout, self.reopen.bitrate) else {
+            return Err(CanError::Device(CanDeviceError::new(
+                CanDeviceErrorKind::NoDevice,
+                "GS-USB device disconnected",
+            )));
+        };
+        self.started = false;
+        #[cfg(test)]
+        if let Some(reopen) = self.reopen.test_reopen {
+            return reopen().map(|()| self.reopen.reopen_count += 1);
+        }
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            match GsUsbDevice::open(&self.reopen.selector) {
+                Ok(device) => {
+                    let write_timeout = self.device.write_timeout();
+                    self.device = device;
+                    self.device.set_write_timeout(write_timeout);
+                    if let Some(callback) = self.reopen.stall_count_callback.clone() {
+                        self.device.set_stall_count_callback(move || callback());
+                    }
+                    // 设备时间戳计数器随重新启动归零
+                    self.timestamp_extender = HwTimestampExtender::default();
+                    match self.configure_with_mode(bitrate, self.mode) {
+                        Ok(()) => {
+                            self.reopen.reopen_count += 1;
+                            tracing::info!("GS-USB device reopened at {} bps", bitrate);
+                            if let Some(callback) = &self.reopen.reconnect_callback {
+                                callback();
+                            }
+                            return Ok(());
+                        },
+                        Err(e) => warn!("Failed to restart reopened GS-USB device: {}", e),
+                    }
+                },
+                Err(e) => trace!("GS-USB device not available yet: {}", e),
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(CanError::Device(CanDeviceError::new(
+                    CanDeviceErrorKind::NoDevice,
+                    format!("GS-USB device did not reappear within {:?}", timeout),
+                )));
+            }
+            std::thread::sleep(POLL_INTERVAL.min(deadline - now));
+        }
     }
 
     /// 内部方法：统一配置逻辑
@@ -368,9 +701,29 @@ impl GsUsbCanAdapter {
         // 1. 设置波特率（在 start() 之前）
         // 注意：start() 内部会 reset，但 reset 不会清除 bitrate 设置
         // 因为 bitrate 是通过控制请求设置的，是持久化配置
-        self.device.set_bitrate(bitrate).map_err(|e| {
+        // 自定义位定时仅在其实现的波特率与请求一致时生效
+        let result = match self.bit_timing {
+            Some(timing) => {
+                let clock = self.device_clock();
+                if timing.bitrate(clock) != bitrate {
+                    return Err(CanError::Device(CanDeviceError::new(
+                        CanDeviceErrorKind::UnsupportedConfig,
+                        format!(
+                            "Custom bit timing realizes {} bps on {} Hz clock, requested {} bps",
+                            timing.bitrate(clock),
+                            clock,
+                            bitrate
+                        ),
+                    )));
+                }
+                self.device.set_bit_timing(&timing)
+            },
+            None => self.device.set_bitrate(bitrate),
+        };
+        result.map_err(|e| {
             let kind = match e {
-                crate::gs_usb::error::GsUsbError::UnsupportedBitrate { .. } => {
+                crate::gs_usb::error::GsUsbError::UnsupportedBitrate { .. }
+                | crate::gs_usb::error::GsUsbError::UnsupportedBitTiming(_) => {
                     CanDeviceErrorKind::UnsupportedConfig
                 },
                 _ => CanDeviceErrorKind::Backend,
@@ -393,6 +746,20 @@ impl GsUsbCanAdapter {
         self.started = true;
         self.mode = mode;
         self.rx_queue.clear(); // 启动时清空队列
+        self.reopen.bitrate = Some(bitrate);
+        let device_info = match self.device.device_config() {
+            Ok(info) => Some(info),
+            Err(e) => {
+                trace!("GS-USB device config query failed: {}", e);
+                None
+            },
+        };
+        self.capabilities = Some(GsUsbCapabilities {
+            feature: start_result.capability.feature,
+            fclk_can: start_result.capability.fclk_can,
+            effective_flags: start_result.effective_flags,
+            device_info,
+        });
 
         // 构建模式名称（支持组合模式，如 LOOP_BACK|HW_TIMESTAMP）
         let mode_name = {
@@ -403,6 +770,9 @@ impl GsUsbCanAdapter {
             if (mode & GS_CAN_MODE_LISTEN_ONLY) != 0 {
                 parts.push("LISTEN_ONLY");
             }
+            if (mode & GS_CAN_MODE_ONE_SHOT) != 0 {
+                parts.push("ONE_SHOT");
+            }
             if (mode & GS_CAN_MODE_HW_TIMESTAMP) != 0 {
                 parts.push("HW_TIMESTAMP");
             }
@@ -450,6 +820,165 @@ impl GsUsbCanAdapter {
         self.configure_with_mode(bitrate, GS_CAN_MODE_LISTEN_ONLY | GS_CAN_MODE_HW_TIMESTAMP)
     }
 
+    /// 设置自定义位定时（覆盖推荐表，例如调整长总线的采样点）
+    ///
+    /// 参数会按设备能力（BT_CONST）校验，不满足时返回 `UnsupportedConfig`。
+    /// 之后的 `configure*(bitrate)` 要求 `bitrate` 与该位定时实现的波特率一致；
+    /// 设备已启动时会立即以新位定时重新启动。
+    pub fn set_bit_timing(&mut self, timing: DeviceBitTiming) -> Result<(), CanError> {
+        let capability = self.device_capability_or_default();
+        timing.validate(&capability).map_err(|reason| {
+            CanError::Device(CanDeviceError::new(
+                CanDeviceErrorKind::UnsupportedConfig,
+                format!("Unsupported bit timing: {}", reason),
+            ))
+        })?;
+        self.bit_timing = Some(timing);
+        if self.started {
+            self.configure_with_mode(timing.bitrate(capability.fclk_can), self.mode)?;
+        }
+        Ok(())
+    }
+
+    /// 按 (波特率, 采样点) 在设备时钟上计算并设置位定时
+    ///
+    /// `sample_point` 为比例值（如 `0.75`）。无法在设备时钟上实现时返回 `UnsupportedConfig`。
+    pub fn set_sample_point(
+        &mut self,
+        bitrate: u32,
+        sample_point: f32,
+    ) -> Result<DeviceBitTiming, CanError> {
+        let capability = self.device_capability_or_default();
+        let timing = DeviceBitTiming::calculate_for(bitrate, sample_point, &capability)
+            .ok_or_else(|| {
+                CanError::Device(CanDeviceError::new(
+                    CanDeviceErrorKind::UnsupportedConfig,
+                    format!(
+                        "Cannot realize {} bps at sample point {} on {} Hz clock",
+                        bitrate, sample_point, capability.fclk_can
+                    ),
+                ))
+            })?;
+        self.set_bit_timing(timing)?;
+        Ok(timing)
+    }
+
+    /// 清除自定义位定时，恢复推荐表（下次 `configure*` 生效）
+    pub fn clear_bit_timing(&mut self) {
+        self.bit_timing = None;
+    }
+
+    /// 当前自定义位定时
+    pub fn bit_timing(&self) -> Option<DeviceBitTiming> {
+        self.bit_timing
+    }
+
+    /// 设备能力；无法查询时退回 48MHz 常见约束（与 `set_bitrate` 一致）
+    fn device_capability_or_default(&mut self) -> DeviceCapability {
+        self.device.device_capability().unwrap_or_else(|e| {
+            trace!(
+                "Failed to get device capability, using default clock (48MHz): {}",
+                e
+            );
+            DeviceCapability::with_clock(48_000_000)
+        })
+    }
+
+    fn device_clock(&mut self) -> u32 {
+        self.device_capability_or_default().fclk_can
+    }
+
+    /// 配置并启动设备（One-Shot 模式，默认启用硬件时间戳）
+    ///
+    /// One-Shot 模式下，未被 ACK 的帧不会由控制器自动重发，
+    /// 适合在未知/新搭建的总线上探测，避免单个无应答帧引发持续的错误帧。
+    ///
+    /// 如果设备不支持 One-Shot，会停止设备并返回 `UnsupportedConfig`，
+    /// 不会静默退化为会自动重发的普通模式。
+    pub fn configure_one_shot(&mut self, bitrate: u32) -> Result<(), CanError> {
+        self.configure_with_mode(
+            bitrate,
+            GS_CAN_MODE_NORMAL | GS_CAN_MODE_ONE_SHOT | GS_CAN_MODE_HW_TIMESTAMP,
+        )?;
+        self.ensure_one_shot_effective()
+    }
+
+    /// 切换 One-Shot 发送（按上次 `configure*` 的波特率与模式重新启动设备）
+    ///
+    /// GS-USB 协议没有逐帧的 One-Shot 标志，One-Shot 是控制器级模式；
+    /// 需要只对部分帧禁用重发时，在发送前后调用此方法切换。
+    /// 重新启动会清空接收队列。
+    pub fn set_one_shot(&mut self, enabled: bool) -> Result<(), CanError> {
+        let Some(bitrate) = self.reopen.bitrate else {
+            return Err(CanError::NotStarted);
+        };
+        let mode = if enabled {
+            self.mode | GS_CAN_MODE_ONE_SHOT
+        } else {
+            self.mode & !GS_CAN_MODE_ONE_SHOT
+        };
+        if mode == self.mode {
+            return Ok(());
+        }
+        self.configure_with_mode(bitrate, mode)?;
+        if enabled {
+            self.ensure_one_shot_effective()?;
+        }
+        Ok(())
+    }
+
+    /// 当前是否处于 One-Shot 模式（以设备实际生效的标志为准）
+    pub fn is_one_shot(&self) -> bool {
+        self.capabilities
+            .is_some_and(|caps| caps.effective_flags & GS_CAN_MODE_ONE_SHOT != 0)
+    }
+
+    /// 发送帧并等待设备回传的 TX Echo（确认帧已进入控制器 TX FIFO）
+    ///
+    /// 为帧分配独立的 `echo_id`，在 `timeout` 内读取 USB 直到看到匹配的 Echo。
+    /// 期间读到的数据帧照常进入接收队列，不会丢失。用于持久化配置写入等
+    /// 低频、可靠性优先的场景；热路径请继续使用 fire-and-forget 的 `send`。
+    ///
+    /// # 错误
+    /// - `CanError::Timeout`: `timeout` 内未看到匹配的 Echo
+    /// - 其余错误同 `send` / `receive`
+    ///
+    /// 看到匹配的 Echo 后帧已确认发出，同一 USB 包导致的接收队列溢出不再报
+    /// `BufferOverflow`（即使策略为 [`RxQueueOverflowPolicy::Error`]），只计入 `rx_overflow_count`。
+    pub fn send_confirmed(&mut self, frame: PiperFrame, timeout: Duration) -> Result<(), CanError> {
+        self.send_with_auto_reopen(|adapter| adapter.send_confirmed_once(frame, timeout))
+    }
+
+    /// TX→Echo 延迟与丢失统计
+    ///
+    /// 每次发送分配轮转的 `echo_id`，接收路径读到对应 Echo 时计算延迟（见 [`echo`] 模块）。
+    /// split 后的 RX / TX 适配器共享同一张表，RX 侧经 [`RxAdapter::tx_echo_stats`](crate::RxAdapter::tx_echo_stats)
+    /// 暴露给驱动层的 `PiperMetrics`。
+    pub fn tx_echo_stats(&self) -> TxEchoStats {
+        self.tx_echo.stats()
+    }
+
+    /// One-Shot 未生效时停止设备并报错
+    fn ensure_one_shot_effective(&mut self) -> Result<(), CanError> {
+        if self.is_one_shot() {
+            return Ok(());
+        }
+        let _ = self.device.stop();
+        self.started = false;
+        Err(CanError::Device(CanDeviceError::new(
+            CanDeviceErrorKind::UnsupportedConfig,
+            "GS-USB device does not support one-shot mode",
+        )))
+    }
+
+    /// 获取设备能力（支持的模式、时钟频率、固件版本）
+    ///
+    /// 仅在 `configure*` 成功后可用；未配置时返回 `None`。
+    /// 可用 [`GsUsbCapabilities::hw_timestamp_enabled`] 在依赖 `timestamp_us` 前确认硬件时间戳是否生效。
+    pub fn capabilities(&self) -> Option<GsUsbCapabilities> {
+        self.capabilities
+    }
+
     pub fn backend_capability(&self) -> BackendCapability {
         if self.device.hw_timestamp_enabled() {
             BackendCapability::SoftRealtime
@@ -459,6 +988,30 @@ impl GsUsbCanAdapter {
     }
 }
 
+/// 将 USB 批量读取错误映射为 `CanError`
+fn usb_receive_error(e: crate::gs_usb::error::GsUsbError) -> CanError {
+    let kind = match e {
+        crate::gs_usb::error::GsUsbError::Usb(rusb::Error::NoDevice) => {
+            CanDeviceErrorKind::NoDevice
+        },
+        crate::gs_usb::error::GsUsbError::Usb(rusb::Error::Access) => {
+            CanDeviceErrorKind::AccessDenied
+        },
+        crate::gs_usb::error::GsUsbError::Usb(rusb::Error::NotFound) => {
+            CanDeviceErrorKind::NotFound
+        },
+        crate::gs_usb::error::GsUsbError::InvalidFrame(_) => CanDeviceErrorKind::InvalidFrame,
+        crate::gs_usb::error::GsUsbError::InvalidResponse { .. } => {
+            CanDeviceErrorKind::InvalidResponse
+        },
+        _ => CanDeviceErrorKind::Backend,
+    };
+    CanError::Device(CanDeviceError::new(
+        kind,
+        format!("USB receive failed: {}", e),
+    ))
+}
+
 impl SplittableAdapter for GsUsbCanAdapter {
     type RxAdapter = GsUsbRxAdapter;
     type TxAdapter = GsUsbTxAdapter;
@@ -472,21 +1025,24 @@ impl SplittableAdapter for GsUsbCanAdapter {
     }
 }
 
-impl CanAdapter for GsUsbCanAdapter {
+impl GsUsbCanAdapter {
     /// 发送帧（Fire-and-Forget）
-    fn send(&mut self, frame: PiperFrame) -> Result<(), CanError> {
+    fn send_once(&mut self, frame: PiperFrame) -> Result<(), CanError> {
+        self.send_tracked(frame).map(|_| ())
+    }
+
+    /// 分配跟踪用 `echo_id` 并发送帧（不等待 Echo），返回分配的 `echo_id`
+    fn send_tracked(&mut self, frame: PiperFrame) -> Result<u32, CanError> {
         if !self.started {
             return Err(CanError::NotStarted);
         }
 
-        let can_id = match frame.id() {
-            CanId::Standard(id) => id.raw() as u32,
-            CanId::Extended(id) => id.raw() | CAN_EFF_FLAG,
-        };
+        let can_id = tx_can_id(&frame);
+        let echo_id = self.tx_echo.allocate(can_id);
 
         // 1. 转换 PiperFrame -> GsUsbFrame
         let gs_frame = GsUsbFrame {
-            echo_id: GS_USB_ECHO_ID,
+            echo_id,
             can_id,
             can_dlc: frame.dlc(),
             channel: 0,
@@ -497,7 +1053,11 @@ impl CanAdapter for GsUsbCanAdapter {
         };
 
         // 2. 发送 USB Bulk OUT（不等待 Echo）
-        match self.device.send_raw(&gs_frame) {
+        let result = self.device.send_raw(&gs_frame);
+        if result.is_err() {
+            self.tx_echo.cancel(echo_id);
+        }
+        match result {
             Ok(_) => {
                 // 发送成功，重置连续超时计数
                 self.consecutive_write_timeouts = 0;
@@ -549,8 +1109,15 @@ impl CanAdapter for GsUsbCanAdapter {
             },
         }
 
-        // Hot path: removed trace! call (TX can be high frequency)
-        Ok(())
+        crate::frame_trace::tx(self.device.trace_interface(), &frame);
+        Ok(echo_id)
+    }
+
+    /// 将刚读到的 USB 包中的 TX Echo 计入在途表
+    fn track_tx_echoes(&self) {
+        for raw in self.rx_batch_frames.iter().filter(|raw| raw.is_tx_echo()) {
+            self.tx_echo.complete(raw.echo_id, raw.can_id);
+        }
     }
 
     /// 接收帧（带缓冲的批量处理）
@@ -562,14 +1129,14 @@ impl CanAdapter for GsUsbCanAdapter {
     /// 1. 使用内部队列 (`rx_queue`) 缓存从 USB 包中解析出的所有帧
     /// 2. 优先从队列中返回帧（如果队列非空）
     /// 3. 队列为空时，从 USB 读取一个包，解析出所有帧并放入队列
-    fn receive(&mut self) -> Result<ReceivedFrame, CanError> {
+    fn receive_once(&mut self) -> Result<ReceivedFrame, CanError> {
         if !self.started {
             return Err(CanError::NotStarted);
         }
 
         // 1. 优先从队列中取（如果有上次读剩下的）
         if let Some(frame) = self.rx_queue.pop_front() {
-            // Hot path: removed trace! call (queue return is 200Hz+)
+            crate::frame_trace::rx(self.device.trace_interface(), &frame.frame);
             return Ok(frame);
         }
         // 2. 队列为空，从 USB 读取一批数据
@@ -585,32 +1152,11 @@ impl CanAdapter for GsUsbCanAdapter {
                 Err(crate::gs_usb::error::GsUsbError::ReadTimeout) => {
                     return Err(CanError::Timeout);
                 },
-                Err(e) => {
-                    let kind = match e {
-                        crate::gs_usb::error::GsUsbError::Usb(rusb::Error::NoDevice) => {
-                            CanDeviceErrorKind::NoDevice
-                        },
-                        crate::gs_usb::error::GsUsbError::Usb(rusb::Error::Access) => {
-                            CanDeviceErrorKind::AccessDenied
-                        },
-                        crate::gs_usb::error::GsUsbError::Usb(rusb::Error::NotFound) => {
-                            CanDeviceErrorKind::NotFound
-                        },
-                        crate::gs_usb::error::GsUsbError::InvalidFrame(_) => {
-                            CanDeviceErrorKind::InvalidFrame
-                        },
-                        crate::gs_usb::error::GsUsbError::InvalidResponse { .. } => {
-                            CanDeviceErrorKind::InvalidResponse
-                        },
-                        _ => CanDeviceErrorKind::Backend,
-                    };
-                    return Err(CanError::Device(CanDeviceError::new(
-                        kind,
-                        format!("USB receive failed: {}", e),
-                    )));
-                },
+                Err(e) => return Err(usb_receive_error(e)),
             }
 
+            self.track_tx_echoes();
+
             // 如果读取成功但没有帧（可能是空包），继续读下一个包
             // 注意：空包是正常情况，USB 硬件可能发送空的批量传输包
             // 超时时间短（2ms），影响不大，继续读取即可
@@ -622,20 +1168,100 @@ impl CanAdapter for GsUsbCanAdapter {
             self.rx_batch_frames.clear();
             let parsed = parsed?;
             let provenance = self.timestamp_provenance();
+            let mut overflowed = false;
             for frame in parsed {
-                self.push_to_rx_queue(ReceivedFrame::new(frame, provenance));
+                let frame = self.extend_frame_timestamp(frame);
+                overflowed |= self.push_to_rx_queue(ReceivedFrame::new(frame, provenance));
+            }
+            if overflowed {
+                return Err(CanError::BufferOverflow);
             }
 
             // 4. 如果队列里有东西了，返回第一个；否则继续循环读 USB
             // 注意：如果这批数据都被过滤掉了（例如全是 Echo），循环继续
             if let Some(frame) = self.rx_queue.pop_front() {
-                // Hot path: removed trace! call (200Hz+)
+                crate::frame_trace::rx(self.device.trace_interface(), &frame.frame);
                 return Ok(frame);
             }
             // 如果这批数据都被过滤掉了，继续读下一个 USB 包
         }
     }
 
+    /// 发送帧并等待对应 `echo_id` 的 TX Echo（见 [`Self::send_confirmed`]）
+    fn send_confirmed_once(
+        &mut self,
+        frame: PiperFrame,
+        timeout: Duration,
+    ) -> Result<(), CanError> {
+        if !self.started {
+            return Err(CanError::NotStarted);
+        }
+
+        let deadline = Instant::now() + timeout;
+        let echo_id = self.send_tracked(frame)?;
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(CanError::Timeout);
+            }
+            match self.device.receive_batch_into(
+                remaining.min(self.rx_timeout),
+                &mut self.rx_usb_buf,
+                &mut self.rx_batch_frames,
+            ) {
+                Ok(()) => {},
+                Err(crate::gs_usb::error::GsUsbError::ReadTimeout) => continue,
+                Err(e) => return Err(usb_receive_error(e)),
+            }
+
+            self.track_tx_echoes();
+
+            let confirmed = self.rx_batch_frames.iter().any(|raw| raw.echo_id == echo_id);
+
+            // 同一 USB 包中的数据帧照常入队，留给后续 receive()
+            let parsed = parse_gs_usb_batch(&self.rx_batch_frames);
+            self.rx_batch_frames.clear();
+            let parsed = parsed?;
+            let provenance = self.timestamp_provenance();
+            let mut overflowed = false;
+            for frame in parsed {
+                let frame = self.extend_frame_timestamp(frame);
+                overflowed |= self.push_to_rx_queue(ReceivedFrame::new(frame, provenance));
+            }
+
+            // 帧已确认发出：溢出只记入 `rx_overflow_count`，报错会让调用方重发持久化配置
+            if confirmed {
+                if overflowed {
+                    warn!(
+                        "RX queue full ({} frames) while confirming TX echo, dropping newest frames",
+                        self.rx_queue_capacity
+                    );
+                }
+                return Ok(());
+            }
+            if overflowed {
+                return Err(CanError::BufferOverflow);
+            }
+        }
+    }
+}
+
+impl CanAdapter for GsUsbCanAdapter {
+    /// 发送帧（Fire-and-Forget）
+    ///
+    /// 启用自动重开时，设备拔出后会等待其重新出现，但不会重发该帧，仍返回 `NoDevice`。
+    fn send(&mut self, frame: PiperFrame) -> Result<(), CanError> {
+        self.send_with_auto_reopen(|adapter| adapter.send_once(frame))
+    }
+
+    /// 接收帧（带缓冲的批量处理，见 `receive_once`）
+    ///
+    /// 启用自动重开时，设备拔出后会等待其重新出现并重试一次。
+    fn receive(&mut self) -> Result<ReceivedFrame, CanError> {
+        self.with_auto_reopen(Self::receive_once)
+    }
+
     /// 设置接收超时
     fn set_receive_timeout(&mut self, timeout: Duration) {
         // 直接设置 rx_timeout 字段
@@ -686,6 +1312,80 @@ impl CanAdapter for GsUsbCanAdapter {
 
         result
     }
+
+    /// 设置 USB Bulk OUT 写超时
+    ///
+    /// 覆盖 `set_realtime_mode` 的 5ms/1000ms 预设，直到下次调用 `set_realtime_mode`。
+    /// 注意：`Duration::ZERO` 在 libusb 中表示无限等待。
+    fn set_send_timeout(&mut self, timeout: Duration) {
+        self.device.set_write_timeout(timeout);
+        self.consecutive_write_timeouts = 0;
+    }
+
+    /// 停止并按上次的波特率与模式重新启动设备
+    ///
+    /// 清空接收队列与连续写超时计数。设备已拔出时返回 `NoDevice`
+    /// （启用自动重开时会先等待设备重新出现）。
+    fn reset(&mut self) -> Result<(), CanError> {
+        self.with_auto_reopen(Self::restart)
+    }
+
+    /// 丢弃接收队列与设备端已缓冲的帧
+    ///
+    /// 以 1ms 超时反复读取 USB，直到读超时；总线持续有流量时最多读取 50ms，
+    /// 避免在机械臂持续反馈时无法返回。
+    fn drain_rx(&mut self) -> Result<usize, CanError> {
+        const DRAIN_READ_TIMEOUT: Duration = Duration::from_millis(1);
+        const DRAIN_MAX_DURATION: Duration = Duration::from_millis(50);
+
+        if !self.started {
+            return Err(CanError::NotStarted);
+        }
+        let mut drained = self.rx_queue.len();
+        self.rx_queue.clear();
+
+        let old_timeout = self.rx_timeout;
+        self.rx_timeout = DRAIN_READ_TIMEOUT;
+        let deadline = Instant::now() + DRAIN_MAX_DURATION;
+        let result = loop {
+            match self.receive_batch_frames_once() {
+                Ok(frames) => drained += frames.len(),
+                Err(CanError::Timeout) => break Ok(drained),
+                Err(e) => break Err(e),
+            }
+            if Instant::now() >= deadline {
+                break Ok(drained);
+            }
+        };
+        self.rx_timeout = old_timeout;
+        result
+    }
+
+    /// 批量接收：至多一次 USB 读取，返回该包解出的所有帧（最多 `max` 帧）
+    ///
+    /// 超出 `max` 的帧保留在接收队列中，由下一次调用返回。
+    fn receive_batch(
+        &mut self,
+        max: usize,
+        timeout: Duration,
+    ) -> Result<Vec<ReceivedFrame>, CanError> {
+        let mut frames = Vec::new();
+        if max == 0 {
+            return Ok(frames);
+        }
+        match self.receive_timeout(timeout) {
+            Ok(frame) => frames.push(frame),
+            Err(CanError::Timeout) => return Ok(frames),
+            Err(e) => return Err(e),
+        }
+        while frames.len() < max {
+            match self.rx_queue.pop_front() {
+                Some(frame) => frames.push(frame),
+                None => break,
+            }
+        }
+        Ok(frames)
+    }
 }
 
 #[cfg(test)]
@@ -751,6 +1451,14 @@ mod tests {
             rx_batch_frames: Vec::with_capacity(GS_USB_BATCH_FRAME_CAPACITY),
             realtime_mode: false,
             consecutive_write_timeouts: 0,
+            timestamp_extender: HwTimestampExtender::default(),
+            rx_queue_capacity: GsUsbCanAdapter::MAX_QUEUE_SIZE,
+            rx_overflow_policy: RxQueueOverflowPolicy::default(),
+            rx_overflow_count: 0,
+            reopen: AutoReopen::default(),
+            capabilities: None,
+            bit_timing: None,
+            tx_echo: Arc::default(),
         }
     }
 
@@ -767,6 +1475,14 @@ mod tests {
             rx_batch_frames: Vec::with_capacity(GS_USB_BATCH_FRAME_CAPACITY),
             realtime_mode: false,
             consecutive_write_timeouts: 0,
+            timestamp_extender: HwTimestampExtender::default(),
+            rx_queue_capacity: GsUsbCanAdapter::MAX_QUEUE_SIZE,
+            rx_overflow_policy: RxQueueOverflowPolicy::default(),
+            rx_overflow_count: 0,
+            reopen: AutoReopen::default(),
+            capabilities: None,
+            bit_timing: None,
+            tx_echo: Arc::default(),
         };
 
         drop(adapter);
@@ -788,6 +1504,14 @@ mod tests {
             rx_batch_frames: Vec::with_capacity(GS_USB_BATCH_FRAME_CAPACITY),
             realtime_mode: false,
             consecutive_write_timeouts: 0,
+            timestamp_extender: HwTimestampExtender::default(),
+            rx_queue_capacity: GsUsbCanAdapter::MAX_QUEUE_SIZE,
+            rx_overflow_policy: RxQueueOverflowPolicy::default(),
+            rx_overflow_count: 0,
+            reopen: AutoReopen::default(),
+            capabilities: None,
+            bit_timing: None,
+            tx_echo: Arc::default(),
         };
 
         let (rx, tx) = adapter.split().expect("test device should split");
@@ -821,6 +1545,14 @@ mod tests {
             rx_batch_frames: Vec::with_capacity(GS_USB_BATCH_FRAME_CAPACITY),
             realtime_mode: false,
             consecutive_write_timeouts: 0,
+            timestamp_extender: HwTimestampExtender::default(),
+            rx_queue_capacity: GsUsbCanAdapter::MAX_QUEUE_SIZE,
+            rx_overflow_policy: RxQueueOverflowPolicy::default(),
+            rx_overflow_count: 0,
+            reopen: AutoReopen::default(),
+            capabilities: None,
+            bit_timing: None,
+            tx_echo: Arc::default(),
         };
 
         let overflow =
@@ -925,6 +1657,72 @@ mod tests {
         assert_eq!(queued[0].timestamp_provenance, TimestampProvenance::None);
     }
 
+    fn three_frame_adapter(policy: RxQueueOverflowPolicy) -> GsUsbCanAdapter {
+        let (device, harness) = GsUsbDevice::new_test_device(false, false);
+        harness.enqueue_read_packet(pack_packet(
+            &[
+                rx_frame(0x100, 0, 0x10),
+                rx_frame(0x101, 0, 0x11),
+                rx_frame(0x102, 0, 0x12),
+            ],
+            false,
+        ));
+        let mut adapter = started_adapter(device);
+        adapter.set_rx_queue_capacity(2);
+        adapter.set_rx_overflow_policy(policy);
+        adapter
+    }
+
+    fn drain_ids(adapter: &mut GsUsbCanAdapter) -> Vec<u32> {
+        let mut ids = Vec::new();
+        while let Some(received) = adapter.rx_queue.pop_front() {
+            ids.push(received.frame.raw_id());
+        }
+        ids
+    }
+
+    #[test]
+    fn unsplit_rx_queue_overflow_policies() {
+        let mut adapter = three_frame_adapter(RxQueueOverflowPolicy::DropOldest);
+        assert_eq!(adapter.receive().unwrap().frame.raw_id(), 0x101);
+        assert_eq!(drain_ids(&mut adapter), vec![0x102]);
+        assert_eq!(adapter.rx_overflow_count(), 1);
+
+        let mut adapter = three_frame_adapter(RxQueueOverflowPolicy::DropNewest);
+        assert_eq!(adapter.receive().unwrap().frame.raw_id(), 0x100);
+        assert_eq!(drain_ids(&mut adapter), vec![0x101]);
+        assert_eq!(adapter.rx_overflow_count(), 1);
+
+        let mut adapter = three_frame_adapter(RxQueueOverflowPolicy::Block);
+        assert_eq!(adapter.receive().unwrap().frame.raw_id(), 0x100);
+        assert_eq!(drain_ids(&mut adapter), vec![0x101, 0x102]);
+        assert_eq!(adapter.rx_overflow_count(), 1);
+
+        let mut adapter = three_frame_adapter(RxQueueOverflowPolicy::Error);
+        assert!(matches!(adapter.receive(), Err(CanError::BufferOverflow)));
+        assert_eq!(drain_ids(&mut adapter), vec![0x100, 0x101]);
+        assert_eq!(adapter.rx_overflow_count(), 1);
+    }
+
+    #[test]
+    fn unsplit_receive_extends_wrapped_hw_timestamps() {
+        let (mut device, harness) = GsUsbDevice::new_test_device(false, false);
+        device.set_test_hw_timestamp(true);
+        let mut before_wrap = rx_frame(0x100, 0, 0x10);
+        before_wrap.timestamp_us = 0xFFFF_FFF0;
+        let mut after_wrap = rx_frame(0x101, 0, 0x11);
+        after_wrap.timestamp_us = 0x10;
+        harness.enqueue_read_packet(pack_packet(&[before_wrap, after_wrap], true));
+        let mut adapter = started_adapter(device);
+
+        assert_eq!(adapter.receive().unwrap().frame.timestamp_us(), 0xFFFF_FFF0);
+        assert_eq!(
+            adapter.receive().unwrap().frame.timestamp_us(),
+            (1 << 32) | 0x10
+        );
+        assert_eq!(adapter.timestamp_wrap_count(), 1);
+    }
+
     #[test]
     fn unsplit_receive_skips_recoverable_between_valid_frames() {
         let (device, harness) = GsUsbDevice::new_test_device(false, false);
@@ -998,4 +1796,317 @@ mod tests {
         assert!(matches!(adapter.receive(), Err(CanError::Timeout)));
         assert!(matches!(adapter.receive(), Err(CanError::Timeout)));
     }
+
+    #[test]
+    fn auto_reopen_pins_selector_to_opened_serial() {
+        let pinned = AutoReopen::new(&GsUsbDeviceSelector::any(), Some("ABC123"));
+        assert_eq!(pinned.selector.serial_number.as_deref(), Some("ABC123"));
+
+        let original = GsUsbDeviceSelector {
+            serial_number: None,
+            bus_number: Some(1),
+            address: Some(4),
+        };
+        let fallback = AutoReopen::new(&original, None);
+        assert_eq!(fallback.selector.serial_number, None);
+        assert_eq!(fallback.selector.bus_number, Some(1));
+        assert_eq!(fallback.selector.address, Some(4));
+    }
+
+    #[test]
+    fn auto_reopen_requires_enable_and_prior_configure() {
+        let (device, _harness) = GsUsbDevice::new_test_device(false, false);
+        let mut adapter = started_adapter(device);
+        let mut calls = 0;
+        let mut unplugged = |_: &mut GsUsbCanAdapter| -> Result<(), CanError> {
+            calls += 1;
+            Err(CanError::Device(CanDeviceError::new(
+                CanDeviceErrorKind::NoDevice,
+                "unplugged",
+            )))
+        };
+
+        // 未启用：原样返回
+        let err = adapter.with_auto_reopen(&mut unplugged).unwrap_err();
+        assert!(matches!(err, CanError::Device(e) if e.message == "unplugged"));
+
+        // 已启用但从未 configure：没有可恢复的配置，同样原样返回
+        adapter.set_auto_reopen(Some(Duration::from_millis(10)));
+        let err = adapter.with_auto_reopen(&mut unplugged).unwrap_err();
+        assert!(matches!(err, CanError::Device(e) if e.message == "unplugged"));
+
+        assert_eq!(calls, 2);
+        assert_eq!(adapter.reopen_count(), 0);
+    }
+
+    #[test]
+    fn auto_reopen_never_replays_sends() {
+        let (device, _harness) = GsUsbDevice::new_test_device(false, false);
+        let mut adapter = started_adapter(device);
+        adapter.reopen.bitrate = Some(500_000);
+        adapter.reopen.test_reopen = Some(|| Ok(()));
+        adapter.set_auto_reopen(Some(Duration::from_millis(10)));
+        let calls = std::cell::Cell::new(0);
+        // 每次调用先返回 NoDevice，随后的调用成功
+        let unplugged_once = |_: &mut GsUsbCanAdapter| -> Result<(), CanError> {
+            calls.set(calls.get() + 1);
+            if calls.get() % 2 == 1 {
+                Err(CanError::Device(CanDeviceError::new(
+                    CanDeviceErrorKind::NoDevice,
+                    "unplugged",
+                )))
+            } else {
+                Ok(())
+            }
+        };
+
+        // 发送：重开成功后也不重发，返回原错误，由调用方决定是否重发
+        let err = adapter.send_with_auto_reopen(unplugged_once).unwrap_err();
+        assert!(matches!(err, CanError::Device(e) if e.message == "unplugged"));
+        assert_eq!(calls.get(), 1);
+        assert_eq!(adapter.reopen_count(), 1);
+
+        // 接收：重开后重试一次
+        calls.set(0);
+        adapter.with_auto_reopen(unplugged_once).expect("receive should be retried");
+        assert_eq!(calls.get(), 2);
+        assert_eq!(adapter.reopen_count(), 2);
+    }
+
+    #[test]
+    fn capabilities_decode_feature_and_effective_flags() {
+        let caps = GsUsbCapabilities {
+            feature: GS_CAN_MODE_LISTEN_ONLY | GS_CAN_MODE_LOOP_BACK | GS_CAN_MODE_HW_TIMESTAMP,
+            fclk_can: 48_000_000,
+            effective_flags: GS_CAN_MODE_LOOP_BACK,
+            device_info: Some(DeviceInfo {
+                icount: 0,
+                fw_version: 21,
+                hw_version: 10,
+            }),
+        };
+        assert!(caps.supports_listen_only());
+        assert!(caps.supports_loopback());
+        assert!(caps.supports_hw_timestamp());
+        assert!(!caps.supports_one_shot());
+        assert!(!caps.supports_triple_sample());
+        assert!(!caps.supports_fd());
+        // 设备支持但本次未启用
+        assert!(!caps.hw_timestamp_enabled());
+        assert_eq!(caps.firmware_version(), Some(2.1));
+
+        let (device, _harness) = GsUsbDevice::new_test_device(false, false);
+        assert!(started_adapter(device).capabilities().is_none());
+    }
+
+    #[test]
+    fn one_shot_requires_effective_flag_and_prior_configure() {
+        let (device, harness) = GsUsbDevice::new_test_device(true, true);
+        let mut adapter = started_adapter(device);
+        assert!(matches!(
+            adapter.set_one_shot(true),
+            Err(CanError::NotStarted)
+        ));
+        assert!(!adapter.is_one_shot());
+
+        // 设备未接受 ONE_SHOT：停止设备，拒绝以会重发的模式继续运行
+        adapter.capabilities = Some(GsUsbCapabilities {
+            feature: GS_CAN_MODE_HW_TIMESTAMP,
+            fclk_can: 48_000_000,
+            effective_flags: GS_CAN_MODE_HW_TIMESTAMP,
+            device_info: None,
+        });
+        let err = adapter.ensure_one_shot_effective().unwrap_err();
+        assert!(matches!(
+            err,
+            CanError::Device(e) if e.kind == CanDeviceErrorKind::UnsupportedConfig
+        ));
+        assert!(matches!(
+            adapter.send(PiperFrame::new_standard(0x123, [1u8, 2]).unwrap()),
+            Err(CanError::NotStarted)
+        ));
+        assert_eq!(harness.stop_requests(), 1);
+
+        adapter.capabilities = Some(GsUsbCapabilities {
+            effective_flags: GS_CAN_MODE_ONE_SHOT,
+            ..adapter.capabilities.unwrap()
+        });
+        assert!(adapter.is_one_shot());
+    }
+
+    #[test]
+    fn bit_timing_override_must_match_configured_bitrate() {
+        let (device, _harness) = GsUsbDevice::new_test_device(false, true);
+        let mut adapter = started_adapter(device);
+        adapter.started = false;
+
+        // 超出约束的参数被拒绝
+        let err = adapter.set_bit_timing(DeviceBitTiming::new(1, 30, 2, 1, 6)).unwrap_err();
+        assert!(matches!(
+            err,
+            CanError::Device(e) if e.kind == CanDeviceErrorKind::UnsupportedConfig
+        ));
+        assert!(adapter.bit_timing().is_none());
+
+        let timing = adapter.set_sample_point(500_000, 0.75).unwrap();
+        assert_eq!(adapter.bit_timing().map(|t| t.sample_point()), Some(0.75));
+
+        // 500k 的位定时不能用于 1M 配置
+        let err = adapter.configure(1_000_000).unwrap_err();
+        assert!(matches!(
+            err,
+            CanError::Device(e) if e.kind == CanDeviceErrorKind::UnsupportedConfig
+        ));
+        assert_eq!(timing.bitrate(48_000_000), 500_000);
+
+        adapter.clear_bit_timing();
+        assert!(adapter.bit_timing().is_none());
+    }
+
+    #[test]
+    fn unsplit_receive_batch_returns_one_usb_packet() {
+        let (device, harness) = GsUsbDevice::new_test_device(false, false);
+        harness.enqueue_read_packet(pack_packet(
+            &[
+                rx_frame(0x100, 0, 0x10),
+                rx_frame(0x101, 0, 0x11),
+                rx_frame(0x102, 0, 0x12),
+            ],
+            false,
+        ));
+        harness.enqueue_read_packet(pack_packet(&[rx_frame(0x103, 0, 0x13)], false));
+        let mut adapter = started_adapter(device);
+
+        let ids = |frames: Vec<ReceivedFrame>| {
+            frames.iter().map(|received| received.frame.raw_id()).collect::<Vec<_>>()
+        };
+        let timeout = Duration::from_millis(2);
+        assert_eq!(
+            ids(adapter.receive_batch(2, timeout).unwrap()),
+            vec![0x100, 0x101]
+        );
+        // 剩余帧来自队列，不会与下一个 USB 包合并
+        assert_eq!(ids(adapter.receive_batch(8, timeout).unwrap()), vec![0x102]);
+        assert_eq!(ids(adapter.receive_batch(8, timeout).unwrap()), vec![0x103]);
+        assert!(adapter.receive_batch(8, timeout).unwrap().is_empty());
+    }
+
+    #[test]
+    fn send_confirmed_waits_for_matching_echo_and_keeps_rx_frames() {
+        let (mut device, harness) = GsUsbDevice::new_test_device(false, false);
+        device.set_write_timeout(Duration::from_millis(50));
+        let matching_echo = GsUsbFrame {
+            echo_id: 1,
+            ..rx_frame(0x471, 0, 0x01)
+        };
+        harness.enqueue_read_packet(pack_packet(
+            &[rx_frame(0x251, 0, 0x51), recoverable_echo_frame()],
+            false,
+        ));
+        harness.enqueue_read_packet(pack_packet(
+            &[matching_echo, rx_frame(0x252, 0, 0x52)],
+            false,
+        ));
+        let mut adapter = started_adapter(device);
+
+        let frame = PiperFrame::new_standard(0x471, [0x01]).unwrap();
+        adapter.send_confirmed(frame, Duration::from_millis(50)).unwrap();
+
+        assert_eq!(adapter.receive().unwrap().frame.raw_id(), 0x251);
+        assert_eq!(adapter.receive().unwrap().frame.raw_id(), 0x252);
+    }
+
+    #[test]
+    fn send_confirmed_reports_rx_overflow_via_counter_once_echo_matched() {
+        let (mut device, harness) = GsUsbDevice::new_test_device(false, false);
+        device.set_write_timeout(Duration::from_millis(50));
+        let matching_echo = GsUsbFrame {
+            echo_id: 1,
+            ..rx_frame(0x471, 0, 0x01)
+        };
+        harness.enqueue_read_packet(pack_packet(
+            &[
+                rx_frame(0x251, 0, 0x51),
+                matching_echo,
+                rx_frame(0x252, 0, 0x52),
+            ],
+            false,
+        ));
+        let mut adapter = started_adapter(device);
+        adapter.set_rx_queue_capacity(1);
+        adapter.set_rx_overflow_policy(RxQueueOverflowPolicy::Error);
+
+        let frame = PiperFrame::new_standard(0x471, [0x01]).unwrap();
+        adapter.send_confirmed(frame, Duration::from_millis(50)).unwrap();
+
+        assert_eq!(adapter.rx_overflow_count(), 1);
+        assert_eq!(adapter.receive().unwrap().frame.raw_id(), 0x251);
+    }
+
+    #[test]
+    fn send_confirmed_times_out_without_matching_echo() {
+        let (mut device, harness) = GsUsbDevice::new_test_device(false, false);
+        device.set_write_timeout(Duration::from_millis(50));
+        // fire-and-forget 的 Echo（echo_id = 0）不算确认
+        harness.enqueue_read_packet(pack_packet(&[recoverable_echo_frame()], false));
+        let mut adapter = started_adapter(device);
+
+        let frame = PiperFrame::new_standard(0x471, [0x01]).unwrap();
+        let result = adapter.send_confirmed(frame, Duration::from_millis(5));
+        assert!(matches!(result, Err(CanError::Timeout)));
+    }
+
+    #[test]
+    fn unsplit_drain_rx_discards_queue_and_pending_packets() {
+        let (device, harness) = GsUsbDevice::new_test_device(false, false);
+        harness.enqueue_read_packet(pack_packet(
+            &[rx_frame(0x100, 0, 0x10), rx_frame(0x101, 0, 0x11)],
+            false,
+        ));
+        harness.enqueue_read_packet(pack_packet(&[rx_frame(0x102, 0, 0x12)], false));
+        harness.enqueue_read_packet(pack_packet(&[rx_frame(0x103, 0, 0x13)], false));
+        let mut adapter = started_adapter(device);
+
+        // 第一个包中的第二帧留在接收队列里
+        assert_eq!(adapter.receive().unwrap().frame.raw_id(), 0x100);
+        assert_eq!(adapter.drain_rx().unwrap(), 3);
+        assert!(matches!(adapter.receive(), Err(CanError::Timeout)));
+        assert_eq!(adapter.rx_timeout, Duration::from_millis(2));
+    }
+
+    #[test]
+    fn unsplit_reset_restarts_with_previous_configuration() {
+        let (device, harness) = GsUsbDevice::new_test_device(true, true);
+        let mut adapter = started_adapter(device);
+        // 从未 configure：没有可恢复的配置
+        assert!(matches!(adapter.reset(), Err(CanError::NotStarted)));
+
+        adapter.reopen.bitrate = Some(500_000);
+        adapter.consecutive_write_timeouts = 7;
+        adapter.rx_queue.push_back(ReceivedFrame::new(
+            PiperFrame::new_standard(0x100, [1u8]).unwrap(),
+            TimestampProvenance::None,
+        ));
+        // 测试句柄不支持控制 IN，重新启动在查询能力时失败
+        assert!(adapter.reset().is_err());
+        assert_eq!(harness.stop_requests(), 1);
+        assert!(!adapter.started);
+        assert_eq!(adapter.consecutive_write_timeouts, 0);
+    }
+
+    #[test]
+    fn unsplit_set_send_timeout_overrides_realtime_preset() {
+        let (device, _harness) = GsUsbDevice::new_test_device(true, true);
+        let mut adapter = started_adapter(device);
+        adapter.set_realtime_mode(true);
+        adapter.consecutive_write_timeouts = 3;
+
+        adapter.set_send_timeout(Duration::from_millis(12));
+        assert_eq!(adapter.device.write_timeout(), Duration::from_millis(12));
+        assert_eq!(adapter.consecutive_write_timeouts, 0);
+        assert!(adapter.is_realtime_mode());
+
+        adapter.set_realtime_mode(false);
+        assert_eq!(adapter.device.write_timeout(), Duration::from_millis(1000));
+    }
 }
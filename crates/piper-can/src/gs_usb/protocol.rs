@@ -39,6 +39,8 @@ pub const GS_CAN_MODE_TRIPLE_SAMPLE: u32 = 1 << 2;
 pub const GS_CAN_MODE_ONE_SHOT: u32 = 1 << 3;
 /// Hardware timestamp mode
 pub const GS_CAN_MODE_HW_TIMESTAMP: u32 = 1 << 4;
+/// CAN FD mode（仅用于能力探测，当前驱动不会启用）
+pub const GS_CAN_MODE_FD: u32 = 1 << 8;
 
 // ============================================================================
 // GS-USB Mode Values
@@ -162,6 +164,119 @@ impl DeviceBitTiming {
         }
     }
 
+    /// 由 (波特率, 采样点, CAN 时钟) 计算位定时（使用常见 bxCAN 约束）
+    ///
+    /// `sample_point` 为比例值（如 `0.875`）。只接受能精确实现该波特率、
+    /// 且采样点误差不超过 2% 的参数；否则返回 `None`。
+    pub fn calculate(bitrate: u32, sample_point: f32, clock_hz: u32) -> Option<Self> {
+        Self::calculate_for(
+            bitrate,
+            sample_point,
+            &DeviceCapability::with_clock(clock_hz),
+        )
+    }
+
+    /// 由 (波特率, 采样点) 按设备能力（时钟与 tseg/sjw/brp 约束）计算位定时
+    ///
+    /// 在所有可行参数中选择采样点误差最小者；误差相同时优先更小的 brp（更多 tq）。
+    pub fn calculate_for(
+        bitrate: u32,
+        sample_point: f32,
+        capability: &DeviceCapability,
+    ) -> Option<Self> {
+        const MAX_SAMPLE_POINT_ERROR: f32 = 0.02;
+
+        if bitrate == 0 || !(sample_point > 0.0 && sample_point < 1.0) {
+            return None;
+        }
+        let clock = u64::from(capability.fclk_can);
+        let brp_inc = capability.brp_inc.max(1);
+        // prop_seg 固定为 1，因此 tseg1 至少为 2
+        let tseg1_min = capability.tseg1_min.max(2);
+        let mut best: Option<(f32, Self)> = None;
+
+        let mut brp = capability.brp_min.max(1);
+        while brp <= capability.brp_max {
+            let denom = u64::from(brp) * u64::from(bitrate);
+            if clock.is_multiple_of(denom) {
+                let total = (clock / denom) as u32;
+                let ideal_tseg1 = (total as f32 * sample_point).round() as u32;
+                let tseg1 = ideal_tseg1.saturating_sub(1).clamp(tseg1_min, capability.tseg1_max);
+                if let Some(tseg2) = total.checked_sub(1 + tseg1)
+                    && (capability.tseg2_min..=capability.tseg2_max).contains(&tseg2)
+                    && tseg2 > 0
+                {
+                    let timing = Self::new(1, tseg1 - 1, tseg2, 1, brp);
+                    let error = (timing.sample_point() - sample_point).abs();
+                    if best.is_none_or(|(best_error, _)| error < best_error) {
+                        best = Some((error, timing));
+                    }
+                }
+            }
+            brp += brp_inc;
+        }
+
+        best.filter(|(error, _)| *error <= MAX_SAMPLE_POINT_ERROR)
+            .map(|(_, timing)| timing)
+    }
+
+    /// 检查位定时是否满足设备约束，不满足时返回原因
+    pub fn validate(&self, capability: &DeviceCapability) -> Result<(), String> {
+        let tseg1 = self.tseg1();
+        if !(capability.tseg1_min..=capability.tseg1_max).contains(&tseg1) {
+            return Err(format!(
+                "tseg1 (prop_seg + phase_seg1) = {} outside {}..={}",
+                tseg1, capability.tseg1_min, capability.tseg1_max
+            ));
+        }
+        if !(capability.tseg2_min..=capability.tseg2_max).contains(&self.phase_seg2) {
+            return Err(format!(
+                "phase_seg2 = {} outside {}..={}",
+                self.phase_seg2, capability.tseg2_min, capability.tseg2_max
+            ));
+        }
+        if self.sjw == 0 || self.sjw > capability.sjw_max || self.sjw > self.phase_seg2 {
+            return Err(format!(
+                "sjw = {} must be 1..={} and <= phase_seg2",
+                self.sjw, capability.sjw_max
+            ));
+        }
+        let brp_inc = capability.brp_inc.max(1);
+        if !(capability.brp_min..=capability.brp_max).contains(&self.brp)
+            || !(self.brp - capability.brp_min).is_multiple_of(brp_inc)
+        {
+            return Err(format!(
+                "brp = {} outside {}..={} (step {})",
+                self.brp, capability.brp_min, capability.brp_max, brp_inc
+            ));
+        }
+        Ok(())
+    }
+
+    /// prop_seg + phase_seg1
+    pub fn tseg1(&self) -> u32 {
+        self.prop_seg + self.phase_seg1
+    }
+
+    /// 每位的 time quanta 数（含同步段）
+    pub fn total_tq(&self) -> u32 {
+        1 + self.tseg1() + self.phase_seg2
+    }
+
+    /// 采样点（比例值，如 `0.875`）
+    pub fn sample_point(&self) -> f32 {
+        (1 + self.tseg1()) as f32 / self.total_tq() as f32
+    }
+
+    /// 在给定 CAN 时钟下实现的波特率
+    pub fn bitrate(&self, clock_hz: u32) -> u32 {
+        let denom = u64::from(self.brp) * u64::from(self.total_tq());
+        if denom == 0 {
+            return 0;
+        }
+        (u64::from(clock_hz) / denom) as u32
+    }
+
     /// Pack into bytes for USB transfer (20 bytes)
     pub fn pack(&self) -> [u8; 20] {
         let mut buf = [0u8; 20];
@@ -211,6 +326,22 @@ pub struct DeviceCapability {
 }
 
 impl DeviceCapability {
+    /// 常见 bxCAN（STM32）约束下的能力描述，用于无法查询设备时计算位定时
+    pub fn with_clock(fclk_can: u32) -> Self {
+        Self {
+            feature: 0,
+            fclk_can,
+            tseg1_min: 1,
+            tseg1_max: 16,
+            tseg2_min: 1,
+            tseg2_max: 8,
+            sjw_max: 4,
+            brp_min: 1,
+            brp_max: 1024,
+            brp_inc: 1,
+        }
+    }
+
     /// Unpack from BT_CONST response (40 bytes)
     pub fn unpack(data: &[u8]) -> Self {
         Self {
@@ -570,4 +701,46 @@ mod tests {
         assert_eq!(GS_USB_RX_ECHO_ID, 0xFFFF_FFFF);
         assert_ne!(GS_USB_ECHO_ID, GS_USB_RX_ECHO_ID);
     }
+
+    #[test]
+    fn test_bit_timing_calculate_matches_recommended_table() {
+        // 48MHz / 500k / 87.5% 与 set_bitrate 推荐表一致
+        let timing = DeviceBitTiming::calculate(500_000, 0.875, 48_000_000).unwrap();
+        assert_eq!(
+            (
+                timing.prop_seg,
+                timing.phase_seg1,
+                timing.phase_seg2,
+                timing.sjw,
+                timing.brp
+            ),
+            (1, 12, 2, 1, 6)
+        );
+        assert_eq!(timing.bitrate(48_000_000), 500_000);
+
+        let timing = DeviceBitTiming::calculate(500_000, 0.75, 48_000_000).unwrap();
+        assert_eq!(timing.bitrate(48_000_000), 500_000);
+        assert_eq!(timing.sample_point(), 0.75);
+        assert!(timing.validate(&DeviceCapability::with_clock(48_000_000)).is_ok());
+    }
+
+    #[test]
+    fn test_bit_timing_calculate_rejects_unrealizable() {
+        // 无法整除时钟
+        assert!(DeviceBitTiming::calculate(333_333, 0.875, 48_000_000).is_none());
+        // 采样点越界
+        assert!(DeviceBitTiming::calculate(500_000, 1.0, 48_000_000).is_none());
+        // 1M @ 8MHz 最多 8 tq，99% 采样点无法实现
+        assert!(DeviceBitTiming::calculate(1_000_000, 0.99, 8_000_000).is_none());
+    }
+
+    #[test]
+    fn test_bit_timing_validate_against_capability() {
+        let cap = DeviceCapability::with_clock(48_000_000);
+        assert!(DeviceBitTiming::new(1, 20, 2, 1, 6).validate(&cap).is_err());
+        assert!(DeviceBitTiming::new(1, 12, 9, 1, 6).validate(&cap).is_err());
+        assert!(DeviceBitTiming::new(1, 12, 2, 3, 6).validate(&cap).is_err());
+        assert!(DeviceBitTiming::new(1, 12, 2, 1, 0).validate(&cap).is_err());
+        assert!(DeviceBitTiming::new(1, 12, 2, 1, 6).validate(&cap).is_ok());
+    }
 }
@@ -7,11 +7,12 @@ use crate::gs_usb::classify::{
     GsUsbFrameClass, RecoverableGsUsbFrameStatus, classify_gs_usb_frame,
 };
 use crate::gs_usb::device::{GS_USB_BATCH_FRAME_CAPACITY, GS_USB_READ_BUFFER_SIZE, GsUsbDevice};
-use crate::gs_usb::frame::GsUsbFrame;
-use crate::gs_usb::protocol::{CAN_EFF_FLAG, GS_USB_ECHO_ID};
+use crate::gs_usb::echo::TxEchoTracker;
+use crate::gs_usb::frame::{GsUsbFrame, HwTimestampExtender};
+use crate::gs_usb::protocol::CAN_EFF_FLAG;
 use crate::{
     BackendCapability, BridgeTxAdapter, CanDeviceError, CanDeviceErrorKind, CanError, CanId,
-    PiperFrame, RealtimeTxAdapter, ReceivedFrame, RxAdapter, TimestampProvenance,
+    PiperFrame, RealtimeTxAdapter, ReceivedFrame, RxAdapter, TimestampProvenance, TxEchoStats,
 };
 use std::collections::VecDeque;
 use std::sync::Arc;
@@ -26,8 +27,7 @@ pub struct GsUsbRxAdapter {
     device: Arc<GsUsbDevice>,
     rx_timeout: Duration,
     hw_timestamp_enabled: bool,
-    timestamp_wraps: u64,
-    last_timestamp_low: Option<u32>,
+    timestamp_extender: HwTimestampExtender,
     /// 接收队列：缓存从 USB 包中解包的多余帧
     ///
     /// **性能优化**：预分配容量以避免动态扩容的内存分配抖动（Allocator Jitter）。
@@ -44,6 +44,8 @@ pub struct GsUsbRxAdapter {
     bus_off_callback: Option<Arc<dyn Fn(bool) + Send + Sync>>,
     /// Error Passive 状态更新回调（可选）
     error_passive_callback: Option<Arc<dyn Fn(bool) + Send + Sync>>,
+    /// TX Echo 在途表（与 TX 适配器共享）
+    tx_echo: Arc<TxEchoTracker>,
 }
 
 impl GsUsbRxAdapter {
@@ -62,8 +64,7 @@ impl GsUsbRxAdapter {
             device,
             rx_timeout,
             hw_timestamp_enabled,
-            timestamp_wraps: 0,
-            last_timestamp_low: None,
+            timestamp_extender: HwTimestampExtender::default(),
             // 关键：预分配容量，避免运行时扩容
             // 64 是经验值：足够应对突发，但不会浪费过多内存
             rx_queue: VecDeque::with_capacity(64),
@@ -71,6 +72,7 @@ impl GsUsbRxAdapter {
             rx_batch_frames: Vec::with_capacity(GS_USB_BATCH_FRAME_CAPACITY),
             bus_off_callback: None,
             error_passive_callback: None,
+            tx_echo: Arc::default(),
         }
     }
 
@@ -156,6 +158,7 @@ impl GsUsbRxAdapter {
     pub fn receive(&mut self) -> Result<ReceivedFrame, CanError> {
         // 1. 优先从缓存队列返回
         if let Some(frame) = self.rx_queue.pop_front() {
+            crate::frame_trace::rx(self.device.trace_interface(), &frame.frame);
             return Ok(frame);
         }
 
@@ -196,6 +199,10 @@ impl GsUsbRxAdapter {
                 },
             }
 
+            for raw in self.rx_batch_frames.iter().filter(|raw| raw.is_tx_echo()) {
+                self.tx_echo.complete(raw.echo_id, raw.can_id);
+            }
+
             // 如果读取成功但没有帧（可能是空包），继续读下一个包
             if self.rx_batch_frames.is_empty() {
                 continue;
@@ -223,6 +230,7 @@ impl GsUsbRxAdapter {
 
             // 4. 返回第一帧（如果有）
             if let Some(frame) = self.rx_queue.pop_front() {
+                crate::frame_trace::rx(self.device.trace_interface(), &frame.frame);
                 return Ok(frame);
             }
 
@@ -231,22 +239,24 @@ impl GsUsbRxAdapter {
     }
 
     fn extend_frame_timestamp(&mut self, frame: PiperFrame) -> PiperFrame {
-        frame.with_timestamp_us(self.extend_hw_timestamp(frame.timestamp_us() as u32))
+        frame.with_timestamp_us(self.timestamp_extender.extend(frame.timestamp_us() as u32))
     }
 
-    fn extend_hw_timestamp(&mut self, timestamp_low: u32) -> u64 {
-        if timestamp_low == 0 {
-            return 0;
-        }
+    /// 继承分离前累计的时间戳回绕状态
+    pub(crate) fn with_timestamp_extender(mut self, extender: HwTimestampExtender) -> Self {
+        self.timestamp_extender = extender;
+        self
+    }
 
-        if let Some(previous) = self.last_timestamp_low
-            && timestamp_low < previous
-        {
-            self.timestamp_wraps = self.timestamp_wraps.saturating_add(1);
-        }
+    /// 硬件时间戳回绕次数（诊断用）
+    pub fn timestamp_wrap_count(&self) -> u64 {
+        self.timestamp_extender.wrap_count()
+    }
 
-        self.last_timestamp_low = Some(timestamp_low);
-        (self.timestamp_wraps << 32) | (timestamp_low as u64)
+    /// 与 TX 适配器共享 TX Echo 在途表
+    pub(crate) fn with_tx_echo_tracker(mut self, tracker: Arc<TxEchoTracker>) -> Self {
+        self.tx_echo = tracker;
+        self
     }
 }
 
@@ -262,6 +272,10 @@ impl RxAdapter for GsUsbRxAdapter {
             BackendCapability::MonitorOnly
         }
     }
+
+    fn tx_echo_stats(&self) -> Option<TxEchoStats> {
+        Some(self.tx_echo.stats())
+    }
 }
 
 /// 只写适配器（用于 TX 线程）
@@ -270,23 +284,29 @@ impl RxAdapter for GsUsbRxAdapter {
 /// 可以在不同线程中与 `GsUsbRxAdapter` 并发使用。
 pub struct GsUsbTxAdapter {
     device: Arc<GsUsbDevice>,
+    /// TX Echo 在途表（与 RX 适配器共享）
+    tx_echo: Arc<TxEchoTracker>,
 }
 
 #[doc(hidden)]
 pub struct GsUsbBridgeTxAdapter {
     device: Arc<GsUsbDevice>,
     bridge_timeout: Duration,
+    tx_echo: Arc<TxEchoTracker>,
 }
 
-fn encode_tx_frame(frame: PiperFrame) -> GsUsbFrame {
-    let can_id = match frame.id() {
+/// GS-USB 线上 `can_id`（扩展帧带 EFF 标志）
+pub(crate) fn tx_can_id(frame: &PiperFrame) -> u32 {
+    match frame.id() {
         CanId::Standard(id) => id.raw() as u32,
         CanId::Extended(id) => id.raw() | CAN_EFF_FLAG,
-    };
+    }
+}
 
+fn encode_tx_frame(frame: PiperFrame, echo_id: u32) -> GsUsbFrame {
     GsUsbFrame {
-        echo_id: GS_USB_ECHO_ID,
-        can_id,
+        echo_id,
+        can_id: tx_can_id(&frame),
         can_dlc: frame.dlc(),
         channel: 0,
         flags: 0,
@@ -321,23 +341,40 @@ fn map_usb_send_error(error: crate::gs_usb::error::GsUsbError) -> CanError {
 
 fn send_frame_until(
     device: &GsUsbDevice,
+    tx_echo: &TxEchoTracker,
     frame: PiperFrame,
     deadline: Instant,
 ) -> Result<(), CanError> {
-    let gs_frame = encode_tx_frame(frame);
-    device.send_raw_until(&gs_frame, deadline).map_err(map_usb_send_error)
+    let echo_id = tx_echo.allocate(tx_can_id(&frame));
+    let gs_frame = encode_tx_frame(frame, echo_id);
+    if let Err(error) = device.send_raw_until(&gs_frame, deadline) {
+        tx_echo.cancel(echo_id);
+        return Err(map_usb_send_error(error));
+    }
+    crate::frame_trace::tx(device.trace_interface(), &frame);
+    Ok(())
 }
 
 impl GsUsbTxAdapter {
     /// 创建新的 TX 适配器
     pub fn new(device: Arc<GsUsbDevice>) -> Self {
-        Self { device }
+        Self {
+            device,
+            tx_echo: Arc::default(),
+        }
+    }
+
+    /// 与 RX 适配器共享 TX Echo 在途表
+    pub(crate) fn with_tx_echo_tracker(mut self, tracker: Arc<TxEchoTracker>) -> Self {
+        self.tx_echo = tracker;
+        self
     }
 
     pub fn into_bridge(self, bridge_timeout: Duration) -> GsUsbBridgeTxAdapter {
         GsUsbBridgeTxAdapter {
             device: self.device,
             bridge_timeout,
+            tx_echo: self.tx_echo,
         }
     }
 
@@ -359,7 +396,7 @@ impl GsUsbTxAdapter {
         frame: PiperFrame,
         deadline: Instant,
     ) -> Result<(), CanError> {
-        send_frame_until(self.device.as_ref(), frame, deadline)
+        send_frame_until(self.device.as_ref(), &self.tx_echo, frame, deadline)
     }
 }
 
@@ -368,6 +405,7 @@ impl GsUsbBridgeTxAdapter {
         Self {
             device,
             bridge_timeout,
+            tx_echo: Arc::default(),
         }
     }
 
@@ -397,6 +435,7 @@ impl BridgeTxAdapter for GsUsbBridgeTxAdapter {
         }
         send_frame_until(
             self.device.as_ref(),
+            &self.tx_echo,
             frame,
             Instant::now() + self.bridge_timeout,
         )
@@ -407,8 +446,8 @@ impl BridgeTxAdapter for GsUsbBridgeTxAdapter {
 mod tests {
     use super::*;
     use crate::gs_usb::protocol::{
-        CAN_ERR_CRTL_RX_PASSIVE, CAN_ERR_FLAG, GS_CAN_FLAG_OVERFLOW, GS_USB_FRAME_SIZE,
-        GS_USB_FRAME_SIZE_HW_TIMESTAMP, GS_USB_RX_ECHO_ID,
+        CAN_ERR_CRTL_RX_PASSIVE, CAN_ERR_FLAG, GS_CAN_FLAG_OVERFLOW, GS_USB_ECHO_ID,
+        GS_USB_FRAME_SIZE, GS_USB_FRAME_SIZE_HW_TIMESTAMP, GS_USB_RX_ECHO_ID,
     };
     use std::sync::atomic::{AtomicBool, Ordering};
 
@@ -488,6 +527,39 @@ mod tests {
         assert!(matches!(later, CanError::Timeout));
     }
 
+    #[test]
+    fn split_rx_completes_tx_echoes_from_shared_tracker() {
+        let (mut device, harness) = GsUsbDevice::new_test_device(false, false);
+        device.set_write_timeout(Duration::from_millis(50));
+        let device = Arc::new(device);
+        let tracker = Arc::new(TxEchoTracker::default());
+        let mut tx = GsUsbTxAdapter::new(device.clone()).with_tx_echo_tracker(tracker.clone());
+        let mut rx = GsUsbRxAdapter::new(device, Duration::from_millis(2), 0, false)
+            .with_tx_echo_tracker(tracker);
+
+        let frame = PiperFrame::new_standard(0x151, [0x01]).unwrap();
+        tx.send_frame_with_budget(frame, Duration::from_millis(50)).unwrap();
+        tx.send_frame_with_budget(frame, Duration::from_millis(50)).unwrap();
+        assert_eq!(rx.tx_echo_stats().unwrap().in_flight, 2);
+
+        harness.enqueue_read_packet(pack_packet(
+            &[
+                GsUsbFrame {
+                    echo_id: 1,
+                    ..rx_frame(0x151, 0, 0x01)
+                },
+                rx_frame(0x251, 0, 0x11),
+            ],
+            false,
+        ));
+        assert_eq!(rx.receive().unwrap().frame.raw_id(), 0x251);
+
+        let stats = rx.tx_echo_stats().unwrap();
+        assert_eq!(stats.completed, 1);
+        assert_eq!(stats.in_flight, 1);
+        assert_eq!(stats.unmatched, 0);
+    }
+
     #[test]
     fn split_receive_skips_recoverable_between_valid_frames() {
         let (device, harness) = GsUsbDevice::new_test_device(false, false);
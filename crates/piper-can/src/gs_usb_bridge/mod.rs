@@ -24,7 +24,9 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
-pub use protocol::{BridgeEvent, BridgeRole, BridgeStatus, ErrorCode, SessionToken};
+pub use protocol::{
+    BridgeEvent, BridgeRole, BridgeSessionInfo, BridgeStatus, ErrorCode, SessionToken,
+};
 
 #[derive(Debug)]
 pub enum BridgeError {
@@ -72,12 +74,52 @@ impl From<rustls::Error> for BridgeError {
 }
 
 impl BridgeError {
+    /// Whether retrying the operation on a fresh connection may succeed.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::Io(_) | Self::Protocol(_) | Self::NotConnected)
+    }
+
+    /// Whether the stream to the host is gone (EOF, reset, broken pipe, ...),
+    /// as happens when the host process restarts.
+    pub fn is_connection_lost(&self) -> bool {
+        use std::io::ErrorKind;
+        matches!(
+            self.io_kind(),
+            Some(
+                ErrorKind::UnexpectedEof
+                    | ErrorKind::ConnectionReset
+                    | ErrorKind::ConnectionAborted
+                    | ErrorKind::ConnectionRefused
+                    | ErrorKind::BrokenPipe
+                    | ErrorKind::NotConnected
+            )
+        )
+    }
+
+    /// Whether a read or write hit the configured timeout.
+    pub fn is_timeout(&self) -> bool {
+        matches!(
+            self.io_kind(),
+            Some(std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock)
+        )
+    }
+
+    fn io_kind(&self) -> Option<std::io::ErrorKind> {
+        match self {
+            Self::Io(err) => Some(err.kind()),
+            Self::Protocol(protocol::ProtocolError::Io { kind, .. }) => Some(*kind),
+            _ => None,
+        }
+    }
+
     pub fn as_can_device_error(&self) -> Option<CanDeviceError> {
         match self {
             Self::Remote { code, message } => {
                 let kind = match code {
                     ErrorCode::DeviceNotFound => CanDeviceErrorKind::NotFound,
-                    ErrorCode::DeviceBusy | ErrorCode::Busy => CanDeviceErrorKind::Busy,
+                    ErrorCode::DeviceBusy | ErrorCode::Busy | ErrorCode::Throttled => {
+                        CanDeviceErrorKind::Busy
+                    },
                     ErrorCode::InvalidMessage | ErrorCode::ProtocolError => {
                         CanDeviceErrorKind::InvalidResponse
                     },
@@ -106,6 +148,59 @@ pub enum BridgeEndpoint {
     TcpTls(SocketAddr),
 }
 
+impl std::str::FromStr for BridgeEndpoint {
+    type Err = BridgeError;
+
+    /// Parses `unix:/path`, `unix:///path`, an absolute path, `tcp://host:port`,
+    /// `tls://host:port` or a bare `host:port`.
+    ///
+    /// TCP endpoints are always TLS-protected; plaintext TCP is intentionally unsupported.
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let raw = raw.trim();
+        let unix_path = raw
+            .strip_prefix("unix://")
+            .or_else(|| raw.strip_prefix("unix:"))
+            .or_else(|| raw.starts_with('/').then_some(raw));
+        if let Some(path) = unix_path {
+            if path.is_empty() {
+                return Err(BridgeError::Config(
+                    "empty unix bridge endpoint path".to_string(),
+                ));
+            }
+            if cfg!(unix) {
+                return Ok(Self::Unix(PathBuf::from(path)));
+            }
+            return Err(BridgeError::Config(
+                "unix bridge endpoints are not supported on this platform".to_string(),
+            ));
+        }
+
+        let host_port =
+            raw.strip_prefix("tcp://").or_else(|| raw.strip_prefix("tls://")).unwrap_or(raw);
+        if let Ok(addr) = host_port.parse::<SocketAddr>() {
+            return Ok(Self::TcpTls(addr));
+        }
+        std::net::ToSocketAddrs::to_socket_addrs(host_port)
+            .map_err(|err| BridgeError::Config(format!("invalid bridge endpoint {raw}: {err}")))?
+            .next()
+            .map(Self::TcpTls)
+            .ok_or_else(|| {
+                BridgeError::Config(format!(
+                    "bridge endpoint {raw} did not resolve to an address"
+                ))
+            })
+    }
+}
+
+impl std::fmt::Display for BridgeEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unix(path) => write!(f, "unix:{}", path.display()),
+            Self::TcpTls(addr) => write!(f, "tcp://{addr}"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BridgeTlsClientConfig {
     pub ca_cert_pem: PathBuf,
@@ -121,6 +216,10 @@ pub struct BridgeClientOptions {
     pub connect_timeout: Duration,
     pub request_timeout: Duration,
     pub tcp_tls: Option<BridgeTlsClientConfig>,
+    pub reconnect: BridgeReconnectPolicy,
+    /// Transparently [`GsUsbBridgeClient::reconnect`] once when a non-lease
+    /// operation finds the connection lost or its request unanswered.
+    pub auto_reconnect: bool,
 }
 
 impl Default for BridgeClientOptions {
@@ -131,10 +230,39 @@ impl Default for BridgeClientOptions {
             connect_timeout: Duration::from_secs(5),
             request_timeout: Duration::from_millis(100),
             tcp_tls: None,
+            reconnect: BridgeReconnectPolicy::default(),
+            auto_reconnect: false,
+        }
+    }
+}
+
+/// Retry schedule used by [`GsUsbBridgeClient::reconnect`]: exponential backoff
+/// starting at `initial_backoff`, doubling per attempt and capped at `max_backoff`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BridgeReconnectPolicy {
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for BridgeReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
         }
     }
 }
 
+impl BridgeReconnectPolicy {
+    /// Delay before retrying after the zero-based `attempt` failed.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u32.checked_shl(attempt.min(31)).unwrap_or(u32::MAX);
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
 enum BridgeStream {
     #[cfg(unix)]
     Unix(UnixStream),
@@ -260,26 +388,86 @@ impl Write for BridgeStream {
 pub struct GsUsbBridgeClient {
     stream: BridgeStream,
     endpoint: BridgeEndpoint,
-    session_token: SessionToken,
+    options: BridgeClientOptions,
     session_id: u32,
     role_granted: BridgeRole,
-    request_timeout: Duration,
     next_request_id: u32,
     event_buffer: VecDeque<BridgeEvent>,
     writer_lease_held: bool,
+    raw_frame_tap: bool,
     connected: bool,
+    session_replaced: bool,
+    reconnect_count: u64,
 }
 
 impl GsUsbBridgeClient {
     pub fn connect(endpoint: BridgeEndpoint, options: BridgeClientOptions) -> BridgeResult<Self> {
-        let mut stream = BridgeStream::connect(&endpoint, &options)?;
+        let (stream, session_id, role_granted) = Self::handshake(&endpoint, &options, 1)?;
+        Ok(Self {
+            stream,
+            endpoint,
+            options,
+            session_id,
+            role_granted,
+            next_request_id: 2,
+            event_buffer: VecDeque::new(),
+            writer_lease_held: false,
+            raw_frame_tap: false,
+            connected: true,
+            session_replaced: false,
+            reconnect_count: 0,
+        })
+    }
+
+    /// Re-establishes the session after a transport failure or host restart.
+    ///
+    /// The same session token is reused, the last filters and raw frame tap
+    /// subscription are restored, and request ids keep counting from where the
+    /// previous connection stopped. Any writer lease is lost and must be
+    /// re-acquired. Transient failures are retried per
+    /// [`BridgeClientOptions::reconnect`].
+    pub fn reconnect(&mut self) -> BridgeResult<()> {
+        self.disconnect();
+        let policy = self.options.reconnect;
+        let mut attempt = 0;
+        let (stream, session_id, role_granted) = loop {
+            let request_id = self.next_request_id();
+            match Self::handshake(&self.endpoint, &self.options, request_id) {
+                Ok(session) => break session,
+                Err(err) if !err.is_transient() || attempt + 1 >= policy.max_attempts.max(1) => {
+                    return Err(err);
+                },
+                Err(_) => {
+                    std::thread::sleep(policy.backoff(attempt));
+                    attempt += 1;
+                },
+            }
+        };
+        self.stream = stream;
+        self.session_id = session_id;
+        self.role_granted = role_granted;
+        self.connected = true;
+        self.session_replaced = false;
+        self.reconnect_count += 1;
+        if self.raw_frame_tap {
+            self.set_raw_frame_tap_once(true)?;
+        }
+        Ok(())
+    }
+
+    fn handshake(
+        endpoint: &BridgeEndpoint,
+        options: &BridgeClientOptions,
+        hello_request_id: u32,
+    ) -> BridgeResult<(BridgeStream, u32, BridgeRole)> {
+        let mut stream = BridgeStream::connect(endpoint, options)?;
         stream.set_read_timeout(Some(options.request_timeout))?;
         stream.set_write_timeout(Some(options.request_timeout))?;
 
         let hello = ClientRequest::Hello {
-            request_id: 1,
+            request_id: hello_request_id,
             session_token: options.session_token,
-            filters: options.filters,
+            filters: options.filters.clone(),
         };
         let encoded = encode_client_request(&hello)?;
         write_framed(&mut stream, &encoded)?;
@@ -291,36 +479,21 @@ impl GsUsbBridgeClient {
             ));
         };
 
-        let (session_id, role_granted) = match response {
+        match response {
             ServerResponse::HelloAck {
-                request_id: 1,
+                request_id,
                 session_id,
                 role_granted,
-            } => (session_id, role_granted),
+            } if request_id == hello_request_id => Ok((stream, session_id, role_granted)),
             ServerResponse::Error {
-                request_id: 1,
+                request_id,
                 code,
                 message,
-            } => return Err(BridgeError::Remote { code, message }),
-            _ => {
-                return Err(BridgeError::UnexpectedMessage(
-                    "unexpected response during connect",
-                ));
-            },
-        };
-
-        Ok(Self {
-            stream,
-            endpoint,
-            session_token: options.session_token,
-            session_id,
-            role_granted,
-            request_timeout: options.request_timeout,
-            next_request_id: 2,
-            event_buffer: VecDeque::new(),
-            writer_lease_held: false,
-            connected: true,
-        })
+            } if request_id == hello_request_id => Err(BridgeError::Remote { code, message }),
+            _ => Err(BridgeError::UnexpectedMessage(
+                "unexpected response during connect",
+            )),
+        }
     }
 
     pub fn endpoint(&self) -> &BridgeEndpoint {
@@ -328,7 +501,12 @@ impl GsUsbBridgeClient {
     }
 
     pub fn session_token(&self) -> SessionToken {
-        self.session_token
+        self.options.session_token
+    }
+
+    /// Filters that will be restored by [`Self::reconnect`].
+    pub fn filters(&self) -> &[protocol::CanIdFilter] {
+        &self.options.filters
     }
 
     pub fn session_id(&self) -> u32 {
@@ -339,7 +517,58 @@ impl GsUsbBridgeClient {
         self.role_granted
     }
 
+    /// Number of successful reconnects, manual or automatic.
+    pub fn reconnect_count(&self) -> u64 {
+        self.reconnect_count
+    }
+
     pub fn recv_event(&mut self, timeout: Duration) -> BridgeResult<BridgeEvent> {
+        self.with_recovery(false, |client| client.recv_event_once(timeout))
+    }
+
+    pub fn get_status(&mut self) -> BridgeResult<BridgeStatus> {
+        self.with_recovery(true, Self::get_status_once)
+    }
+
+    pub fn list_sessions(&mut self) -> BridgeResult<Vec<BridgeSessionInfo>> {
+        self.with_recovery(true, Self::list_sessions_once)
+    }
+
+    pub fn set_filters(&mut self, filters: Vec<protocol::CanIdFilter>) -> BridgeResult<()> {
+        self.with_recovery(true, |client| client.set_filters_once(filters.clone()))
+    }
+
+    pub fn set_raw_frame_tap(&mut self, enabled: bool) -> BridgeResult<()> {
+        self.with_recovery(true, |client| client.set_raw_frame_tap_once(enabled))
+    }
+
+    pub fn ping(&mut self) -> BridgeResult<()> {
+        self.with_recovery(true, Self::ping_once)
+    }
+
+    /// Runs `op`, and with [`BridgeClientOptions::auto_reconnect`] retries it
+    /// once on a fresh connection if the host went away. Request timeouts
+    /// count as a lost host only when `request` is set; a session replaced by
+    /// another client is never recovered.
+    fn with_recovery<T>(
+        &mut self,
+        request: bool,
+        mut op: impl FnMut(&mut Self) -> BridgeResult<T>,
+    ) -> BridgeResult<T> {
+        match op(self) {
+            Err(err)
+                if self.options.auto_reconnect
+                    && !self.session_replaced
+                    && (err.is_connection_lost() || (request && err.is_timeout())) =>
+            {
+                self.reconnect()?;
+                op(self)
+            },
+            result => result,
+        }
+    }
+
+    fn recv_event_once(&mut self, timeout: Duration) -> BridgeResult<BridgeEvent> {
         self.ensure_connected()?;
         if let Some(event) = self.pop_event() {
             return Ok(event);
@@ -358,7 +587,7 @@ impl GsUsbBridgeClient {
         }
     }
 
-    pub fn get_status(&mut self) -> BridgeResult<BridgeStatus> {
+    fn get_status_once(&mut self) -> BridgeResult<BridgeStatus> {
         self.ensure_connected()?;
         let request_id = self.next_request_id();
         self.send_request(ClientRequest::GetStatus { request_id })?;
@@ -368,20 +597,33 @@ impl GsUsbBridgeClient {
         }
     }
 
-    pub fn set_filters(&mut self, filters: Vec<protocol::CanIdFilter>) -> BridgeResult<()> {
+    fn list_sessions_once(&mut self) -> BridgeResult<Vec<BridgeSessionInfo>> {
+        self.ensure_connected()?;
+        let request_id = self.next_request_id();
+        self.send_request(ClientRequest::ListSessions { request_id })?;
+        match self.wait_for_response(request_id)? {
+            ServerResponse::SessionList { sessions, .. } => Ok(sessions),
+            response => Err(self.unexpected_response("session list response", response)),
+        }
+    }
+
+    fn set_filters_once(&mut self, filters: Vec<protocol::CanIdFilter>) -> BridgeResult<()> {
         self.ensure_connected()?;
         let request_id = self.next_request_id();
         self.send_request(ClientRequest::SetFilters {
             request_id,
-            filters,
+            filters: filters.clone(),
         })?;
         match self.wait_for_response(request_id)? {
-            ServerResponse::Ok { .. } => Ok(()),
+            ServerResponse::Ok { .. } => {
+                self.options.filters = filters;
+                Ok(())
+            },
             response => Err(self.unexpected_response("ok response", response)),
         }
     }
 
-    pub fn set_raw_frame_tap(&mut self, enabled: bool) -> BridgeResult<()> {
+    fn set_raw_frame_tap_once(&mut self, enabled: bool) -> BridgeResult<()> {
         self.ensure_connected()?;
         let request_id = self.next_request_id();
         self.send_request(ClientRequest::SetRawFrameTap {
@@ -389,12 +631,15 @@ impl GsUsbBridgeClient {
             enabled,
         })?;
         match self.wait_for_response(request_id)? {
-            ServerResponse::Ok { .. } => Ok(()),
+            ServerResponse::Ok { .. } => {
+                self.raw_frame_tap = enabled;
+                Ok(())
+            },
             response => Err(self.unexpected_response("ok response", response)),
         }
     }
 
-    pub fn ping(&mut self) -> BridgeResult<()> {
+    fn ping_once(&mut self) -> BridgeResult<()> {
         self.ensure_connected()?;
         let request_id = self.next_request_id();
         self.send_request(ClientRequest::Ping { request_id })?;
@@ -474,13 +719,13 @@ impl GsUsbBridgeClient {
 
     fn send_request(&mut self, request: ClientRequest) -> BridgeResult<()> {
         let encoded = encode_client_request(&request)?;
-        self.stream.set_write_timeout(Some(self.request_timeout))?;
+        self.stream.set_write_timeout(Some(self.options.request_timeout))?;
         write_framed(&mut self.stream, &encoded)?;
         Ok(())
     }
 
     fn wait_for_response(&mut self, request_id: u32) -> BridgeResult<ServerResponse> {
-        self.stream.set_read_timeout(Some(self.request_timeout))?;
+        self.stream.set_read_timeout(Some(self.options.request_timeout))?;
         loop {
             match self.read_server_message()? {
                 ServerMessage::Event(event) => {
@@ -528,6 +773,7 @@ impl GsUsbBridgeClient {
             BridgeEvent::SessionReplaced => {
                 self.writer_lease_held = false;
                 self.connected = false;
+                self.session_replaced = true;
                 BridgeEvent::SessionReplaced
             },
             other => other,
@@ -602,7 +848,8 @@ fn response_request_id(response: &ServerResponse) -> u32 {
         | ServerResponse::Error { request_id, .. }
         | ServerResponse::StatusResponse { request_id, .. }
         | ServerResponse::LeaseGranted { request_id, .. }
-        | ServerResponse::LeaseDenied { request_id, .. } => *request_id,
+        | ServerResponse::LeaseDenied { request_id, .. }
+        | ServerResponse::SessionList { request_id, .. } => *request_id,
     }
 }
 
@@ -640,6 +887,34 @@ mod tests {
 
     static NEXT_TLS_FIXTURE_ID: AtomicU64 = AtomicU64::new(1);
 
+    #[test]
+    fn endpoint_parses_unix_and_tcp_address_forms() {
+        let tcp: BridgeEndpoint = "tcp://127.0.0.1:18888".parse().unwrap();
+        assert_eq!(
+            tcp,
+            BridgeEndpoint::TcpTls("127.0.0.1:18888".parse().unwrap())
+        );
+        assert_eq!("127.0.0.1:18888".parse::<BridgeEndpoint>().unwrap(), tcp);
+        assert_eq!(
+            "tls://127.0.0.1:18888".parse::<BridgeEndpoint>().unwrap(),
+            tcp
+        );
+        assert_eq!(tcp.to_string(), "tcp://127.0.0.1:18888");
+        assert!("tcp://127.0.0.1".parse::<BridgeEndpoint>().is_err());
+
+        if cfg!(unix) {
+            let unix = BridgeEndpoint::Unix(PathBuf::from("/tmp/piper_bridge.sock"));
+            for raw in [
+                "/tmp/piper_bridge.sock",
+                "unix:/tmp/piper_bridge.sock",
+                "unix:///tmp/piper_bridge.sock",
+            ] {
+                assert_eq!(raw.parse::<BridgeEndpoint>().unwrap(), unix);
+            }
+            assert_eq!(unix.to_string(), "unix:/tmp/piper_bridge.sock");
+        }
+    }
+
     fn standard_filter(min: u32, max: u32) -> CanIdFilter {
         CanIdFilter::standard(
             StandardCanId::new(min).unwrap(),
@@ -725,21 +1000,36 @@ mod tests {
         fn spawn_server<F>(&self, handler: F) -> std::net::SocketAddr
         where
             F: FnOnce(StreamOwned<ServerConnection, TcpStream>) + Send + 'static,
+        {
+            let mut handler = Some(handler);
+            self.spawn_server_connections(1, move |_, stream| (handler.take().unwrap())(stream))
+        }
+
+        /// Accepts `connections` sequential clients on the same address.
+        fn spawn_server_connections<F>(
+            &self,
+            connections: usize,
+            mut handler: F,
+        ) -> std::net::SocketAddr
+        where
+            F: FnMut(usize, StreamOwned<ServerConnection, TcpStream>) + Send + 'static,
         {
             let listener = TcpListener::bind("127.0.0.1:0").unwrap();
             let addr = listener.local_addr().unwrap();
             let server_config = self.server_config.clone();
             thread::spawn(move || {
-                let (tcp_stream, _) = listener.accept().unwrap();
-                tcp_stream.set_nodelay(true).unwrap();
-                tcp_stream.set_read_timeout(Some(Duration::from_secs(1))).unwrap();
-                tcp_stream.set_write_timeout(Some(Duration::from_secs(1))).unwrap();
-                let connection = ServerConnection::new(server_config).unwrap();
-                let mut stream = StreamOwned::new(connection, tcp_stream);
-                while stream.conn.is_handshaking() {
-                    stream.conn.complete_io(&mut stream.sock).unwrap();
+                for index in 0..connections {
+                    let (tcp_stream, _) = listener.accept().unwrap();
+                    tcp_stream.set_nodelay(true).unwrap();
+                    tcp_stream.set_read_timeout(Some(Duration::from_secs(1))).unwrap();
+                    tcp_stream.set_write_timeout(Some(Duration::from_secs(1))).unwrap();
+                    let connection = ServerConnection::new(server_config.clone()).unwrap();
+                    let mut stream = StreamOwned::new(connection, tcp_stream);
+                    while stream.conn.is_handshaking() {
+                        stream.conn.complete_io(&mut stream.sock).unwrap();
+                    }
+                    handler(index, stream);
                 }
-                handler(stream);
             });
             addr
         }
@@ -783,6 +1073,8 @@ mod tests {
             connect_timeout: Duration::from_secs(1),
             request_timeout: Duration::from_secs(1),
             tcp_tls: Some(tls.client_tls_config()),
+            reconnect: BridgeReconnectPolicy::default(),
+            auto_reconnect: false,
         };
         let client = GsUsbBridgeClient::connect(BridgeEndpoint::TcpTls(addr), options).unwrap();
         assert_eq!(client.session_id(), 42);
@@ -812,6 +1104,8 @@ mod tests {
             connect_timeout: Duration::from_secs(1),
             request_timeout: Duration::from_secs(1),
             tcp_tls: Some(tls.client_tls_config()),
+            reconnect: BridgeReconnectPolicy::default(),
+            auto_reconnect: false,
         };
         let mut client = GsUsbBridgeClient::connect(BridgeEndpoint::TcpTls(addr), options).unwrap();
         let event = client.recv_event(Duration::from_secs(1)).unwrap();
@@ -853,10 +1147,140 @@ mod tests {
             connect_timeout: Duration::from_secs(1),
             request_timeout: Duration::from_secs(1),
             tcp_tls: Some(tls.client_tls_config()),
+            reconnect: BridgeReconnectPolicy::default(),
+            auto_reconnect: false,
         };
         let mut client = GsUsbBridgeClient::connect(BridgeEndpoint::TcpTls(addr), options).unwrap();
         client.ping().unwrap();
         let event = client.recv_event(Duration::from_secs(1)).unwrap();
         assert!(matches!(event, BridgeEvent::ReceiveFrame(_)));
     }
+
+    #[test]
+    fn reconnect_policy_backoff_doubles_up_to_cap() {
+        let policy = BridgeReconnectPolicy {
+            max_attempts: 10,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(500),
+        };
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(2), Duration::from_millis(400));
+        assert_eq!(policy.backoff(3), Duration::from_millis(500));
+        assert_eq!(policy.backoff(64), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_reconnect_restores_token_filters_and_raw_tap() {
+        let tls = TestTlsFixture::new();
+        let token = SessionToken::new([3; SESSION_TOKEN_LEN]);
+        let addr = tls.spawn_server_connections(2, move |index, mut stream| {
+            let ClientRequest::Hello {
+                request_id,
+                session_token,
+                filters,
+            } = decode_client_request(&read_framed(&mut stream).unwrap()).unwrap()
+            else {
+                panic!("expected hello");
+            };
+            assert_eq!(session_token, token);
+            let hello_ack = ServerMessage::Response(ServerResponse::HelloAck {
+                request_id,
+                session_id: 10 + index as u32,
+                role_granted: BridgeRole::Observer,
+            });
+            write_framed(&mut stream, &encode_server_message(&hello_ack).unwrap()).unwrap();
+
+            if index == 0 {
+                assert_eq!(request_id, 1);
+                assert!(filters.is_empty());
+                for _ in 0..2 {
+                    let request =
+                        decode_client_request(&read_framed(&mut stream).unwrap()).unwrap();
+                    let request_id = match request {
+                        ClientRequest::SetFilters { request_id, .. }
+                        | ClientRequest::SetRawFrameTap { request_id, .. } => request_id,
+                        other => panic!("unexpected request: {other:?}"),
+                    };
+                    let ok = ServerMessage::Response(ServerResponse::Ok { request_id });
+                    write_framed(&mut stream, &encode_server_message(&ok).unwrap()).unwrap();
+                }
+                // 断开连接，模拟 host 重启
+                return;
+            }
+
+            assert_eq!(request_id, 4);
+            assert_eq!(filters, vec![standard_filter(0x200, 0x2FF)]);
+            let request = decode_client_request(&read_framed(&mut stream).unwrap()).unwrap();
+            let ClientRequest::SetRawFrameTap {
+                request_id,
+                enabled: true,
+            } = request
+            else {
+                panic!("expected raw frame tap restore, got {request:?}");
+            };
+            let ok = ServerMessage::Response(ServerResponse::Ok { request_id });
+            write_framed(&mut stream, &encode_server_message(&ok).unwrap()).unwrap();
+        });
+
+        let options = BridgeClientOptions {
+            session_token: token,
+            connect_timeout: Duration::from_secs(1),
+            request_timeout: Duration::from_secs(1),
+            tcp_tls: Some(tls.client_tls_config()),
+            ..Default::default()
+        };
+        let mut client = GsUsbBridgeClient::connect(BridgeEndpoint::TcpTls(addr), options).unwrap();
+        client.set_filters(vec![standard_filter(0x200, 0x2FF)]).unwrap();
+        client.set_raw_frame_tap(true).unwrap();
+
+        client.reconnect().unwrap();
+        assert_eq!(client.session_id(), 11);
+        assert_eq!(client.session_token(), token);
+        assert_eq!(client.filters(), &[standard_filter(0x200, 0x2FF)]);
+    }
+
+    #[test]
+    fn test_auto_reconnect_recovers_after_host_restart() {
+        let tls = TestTlsFixture::new();
+        let addr = tls.spawn_server_connections(2, move |index, mut stream| {
+            let ClientRequest::Hello { request_id, .. } =
+                decode_client_request(&read_framed(&mut stream).unwrap()).unwrap()
+            else {
+                panic!("expected hello");
+            };
+            let hello_ack = ServerMessage::Response(ServerResponse::HelloAck {
+                request_id,
+                session_id: 20 + index as u32,
+                role_granted: BridgeRole::Observer,
+            });
+            write_framed(&mut stream, &encode_server_message(&hello_ack).unwrap()).unwrap();
+            if index == 0 {
+                // 首个连接直接关闭，模拟 host 重启
+                return;
+            }
+
+            let ClientRequest::Ping { request_id } =
+                decode_client_request(&read_framed(&mut stream).unwrap()).unwrap()
+            else {
+                panic!("expected ping");
+            };
+            let ok = ServerMessage::Response(ServerResponse::Ok { request_id });
+            write_framed(&mut stream, &encode_server_message(&ok).unwrap()).unwrap();
+        });
+
+        let options = BridgeClientOptions {
+            connect_timeout: Duration::from_secs(1),
+            request_timeout: Duration::from_secs(1),
+            tcp_tls: Some(tls.client_tls_config()),
+            auto_reconnect: true,
+            ..Default::default()
+        };
+        let mut client = GsUsbBridgeClient::connect(BridgeEndpoint::TcpTls(addr), options).unwrap();
+        assert_eq!(client.session_id(), 20);
+
+        client.ping().unwrap();
+        assert_eq!(client.session_id(), 21);
+        assert_eq!(client.reconnect_count(), 1);
+    }
 }
@@ -21,6 +21,7 @@ const TAG_SEND_FRAME: u8 = 0x07;
 const TAG_PING: u8 = 0x08;
 const TAG_HELLO_V3: u8 = 0x09;
 const TAG_SET_FILTERS_V3: u8 = 0x0A;
+const TAG_LIST_SESSIONS: u8 = 0x0B;
 
 const TAG_HELLO_ACK: u8 = 0x81;
 const TAG_OK: u8 = 0x82;
@@ -28,6 +29,7 @@ const TAG_ERROR: u8 = 0x83;
 const TAG_STATUS_RESPONSE: u8 = 0x84;
 const TAG_LEASE_GRANTED: u8 = 0x85;
 const TAG_LEASE_DENIED: u8 = 0x86;
+const TAG_SESSION_LIST: u8 = 0x87;
 
 const TAG_EVENT_RECEIVE_FRAME: u8 = 0xC1;
 const TAG_EVENT_GAP: u8 = 0xC2;
@@ -100,6 +102,7 @@ pub enum ErrorCode {
     DeviceBusy = 7,
     DeviceError = 8,
     ProtocolError = 9,
+    Throttled = 10,
 }
 
 impl ErrorCode {
@@ -115,14 +118,24 @@ impl ErrorCode {
             7 => Ok(Self::DeviceBusy),
             8 => Ok(Self::DeviceError),
             9 => Ok(Self::ProtocolError),
+            10 => Ok(Self::Throttled),
             _ => Err(ProtocolError::InvalidData("invalid error code")),
         }
     }
 }
 
+/// Whether a filter selects frames to deliver or frames to suppress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CanIdFilterMode {
+    #[default]
+    Allow,
+    Block,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct CanIdFilter {
     kind: CanIdFilterKind,
+    mode: CanIdFilterMode,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -135,8 +148,20 @@ enum CanIdFilterKind {
         min: ExtendedCanId,
         max: ExtendedCanId,
     },
+    StandardMask {
+        id: StandardCanId,
+        mask: StandardCanId,
+    },
+    ExtendedMask {
+        id: ExtendedCanId,
+        mask: ExtendedCanId,
+    },
 }
 
+const FILTER_FORMAT_EXTENDED: u8 = 0x01;
+const FILTER_FORMAT_MASK: u8 = 0x02;
+const FILTER_FORMAT_BLOCK: u8 = 0x80;
+
 impl CanIdFilter {
     pub fn standard(min: StandardCanId, max: StandardCanId) -> Result<Self, ProtocolError> {
         if min > max {
@@ -146,6 +171,7 @@ impl CanIdFilter {
         }
         Ok(Self {
             kind: CanIdFilterKind::Standard { min, max },
+            mode: CanIdFilterMode::Allow,
         })
     }
 
@@ -157,25 +183,110 @@ impl CanIdFilter {
         }
         Ok(Self {
             kind: CanIdFilterKind::Extended { min, max },
+            mode: CanIdFilterMode::Allow,
         })
     }
 
+    /// Matches standard IDs where `raw & mask == id & mask`.
+    pub fn standard_mask(id: StandardCanId, mask: StandardCanId) -> Self {
+        Self {
+            kind: CanIdFilterKind::StandardMask { id, mask },
+            mode: CanIdFilterMode::Allow,
+        }
+    }
+
+    /// Matches extended IDs where `raw & mask == id & mask`.
+    pub fn extended_mask(id: ExtendedCanId, mask: ExtendedCanId) -> Self {
+        Self {
+            kind: CanIdFilterKind::ExtendedMask { id, mask },
+            mode: CanIdFilterMode::Allow,
+        }
+    }
+
+    /// Turns this filter into a blocklist entry.
+    pub fn blocking(mut self) -> Self {
+        self.mode = CanIdFilterMode::Block;
+        self
+    }
+
+    pub fn mode(&self) -> CanIdFilterMode {
+        self.mode
+    }
+
     pub fn matches(&self, id: CanId) -> bool {
         match (self.kind, id) {
             (CanIdFilterKind::Standard { min, max }, CanId::Standard(id)) => min <= id && id <= max,
             (CanIdFilterKind::Extended { min, max }, CanId::Extended(id)) => min <= id && id <= max,
+            (CanIdFilterKind::StandardMask { id: want, mask }, CanId::Standard(id)) => {
+                id.raw() & mask.raw() == want.raw() & mask.raw()
+            },
+            (CanIdFilterKind::ExtendedMask { id: want, mask }, CanId::Extended(id)) => {
+                id.raw() & mask.raw() == want.raw() & mask.raw()
+            },
             _ => false,
         }
     }
 
+    /// Smallest and largest IDs this filter can match.
     pub fn bounds(&self) -> (CanId, CanId) {
         match self.kind {
             CanIdFilterKind::Standard { min, max } => (CanId::from(min), CanId::from(max)),
             CanIdFilterKind::Extended { min, max } => (CanId::from(min), CanId::from(max)),
+            CanIdFilterKind::StandardMask { id, mask } => {
+                let min = id.raw() & mask.raw();
+                let max = min | (!mask.raw() & STANDARD_ID_MASK);
+                (
+                    CanId::Standard(StandardCanId::new(min as u32).expect("masked standard id")),
+                    CanId::Standard(StandardCanId::new(max as u32).expect("masked standard id")),
+                )
+            },
+            CanIdFilterKind::ExtendedMask { id, mask } => {
+                let min = id.raw() & mask.raw();
+                let max = min | (!mask.raw() & EXTENDED_ID_MASK);
+                (
+                    CanId::Extended(ExtendedCanId::new(min).expect("masked extended id")),
+                    CanId::Extended(ExtendedCanId::new(max).expect("masked extended id")),
+                )
+            },
+        }
+    }
+
+    fn wire_format(&self) -> u8 {
+        let shape = match self.kind {
+            CanIdFilterKind::Standard { .. } => 0,
+            CanIdFilterKind::Extended { .. } => FILTER_FORMAT_EXTENDED,
+            CanIdFilterKind::StandardMask { .. } => FILTER_FORMAT_MASK,
+            CanIdFilterKind::ExtendedMask { .. } => FILTER_FORMAT_MASK | FILTER_FORMAT_EXTENDED,
+        };
+        match self.mode {
+            CanIdFilterMode::Allow => shape,
+            CanIdFilterMode::Block => shape | FILTER_FORMAT_BLOCK,
         }
     }
 }
 
+const STANDARD_ID_MASK: u16 = 0x7FF;
+const EXTENDED_ID_MASK: u32 = 0x1FFF_FFFF;
+
+/// Applies a session filter list: an empty list accepts everything; otherwise an ID must
+/// match an allow entry (when any exist) and must not match a block entry.
+pub fn filters_accept(filters: &[CanIdFilter], id: CanId) -> bool {
+    let mut has_allow = false;
+    let mut allowed = false;
+    for filter in filters {
+        let matched = filter.matches(id);
+        match filter.mode {
+            CanIdFilterMode::Block if matched => return false,
+            CanIdFilterMode::Block => {},
+            CanIdFilterMode::Allow => {
+                has_allow = true;
+                allowed |= matched;
+            },
+        }
+    }
+    allowed || !has_allow
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BridgeStatus {
     pub device_state: BridgeDeviceState,
@@ -192,6 +303,21 @@ pub struct BridgeStatus {
     pub queue_drop_count: u64,
     pub inactive_enqueue_count: u64,
     pub session_replacement_discard_count: u64,
+    pub send_throttle_count: u64,
+    pub rx_frame_count: u64,
+    pub tx_frame_count: u64,
+}
+
+/// One connected bridge session as reported by `ListSessions`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BridgeSessionInfo {
+    pub session_id: u32,
+    pub role_granted: BridgeRole,
+    /// Transport peer label, e.g. `unix://peer` or `tls://10.0.0.2:51234`.
+    pub peer: String,
+    /// Milliseconds since the host last received a request from this session.
+    pub idle_ms: u32,
+    pub raw_frame_tap: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -226,6 +352,9 @@ pub enum ClientRequest {
     Ping {
         request_id: u32,
     },
+    ListSessions {
+        request_id: u32,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -255,6 +384,10 @@ pub enum ServerResponse {
         request_id: u32,
         holder_session_id: Option<u32>,
     },
+    SessionList {
+        request_id: u32,
+        sessions: Vec<BridgeSessionInfo>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -341,16 +474,17 @@ fn put_filters(buf: &mut Vec<u8>, filters: &[CanIdFilter]) -> Result<(), Protoco
         u16::try_from(filters.len()).map_err(|_| ProtocolError::InvalidData("too many filters"))?;
     put_u16(buf, count);
     for filter in filters {
+        put_u8(buf, filter.wire_format());
         match filter.kind {
-            CanIdFilterKind::Standard { min, max } => {
-                put_u8(buf, 0);
-                put_u32(buf, min.raw() as u32);
-                put_u32(buf, max.raw() as u32);
+            CanIdFilterKind::Standard { min: a, max: b }
+            | CanIdFilterKind::StandardMask { id: a, mask: b } => {
+                put_u32(buf, a.raw() as u32);
+                put_u32(buf, b.raw() as u32);
             },
-            CanIdFilterKind::Extended { min, max } => {
-                put_u8(buf, 1);
-                put_u32(buf, min.raw());
-                put_u32(buf, max.raw());
+            CanIdFilterKind::Extended { min: a, max: b }
+            | CanIdFilterKind::ExtendedMask { id: a, mask: b } => {
+                put_u32(buf, a.raw());
+                put_u32(buf, b.raw());
             },
         }
     }
@@ -439,22 +573,28 @@ impl<'a> Cursor<'a> {
             let format = self.u8()?;
             let min = self.u32()?;
             let max = self.u32()?;
-            let filter = match format {
-                0 => Self::wire_filter(CanIdFilter::standard(
-                    StandardCanId::new(min)
-                        .map_err(|err| map_frame_error("invalid bridge standard filter id", err))?,
-                    StandardCanId::new(max)
-                        .map_err(|err| map_frame_error("invalid bridge standard filter id", err))?,
-                ))?,
-                1 => Self::wire_filter(CanIdFilter::extended(
-                    ExtendedCanId::new(min)
-                        .map_err(|err| map_frame_error("invalid bridge extended filter id", err))?,
-                    ExtendedCanId::new(max)
-                        .map_err(|err| map_frame_error("invalid bridge extended filter id", err))?,
-                ))?,
+            let standard = |raw| {
+                StandardCanId::new(raw)
+                    .map_err(|err| map_frame_error("invalid bridge standard filter id", err))
+            };
+            let extended = |raw| {
+                ExtendedCanId::new(raw)
+                    .map_err(|err| map_frame_error("invalid bridge extended filter id", err))
+            };
+            let filter = match format & !FILTER_FORMAT_BLOCK {
+                0 => Self::wire_filter(CanIdFilter::standard(standard(min)?, standard(max)?))?,
+                FILTER_FORMAT_EXTENDED => {
+                    Self::wire_filter(CanIdFilter::extended(extended(min)?, extended(max)?))?
+                },
+                FILTER_FORMAT_MASK => CanIdFilter::standard_mask(standard(min)?, standard(max)?),
+                0x03 => CanIdFilter::extended_mask(extended(min)?, extended(max)?),
                 _ => return Err(ProtocolError::InvalidData("invalid bridge filter format")),
             };
-            filters.push(filter);
+            filters.push(if format & FILTER_FORMAT_BLOCK != 0 {
+                filter.blocking()
+            } else {
+                filter
+            });
         }
         Ok(filters)
     }
@@ -557,6 +697,10 @@ fn encode_payload_from_request(message: &ClientRequest) -> Result<Vec<u8>, Proto
             put_u8(&mut buf, TAG_PING);
             put_u32(&mut buf, *request_id);
         },
+        ClientRequest::ListSessions { request_id } => {
+            put_u8(&mut buf, TAG_LIST_SESSIONS);
+            put_u32(&mut buf, *request_id);
+        },
     }
     Ok(buf)
 }
@@ -605,6 +749,9 @@ fn encode_payload_from_server(message: &ServerMessage) -> Result<Vec<u8>, Protoc
             put_u64(&mut buf, status.queue_drop_count);
             put_u64(&mut buf, status.inactive_enqueue_count);
             put_u64(&mut buf, status.session_replacement_discard_count);
+            put_u64(&mut buf, status.send_throttle_count);
+            put_u64(&mut buf, status.rx_frame_count);
+            put_u64(&mut buf, status.tx_frame_count);
         },
         ServerMessage::Response(ServerResponse::LeaseGranted {
             request_id,
@@ -623,6 +770,23 @@ fn encode_payload_from_server(message: &ServerMessage) -> Result<Vec<u8>, Protoc
             put_u8(&mut buf, u8::from(holder_session_id.is_some()));
             put_u32(&mut buf, holder_session_id.unwrap_or_default());
         },
+        ServerMessage::Response(ServerResponse::SessionList {
+            request_id,
+            sessions,
+        }) => {
+            put_u8(&mut buf, TAG_SESSION_LIST);
+            put_u32(&mut buf, *request_id);
+            let count = u16::try_from(sessions.len())
+                .map_err(|_| ProtocolError::InvalidData("too many sessions"))?;
+            put_u16(&mut buf, count);
+            for session in sessions {
+                put_u32(&mut buf, session.session_id);
+                put_u8(&mut buf, session.role_granted as u8);
+                put_string(&mut buf, &session.peer)?;
+                put_u32(&mut buf, session.idle_ms);
+                put_u8(&mut buf, u8::from(session.raw_frame_tap));
+            }
+        },
         ServerMessage::Event(BridgeEvent::ReceiveFrame(frame)) => {
             put_u8(&mut buf, TAG_EVENT_RECEIVE_FRAME);
             put_frame_event(&mut buf, frame);
@@ -689,6 +853,7 @@ pub fn decode_client_request(payload: &[u8]) -> Result<ClientRequest, ProtocolEr
             frame: cursor.frame_request()?,
         },
         TAG_PING => ClientRequest::Ping { request_id },
+        TAG_LIST_SESSIONS => ClientRequest::ListSessions { request_id },
         other => return Err(ProtocolError::InvalidTag(other)),
     };
     if cursor.remaining() != 0 {
@@ -740,6 +905,9 @@ pub fn decode_server_message(payload: &[u8]) -> Result<ServerMessage, ProtocolEr
                     queue_drop_count: cursor.u64()?,
                     inactive_enqueue_count: cursor.u64()?,
                     session_replacement_discard_count: cursor.u64()?,
+                    send_throttle_count: cursor.u64()?,
+                    rx_frame_count: cursor.u64()?,
+                    tx_frame_count: cursor.u64()?,
                 },
             })
         },
@@ -759,6 +927,24 @@ pub fn decode_server_message(payload: &[u8]) -> Result<ServerMessage, ProtocolEr
                 holder_session_id: if has_holder { Some(holder) } else { None },
             })
         },
+        TAG_SESSION_LIST => {
+            let request_id = cursor.u32()?;
+            let count = cursor.u16()? as usize;
+            let mut sessions = Vec::with_capacity(count);
+            for _ in 0..count {
+                sessions.push(BridgeSessionInfo {
+                    session_id: cursor.u32()?,
+                    role_granted: BridgeRole::from_u8(cursor.u8()?)?,
+                    peer: cursor.string()?,
+                    idle_ms: cursor.u32()?,
+                    raw_frame_tap: decode_bool(cursor.u8()?, "session raw frame tap")?,
+                });
+            }
+            ServerMessage::Response(ServerResponse::SessionList {
+                request_id,
+                sessions,
+            })
+        },
         TAG_EVENT_RECEIVE_FRAME => {
             ServerMessage::Event(BridgeEvent::ReceiveFrame(cursor.frame_event()?))
         },
@@ -825,6 +1011,9 @@ mod tests {
             queue_drop_count: 5,
             inactive_enqueue_count: 6,
             session_replacement_discard_count: 7,
+            send_throttle_count: 8,
+            rx_frame_count: 9,
+            tx_frame_count: 10,
         }
     }
 
@@ -950,6 +1139,35 @@ mod tests {
         assert_eq!(decoded, message);
     }
 
+    #[test]
+    fn session_list_roundtrip() {
+        let request = ClientRequest::ListSessions { request_id: 12 };
+        let encoded = encode_client_request(&request).unwrap();
+        assert_eq!(decode_client_request(&encoded[4..]).unwrap(), request);
+
+        let message = ServerMessage::Response(ServerResponse::SessionList {
+            request_id: 12,
+            sessions: vec![
+                BridgeSessionInfo {
+                    session_id: 1,
+                    role_granted: BridgeRole::Observer,
+                    peer: "unix://peer".to_string(),
+                    idle_ms: 250,
+                    raw_frame_tap: true,
+                },
+                BridgeSessionInfo {
+                    session_id: 2,
+                    role_granted: BridgeRole::WriterCandidate,
+                    peer: "tls://127.0.0.1:50000".to_string(),
+                    idle_ms: 0,
+                    raw_frame_tap: false,
+                },
+            ],
+        });
+        let encoded = encode_server_message(&message).unwrap();
+        assert_eq!(decode_server_message(&encoded[4..]).unwrap(), message);
+    }
+
     #[test]
     fn lease_denied_rejects_noncanonical_has_holder() {
         let mut payload = Vec::new();
@@ -1120,7 +1338,7 @@ mod tests {
         put_u8(&mut payload, 0x0A);
         put_u32(&mut payload, 8);
         put_u16(&mut payload, 1);
-        put_u8(&mut payload, 2);
+        put_u8(&mut payload, 0x04);
         put_u32(&mut payload, 0x100);
         put_u32(&mut payload, 0x1FF);
 
@@ -1196,6 +1414,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn mask_and_block_filters_roundtrip_and_apply_allow_then_block() {
+        let allow_feedback = CanIdFilter::standard_mask(
+            StandardCanId::new(0x2A0).unwrap(),
+            StandardCanId::new(0x7F0).unwrap(),
+        );
+        let block_one = CanIdFilter::standard(
+            StandardCanId::new(0x2A5).unwrap(),
+            StandardCanId::new(0x2A5).unwrap(),
+        )
+        .unwrap()
+        .blocking();
+        let block_extended = CanIdFilter::extended_mask(
+            ExtendedCanId::new(0x100).unwrap(),
+            ExtendedCanId::new(0x1FFF_FF00).unwrap(),
+        )
+        .blocking();
+        let filters = vec![allow_feedback, block_one, block_extended];
+
+        let request = ClientRequest::SetFilters {
+            request_id: 3,
+            filters: filters.clone(),
+        };
+        let encoded = encode_client_request(&request).unwrap();
+        assert_eq!(decode_client_request(&encoded[4..]).unwrap(), request);
+
+        let std_id = |raw| CanId::standard(raw).unwrap();
+        assert!(filters_accept(&filters, std_id(0x2A1)));
+        assert!(!filters_accept(&filters, std_id(0x2A5)));
+        assert!(!filters_accept(&filters, std_id(0x251)));
+        assert!(filters_accept(&[], std_id(0x251)));
+
+        let block_only = [block_extended];
+        assert!(filters_accept(&block_only, std_id(0x100)));
+        assert!(!filters_accept(
+            &block_only,
+            CanId::extended(0x1AB).unwrap()
+        ));
+        assert!(filters_accept(&block_only, CanId::extended(0x2AB).unwrap()));
+
+        assert_eq!(allow_feedback.bounds(), (std_id(0x2A0), std_id(0x2AF)));
+    }
+
     #[test]
     fn valid_filters_expose_typed_bounds_for_encoding() {
         let filter = CanIdFilter::extended(
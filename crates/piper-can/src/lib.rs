@@ -6,11 +6,20 @@ use std::time::{Duration, Instant};
 use thiserror::Error;
 
 // 重新导出 piper-protocol 中的 typed frame primitives.
-pub use piper_protocol::{CanData, CanId, ExtendedCanId, FrameError, PiperFrame, StandardCanId};
+pub use piper_protocol::{
+    CanData, CanId, ExtendedCanId, FrameError, PiperFrame, PiperFrameBuilder, StandardCanId,
+};
 
+pub mod frame_trace;
 pub mod raw_timestamp;
 pub use raw_timestamp::{RawTimestampInfo, RawTimestampSample, monotonic_micros};
 
+// tokio 异步适配器（可选）
+#[cfg(feature = "async")]
+pub mod async_adapter;
+#[cfg(feature = "async")]
+pub use async_adapter::{AsyncCanAdapter, BlockingAsyncAdapter};
+
 // SocketCAN (Linux only)
 // 优先级：mock 优先级最高，然后是显式 feature，最后是 auto-backend
 #[cfg(all(
@@ -19,11 +28,19 @@ pub use raw_timestamp::{RawTimestampInfo, RawTimestampSample, monotonic_micros};
 ))]
 pub mod socketcan;
 
+// vcan 测试辅助（需要 SocketCAN 后端）
+#[cfg(all(
+    target_os = "linux",
+    any(feature = "socketcan", feature = "auto-backend"),
+    any(test, feature = "test-helpers")
+))]
+pub mod testutil;
+
 #[cfg(all(
     target_os = "linux",
     any(feature = "socketcan", feature = "auto-backend")
 ))]
-pub use socketcan::SocketCanAdapter;
+pub use socketcan::{SocketCanAdapter, SocketCanConfig, TimestampMode};
 
 #[cfg(all(
     target_os = "linux",
@@ -31,6 +48,13 @@ pub use socketcan::SocketCanAdapter;
 ))]
 pub use socketcan::split::{SocketCanRxAdapter, SocketCanTxAdapter};
 
+#[cfg(all(
+    target_os = "linux",
+    feature = "async",
+    any(feature = "socketcan", feature = "auto-backend")
+))]
+pub use socketcan::AsyncSocketCanAdapter;
+
 // GS-USB (所有平台)
 // 优先级：mock 优先级最高，然后是显式 feature，最后是 auto-backend
 #[cfg(any(
@@ -40,7 +64,7 @@ pub use socketcan::split::{SocketCanRxAdapter, SocketCanTxAdapter};
 pub mod gs_usb;
 
 #[cfg(any(feature = "gs_usb", feature = "auto-backend"))]
-pub use gs_usb::GsUsbCanAdapter;
+pub use gs_usb::{GsUsbCanAdapter, GsUsbCapabilities, RxQueueOverflowPolicy};
 
 // Controller-owned bridge client (UnixStream/TCP)
 // Non-realtime debug / record / replay path only.
@@ -48,16 +72,17 @@ mod gs_usb_bridge;
 pub mod bridge {
     pub use super::gs_usb_bridge::protocol;
     pub use super::gs_usb_bridge::{
-        BridgeClientOptions, BridgeEndpoint, BridgeError, BridgeResult, BridgeTlsClientConfig,
-        GsUsbBridgeClient as BridgeClient, WriterLease as MaintenanceLease,
+        BridgeClientOptions, BridgeEndpoint, BridgeError, BridgeReconnectPolicy, BridgeResult,
+        BridgeTlsClientConfig, GsUsbBridgeClient as BridgeClient, WriterLease as MaintenanceLease,
     };
 }
 pub use bridge::protocol::{
-    BridgeDeviceState, BridgeEvent, BridgeRole, BridgeStatus, CanIdFilter, ErrorCode, SessionToken,
+    BridgeDeviceState, BridgeEvent, BridgeRole, BridgeSessionInfo, BridgeStatus, CanIdFilter,
+    CanIdFilterMode, ErrorCode, SessionToken,
 };
 pub use bridge::{
-    BridgeClient, BridgeClientOptions, BridgeEndpoint, BridgeError, BridgeResult,
-    BridgeTlsClientConfig, MaintenanceLease,
+    BridgeClient, BridgeClientOptions, BridgeEndpoint, BridgeError, BridgeReconnectPolicy,
+    BridgeResult, BridgeTlsClientConfig, MaintenanceLease,
 };
 
 // 导出 split 相关的类型（如果可用）
@@ -150,6 +175,27 @@ impl ReceivedFrame {
     }
 }
 
+/// TX Echo 统计快照
+///
+/// 由支持 TX Echo 跟踪的后端（GS-USB）产生，经 [`RxAdapter::tx_echo_stats`] 暴露给驱动层指标。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TxEchoStats {
+    /// 收到匹配 Echo 的发送数
+    pub completed: u64,
+    /// Echo 到达前槽位被复用的发送数（视为丢失）
+    pub lost: u64,
+    /// 无法匹配在途记录的 Echo 数（未知 `echo_id` 或 CAN ID 不符）
+    pub unmatched: u64,
+    /// 当前仍在等待 Echo 的发送数
+    pub in_flight: usize,
+    /// 最近一次 TX→Echo 延迟（微秒）
+    pub last_latency_us: u64,
+    /// 最大 TX→Echo 延迟（微秒）
+    pub max_latency_us: u64,
+    /// 平均 TX→Echo 延迟（微秒）
+    pub mean_latency_us: u64,
+}
+
 /// 设备/后端错误的结构化分类
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CanDeviceErrorKind {
@@ -202,6 +248,20 @@ impl From<&str> for CanDeviceError {
     }
 }
 
+/// [`CanAdapter::send_batch`] 的逐帧实现（默认实现与后端回退共用）
+pub(crate) fn send_frames_individually<A: CanAdapter + ?Sized>(
+    adapter: &mut A,
+    frames: &[PiperFrame],
+) -> Result<usize, CanError> {
+    for (sent, frame) in frames.iter().enumerate() {
+        if let Err(error) = adapter.send(*frame) {
+            // 已发送的帧优先报告；错误留给下一次调用暴露
+            return if sent == 0 { Err(error) } else { Ok(sent) };
+        }
+    }
+    Ok(frames.len())
+}
+
 pub trait CanAdapter {
     fn send(&mut self, frame: PiperFrame) -> Result<(), CanError>;
     fn receive(&mut self) -> Result<ReceivedFrame, CanError>;
@@ -220,6 +280,63 @@ pub trait CanAdapter {
     fn send_timeout(&mut self, frame: PiperFrame, _timeout: Duration) -> Result<(), CanError> {
         self.send(frame)
     }
+    /// 设置 `send()` 的写超时（默认空操作）
+    fn set_send_timeout(&mut self, _timeout: Duration) {}
+
+    /// 批量发送：按顺序发送 `frames`，返回被接受的帧数
+    ///
+    /// 部分帧已发送后遇到错误（如发送缓冲满）时返回已发送的帧数，剩余帧由调用方重试；
+    /// 首帧即失败时返回错误。默认实现逐帧调用 `send`；后端可覆盖为一次底层写入。
+    fn send_batch(&mut self, frames: &[PiperFrame]) -> Result<usize, CanError> {
+        send_frames_individually(self, frames)
+    }
+
+    /// 批量接收：最多等待 `timeout` 收到第一帧，再取走当前已就绪的帧（最多 `max` 帧）
+    ///
+    /// 超时未收到任何帧时返回空 `Vec`。默认实现循环调用 `try_receive`；
+    /// 后端可覆盖为一次底层读取。
+    fn receive_batch(
+        &mut self,
+        max: usize,
+        timeout: Duration,
+    ) -> Result<Vec<ReceivedFrame>, CanError> {
+        let mut frames = Vec::new();
+        if max == 0 {
+            return Ok(frames);
+        }
+        match self.receive_timeout(timeout) {
+            Ok(frame) => frames.push(frame),
+            Err(CanError::Timeout) => return Ok(frames),
+            Err(e) => return Err(e),
+        }
+        while frames.len() < max {
+            // 已收到的帧优先返回；错误留给下一次调用暴露
+            match self.try_receive() {
+                Ok(Some(frame)) => frames.push(frame),
+                Ok(None) | Err(_) => break,
+            }
+        }
+        Ok(frames)
+    }
+
+    /// 非阻塞地读取并丢弃当前已缓冲的所有接收帧，返回丢弃的帧数
+    ///
+    /// 用于开始控制前清除使能前残留的旧反馈。默认实现循环调用 `try_receive`。
+    fn drain_rx(&mut self) -> Result<usize, CanError> {
+        let mut drained = 0;
+        while self.try_receive()?.is_some() {
+            drained += 1;
+        }
+        Ok(drained)
+    }
+
+    /// 致命错误（如 `BufferOverflow`、`BusOff`）后的统一恢复入口
+    ///
+    /// 清除适配器内部错误状态并按原配置重新启动设备；设备确实已不存在时返回错误。
+    /// 默认实现没有需要恢复的状态，直接返回 `Ok(())`。
+    fn reset(&mut self) -> Result<(), CanError> {
+        Ok(())
+    }
 }
 
 pub trait RxAdapter {
@@ -241,6 +358,14 @@ pub trait RxAdapter {
     ) -> Result<Option<BackendCapability>, CanError> {
         Ok(None)
     }
+
+    /// TX Echo 延迟与丢失统计
+    ///
+    /// GS-USB split 后 TX / RX 共享同一在途表，由 RX 侧暴露给驱动层指标；
+    /// 不跟踪 TX Echo 的后端返回 `None`。
+    fn tx_echo_stats(&self) -> Option<TxEchoStats> {
+        None
+    }
 }
 
 impl<T> RxAdapter for Box<T>
@@ -261,6 +386,10 @@ where
     ) -> Result<Option<BackendCapability>, CanError> {
         (**self).startup_probe_until(deadline)
     }
+
+    fn tx_echo_stats(&self) -> Option<TxEchoStats> {
+        (**self).tx_echo_stats()
+    }
 }
 
 /// 实时控制专用 TX 适配器。
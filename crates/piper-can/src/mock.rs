@@ -498,4 +498,48 @@ mod tests {
         let frame = rx.receive().unwrap().frame;
         assert_eq!(frame.raw_id(), 0x123);
     }
+
+    #[test]
+    fn test_mock_adapter_send_batch_default_impl() {
+        let mut adapter = MockCanAdapter::new();
+        let frames: Vec<_> = (0x100..0x104).map(|id| standard_frame(id, &[1])).collect();
+
+        assert_eq!(adapter.send_batch(&frames).unwrap(), 4);
+        assert_eq!(adapter.send_batch(&[]).unwrap(), 0);
+        let received = adapter.receive_batch(10, Duration::from_millis(1)).unwrap();
+        assert_eq!(
+            received.iter().map(|received| received.frame.raw_id()).collect::<Vec<_>>(),
+            vec![0x100, 0x101, 0x102, 0x103]
+        );
+    }
+
+    #[test]
+    fn test_mock_adapter_receive_batch_default_impl() {
+        let mut adapter = MockCanAdapter::new();
+        for id in 0x100..0x105 {
+            adapter.inject(standard_frame(id, &[1]));
+        }
+        let timeout = Duration::from_millis(1);
+
+        let batch = adapter.receive_batch(3, timeout).unwrap();
+        assert_eq!(
+            batch.iter().map(|received| received.frame.raw_id()).collect::<Vec<_>>(),
+            vec![0x100, 0x101, 0x102]
+        );
+        assert_eq!(adapter.receive_batch(10, timeout).unwrap().len(), 2);
+        assert!(adapter.receive_batch(10, timeout).unwrap().is_empty());
+        assert!(adapter.receive_batch(0, timeout).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_mock_adapter_drain_rx_default_impl() {
+        let mut adapter = MockCanAdapter::new();
+        for id in 0x100..0x104 {
+            adapter.inject(standard_frame(id, &[1]));
+        }
+
+        assert_eq!(adapter.drain_rx().unwrap(), 4);
+        assert!(adapter.is_empty());
+        assert_eq!(adapter.drain_rx().unwrap(), 0);
+    }
 }
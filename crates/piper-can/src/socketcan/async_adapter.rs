@@ -0,0 +1,81 @@
+//! 基于 tokio `AsyncFd` 的 SocketCAN 异步适配器
+//!
+//! socket 切换为非阻塞模式并注册到 tokio reactor，收发都由就绪事件驱动，
+//! 不占用额外线程。时间戳提取与错误帧过滤复用 [`SocketCanAdapter`] 的实现。
+
+use super::{SocketCanAdapter, to_socketcan_frame};
+use crate::async_adapter::AsyncCanAdapter;
+use crate::{CanError, PiperFrame, ReceivedFrame};
+use socketcan::Socket;
+use std::time::Duration;
+use tokio::io::unix::AsyncFd;
+
+/// SocketCAN 异步适配器
+///
+/// `receive` 是取消安全的：只有在 socket 就绪后才会同步读取一帧。
+///
+/// # 示例
+///
+/// ```no_run
+/// # async fn example() -> Result<(), piper_can::CanError> {
+/// use piper_can::{AsyncCanAdapter, AsyncSocketCanAdapter};
+///
+/// let mut adapter = AsyncSocketCanAdapter::new("can0")?;
+/// let frame = adapter.receive().await?;
+/// println!("0x{:03X}", frame.frame.raw_id());
+/// # Ok(())
+/// # }
+/// ```
+pub struct AsyncSocketCanAdapter {
+    inner: AsyncFd<SocketCanAdapter>,
+}
+
+impl AsyncSocketCanAdapter {
+    /// 打开 CAN 接口并注册到当前 tokio 运行时
+    ///
+    /// # 错误
+    ///
+    /// 与 [`SocketCanAdapter::new`] 相同；不在 tokio 运行时内调用时返回 `CanError::Io`。
+    pub fn new(interface: impl Into<String>) -> Result<Self, CanError> {
+        Self::from_adapter(SocketCanAdapter::new(interface)?)
+    }
+
+    /// 把已打开的阻塞适配器转换为异步适配器
+    pub fn from_adapter(mut adapter: SocketCanAdapter) -> Result<Self, CanError> {
+        adapter.socket.set_nonblocking(true).map_err(CanError::Io)?;
+        // 就绪后才读取，poll 不应再等待
+        adapter.read_timeout = Duration::ZERO;
+        Ok(Self {
+            inner: AsyncFd::new(adapter).map_err(CanError::Io)?,
+        })
+    }
+
+    /// 底层阻塞适配器（只读访问，例如查询接口名或时间戳能力）
+    pub fn get_ref(&self) -> &SocketCanAdapter {
+        self.inner.get_ref()
+    }
+}
+
+impl AsyncCanAdapter for AsyncSocketCanAdapter {
+    async fn send(&mut self, frame: PiperFrame) -> Result<(), CanError> {
+        let can_frame = to_socketcan_frame(&frame)?;
+        loop {
+            let mut guard = self.inner.writable().await.map_err(CanError::Io)?;
+            match guard.try_io(|inner| inner.get_ref().socket.write_frame(&can_frame)) {
+                Ok(result) => return result.map_err(CanError::Io),
+                Err(_would_block) => continue,
+            }
+        }
+    }
+
+    async fn receive(&mut self) -> Result<ReceivedFrame, CanError> {
+        loop {
+            let mut guard = self.inner.readable_mut().await.map_err(CanError::Io)?;
+            match guard.get_inner_mut().receive_with_timestamp() {
+                // 非阻塞读返回 EAGAIN（或只读到错误帧）：清除就绪状态后继续等待
+                Err(CanError::Timeout) => guard.clear_ready(),
+                result => return result,
+            }
+        }
+    }
+}
@@ -4,6 +4,7 @@
 //!
 //! 此模块仅提供检查功能，不进行任何配置操作，因此不需要特殊权限。
 
+use super::netlink::{self, FdGuard};
 use crate::CanError;
 use libc::{AF_INET, IFF_UP, SIOCGIFFLAGS, SOCK_DGRAM, if_nametoindex, ifreq};
 use std::ffi::CString;
@@ -26,32 +27,7 @@ use tracing::trace;
 /// # 权限要求
 /// 此函数只进行读取操作，普通用户即可执行，不需要 root 或 CAP_NET_ADMIN 权限。
 pub fn check_interface_status(interface: &str) -> Result<bool, CanError> {
-    // 0. 先检查接口名长度（必须在调用 if_nametoindex 之前检查）
-    // ifr_name 通常是 IFNAMSIZ = 16 字节，包括结尾的 NUL，所以最大长度是 15
-    const MAX_IFACE_NAME_LEN: usize = 15; // IFNAMSIZ - 1
-    if interface.len() > MAX_IFACE_NAME_LEN {
-        return Err(CanError::Device(
-            format!(
-                "Interface name '{}' is too long (max {} characters)",
-                interface, MAX_IFACE_NAME_LEN
-            )
-            .into(),
-        ));
-    }
-
-    // 1. 检查接口名是否包含 NUL 字符
-    let c_iface = CString::new(interface)
-        .map_err(|e| CanError::Device(format!("Invalid interface name: {}", e).into()))?;
-
-    // 2. 检查接口是否存在
-    let ifindex = unsafe { if_nametoindex(c_iface.as_ptr()) };
-    if ifindex == 0 {
-        let errno = io::Error::last_os_error();
-        return Err(CanError::Device(format!(
-            "CAN interface '{}' does not exist ({}). Please create it first:\n  sudo ip link add dev {} type can",
-            interface, errno, interface
-        ).into()));
-    }
+    interface_index(interface)?;
 
     // 3. 准备 ifreq 结构
     let mut ifr: ifreq = unsafe { std::mem::zeroed() };
@@ -68,17 +44,7 @@ pub fn check_interface_status(interface: &str) -> Result<bool, CanError> {
         ifr.ifr_name[c_iface_bytes.len()] = 0;
     }
 
-    // 3. 创建 socket 用于 ioctl
-    // 使用 RAII 确保 socket 被正确关闭
-    struct FdGuard(libc::c_int);
-    impl Drop for FdGuard {
-        fn drop(&mut self) {
-            if self.0 >= 0 {
-                unsafe { libc::close(self.0) };
-            }
-        }
-    }
-
+    // 3. 创建 socket 用于 ioctl（FdGuard 确保 socket 被正确关闭）
     let sockfd = unsafe { libc::socket(AF_INET, SOCK_DGRAM, 0) };
     if sockfd < 0 {
         return Err(CanError::Io(io::Error::last_os_error()));
@@ -117,6 +83,132 @@ pub fn check_interface_status(interface: &str) -> Result<bool, CanError> {
     Ok(is_up)
 }
 
+/// 校验接口名并返回接口索引
+///
+/// - `Err(CanError::Device)`: 接口名过长/无效，或接口不存在
+fn interface_index(interface: &str) -> Result<u32, CanError> {
+    // 0. 先检查接口名长度（必须在调用 if_nametoindex 之前检查）
+    // ifr_name 通常是 IFNAMSIZ = 16 字节，包括结尾的 NUL，所以最大长度是 15
+    const MAX_IFACE_NAME_LEN: usize = 15; // IFNAMSIZ - 1
+    if interface.len() > MAX_IFACE_NAME_LEN {
+        return Err(CanError::Device(
+            format!(
+                "Interface name '{}' is too long (max {} characters)",
+                interface, MAX_IFACE_NAME_LEN
+            )
+            .into(),
+        ));
+    }
+
+    // 1. 检查接口名是否包含 NUL 字符
+    let c_iface = CString::new(interface)
+        .map_err(|e| CanError::Device(format!("Invalid interface name: {}", e).into()))?;
+
+    // 2. 检查接口是否存在
+    let ifindex = unsafe { if_nametoindex(c_iface.as_ptr()) };
+    if ifindex == 0 {
+        let errno = io::Error::last_os_error();
+        return Err(CanError::Device(format!(
+            "CAN interface '{}' does not exist ({}). Please create it first:\n  sudo ip link add dev {} type can",
+            interface, errno, interface
+        ).into()));
+    }
+    Ok(ifindex)
+}
+
+/// `IFLA_CAN_CTRLMODE` 中的 listen-only 标志（`CAN_CTRLMODE_LISTENONLY`）
+pub const CAN_CTRLMODE_LISTENONLY: u32 = 0x02;
+
+/// 通过 rtnetlink 读取的 CAN 接口链路信息
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CanLinkInfo {
+    /// 链路类型（`IFLA_INFO_KIND`，如 "can"、"vcan"、"vxcan"）
+    pub kind: Option<String>,
+    /// 接口 MTU（`IFLA_MTU`；经典 CAN 为 16，CAN FD 为 72）
+    pub mtu: Option<u32>,
+    /// 控制器模式标志（`IFLA_CAN_CTRLMODE.flags`，仅真实 CAN 控制器提供）
+    pub ctrlmode: Option<u32>,
+}
+
+impl CanLinkInfo {
+    /// 是否为虚拟接口（vcan/vxcan 没有物理总线，不会发送 ACK）
+    pub fn is_virtual(&self) -> bool {
+        matches!(self.kind.as_deref(), Some("vcan" | "vxcan"))
+    }
+
+    /// 控制器是否处于 listen-only 模式
+    pub fn is_listen_only(&self) -> bool {
+        self.ctrlmode.is_some_and(|flags| flags & CAN_CTRLMODE_LISTENONLY != 0)
+    }
+}
+
+/// 通过 rtnetlink（`RTM_GETLINK`）查询接口链路信息
+///
+/// 与 [`check_interface_status`] 一样只读，不需要 CAP_NET_ADMIN。
+pub fn query_can_link_info(interface: &str) -> Result<CanLinkInfo, CanError> {
+    let ifindex = interface_index(interface)?;
+    let request = netlink::link_message(
+        libc::RTM_GETLINK,
+        libc::NLM_F_REQUEST as u16,
+        ifindex as i32,
+        0,
+        0,
+        &[],
+    );
+    let info = parse_link_response(&netlink::request(&request)?)?;
+    trace!("Interface '{}' link info: {:?}", interface, info);
+    Ok(info)
+}
+
+/// 解析 `RTM_NEWLINK` 应答
+fn parse_link_response(buf: &[u8]) -> Result<CanLinkInfo, CanError> {
+    use netlink::{
+        IFINFOMSG_LEN, IFLA_CAN_CTRLMODE, IFLA_INFO_DATA, IFLA_INFO_KIND, IFLA_LINKINFO, IFLA_MTU,
+        NLMSG_HDR_LEN, rtattrs,
+    };
+
+    let (msg_type, msg) = netlink::first_message(buf)?;
+    if msg_type == libc::NLMSG_ERROR as u16 {
+        netlink::error_result(msg)?;
+        return Err(netlink::malformed());
+    }
+    if msg_type != libc::RTM_NEWLINK {
+        return Err(netlink::malformed());
+    }
+
+    let mut info = CanLinkInfo::default();
+    let attrs = msg.get(NLMSG_HDR_LEN + IFINFOMSG_LEN..).ok_or_else(netlink::malformed)?;
+    for (kind, payload) in rtattrs(attrs) {
+        match kind {
+            IFLA_MTU if payload.len() >= 4 => {
+                info.mtu = Some(u32::from_ne_bytes(payload[0..4].try_into().unwrap()));
+            },
+            IFLA_LINKINFO => {
+                for (kind, payload) in rtattrs(payload) {
+                    match kind {
+                        IFLA_INFO_KIND => {
+                            let name = payload.split(|&b| b == 0).next().unwrap_or_default();
+                            info.kind = Some(String::from_utf8_lossy(name).into_owned());
+                        },
+                        IFLA_INFO_DATA => {
+                            for (kind, payload) in rtattrs(payload) {
+                                // struct can_ctrlmode { __u32 mask; __u32 flags; }
+                                if kind == IFLA_CAN_CTRLMODE && payload.len() >= 8 {
+                                    info.ctrlmode =
+                                        Some(u32::from_ne_bytes(payload[4..8].try_into().unwrap()));
+                                }
+                            }
+                        },
+                        _ => {},
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+    Ok(info)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -266,4 +358,63 @@ mod tests {
             panic!("Expected Device error, got: {:?}", result);
         }
     }
+
+    fn rtattr(kind: u16, payload: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        netlink::push_rtattr(&mut out, kind, payload);
+        out
+    }
+
+    fn newlink_message(attrs: &[u8]) -> Vec<u8> {
+        netlink::link_message(libc::RTM_NEWLINK, 0, 1, 0, 0, attrs)
+    }
+
+    #[test]
+    fn test_parse_link_response_extracts_kind_mtu_and_ctrlmode() {
+        let mut ctrlmode = Vec::new();
+        ctrlmode.extend_from_slice(&0xffu32.to_ne_bytes());
+        ctrlmode.extend_from_slice(&CAN_CTRLMODE_LISTENONLY.to_ne_bytes());
+        let info_data = rtattr(5, &ctrlmode);
+        let mut link_info = rtattr(1, b"can\0");
+        // IFLA_INFO_DATA 带 NLA_F_NESTED 标志
+        link_info.extend(rtattr(
+            netlink::IFLA_INFO_DATA | netlink::NLA_F_NESTED,
+            &info_data,
+        ));
+        let mut attrs = rtattr(4, &16u32.to_ne_bytes());
+        attrs.extend(rtattr(18, &link_info));
+
+        let info = parse_link_response(&newlink_message(&attrs)).unwrap();
+        assert_eq!(info.kind.as_deref(), Some("can"));
+        assert_eq!(info.mtu, Some(16));
+        assert!(info.is_listen_only());
+        assert!(!info.is_virtual());
+
+        let attrs = rtattr(18, &rtattr(1, b"vcan\0"));
+        let info = parse_link_response(&newlink_message(&attrs)).unwrap();
+        assert!(info.is_virtual());
+        assert!(!info.is_listen_only());
+    }
+
+    #[test]
+    fn test_parse_link_response_reports_netlink_error() {
+        let mut msg = vec![0u8; 36];
+        msg[0..4].copy_from_slice(&36u32.to_ne_bytes());
+        msg[4..6].copy_from_slice(&(libc::NLMSG_ERROR as u16).to_ne_bytes());
+        msg[16..20].copy_from_slice(&(-libc::ENODEV).to_ne_bytes());
+        assert!(matches!(
+            parse_link_response(&msg),
+            Err(CanError::Io(e)) if e.raw_os_error() == Some(libc::ENODEV)
+        ));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_query_can_link_info_on_loopback() {
+        // lo 总是存在：验证 rtnetlink 往返，且非 CAN 接口没有 ctrlmode
+        let info = query_can_link_info("lo").unwrap();
+        assert!(info.mtu.is_some());
+        assert_eq!(info.ctrlmode, None);
+        assert!(!info.is_virtual());
+    }
 }
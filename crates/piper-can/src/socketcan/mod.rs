@@ -28,7 +28,6 @@ use crate::{
 };
 use nix::poll::{PollFd, PollFlags, PollTimeout, poll};
 use nix::sys::socket::{ControlMessageOwned, MsgFlags, SockaddrStorage, recvmsg};
-use raw_frame::{ParsedSocketCanFrame, parse_libc_can_frame_bytes};
 use socketcan::{BlockingCan, CanFrame, CanSocket, EmbeddedFrame, ExtendedId, Socket, StandardId};
 use std::io::IoSliceMut;
 use std::mem;
@@ -39,11 +38,23 @@ use tracing::{trace, warn};
 const CLASSIC_CAN_MTU: usize = mem::size_of::<libc::can_frame>();
 const CANFD_MTU: usize = mem::size_of::<libc::canfd_frame>();
 
+#[cfg(feature = "async")]
+mod async_adapter;
 mod interface_check;
+pub(crate) mod netlink;
 mod raw_frame;
 pub mod split;
 
-use interface_check::check_interface_status;
+#[cfg(not(feature = "test-helpers"))]
+use raw_frame::{ParsedSocketCanFrame, parse_libc_can_frame_bytes};
+/// 原始 `can_frame` 字节解析（`test-helpers`，供 `socketcan_rx_parse` 基准使用）
+#[cfg(feature = "test-helpers")]
+pub use raw_frame::{ParsedSocketCanFrame, parse_libc_can_frame_bytes};
+
+#[cfg(feature = "async")]
+pub use async_adapter::AsyncSocketCanAdapter;
+
+use interface_check::{check_interface_status, query_can_link_info};
 pub use split::{SocketCanRxAdapter, SocketCanTxAdapter};
 
 #[derive(Debug, Clone, Copy)]
@@ -69,6 +80,59 @@ fn timestamp_provenance_from_parts(
     }
 }
 
+/// SocketCAN socket 配置
+///
+/// 在 [`SocketCanAdapter::with_config`] 打开接口时应用；`None` 表示保持内核默认值。
+///
+/// # 缓冲区大小
+///
+/// `SO_RCVBUF` / `SO_SNDBUF` 以字节为单位。内核会将设置值**翻倍**（预留簿记开销），
+/// 因此读回的值约为设置值的 2 倍；同时会被 `net.core.rmem_max` / `net.core.wmem_max`
+/// 截断，需要更大的缓冲区时请先调整 sysctl。增大 RX 缓冲区可以吸收控制线程短暂停顿
+/// 期间的突发帧，避免内核队列溢出。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SocketCanConfig {
+    /// `SO_RCVBUF`（字节）
+    pub recv_buffer_size: Option<usize>,
+    /// `SO_SNDBUF`（字节）
+    pub send_buffer_size: Option<usize>,
+    /// 接收时间戳来源
+    pub timestamp_mode: TimestampMode,
+}
+
+/// SocketCAN 接收时间戳来源
+///
+/// 决定设置哪些 `SOF_TIMESTAMPING` 标志，以及 `PiperFrame::timestamp_us` 的取值。
+/// 仅影响 [`SocketCanAdapter`] 自身的接收路径；`split()` 后的 RX 适配器继承 socket 上的
+/// 标志，但仍按内核时间戳解析（实时链路要求可信的内核时间戳）。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TimestampMode {
+    /// 硬件时间戳（`hw_trans`）优先，不可用时降级到内核软件时间戳（默认）
+    #[default]
+    HardwarePreferred,
+    /// 只使用内核软件时间戳（CLOCK_REALTIME），忽略硬件时间戳
+    SoftwareOnly,
+    /// 使用 `recvmsg` 返回后的主机单调时钟（`CLOCK_MONOTONIC`），不受 RTC 同步影响；
+    /// 来源标记为 `TimestampProvenance::Userspace`，内核时间戳仍保留在 `raw_timestamp` 中
+    Monotonic,
+    /// 不启用 `SO_TIMESTAMPING`，时间戳恒为 0
+    None,
+}
+
+impl TimestampMode {
+    /// 该模式需要设置的 `SO_TIMESTAMPING` 标志；`None` 表示不设置
+    fn timestamping_flags(self) -> Option<u32> {
+        let software = libc::SOF_TIMESTAMPING_RX_SOFTWARE | libc::SOF_TIMESTAMPING_SOFTWARE;
+        match self {
+            Self::HardwarePreferred => Some(
+                libc::SOF_TIMESTAMPING_RX_HARDWARE | libc::SOF_TIMESTAMPING_RAW_HARDWARE | software,
+            ),
+            Self::SoftwareOnly | Self::Monotonic => Some(software),
+            Self::None => None,
+        }
+    }
+}
+
 /// SocketCAN 适配器
 ///
 /// 实现 `CanAdapter` trait，提供 Linux 平台下的 SocketCAN 支持。
@@ -102,6 +166,14 @@ pub struct SocketCanAdapter {
     timestamping_enabled: bool,
     /// 是否检测到硬件时间戳支持（运行时检测）
     hw_timestamp_available: bool,
+    /// 时间戳来源
+    timestamp_mode: TimestampMode,
+    /// 创建时的 socket 配置（`reset()` 重新打开时复用）
+    config: SocketCanConfig,
+    /// 是否为只听适配器（拒绝发送）
+    listen_only: bool,
+    /// 打开时查询到的接口 MTU（`CAN_MTU` = 16 或 `CANFD_MTU` = 72）
+    interface_mtu: Option<u32>,
 }
 
 impl SocketCanAdapter {
@@ -128,6 +200,20 @@ impl SocketCanAdapter {
     /// let adapter = SocketCanAdapter::new("can0").unwrap();
     /// ```
     pub fn new(interface: impl Into<String>) -> Result<Self, CanError> {
+        Self::with_config(interface, SocketCanConfig::default())
+    }
+
+    /// 使用自定义 socket 配置创建 SocketCAN 适配器
+    ///
+    /// 除 [`SocketCanConfig`] 中的选项外，行为与 [`SocketCanAdapter::new`] 相同。
+    ///
+    /// # 错误
+    /// - 同 [`SocketCanAdapter::new`]
+    /// - `CanError::Io`: 设置缓冲区大小失败
+    pub fn with_config(
+        interface: impl Into<String>,
+        config: SocketCanConfig,
+    ) -> Result<Self, CanError> {
         let interface = interface.into();
 
         // 1. 检查接口状态（仅检查，不自动配置）
@@ -164,7 +250,8 @@ impl SocketCanAdapter {
         // 禁用 loopback 后：
         //   - TX 帧不会回环到 RX 接收端
         //   - 只有真实的外部 CAN 帧会被 RX 钩子录制
-        //   - TX 帧只能通过 TX 钩子（on_frame_sent）录制
+        //   - TX 帧由 tx_loop 在发送成功后以 `FrameDirection::Tx` 触发同一套帧钩子，
+        //     单个钩子即可得到合并的 TX+RX 帧流
         //
         // 注意：这需要 socketcan crate 3.x 支持，通过 raw setsockopt 调用实现
         let loopback_enabled: libc::c_int = 0; // 0 = 禁用，1 = 启用
@@ -199,38 +286,31 @@ impl SocketCanAdapter {
         let read_timeout = Duration::from_millis(2);
         socket.set_read_timeout(read_timeout).map_err(CanError::Io)?;
 
-        // 启用 SO_TIMESTAMPING（默认开启，优先使用硬件时间戳）
-        let flags = libc::SOF_TIMESTAMPING_RX_HARDWARE
-            | libc::SOF_TIMESTAMPING_RAW_HARDWARE
-            | libc::SOF_TIMESTAMPING_RX_SOFTWARE
-            | libc::SOF_TIMESTAMPING_SOFTWARE;
-
-        let timestamping_enabled = unsafe {
-            let ret = libc::setsockopt(
-                socket.as_raw_fd(),
-                libc::SOL_SOCKET,
-                libc::SO_TIMESTAMPING,
-                &flags as *const _ as *const libc::c_void,
-                mem::size_of::<u32>() as libc::socklen_t,
-            );
-
-            if ret < 0 {
-                return Err(CanError::Device(CanDeviceError::new(
-                    CanDeviceErrorKind::UnsupportedConfig,
-                    format!(
-                        "failed to enable SO_TIMESTAMPING on '{}': {}; strict realtime requires trusted CAN timestamps",
-                        interface,
-                        std::io::Error::last_os_error()
-                    ),
-                )));
-            } else {
-                true
-            }
+        // 启用 SO_TIMESTAMPING（默认开启，优先使用硬件时间戳；标志由 TimestampMode 决定）
+        let timestamping_enabled = match config.timestamp_mode.timestamping_flags() {
+            Some(flags) => Self::enable_timestamping(&socket, &interface, flags)?,
+            None => false,
         };
 
+        if let Some(bytes) = config.recv_buffer_size {
+            set_socket_buffer_size(socket.as_raw_fd(), libc::SO_RCVBUF, bytes)?;
+        }
+        if let Some(bytes) = config.send_buffer_size {
+            set_socket_buffer_size(socket.as_raw_fd(), libc::SO_SNDBUF, bytes)?;
+        }
+
         // 初始化时不检测硬件支持（首次接收时检测）
         let hw_timestamp_available = false;
 
+        // 查询接口 MTU（失败不阻塞初始化，仅影响 FD 能力判断）
+        let interface_mtu = match query_can_link_info(&interface) {
+            Ok(link) => link.mtu,
+            Err(e) => {
+                warn!("Failed to query link info of '{}': {}", interface, e);
+                None
+            },
+        };
+
         if timestamping_enabled {
             trace!(
                 "SocketCAN interface '{}' opened with timestamping enabled",
@@ -250,9 +330,98 @@ impl SocketCanAdapter {
             read_timeout,
             timestamping_enabled,
             hw_timestamp_available,
+            timestamp_mode: config.timestamp_mode,
+            config,
+            listen_only: false,
+            interface_mtu,
         })
     }
 
+    /// 创建只听（listen-only）SocketCAN 适配器，用于安全监听总线
+    ///
+    /// 与 [`SocketCanAdapter::new`] 一样不修改接口配置，而是通过 rtnetlink 校验接口
+    /// 已处于不会发送 ACK 的状态：
+    /// - 真实 CAN 控制器必须已开启 `CAN_CTRLMODE_LISTENONLY`
+    ///   （`sudo ip link set can0 type can bitrate 1000000 listen-only on`）
+    /// - vcan/vxcan 虚拟接口没有物理总线，直接接受
+    ///
+    /// 返回的适配器拒绝 `send()` 与 `split()`（返回 `UnsupportedConfig`）。
+    ///
+    /// # 错误
+    /// - 同 [`SocketCanAdapter::new`]
+    /// - `CanError::Device`（`UnsupportedConfig`）：接口不是 listen-only
+    pub fn new_listen_only(interface: impl Into<String>) -> Result<Self, CanError> {
+        let interface = interface.into();
+        let link = query_can_link_info(&interface)?;
+        if !link.is_virtual() && !link.is_listen_only() {
+            return Err(CanError::Device(CanDeviceError::new(
+                CanDeviceErrorKind::UnsupportedConfig,
+                format!(
+                    "CAN interface '{}' is not in listen-only mode. Configure it first:\n  sudo ip link set {} down\n  sudo ip link set {} type can listen-only on\n  sudo ip link set {} up",
+                    interface, interface, interface, interface
+                ),
+            )));
+        }
+        let mut adapter = Self::new(interface)?;
+        adapter.listen_only = true;
+        Ok(adapter)
+    }
+
+    /// 打开时接口的 MTU（`ip link set can0 mtu 72` 启用 CAN FD）
+    ///
+    /// 查询失败时为 `None`。
+    pub fn interface_mtu(&self) -> Option<u32> {
+        self.interface_mtu
+    }
+
+    /// 接口是否以 CAN FD MTU（72）启动
+    ///
+    /// 为 `false` 时应只发送经典 CAN 帧。
+    pub fn supports_fd(&self) -> bool {
+        self.interface_mtu == Some(CANFD_MTU as u32)
+    }
+
+    /// 是否为只听适配器
+    pub fn is_listen_only(&self) -> bool {
+        self.listen_only
+    }
+
+    fn listen_only_error(&self) -> CanError {
+        CanError::Device(CanDeviceError::new(
+            CanDeviceErrorKind::UnsupportedConfig,
+            format!("CAN interface '{}' is opened listen-only", self.interface),
+        ))
+    }
+
+    /// 设置 `SO_TIMESTAMPING` 标志
+    fn enable_timestamping(
+        socket: &CanSocket,
+        interface: &str,
+        flags: u32,
+    ) -> Result<bool, CanError> {
+        let ret = unsafe {
+            libc::setsockopt(
+                socket.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_TIMESTAMPING,
+                &flags as *const _ as *const libc::c_void,
+                mem::size_of::<u32>() as libc::socklen_t,
+            )
+        };
+
+        if ret < 0 {
+            return Err(CanError::Device(CanDeviceError::new(
+                CanDeviceErrorKind::UnsupportedConfig,
+                format!(
+                    "failed to enable SO_TIMESTAMPING on '{}': {}; strict realtime requires trusted CAN timestamps",
+                    interface,
+                    std::io::Error::last_os_error()
+                ),
+            )));
+        }
+        Ok(true)
+    }
+
     /// 获取接口名称
     pub fn interface(&self) -> &str {
         &self.interface
@@ -278,6 +447,11 @@ impl SocketCanAdapter {
         self.hw_timestamp_available
     }
 
+    /// 获取时间戳来源
+    pub fn timestamp_mode(&self) -> TimestampMode {
+        self.timestamp_mode
+    }
+
     /// 设置读超时
     ///
     /// # 参数
@@ -291,6 +465,40 @@ impl SocketCanAdapter {
         Ok(())
     }
 
+    /// 设置内核接收缓冲区大小（`SO_RCVBUF`）
+    ///
+    /// 内核会将该值翻倍，且受 `net.core.rmem_max` 限制，详见 [`SocketCanConfig`]。
+    ///
+    /// # 错误
+    /// - `CanError::Io`: `setsockopt` 失败
+    pub fn set_recv_buffer_size(&mut self, bytes: usize) -> Result<(), CanError> {
+        set_socket_buffer_size(self.socket.as_raw_fd(), libc::SO_RCVBUF, bytes)?;
+        self.config.recv_buffer_size = Some(bytes);
+        Ok(())
+    }
+
+    /// 设置内核发送缓冲区大小（`SO_SNDBUF`）
+    ///
+    /// 内核会将该值翻倍，且受 `net.core.wmem_max` 限制，详见 [`SocketCanConfig`]。
+    ///
+    /// # 错误
+    /// - `CanError::Io`: `setsockopt` 失败
+    pub fn set_send_buffer_size(&mut self, bytes: usize) -> Result<(), CanError> {
+        set_socket_buffer_size(self.socket.as_raw_fd(), libc::SO_SNDBUF, bytes)?;
+        self.config.send_buffer_size = Some(bytes);
+        Ok(())
+    }
+
+    /// 读取内核实际生效的接收缓冲区大小（字节，已包含内核翻倍）
+    pub fn recv_buffer_size(&self) -> Result<usize, CanError> {
+        socket_buffer_size(self.socket.as_raw_fd(), libc::SO_RCVBUF)
+    }
+
+    /// 读取内核实际生效的发送缓冲区大小（字节，已包含内核翻倍）
+    pub fn send_buffer_size(&self) -> Result<usize, CanError> {
+        socket_buffer_size(self.socket.as_raw_fd(), libc::SO_SNDBUF)
+    }
+
     /// 配置接口（可选，通常由系统工具配置）
     ///
     /// 注意：SocketCAN 的波特率通常由 `ip link set can0 type can bitrate 500000` 配置。
@@ -356,57 +564,96 @@ impl SocketCanAdapter {
                 },
             }
 
-            // Read into CANFD_MTU so recvmsg can report CAN FD/non-classic frames without
-            // truncating them before the shared parser rejects them.
-            let mut frame_buf = [0u8; CANFD_MTU];
-            let mut cmsg_buf = [0u8; 1024]; // CMSG 缓冲区
-
-            // 构建 IO 向量
-            let mut iov = [IoSliceMut::new(&mut frame_buf)];
-
-            // 调用 recvmsg
-            let (msg_bytes, msg_flags, timestamp_info, host_rx_mono_us) = {
-                let msg = match recvmsg::<SockaddrStorage>(
-                    fd,
-                    &mut iov,
-                    Some(&mut cmsg_buf),
-                    MsgFlags::empty(),
-                ) {
-                    Ok(msg) => msg,
-                    Err(nix::errno::Errno::EAGAIN) => {
-                        return Err(CanError::Timeout);
-                    },
-                    Err(e) => {
-                        return Err(CanError::Io(std::io::Error::other(format!(
-                            "recvmsg failed: {}",
-                            e
-                        ))));
-                    },
-                };
+            if let Some(received) = self.recv_frame(MsgFlags::empty())? {
+                return Ok(received);
+            }
+        }
+    }
 
-                let host_rx_mono_us = crate::monotonic_micros();
-                let timestamp_info = self.extract_timestamp_from_cmsg(&msg)?;
-                (msg.bytes, msg.flags.bits(), timestamp_info, host_rx_mono_us)
+    /// 非阻塞接收（跳过内部 `poll`）
+    ///
+    /// 供已将 [`AsRawFd::as_raw_fd`] 注册到自有 epoll/mio reactor 的调用方使用：
+    /// 在收到可读事件后调用，以 `MSG_DONTWAIT` 直接 `recvmsg`，避免重复 poll。
+    /// 时间戳提取与错误帧过滤与 [`Self::receive_with_timestamp`] 相同。
+    ///
+    /// # 返回值
+    /// - `Ok(Some(ReceivedFrame))`: 收到有效数据帧
+    /// - `Ok(None)`: 当前没有可读数据帧（`EAGAIN`）
+    /// - `Err(CanError::Io)`: IO 错误
+    pub fn receive_nonblocking(&mut self) -> Result<Option<ReceivedFrame>, CanError> {
+        if !self.started {
+            return Err(CanError::NotStarted);
+        }
+
+        loop {
+            match self.recv_frame(MsgFlags::MSG_DONTWAIT) {
+                Ok(Some(received)) => return Ok(Some(received)),
+                Ok(None) => continue,
+                Err(CanError::Timeout) => return Ok(None),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// 调用一次 `recvmsg` 并解析帧
+    ///
+    /// - `Ok(None)`: 收到可恢复的非数据帧（如错误帧），调用方应继续读取
+    /// - `Err(CanError::Timeout)`: `EAGAIN`
+    fn recv_frame(&mut self, flags: MsgFlags) -> Result<Option<ReceivedFrame>, CanError> {
+        let fd = self.socket.as_raw_fd();
+
+        // Read into CANFD_MTU so recvmsg can report CAN FD/non-classic frames without
+        // truncating them before the shared parser rejects them.
+        let mut frame_buf = [0u8; CANFD_MTU];
+        let mut cmsg_buf = [0u8; 1024]; // CMSG 缓冲区
+
+        // 构建 IO 向量
+        let mut iov = [IoSliceMut::new(&mut frame_buf)];
+
+        // 调用 recvmsg
+        let (msg_bytes, msg_flags, timestamp_info, host_rx_mono_us) = {
+            let msg = match recvmsg::<SockaddrStorage>(fd, &mut iov, Some(&mut cmsg_buf), flags) {
+                Ok(msg) => msg,
+                Err(nix::errno::Errno::EAGAIN) => {
+                    return Err(CanError::Timeout);
+                },
+                Err(e) => {
+                    return Err(CanError::Io(std::io::Error::other(format!(
+                        "recvmsg failed: {}",
+                        e
+                    ))));
+                },
             };
 
-            match parse_libc_can_frame_bytes(&frame_buf, msg_bytes, msg_flags) {
-                ParsedSocketCanFrame::Data(frame) => {
-                    let raw_timestamp = RawTimestampInfo {
-                        can_id: frame.raw_id(),
-                        host_rx_mono_us,
-                        system_ts_us: timestamp_info.system_ts_us,
-                        hw_trans_us: timestamp_info.hw_trans_us,
-                        hw_raw_us: timestamp_info.hw_raw_us,
-                    };
-                    return Ok(ReceivedFrame::new(
+            let host_rx_mono_us = crate::monotonic_micros();
+            let mut timestamp_info = self.extract_timestamp_from_cmsg(&msg)?;
+            if self.timestamp_mode == TimestampMode::Monotonic {
+                timestamp_info.timestamp_us = host_rx_mono_us;
+                timestamp_info.provenance = TimestampProvenance::Userspace;
+            }
+            (msg.bytes, msg.flags.bits(), timestamp_info, host_rx_mono_us)
+        };
+
+        match parse_libc_can_frame_bytes(&frame_buf, msg_bytes, msg_flags) {
+            ParsedSocketCanFrame::Data(frame) => {
+                crate::frame_trace::rx(&self.interface, &frame);
+                let raw_timestamp = RawTimestampInfo {
+                    can_id: frame.raw_id(),
+                    host_rx_mono_us,
+                    system_ts_us: timestamp_info.system_ts_us,
+                    hw_trans_us: timestamp_info.hw_trans_us,
+                    hw_raw_us: timestamp_info.hw_raw_us,
+                };
+                Ok(Some(
+                    ReceivedFrame::new(
                         frame.with_timestamp_us(timestamp_info.timestamp_us),
                         timestamp_info.provenance,
                     )
-                    .with_raw_timestamp(raw_timestamp));
-                },
-                ParsedSocketCanFrame::RecoverableNonData => continue,
-                ParsedSocketCanFrame::Fatal(error) => return Err(error),
-            }
+                    .with_raw_timestamp(raw_timestamp),
+                ))
+            },
+            ParsedSocketCanFrame::RecoverableNonData => Ok(None),
+            ParsedSocketCanFrame::Fatal(error) => Err(error),
         }
     }
 
@@ -463,6 +710,9 @@ impl SocketCanAdapter {
                         // ✅ 优先级 1：硬件时间戳（已同步到系统时钟）
                         // timestamps.hw_trans 是硬件时间经过内核转换后的系统时间
                         // 这是最理想的：硬件精度 + 系统时间轴一致性
+                        let hw_trans_us = hw_trans_us
+                            .filter(|_| self.timestamp_mode != TimestampMode::SoftwareOnly);
+
                         if let Some(timestamp_us) = hw_trans_us {
                             if !self.hw_timestamp_available {
                                 trace!("Hardware timestamp (system-synced) detected and enabled");
@@ -629,6 +879,9 @@ impl SplittableAdapter for SocketCanAdapter {
         if !self.started {
             return Err(CanError::NotStarted);
         }
+        if self.listen_only {
+            return Err(self.listen_only_error());
+        }
 
         // 使用 ManuallyDrop 防止 Drop 被调用
         // 因为我们要移动 socket 到分离的适配器中
@@ -642,7 +895,8 @@ impl SplittableAdapter for SocketCanAdapter {
         )?;
 
         // 创建 TX 适配器（会克隆 socket）
-        let tx_adapter = SocketCanTxAdapter::new(&adapter.socket)?;
+        let tx_adapter =
+            SocketCanTxAdapter::new_with_iface(&adapter.socket, adapter.interface.clone())?;
 
         trace!(
             "SocketCanAdapter split into RX and TX adapters (interface: {})",
@@ -653,6 +907,120 @@ impl SplittableAdapter for SocketCanAdapter {
     }
 }
 
+/// 设置 `SO_RCVBUF` / `SO_SNDBUF`
+fn set_socket_buffer_size(
+    fd: std::os::unix::io::RawFd,
+    option: libc::c_int,
+    bytes: usize,
+) -> Result<(), CanError> {
+    let value = libc::c_int::try_from(bytes).map_err(|_| {
+        CanError::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("socket buffer size {bytes} exceeds c_int range"),
+        ))
+    })?;
+    let ret = unsafe {
+        libc::setsockopt(
+            fd,
+            libc::SOL_SOCKET,
+            option,
+            &value as *const _ as *const libc::c_void,
+            mem::size_of::<libc::c_int>() as libc::socklen_t,
+        )
+    };
+    if ret < 0 {
+        return Err(CanError::Io(std::io::Error::last_os_error()));
+    }
+    Ok(())
+}
+
+/// 读取 `SO_RCVBUF` / `SO_SNDBUF` 的实际值
+fn socket_buffer_size(
+    fd: std::os::unix::io::RawFd,
+    option: libc::c_int,
+) -> Result<usize, CanError> {
+    let mut value: libc::c_int = 0;
+    let mut len = mem::size_of::<libc::c_int>() as libc::socklen_t;
+    let ret = unsafe {
+        libc::getsockopt(
+            fd,
+            libc::SOL_SOCKET,
+            option,
+            &mut value as *mut _ as *mut libc::c_void,
+            &mut len,
+        )
+    };
+    if ret < 0 {
+        return Err(CanError::Io(std::io::Error::last_os_error()));
+    }
+    Ok(value.max(0) as usize)
+}
+
+/// 转换 PiperFrame -> socketcan::CanFrame
+fn to_socketcan_frame(frame: &PiperFrame) -> Result<CanFrame, CanError> {
+    let payload = &frame.data_padded()[..frame.dlc() as usize];
+    match frame.id() {
+        CanId::Extended(id) => ExtendedId::new(id.raw())
+            .and_then(|id| CanFrame::new(id, payload))
+            .ok_or_else(|| {
+                CanError::Device(
+                    format!(
+                        "Failed to create extended frame with ID 0x{:X}",
+                        frame.raw_id()
+                    )
+                    .into(),
+                )
+            }),
+        CanId::Standard(id) => StandardId::new(id.raw())
+            .and_then(|id| CanFrame::new(id, payload))
+            .ok_or_else(|| {
+                CanError::Device(
+                    format!(
+                        "Failed to create standard frame with ID 0x{:X}",
+                        frame.raw_id()
+                    )
+                    .into(),
+                )
+            }),
+    }
+}
+
+/// 转换 PiperFrame -> libc::can_frame（`sendmmsg` 批量发送使用）
+fn to_libc_can_frame(frame: &PiperFrame) -> libc::can_frame {
+    // SAFETY: can_frame 为纯数据结构，全零是合法值（含保留/填充字段）
+    let mut raw: libc::can_frame = unsafe { mem::zeroed() };
+    raw.can_id = match frame.id() {
+        CanId::Extended(id) => id.raw() | libc::CAN_EFF_FLAG,
+        CanId::Standard(id) => u32::from(id.raw()),
+    };
+    raw.can_dlc = frame.dlc();
+    raw.data = *frame.data_padded();
+    raw
+}
+
+/// 单次 `sendmmsg` 的最大消息数
+///
+/// 发送缓冲放在栈上以避免控制循环中的堆分配，因此远小于内核上限 `UIO_MAXIOV`（1024）；
+/// 控制循环的一组 MIT/位置帧只有数帧，单次调用即可发完。
+const SENDMMSG_MAX_BATCH: usize = 64;
+
+/// 暴露底层 SocketCAN fd，供集成到外部 epoll/mio 事件循环
+///
+/// fd 在适配器生命周期内保持不变；调用方只应注册可读事件并配合
+/// [`SocketCanAdapter::receive_nonblocking`] 读取。**不要**对其设置 `O_NONBLOCK`
+/// 或关闭它：该标志与 `split()` 产生的 RX/TX fd 共享（见 [`SplittableAdapter::split`]）。
+impl AsRawFd for SocketCanAdapter {
+    fn as_raw_fd(&self) -> std::os::unix::io::RawFd {
+        self.socket.as_raw_fd()
+    }
+}
+
+impl std::os::fd::AsFd for SocketCanAdapter {
+    fn as_fd(&self) -> std::os::fd::BorrowedFd<'_> {
+        self.socket.as_fd()
+    }
+}
+
 impl CanAdapter for SocketCanAdapter {
     /// 发送帧（Fire-and-Forget）
     ///
@@ -664,33 +1032,12 @@ impl CanAdapter for SocketCanAdapter {
         if !self.started {
             return Err(CanError::NotStarted);
         }
+        if self.listen_only {
+            return Err(self.listen_only_error());
+        }
 
         // 1. 转换 PiperFrame -> CanFrame
-        let payload = &frame.data_padded()[..frame.dlc() as usize];
-        let can_frame = match frame.id() {
-            CanId::Extended(id) => ExtendedId::new(id.raw())
-                .and_then(|id| CanFrame::new(id, payload))
-                .ok_or_else(|| {
-                    CanError::Device(
-                        format!(
-                            "Failed to create extended frame with ID 0x{:X}",
-                            frame.raw_id()
-                        )
-                        .into(),
-                    )
-                })?,
-            CanId::Standard(id) => StandardId::new(id.raw())
-                .and_then(|id| CanFrame::new(id, payload))
-                .ok_or_else(|| {
-                    CanError::Device(
-                        format!(
-                            "Failed to create standard frame with ID 0x{:X}",
-                            frame.raw_id()
-                        )
-                        .into(),
-                    )
-                })?,
-        };
+        let can_frame = to_socketcan_frame(&frame)?;
 
         // 2. 发送（Fire-and-Forget）
         self.socket.transmit(&can_frame).map_err(|e| {
@@ -700,10 +1047,82 @@ impl CanAdapter for SocketCanAdapter {
             )))
         })?;
 
-        // Hot path: removed trace! call (TX can be high frequency)
+        crate::frame_trace::tx(&self.interface, &frame);
         Ok(())
     }
 
+    /// 批量发送：每 [`SENDMMSG_MAX_BATCH`] 帧一次 `sendmmsg` 系统调用
+    ///
+    /// 返回内核接受的帧数。发送缓冲满（`EAGAIN`/`ENOBUFS`）等错误发生在部分帧之后时，
+    /// 返回已接受的帧数；首帧即失败时返回 `CanError::Io`。内核不支持 `sendmmsg`
+    /// （`ENOSYS`）时退回逐帧 `send`。
+    fn send_batch(&mut self, frames: &[PiperFrame]) -> Result<usize, CanError> {
+        if !self.started {
+            return Err(CanError::NotStarted);
+        }
+        if self.listen_only {
+            return Err(self.listen_only_error());
+        }
+
+        // SAFETY: can_frame/iovec/mmsghdr 均为纯数据结构，全零是合法值
+        // （mmsghdr 全零表示无地址、无控制消息）
+        let mut raw_frames: [libc::can_frame; SENDMMSG_MAX_BATCH] = unsafe { mem::zeroed() };
+        let mut iovecs: [libc::iovec; SENDMMSG_MAX_BATCH] = unsafe { mem::zeroed() };
+        let mut messages: [libc::mmsghdr; SENDMMSG_MAX_BATCH] = unsafe { mem::zeroed() };
+
+        let mut sent = 0;
+        for chunk in frames.chunks(SENDMMSG_MAX_BATCH) {
+            for (((frame, raw), iov), message) in chunk
+                .iter()
+                .zip(raw_frames.iter_mut())
+                .zip(iovecs.iter_mut())
+                .zip(messages.iter_mut())
+            {
+                *raw = to_libc_can_frame(frame);
+                *iov = libc::iovec {
+                    iov_base: (raw as *mut libc::can_frame).cast(),
+                    iov_len: CLASSIC_CAN_MTU,
+                };
+                message.msg_hdr.msg_iov = iov;
+                message.msg_hdr.msg_iovlen = 1;
+            }
+
+            // SAFETY: messages/iovecs/raw_frames 在调用期间保持存活，前 vlen 项已初始化
+            let ret = unsafe {
+                libc::sendmmsg(
+                    self.socket.as_raw_fd(),
+                    messages.as_mut_ptr(),
+                    chunk.len() as libc::c_uint,
+                    0,
+                )
+            };
+            if ret < 0 {
+                let error = std::io::Error::last_os_error();
+                if sent == 0 && error.raw_os_error() == Some(libc::ENOSYS) {
+                    return crate::send_frames_individually(self, frames);
+                }
+                return if sent == 0 {
+                    Err(CanError::Io(std::io::Error::other(format!(
+                        "SocketCAN transmit error: {}",
+                        error
+                    ))))
+                } else {
+                    Ok(sent)
+                };
+            }
+
+            let accepted = ret as usize;
+            for frame in &chunk[..accepted] {
+                crate::frame_trace::tx(&self.interface, frame);
+            }
+            sent += accepted;
+            if accepted < chunk.len() {
+                break;
+            }
+        }
+        Ok(sent)
+    }
+
     /// 接收帧（阻塞直到收到有效数据帧或超时）
     ///
     /// **关键**：自动过滤错误帧，只返回有效数据帧。
@@ -724,7 +1143,6 @@ impl CanAdapter for SocketCanAdapter {
             return Err(CanError::NotStarted);
         }
 
-        // Hot path: removed trace! call (200Hz+)
         self.receive_with_timestamp()
     }
 
@@ -752,6 +1170,52 @@ impl CanAdapter for SocketCanAdapter {
         result
     }
 
+    /// 按原配置重新打开 socket（保留读超时），丢弃旧 socket 中积压的帧
+    ///
+    /// 与创建时一样只检查接口状态、不自动配置接口：接口不存在或未 UP 时返回
+    /// `CanError::Device`（附带 `ip link` 修复命令）。控制器 Bus-Off 的自动恢复
+    /// 需通过 `ip link set <if> type can restart-ms <ms>` 在系统侧配置。
+    fn reset(&mut self) -> Result<(), CanError> {
+        let mut reopened = Self::with_config(self.interface.clone(), self.config)?;
+        reopened.set_read_timeout(self.read_timeout)?;
+        reopened.listen_only = self.listen_only;
+        *self = reopened;
+        Ok(())
+    }
+
+    /// 以 `MSG_DONTWAIT` 读取并丢弃 socket 接收缓冲中的所有帧
+    fn drain_rx(&mut self) -> Result<usize, CanError> {
+        let mut drained = 0;
+        while self.receive_nonblocking()?.is_some() {
+            drained += 1;
+        }
+        Ok(drained)
+    }
+
+    /// 批量接收：等待第一帧后以 `MSG_DONTWAIT` 取空 socket 接收缓冲（最多 `max` 帧）
+    fn receive_batch(
+        &mut self,
+        max: usize,
+        timeout: Duration,
+    ) -> Result<Vec<ReceivedFrame>, CanError> {
+        let mut frames = Vec::new();
+        if max == 0 {
+            return Ok(frames);
+        }
+        match self.receive_timeout(timeout) {
+            Ok(frame) => frames.push(frame),
+            Err(CanError::Timeout) => return Ok(frames),
+            Err(e) => return Err(e),
+        }
+        while frames.len() < max {
+            match self.receive_nonblocking() {
+                Ok(Some(frame)) => frames.push(frame),
+                Ok(None) | Err(_) => break,
+            }
+        }
+        Ok(frames)
+    }
+
     /// 非阻塞接收
     fn try_receive(&mut self) -> Result<Option<ReceivedFrame>, CanError> {
         // 使用零超时模拟非阻塞
@@ -762,6 +1226,13 @@ impl CanAdapter for SocketCanAdapter {
         }
     }
 
+    /// 设置发送超时（`SO_SNDTIMEO`）
+    fn set_send_timeout(&mut self, timeout: Duration) {
+        if let Err(e) = self.socket.set_write_timeout(timeout) {
+            warn!("Failed to set send timeout: {}", e);
+        }
+    }
+
     /// 带超时的发送
     fn send_timeout(&mut self, frame: PiperFrame, timeout: Duration) -> Result<(), CanError> {
         // SocketCAN 支持发送超时（通过 SO_SNDTIMEO）
@@ -833,22 +1304,35 @@ mod tests {
         output.is_ok() && output.unwrap().status.success()
     }
 
-    /// 宏：要求 vcan0 接口存在，如果不存在则跳过测试
-    macro_rules! require_vcan0 {
-        () => {{
-            if !can_interface_exists("vcan0") {
-                eprintln!("Skipping test: vcan0 interface not available");
-                return;
-            }
-            "vcan0"
-        }};
+    /// 宏：绑定可用的 vcan 接口名到 `$name`
+    ///
+    /// 优先使用已存在的 vcan0；否则通过 [`crate::testutil::VcanGuard`] 创建临时接口
+    /// （需要 CAP_NET_ADMIN 与 vcan 内核模块），都不可用时跳过测试。
+    macro_rules! require_vcan {
+        ($name:ident) => {
+            let _vcan_guard;
+            let $name: &str = if can_interface_exists("vcan0") {
+                "vcan0"
+            } else {
+                match crate::testutil::VcanGuard::create_unique() {
+                    Ok(guard) => {
+                        _vcan_guard = guard;
+                        _vcan_guard.name()
+                    },
+                    Err(e) => {
+                        eprintln!("Skipping test: no vcan interface available ({})", e);
+                        return;
+                    },
+                }
+            };
+        };
     }
 
     #[test]
     #[cfg(target_os = "linux")]
     fn test_socketcan_adapter_new_success() {
         // 注意：需要 vcan0 接口存在
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let adapter = SocketCanAdapter::new(interface);
         assert!(adapter.is_ok());
     }
@@ -868,24 +1352,231 @@ mod tests {
     #[test]
     #[cfg(target_os = "linux")]
     fn test_socketcan_adapter_new_stores_interface_name() {
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let adapter = SocketCanAdapter::new(interface).unwrap();
-        assert_eq!(adapter.interface(), "vcan0");
+        assert_eq!(adapter.interface(), interface);
     }
 
     #[test]
     #[cfg(target_os = "linux")]
     fn test_socketcan_adapter_new_sets_read_timeout() {
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let adapter = SocketCanAdapter::new(interface).unwrap();
         // 验证默认超时时间已设置（2ms，与 PipelineConfig 的默认值一致，确保 io_loop 能及时响应退出信号）
         assert_eq!(adapter.read_timeout(), Duration::from_millis(2));
     }
 
+    #[test]
+    fn socket_buffer_size_roundtrip_reports_kernel_doubled_value() {
+        // 任意 socket 都遵循相同的 SO_RCVBUF 语义，无需 vcan0
+        let socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let fd = socket.as_raw_fd();
+        set_socket_buffer_size(fd, libc::SO_RCVBUF, 4096).unwrap();
+        assert_eq!(socket_buffer_size(fd, libc::SO_RCVBUF).unwrap(), 8192);
+        assert!(set_socket_buffer_size(fd, libc::SO_RCVBUF, usize::MAX).is_err());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_socketcan_adapter_with_config_applies_buffer_sizes() {
+        require_vcan!(interface);
+        let config = SocketCanConfig {
+            recv_buffer_size: Some(64 * 1024),
+            ..Default::default()
+        };
+        let mut adapter = SocketCanAdapter::with_config(interface, config).unwrap();
+        assert!(adapter.recv_buffer_size().unwrap() >= 64 * 1024);
+        adapter.set_send_buffer_size(8 * 1024).unwrap();
+        assert!(adapter.send_buffer_size().unwrap() > 0);
+    }
+
+    #[test]
+    fn timestamp_mode_selects_timestamping_flags() {
+        let hardware = TimestampMode::HardwarePreferred.timestamping_flags().unwrap();
+        assert_ne!(hardware & libc::SOF_TIMESTAMPING_RX_HARDWARE, 0);
+
+        for mode in [TimestampMode::SoftwareOnly, TimestampMode::Monotonic] {
+            let flags = mode.timestamping_flags().unwrap();
+            assert_eq!(flags & libc::SOF_TIMESTAMPING_RX_HARDWARE, 0);
+            assert_ne!(flags & libc::SOF_TIMESTAMPING_RX_SOFTWARE, 0);
+        }
+        assert_eq!(TimestampMode::None.timestamping_flags(), None);
+        assert_eq!(TimestampMode::default(), TimestampMode::HardwarePreferred);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_socketcan_adapter_timestamp_mode_none_disables_timestamping() {
+        require_vcan!(interface);
+        let config = SocketCanConfig {
+            timestamp_mode: TimestampMode::None,
+            ..Default::default()
+        };
+        let adapter = SocketCanAdapter::with_config(interface, config).unwrap();
+        assert!(!adapter.timestamping_enabled());
+        assert_eq!(adapter.timestamp_mode(), TimestampMode::None);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_socketcan_adapter_receive_nonblocking_returns_none_when_idle() {
+        require_vcan!(interface);
+        let mut adapter = SocketCanAdapter::new(interface).unwrap();
+        while adapter.receive_nonblocking().unwrap().is_some() {}
+
+        assert!(adapter.receive_nonblocking().unwrap().is_none());
+        assert!(adapter.as_raw_fd() >= 0);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_socketcan_adapter_receive_batch_drains_socket() {
+        require_vcan!(interface);
+        let mut tx = SocketCanAdapter::new(interface).unwrap();
+        let mut rx = SocketCanAdapter::new(interface).unwrap();
+        while rx.receive_nonblocking().unwrap().is_some() {}
+
+        for id in 0x200..0x204 {
+            tx.send(PiperFrame::new_standard(id, [id as u8]).unwrap()).unwrap();
+        }
+
+        let batch = rx.receive_batch(3, Duration::from_millis(100)).unwrap();
+        assert_eq!(batch.len(), 3);
+        let rest = rx.receive_batch(8, Duration::from_millis(100)).unwrap();
+        assert_eq!(rest.len(), 1);
+        assert!(rx.receive_batch(8, Duration::from_millis(1)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_to_libc_can_frame_round_trips_through_raw_parser() {
+        let frames = [
+            PiperFrame::new_standard(0x155, [1, 2, 3, 4, 5, 6, 7, 8]).unwrap(),
+            PiperFrame::new_standard(0x7FF, [0xAA]).unwrap(),
+            PiperFrame::new_standard(0x000, []).unwrap(),
+            PiperFrame::new_extended(0x1FFF_FFFF, [9, 8, 7]).unwrap(),
+        ];
+        for frame in frames {
+            let raw = to_libc_can_frame(&frame);
+            // SAFETY: 按字节视图读取刚构造的 can_frame
+            let bytes = unsafe {
+                std::slice::from_raw_parts(
+                    (&raw as *const libc::can_frame).cast::<u8>(),
+                    CLASSIC_CAN_MTU,
+                )
+            };
+            let ParsedSocketCanFrame::Data(parsed) =
+                parse_libc_can_frame_bytes(bytes, CLASSIC_CAN_MTU, 0)
+            else {
+                panic!("expected data frame for {frame:?}");
+            };
+            assert_eq!(parsed, frame);
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    #[ignore = "requires vcan interface"]
+    fn test_socketcan_adapter_send_batch_delivers_frames_in_order() {
+        require_vcan!(interface);
+        let mut tx = SocketCanAdapter::new(interface).unwrap();
+        let mut rx = SocketCanAdapter::new(interface).unwrap();
+        rx.drain_rx().unwrap();
+
+        // 超过单次 sendmmsg 的栈缓冲，覆盖分块边界
+        let frames: Vec<PiperFrame> = (0..(SENDMMSG_MAX_BATCH as u32 + 36))
+            .map(|index| {
+                if index % 4 == 3 {
+                    PiperFrame::new_extended(0x0100_0000 | index, [index as u8; 8]).unwrap()
+                } else {
+                    PiperFrame::new_standard(0x300 + index, &[index as u8; 3][..]).unwrap()
+                }
+            })
+            .collect();
+        assert_eq!(tx.send_batch(&frames).unwrap(), frames.len());
+        assert_eq!(tx.send_batch(&[]).unwrap(), 0);
+
+        let mut received = Vec::new();
+        while received.len() < frames.len() {
+            let batch = rx.receive_batch(frames.len(), Duration::from_millis(100)).unwrap();
+            assert!(!batch.is_empty(), "timed out waiting for batched frames");
+            received.extend(batch.into_iter().map(|received| received.frame));
+        }
+        for (sent, received) in frames.iter().zip(&received) {
+            assert_eq!(received.id(), sent.id());
+            assert_eq!(received.data(), sent.data());
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_socketcan_adapter_drain_rx_discards_pending_frames() {
+        require_vcan!(interface);
+        let mut tx = SocketCanAdapter::new(interface).unwrap();
+        let mut rx = SocketCanAdapter::new(interface).unwrap();
+        rx.drain_rx().unwrap();
+
+        for id in 0x210..0x213 {
+            tx.send(PiperFrame::new_standard(id, [0]).unwrap()).unwrap();
+        }
+        std::thread::sleep(Duration::from_millis(10));
+
+        assert_eq!(rx.drain_rx().unwrap(), 3);
+        assert_eq!(rx.drain_rx().unwrap(), 0);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_socketcan_adapter_reports_interface_mtu() {
+        require_vcan!(interface);
+        let adapter = SocketCanAdapter::new(interface).unwrap();
+        let mtu = adapter.interface_mtu().unwrap();
+        assert!(mtu == CLASSIC_CAN_MTU as u32 || mtu == CANFD_MTU as u32);
+        assert_eq!(adapter.supports_fd(), mtu == CANFD_MTU as u32);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_socketcan_adapter_listen_only_rejects_send_and_split() {
+        require_vcan!(interface);
+        let mut adapter = SocketCanAdapter::new_listen_only(interface).unwrap();
+        assert!(adapter.is_listen_only());
+
+        let err = adapter.send(PiperFrame::new_standard(0x123, [1u8]).unwrap()).unwrap_err();
+        assert!(matches!(
+            err,
+            CanError::Device(e) if e.kind == CanDeviceErrorKind::UnsupportedConfig
+        ));
+        adapter.reset().unwrap();
+        assert!(adapter.is_listen_only());
+        assert!(matches!(
+            adapter.split(),
+            Err(CanError::Device(e)) if e.kind == CanDeviceErrorKind::UnsupportedConfig
+        ));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_socketcan_adapter_reset_reopens_with_same_configuration() {
+        require_vcan!(interface);
+        let config = SocketCanConfig {
+            timestamp_mode: TimestampMode::SoftwareOnly,
+            ..Default::default()
+        };
+        let mut adapter = SocketCanAdapter::with_config(interface, config).unwrap();
+        adapter.set_read_timeout(Duration::from_millis(7)).unwrap();
+        adapter.reset().unwrap();
+
+        assert!(adapter.is_started());
+        assert_eq!(adapter.interface(), interface);
+        assert_eq!(adapter.read_timeout(), Duration::from_millis(7));
+        assert_eq!(adapter.timestamp_mode(), TimestampMode::SoftwareOnly);
+        assert!(adapter.as_raw_fd() >= 0);
+    }
+
     #[test]
     #[cfg(target_os = "linux")]
     fn test_socketcan_adapter_new_sets_started_true() {
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let adapter = SocketCanAdapter::new(interface).unwrap();
         assert!(adapter.is_started());
     }
@@ -895,7 +1586,7 @@ mod tests {
     fn test_socketcan_adapter_new_enables_timestamping() {
         // 测试 SO_TIMESTAMPING 是否成功启用
         // 在 vcan0 上，SO_TIMESTAMPING 应该能够成功设置
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let adapter = SocketCanAdapter::new(interface).unwrap();
 
         // 在支持的平台上，timestamping_enabled 应该为 true
@@ -911,7 +1602,7 @@ mod tests {
     fn test_socketcan_adapter_new_initializes_hw_timestamp_available() {
         // 测试 hw_timestamp_available 是否正确初始化为 false
         // 初始化时不应该检测硬件支持，应该在首次接收时检测
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let adapter = SocketCanAdapter::new(interface).unwrap();
 
         // 初始化时应该为 false（首次接收时才会检测）
@@ -925,7 +1616,7 @@ mod tests {
     #[cfg(target_os = "linux")]
     fn test_socketcan_adapter_timestamping_fields_exist() {
         // 验证时间戳相关字段存在且可访问
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let adapter = SocketCanAdapter::new(interface).unwrap();
 
         // 验证字段可以通过 getter 方法访问
@@ -939,7 +1630,7 @@ mod tests {
     #[cfg(target_os = "linux")]
     fn test_socketcan_adapter_receive_with_timestamp_skeleton() {
         // 验证 receive_with_timestamp() 方法骨架存在且可调用
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let mut adapter = SocketCanAdapter::new(interface).unwrap();
 
         // 设置短超时，避免无限阻塞
@@ -981,7 +1672,7 @@ mod tests {
         // 验证 extract_timestamp_from_cmsg() 方法已实现（不再测试骨架）
         // 实际的时间戳提取测试在 test_socketcan_adapter_receive_with_timestamp_full_flow 中
         // 此测试主要用于确认方法签名正确（编译通过即表示签名正确）
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let adapter = SocketCanAdapter::new(interface).unwrap();
 
         // 验证方法存在（通过编译）
@@ -1013,7 +1704,7 @@ mod tests {
     fn test_socketcan_adapter_receive_with_timestamp_full_flow() {
         // 验证 receive_with_timestamp() 完整流程（发送帧 → 接收帧）
         // 注意：vcan0 默认不回环，需要使用两个 socket
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let mut tx_adapter = SocketCanAdapter::new(interface).unwrap();
         let mut rx_adapter = SocketCanAdapter::new(interface).unwrap();
 
@@ -1072,7 +1763,7 @@ mod tests {
     #[cfg(target_os = "linux")]
     fn test_socketcan_adapter_receive_with_timestamp_timeout() {
         // 验证 receive_with_timestamp() 的超时逻辑
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let mut adapter = SocketCanAdapter::new(interface).unwrap();
 
         // 清空缓冲区（持续多次，确保清空所有待处理帧）
@@ -1158,7 +1849,7 @@ mod tests {
     fn test_socketcan_adapter_receive_with_timestamp_monotonic() {
         // 验证时间戳的单调性（发送多个帧，时间戳应该递增）
         // 参考：hardware_timestamp_implementation_plan.md:529-547
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let mut tx_adapter = SocketCanAdapter::new(interface).unwrap();
         let mut rx_adapter = SocketCanAdapter::new(interface).unwrap();
 
@@ -1337,7 +2028,7 @@ mod tests {
     fn test_socketcan_adapter_receive_with_timestamp_extended_frame() {
         // 验证 receive_with_timestamp() 支持扩展帧
         // 注意：vcan0 默认不回环，需要使用两个 socket
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let mut tx_adapter = SocketCanAdapter::new(interface).unwrap();
         let mut rx_adapter = SocketCanAdapter::new(interface).unwrap();
         rx_adapter.set_read_timeout(Duration::from_millis(100)).unwrap();
@@ -1378,7 +2069,7 @@ mod tests {
     #[test]
     #[cfg(target_os = "linux")]
     fn test_socketcan_adapter_set_read_timeout() {
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let mut adapter = SocketCanAdapter::new(interface).unwrap();
         let new_timeout = Duration::from_millis(200);
         adapter.set_read_timeout(new_timeout).unwrap();
@@ -1388,7 +2079,7 @@ mod tests {
     #[test]
     #[cfg(target_os = "linux")]
     fn test_socketcan_adapter_send_standard_frame() {
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let mut adapter = SocketCanAdapter::new(interface).unwrap();
         let frame = PiperFrame::new_standard(0x123, [1, 2, 3, 4]).unwrap();
 
@@ -1399,7 +2090,7 @@ mod tests {
     #[test]
     #[cfg(target_os = "linux")]
     fn test_socketcan_adapter_send_extended_frame() {
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let mut adapter = SocketCanAdapter::new(interface).unwrap();
         let frame = PiperFrame::new_extended(0x12345678, [0xFF; 8]).unwrap();
 
@@ -1410,7 +2101,7 @@ mod tests {
     #[test]
     #[cfg(target_os = "linux")]
     fn test_socketcan_adapter_send_empty_frame() {
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let mut adapter = SocketCanAdapter::new(interface).unwrap();
         let frame = PiperFrame::new_standard(0x123, []).unwrap();
 
@@ -1422,7 +2113,7 @@ mod tests {
     #[cfg(target_os = "linux")]
     fn test_socketcan_adapter_receive_timestamp() {
         // 验证 receive() 返回的 PiperFrame 包含时间戳
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let mut tx_adapter = SocketCanAdapter::new(interface).unwrap();
         let mut rx_adapter = SocketCanAdapter::new(interface).unwrap();
 
@@ -1507,7 +2198,7 @@ mod tests {
     fn test_socketcan_adapter_receive_timestamp_monotonic() {
         // 验证 receive() 返回的时间戳单调递增（Task 4.2）
         // 参考：hardware_timestamp_implementation_plan.md:529-547
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let mut tx_adapter = SocketCanAdapter::new(interface).unwrap();
         let mut rx_adapter = SocketCanAdapter::new(interface).unwrap();
 
@@ -1629,7 +2320,7 @@ mod tests {
         // 验证时间戳精度和系统时间轴一致性（Task 4.3）
         // 参考：hardware_timestamp_implementation_plan.md:556-625
         // 注意：vcan0 不支持真正的回环，使用两个独立的 socket（一个发送，一个接收）
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let mut tx_adapter = SocketCanAdapter::new(interface).unwrap();
         let mut rx_adapter = SocketCanAdapter::new(interface).unwrap();
 
@@ -1745,7 +2436,7 @@ mod tests {
     #[test]
     #[cfg(target_os = "linux")]
     fn test_socketcan_adapter_receive_timeout() {
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let mut adapter = SocketCanAdapter::new(interface).unwrap();
 
         // 设置短超时（1ms，用于清空缓冲区）
@@ -1871,7 +2562,7 @@ mod tests {
         // 或者使用另一个线程/工具发送
         // 这个测试可能需要在真实 CAN 总线上运行，或使用特定的测试工具
         // 暂时标记为可能需要手动验证
-        let interface = require_vcan0!();
+        require_vcan!(interface);
         let mut adapter = SocketCanAdapter::new(interface).unwrap();
 
         // 发送帧
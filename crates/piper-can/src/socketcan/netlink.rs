@@ -0,0 +1,162 @@
+//! 最小 rtnetlink 客户端
+//!
+//! 只实现链路查询（`RTM_GETLINK`）与测试辅助所需的链路创建/删除，
+//! 避免为几条请求引入完整的 netlink 依赖。
+
+use crate::CanError;
+use std::io;
+
+pub(crate) const NLMSG_HDR_LEN: usize = 16;
+pub(crate) const IFINFOMSG_LEN: usize = 16;
+
+#[cfg(any(test, feature = "test-helpers"))]
+pub(crate) const IFLA_IFNAME: u16 = 3;
+pub(crate) const IFLA_MTU: u16 = 4;
+pub(crate) const IFLA_LINKINFO: u16 = 18;
+pub(crate) const IFLA_INFO_KIND: u16 = 1;
+pub(crate) const IFLA_INFO_DATA: u16 = 2;
+pub(crate) const IFLA_CAN_CTRLMODE: u16 = 5;
+
+/// 嵌套属性标志
+#[cfg(any(test, feature = "test-helpers"))]
+pub(crate) const NLA_F_NESTED: u16 = 0x8000;
+
+/// RAII：确保临时 socket 被正确关闭
+pub(crate) struct FdGuard(pub(crate) libc::c_int);
+
+impl Drop for FdGuard {
+    fn drop(&mut self) {
+        if self.0 >= 0 {
+            unsafe { libc::close(self.0) };
+        }
+    }
+}
+
+/// 构造 `nlmsghdr + ifinfomsg + attrs` 形式的链路请求
+pub(crate) fn link_message(
+    msg_type: u16,
+    flags: u16,
+    ifindex: i32,
+    ifi_flags: u32,
+    ifi_change: u32,
+    attrs: &[u8],
+) -> Vec<u8> {
+    let len = NLMSG_HDR_LEN + IFINFOMSG_LEN + attrs.len();
+    let mut msg = Vec::with_capacity(len);
+    msg.extend_from_slice(&(len as u32).to_ne_bytes());
+    msg.extend_from_slice(&msg_type.to_ne_bytes());
+    msg.extend_from_slice(&flags.to_ne_bytes());
+    msg.extend_from_slice(&1u32.to_ne_bytes()); // nlmsg_seq
+    msg.extend_from_slice(&0u32.to_ne_bytes()); // nlmsg_pid
+    // struct ifinfomsg
+    msg.push(libc::AF_UNSPEC as u8);
+    msg.push(0);
+    msg.extend_from_slice(&0u16.to_ne_bytes());
+    msg.extend_from_slice(&ifindex.to_ne_bytes());
+    msg.extend_from_slice(&ifi_flags.to_ne_bytes());
+    msg.extend_from_slice(&ifi_change.to_ne_bytes());
+    msg.extend_from_slice(attrs);
+    msg
+}
+
+/// 追加一个 rtattr（负载按 4 字节对齐）
+#[cfg(any(test, feature = "test-helpers"))]
+pub(crate) fn push_rtattr(buf: &mut Vec<u8>, kind: u16, payload: &[u8]) {
+    buf.extend_from_slice(&((4 + payload.len()) as u16).to_ne_bytes());
+    buf.extend_from_slice(&kind.to_ne_bytes());
+    buf.extend_from_slice(payload);
+    while !buf.len().is_multiple_of(4) {
+        buf.push(0);
+    }
+}
+
+/// 遍历 rtattr 列表，返回 (类型, 负载)；类型已去除 `NLA_F_NESTED` 等标志位
+pub(crate) fn rtattrs(mut buf: &[u8]) -> impl Iterator<Item = (u16, &[u8])> {
+    std::iter::from_fn(move || {
+        if buf.len() < 4 {
+            return None;
+        }
+        let len = u16::from_ne_bytes([buf[0], buf[1]]) as usize;
+        let kind = u16::from_ne_bytes([buf[2], buf[3]]) & 0x3fff;
+        if len < 4 || len > buf.len() {
+            return None;
+        }
+        let payload = &buf[4..len];
+        let aligned = (len + 3) & !3;
+        buf = &buf[aligned.min(buf.len())..];
+        Some((kind, payload))
+    })
+}
+
+/// 发送一条 `NETLINK_ROUTE` 请求并读取第一条应答
+pub(crate) fn request(msg: &[u8]) -> Result<Vec<u8>, CanError> {
+    let sockfd = unsafe {
+        libc::socket(
+            libc::AF_NETLINK,
+            libc::SOCK_RAW | libc::SOCK_CLOEXEC,
+            libc::NETLINK_ROUTE,
+        )
+    };
+    if sockfd < 0 {
+        return Err(CanError::Io(io::Error::last_os_error()));
+    }
+    let _guard = FdGuard(sockfd);
+
+    let sent = unsafe { libc::send(sockfd, msg.as_ptr() as *const libc::c_void, msg.len(), 0) };
+    if sent < 0 {
+        return Err(CanError::Io(io::Error::last_os_error()));
+    }
+
+    let mut response = vec![0u8; 16 * 1024];
+    let received = unsafe {
+        libc::recv(
+            sockfd,
+            response.as_mut_ptr() as *mut libc::c_void,
+            response.len(),
+            0,
+        )
+    };
+    if received < 0 {
+        return Err(CanError::Io(io::Error::last_os_error()));
+    }
+    response.truncate(received as usize);
+    Ok(response)
+}
+
+/// 拆出应答中的第一条消息：返回 (消息类型, 消息体)
+pub(crate) fn first_message(buf: &[u8]) -> Result<(u16, &[u8]), CanError> {
+    if buf.len() < NLMSG_HDR_LEN {
+        return Err(malformed());
+    }
+    let msg_len = u32::from_ne_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
+    let msg_type = u16::from_ne_bytes([buf[4], buf[5]]);
+    let msg = buf.get(..msg_len).ok_or_else(malformed)?;
+    Ok((msg_type, msg))
+}
+
+/// 解析 `NLMSG_ERROR` 消息；错误码为 0 表示 ACK
+pub(crate) fn error_result(msg: &[u8]) -> Result<(), CanError> {
+    let errno = msg
+        .get(NLMSG_HDR_LEN..NLMSG_HDR_LEN + 4)
+        .map(|b| i32::from_ne_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(malformed)?;
+    if errno == 0 {
+        Ok(())
+    } else {
+        Err(CanError::Io(io::Error::from_raw_os_error(-errno)))
+    }
+}
+
+/// 发送带 `NLM_F_ACK` 的请求并等待内核确认
+#[cfg(any(test, feature = "test-helpers"))]
+pub(crate) fn request_ack(msg: &[u8]) -> Result<(), CanError> {
+    let response = request(msg)?;
+    match first_message(&response)? {
+        (msg_type, msg) if msg_type == libc::NLMSG_ERROR as u16 => error_result(msg),
+        _ => Err(malformed()),
+    }
+}
+
+pub(crate) fn malformed() -> CanError {
+    CanError::Io(io::Error::other("malformed rtnetlink response"))
+}
@@ -133,9 +133,11 @@ pub fn parse_libc_can_frame_bytes(
 #[cfg(test)]
 mod tests {
     use super::{CLASSIC_CAN_MTU, ParsedSocketCanFrame, parse_libc_can_frame_bytes};
+    use crate::PiperFrame;
     use crate::socketcan::CANFD_MTU;
     use crate::{CanDeviceErrorKind, CanError};
     use piper_protocol::FrameError;
+    use socketcan::{EmbeddedFrame, Id};
 
     fn raw_frame_bytes(can_id: u32, dlc: u8, data: [u8; 8]) -> [u8; CLASSIC_CAN_MTU] {
         let mut bytes = [0u8; CLASSIC_CAN_MTU];
@@ -193,6 +195,56 @@ mod tests {
         );
     }
 
+    /// 经 `socketcan::CanFrame` 中转的参考路径
+    fn parse_via_socketcan(bytes: &[u8; CLASSIC_CAN_MTU]) -> Option<PiperFrame> {
+        // SAFETY: `bytes` 恰为一个 `libc::can_frame` 大小，`read_unaligned` 不要求对齐
+        let raw: libc::can_frame = unsafe { std::ptr::read_unaligned(bytes.as_ptr().cast()) };
+        let socketcan::CanFrame::Data(frame) = socketcan::CanFrame::from(raw) else {
+            return None;
+        };
+        match frame.id() {
+            Id::Standard(id) => PiperFrame::new_standard(u32::from(id.as_raw()), frame.data()),
+            Id::Extended(id) => PiperFrame::new_extended(id.as_raw(), frame.data()),
+        }
+        .ok()
+    }
+
+    #[test]
+    fn raw_parse_matches_socketcan_frame_conversion() {
+        let payload = [0x10, 0x21, 0x32, 0x43, 0x54, 0x65, 0x76, 0x87];
+        let ids = [
+            0x000,
+            0x123,
+            0x2A5,
+            libc::CAN_SFF_MASK,
+            libc::CAN_EFF_FLAG,
+            libc::CAN_EFF_FLAG | 0x0000_0155,
+            libc::CAN_EFF_FLAG | 0x01AB_CDEF,
+            libc::CAN_EFF_FLAG | libc::CAN_EFF_MASK,
+            libc::CAN_RTR_FLAG | 0x155,
+            libc::CAN_RTR_FLAG | libc::CAN_EFF_FLAG | 0x01AB_CDEF,
+            libc::CAN_ERR_FLAG | libc::CAN_ERR_PROT,
+        ];
+
+        for can_id in ids {
+            for dlc in 0..=8 {
+                let bytes = raw_frame_bytes(can_id, dlc, payload);
+                let fast = match parse(&bytes, CLASSIC_CAN_MTU, 0) {
+                    ParsedSocketCanFrame::Data(frame) => Some(frame),
+                    ParsedSocketCanFrame::RecoverableNonData => None,
+                    ParsedSocketCanFrame::Fatal(error) => {
+                        panic!("can_id {can_id:#x} dlc {dlc}: unexpected fatal {error}")
+                    },
+                };
+                assert_eq!(
+                    fast,
+                    parse_via_socketcan(&bytes),
+                    "can_id {can_id:#x} dlc {dlc}"
+                );
+            }
+        }
+    }
+
     #[test]
     fn rejects_canfd_mtu() {
         let bytes = [0u8; CANFD_MTU];
@@ -354,6 +354,7 @@ impl SocketCanRxAdapter {
 
             match parse_libc_can_frame_bytes(&frame_buf, msg_bytes, msg_flags) {
                 ParsedSocketCanFrame::Data(frame) => {
+                    crate::frame_trace::rx(&self.iface, &frame);
                     let timestamp_provenance =
                         timestamp_provenance_for_source(timestamp_info.source);
                     let raw_timestamp = RawTimestampInfo {
@@ -603,6 +604,7 @@ impl Drop for SocketCanRxAdapter {
 /// - **FD 共享**：通过 `try_clone()` 共享同一个打开的文件描述，共享文件状态标志
 pub struct SocketCanTxAdapter {
     socket: CanSocket,
+    iface: String,
     current_write_timeout: Duration,
 }
 
@@ -615,6 +617,13 @@ impl SocketCanTxAdapter {
     /// # 错误
     /// - `CanError::Io`: 克隆 socket 或设置写超时失败
     pub fn new(socket: &CanSocket) -> Result<Self, CanError> {
+        Self::new_with_iface(socket, "unknown")
+    }
+
+    pub(crate) fn new_with_iface(
+        socket: &CanSocket,
+        iface: impl Into<String>,
+    ) -> Result<Self, CanError> {
         // 克隆 socket（使用 dup() 系统调用）
         let tx_socket = dup_socket(socket)?;
 
@@ -631,6 +640,7 @@ impl SocketCanTxAdapter {
 
         Ok(Self {
             socket: tx_socket,
+            iface: iface.into(),
             current_write_timeout: Duration::from_millis(5),
         })
     }
@@ -699,6 +709,7 @@ impl SocketCanTxAdapter {
             )))
         })?;
 
+        crate::frame_trace::tx(&self.iface, &frame);
         Ok(())
     }
 }
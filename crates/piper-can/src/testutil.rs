@@ -0,0 +1,110 @@
+//! 测试辅助工具（`test-helpers` feature，Linux）
+//!
+//! 提供 [`VcanGuard`]：通过 rtnetlink 创建临时 vcan 接口并在 drop 时删除，
+//! 让 SocketCAN 测试无需手动 `ip link` 即可运行。
+//!
+//! 创建接口需要 `CAP_NET_ADMIN` 且内核已加载 `vcan` 模块；条件不满足时
+//! [`VcanGuard::create`] 返回错误，调用方应据此跳过测试。
+
+use crate::CanError;
+use crate::socketcan::netlink;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// 临时 vcan 接口，drop 时自动删除
+#[derive(Debug)]
+pub struct VcanGuard {
+    name: String,
+}
+
+impl VcanGuard {
+    /// 创建并启动（UP）名为 `name` 的 vcan 接口
+    ///
+    /// # 错误
+    /// - `CanError::Io`: 权限不足（`EPERM`）、接口已存在（`EEXIST`）、
+    ///   或内核不支持 vcan（`EOPNOTSUPP`）
+    pub fn create(name: impl Into<String>) -> Result<Self, CanError> {
+        let name = name.into();
+        let mut link_info = Vec::new();
+        netlink::push_rtattr(&mut link_info, netlink::IFLA_INFO_KIND, b"vcan\0");
+
+        let mut attrs = Vec::new();
+        netlink::push_rtattr(&mut attrs, netlink::IFLA_IFNAME, &nul_terminated(&name));
+        netlink::push_rtattr(
+            &mut attrs,
+            netlink::IFLA_LINKINFO | netlink::NLA_F_NESTED,
+            &link_info,
+        );
+
+        let flags = libc::NLM_F_REQUEST | libc::NLM_F_ACK | libc::NLM_F_CREATE | libc::NLM_F_EXCL;
+        let up = libc::IFF_UP as u32;
+        let msg = netlink::link_message(libc::RTM_NEWLINK, flags as u16, 0, up, up, &attrs);
+        netlink::request_ack(&msg)?;
+        Ok(Self { name })
+    }
+
+    /// 以进程内唯一的名字创建 vcan 接口（如 `pvcan1234_0`）
+    pub fn create_unique() -> Result<Self, CanError> {
+        static NEXT: AtomicU32 = AtomicU32::new(0);
+        let id = NEXT.fetch_add(1, Ordering::Relaxed);
+        // IFNAMSIZ 限制 15 个字符
+        Self::create(format!(
+            "pvcan{}_{}",
+            std::process::id() % 100_000,
+            id % 1_000
+        ))
+    }
+
+    /// 接口名称
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Drop for VcanGuard {
+    fn drop(&mut self) {
+        let mut attrs = Vec::new();
+        netlink::push_rtattr(
+            &mut attrs,
+            netlink::IFLA_IFNAME,
+            &nul_terminated(&self.name),
+        );
+        let flags = libc::NLM_F_REQUEST | libc::NLM_F_ACK;
+        let msg = netlink::link_message(libc::RTM_DELLINK, flags as u16, 0, 0, 0, &attrs);
+        if let Err(e) = netlink::request_ack(&msg) {
+            tracing::warn!("Failed to delete vcan interface '{}': {}", self.name, e);
+        }
+    }
+}
+
+fn nul_terminated(name: &str) -> Vec<u8> {
+    let mut bytes = name.as_bytes().to_vec();
+    bytes.push(0);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vcan_guard_create_and_drop() {
+        let guard = match VcanGuard::create_unique() {
+            Ok(guard) => guard,
+            Err(e) => {
+                // 无 CAP_NET_ADMIN 或内核无 vcan 模块：请求必须被内核明确拒绝，而不是格式错误
+                let CanError::Io(io) = e else {
+                    panic!("unexpected error: {e:?}")
+                };
+                assert_ne!(io.raw_os_error(), Some(libc::EINVAL), "{io}");
+                eprintln!("Skipping test: cannot create vcan interface ({io})");
+                return;
+            },
+        };
+        let name = guard.name().to_string();
+        assert!(name.len() <= 15);
+        assert!(crate::socketcan::SocketCanAdapter::new(name.as_str()).is_ok());
+
+        drop(guard);
+        assert!(crate::socketcan::SocketCanAdapter::new(name.as_str()).is_err());
+    }
+}
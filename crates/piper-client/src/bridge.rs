@@ -7,10 +7,12 @@
 use piper_can::PiperFrame;
 
 pub use piper_can::bridge::protocol::{
-    BridgeDeviceState, BridgeRole, BridgeStatus, CanIdFilter, ErrorCode, SessionToken,
+    BridgeDeviceState, BridgeRole, BridgeSessionInfo, BridgeStatus, CanIdFilter, CanIdFilterMode,
+    ErrorCode, SessionToken,
 };
 pub use piper_can::{
-    BridgeClientOptions, BridgeEndpoint, BridgeError, BridgeResult, BridgeTlsClientConfig,
+    BridgeClientOptions, BridgeEndpoint, BridgeError, BridgeReconnectPolicy, BridgeResult,
+    BridgeTlsClientConfig,
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -69,6 +71,10 @@ impl PiperBridgeClient {
         self.inner.get_status()
     }
 
+    pub fn list_sessions(&mut self) -> BridgeResult<Vec<BridgeSessionInfo>> {
+        self.inner.list_sessions()
+    }
+
     pub fn set_filters(&mut self, filters: Vec<CanIdFilter>) -> BridgeResult<()> {
         self.inner.set_filters(filters)
     }
@@ -90,6 +96,14 @@ impl PiperBridgeClient {
         })
     }
 
+    pub fn reconnect(&mut self) -> BridgeResult<()> {
+        self.inner.reconnect()
+    }
+
+    pub fn reconnect_count(&self) -> u64 {
+        self.inner.reconnect_count()
+    }
+
     pub fn disconnect(&mut self) {
         self.inner.disconnect();
     }
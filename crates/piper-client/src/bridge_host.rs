@@ -11,15 +11,15 @@ use mio::net::TcpStream as MioTcpStream;
 use mio::net::UnixStream as MioUnixStream;
 use mio::{Events, Interest, Poll, Token, Waker};
 use piper_can::bridge::protocol::{
-    self, BridgeDeviceState, BridgeEvent, BridgeRole, BridgeStatus, CanIdFilter, ClientRequest,
-    ErrorCode, MAX_PAYLOAD_LEN, ServerMessage, ServerResponse, SessionToken,
+    self, BridgeDeviceState, BridgeEvent, BridgeRole, BridgeSessionInfo, BridgeStatus, CanIdFilter,
+    ClientRequest, ErrorCode, MAX_PAYLOAD_LEN, ServerMessage, ServerResponse, SessionToken,
 };
 use piper_can::{CanId, PiperFrame};
 use piper_driver::hooks::FrameCallback;
 use piper_driver::recording::RecordedFrameEvent;
 use piper_driver::{
     DriverError, HealthStatus, HookHandle, MaintenanceGateState, MaintenanceLeaseAcquireResult,
-    MaintenanceRevocationEvent, Piper as RobotPiper,
+    MaintenanceRevocationEvent, MaintenanceRevocationReason, Piper as RobotPiper,
 };
 use rustls::pki_types::PrivateKeyDer;
 use rustls::server::{ServerConfig, ServerConnection, WebPkiClientVerifier};
@@ -44,6 +44,7 @@ const RAW_FRAME_TAP_QUEUE_CAPACITY: usize = 1024;
 const AUTH_LOG_WINDOW: Duration = Duration::from_secs(1);
 const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
 const MAX_EVENT_BURST: usize = 128;
+const MAX_LISTED_SESSIONS: usize = 64;
 const SOCKET_TOKEN: Token = Token(0);
 const WAKE_TOKEN: Token = Token(1);
 
@@ -82,11 +83,21 @@ pub struct BridgeUdsListenerConfig {
     pub granted_role: BridgeRole,
 }
 
+/// Per-session token bucket applied to maintenance `SendFrame` requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BridgeSendRateLimit {
+    pub frames_per_sec: u32,
+    pub burst: u32,
+}
+
 #[derive(Debug, Clone)]
 pub struct BridgeHostConfig {
     pub uds: Option<BridgeUdsListenerConfig>,
     pub tcp_tls: Option<BridgeTlsServerConfig>,
     pub allow_raw_frame_tap: bool,
+    pub send_rate_limit: Option<BridgeSendRateLimit>,
+    /// Revokes the maintenance lease when its holder sends no request for this long.
+    pub writer_lease_idle_timeout: Option<Duration>,
 }
 
 impl Default for BridgeHostConfig {
@@ -101,6 +112,41 @@ impl Default for BridgeHostConfig {
             uds: None,
             tcp_tls: None,
             allow_raw_frame_tap: false,
+            send_rate_limit: None,
+            writer_lease_idle_timeout: None,
+        }
+    }
+}
+
+struct SendRateLimiter {
+    limit: BridgeSendRateLimit,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl SendRateLimiter {
+    fn new(limit: BridgeSendRateLimit, now: Instant) -> Self {
+        Self {
+            limit,
+            tokens: Self::capacity(limit),
+            refilled_at: now,
+        }
+    }
+
+    fn capacity(limit: BridgeSendRateLimit) -> f64 {
+        f64::from(limit.burst.max(1))
+    }
+
+    fn try_acquire(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.refilled_at = now;
+        self.tokens = (self.tokens + elapsed * f64::from(self.limit.frames_per_sec))
+            .min(Self::capacity(self.limit));
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
         }
     }
 }
@@ -244,6 +290,10 @@ trait BridgeControllerBackend: Send + Sync {
         &self,
         authority: &SessionAuthority,
     ) -> Result<bool, BridgeBackendError>;
+    fn revoke_idle_maintenance_lease(
+        &self,
+        authority: &SessionAuthority,
+    ) -> Result<bool, BridgeBackendError>;
     fn send_maintenance_frame(
         &self,
         authority: &SessionAuthority,
@@ -361,6 +411,7 @@ struct BridgeHostStats {
     queue_drop_total: AtomicU64,
     inactive_enqueue_total: AtomicU64,
     session_replacement_discard_total: AtomicU64,
+    send_throttle_total: AtomicU64,
 }
 
 impl BridgeHostStats {
@@ -374,6 +425,7 @@ impl BridgeHostStats {
             queue_drop_total: AtomicU64::new(0),
             inactive_enqueue_total: AtomicU64::new(0),
             session_replacement_discard_total: AtomicU64::new(0),
+            send_throttle_total: AtomicU64::new(0),
         }
     }
 
@@ -1098,6 +1150,8 @@ struct BridgeSession {
     authority_epoch: AtomicU64,
     replacement_close_queued: AtomicBool,
     wake: Arc<dyn ConnectionWake>,
+    peer_label: String,
+    last_seen: Mutex<Instant>,
 }
 
 impl BridgeSession {
@@ -1111,6 +1165,7 @@ impl BridgeSession {
         event_tx: Sender<ConnectionOutput>,
         control_tx: Sender<ConnectionOutput>,
         wake: Arc<dyn ConnectionWake>,
+        peer_label: String,
     ) -> Self {
         Self {
             session_id,
@@ -1125,6 +1180,27 @@ impl BridgeSession {
             authority_epoch: AtomicU64::new(1),
             replacement_close_queued: AtomicBool::new(false),
             wake,
+            peer_label,
+            last_seen: Mutex::new(Instant::now()),
+        }
+    }
+
+    fn touch(&self) {
+        *self.last_seen.lock().unwrap() = Instant::now();
+    }
+
+    fn idle_for(&self) -> Duration {
+        self.last_seen.lock().unwrap().elapsed()
+    }
+
+    fn info(&self, raw_frame_tap: bool) -> BridgeSessionInfo {
+        let idle = self.idle_for();
+        BridgeSessionInfo {
+            session_id: self.session_id,
+            role_granted: self.role_granted,
+            peer: self.peer_label.clone(),
+            idle_ms: idle.as_millis().min(u128::from(u32::MAX)) as u32,
+            raw_frame_tap,
         }
     }
 
@@ -1191,11 +1267,7 @@ impl BridgeSession {
     }
 
     fn matches_filter(&self, can_id: CanId) -> bool {
-        let filters = self.filters.read().unwrap();
-        if filters.is_empty() {
-            return true;
-        }
-        filters.iter().any(|filter| filter.matches(can_id))
+        protocol::filters_accept(&self.filters.read().unwrap(), can_id)
     }
 
     fn enqueue_frame(&self, frame: PiperFrame) -> EnqueueFrameResult {
@@ -1282,9 +1354,15 @@ struct PreparedSession {
     control_tx: Sender<ConnectionOutput>,
     control_rx: Receiver<ConnectionOutput>,
     wake: Arc<dyn ConnectionWake>,
+    peer_label: String,
 }
 
 impl PreparedSession {
+    fn with_peer_label(mut self, peer_label: impl Into<String>) -> Self {
+        self.peer_label = peer_label.into();
+        self
+    }
+
     fn session_id(&self) -> u32 {
         self.session_id
     }
@@ -1370,6 +1448,7 @@ impl SessionManager {
             control_tx,
             control_rx,
             wake,
+            peer_label: String::new(),
         }
     }
 
@@ -1399,6 +1478,7 @@ impl SessionManager {
             prepared.event_tx,
             prepared.control_tx,
             prepared.wake,
+            prepared.peer_label,
         ));
         registry.token_to_session.insert(prepared.session_token, prepared.session_id);
         registry.key_to_session.insert(prepared.session_key, prepared.session_id);
@@ -1473,6 +1553,30 @@ impl SessionManager {
         true
     }
 
+    fn idle_sessions(&self, idle_timeout: Duration) -> Vec<Arc<BridgeSession>> {
+        self.registry
+            .read()
+            .unwrap()
+            .sessions
+            .values()
+            .filter(|session| session.is_active() && session.idle_for() >= idle_timeout)
+            .cloned()
+            .collect()
+    }
+
+    fn session_infos(&self) -> Vec<BridgeSessionInfo> {
+        let registry = self.registry.read().unwrap();
+        let mut infos = registry
+            .sessions
+            .values()
+            .filter(|session| session.is_active())
+            .map(|session| session.info(registry.raw_tap_sessions.contains(&session.session_key())))
+            .collect::<Vec<_>>();
+        infos.sort_by_key(|info| info.session_id);
+        infos.truncate(MAX_LISTED_SESSIONS);
+        infos
+    }
+
     fn raw_tap_subscriber_count(&self) -> usize {
         self.registry.read().unwrap().raw_tap_sessions.len()
     }
@@ -1520,6 +1624,7 @@ struct ConnectionContext<'a> {
     raw_tap: &'a Arc<Mutex<RawTapManager>>,
     stats: &'a Arc<BridgeHostStats>,
     warn_limiter: &'a Arc<WarnRateLimiter>,
+    send_rate_limit: Option<BridgeSendRateLimit>,
 }
 
 pub struct PiperBridgeHost {
@@ -1596,6 +1701,17 @@ impl PiperBridgeHost {
                 })?;
         }
 
+        if let Some(idle_timeout) = self.config.writer_lease_idle_timeout {
+            let backend = Arc::clone(&self.backend);
+            let sessions = Arc::clone(&self.sessions);
+            thread::Builder::new()
+                .name("bridge_lease_idle".into())
+                .spawn(move || Self::lease_idle_loop(backend, sessions, idle_timeout))
+                .map_err(|err| {
+                    BridgeHostError::Io(format!("failed to spawn lease idle loop: {err}"))
+                })?;
+        }
+
         let mut handles = Vec::new();
 
         #[cfg(unix)]
@@ -1621,6 +1737,7 @@ impl PiperBridgeHost {
             let raw_tap_manager = Arc::clone(&raw_tap);
             let stats = Arc::clone(&self.stats);
             let warn_limiter = Arc::clone(&self.warn_limiter);
+            let send_rate_limit = self.config.send_rate_limit;
             handles.push(
                 thread::Builder::new()
                     .name("bridge_accept_uds".into())
@@ -1659,6 +1776,7 @@ impl PiperBridgeHost {
                                                 raw_tap: &raw_tap_manager,
                                                 stats: &stats,
                                                 warn_limiter: &warn_limiter,
+                                                send_rate_limit,
                                             },
                                         );
                                     });
@@ -1684,6 +1802,7 @@ impl PiperBridgeHost {
             let raw_tap_manager = Arc::clone(&raw_tap);
             let stats = Arc::clone(&self.stats);
             let warn_limiter = Arc::clone(&self.warn_limiter);
+            let send_rate_limit = self.config.send_rate_limit;
             let tls_listener = Arc::clone(&tls_listener);
             handles.push(
                 thread::Builder::new()
@@ -1713,6 +1832,7 @@ impl PiperBridgeHost {
                                                         raw_tap: &raw_tap_manager,
                                                         stats: &stats,
                                                         warn_limiter: &warn_limiter,
+                                                        send_rate_limit,
                                                     },
                                                 )
                                             },
@@ -1770,6 +1890,35 @@ impl PiperBridgeHost {
         }
     }
 
+    fn lease_idle_loop(
+        backend: Arc<dyn BridgeControllerBackend>,
+        sessions: Arc<SessionManager>,
+        idle_timeout: Duration,
+    ) {
+        let tick = (idle_timeout / 4).clamp(Duration::from_millis(10), Duration::from_secs(1));
+        loop {
+            thread::sleep(tick);
+            Self::revoke_idle_leases(backend.as_ref(), &sessions, idle_timeout);
+        }
+    }
+
+    fn revoke_idle_leases(
+        backend: &dyn BridgeControllerBackend,
+        sessions: &Arc<SessionManager>,
+        idle_timeout: Duration,
+    ) -> usize {
+        let mut revoked = 0;
+        for session in sessions.idle_sessions(idle_timeout) {
+            let authority = SessionAuthority::new(session, sessions);
+            match backend.revoke_idle_maintenance_lease(&authority) {
+                Ok(true) => revoked += 1,
+                Ok(false) => {},
+                Err(err) => warn!("failed to revoke idle bridge maintenance lease: {err}"),
+            }
+        }
+        revoked
+    }
+
     fn build_status(
         backend: &dyn BridgeControllerBackend,
         sessions: &SessionManager,
@@ -1801,6 +1950,9 @@ impl PiperBridgeHost {
             session_replacement_discard_count: stats
                 .session_replacement_discard_total
                 .load(Ordering::Relaxed),
+            send_throttle_count: stats.send_throttle_total.load(Ordering::Relaxed),
+            rx_frame_count: stats.frame_rx_total.load(Ordering::Relaxed),
+            tx_frame_count: stats.maintenance_tx_total.load(Ordering::Relaxed),
         }
     }
 
@@ -2093,6 +2245,8 @@ impl PiperBridgeHost {
         let mut response_queue: VecDeque<QueuedMessage> = VecDeque::new();
         let mut event_queue: VecDeque<QueuedMessage> = VecDeque::new();
         let mut pending_post_flush: Option<PostFlushAction> = None;
+        let mut send_limiter =
+            ctx.send_rate_limit.map(|limit| SendRateLimiter::new(limit, Instant::now()));
 
         'actor: loop {
             let mut force_poll = false;
@@ -2271,12 +2425,15 @@ impl PiperBridgeHost {
                                 continue;
                             }
 
-                            let prepared = ctx.sessions.prepare_session(
-                                session_token,
-                                granted_role,
-                                filters,
-                                Arc::clone(&wake),
-                            );
+                            let prepared = ctx
+                                .sessions
+                                .prepare_session(
+                                    session_token,
+                                    granted_role,
+                                    filters,
+                                    Arc::clone(&wake),
+                                )
+                                .with_peer_label(peer_label.clone());
                             response_queue.push_back(QueuedMessage::response_with_post_flush(
                                 ServerResponse::HelloAck {
                                     request_id,
@@ -2316,8 +2473,17 @@ impl PiperBridgeHost {
                                 );
                                 continue;
                             }
+                            active_session.touch();
 
                             match other {
+                                ClientRequest::ListSessions { request_id } => {
+                                    response_queue.push_back(QueuedMessage::response(
+                                        ServerResponse::SessionList {
+                                            request_id,
+                                            sessions: ctx.sessions.session_infos(),
+                                        },
+                                    ));
+                                },
                                 ClientRequest::GetStatus { request_id } => {
                                     let status = Self::build_status(
                                         ctx.backend.as_ref(),
@@ -2427,6 +2593,20 @@ impl PiperBridgeHost {
                                     ));
                                 },
                                 ClientRequest::SendFrame { request_id, frame } => {
+                                    if let Some(limiter) = send_limiter.as_mut()
+                                        && !limiter.try_acquire(Instant::now())
+                                    {
+                                        ctx.stats
+                                            .send_throttle_total
+                                            .fetch_add(1, Ordering::Relaxed);
+                                        Self::queue_error_response(
+                                            &mut response_queue,
+                                            request_id,
+                                            ErrorCode::Throttled,
+                                            "bridge send rate limit exceeded",
+                                        );
+                                        continue;
+                                    }
                                     match ctx.backend.send_maintenance_frame(&authority, frame) {
                                         Ok(()) => {
                                             ctx.stats
@@ -2498,7 +2678,9 @@ fn bridge_backend_error_from_driver(error: &DriverError) -> BridgeBackendError {
         | DriverError::CommandAbortedByFault
         | DriverError::CommandAbortedByStateTransition
         | DriverError::RealtimeDeliveryAbortedByFault { .. } => ErrorCode::PermissionDenied,
-        DriverError::MaintenanceWriteDenied(_) => ErrorCode::PermissionDenied,
+        DriverError::MaintenanceWriteDenied(_)
+        | DriverError::UnsupportedByFirmware { .. }
+        | DriverError::RobotFault(_) => ErrorCode::PermissionDenied,
         DriverError::ChannelClosed
         | DriverError::IoThread(_)
         | DriverError::NotDualThread
@@ -2591,6 +2773,16 @@ impl MaintenanceBroker {
             .map_err(|err| bridge_backend_error_from_driver(&err))
     }
 
+    fn revoke_idle(&self, authority: &SessionAuthority) -> Result<bool, BridgeBackendError> {
+        self.driver
+            .revoke_maintenance_lease_gate(
+                authority.session_key().raw(),
+                MaintenanceRevocationReason::IdleTimeout,
+            )
+            .map(|event| event.is_some())
+            .map_err(|err| bridge_backend_error_from_driver(&err))
+    }
+
     fn issue_send_permit(
         &self,
         authority: &SessionAuthority,
@@ -2693,6 +2885,13 @@ impl BridgeControllerBackend for PiperBridgeBackend {
         self.broker.release(authority)
     }
 
+    fn revoke_idle_maintenance_lease(
+        &self,
+        authority: &SessionAuthority,
+    ) -> Result<bool, BridgeBackendError> {
+        self.broker.revoke_idle(authority)
+    }
+
     fn send_maintenance_frame(
         &self,
         authority: &SessionAuthority,
@@ -2784,7 +2983,8 @@ fn request_id_of(request: &ClientRequest) -> u32 {
         | ClientRequest::AcquireWriterLease { request_id, .. }
         | ClientRequest::ReleaseWriterLease { request_id }
         | ClientRequest::SendFrame { request_id, .. }
-        | ClientRequest::Ping { request_id } => *request_id,
+        | ClientRequest::Ping { request_id }
+        | ClientRequest::ListSessions { request_id } => *request_id,
     }
 }
 
@@ -2798,6 +2998,7 @@ fn message_kind(request: &ClientRequest) -> &'static str {
         ClientRequest::ReleaseWriterLease { .. } => "ReleaseWriterLease",
         ClientRequest::SendFrame { .. } => "SendFrame",
         ClientRequest::Ping { .. } => "Ping",
+        ClientRequest::ListSessions { .. } => "ListSessions",
     }
 }
 
@@ -2913,6 +3114,13 @@ mod tests {
             Ok(false)
         }
 
+        fn revoke_idle_maintenance_lease(
+            &self,
+            _authority: &SessionAuthority,
+        ) -> Result<bool, BridgeBackendError> {
+            Ok(false)
+        }
+
         fn send_maintenance_frame(
             &self,
             _authority: &SessionAuthority,
@@ -3160,6 +3368,64 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn session_infos_list_active_sessions_with_peer_and_raw_tap_state() {
+        let manager = Arc::new(SessionManager::new());
+        let observer = manager.commit_prepared(
+            manager
+                .prepare_session(token(1), BridgeRole::Observer, vec![], Arc::new(NoopWake))
+                .with_peer_label("unix://peer"),
+        );
+        let writer = manager.commit_prepared(
+            manager
+                .prepare_session(
+                    token(2),
+                    BridgeRole::WriterCandidate,
+                    vec![],
+                    Arc::new(NoopWake),
+                )
+                .with_peer_label("tls://127.0.0.1:50000"),
+        );
+        assert!(manager.set_raw_tap_subscription(observer.session.session_key(), true));
+
+        let infos = manager.session_infos();
+        assert_eq!(infos.len(), 2);
+        assert_eq!(infos[0].session_id, observer.session.session_id());
+        assert_eq!(infos[0].peer, "unix://peer");
+        assert!(infos[0].raw_frame_tap);
+        assert_eq!(infos[1].session_id, writer.session.session_id());
+        assert_eq!(infos[1].role_granted, BridgeRole::WriterCandidate);
+        assert!(!infos[1].raw_frame_tap);
+
+        writer.session.replace_and_close();
+        assert_eq!(manager.session_infos().len(), 1);
+    }
+
+    #[test]
+    fn idle_sessions_only_include_active_sessions_past_the_timeout() {
+        let manager = Arc::new(SessionManager::new());
+        let first = manager.commit_prepared(manager.prepare_session(
+            token(1),
+            BridgeRole::WriterCandidate,
+            vec![],
+            Arc::new(NoopWake),
+        ));
+        let second = manager.commit_prepared(manager.prepare_session(
+            token(2),
+            BridgeRole::WriterCandidate,
+            vec![],
+            Arc::new(NoopWake),
+        ));
+
+        assert!(manager.idle_sessions(Duration::from_secs(60)).is_empty());
+        assert_eq!(manager.idle_sessions(Duration::ZERO).len(), 2);
+
+        second.session.replace_and_close();
+        let idle = manager.idle_sessions(Duration::ZERO);
+        assert_eq!(idle.len(), 1);
+        assert_eq!(idle[0].session_id(), first.session.session_id());
+    }
+
     #[test]
     fn session_authority_is_invalidated_by_same_token_replacement() {
         let manager = Arc::new(SessionManager::new());
@@ -3220,6 +3486,7 @@ mod tests {
             event_tx,
             control_tx,
             Arc::new(NoopWake),
+            "unix://peer".to_string(),
         );
 
         for _ in 0..OUTBOUND_QUEUE_CAPACITY {
@@ -3363,6 +3630,28 @@ mod tests {
         assert_eq!(backend.uninstalls.load(Ordering::Relaxed), 0);
     }
 
+    #[test]
+    fn send_rate_limiter_allows_burst_then_refills_at_configured_rate() {
+        let start = Instant::now();
+        let mut limiter = SendRateLimiter::new(
+            BridgeSendRateLimit {
+                frames_per_sec: 100,
+                burst: 3,
+            },
+            start,
+        );
+
+        assert!((0..3).all(|_| limiter.try_acquire(start)));
+        assert!(!limiter.try_acquire(start));
+        assert!(!limiter.try_acquire(start + Duration::from_millis(5)));
+        assert!(limiter.try_acquire(start + Duration::from_millis(11)));
+        assert!(!limiter.try_acquire(start + Duration::from_millis(11)));
+        // 长时间空闲后令牌数不超过 burst
+        let idle = start + Duration::from_secs(10);
+        assert!((0..3).all(|_| limiter.try_acquire(idle)));
+        assert!(!limiter.try_acquire(idle));
+    }
+
     #[test]
     fn supported_bridge_endpoint_count_ignores_uds_when_platform_does_not_support_it() {
         let config = BridgeHostConfig {
@@ -3372,6 +3661,8 @@ mod tests {
             }),
             tcp_tls: None,
             allow_raw_frame_tap: false,
+            send_rate_limit: None,
+            writer_lease_idle_timeout: None,
         };
 
         assert_eq!(supported_bridge_endpoint_count(&config, false), 0);
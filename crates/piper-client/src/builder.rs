@@ -1,11 +1,25 @@
 //! Client 层 Piper Builder
 //!
 //! 提供链式 API 创建 `ConnectedPiper` 实例，自动处理启动握手与固件 quirks 初始化。
+//!
+//! # 多进程共享同一台机械臂
+//!
+//! Builder 只直连 SocketCAN / GS-USB 后端（或 `build_with_adapter()` 注入的适配器），
+//! 不支持经由守护进程连接：实时控制链路必须由一个进程独占。需要让记录器、监控 UI 等其他进程同时访问时，由控制进程调用
+//! `Piper::attach_bridge_host()` 暴露非实时 bridge（UDS / TLS），其他进程再用
+//! [`PiperBridgeClient`](crate::PiperBridgeClient) 连接：可设置 `CanIdFilter`、
+//! 开启原始帧旁路（需 `BridgeHostConfig::allow_raw_frame_tap`），并通过
+//! `BridgeReconnectPolicy` 自动重连。
 
 use crate::connection::initialize_connected_driver;
 use crate::state::*;
-use crate::types::Result;
-use piper_driver::{ConnectionTarget, PiperBuilder as DriverBuilder};
+use crate::types::{Result, RobotError};
+use piper_can::SplittableAdapter;
+use piper_driver::{
+    ConnectionTarget, DropStopAction, PiperBuilder as DriverBuilder, WatchdogConfig,
+};
+use piper_protocol::FirmwareCapabilities;
+use piper_tools::SafetyLimits;
 use std::sync::Arc;
 use std::time::Duration;
 use tracing::debug;
@@ -16,6 +30,10 @@ pub struct PiperBuilder {
     baud_rate: u32,
     feedback_timeout: Duration,
     firmware_timeout: Duration,
+    safety_limits: Option<SafetyLimits>,
+    firmware_capabilities: Option<FirmwareCapabilities>,
+    watchdog: Option<WatchdogConfig>,
+    enable_timeout: Option<Duration>,
 }
 
 impl PiperBuilder {
@@ -66,22 +84,114 @@ impl PiperBuilder {
         self.firmware_timeout = timeout;
         self
     }
+
+    /// 设置关节位置 / 力矩 / 速度安全限制
+    ///
+    /// 位置命令目标按 `limits.joints_min` / `limits.joints_max` 检查，MIT 命令的前馈力矩与
+    /// 速度按 `limits.max_torque_nm` / `limits.max_velocity_rad_s` 检查。超限处理由
+    /// `limits.mode` 决定：
+    /// - `SafetyLimitMode::Clamp`（默认）：限制到边界后下发，并输出警告
+    /// - `SafetyLimitMode::Reject`：整批命令被拒绝并返回 `RobotError::JointLimitExceeded` /
+    ///   `RobotError::TorqueLimitExceeded` / `RobotError::VelocityLimitExceeded`，不会下发
+    ///
+    /// 配置在 `build()` / `build_with_adapter()` 时校验（见 `SafetyLimits::validate`），
+    /// 非法配置（负数/NaN 上限、`joints_min > joints_max`）返回 `RobotError::ConfigError`。
+    pub fn with_safety_limits(mut self, limits: SafetyLimits) -> Self {
+        self.safety_limits = Some(limits);
+        self
+    }
+
+    /// 覆盖按固件版本检测出的能力集合
+    ///
+    /// 默认按能力表从握手得到的固件版本推导；不支持的命令会返回
+    /// `DriverError::UnsupportedByFirmware`。主要用于测试或非标准固件。
+    pub fn firmware_capabilities(mut self, capabilities: FirmwareCapabilities) -> Self {
+        self.firmware_capabilities = Some(capabilities);
+        self
+    }
+
+    /// 启用软件看门狗
+    ///
+    /// 第一次 `pet_watchdog()` 之后，若控制线程超过 `interval` 未再次喂狗，
+    /// driver 的 TX 线程会锁存 `RuntimeFaultKind::WatchdogExpired`、丢弃排队中的命令，
+    /// 并发送 `action` 对应的停止帧（如 `DropStopAction::Disable`）。
+    /// `run_controller()` 每次迭代自动喂狗。
+    pub fn with_watchdog(mut self, interval: Duration, action: DropStopAction) -> Self {
+        self.watchdog = Some(WatchdogConfig::new(interval, action));
+        self
+    }
+
+    /// 设置使能确认超时
+    ///
+    /// 发送使能指令后，`enable_*_mode()` 等待 6 个关节的驱动使能位全部置位（需为发送之后
+    /// 收到的新反馈）；超过该时间返回 `RobotError::Timeout` 并尝试失能。
+    /// 设置后覆盖 `MitModeConfig` / `PositionModeConfig` 等配置中用于该等待的 `timeout`。
+    pub fn enable_timeout(mut self, timeout: Duration) -> Self {
+        self.enable_timeout = Some(timeout);
+        self
+    }
+
     pub fn build(self) -> Result<ConnectedPiper> {
         debug!("Building Piper client connection");
 
-        let driver = Arc::new(
-            DriverBuilder::new()
-                .target(self.target.clone())
-                .baud_rate(self.baud_rate)
-                .startup_validation_timeout(self.feedback_timeout)
-                .build()?,
-        );
+        self.validate()?;
+        let driver = self.driver_builder().build()?;
+        self.connect_driver(driver)
+    }
+
+    /// 使用调用方提供的 CAN 适配器建立连接
+    ///
+    /// 适配器（mock、回放或第三方后端）接入完整的 driver/client 栈，照常执行启动握手、
+    /// 固件 quirks 检测、安全限制与看门狗配置；`target()` / `baud_rate()` 被忽略。
+    pub fn build_with_adapter<A>(self, adapter: A) -> Result<ConnectedPiper>
+    where
+        A: SplittableAdapter + Send + 'static,
+        A::RxAdapter: Send + 'static,
+        A::TxAdapter: Send + 'static,
+    {
+        debug!("Building Piper client connection with a custom CAN adapter");
 
-        let initialized = initialize_connected_driver(
+        self.validate()?;
+        let driver = self.driver_builder().build_with_adapter(adapter)?;
+        self.connect_driver(driver)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if let Some(limits) = &self.safety_limits {
+            limits.validate().map_err(|error| {
+                RobotError::ConfigError(format!("invalid safety limits: {error}"))
+            })?;
+        }
+        Ok(())
+    }
+
+    fn driver_builder(&self) -> DriverBuilder {
+        let mut driver_builder = DriverBuilder::new()
+            .target(self.target.clone())
+            .baud_rate(self.baud_rate)
+            .startup_validation_timeout(self.feedback_timeout);
+        if let Some(watchdog) = self.watchdog {
+            driver_builder = driver_builder.with_watchdog(watchdog.interval, watchdog.action);
+        }
+        driver_builder
+    }
+
+    fn connect_driver(self, driver: piper_driver::Piper) -> Result<ConnectedPiper> {
+        let driver = Arc::new(driver);
+        let mut initialized = initialize_connected_driver(
             driver.clone(),
             self.feedback_timeout,
             self.firmware_timeout,
         )?;
+        initialized.safety_limits = self.safety_limits.map(Arc::new);
+        initialized.enable_timeout = self.enable_timeout;
+        if let Some(capabilities) = self.firmware_capabilities {
+            debug!(
+                "Overriding detected firmware capabilities: {:?}",
+                capabilities
+            );
+            initialized.quirks = initialized.quirks.with_capabilities(capabilities);
+        }
 
         machine::connected_piper_from_driver(driver, initialized)
     }
@@ -94,6 +204,10 @@ impl Default for PiperBuilder {
             baud_rate: 1_000_000,
             feedback_timeout: Duration::from_secs(5),
             firmware_timeout: Duration::from_millis(100),
+            safety_limits: None,
+            firmware_capabilities: None,
+            watchdog: None,
+            enable_timeout: None,
         }
     }
 }
@@ -109,6 +223,16 @@ mod tests {
         assert_eq!(builder.baud_rate, 1_000_000);
         assert_eq!(builder.feedback_timeout, Duration::from_secs(5));
         assert_eq!(builder.firmware_timeout, Duration::from_millis(100));
+        assert!(builder.safety_limits.is_none());
+        assert!(builder.firmware_capabilities.is_none());
+        assert!(builder.watchdog.is_none());
+        assert!(builder.enable_timeout.is_none());
+    }
+
+    #[test]
+    fn test_piper_builder_enable_timeout() {
+        let builder = PiperBuilder::new().enable_timeout(Duration::from_millis(300));
+        assert_eq!(builder.enable_timeout, Some(Duration::from_millis(300)));
     }
 
     #[test]
@@ -127,4 +251,59 @@ mod tests {
         assert_eq!(builder.feedback_timeout, Duration::from_secs(2));
         assert_eq!(builder.firmware_timeout, Duration::from_millis(50));
     }
+
+    #[test]
+    fn test_piper_builder_with_safety_limits() {
+        let limits = SafetyLimits {
+            max_torque_nm: vec![2.0; 6],
+            ..SafetyLimits::default()
+        };
+
+        let builder = PiperBuilder::new().with_safety_limits(limits);
+
+        let stored = builder.safety_limits.expect("limits should be stored");
+        assert_eq!(stored.max_torque_nm, vec![2.0; 6]);
+    }
+
+    #[test]
+    fn test_piper_builder_rejects_inverted_safety_limit_range() {
+        let mut limits = SafetyLimits::default();
+        limits.joints_min[2] = 0.5;
+        limits.joints_max[2] = -0.5;
+
+        // 校验先于连接，不会触达不存在的接口
+        let result = PiperBuilder::new()
+            .socketcan("piper-invalid-limits")
+            .with_safety_limits(limits)
+            .build();
+
+        assert!(
+            matches!(result, Err(RobotError::ConfigError(message)) if message.contains("joint 2"))
+        );
+    }
+
+    #[test]
+    fn test_piper_builder_with_watchdog() {
+        let builder =
+            PiperBuilder::new().with_watchdog(Duration::from_millis(20), DropStopAction::Disable);
+
+        assert_eq!(
+            builder.watchdog,
+            Some(WatchdogConfig::new(
+                Duration::from_millis(20),
+                DropStopAction::Disable
+            ))
+        );
+    }
+
+    #[test]
+    fn test_piper_builder_firmware_capabilities_override() {
+        use piper_protocol::FirmwareFeature;
+
+        let capabilities =
+            FirmwareCapabilities::all().with_feature(FirmwareFeature::MoveCpv, false);
+        let builder = PiperBuilder::new().firmware_capabilities(capabilities);
+
+        assert_eq!(builder.firmware_capabilities, Some(capabilities));
+    }
 }
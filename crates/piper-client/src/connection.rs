@@ -1,5 +1,6 @@
 use crate::types::{DeviceQuirks, Result, RobotError};
 use piper_driver::Piper as DriverPiper;
+use piper_tools::SafetyLimits;
 use semver::Version;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -25,6 +26,8 @@ impl InitialMotionState {
 #[derive(Debug, Clone)]
 pub(crate) struct InitializedConnection {
     pub(crate) quirks: DeviceQuirks,
+    pub(crate) safety_limits: Option<Arc<SafetyLimits>>,
+    pub(crate) enable_timeout: Option<Duration>,
     pub(crate) initial_state: InitialMotionState,
 }
 
@@ -47,6 +50,8 @@ pub(crate) fn initialize_connected_driver(
 
     Ok(InitializedConnection {
         quirks,
+        safety_limits: None,
+        enable_timeout: None,
         initial_state,
     })
 }
@@ -0,0 +1,637 @@
+//! PID 自整定 - 继电反馈法（Åström–Hägglund）
+//!
+//! 对单个关节施加 `bias ± d` 的继电（bang-bang）力矩，使其围绕起始位置产生极限环振荡，
+//! 由振荡半幅值 `a` 与周期 `Tu` 估计临界增益：
+//!
+//! ```text
+//! Ku = 4·d / (π·√(a² − ε²))      ε 为继电滞环宽度
+//! ```
+//!
+//! 再按 [`TuningRule`]（Ziegler–Nichols 类规则）换算为 [`PidGains`]，可直接用于
+//! [`PidController::with_gains`](super::PidController::with_gains)。结果只是粗调起点，
+//! 上线前仍需在实际负载下验证。
+//!
+//! # 安全
+//!
+//! 其余关节以 MIT 阻抗（`hold_kp` / `hold_kd`）保持在起始位置。以下任一条件触发即中止，
+//! 并把所有关节（含被整定关节）拉回起始位置：
+//!
+//! - 偏离起始位置超过 `max_deviation`，或速度超过 `max_velocity`
+//! - 机械臂状态不是 `RobotStatus::Normal`，或运行时健康异常
+//! - `timeout` 内未测到足够的振荡周期
+//!
+//! # 示例
+//!
+//! ```rust,ignore
+//! use piper_client::control::{PidController, RelayAutotuneConfig, relay_autotune};
+//! use piper_client::types::{Joint, JointArray, NewtonMeter, Rad};
+//!
+//! # fn example(
+//! #     robot: piper_client::state::Piper<
+//! #         piper_client::state::Active<piper_client::state::MitMode>,
+//! #         piper_client::state::StrictRealtime,
+//! #     >,
+//! # ) -> Result<(), Box<dyn std::error::Error>> {
+//! let report = relay_autotune(&robot, &RelayAutotuneConfig {
+//!     joint: Joint::J1,
+//!     relay_torque: NewtonMeter(1.5),
+//!     ..Default::default()
+//! })?;
+//! println!("Ku={:.2} Tu={:?}", report.ultimate_gain, report.ultimate_period);
+//!
+//! let gains = report.gains;
+//! let pid = PidController::new(JointArray::splat(Rad(0.0)))
+//!     .with_gains(gains.kp, gains.ki, gains.kd);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::loop_runner::ensure_realtime_control_supported;
+use super::scheduler::{CycleScheduler, SleepStrategy};
+use super::snapshot_ready::{
+    CONTROL_SNAPSHOT_POLL_INTERVAL, CONTROL_SNAPSHOT_READY_TIMEOUT, wait_for_control_snapshot_ready,
+};
+use crate::Piper;
+use crate::observer::ControlReadPolicy;
+use crate::state::{Active, MitMode, StrictRealtime};
+use crate::types::{Joint, JointArray, NewtonMeter, Rad, RadPerSecond, RobotError};
+use piper_protocol::RobotStatus;
+use std::f64::consts::PI;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// PID 增益
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PidGains {
+    /// 比例增益（N·m/rad）
+    pub kp: f64,
+    /// 积分增益（N·m/(rad·s)）
+    pub ki: f64,
+    /// 微分增益（N·m·s/rad）
+    pub kd: f64,
+}
+
+/// 由临界增益/周期换算 PID 增益的规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TuningRule {
+    /// 经典 Ziegler–Nichols：`Kp = 0.6·Ku, Ti = Tu/2, Td = Tu/8`
+    #[default]
+    ZieglerNichols,
+    /// 少量超调：`Kp = Ku/3, Ti = Tu/2, Td = Tu/3`
+    SomeOvershoot,
+    /// 无超调：`Kp = 0.2·Ku, Ti = Tu/2, Td = Tu/3`
+    NoOvershoot,
+}
+
+impl TuningRule {
+    /// 按规则计算 PID 增益
+    pub fn gains(self, ultimate_gain: f64, ultimate_period: Duration) -> PidGains {
+        let (kp_ratio, ti_ratio, td_ratio) = match self {
+            TuningRule::ZieglerNichols => (0.6, 0.5, 0.125),
+            TuningRule::SomeOvershoot => (1.0 / 3.0, 0.5, 1.0 / 3.0),
+            TuningRule::NoOvershoot => (0.2, 0.5, 1.0 / 3.0),
+        };
+        let tu = ultimate_period.as_secs_f64();
+        let kp = kp_ratio * ultimate_gain;
+        PidGains {
+            kp,
+            ki: kp / (ti_ratio * tu),
+            kd: kp * td_ratio * tu,
+        }
+    }
+}
+
+/// 继电自整定配置
+#[derive(Debug, Clone)]
+pub struct RelayAutotuneConfig {
+    /// 被整定的关节
+    pub joint: Joint,
+    /// 继电幅值 `d`
+    pub relay_torque: NewtonMeter,
+    /// 继电偏置力矩（用于抵消重力等静态负载）
+    pub bias_torque: NewtonMeter,
+    /// 继电滞环宽度 `ε`（抑制噪声引起的抖动切换）
+    pub hysteresis: Rad,
+    /// 相对起始位置的最大允许偏离
+    pub max_deviation: Rad,
+    /// 最大允许关节速度
+    pub max_velocity: RadPerSecond,
+    /// 参与统计的振荡周期数
+    pub cycles: usize,
+    /// 开始统计前丢弃的周期数（等待极限环稳定）
+    pub settle_cycles: usize,
+    /// 整定总超时
+    pub timeout: Duration,
+    /// 控制频率（Hz）
+    pub frequency_hz: f64,
+    /// 其余关节保持位置的 MIT Kp
+    pub hold_kp: f64,
+    /// 其余关节保持位置的 MIT Kd
+    pub hold_kd: f64,
+    /// 增益换算规则
+    pub rule: TuningRule,
+    /// 反馈读取策略
+    pub read_policy: ControlReadPolicy,
+}
+
+impl Default for RelayAutotuneConfig {
+    fn default() -> Self {
+        RelayAutotuneConfig {
+            joint: Joint::J1,
+            relay_torque: NewtonMeter(1.0),
+            bias_torque: NewtonMeter(0.0),
+            hysteresis: Rad(0.005),
+            max_deviation: Rad(0.2),
+            max_velocity: RadPerSecond(2.0),
+            cycles: 4,
+            settle_cycles: 1,
+            timeout: Duration::from_secs(20),
+            frequency_hz: 200.0,
+            hold_kp: 10.0,
+            hold_kd: 0.8,
+            rule: TuningRule::default(),
+            read_policy: ControlReadPolicy::default(),
+        }
+    }
+}
+
+impl RelayAutotuneConfig {
+    fn validate(&self) -> Result<(), RobotError> {
+        let positive = [
+            ("relay_torque", self.relay_torque.0),
+            ("max_deviation", self.max_deviation.0),
+            ("max_velocity", self.max_velocity.0),
+            ("frequency_hz", self.frequency_hz),
+        ];
+        for (param, value) in positive {
+            if !(value.is_finite() && value > 0.0) {
+                return Err(invalid_parameter(
+                    param,
+                    format!("must be finite and > 0, got {value}"),
+                ));
+            }
+        }
+        if !(self.hysteresis.0.is_finite()
+            && self.hysteresis.0 >= 0.0
+            && self.hysteresis.0 < self.max_deviation.0)
+        {
+            return Err(invalid_parameter(
+                "hysteresis",
+                format!(
+                    "must be in [0, max_deviation), got {} (max_deviation {})",
+                    self.hysteresis.0, self.max_deviation.0
+                ),
+            ));
+        }
+        if self.cycles == 0 {
+            return Err(invalid_parameter("cycles", "must be > 0".to_string()));
+        }
+        Ok(())
+    }
+}
+
+fn invalid_parameter(param: &str, reason: String) -> RobotError {
+    RobotError::InvalidParameter {
+        param: param.to_string(),
+        reason,
+    }
+}
+
+/// 继电自整定结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RelayAutotuneReport {
+    /// 被整定的关节
+    pub joint: Joint,
+    /// 换算得到的 PID 增益
+    pub gains: PidGains,
+    /// 临界增益 `Ku`（N·m/rad）
+    pub ultimate_gain: f64,
+    /// 临界周期 `Tu`
+    pub ultimate_period: Duration,
+    /// 平均振荡半幅值
+    pub amplitude: Rad,
+    /// 参与统计的周期数
+    pub cycles: usize,
+}
+
+/// 继电自整定错误
+#[derive(Debug, Error)]
+pub enum AutotuneError {
+    /// 超时前未测到足够的振荡周期
+    #[error("Autotune measured {measured}/{required} oscillation cycles within {timeout_ms}ms")]
+    NoOscillation {
+        measured: usize,
+        required: usize,
+        timeout_ms: u64,
+    },
+
+    /// 关节偏离起始位置过远
+    #[error("Joint {joint} deviated {deviation:.3} rad from its start (limit {limit:.3})")]
+    DeviationExceeded {
+        joint: Joint,
+        deviation: f64,
+        limit: f64,
+    },
+
+    /// 关节速度过大
+    #[error("Joint {joint} velocity {velocity:.3} rad/s exceeds {limit:.3}")]
+    VelocityExceeded {
+        joint: Joint,
+        velocity: f64,
+        limit: f64,
+    },
+
+    /// 振荡幅值不大于滞环宽度，无法估计临界增益
+    #[error("Oscillation amplitude {amplitude:.4} rad does not exceed hysteresis {hysteresis:.4}")]
+    AmplitudeTooSmall { amplitude: f64, hysteresis: f64 },
+
+    /// 整定过程中机械臂上报非正常状态
+    #[error("Robot reported {0:?} during autotune")]
+    Fault(RobotStatus),
+
+    /// 发送命令或读取反馈失败
+    #[error(transparent)]
+    Robot(#[from] RobotError),
+}
+
+/// [`RelayAutotuner::update`] 的单步输出
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RelayStep {
+    /// 本周期应施加到被整定关节的力矩
+    Torque(NewtonMeter),
+    /// 已测到足够周期
+    Finished(RelayAutotuneReport),
+}
+
+/// 继电自整定状态机
+///
+/// 不直接访问硬件：每个控制周期输入被整定关节的位置/速度，输出继电力矩。
+/// [`relay_autotune`] 用它驱动真实机械臂，也可接入仿真。
+#[derive(Debug, Clone)]
+pub struct RelayAutotuner {
+    config: RelayAutotuneConfig,
+    setpoint: Rad,
+    output_high: bool,
+    last_rise: Option<Duration>,
+    rises: usize,
+    peak_max: f64,
+    peak_min: f64,
+    periods: Vec<Duration>,
+    amplitudes: Vec<f64>,
+}
+
+impl RelayAutotuner {
+    /// 以 `setpoint` 为振荡中心创建状态机
+    pub fn new(config: RelayAutotuneConfig, setpoint: Rad) -> Self {
+        RelayAutotuner {
+            config,
+            setpoint,
+            output_high: true,
+            last_rise: None,
+            rises: 0,
+            peak_max: setpoint.0,
+            peak_min: setpoint.0,
+            periods: Vec::new(),
+            amplitudes: Vec::new(),
+        }
+    }
+
+    /// 振荡中心
+    pub fn setpoint(&self) -> Rad {
+        self.setpoint
+    }
+
+    /// 推进一个控制周期
+    ///
+    /// `elapsed` 为自整定开始以来的时间。
+    pub fn update(
+        &mut self,
+        position: Rad,
+        velocity: RadPerSecond,
+        elapsed: Duration,
+    ) -> Result<RelayStep, AutotuneError> {
+        let config = &self.config;
+        let error = (position - self.setpoint).0;
+        if error.abs() > config.max_deviation.0 {
+            return Err(AutotuneError::DeviationExceeded {
+                joint: config.joint,
+                deviation: error.abs(),
+                limit: config.max_deviation.0,
+            });
+        }
+        if velocity.0.abs() > config.max_velocity.0 {
+            return Err(AutotuneError::VelocityExceeded {
+                joint: config.joint,
+                velocity: velocity.0.abs(),
+                limit: config.max_velocity.0,
+            });
+        }
+        if elapsed > config.timeout {
+            return Err(AutotuneError::NoOscillation {
+                measured: self.periods.len(),
+                required: config.cycles,
+                timeout_ms: config.timeout.as_millis() as u64,
+            });
+        }
+
+        self.peak_max = self.peak_max.max(position.0);
+        self.peak_min = self.peak_min.min(position.0);
+
+        if self.output_high && error > config.hysteresis.0 {
+            self.output_high = false;
+        } else if !self.output_high && error < -config.hysteresis.0 {
+            // 上升切换：一个完整周期结束
+            self.output_high = true;
+            if let Some(last_rise) = self.last_rise {
+                self.rises += 1;
+                if self.rises > config.settle_cycles {
+                    self.periods.push(elapsed.saturating_sub(last_rise));
+                    self.amplitudes.push((self.peak_max - self.peak_min) / 2.0);
+                }
+            }
+            self.last_rise = Some(elapsed);
+            self.peak_max = position.0;
+            self.peak_min = position.0;
+
+            if self.periods.len() >= config.cycles {
+                return self.finish().map(RelayStep::Finished);
+            }
+        }
+
+        let relay = if self.output_high {
+            config.relay_torque.0
+        } else {
+            -config.relay_torque.0
+        };
+        Ok(RelayStep::Torque(NewtonMeter(config.bias_torque.0 + relay)))
+    }
+
+    fn finish(&self) -> Result<RelayAutotuneReport, AutotuneError> {
+        let cycles = self.periods.len();
+        let ultimate_period = self.periods.iter().sum::<Duration>() / cycles as u32;
+        let amplitude = self.amplitudes.iter().sum::<f64>() / cycles as f64;
+        let hysteresis = self.config.hysteresis.0;
+        if amplitude <= hysteresis {
+            return Err(AutotuneError::AmplitudeTooSmall {
+                amplitude,
+                hysteresis,
+            });
+        }
+
+        let ultimate_gain = 4.0 * self.config.relay_torque.0
+            / (PI * (amplitude.powi(2) - hysteresis.powi(2)).sqrt());
+        Ok(RelayAutotuneReport {
+            joint: self.config.joint,
+            gains: self.config.rule.gains(ultimate_gain, ultimate_period),
+            ultimate_gain,
+            ultimate_period,
+            amplitude: Rad(amplitude),
+            cycles,
+        })
+    }
+}
+
+/// 在真实机械臂上运行继电自整定
+///
+/// 阻塞直到测得 `config.cycles` 个振荡周期或中止。无论成功与否，返回前都会把所有关节
+/// 以 `hold_kp` / `hold_kd` 拉回起始位置。
+///
+/// # 错误
+///
+/// - `AutotuneError::Robot(RobotError::InvalidParameter)`：配置非法
+/// - `AutotuneError::Robot(RobotError::RealtimeUnsupported)`：后端不是 StrictRealtime
+/// - `AutotuneError::Fault`：机械臂状态异常
+/// - 其余见 [`AutotuneError`]
+pub fn relay_autotune(
+    piper: &Piper<Active<MitMode>, StrictRealtime>,
+    config: &RelayAutotuneConfig,
+) -> Result<RelayAutotuneReport, AutotuneError> {
+    config.validate()?;
+    ensure_realtime_control_supported(piper)?;
+
+    let initial = wait_for_control_snapshot_ready(
+        CONTROL_SNAPSHOT_READY_TIMEOUT,
+        CONTROL_SNAPSHOT_POLL_INTERVAL,
+        || piper.observer().control_snapshot(config.read_policy),
+    )?;
+    let joint = config.joint;
+    let hold_positions = initial.position;
+    let velocities = JointArray::splat(0.0);
+    let hold_kp = JointArray::splat(config.hold_kp);
+    let hold_kd = JointArray::splat(config.hold_kd);
+    let mut relay_kp = hold_kp;
+    relay_kp[joint] = 0.0;
+    let mut relay_kd = hold_kd;
+    relay_kd[joint] = 0.0;
+
+    tracing::info!(
+        "Relay autotune on {}: setpoint {:.4} rad, relay ±{:.3} N·m",
+        joint,
+        hold_positions[joint].0,
+        config.relay_torque.0
+    );
+
+    let mut tuner = RelayAutotuner::new(config.clone(), hold_positions[joint]);
+    let mut scheduler = CycleScheduler::new(
+        Duration::from_secs_f64(1.0 / config.frequency_hz),
+        SleepStrategy::Hybrid,
+    );
+    let start = Instant::now();
+    let result = loop {
+        scheduler.wait_next();
+        let step = ensure_robot_normal(piper).and_then(|()| {
+            let snapshot = piper.observer().control_snapshot(config.read_policy)?;
+            tuner.update(
+                snapshot.position[joint],
+                snapshot.velocity[joint],
+                start.elapsed(),
+            )
+        });
+        match step {
+            Ok(RelayStep::Torque(torque)) => {
+                let mut torques = JointArray::splat(NewtonMeter(0.0));
+                torques[joint] = torque;
+                if let Err(error) = piper.command_torques(
+                    &hold_positions,
+                    &velocities,
+                    &relay_kp,
+                    &relay_kd,
+                    &torques,
+                ) {
+                    break Err(error.into());
+                }
+            },
+            Ok(RelayStep::Finished(report)) => break Ok(report),
+            Err(error) => break Err(error),
+        }
+    };
+
+    // 撤销继电力矩，所有关节保持在起始位置
+    if let Err(error) = piper.command_torques(
+        &hold_positions,
+        &velocities,
+        &hold_kp,
+        &hold_kd,
+        &JointArray::splat(NewtonMeter(0.0)),
+    ) {
+        tracing::warn!("Failed to restore hold command after autotune: {}", error);
+    }
+
+    match &result {
+        Ok(report) => tracing::info!(
+            "Relay autotune on {} finished: Ku={:.3}, Tu={:?}, gains={:?}",
+            joint,
+            report.ultimate_gain,
+            report.ultimate_period,
+            report.gains
+        ),
+        Err(error) => tracing::warn!("Relay autotune on {} aborted: {}", joint, error),
+    }
+    result
+}
+
+fn ensure_robot_normal(
+    piper: &Piper<Active<MitMode>, StrictRealtime>,
+) -> Result<(), AutotuneError> {
+    let observer = piper.observer();
+    let health = observer.runtime_health();
+    if health.fault.is_some() || !health.rx_alive || !health.tx_alive {
+        return Err(RobotError::runtime_health_unhealthy(
+            health.rx_alive,
+            health.tx_alive,
+            health.fault,
+        )
+        .into());
+    }
+    let status = RobotStatus::from(observer.robot_control_snapshot().robot_status);
+    if status != RobotStatus::Normal {
+        return Err(AutotuneError::Fault(status));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 带粘性阻尼的单关节：`J·θ'' = u − b·θ'`，输出经一拍延迟
+    struct SimulatedJoint {
+        position: f64,
+        velocity: f64,
+        pending_torque: f64,
+    }
+
+    impl SimulatedJoint {
+        const INERTIA: f64 = 0.05;
+        const DAMPING: f64 = 0.2;
+
+        fn step(&mut self, torque: f64, dt: f64) {
+            let acceleration =
+                (self.pending_torque - Self::DAMPING * self.velocity) / Self::INERTIA;
+            self.velocity += acceleration * dt;
+            self.position += self.velocity * dt;
+            self.pending_torque = torque;
+        }
+    }
+
+    fn run_simulation(config: RelayAutotuneConfig) -> Result<RelayAutotuneReport, AutotuneError> {
+        let dt = Duration::from_millis(5);
+        let mut joint = SimulatedJoint {
+            position: 0.3,
+            velocity: 0.0,
+            pending_torque: 0.0,
+        };
+        let mut tuner = RelayAutotuner::new(config, Rad(0.3));
+        let mut elapsed = Duration::ZERO;
+        loop {
+            match tuner.update(Rad(joint.position), RadPerSecond(joint.velocity), elapsed)? {
+                RelayStep::Torque(torque) => joint.step(torque.0, dt.as_secs_f64()),
+                RelayStep::Finished(report) => return Ok(report),
+            }
+            elapsed += dt;
+        }
+    }
+
+    #[test]
+    fn relay_autotune_converges_on_simulated_joint() {
+        let report = run_simulation(RelayAutotuneConfig {
+            joint: Joint::J2,
+            relay_torque: NewtonMeter(0.2),
+            hysteresis: Rad(0.002),
+            ..Default::default()
+        })
+        .expect("simulated joint should oscillate");
+
+        assert_eq!(report.joint, Joint::J2);
+        assert_eq!(report.cycles, 4);
+        assert!(report.amplitude.0 > 0.002 && report.amplitude.0 < 0.2);
+        assert!(report.ultimate_period > Duration::from_millis(20));
+        assert!(report.ultimate_gain > 0.0);
+        let expected =
+            TuningRule::ZieglerNichols.gains(report.ultimate_gain, report.ultimate_period);
+        assert_eq!(report.gains, expected);
+        assert!(report.gains.kp > 0.0 && report.gains.ki > 0.0 && report.gains.kd > 0.0);
+    }
+
+    #[test]
+    fn relay_autotune_aborts_when_deviation_exceeded() {
+        let error = run_simulation(RelayAutotuneConfig {
+            relay_torque: NewtonMeter(5.0),
+            hysteresis: Rad(0.05),
+            max_deviation: Rad(0.06),
+            max_velocity: RadPerSecond(100.0),
+            ..Default::default()
+        })
+        .unwrap_err();
+
+        assert!(matches!(error, AutotuneError::DeviationExceeded { .. }));
+    }
+
+    #[test]
+    fn relay_autotune_times_out_without_oscillation() {
+        let config = RelayAutotuneConfig {
+            timeout: Duration::from_millis(100),
+            ..Default::default()
+        };
+        let mut tuner = RelayAutotuner::new(config, Rad(0.0));
+        let mut elapsed = Duration::ZERO;
+        let error = loop {
+            match tuner.update(Rad(0.0), RadPerSecond(0.0), elapsed) {
+                Ok(_) => elapsed += Duration::from_millis(10),
+                Err(error) => break error,
+            }
+        };
+
+        assert!(matches!(
+            error,
+            AutotuneError::NoOscillation {
+                measured: 0,
+                required: 4,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn tuning_rules_follow_ziegler_nichols_ratios() {
+        let gains = TuningRule::ZieglerNichols.gains(10.0, Duration::from_millis(500));
+        assert!((gains.kp - 6.0).abs() < 1e-9);
+        assert!((gains.ki - 24.0).abs() < 1e-9);
+        assert!((gains.kd - 0.375).abs() < 1e-9);
+
+        let gentle = TuningRule::NoOvershoot.gains(10.0, Duration::from_millis(500));
+        assert!(gentle.kp < gains.kp);
+    }
+
+    #[test]
+    fn config_validation_rejects_non_positive_relay() {
+        let config = RelayAutotuneConfig {
+            relay_torque: NewtonMeter(0.0),
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(RobotError::InvalidParameter { .. })
+        ));
+    }
+}
@@ -7,6 +7,7 @@
 //! - **精确定时**: 使用 `spin_sleep` 实现低抖动延时
 //! - **dt 钳位**: 限制异常大的时间步长
 //! - **时间跳变处理**: 自动调用 `on_time_jump()`
+//! - **截止期监控**: 统计单次迭代执行时间超出周期的次数，可回调或在连续超时后中止
 //! - **错误传播**: 透明传播控制器和命令错误
 //!
 //! # 使用场景
@@ -21,9 +22,11 @@
 //!     dt_clamp_multiplier: 2.0,         // dt 最大为 2x 标称值
 //!     read_policy: ControlReadPolicy::default(), // 默认严格控制级新鲜度（15ms）
 //!     max_iterations: Some(1000),       // 运行 1000 次后停止
+//!     ..Default::default()
 //! };
 //!
-//! run_controller(piper, controller, config)?;
+//! let stats = run_controller(piper, controller, config)?;
+//! println!("overruns: {}", stats.overruns);
 //! # Ok(())
 //! # }
 //! ```
@@ -38,10 +41,88 @@ use crate::observer::{ControlReadPolicy, ControlSnapshot};
 use crate::state::{Active, MitMode, StrictRealtime};
 use crate::types::{JointArray, NewtonMeter, RobotError};
 use piper_driver::BackendCapability;
+use std::fmt;
+use std::sync::Arc;
 use std::time::Duration;
 
+/// 单次迭代超出周期的事件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoopOverrun {
+    /// 迭代序号（从 0 开始）
+    pub iteration: u64,
+    /// 本次迭代执行时间（读取快照 + 控制器计算 + 发送命令）
+    pub execution: Duration,
+    /// 标称周期
+    pub period: Duration,
+    /// 截至本次的连续超时次数
+    pub consecutive: u64,
+}
+
+/// 超时回调（在控制循环线程中同步执行，必须快速返回）
+pub type OverrunCallback = Arc<dyn Fn(&LoopOverrun) + Send + Sync>;
+
+/// 控制循环时序统计
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LoopStats {
+    /// 已完成的迭代数
+    pub iterations: u64,
+    /// 执行时间超出周期的迭代数
+    pub overruns: u64,
+    /// 当前连续超时次数
+    pub consecutive_overruns: u64,
+    /// 最长连续超时次数
+    pub max_consecutive_overruns: u64,
+    /// 调度器因迟到而跳过的截止期数
+    pub missed_deadlines: u64,
+    /// 最近一次迭代执行时间
+    pub last_execution: Duration,
+    /// 最长迭代执行时间
+    pub max_execution: Duration,
+}
+
+/// 迭代执行时间与周期的比较器
+#[derive(Debug)]
+struct DeadlineMonitor {
+    period: Duration,
+    stats: LoopStats,
+}
+
+impl DeadlineMonitor {
+    fn new(period: Duration) -> Self {
+        Self {
+            period,
+            stats: LoopStats::default(),
+        }
+    }
+
+    /// 记录一次迭代；超出周期时返回超时事件
+    fn record(&mut self, execution: Duration, missed_deadlines: u64) -> Option<LoopOverrun> {
+        let iteration = self.stats.iterations;
+        let stats = &mut self.stats;
+        stats.iterations += 1;
+        stats.missed_deadlines += missed_deadlines;
+        stats.last_execution = execution;
+        stats.max_execution = stats.max_execution.max(execution);
+
+        if execution <= self.period {
+            stats.consecutive_overruns = 0;
+            return None;
+        }
+        stats.overruns += 1;
+        stats.consecutive_overruns += 1;
+        stats.max_consecutive_overruns =
+            stats.max_consecutive_overruns.max(stats.consecutive_overruns);
+        Some(LoopOverrun {
+            iteration,
+            execution,
+            period: self.period,
+            consecutive: stats.consecutive_overruns,
+        })
+    }
+}
+
 /// 控制循环配置
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct LoopConfig {
     /// 控制频率（Hz）
     ///
@@ -64,6 +145,27 @@ pub struct LoopConfig {
     ///
     /// 用于测试或定时运行。
     pub max_iterations: Option<usize>,
+
+    /// 单次迭代执行时间超出周期时的回调
+    pub on_overrun: Option<OverrunCallback>,
+
+    /// 连续超时达到此次数时中止循环（返回 `RobotError::ControlLoopOverrun`）
+    ///
+    /// `None` 表示只统计不中止。
+    pub max_consecutive_overruns: Option<u64>,
+}
+
+impl fmt::Debug for LoopConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LoopConfig")
+            .field("frequency_hz", &self.frequency_hz)
+            .field("dt_clamp_multiplier", &self.dt_clamp_multiplier)
+            .field("read_policy", &self.read_policy)
+            .field("max_iterations", &self.max_iterations)
+            .field("on_overrun", &self.on_overrun.as_ref().map(|_| "Some(..)"))
+            .field("max_consecutive_overruns", &self.max_consecutive_overruns)
+            .finish()
+    }
 }
 
 impl Default for LoopConfig {
@@ -73,6 +175,8 @@ impl Default for LoopConfig {
             dt_clamp_multiplier: 2.0, // 默认 2x
             read_policy: ControlReadPolicy::default(),
             max_iterations: None, // 默认无限循环
+            on_overrun: None,
+            max_consecutive_overruns: None,
         }
     }
 }
@@ -92,8 +196,9 @@ impl Default for LoopConfig {
 ///
 /// # 返回
 ///
-/// - `Ok(())`: 正常结束（达到 max_iterations）
-/// - `Err(RobotError)`: 发生错误
+/// - `Ok(LoopStats)`: 正常结束（达到 max_iterations），附带时序统计
+/// - `Err(RobotError::ControlLoopOverrun)`: 连续超时达到 `max_consecutive_overruns`
+/// - `Err(RobotError)`: 发生其他错误
 ///
 /// # 时间处理
 ///
@@ -102,11 +207,14 @@ impl Default for LoopConfig {
 /// - 计算实际 dt
 /// - 如果 dt > max_dt，调用 `controller.on_time_jump(real_dt)`，然后钳位 dt
 /// - 使用钳位后的 dt 调用 `controller.tick()`，并传入完整 `ControlSnapshot`
+/// - 每次迭代的执行时间（从唤醒到命令发出）与标称周期比较，超出即计为一次超时
+/// - 每次成功发送命令后喂软件看门狗（见 `PiperBuilder::with_watchdog`）
 ///
 /// # 示例
 ///
 /// ```rust,ignore
-/// use piper_client::control::{run_controller, LoopConfig};
+/// use piper_client::control::{run_controller, LoopConfig, LoopOverrun};
+/// use std::sync::Arc;
 /// # use piper_client::state::Piper;
 /// # use piper_client::control::Controller;
 /// # fn example(
@@ -118,6 +226,10 @@ impl Default for LoopConfig {
 ///     dt_clamp_multiplier: 1.5,
 ///     read_policy: ControlReadPolicy::default(), // 默认严格控制级新鲜度（15ms）
 ///     max_iterations: Some(2000),  // 运行 10 秒后停止
+///     on_overrun: Some(Arc::new(|overrun: &LoopOverrun| {
+///         eprintln!("iteration {} took {:?}", overrun.iteration, overrun.execution);
+///     })),
+///     max_consecutive_overruns: Some(10),
 /// };
 ///
 /// run_controller(piper, controller, config)?;
@@ -128,7 +240,7 @@ pub fn run_controller<C>(
     piper: Piper<Active<MitMode>, StrictRealtime>,
     controller: C,
     config: LoopConfig,
-) -> Result<(), RobotError>
+) -> Result<LoopStats, RobotError>
 where
     C: Controller,
     RobotError: From<C::Error>,
@@ -148,7 +260,7 @@ pub fn run_controller_spin<C>(
     piper: Piper<Active<MitMode>, StrictRealtime>,
     controller: C,
     config: LoopConfig,
-) -> Result<(), RobotError>
+) -> Result<LoopStats, RobotError>
 where
     C: Controller,
     RobotError: From<C::Error>,
@@ -192,7 +304,7 @@ fn run_controller_with_strategy<C>(
     mut controller: C,
     config: LoopConfig,
     strategy: SleepStrategy,
-) -> Result<(), RobotError>
+) -> Result<LoopStats, RobotError>
 where
     C: Controller,
     RobotError: From<C::Error>,
@@ -201,7 +313,7 @@ where
     validate_loop_config(&config)?;
 
     if matches!(config.max_iterations, Some(0)) {
-        return Ok(());
+        return Ok(LoopStats::default());
     }
 
     let _initial_snapshot = wait_for_control_snapshot_ready(
@@ -214,6 +326,7 @@ where
     let nominal_period = Duration::from_secs_f64(1.0 / config.frequency_hz);
     let max_dt = nominal_period.mul_f64(config.dt_clamp_multiplier);
     let mut scheduler = CycleScheduler::new(nominal_period, strategy);
+    let mut monitor = DeadlineMonitor::new(nominal_period);
     let mut iteration = 0;
     let zero_positions = crate::types::JointArray::from([crate::types::Rad(0.0); 6]);
     let zero_velocities = crate::types::JointArray::from([0.0; 6]);
@@ -224,7 +337,7 @@ where
         if let Some(max_iter) = config.max_iterations
             && iteration >= max_iter
         {
-            return Ok(());
+            return Ok(monitor.stats);
         }
 
         let cycle = scheduler.wait_next();
@@ -246,12 +359,33 @@ where
             &zero_kd,
             &torques,
         )?;
+        piper.pet_watchdog();
+
+        let execution = cycle.tick_start.elapsed();
+        if let Some(overrun) = monitor.record(execution, cycle.missed_deadlines) {
+            handle_overrun(&config, &overrun)?;
+        }
 
         iteration += 1;
     }
 }
 
-fn ensure_realtime_control_supported(
+fn handle_overrun(config: &LoopConfig, overrun: &LoopOverrun) -> Result<(), RobotError> {
+    if let Some(callback) = &config.on_overrun {
+        callback(overrun);
+    }
+    if let Some(limit) = config.max_consecutive_overruns
+        && overrun.consecutive >= limit
+    {
+        return Err(RobotError::ControlLoopOverrun {
+            consecutive: overrun.consecutive,
+            period_us: overrun.period.as_micros() as u64,
+        });
+    }
+    Ok(())
+}
+
+pub(crate) fn ensure_realtime_control_supported(
     piper: &Piper<Active<MitMode>, StrictRealtime>,
 ) -> Result<(), RobotError> {
     match piper.driver.backend_capability() {
@@ -330,6 +464,7 @@ mod tests {
             dt_clamp_multiplier: 1.5,
             read_policy: ControlReadPolicy::default(),
             max_iterations: Some(1000),
+            ..Default::default()
         };
         assert_eq!(config.frequency_hz, 200.0);
         assert_eq!(config.dt_clamp_multiplier, 1.5);
@@ -360,6 +495,63 @@ mod tests {
         assert_eq!(torques, JointArray::splat(NewtonMeter(0.0)));
     }
 
+    #[test]
+    fn test_deadline_monitor_counts_overruns_and_consecutive_streaks() {
+        let period = Duration::from_millis(1);
+        let mut monitor = DeadlineMonitor::new(period);
+
+        assert_eq!(monitor.record(Duration::from_micros(400), 0), None);
+        let first = monitor.record(Duration::from_micros(1_500), 1).expect("overrun");
+        assert_eq!(first.iteration, 1);
+        assert_eq!(first.consecutive, 1);
+        let second = monitor.record(Duration::from_micros(2_000), 2).expect("overrun");
+        assert_eq!(second.consecutive, 2);
+        assert_eq!(monitor.record(period, 0), None);
+
+        let stats = monitor.stats;
+        assert_eq!(stats.iterations, 4);
+        assert_eq!(stats.overruns, 2);
+        assert_eq!(stats.consecutive_overruns, 0);
+        assert_eq!(stats.max_consecutive_overruns, 2);
+        assert_eq!(stats.missed_deadlines, 3);
+        assert_eq!(stats.last_execution, period);
+        assert_eq!(stats.max_execution, Duration::from_micros(2_000));
+    }
+
+    #[test]
+    fn test_handle_overrun_invokes_callback_and_aborts_at_limit() {
+        let seen = Arc::new(std::sync::atomic::AtomicU64::new(0));
+        let config = LoopConfig {
+            on_overrun: Some({
+                let seen = seen.clone();
+                Arc::new(move |overrun: &LoopOverrun| {
+                    seen.store(overrun.consecutive, std::sync::atomic::Ordering::Relaxed);
+                })
+            }),
+            max_consecutive_overruns: Some(3),
+            ..Default::default()
+        };
+        let overrun = |consecutive| LoopOverrun {
+            iteration: 7,
+            execution: Duration::from_micros(1_200),
+            period: Duration::from_millis(1),
+            consecutive,
+        };
+
+        handle_overrun(&config, &overrun(2)).expect("below limit");
+        assert_eq!(seen.load(std::sync::atomic::Ordering::Relaxed), 2);
+
+        let error = handle_overrun(&config, &overrun(3)).expect_err("limit reached");
+        assert!(matches!(
+            error,
+            RobotError::ControlLoopOverrun {
+                consecutive: 3,
+                period_us: 1_000
+            }
+        ));
+        assert_eq!(seen.load(std::sync::atomic::Ordering::Relaxed), 3);
+    }
+
     #[test]
     fn test_run_controller_defaults_to_hybrid_strategy() {
         assert_eq!(default_sleep_strategy(), SleepStrategy::Hybrid);
@@ -40,6 +40,7 @@
 //!     rest_position: None,
 //!     control_rate: 200.0,
 //!     read_policy: ControlReadPolicy::default(), // 默认严格控制级新鲜度（15ms）
+//!     gravity_compensation: None,
 //! };
 //! # // let mut controller = MitController::new(piper, config);
 //!
@@ -73,6 +74,7 @@ use super::mit_diagnostic_dispatcher::{
 use super::snapshot_ready::{
     CONTROL_SNAPSHOT_POLL_INTERVAL, CONTROL_SNAPSHOT_READY_TIMEOUT, wait_for_control_snapshot_ready,
 };
+use crate::kinematics::GravityModel;
 use crate::observer::{ControlReadPolicy, Observer};
 use crate::raw_commander::RawCommander;
 use crate::state::StrictRealtime;
@@ -121,6 +123,13 @@ pub struct MitControllerConfig {
     ///
     /// 默认建议使用 `ControlReadPolicy::default()`，其最大反馈年龄为 15ms。
     pub read_policy: ControlReadPolicy,
+
+    /// 重力补偿模型
+    ///
+    /// 设置后，每个控制周期按当前关节角（Observer 快照）计算重力力矩并叠加到前馈力矩，
+    /// safe-hold 时按保持锚点计算。配合很小的 `kp_gains` 可实现柔顺保持。
+    /// 默认 `None`（不补偿）。
+    pub gravity_compensation: Option<GravityModel>,
 }
 
 impl Default for MitControllerConfig {
@@ -133,6 +142,7 @@ impl Default for MitControllerConfig {
             rest_position: None,
             control_rate: 200.0,
             read_policy: ControlReadPolicy::default(),
+            gravity_compensation: None,
         }
     }
 }
@@ -400,6 +410,21 @@ impl MitController {
             &config.safe_hold_kd_gains,
         )?;
 
+        if let Some(model) = &config.gravity_compensation {
+            let valid = model.gravity.iter().all(|value| value.is_finite())
+                && model.links.iter().all(|link| {
+                    link.mass_kg.is_finite()
+                        && link.mass_kg >= 0.0
+                        && link.com_m.iter().all(|value| value.is_finite())
+                });
+            if !valid {
+                return Err(RobotError::ConfigError(
+                    "MitControllerConfig.gravity_compensation must have finite gravity, finite non-negative masses and finite centers of mass"
+                        .to_string(),
+                ));
+            }
+        }
+
         Ok(())
     }
 
@@ -739,12 +764,20 @@ impl MitController {
     /// # 参数
     ///
     /// - `target`: 目标位置
-    /// - `feedforward`: 前馈力矩（可选）
+    /// - `feedforward`: 前馈力矩（可选）；配置了重力补偿时再叠加重力力矩
     fn command_joints(
         &self,
         target: JointArray<Rad>,
         feedforward: Option<JointArray<NewtonMeter>>,
     ) -> crate::types::Result<()> {
+        let feedforward = match &self.config.gravity_compensation {
+            Some(model) => {
+                let current = self.observer.control_snapshot(self.config.read_policy)?.position;
+                let gravity = model.torques(&current);
+                Some(feedforward.map_or(gravity, |torques| torques + gravity))
+            },
+            None => feedforward,
+        };
         self.command_joints_with_gains(
             target,
             feedforward,
@@ -755,7 +788,10 @@ impl MitController {
 
     fn send_safe_hold(&self, anchor: JointArray<Rad>) -> crate::types::Result<()> {
         let velocities = JointArray::from([0.0; 6]);
-        let torques = JointArray::from([NewtonMeter(0.0); 6]);
+        let torques = match &self.config.gravity_compensation {
+            Some(model) => model.torques(&anchor),
+            None => JointArray::from([NewtonMeter(0.0); 6]),
+        };
         self.active_piper()?.command_torques_confirmed(
             &anchor,
             &velocities,
@@ -1319,6 +1355,8 @@ mod tests {
             driver,
             observer,
             quirks: DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+            safety_limits: None,
+            enable_timeout: None,
             drop_policy: DropPolicy::Noop,
             driver_mode_drop_policy: DriverModeDropPolicy::Preserve,
             _state: unsafe { std::mem::zeroed() },
@@ -1836,6 +1874,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn safe_hold_adds_gravity_feedforward_when_configured() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let active = build_active_mit_piper(sent_frames.clone(), Duration::ZERO);
+        let controller = MitController::new(
+            active,
+            MitControllerConfig {
+                safe_hold_kp_gains: [0.0; 6],
+                safe_hold_kd_gains: [0.9; 6],
+                gravity_compensation: Some(GravityModel::default()),
+                ..MitControllerConfig::default()
+            },
+        )
+        .expect("strict realtime driver should support MitController");
+
+        let anchor =
+            JointArray::from([Rad(0.0), Rad(1.0), Rad(-1.0), Rad(0.0), Rad(0.0), Rad(0.0)]);
+        controller.send_safe_hold(anchor).expect("safe-hold should be sent");
+
+        let frames = wait_for_sent_frames(&sent_frames, 6);
+        let zero_torque = |joint: u8, position: f64| {
+            MitControlCommand::try_new(joint, position as f32, 0.0, 0.0, 0.9, 0.0)
+                .expect("command should build")
+                .to_frame()
+        };
+        let sent_for = |expected: &PiperFrame| {
+            frames
+                .iter()
+                .find(|frame| frame.id() == expected.id())
+                .copied()
+                .expect("joint frame")
+        };
+        // J1 轴竖直不受重力；J2 需要前馈力矩
+        let j1 = zero_torque(1, 0.0);
+        assert_eq!(sent_for(&j1).data(), j1.data());
+        let j2 = zero_torque(2, 1.0);
+        assert_ne!(sent_for(&j2).data(), j2.data());
+    }
+
     #[test]
     fn move_to_position_read_failure_with_anchor_enters_safe_hold_and_latches_safed_out() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
@@ -3,11 +3,14 @@
 //! 提供高级控制接口，包括：
 //! - `Controller` trait - 控制器通用接口
 //! - `PidController` - PID 位置控制器
+//! - `relay_autotune` - 继电反馈法 PID 自整定
 //! - `MitController` - MIT 模式高层控制器（循环锚点机制）
 //! - `ZeroingConfirmToken` - 关节归零确认令牌
+//! - `ZeroingSession` - 逐关节归零流程
 //! - `TrajectoryPlanner` - 轨迹规划器
 //! - Loop Runner - 控制循环包装器
 
+pub mod autotune;
 pub mod controller;
 pub(crate) mod hot_path_diagnostics;
 pub mod loop_runner;
@@ -17,12 +20,22 @@ pub mod pid;
 pub(crate) mod scheduler;
 pub(crate) mod snapshot_ready;
 pub mod trajectory;
+pub mod zeroing;
 pub mod zeroing_token;
 
 // 重新导出常用类型
+pub use autotune::{
+    AutotuneError, PidGains, RelayAutotuneConfig, RelayAutotuneReport, RelayAutotuner, TuningRule,
+    relay_autotune,
+};
 pub use controller::Controller;
-pub use loop_runner::{LoopConfig, run_controller};
+pub use loop_runner::{
+    LoopConfig, LoopOverrun, LoopStats, OverrunCallback, run_controller, run_controller_spin,
+};
 pub use mit_controller::{ControlError, MitController, MitControllerConfig, SafeAction};
 pub use pid::PidController;
-pub use trajectory::TrajectoryPlanner;
+pub use trajectory::{MotionProfileLimits, ProfiledTrajectory, TrajectoryPlanner};
+pub use zeroing::{
+    JointZeroingOutcome, JointZeroingStatus, ZeroingEvent, ZeroingReport, ZeroingSession,
+};
 pub use zeroing_token::{ZeroingConfirmToken, ZeroingTokenError};
@@ -1,6 +1,7 @@
 //! Trajectory Planner - 轨迹规划器
 //!
-//! 使用三次样条插值生成平滑的关节空间轨迹。
+//! 使用三次样条插值生成平滑的关节空间轨迹；
+//! 也可通过 [`TrajectoryPlanner::profiled`] 生成速度 / 加速度受限的梯形或 S 曲线轨迹。
 //!
 //! # 算法
 //!
@@ -211,6 +212,41 @@ impl TrajectoryPlanner {
         (position, velocity)
     }
 
+    /// 创建带速度剖面约束的轨迹
+    ///
+    /// 与 [`new`](Self::new) 指定时长不同，这里由 `limits` 决定最短时长：
+    /// `max_jerk` 为 `None` 时使用梯形速度剖面，否则使用 S 曲线剖面。
+    /// 起点通常取自 `Observer::joint_positions()`，起止速度均为 0。
+    ///
+    /// # Panics
+    ///
+    /// `limits` 中任一限制不是有限正数时 panic。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// # use piper_client::control::{MotionProfileLimits, TrajectoryPlanner};
+    /// # use piper_client::types::{JointArray, Rad};
+    /// # use std::time::Duration;
+    /// let start = JointArray::from([Rad(0.0); 6]);
+    /// let goal = JointArray::from([Rad(1.0); 6]);
+    /// let mut plan =
+    ///     TrajectoryPlanner::profiled(start, goal, MotionProfileLimits::s_curve(1.0, 2.0, 10.0));
+    ///
+    /// let dt = Duration::from_millis(5); // 200Hz 控制周期
+    /// while let Some(setpoint) = plan.next(dt) {
+    ///     // 下发 setpoint
+    /// #   let _ = setpoint;
+    /// }
+    /// ```
+    pub fn profiled(
+        start: JointArray<Rad>,
+        goal: JointArray<Rad>,
+        limits: MotionProfileLimits,
+    ) -> ProfiledTrajectory {
+        ProfiledTrajectory::plan(start, goal, limits)
+    }
+
     /// 重置迭代器到起点
     pub fn reset(&mut self) {
         self.current_index = 0;
@@ -253,6 +289,287 @@ impl Iterator for TrajectoryPlanner {
     }
 }
 
+/// 速度剖面限制
+///
+/// 用于 [`TrajectoryPlanner::profiled`]：`max_jerk` 为 `None` 时生成梯形速度剖面，
+/// 为 `Some` 时生成加加速度受限的 S 曲线剖面。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MotionProfileLimits {
+    /// 最大关节速度（rad/s）
+    pub max_velocity: f64,
+    /// 最大关节加速度（rad/s²）
+    pub max_acceleration: f64,
+    /// 最大关节加加速度（rad/s³），`None` 表示不限制（梯形剖面）
+    pub max_jerk: Option<f64>,
+}
+
+impl MotionProfileLimits {
+    /// 梯形速度剖面
+    pub fn trapezoidal(max_velocity: f64, max_acceleration: f64) -> Self {
+        Self {
+            max_velocity,
+            max_acceleration,
+            max_jerk: None,
+        }
+    }
+
+    /// S 曲线剖面（加加速度受限）
+    pub fn s_curve(max_velocity: f64, max_acceleration: f64, max_jerk: f64) -> Self {
+        Self {
+            max_velocity,
+            max_acceleration,
+            max_jerk: Some(max_jerk),
+        }
+    }
+
+    fn assert_valid(&self) {
+        assert!(
+            self.max_velocity.is_finite() && self.max_velocity > 0.0,
+            "max_velocity must be positive, got: {}",
+            self.max_velocity
+        );
+        assert!(
+            self.max_acceleration.is_finite() && self.max_acceleration > 0.0,
+            "max_acceleration must be positive, got: {}",
+            self.max_acceleration
+        );
+        if let Some(max_jerk) = self.max_jerk {
+            assert!(
+                max_jerk.is_finite() && max_jerk > 0.0,
+                "max_jerk must be positive, got: {}",
+                max_jerk
+            );
+        }
+    }
+}
+
+/// 加速段（从静止加速到峰值速度）
+///
+/// 减速段与加速段时间对称，因此只需描述加速段。
+/// 梯形剖面对应 `jerk_time == 0`。
+#[derive(Debug, Clone, Copy, Default)]
+struct AccelPhase {
+    /// 加加速度段时长（s）
+    jerk_time: f64,
+    /// 加速段总时长（s）
+    accel_time: f64,
+    /// 峰值加速度（rad/s²）
+    peak_accel: f64,
+    /// 峰值速度（rad/s）
+    peak_velocity: f64,
+}
+
+impl AccelPhase {
+    fn to_velocity(peak_velocity: f64, limits: &MotionProfileLimits) -> Self {
+        let a_max = limits.max_acceleration;
+        match limits.max_jerk {
+            None => Self {
+                jerk_time: 0.0,
+                accel_time: peak_velocity / a_max,
+                peak_accel: a_max,
+                peak_velocity,
+            },
+            // 达不到最大加速度：只有加加速度 / 减加速度两段
+            Some(jerk) if peak_velocity * jerk < a_max * a_max => {
+                let jerk_time = (peak_velocity / jerk).sqrt();
+                Self {
+                    jerk_time,
+                    accel_time: 2.0 * jerk_time,
+                    peak_accel: jerk * jerk_time,
+                    peak_velocity,
+                }
+            },
+            Some(jerk) => {
+                let jerk_time = a_max / jerk;
+                Self {
+                    jerk_time,
+                    accel_time: jerk_time + peak_velocity / a_max,
+                    peak_accel: a_max,
+                    peak_velocity,
+                }
+            },
+        }
+    }
+
+    /// 加速段走过的距离（速度曲线关于中点对称）
+    fn distance(&self) -> f64 {
+        self.peak_velocity * self.accel_time / 2.0
+    }
+
+    /// 加速段内 t 时刻的位移
+    fn position(&self, t: f64) -> f64 {
+        let tj = self.jerk_time;
+        let ta = self.accel_time;
+        let v = self.peak_velocity;
+        if t < tj {
+            self.peak_accel / tj * t * t * t / 6.0
+        } else if t <= ta - tj {
+            self.peak_accel / 6.0 * (3.0 * t * t - 3.0 * tj * t + tj * tj)
+        } else {
+            let rem = ta - t;
+            v * ta / 2.0 - v * rem + self.peak_accel / tj * rem * rem * rem / 6.0
+        }
+    }
+
+    /// 加速段内 t 时刻的速度
+    fn velocity(&self, t: f64) -> f64 {
+        let tj = self.jerk_time;
+        let ta = self.accel_time;
+        if t < tj {
+            self.peak_accel / tj * t * t / 2.0
+        } else if t <= ta - tj {
+            self.peak_accel * (t - tj / 2.0)
+        } else {
+            let rem = ta - t;
+            self.peak_velocity - self.peak_accel / tj * rem * rem / 2.0
+        }
+    }
+}
+
+/// 带速度 / 加速度（/ 加加速度）约束的关节空间轨迹
+///
+/// 由 [`TrajectoryPlanner::profiled`] 创建。所有关节同步到达：以位移最大的关节规划
+/// 归一化剖面，其余关节按位移比例缩放，因此每个关节都满足同一组限制。
+///
+/// 通过 [`next`](Self::next) 按控制周期推进，轨迹结束后返回 `None`。
+#[derive(Debug, Clone)]
+pub struct ProfiledTrajectory {
+    start: JointArray<Rad>,
+    goal: JointArray<Rad>,
+    /// 位移最大关节的位移（rad）
+    distance: f64,
+    accel: AccelPhase,
+    /// 匀速段时长（s）
+    cruise_time: f64,
+    /// 总时长（s）
+    total_time: f64,
+    /// 已推进时间（s）
+    elapsed: f64,
+    finished: bool,
+}
+
+impl ProfiledTrajectory {
+    fn plan(start: JointArray<Rad>, goal: JointArray<Rad>, limits: MotionProfileLimits) -> Self {
+        limits.assert_valid();
+
+        let distance = start
+            .map_with(goal, |s, g| (g.0 - s.0).abs())
+            .iter()
+            .fold(0.0, |a: f64, &b| a.max(b));
+
+        let (accel, cruise_time) = if distance <= 0.0 {
+            (AccelPhase::default(), 0.0)
+        } else {
+            let full = AccelPhase::to_velocity(limits.max_velocity, &limits);
+            if 2.0 * full.distance() <= distance {
+                (
+                    full,
+                    (distance - 2.0 * full.distance()) / limits.max_velocity,
+                )
+            } else {
+                // 距离不足以达到最大速度：二分求解峰值速度（加速段距离关于峰值速度单调）
+                let (mut low, mut high) = (0.0, limits.max_velocity);
+                for _ in 0..100 {
+                    let mid = (low + high) / 2.0;
+                    if 2.0 * AccelPhase::to_velocity(mid, &limits).distance() < distance {
+                        low = mid;
+                    } else {
+                        high = mid;
+                    }
+                }
+                (AccelPhase::to_velocity(high, &limits), 0.0)
+            }
+        };
+
+        Self {
+            start,
+            goal,
+            distance,
+            accel,
+            cruise_time,
+            total_time: 2.0 * accel.accel_time + cruise_time,
+            elapsed: 0.0,
+            finished: false,
+        }
+    }
+
+    /// 推进 `dt` 并返回新的位置设定点
+    ///
+    /// 到达终点时返回一次精确的目标位置，之后返回 `None`。
+    pub fn next(&mut self, dt: Duration) -> Option<JointArray<Rad>> {
+        if self.finished {
+            return None;
+        }
+
+        self.elapsed += dt.as_secs_f64();
+        if self.elapsed >= self.total_time {
+            self.elapsed = self.total_time;
+            self.finished = true;
+            return Some(self.goal);
+        }
+
+        Some(self.sample_at(self.elapsed).0)
+    }
+
+    /// 在 t 时刻（秒，从轨迹起点计）采样位置和速度（rad/s）
+    pub fn sample_at(&self, t: f64) -> (JointArray<Rad>, JointArray<f64>) {
+        if self.distance <= 0.0 || t >= self.total_time {
+            return (self.goal, JointArray::splat(0.0));
+        }
+
+        let (s, ds) = self.normalized_profile(t.max(0.0));
+        let ratio = s / self.distance;
+        let rate = ds / self.distance;
+        let position = self.start.map_with(self.goal, |s, g| Rad(s.0 + (g.0 - s.0) * ratio));
+        let velocity = self.start.map_with(self.goal, |s, g| (g.0 - s.0) * rate);
+        (position, velocity)
+    }
+
+    /// 位移最大关节上的位移与速度
+    fn normalized_profile(&self, t: f64) -> (f64, f64) {
+        let ta = self.accel.accel_time;
+        let decel_start = ta + self.cruise_time;
+        if t < ta {
+            (self.accel.position(t), self.accel.velocity(t))
+        } else if t < decel_start {
+            let v = self.accel.peak_velocity;
+            (self.accel.distance() + v * (t - ta), v)
+        } else {
+            let rem = self.total_time - t;
+            (
+                self.distance - self.accel.position(rem),
+                self.accel.velocity(rem),
+            )
+        }
+    }
+
+    /// 轨迹总时长
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.total_time)
+    }
+
+    /// 已推进的时间
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_secs_f64(self.elapsed)
+    }
+
+    /// 目标位置
+    pub fn goal(&self) -> JointArray<Rad> {
+        self.goal
+    }
+
+    /// 轨迹是否已结束（`next` 已返回目标位置）
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// 重置到轨迹起点
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+        self.finished = false;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -443,4 +760,127 @@ mod tests {
 
         assert!(count > 0);
     }
+
+    fn max_abs_derivatives(plan: &ProfiledTrajectory, joint: usize) -> (f64, f64, f64) {
+        let dt = 1e-4;
+        let steps = (plan.total_time / dt).ceil() as usize;
+        let velocities: Vec<f64> =
+            (0..=steps).map(|i| plan.sample_at(i as f64 * dt).1[joint]).collect();
+        let accels: Vec<f64> = velocities.windows(2).map(|w| (w[1] - w[0]) / dt).collect();
+        let jerks: Vec<f64> = accels.windows(2).map(|w| (w[1] - w[0]) / dt).collect();
+        let max = |values: &[f64]| values.iter().fold(0.0_f64, |a, b| a.max(b.abs()));
+        (max(&velocities), max(&accels), max(&jerks))
+    }
+
+    #[test]
+    fn test_profiled_trapezoid_respects_velocity_and_acceleration() {
+        let start = JointArray::from([Rad(0.0); 6]);
+        let goal = JointArray::from([Rad(2.0), Rad(-1.0), Rad(0.0), Rad(0.5), Rad(0.0), Rad(0.0)]);
+        let plan =
+            TrajectoryPlanner::profiled(start, goal, MotionProfileLimits::trapezoidal(1.0, 2.0));
+
+        // 加速 0.5s + 匀速 1.5s + 减速 0.5s
+        assert!((plan.duration().as_secs_f64() - 2.5).abs() < 1e-9);
+
+        let (max_vel, max_acc, _) = max_abs_derivatives(&plan, 0);
+        assert!((max_vel - 1.0).abs() < 1e-6, "max vel: {}", max_vel);
+        assert!(max_acc <= 2.0 + 1e-3, "max acc: {}", max_acc);
+
+        // 其余关节按比例同步
+        let (mid_pos, mid_vel) = plan.sample_at(1.25);
+        assert!((mid_pos[0].0 - 1.0).abs() < 1e-9);
+        assert!((mid_pos[1].0 + 0.5).abs() < 1e-9);
+        assert!((mid_vel[1] + 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_profiled_trapezoid_short_move_is_triangular() {
+        let start = JointArray::from([Rad(0.0); 6]);
+        let goal = JointArray::from([Rad(0.5); 6]);
+        let plan =
+            TrajectoryPlanner::profiled(start, goal, MotionProfileLimits::trapezoidal(1.0, 2.0));
+
+        // 峰值速度 sqrt(0.5 * 2) = 1.0 → 恰好不需要匀速段；缩短到 0.25 时为三角形
+        let short = TrajectoryPlanner::profiled(
+            start,
+            JointArray::from([Rad(0.25); 6]),
+            MotionProfileLimits::trapezoidal(1.0, 2.0),
+        );
+        assert!((plan.duration().as_secs_f64() - 1.0).abs() < 1e-6);
+        assert!((short.duration().as_secs_f64() - 2.0 * 0.5_f64.sqrt() / 2.0).abs() < 1e-6);
+
+        let (max_vel, _, _) = max_abs_derivatives(&short, 0);
+        assert!(max_vel < 1.0);
+    }
+
+    #[test]
+    fn test_profiled_s_curve_bounds_jerk() {
+        let start = JointArray::from([Rad(0.0); 6]);
+        let goal = JointArray::from([Rad(1.5); 6]);
+        let limits = MotionProfileLimits::s_curve(1.0, 2.0, 10.0);
+        let plan = TrajectoryPlanner::profiled(start, goal, limits);
+
+        let (max_vel, max_acc, max_jerk) = max_abs_derivatives(&plan, 0);
+        assert!(max_vel <= 1.0 + 1e-6, "max vel: {}", max_vel);
+        assert!(max_acc <= 2.0 + 1e-2, "max acc: {}", max_acc);
+        assert!(max_jerk <= 10.0 + 0.5, "max jerk: {}", max_jerk);
+
+        // S 曲线比梯形慢
+        let trapezoid =
+            TrajectoryPlanner::profiled(start, goal, MotionProfileLimits::trapezoidal(1.0, 2.0));
+        assert!(plan.duration() > trapezoid.duration());
+    }
+
+    #[test]
+    fn test_profiled_next_ends_with_goal_then_none() {
+        let start = JointArray::from([Rad(0.0); 6]);
+        let goal = JointArray::from([Rad(0.3); 6]);
+        let mut plan =
+            TrajectoryPlanner::profiled(start, goal, MotionProfileLimits::s_curve(1.0, 2.0, 20.0));
+
+        let dt = Duration::from_millis(5);
+        let mut last = None;
+        let mut count = 0;
+        let mut previous = 0.0;
+        while let Some(setpoint) = plan.next(dt) {
+            assert!(
+                setpoint[0].0 >= previous - 1e-12,
+                "trajectory should be monotonic"
+            );
+            previous = setpoint[0].0;
+            last = Some(setpoint);
+            count += 1;
+        }
+
+        assert_eq!(last, Some(goal));
+        assert!(plan.is_finished());
+        assert_eq!(
+            count,
+            (plan.duration().as_secs_f64() / 0.005).ceil() as usize
+        );
+        assert!(plan.next(dt).is_none());
+
+        plan.reset();
+        assert!(!plan.is_finished());
+        assert!(plan.next(dt).is_some());
+    }
+
+    #[test]
+    fn test_profiled_zero_distance_returns_goal_once() {
+        let start = JointArray::from([Rad(0.2); 6]);
+        let mut plan =
+            TrajectoryPlanner::profiled(start, start, MotionProfileLimits::trapezoidal(1.0, 1.0));
+
+        assert_eq!(plan.duration(), Duration::ZERO);
+        assert_eq!(plan.next(Duration::from_millis(1)), Some(start));
+        assert!(plan.next(Duration::from_millis(1)).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "max_acceleration must be positive")]
+    fn test_profiled_rejects_non_positive_limits() {
+        let start = JointArray::from([Rad(0.0); 6]);
+        let _ =
+            TrajectoryPlanner::profiled(start, start, MotionProfileLimits::trapezoidal(1.0, 0.0));
+    }
 }
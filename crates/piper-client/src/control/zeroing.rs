@@ -0,0 +1,271 @@
+//! 关节归零流程
+//!
+//! 把"逐个关节摆到零点参考位置 → 确认 → 写入零点"这一串操作封装成可脚本化的会话，
+//! 避免手动拼接 0x475 设置帧并自行等待 0x476 应答。
+//!
+//! # 流程
+//!
+//! 1. `Piper<Standby>::begin_zeroing()` 创建 [`ZeroingSession`]（默认 J1 → J6）
+//! 2. [`ZeroingSession::advance`] 取出下一个关节，并发出 [`ZeroingEvent::JointStarted`]
+//! 3. 调用方把该关节移到零点参考位置（Standby 下电机未使能，可手动拖动或借助工装定位）
+//! 4. [`ZeroingSession::commit`] 凭 [`ZeroingConfirmToken`] 写入零点并等待控制器确认；
+//!    不需要归零的关节用 [`ZeroingSession::skip`] 跳过
+//! 5. 全部关节处理完后 [`ZeroingSession::finish`] 返回 [`ZeroingReport`]
+//!
+//! # 示例
+//!
+//! ```rust,no_run
+//! use piper_client::PiperBuilder;
+//! use piper_client::control::{ZeroingConfirmToken, ZeroingEvent};
+//!
+//! # fn wait_for_operator(_joint: piper_client::types::Joint) {}
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let standby = PiperBuilder::new().socketcan("can0").build()?.require_strict()?;
+//! let token = ZeroingConfirmToken::confirm_from_env()?;
+//!
+//! let mut session = standby.begin_zeroing().on_progress(|event| {
+//!     if let ZeroingEvent::JointStarted { joint, step, total } = event {
+//!         println!("[{step}/{total}] move {joint} to its zero reference");
+//!     }
+//! });
+//! while let Some(joint) = session.advance() {
+//!     wait_for_operator(joint);
+//!     session.commit(token)?;
+//! }
+//! let report = session.finish();
+//! assert!(report.is_complete());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::control::ZeroingConfirmToken;
+use crate::state::{CapabilityMarker, Piper, Standby};
+use crate::types::{Joint, Rad, Result, RobotError};
+
+/// 归零进度事件
+#[derive(Debug, Clone, PartialEq)]
+pub enum ZeroingEvent {
+    /// 开始处理某个关节（调用方应把它移到零点参考位置）
+    JointStarted {
+        joint: Joint,
+        /// 当前序号（从 1 开始）
+        step: usize,
+        /// 本次会话的关节总数
+        total: usize,
+    },
+    /// 控制器确认零点已写入
+    JointZeroed {
+        joint: Joint,
+        /// 写入前读取到的关节位置（反馈不可用时为 `None`）
+        position_before: Option<Rad>,
+    },
+    /// 关节被跳过
+    JointSkipped { joint: Joint },
+    /// 写入失败
+    JointFailed { joint: Joint, reason: String },
+    /// 会话结束
+    Finished { zeroed: usize, total: usize },
+}
+
+/// 单个关节的归零结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum JointZeroingStatus {
+    /// 零点已写入并被控制器确认
+    Zeroed,
+    /// 被调用方跳过
+    Skipped,
+    /// 写入失败
+    Failed(String),
+    /// 会话结束前未处理
+    Pending,
+}
+
+/// 单个关节的归零记录
+#[derive(Debug, Clone, PartialEq)]
+pub struct JointZeroingOutcome {
+    pub joint: Joint,
+    pub status: JointZeroingStatus,
+    /// 写入前读取到的关节位置（反馈不可用时为 `None`）
+    pub position_before: Option<Rad>,
+}
+
+/// 归零会话的最终报告
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ZeroingReport {
+    /// 按会话顺序排列的关节记录
+    pub joints: Vec<JointZeroingOutcome>,
+}
+
+impl ZeroingReport {
+    /// 所有关节是否都已成功归零
+    pub fn is_complete(&self) -> bool {
+        !self.joints.is_empty()
+            && self.joints.iter().all(|outcome| outcome.status == JointZeroingStatus::Zeroed)
+    }
+
+    /// 成功归零的关节
+    pub fn zeroed_joints(&self) -> Vec<Joint> {
+        self.joints
+            .iter()
+            .filter(|outcome| outcome.status == JointZeroingStatus::Zeroed)
+            .map(|outcome| outcome.joint)
+            .collect()
+    }
+}
+
+type ProgressCallback<'a> = Box<dyn FnMut(&ZeroingEvent) + 'a>;
+
+/// 关节归零会话
+///
+/// 由 `Piper<Standby>::begin_zeroing()` 创建，借用 Standby 状态期间不能切换状态。
+/// 每个关节的零点单独写入并等待 0x476 确认，不使用协议的批量回零。
+pub struct ZeroingSession<'a, Capability>
+where
+    Capability: CapabilityMarker,
+{
+    piper: &'a Piper<Standby, Capability>,
+    outcomes: Vec<JointZeroingOutcome>,
+    next: usize,
+    active: Option<usize>,
+    on_progress: Option<ProgressCallback<'a>>,
+}
+
+impl<'a, Capability> ZeroingSession<'a, Capability>
+where
+    Capability: CapabilityMarker,
+{
+    pub(crate) fn new(piper: &'a Piper<Standby, Capability>) -> Self {
+        Self {
+            piper,
+            outcomes: Vec::new(),
+            next: 0,
+            active: None,
+            on_progress: None,
+        }
+        .joints(&Joint::ALL)
+    }
+
+    /// 指定需要归零的关节及顺序（重复的关节只保留第一次出现）
+    pub fn joints(mut self, joints: &[Joint]) -> Self {
+        self.outcomes.clear();
+        for &joint in joints {
+            if self.outcomes.iter().all(|outcome| outcome.joint != joint) {
+                self.outcomes.push(JointZeroingOutcome {
+                    joint,
+                    status: JointZeroingStatus::Pending,
+                    position_before: None,
+                });
+            }
+        }
+        self.next = 0;
+        self.active = None;
+        self
+    }
+
+    /// 注册进度回调
+    pub fn on_progress(mut self, callback: impl FnMut(&ZeroingEvent) + 'a) -> Self {
+        self.on_progress = Some(Box::new(callback));
+        self
+    }
+
+    /// 取出下一个待归零的关节
+    ///
+    /// 上一个关节尚未 `commit` / `skip` 时再次调用会返回同一个关节。
+    pub fn advance(&mut self) -> Option<Joint> {
+        if let Some(index) = self.active {
+            return Some(self.outcomes[index].joint);
+        }
+
+        let index = self.next;
+        let joint = self.outcomes.get(index)?.joint;
+        self.next += 1;
+        self.active = Some(index);
+        self.emit(ZeroingEvent::JointStarted {
+            joint,
+            step: index + 1,
+            total: self.outcomes.len(),
+        });
+        Some(joint)
+    }
+
+    /// 把当前关节所在位置写入为零点，并等待控制器确认
+    ///
+    /// # 错误
+    ///
+    /// - `RobotError::ConfigError`：未先调用 `advance()`，或控制器拒绝写入
+    /// - `RobotError::Timeout`：等待 0x476 应答超时
+    pub fn commit(&mut self, _token: ZeroingConfirmToken) -> Result<Joint> {
+        let index = self.active.take().ok_or_else(|| {
+            RobotError::ConfigError("no joint selected; call advance() before commit()".into())
+        })?;
+        let joint = self.outcomes[index].joint;
+        let position_before =
+            self.piper.observer().joint_positions().ok().map(|positions| positions[joint]);
+        self.outcomes[index].position_before = position_before;
+
+        match self.piper.set_joint_zero_positions(&[joint.index()]) {
+            Ok(()) => {
+                self.outcomes[index].status = JointZeroingStatus::Zeroed;
+                self.emit(ZeroingEvent::JointZeroed {
+                    joint,
+                    position_before,
+                });
+                Ok(joint)
+            },
+            Err(error) => {
+                let reason = error.to_string();
+                self.outcomes[index].status = JointZeroingStatus::Failed(reason.clone());
+                self.emit(ZeroingEvent::JointFailed { joint, reason });
+                Err(error)
+            },
+        }
+    }
+
+    /// 跳过当前关节（未调用 `advance()` 时返回 `None`）
+    pub fn skip(&mut self) -> Option<Joint> {
+        let index = self.active.take()?;
+        let joint = self.outcomes[index].joint;
+        self.outcomes[index].status = JointZeroingStatus::Skipped;
+        self.emit(ZeroingEvent::JointSkipped { joint });
+        Some(joint)
+    }
+
+    /// 依次归零全部关节
+    ///
+    /// 每个关节写入前调用 `position_joint`，由调用方把关节移到零点参考位置；
+    /// 其返回错误或任一关节写入失败都会中止流程。
+    pub fn run<F>(
+        mut self,
+        token: ZeroingConfirmToken,
+        mut position_joint: F,
+    ) -> Result<ZeroingReport>
+    where
+        F: FnMut(Joint) -> Result<()>,
+    {
+        while let Some(joint) = self.advance() {
+            position_joint(joint)?;
+            self.commit(token)?;
+        }
+        Ok(self.finish())
+    }
+
+    /// 结束会话并返回报告
+    pub fn finish(mut self) -> ZeroingReport {
+        let zeroed = self
+            .outcomes
+            .iter()
+            .filter(|outcome| outcome.status == JointZeroingStatus::Zeroed)
+            .count();
+        let total = self.outcomes.len();
+        self.emit(ZeroingEvent::Finished { zeroed, total });
+        ZeroingReport {
+            joints: std::mem::take(&mut self.outcomes),
+        }
+    }
+
+    fn emit(&mut self, event: ZeroingEvent) {
+        if let Some(callback) = self.on_progress.as_mut() {
+            callback(&event);
+        }
+    }
+}
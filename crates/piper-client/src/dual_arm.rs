@@ -1910,6 +1910,9 @@ fn force_error_state(
         observer: unsafe { std::ptr::read(&piper.observer) },
         // SAFETY: `piper.quirks` is moved exactly once into the new state wrapper.
         quirks: unsafe { std::ptr::read(&piper.quirks) },
+        // SAFETY: `piper.safety_limits` is moved exactly once into the new state wrapper.
+        safety_limits: unsafe { std::ptr::read(&piper.safety_limits) },
+        enable_timeout: piper.enable_timeout,
         drop_policy: crate::state::machine::DropPolicy::Noop,
         driver_mode_drop_policy: crate::state::machine::DriverModeDropPolicy::Preserve,
         _state: ErrorState,
@@ -2896,6 +2899,8 @@ mod tests {
             driver,
             observer,
             quirks: crate::types::DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+            safety_limits: None,
+            enable_timeout: None,
             drop_policy: crate::state::machine::DropPolicy::Noop,
             driver_mode_drop_policy: crate::state::machine::DriverModeDropPolicy::Preserve,
             _state: state,
@@ -34,9 +34,7 @@ use crate::state::{
     Active, DisableConfig, ErrorState, MitModeConfig, MitPassthroughMode, Piper, SoftRealtime,
     Standby,
 };
-use crate::types::{
-    Joint, JointArray, NewtonMeter, Rad, RadPerSecond, Result as RobotResult, RobotError,
-};
+use crate::types::{JointArray, NewtonMeter, Rad, RadPerSecond, Result as RobotResult, RobotError};
 
 const FAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_millis(20);
 const RAW_CLOCK_STATE_TRANSITION_SEND_TIMEOUT: Duration = Duration::from_millis(50);
@@ -1834,8 +1832,9 @@ impl SoftRealtimeMitFinishedExt for Piper<Active<MitPassthroughMode>, SoftRealti
         timeout: Duration,
     ) -> RobotResult<ConfirmedMitBatch> {
         let raw = RawCommander::new(&self.driver);
-        let (commands, mit_t_ref_nm) = build_validated_mit_command_batch_with_t_refs(
-            self, positions, velocities, kp, kd, torques,
+        // 与 `command_torques_confirmed` 共用同一构建路径：SafetyLimits（clamp/reject）与 quirks
+        let (commands, mit_t_ref_nm) = self.build_validated_mit_command_batch_with_t_refs(
+            positions, velocities, kp, kd, torques,
         )?;
         let tx_finished =
             raw.send_validated_mit_command_batch_confirmed_finished(commands, timeout)?;
@@ -1847,39 +1846,6 @@ impl SoftRealtimeMitFinishedExt for Piper<Active<MitPassthroughMode>, SoftRealti
     }
 }
 
-fn build_validated_mit_command_batch_with_t_refs(
-    piper: &Piper<Active<MitPassthroughMode>, SoftRealtime>,
-    positions: &JointArray<Rad>,
-    velocities: &JointArray<f64>,
-    kp: &JointArray<f64>,
-    kd: &JointArray<f64>,
-    torques: &JointArray<NewtonMeter>,
-) -> RobotResult<([piper_protocol::control::MitControlCommand; 6], [f64; 6])> {
-    let mut commands =
-        [piper_protocol::control::MitControlCommand::try_new(1, 0.0, 0.0, 0.0, 0.0, 0.0)?; 6];
-    let mut t_refs = [0.0; 6];
-
-    for (index, joint) in Joint::ALL.into_iter().enumerate() {
-        let joint_index = joint.index() as u8 + 1;
-        let (position, flipped_torque) =
-            piper.quirks.apply_flip(joint, positions[joint].0, torques[joint].0);
-        let torque = piper.quirks.scale_torque(joint, flipped_torque);
-        let torque_ref = torque as f32;
-
-        commands[index] = piper_protocol::control::MitControlCommand::try_new(
-            joint_index,
-            position as f32,
-            velocities[joint] as f32,
-            kp[joint] as f32,
-            kd[joint] as f32,
-            torque_ref,
-        )?;
-        t_refs[index] = f64::from(torque_ref);
-    }
-
-    Ok((commands, t_refs))
-}
-
 trait RawClockDynamicsCompensator {
     fn compute(
         &mut self,
@@ -3671,6 +3637,9 @@ fn force_soft_error_state(
         observer: unsafe { std::ptr::read(&piper.observer) },
         // SAFETY: each field is moved exactly once into the replacement state wrapper.
         quirks: unsafe { std::ptr::read(&piper.quirks) },
+        // SAFETY: each field is moved exactly once into the replacement state wrapper.
+        safety_limits: unsafe { std::ptr::read(&piper.safety_limits) },
+        enable_timeout: piper.enable_timeout,
         drop_policy: DropPolicy::Noop,
         driver_mode_drop_policy: DriverModeDropPolicy::Preserve,
         _state: ErrorState,
@@ -3689,6 +3658,9 @@ fn force_soft_standby_state(
         observer: unsafe { std::ptr::read(&piper.observer) },
         // SAFETY: each field is moved exactly once into the replacement state wrapper.
         quirks: unsafe { std::ptr::read(&piper.quirks) },
+        // SAFETY: each field is moved exactly once into the replacement state wrapper.
+        safety_limits: unsafe { std::ptr::read(&piper.safety_limits) },
+        enable_timeout: piper.enable_timeout,
         drop_policy: DropPolicy::Noop,
         driver_mode_drop_policy: DriverModeDropPolicy::Preserve,
         _state: Standby,
@@ -3737,7 +3709,7 @@ mod tests {
         BilateralLoopTelemetry, BilateralTelemetrySinkError, JointMirrorMap,
     };
     use crate::observer::{ControlSnapshot, Observer};
-    use crate::types::{DeviceQuirks, JointArray, NewtonMeter, Rad, RadPerSecond};
+    use crate::types::{DeviceQuirks, Joint, JointArray, NewtonMeter, Rad, RadPerSecond};
     use piper_can::{
         CanError, PiperFrame, RealtimeTxAdapter, ReceivedFrame, RxAdapter, TimestampProvenance,
     };
@@ -4518,6 +4490,8 @@ mod tests {
             driver,
             observer,
             quirks: DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+            safety_limits: None,
+            enable_timeout: None,
             drop_policy: DropPolicy::Noop,
             driver_mode_drop_policy: DriverModeDropPolicy::Preserve,
             _state: Standby,
@@ -4619,6 +4593,53 @@ mod tests {
         assert_eq!(snapshot.right.state.position.as_array().len(), 6);
     }
 
+    #[test]
+    fn raw_clock_mit_batch_applies_safety_limits() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut piper = build_active_raw_clock_piper("master", events.clone());
+        events.lock().expect("tx events lock").clear();
+        let positions = JointArray::splat(Rad(0.0));
+        let velocities = JointArray::splat(0.0);
+        let kp = JointArray::splat(0.0);
+        let kd = JointArray::splat(0.0);
+        let mut torques = JointArray::splat(NewtonMeter(0.0));
+        torques[Joint::J2] = NewtonMeter(5.0);
+
+        piper.safety_limits = Some(Arc::new(piper_tools::SafetyLimits {
+            max_torque_nm: vec![2.0; 6],
+            mode: piper_tools::SafetyLimitMode::Reject,
+            ..piper_tools::SafetyLimits::default()
+        }));
+        let error = piper
+            .command_torques_confirmed_finished(
+                &positions,
+                &velocities,
+                &kp,
+                &kd,
+                &torques,
+                Duration::from_millis(100),
+            )
+            .expect_err("reject mode must refuse the whole raw-clock batch");
+        assert!(matches!(error, RobotError::TorqueLimitExceeded { .. }));
+        assert!(events.lock().expect("tx events lock").is_empty());
+
+        piper.safety_limits = Some(Arc::new(piper_tools::SafetyLimits {
+            max_torque_nm: vec![2.0; 6],
+            ..piper_tools::SafetyLimits::default()
+        }));
+        let confirmed = piper
+            .command_torques_confirmed_finished(
+                &positions,
+                &velocities,
+                &kp,
+                &kd,
+                &torques,
+                Duration::from_millis(100),
+            )
+            .expect("clamp mode should send the clamped batch");
+        assert_eq!(confirmed.mit_t_ref_nm[Joint::J2.index()], 2.0);
+    }
+
     #[test]
     fn raw_clock_standby_snapshot_reads_dual_arm_snapshot() {
         let events = Arc::new(Mutex::new(Vec::new()));
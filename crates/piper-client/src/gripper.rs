@@ -0,0 +1,125 @@
+//! 夹爪控制（Commander）
+//!
+//! 以物理单位（mm / N·m）控制夹爪，自动钳位到夹爪行程与扭矩范围，
+//! 无需手动拼装 0x159（`ID_GRIPPER_CONTROL`）帧。
+//!
+//! # 示例
+//!
+//! ```rust,ignore
+//! # use piper_client::state::*;
+//! # use piper_client::types::*;
+//! # fn example(robot: Piper<Active<PositionMode>>) -> Result<()> {
+//! let gripper = robot.gripper();
+//! gripper.enable()?;
+//! gripper.set_position(Millimeter(40.0), NewtonMeter(1.0))?;
+//! gripper.close()?;
+//!
+//! if let Some(feedback) = robot.observer().gripper() {
+//!     println!("travel: {:.1} mm", feedback.travel());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::raw_commander::RawCommander;
+use crate::types::{Millimeter, NewtonMeter, Result, RobotError};
+use piper_driver::Piper as RobotPiper;
+use piper_protocol::constants::{GRIPPER_TORQUE_MAX_NM, GRIPPER_TRAVEL_MAX_MM};
+
+/// 夹爪控制器
+///
+/// 通过 `Piper<Active<_>>::gripper()` 获取，借用期间与机械臂共享同一个 driver。
+/// 所有命令经可靠队列发送；行程钳位到 `0..=GRIPPER_TRAVEL_MAX_MM`，
+/// 扭矩钳位到 `0..=GRIPPER_TORQUE_MAX_NM`，NaN 会被拒绝。
+pub struct GripperCommander<'a> {
+    driver: &'a RobotPiper,
+    effort: NewtonMeter,
+}
+
+impl<'a> GripperCommander<'a> {
+    /// 夹爪全行程（完全张开）
+    pub const MAX_TRAVEL: Millimeter = Millimeter(GRIPPER_TRAVEL_MAX_MM);
+
+    /// 夹爪最大扭矩
+    pub const MAX_EFFORT: NewtonMeter = NewtonMeter(GRIPPER_TORQUE_MAX_NM);
+
+    /// `open()` / `close()` 默认使用的扭矩（与 `open_gripper()` 的 0.3 归一化力度一致）
+    pub const DEFAULT_EFFORT: NewtonMeter = NewtonMeter(0.3 * GRIPPER_TORQUE_MAX_NM);
+
+    pub(crate) fn new(driver: &'a RobotPiper) -> Self {
+        Self {
+            driver,
+            effort: Self::DEFAULT_EFFORT,
+        }
+    }
+
+    /// 设置 `open()` / `close()` 使用的扭矩
+    pub fn with_effort(mut self, effort: NewtonMeter) -> Self {
+        self.effort = effort;
+        self
+    }
+
+    /// 使能夹爪并移动到指定行程
+    ///
+    /// # 参数
+    ///
+    /// - `travel`: 目标开口（0 = 完全闭合，超出行程会被钳位）
+    /// - `effort`: 夹持扭矩（超出范围会被钳位）
+    ///
+    /// # 错误
+    ///
+    /// - `RobotError::ConfigError`: 行程或扭矩为 NaN
+    pub fn set_position(&self, travel: Millimeter, effort: NewtonMeter) -> Result<()> {
+        let (travel, effort) = clamp_command(travel, effort)?;
+        RawCommander::new(self.driver).send_gripper_control(travel.0, effort.0, true)
+    }
+
+    /// 完全张开
+    pub fn open(&self) -> Result<()> {
+        self.set_position(Self::MAX_TRAVEL, self.effort)
+    }
+
+    /// 完全闭合
+    pub fn close(&self) -> Result<()> {
+        self.set_position(Millimeter::ZERO, self.effort)
+    }
+
+    /// 使能夹爪并保持当前行程
+    pub fn enable(&self) -> Result<()> {
+        let travel = self.current_travel();
+        RawCommander::new(self.driver).send_gripper_control(travel.0, self.effort.0, true)
+    }
+
+    /// 失能夹爪（扭矩归零）
+    pub fn disable(&self) -> Result<()> {
+        let travel = self.current_travel();
+        RawCommander::new(self.driver).send_gripper_control(travel.0, 0.0, false)
+    }
+
+    fn current_travel(&self) -> Millimeter {
+        let travel = Millimeter(self.driver.get_gripper().travel);
+        if travel.0.is_finite() {
+            travel.clamp(Millimeter::ZERO, Self::MAX_TRAVEL)
+        } else {
+            Millimeter::ZERO
+        }
+    }
+}
+
+fn clamp_command(travel: Millimeter, effort: NewtonMeter) -> Result<(Millimeter, NewtonMeter)> {
+    if travel.0.is_nan() {
+        return Err(RobotError::ConfigError(
+            "Gripper travel must not be NaN".to_string(),
+        ));
+    }
+    if effort.0.is_nan() {
+        return Err(RobotError::ConfigError(
+            "Gripper effort must not be NaN".to_string(),
+        ));
+    }
+
+    Ok((
+        travel.clamp(Millimeter::ZERO, GripperCommander::MAX_TRAVEL),
+        effort.clamp(NewtonMeter::ZERO, GripperCommander::MAX_EFFORT),
+    ))
+}
@@ -0,0 +1,731 @@
+//! Kinematics - Piper 正/逆运动学
+//!
+//! 基于 Piper 的改进 DH 参数（Craig 约定）：
+//!
+//! - **正运动学**: 关节角 → 末端法兰位姿
+//! - **逆运动学**: 阻尼最小二乘（Levenberg–Marquardt）数值迭代，末端位姿 → 关节角
+//! - **重力补偿**: 连杆质量模型 + 关节角 → 抵消重力所需的关节力矩（[`GravityModel`]）
+//!
+//! 位姿单位与 [`CartesianPose`] 一致（位置为米）；姿态欧拉角与末端位姿反馈
+//! （0x2A2-0x2A4）一致，采用 Roll-Pitch-Yaw（`R = Rz·Ry·Rx`）约定。
+//!
+//! # 示例
+//!
+//! ```rust
+//! use piper_client::kinematics::PiperKinematics;
+//! use piper_client::types::Rad;
+//!
+//! let kinematics = PiperKinematics::default();
+//! let joints = [Rad(0.2), Rad(1.0), Rad(-1.0), Rad(0.0), Rad(0.3), Rad(0.0)];
+//!
+//! let pose = kinematics.forward(&joints);
+//! let solved = kinematics.inverse(&pose, &joints).expect("pose is reachable");
+//! # let _ = solved;
+//! ```
+
+use crate::types::{CartesianPose, JointArray, NewtonMeter, Position3D, Quaternion, Rad};
+use thiserror::Error;
+
+/// 运动学求解错误
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
+pub enum KinematicsError {
+    /// 目标位置超出工作空间（腕部中心距肩部过远）
+    #[error("Target out of reach: wrist distance {distance_m:.4}m exceeds {max_reach_m:.4}m")]
+    OutOfReach {
+        /// 腕部中心到肩部的距离（米）
+        distance_m: f64,
+        /// 最大可达距离（米）
+        max_reach_m: f64,
+    },
+
+    /// 迭代未收敛（通常是姿态不可达或受关节限位约束）
+    #[error(
+        "No IK solution after {iterations} iterations: position error {position_error_m:.5}m, orientation error {orientation_error_rad:.5}rad"
+    )]
+    NoSolution {
+        /// 已执行的迭代次数
+        iterations: usize,
+        /// 最终位置误差（米）
+        position_error_m: f64,
+        /// 最终姿态误差（弧度）
+        orientation_error_rad: f64,
+    },
+}
+
+/// 单个连杆的改进 DH 参数
+///
+/// 连杆变换：`T = RotX(alpha) · TransX(a) · RotZ(theta + theta_offset) · TransZ(d)`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DhLink {
+    /// 连杆长度 a（米）
+    pub a: f64,
+    /// 连杆扭角 alpha（弧度）
+    pub alpha: f64,
+    /// 连杆偏距 d（米）
+    pub d: f64,
+    /// 关节零位偏置（弧度）
+    pub theta_offset: f64,
+}
+
+impl DhLink {
+    const fn new(a: f64, alpha: f64, d: f64, theta_offset: f64) -> Self {
+        Self {
+            a,
+            alpha,
+            d,
+            theta_offset,
+        }
+    }
+}
+
+/// Piper 机械臂的改进 DH 参数（末端为法兰中心，不含夹爪）
+pub const PIPER_DH: [DhLink; 6] = {
+    use std::f64::consts::{FRAC_PI_2, PI};
+    [
+        DhLink::new(0.0, 0.0, 0.123, 0.0),
+        DhLink::new(0.0, -FRAC_PI_2, 0.0, -PI * 172.22 / 180.0),
+        DhLink::new(0.28503, 0.0, 0.0, -PI * 102.78 / 180.0),
+        DhLink::new(-0.02198, FRAC_PI_2, 0.25075, 0.0),
+        DhLink::new(0.0, -FRAC_PI_2, 0.0, 0.0),
+        DhLink::new(0.0, FRAC_PI_2, 0.091, 0.0),
+    ]
+};
+
+/// Piper 关节限位（弧度，`(min, max)`）
+pub const PIPER_JOINT_LIMITS: [(f64, f64); 6] = [
+    (-2.618, 2.618),
+    (0.0, std::f64::consts::PI),
+    (-2.967, 0.0),
+    (-1.745, 1.745),
+    (-1.22, 1.22),
+    (-2.0944, 2.0944),
+];
+
+/// 单个连杆的质量参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinkMass {
+    /// 质量（kg）
+    pub mass_kg: f64,
+    /// 质心位置（米），在该连杆所属关节的 DH 坐标系中表示
+    pub com_m: [f64; 3],
+}
+
+impl LinkMass {
+    /// 创建连杆质量参数
+    pub const fn new(mass_kg: f64, com_m: [f64; 3]) -> Self {
+        Self { mass_kg, com_m }
+    }
+}
+
+/// Piper 连杆质量（J1 → J6 所驱动的连杆，不含夹爪）
+///
+/// 质量取自官方 URDF；质心取相邻关节原点连线的中点，是粗略估计。
+/// 安装夹爪或负载时应修改 `links[5]`。
+pub const PIPER_LINK_MASSES: [LinkMass; 6] = [
+    LinkMass::new(0.71, [0.0, 0.0, 0.0]),
+    LinkMass::new(1.17, [0.1425, 0.0, 0.0]),
+    LinkMass::new(0.50, [-0.011, -0.1254, 0.0]),
+    LinkMass::new(0.38, [0.0, 0.0, 0.0]),
+    LinkMass::new(0.383, [0.0, -0.0455, 0.0]),
+    LinkMass::new(0.007, [0.0, 0.0, 0.0]),
+];
+
+/// 标准重力加速度（m/s²）
+pub const STANDARD_GRAVITY: f64 = 9.80665;
+
+/// 刚体重力模型
+///
+/// 按 `τᵢ = Σⱼ≥ᵢ mⱼ · (−g) · (zᵢ × (p_cⱼ − oᵢ))` 计算抵消重力所需的关节力矩，
+/// 即静止保持当前姿态的前馈力矩。
+#[derive(Debug, Clone, PartialEq)]
+pub struct GravityModel {
+    /// 改进 DH 参数
+    pub dh: [DhLink; 6],
+    /// 各连杆质量参数
+    pub links: [LinkMass; 6],
+    /// 基座坐标系下的重力加速度向量（m/s²），侧装时需相应旋转
+    pub gravity: [f64; 3],
+}
+
+impl Default for GravityModel {
+    fn default() -> Self {
+        Self {
+            dh: PIPER_DH,
+            links: PIPER_LINK_MASSES,
+            gravity: [0.0, 0.0, -STANDARD_GRAVITY],
+        }
+    }
+}
+
+impl GravityModel {
+    /// 计算保持 `joints` 姿态所需的重力补偿力矩
+    pub fn torques(&self, joints: &JointArray<Rad>) -> JointArray<NewtonMeter> {
+        let frames = dh_frames(&self.dh, joints.as_array());
+        let lift = [-self.gravity[0], -self.gravity[1], -self.gravity[2]];
+
+        // 各连杆质心（基座坐标系）
+        let mut centers = [[0.0; 3]; 6];
+        for ((center, frame), link) in centers.iter_mut().zip(&frames).zip(&self.links) {
+            *center = add(&frame.translation, &mat_vec(&frame.rotation, &link.com_m));
+        }
+
+        let mut torques = [NewtonMeter(0.0); 6];
+        for (joint, torque) in torques.iter_mut().enumerate() {
+            let axis = frames[joint].z_axis();
+            let origin = frames[joint].translation;
+            let value: f64 = (joint..6)
+                .map(|link| {
+                    let moment = cross(&axis, &sub(&centers[link], &origin));
+                    self.links[link].mass_kg * dot(&moment, &lift)
+                })
+                .sum();
+            *torque = NewtonMeter(value);
+        }
+        JointArray::new(torques)
+    }
+}
+
+type Mat3 = [[f64; 3]; 3];
+type Vec3 = [f64; 3];
+
+/// 刚体变换（旋转 + 平移）
+#[derive(Debug, Clone, Copy)]
+struct Transform {
+    rotation: Mat3,
+    translation: Vec3,
+}
+
+impl Transform {
+    const IDENTITY: Self = Self {
+        rotation: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        translation: [0.0; 3],
+    };
+
+    fn from_dh(link: &DhLink, q: f64) -> Self {
+        let (sa, ca) = link.alpha.sin_cos();
+        let (st, ct) = (q + link.theta_offset).sin_cos();
+        Self {
+            rotation: [
+                [ct, -st, 0.0],
+                [st * ca, ct * ca, -sa],
+                [st * sa, ct * sa, ca],
+            ],
+            translation: [link.a, -sa * link.d, ca * link.d],
+        }
+    }
+
+    fn then(&self, other: &Transform) -> Transform {
+        let mut rotation = [[0.0; 3]; 3];
+        for (i, row) in rotation.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..3).map(|k| self.rotation[i][k] * other.rotation[k][j]).sum();
+            }
+        }
+        let rotated = mat_vec(&self.rotation, &other.translation);
+        Transform {
+            rotation,
+            translation: add(&self.translation, &rotated),
+        }
+    }
+
+    fn z_axis(&self) -> Vec3 {
+        [
+            self.rotation[0][2],
+            self.rotation[1][2],
+            self.rotation[2][2],
+        ]
+    }
+}
+
+fn mat_vec(m: &Mat3, v: &Vec3) -> Vec3 {
+    [
+        m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+        m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+        m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+    ]
+}
+
+fn add(a: &Vec3, b: &Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: &Vec3, b: &Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: &Vec3, b: &Vec3) -> Vec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn dot(a: &Vec3, b: &Vec3) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn norm(v: &Vec3) -> f64 {
+    (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
+}
+
+fn quaternion_from_matrix(m: &Mat3) -> Quaternion {
+    let trace = m[0][0] + m[1][1] + m[2][2];
+    let q = if trace > 0.0 {
+        let s = (trace + 1.0).sqrt() * 2.0;
+        Quaternion {
+            w: 0.25 * s,
+            x: (m[2][1] - m[1][2]) / s,
+            y: (m[0][2] - m[2][0]) / s,
+            z: (m[1][0] - m[0][1]) / s,
+        }
+    } else if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+        let s = (1.0 + m[0][0] - m[1][1] - m[2][2]).sqrt() * 2.0;
+        Quaternion {
+            w: (m[2][1] - m[1][2]) / s,
+            x: 0.25 * s,
+            y: (m[0][1] + m[1][0]) / s,
+            z: (m[0][2] + m[2][0]) / s,
+        }
+    } else if m[1][1] > m[2][2] {
+        let s = (1.0 + m[1][1] - m[0][0] - m[2][2]).sqrt() * 2.0;
+        Quaternion {
+            w: (m[0][2] - m[2][0]) / s,
+            x: (m[0][1] + m[1][0]) / s,
+            y: 0.25 * s,
+            z: (m[1][2] + m[2][1]) / s,
+        }
+    } else {
+        let s = (1.0 + m[2][2] - m[0][0] - m[1][1]).sqrt() * 2.0;
+        Quaternion {
+            w: (m[1][0] - m[0][1]) / s,
+            x: (m[0][2] + m[2][0]) / s,
+            y: (m[1][2] + m[2][1]) / s,
+            z: 0.25 * s,
+        }
+    };
+    q.normalize()
+}
+
+fn matrix_from_quaternion(q: &Quaternion) -> Mat3 {
+    let q = q.normalize();
+    let (w, x, y, z) = (q.w, q.x, q.y, q.z);
+    [
+        [
+            1.0 - 2.0 * (y * y + z * z),
+            2.0 * (x * y - w * z),
+            2.0 * (x * z + w * y),
+        ],
+        [
+            2.0 * (x * y + w * z),
+            1.0 - 2.0 * (x * x + z * z),
+            2.0 * (y * z - w * x),
+        ],
+        [
+            2.0 * (x * z - w * y),
+            2.0 * (y * z + w * x),
+            1.0 - 2.0 * (x * x + y * y),
+        ],
+    ]
+}
+
+/// 姿态误差（旋转向量，`target · current⁻¹`）
+fn orientation_error(target: &Quaternion, current: &Quaternion) -> Vec3 {
+    let q = target.normalize().multiply(&current.normalize().conjugate());
+    // 取最短路径
+    let q = if q.w < 0.0 {
+        Quaternion {
+            w: -q.w,
+            x: -q.x,
+            y: -q.y,
+            z: -q.z,
+        }
+    } else {
+        q
+    };
+    let sin_half = (q.x * q.x + q.y * q.y + q.z * q.z).sqrt();
+    if sin_half < 1e-12 {
+        return [2.0 * q.x, 2.0 * q.y, 2.0 * q.z];
+    }
+    let angle = 2.0 * sin_half.atan2(q.w);
+    let scale = angle / sin_half;
+    [q.x * scale, q.y * scale, q.z * scale]
+}
+
+/// 各关节坐标系相对基座的位姿（第 i 个元素为关节 i+1 坐标系）
+fn dh_frames(links: &[DhLink; 6], joints: &[Rad; 6]) -> [Transform; 6] {
+    let mut frames = [Transform::IDENTITY; 6];
+    let mut current = Transform::IDENTITY;
+    for (index, (link, joint)) in links.iter().zip(joints).enumerate() {
+        current = current.then(&Transform::from_dh(link, joint.0));
+        frames[index] = current;
+    }
+    frames
+}
+
+/// 求解 6x6 线性方程组（部分主元高斯消元）
+fn solve6(mut a: [[f64; 6]; 6], mut b: [f64; 6]) -> Option<[f64; 6]> {
+    for col in 0..6 {
+        let pivot = (col..6).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let pivot_row = a[col];
+        for row in (col + 1)..6 {
+            let factor = a[row][col] / pivot_row[col];
+            for (value, pivot_value) in a[row].iter_mut().zip(pivot_row).skip(col) {
+                *value -= factor * pivot_value;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+
+    let mut x = [0.0; 6];
+    for row in (0..6).rev() {
+        let tail: f64 = ((row + 1)..6).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+    Some(x)
+}
+
+/// Piper 运动学求解器
+///
+/// 默认使用 [`PIPER_DH`] 与 [`PIPER_JOINT_LIMITS`]，可通过公开字段调整收敛参数。
+#[derive(Debug, Clone)]
+pub struct PiperKinematics {
+    /// 改进 DH 参数
+    pub links: [DhLink; 6],
+    /// 关节限位（弧度，`(min, max)`）
+    pub joint_limits: [(f64, f64); 6],
+    /// IK 最大迭代次数
+    pub max_iterations: usize,
+    /// IK 位置收敛阈值（米）
+    pub position_tolerance_m: f64,
+    /// IK 姿态收敛阈值（弧度）
+    pub orientation_tolerance_rad: f64,
+    /// 阻尼最小二乘的阻尼系数 λ
+    pub damping: f64,
+}
+
+impl Default for PiperKinematics {
+    fn default() -> Self {
+        Self {
+            links: PIPER_DH,
+            joint_limits: PIPER_JOINT_LIMITS,
+            max_iterations: 200,
+            position_tolerance_m: 1e-4,
+            orientation_tolerance_rad: 1e-3,
+            damping: 0.05,
+        }
+    }
+}
+
+impl PiperKinematics {
+    /// 正运动学：关节角 → 末端法兰位姿
+    pub fn forward(&self, joints: &[Rad; 6]) -> CartesianPose {
+        let frames = self.frames(joints);
+        let flange = &frames[5];
+        CartesianPose::from_position_quaternion(
+            Position3D::new(
+                flange.translation[0],
+                flange.translation[1],
+                flange.translation[2],
+            ),
+            quaternion_from_matrix(&flange.rotation),
+        )
+    }
+
+    /// 逆运动学：末端法兰位姿 → 关节角
+    ///
+    /// 以 `seed`（通常为当前关节角）为初值迭代，因此多解时返回离 `seed` 最近的一支；
+    /// 若从 `seed` 出发未收敛，会再从零位重试一次。结果满足 `joint_limits`。
+    ///
+    /// # 错误
+    ///
+    /// - 目标腕部中心超出臂展：[`KinematicsError::OutOfReach`]
+    /// - 迭代未收敛：[`KinematicsError::NoSolution`]
+    pub fn inverse(
+        &self,
+        target: &CartesianPose,
+        seed: &[Rad; 6],
+    ) -> Result<[Rad; 6], KinematicsError> {
+        self.check_reach(target)?;
+
+        let target_position = [target.position.x, target.position.y, target.position.z];
+        let from_seed = self.solve_from(&target_position, &target.orientation, seed);
+        let Err(seed_error) = from_seed else {
+            return from_seed;
+        };
+
+        self.solve_from(&target_position, &target.orientation, &[Rad(0.0); 6])
+            .map_err(|_| seed_error)
+    }
+
+    /// 臂展检查：腕部中心（法兰沿工具 Z 轴回退 d6）到肩部的距离
+    fn check_reach(&self, target: &CartesianPose) -> Result<(), KinematicsError> {
+        let rotation = matrix_from_quaternion(&target.orientation);
+        let tool_z = [rotation[0][2], rotation[1][2], rotation[2][2]];
+        let d6 = self.links[5].d;
+        let wrist = [
+            target.position.x - tool_z[0] * d6,
+            target.position.y - tool_z[1] * d6,
+            target.position.z - tool_z[2] * d6,
+        ];
+        let shoulder = [0.0, 0.0, self.links[0].d];
+        let distance_m = norm(&sub(&wrist, &shoulder));
+        let upper_arm = self.links[2].a.abs();
+        let forearm = self.links[3].a.hypot(self.links[3].d);
+        let max_reach_m = upper_arm + forearm;
+
+        if distance_m > max_reach_m {
+            return Err(KinematicsError::OutOfReach {
+                distance_m,
+                max_reach_m,
+            });
+        }
+        Ok(())
+    }
+
+    fn solve_from(
+        &self,
+        target_position: &Vec3,
+        target_orientation: &Quaternion,
+        seed: &[Rad; 6],
+    ) -> Result<[Rad; 6], KinematicsError> {
+        /// 单次迭代的最大关节步长（弧度），避免线性化失效
+        const MAX_STEP_RAD: f64 = 0.2;
+
+        let mut q = seed.map(|joint| joint.0);
+        self.clamp_to_limits(&mut q);
+
+        let mut position_error_m = f64::INFINITY;
+        let mut orientation_error_rad = f64::INFINITY;
+        for iteration in 0..=self.max_iterations {
+            let frames = self.frames(&q.map(Rad));
+            let flange = &frames[5];
+            let current_orientation = quaternion_from_matrix(&flange.rotation);
+            let e_pos = sub(target_position, &flange.translation);
+            let e_rot = orientation_error(target_orientation, &current_orientation);
+            position_error_m = norm(&e_pos);
+            orientation_error_rad = norm(&e_rot);
+
+            if position_error_m <= self.position_tolerance_m
+                && orientation_error_rad <= self.orientation_tolerance_rad
+            {
+                return Ok(q.map(Rad));
+            }
+            if iteration == self.max_iterations {
+                break;
+            }
+
+            let jacobian = Self::jacobian(&frames);
+            let error = [e_pos[0], e_pos[1], e_pos[2], e_rot[0], e_rot[1], e_rot[2]];
+
+            // dq = Jᵀ (J Jᵀ + λ² I)⁻¹ e
+            let mut jjt = [[0.0; 6]; 6];
+            for (i, row) in jjt.iter_mut().enumerate() {
+                for (j, value) in row.iter_mut().enumerate() {
+                    *value = (0..6).map(|k| jacobian[i][k] * jacobian[j][k]).sum();
+                }
+                row[i] += self.damping * self.damping;
+            }
+            let Some(y) = solve6(jjt, error) else {
+                break;
+            };
+            let mut dq = [0.0; 6];
+            for (k, step) in dq.iter_mut().enumerate() {
+                *step = (0..6).map(|i| jacobian[i][k] * y[i]).sum();
+            }
+
+            let max_step = dq.iter().fold(0.0_f64, |acc, step| acc.max(step.abs()));
+            let scale = if max_step > MAX_STEP_RAD {
+                MAX_STEP_RAD / max_step
+            } else {
+                1.0
+            };
+            for (joint, step) in q.iter_mut().zip(dq) {
+                *joint += step * scale;
+            }
+            self.clamp_to_limits(&mut q);
+        }
+
+        Err(KinematicsError::NoSolution {
+            iterations: self.max_iterations,
+            position_error_m,
+            orientation_error_rad,
+        })
+    }
+
+    fn clamp_to_limits(&self, q: &mut [f64; 6]) {
+        for (joint, (min, max)) in q.iter_mut().zip(self.joint_limits) {
+            *joint = joint.clamp(min, max);
+        }
+    }
+
+    /// 各关节坐标系相对基座的位姿（第 i 个元素为关节 i+1 坐标系）
+    fn frames(&self, joints: &[Rad; 6]) -> [Transform; 6] {
+        dh_frames(&self.links, joints)
+    }
+
+    /// 几何雅可比（行：vx, vy, vz, wx, wy, wz；列：关节）
+    fn jacobian(frames: &[Transform; 6]) -> [[f64; 6]; 6] {
+        let end = frames[5].translation;
+        let mut jacobian = [[0.0; 6]; 6];
+        for (col, frame) in frames.iter().enumerate() {
+            let axis = frame.z_axis();
+            let linear = cross(&axis, &sub(&end, &frame.translation));
+            for row in 0..3 {
+                jacobian[row][col] = linear[row];
+                jacobian[row + 3][col] = axis[row];
+            }
+        }
+        jacobian
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_pose_close(a: &CartesianPose, b: &CartesianPose) {
+        let position_error = norm(&[
+            a.position.x - b.position.x,
+            a.position.y - b.position.y,
+            a.position.z - b.position.z,
+        ]);
+        assert!(position_error < 2e-4, "position error: {position_error}");
+        let orientation = norm(&orientation_error(&a.orientation, &b.orientation));
+        assert!(orientation < 2e-3, "orientation error: {orientation}");
+    }
+
+    #[test]
+    fn test_forward_kinematics_home_pose_matches_feedback() {
+        let pose = PiperKinematics::default().forward(&[Rad(0.0); 6]);
+
+        // 零位时控制器报告的末端位姿约为 (56.1mm, 0, 213.3mm)，RY ≈ 85°
+        assert!(
+            (pose.position.x - 0.0561).abs() < 1e-3,
+            "{:?}",
+            pose.position
+        );
+        assert!(pose.position.y.abs() < 1e-6);
+        assert!(
+            (pose.position.z - 0.2133).abs() < 1e-3,
+            "{:?}",
+            pose.position
+        );
+
+        let (roll, pitch, yaw) = pose.orientation.to_euler();
+        assert!(roll.0.abs() < 1e-6);
+        assert!(
+            (pitch.0.to_degrees() - 85.0).abs() < 0.1,
+            "pitch: {}",
+            pitch.0.to_degrees()
+        );
+        assert!(yaw.0.abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_inverse_kinematics_round_trips_forward() {
+        let kinematics = PiperKinematics::default();
+        let samples = [
+            [Rad(0.2), Rad(1.0), Rad(-1.0), Rad(0.3), Rad(0.4), Rad(-0.5)],
+            [
+                Rad(-1.0),
+                Rad(1.5),
+                Rad(-0.8),
+                Rad(-0.6),
+                Rad(-0.7),
+                Rad(1.2),
+            ],
+            [Rad(0.8), Rad(0.5), Rad(-1.8), Rad(1.0), Rad(0.2), Rad(0.0)],
+        ];
+
+        for joints in samples {
+            let target = kinematics.forward(&joints);
+            let seed = joints.map(|joint| Rad(joint.0 + 0.15));
+            let solved = kinematics.inverse(&target, &seed).expect("sample pose is reachable");
+            assert_pose_close(&kinematics.forward(&solved), &target);
+        }
+    }
+
+    #[test]
+    fn test_inverse_kinematics_reports_out_of_reach() {
+        let target =
+            CartesianPose::from_position_euler(1.0, 0.0, 0.2, Rad(0.0), Rad(0.0), Rad(0.0));
+
+        let error = PiperKinematics::default()
+            .inverse(&target, &[Rad(0.0); 6])
+            .expect_err("1m is beyond the Piper's reach");
+
+        assert!(
+            matches!(error, KinematicsError::OutOfReach { distance_m, max_reach_m } if distance_m > max_reach_m)
+        );
+    }
+
+    #[test]
+    fn test_inverse_kinematics_reports_no_solution_when_limits_block_target() {
+        // 正后方低处：需要 J1 超过 ±150°，在限位内无解
+        let target = CartesianPose::from_position_euler(
+            -0.25,
+            0.0,
+            0.05,
+            Rad(0.0),
+            Rad(std::f64::consts::PI),
+            Rad(0.0),
+        );
+
+        let error = PiperKinematics::default()
+            .inverse(&target, &[Rad(0.0); 6])
+            .expect_err("target behind the base is blocked by joint limits");
+
+        assert!(matches!(error, KinematicsError::NoSolution { .. }));
+    }
+
+    #[test]
+    fn test_gravity_torques_match_potential_energy_gradient() {
+        let model = GravityModel::default();
+        let potential = |joints: &[Rad; 6]| -> f64 {
+            let frames = dh_frames(&model.dh, joints);
+            frames
+                .iter()
+                .zip(&model.links)
+                .map(|(frame, link)| {
+                    let center = add(&frame.translation, &mat_vec(&frame.rotation, &link.com_m));
+                    -link.mass_kg * dot(&model.gravity, &center)
+                })
+                .sum()
+        };
+
+        let joints = [Rad(0.4), Rad(1.1), Rad(-0.9), Rad(0.5), Rad(-0.6), Rad(0.2)];
+        let torques = model.torques(&JointArray::new(joints));
+        let step = 1e-6;
+        for joint in 0..6 {
+            let mut plus = joints;
+            let mut minus = joints;
+            plus[joint].0 += step;
+            minus[joint].0 -= step;
+            let expected = (potential(&plus) - potential(&minus)) / (2.0 * step);
+            assert!(
+                (torques[joint].0 - expected).abs() < 1e-6,
+                "J{}: {} vs {}",
+                joint + 1,
+                torques[joint].0,
+                expected
+            );
+        }
+
+        // 正装时 J1 轴竖直，重力不产生力矩
+        assert!(torques[0].0.abs() < 1e-9);
+        assert!(torques[1].0.abs() > 0.1);
+    }
+
+    #[test]
+    fn test_quaternion_matrix_round_trip() {
+        let q = Quaternion::from_euler(Rad(0.3), Rad(-0.7), Rad(2.0));
+        let back = quaternion_from_matrix(&matrix_from_quaternion(&q));
+        assert!(norm(&orientation_error(&q, &back)) < 1e-9);
+    }
+}
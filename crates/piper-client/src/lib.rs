@@ -27,11 +27,16 @@ pub mod control;
 pub mod diagnostics;
 pub mod dual_arm;
 pub mod dual_arm_raw_clock;
+pub mod gripper;
 pub mod heartbeat;
+pub mod kinematics;
 pub mod observer;
 pub(crate) mod raw_commander;
 pub mod recording;
+pub mod safety;
 pub mod state;
+pub mod teach;
+pub mod trajectory_streamer;
 pub mod types;
 
 // 测试模块
@@ -40,13 +45,14 @@ mod recording_tests;
 
 // 重新导出常用类型
 pub use bridge::{
-    BridgeClientOptions, BridgeDeviceState, BridgeEndpoint, BridgeError, BridgeEvent, BridgeResult,
-    BridgeRole, BridgeStatus, BridgeTlsClientConfig, CanIdFilter, ErrorCode, MaintenanceLease,
+    BridgeClientOptions, BridgeDeviceState, BridgeEndpoint, BridgeError, BridgeEvent,
+    BridgeReconnectPolicy, BridgeResult, BridgeRole, BridgeSessionInfo, BridgeStatus,
+    BridgeTlsClientConfig, CanIdFilter, CanIdFilterMode, ErrorCode, MaintenanceLease,
     PiperBridgeClient, SessionToken,
 };
 pub use bridge_host::{
-    BridgeHostConfig, BridgeHostError, BridgeMaintenanceState, BridgeTlsClientPolicy,
-    BridgeTlsServerConfig, BridgeUdsListenerConfig, PiperBridgeHost,
+    BridgeHostConfig, BridgeHostError, BridgeMaintenanceState, BridgeSendRateLimit,
+    BridgeTlsClientPolicy, BridgeTlsServerConfig, BridgeUdsListenerConfig, PiperBridgeHost,
 };
 pub use builder::PiperBuilder;
 pub use diagnostics::PiperDiagnostics;
@@ -63,17 +69,27 @@ pub use dual_arm_raw_clock::{
     ExperimentalRawClockConfig, ExperimentalRawClockDualArmActive,
     ExperimentalRawClockDualArmStandby, RawClockRuntimeReport,
 };
+pub use gripper::GripperCommander;
 pub use observer::{
     CollisionProtectionSnapshot, ControlReadPolicy, ControlSnapshot, ControlSnapshotFull,
-    GripperState, MonitorReadPolicy, Observer, RuntimeHealthSnapshot,
+    DriverDiagnostics, GripperState, JointState, MonitorReadPolicy, Observer,
+    RuntimeHealthSnapshot,
 };
-pub use piper_driver::RuntimeFaultKind;
+pub use piper_driver::{DropStopAction, LinkHealth, RuntimeFaultKind, WatchdogConfig};
+pub use piper_tools::{SafetyLimitMode, SafetyLimits};
 pub use recording::{
-    RecordingConfig, RecordingHandle, RecordingMetadata, RecordingStats, StopCondition,
+    RecordingCompression, RecordingConfig, RecordingHandle, RecordingMetadata, RecordingMode,
+    RecordingStateExt, RecordingStats, StopCondition,
 };
+pub use safety::{SafetyMonitor, SafetyViolation, SafetyViolationKind};
 pub use state::machine::ConfirmedMitBatch;
 pub use state::{
-    ConnectedPiper, Maintenance, MonitorOnly, MotionConnectedPiper, MotionConnectedState, Piper,
-    SoftRealtime, StrictRealtime,
+    ConnectedPiper, EmergencyStopHandle, Maintenance, MonitorOnly, MotionCommander,
+    MotionConnectedPiper, MotionConnectedState, Piper, SoftRealtime, StrictRealtime,
 }; // Type State Pattern 的状态机与能力分层入口
+pub use teach::{TeachRecording, TeachRecordingConfig, TeachSample, TeachTrajectory};
+pub use trajectory_streamer::{
+    StreamTick, TrajectorySetpoint, TrajectoryStreamConfig, TrajectoryStreamStats,
+    TrajectoryStreamer,
+};
 pub use types::*;
@@ -44,15 +44,17 @@ use std::time::Duration;
 
 use crate::state::{CapabilityMarker, StrictCapability, UnspecifiedCapability};
 use crate::types::*;
-use piper_driver::observation::{Observation, ObservationPayload};
+use piper_driver::observation::{Freshness, Observation, ObservationPayload};
 use piper_driver::{
-    AlignmentResult, BackendCapability, DriverError, HealthStatus, PartialJointDriverLowSpeed,
-    Piper as RobotPiper, RuntimeFaultKind,
+    AlignmentResult, BackendCapability, DriverError, HealthStatus, JointDriverLowSpeedJoint,
+    LinkHealth, PartialJointDriverLowSpeed, Piper as RobotPiper, RuntimeFaultKind,
 };
 use piper_protocol::constants::*;
+use piper_protocol::feedback::DriverStatus;
 
 const COMPLETE_COLD_GROUP_MASK: u8 = 0b111;
 const COMPLETE_DYNAMIC_GROUP_MASK: u8 = 0b11_1111;
+const COMPLETE_LOW_SPEED_GROUP_MASK: u8 = 0b11_1111;
 pub(crate) const DEFAULT_CONTROL_MAX_FEEDBACK_AGE: Duration = Duration::from_millis(15);
 const DEFAULT_MONITOR_MAX_FEEDBACK_AGE: Duration = Duration::from_millis(50);
 
@@ -171,6 +173,41 @@ impl ControlSnapshotFull {
     }
 }
 
+/// 六个关节的位置 / 速度 / 力矩快照
+///
+/// 取自同一份 coherent control pair（0x2A5-0x2A7 位置组 + J1-J6 高速反馈），
+/// 不会把不同批次的位置和动态数据拼在一起。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JointState {
+    /// 关节位置
+    pub position: JointArray<Rad>,
+    /// 关节速度
+    pub velocity: JointArray<RadPerSecond>,
+    /// 关节力矩（由高速反馈电流换算）
+    pub effort: JointArray<NewtonMeter>,
+    /// 快照时间戳（位置反馈硬件时间戳，即对齐基准，微秒）
+    pub timestamp_us: u64,
+    /// 反馈年龄（取位置/动态中的较大值）
+    pub feedback_age: Duration,
+    /// 反馈年龄是否超过读取策略的 `max_feedback_age`
+    pub stale: bool,
+    /// 读取时的状态代数（见 [`Observer::wait_for_update`]）
+    ///
+    /// 在读取数据之前采样：快照至少与该代数一样新。
+    pub generation: u64,
+}
+
+impl JointState {
+    /// 单个关节的 (position, velocity, effort)
+    pub fn joint(&self, joint: Joint) -> (Rad, RadPerSecond, NewtonMeter) {
+        (
+            self.position[joint],
+            self.velocity[joint],
+            self.effort[joint],
+        )
+    }
+}
+
 /// Driver 运行时健康状态快照
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct RuntimeHealthSnapshot {
@@ -436,6 +473,22 @@ where
         }
     }
 
+    /// 获取最新的夹爪反馈（0x2A8，物理单位）
+    ///
+    /// 尚未收到任何夹爪反馈时返回 `None`。
+    pub fn gripper(&self) -> Option<piper_protocol::feedback::GripperFeedback> {
+        let gripper = self.driver.get_gripper();
+        if gripper.host_rx_mono_us == 0 {
+            return None;
+        }
+
+        Some(piper_protocol::feedback::GripperFeedback {
+            travel_mm: (gripper.travel * 1000.0).round() as i32,
+            torque_nm: (gripper.torque * 1000.0).round() as i16,
+            status: piper_protocol::feedback::GripperStatus::from(gripper.status_code),
+        })
+    }
+
     /// 获取夹爪位置 (0.0-1.0)
     pub fn gripper_position(&self) -> f64 {
         self.gripper_state().position
@@ -483,6 +536,59 @@ where
         }
     }
 
+    /// 获取六个关节驱动器的电压、温度和状态位（监控/诊断接口）
+    ///
+    /// 低速反馈约 40Hz 逐关节上报；只有六个关节都在新鲜窗口内时才返回。
+    ///
+    /// # 错误
+    ///
+    /// - `RobotError::MonitorStateIncomplete`：部分关节尚未上报
+    /// - `RobotError::MonitorStateStale`：低速反馈已过期
+    pub fn driver_diagnostics(&self) -> Result<[DriverDiagnostics; 6]> {
+        const SOURCE: MonitorStateSource = MonitorStateSource::JointDriverLowSpeed;
+
+        let available = match self.driver.get_joint_driver_low_speed() {
+            Observation::Available(available) => available,
+            Observation::Unavailable => {
+                return Err(RobotError::monitor_state_incomplete(
+                    SOURCE,
+                    0,
+                    COMPLETE_LOW_SPEED_GROUP_MASK,
+                ));
+            },
+        };
+        let state = match available.payload {
+            ObservationPayload::Complete(state) => state,
+            ObservationPayload::Partial { partial, .. } => {
+                let valid_mask = partial
+                    .joints
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, joint)| joint.is_some())
+                    .fold(0, |mask, (index, _)| mask | (1 << index));
+                return Err(RobotError::monitor_state_incomplete(
+                    SOURCE,
+                    valid_mask,
+                    COMPLETE_LOW_SPEED_GROUP_MASK,
+                ));
+            },
+        };
+        if let Freshness::Stale { stale_for } = available.freshness {
+            let oldest_host_rx_mono_us =
+                state.joints.iter().map(|joint| joint.host_rx_mono_us).min().unwrap_or(0);
+            let age = host_rx_mono_age(oldest_host_rx_mono_us);
+            return Err(RobotError::monitor_state_stale(
+                SOURCE,
+                age,
+                age.saturating_sub(stale_for),
+            ));
+        }
+
+        Ok(std::array::from_fn(|index| {
+            DriverDiagnostics::from_low_speed(Joint::ALL[index], &state.joints[index])
+        }))
+    }
+
     /// 获取已确认的驱动器使能位掩码。
     ///
     /// 只有在完整且新鲜的 6 轴低速反馈可用时才返回 `Some(mask)`。
@@ -590,16 +696,90 @@ where
         self.is_all_enabled()
     }
 
-    /// 获取单个关节的状态
+    /// 获取六个关节的位置 / 速度 / 力矩快照
     ///
-    /// 返回 (position, velocity, torque) 元组。
-    /// **注意**：此方法独立读取，可能与其他状态有时间偏斜。
-    /// 如需控制闭环使用，请改用 `control_snapshot()`。
-    pub fn joint_state(&self, joint: Joint) -> Result<(Rad, RadPerSecond, NewtonMeter)> {
-        let positions = self.joint_positions()?;
-        let velocities = self.joint_velocities()?;
-        let torques = self.joint_torques()?;
-        Ok((positions[joint], velocities[joint], torques[joint]))
+    /// 三组数据来自同一份 coherent control pair；反馈过期时仍返回数据，
+    /// 但 `stale` 为 `true`（阈值见 [`MonitorReadPolicy::default`]）。
+    /// 如需同时强制时间对齐与新鲜度，请改用 `control_snapshot()`。
+    ///
+    /// # 错误
+    ///
+    /// - `RobotError::ControlStateIncomplete`：尚未收到完整的位置组或动态组
+    pub fn joint_state(&self) -> Result<JointState> {
+        self.joint_state_with_policy(MonitorReadPolicy::default())
+    }
+
+    /// 获取关节状态快照，按指定策略判定 `stale`
+    pub fn joint_state_with_policy(&self, policy: MonitorReadPolicy) -> Result<JointState> {
+        let generation = self.driver.state_generation();
+        let state = match self.driver.get_aligned_motion(u64::MAX, Duration::MAX) {
+            AlignmentResult::Incomplete {
+                position_candidate_mask,
+                dynamic_candidate_mask,
+            } => {
+                return Err(Self::incomplete_control_state_error(
+                    position_candidate_mask,
+                    dynamic_candidate_mask,
+                ));
+            },
+            AlignmentResult::Ok(state)
+            | AlignmentResult::Stale { state, .. }
+            | AlignmentResult::Misaligned { state, .. } => state,
+        };
+        let feedback_age = state.feedback_age();
+
+        Ok(JointState {
+            position: JointArray::new(state.joint_pos.map(Rad)),
+            velocity: JointArray::new(state.joint_vel.map(RadPerSecond)),
+            effort: JointArray::new(std::array::from_fn(|index| {
+                NewtonMeter(piper_driver::JointDynamicState::calculate_torque(
+                    index,
+                    state.joint_current[index],
+                ))
+            })),
+            timestamp_us: state.position_timestamp_us,
+            feedback_age,
+            stale: feedback_age > policy.max_feedback_age,
+            generation,
+        })
+    }
+
+    /// 当前状态代数（每次完整/控制级状态提交加一）
+    pub fn state_generation(&self) -> u64 {
+        self.driver.state_generation()
+    }
+
+    /// 阻塞等待新的状态提交，返回新的关节状态快照
+    ///
+    /// `since` 传入上一份快照的 `generation`（首次可传 0）；状态代数已经超过 `since`
+    /// 时立即返回。适合只需在状态变化时刷新的 UI 线程，代替高频轮询。
+    ///
+    /// # 错误
+    ///
+    /// - `RobotError::Timeout`：`timeout` 内没有新的状态提交
+    /// - 其余同 [`Observer::joint_state`]
+    ///
+    /// # 示例
+    ///
+    /// ```rust,no_run
+    /// # use piper_client::observer::Observer;
+    /// # use std::time::Duration;
+    /// # fn example(observer: Observer) -> piper_client::Result<()> {
+    /// let mut generation = 0;
+    /// loop {
+    ///     let state = observer.wait_for_update(generation, Duration::from_millis(100))?;
+    ///     generation = state.generation;
+    ///     // ... 重绘 ...
+    /// }
+    /// # }
+    /// ```
+    pub fn wait_for_update(&self, since: u64, timeout: Duration) -> Result<JointState> {
+        if self.driver.wait_for_state_update(since, timeout).is_none() {
+            return Err(RobotError::timeout(
+                timeout.as_millis().min(u128::from(u64::MAX)) as u64,
+            ));
+        }
+        self.joint_state()
     }
 
     // ============================================================
@@ -647,6 +827,29 @@ where
         self.driver.connection_age()
     }
 
+    /// 获取结构化的连接健康状态（`Healthy` / `Degraded { missed }` / `Lost`）
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// # use piper_client::{LinkHealth, observer::Observer};
+    /// # fn example(observer: Observer) {
+    /// match observer.link_health() {
+    ///     LinkHealth::Healthy => {},
+    ///     LinkHealth::Degraded { missed } => println!("missed {missed} feedback frames"),
+    ///     LinkHealth::Lost => println!("robot connection lost"),
+    /// }
+    /// # }
+    /// ```
+    pub fn link_health(&self) -> LinkHealth {
+        self.driver.link_health()
+    }
+
+    /// 注册连接健康状态变化回调（在 driver RX 线程中执行，必须快速返回）
+    pub fn on_link_health_change(&self, callback: impl Fn(LinkHealth) + Send + Sync + 'static) {
+        self.driver.on_link_health_change(callback);
+    }
+
     /// 获取 driver 运行时健康快照。
     pub fn runtime_health(&self) -> RuntimeHealthSnapshot {
         self.driver.health().into()
@@ -695,6 +898,21 @@ where
         }
     }
 
+    /// 查询固件版本（发送 0x4AF 读取请求并等待应答）
+    ///
+    /// 可用于在运行时按固件版本启用功能（如 `MoveCpv` 需要 V1.8-1+，`MoveM` 需要 V1.5-2+）。
+    ///
+    /// # 错误
+    ///
+    /// - `DriverError::Timeout`：`timeout` 内未收到完整版本应答
+    /// - `DriverError::Protocol`：版本字符串无法解析
+    pub fn firmware_version(
+        &self,
+        timeout: Duration,
+    ) -> std::result::Result<piper_protocol::FirmwareVersion, DriverError> {
+        self.driver.query_firmware_version(timeout)
+    }
+
     fn ensure_realtime_control_supported(&self) -> Result<()>
     where
         Capability: StrictCapability,
@@ -844,6 +1062,74 @@ impl Default for GripperState {
     }
 }
 
+/// 单个关节驱动器的诊断数据（来自 0x261-0x266 低速反馈）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DriverDiagnostics {
+    /// 关节
+    pub joint: Joint,
+    /// 驱动器电压（V）
+    pub voltage: f32,
+    /// 驱动器温度（°C）
+    pub driver_temp_c: f32,
+    /// 电机温度（°C）
+    pub motor_temp_c: f32,
+    /// 母线电流（A）
+    pub bus_current: f32,
+    /// 驱动器状态位
+    pub status: DriverStatus,
+    /// 主机接收时间戳（微秒）
+    pub host_rx_mono_us: u64,
+}
+
+impl DriverDiagnostics {
+    fn from_low_speed(joint: Joint, state: &JointDriverLowSpeedJoint) -> Self {
+        Self {
+            joint,
+            voltage: state.joint_voltage_v,
+            driver_temp_c: state.driver_temp_c,
+            motor_temp_c: state.motor_temp_c,
+            bus_current: state.joint_bus_current_a,
+            status: DriverStatus::new(
+                state.voltage_low,
+                state.motor_over_temp,
+                state.over_current,
+                state.driver_over_temp,
+                state.collision_protection,
+                state.driver_error,
+                state.enabled,
+                state.stall_protection,
+            ),
+            host_rx_mono_us: state.host_rx_mono_us,
+        }
+    }
+
+    /// 驱动器上报电机或驱动器过温
+    pub fn is_overheating(&self) -> bool {
+        self.status.motor_over_temp() || self.status.driver_over_temp()
+    }
+
+    /// 电机或驱动器温度是否达到给定阈值（°C），用于在固件过温保护之前提前降载
+    pub fn exceeds_temperature(&self, limit_c: f32) -> bool {
+        self.motor_temp_c >= limit_c || self.driver_temp_c >= limit_c
+    }
+
+    /// 驱动器上报电压过低
+    pub fn is_voltage_low(&self) -> bool {
+        self.status.voltage_low()
+    }
+
+    /// 是否存在任一故障位（欠压、过温、过流、碰撞/堵转保护、驱动器错误）
+    pub fn has_fault(&self) -> bool {
+        self.status.voltage_low()
+            || self.status.motor_over_temp()
+            || self.status.driver_over_current()
+            || self.status.driver_over_temp()
+            || self.status.collision_protection()
+            || self.status.driver_error()
+            || self.status.stall_protection()
+    }
+}
+
 // 确保 Send + Sync
 unsafe impl<Capability> Send for Observer<Capability> {}
 unsafe impl<Capability> Sync for Observer<Capability> {}
@@ -1264,6 +1550,16 @@ mod tests {
         assert!(gripper.enabled);
     }
 
+    #[test]
+    fn gripper_returns_latest_feedback_in_physical_units() {
+        let observer = observer_with_gripper_feedback(0, 42.5, 1.25);
+        let feedback = observer.gripper().expect("gripper feedback should be available");
+
+        assert_eq!(feedback.travel(), 42.5);
+        assert_eq!(feedback.torque(), 1.25);
+        assert!(feedback.status.enabled());
+    }
+
     #[test]
     fn test_gripper_effort_full_scale_matches_five_nm_feedback() {
         let travel_raw = 50_000i32.to_be_bytes();
@@ -1349,6 +1645,62 @@ mod tests {
         assert!(!observer.is_all_disabled_confirmed());
     }
 
+    #[test]
+    fn test_driver_diagnostics_decodes_full_low_speed_feedback() {
+        let frames = (1..=6)
+            .map(|joint_index| joint_driver_low_speed_frame(joint_index, true, joint_index as u64))
+            .collect();
+        let (driver, observer) = start_observer_with_frames(frames);
+
+        driver
+            .wait_for_feedback(Duration::from_millis(200))
+            .expect("full low-speed feedback should arrive");
+        let deadline = Instant::now() + Duration::from_millis(200);
+        let diagnostics = loop {
+            match observer.driver_diagnostics() {
+                Ok(diagnostics) => break diagnostics,
+                Err(_) if Instant::now() < deadline => thread::sleep(Duration::from_millis(5)),
+                Err(error) => panic!("diagnostics should become available: {error}"),
+            }
+        };
+
+        let j6 = diagnostics[5];
+        assert_eq!(j6.joint, Joint::J6);
+        assert!((j6.voltage - 24.0).abs() < 1e-4);
+        assert_eq!(j6.driver_temp_c, 45.0);
+        assert_eq!(j6.motor_temp_c, 50.0);
+        assert!((j6.bus_current - 5.0).abs() < 1e-4);
+        assert!(j6.status.enabled());
+        assert!(!j6.is_overheating());
+        assert!(!j6.has_fault());
+        assert!(j6.exceeds_temperature(50.0));
+        assert!(!j6.exceeds_temperature(60.0));
+    }
+
+    #[test]
+    fn test_driver_diagnostics_rejects_partial_low_speed_feedback() {
+        let (driver, observer) =
+            start_observer_with_frames(vec![joint_driver_low_speed_frame(2, true, 1_000)]);
+
+        driver
+            .wait_for_feedback(Duration::from_millis(200))
+            .expect("partial low-speed feedback should arrive");
+        let deadline = Instant::now() + Duration::from_millis(200);
+        while Instant::now() < deadline && observer.joint_enabled_mask() != 0b000010 {
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        let error = observer.driver_diagnostics().unwrap_err();
+        assert!(matches!(
+            error,
+            RobotError::MonitorStateIncomplete {
+                state_source: MonitorStateSource::JointDriverLowSpeed,
+                valid_mask: 0b000010,
+                required_mask: 0b11_1111,
+            }
+        ));
+    }
+
     #[test]
     fn test_confirmed_enable_state_expires_without_new_feedback() {
         let frames = (1..=6)
@@ -1592,6 +1944,73 @@ mod tests {
         assert!(matches!(error, RobotError::FeedbackStale { .. }));
     }
 
+    #[test]
+    fn test_joint_state_returns_coherent_pair_and_flags_stale_feedback() {
+        let timestamp_us = 1_000;
+        let frames = vec![
+            joint_feedback_frame(ID_JOINT_FEEDBACK_12.raw().into(), 0, 0, timestamp_us),
+            joint_feedback_frame(ID_JOINT_FEEDBACK_34.raw().into(), 0, 0, timestamp_us),
+            joint_feedback_frame(ID_JOINT_FEEDBACK_56.raw().into(), 0, 0, timestamp_us),
+            joint_dynamic_frame(1, 1000, 1000, timestamp_us),
+            joint_dynamic_frame(2, 1000, 1000, timestamp_us),
+            joint_dynamic_frame(3, 1000, 1000, timestamp_us),
+            joint_dynamic_frame(4, 1000, 1000, timestamp_us),
+            joint_dynamic_frame(5, 1000, 1000, timestamp_us),
+            joint_dynamic_frame(6, 1000, 1000, timestamp_us),
+        ];
+        let (driver, observer) = start_observer_with_frames(frames);
+
+        driver
+            .wait_for_feedback(Duration::from_millis(200))
+            .expect("feedback should arrive");
+        thread::sleep(Duration::from_millis(30));
+
+        let fresh = observer
+            .joint_state_with_policy(MonitorReadPolicy {
+                max_feedback_age: Duration::from_secs(5),
+            })
+            .expect("complete pair should produce a joint state");
+        assert_eq!(fresh.timestamp_us, timestamp_us);
+        assert_eq!(fresh.velocity[Joint::J1], RadPerSecond(1.0));
+        assert_eq!(fresh.joint(Joint::J6).1, RadPerSecond(1.0));
+        assert!(!fresh.stale);
+
+        let stale = observer
+            .joint_state_with_policy(MonitorReadPolicy {
+                max_feedback_age: Duration::from_millis(10),
+            })
+            .expect("stale pair is still returned");
+        assert!(stale.stale);
+        assert_eq!(stale.position, fresh.position);
+    }
+
+    #[test]
+    fn test_wait_for_update_returns_committed_state_then_times_out_without_commits() {
+        let timestamp_us = 1_000;
+        let mut frames = vec![
+            joint_feedback_frame(ID_JOINT_FEEDBACK_12.raw().into(), 0, 0, timestamp_us),
+            joint_feedback_frame(ID_JOINT_FEEDBACK_34.raw().into(), 0, 0, timestamp_us),
+            joint_feedback_frame(ID_JOINT_FEEDBACK_56.raw().into(), 0, 0, timestamp_us),
+        ];
+        frames.extend((1..=6).map(|joint| joint_dynamic_frame(joint, 1000, 1000, timestamp_us)));
+        let (driver, observer) = start_observer_with_frames(frames);
+
+        driver
+            .wait_for_feedback(Duration::from_millis(200))
+            .expect("feedback should arrive");
+        thread::sleep(Duration::from_millis(30));
+
+        let state = observer
+            .wait_for_update(0, Duration::from_secs(2))
+            .expect("already-committed state should return immediately");
+        assert!(state.generation > 0);
+        assert_eq!(state.velocity[Joint::J1], RadPerSecond(1.0));
+
+        let settled = observer.state_generation();
+        let error = observer.wait_for_update(settled, Duration::from_millis(20)).unwrap_err();
+        assert!(matches!(error, RobotError::Timeout { timeout_ms: 20 }));
+    }
+
     #[test]
     fn test_control_snapshot_reports_feedback_stale_when_driver_marks_pair_stale_due_to_age() {
         let timestamp_us = 1_000;
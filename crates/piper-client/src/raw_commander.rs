@@ -46,6 +46,7 @@ impl<'a> RawCommander<'a> {
         &self,
         commands: [MitControlCommand; 6],
     ) -> Result<()> {
+        self.driver.ensure_no_robot_fault()?;
         let frames_array = commands.map(MitControlCommand::to_frame);
         self.driver.send_realtime_package(frames_array)?;
         Ok(())
@@ -57,6 +58,7 @@ impl<'a> RawCommander<'a> {
         commands: [MitControlCommand; 6],
         timeout: Duration,
     ) -> Result<()> {
+        self.driver.ensure_no_robot_fault()?;
         let frames_array = commands.map(MitControlCommand::to_frame);
         match self.driver.backend_capability() {
             piper_driver::BackendCapability::StrictRealtime => {
@@ -80,6 +82,7 @@ impl<'a> RawCommander<'a> {
         commands: [MitControlCommand; 6],
         timeout: Duration,
     ) -> Result<piper_driver::MitBatchTxFinished> {
+        self.driver.ensure_no_robot_fault()?;
         let frames_array = commands.map(MitControlCommand::to_frame);
         match self.driver.backend_capability() {
             piper_driver::BackendCapability::StrictRealtime => self
@@ -125,11 +128,23 @@ impl<'a> RawCommander<'a> {
         positions: &JointArray<Rad>,
         timeout: Duration,
     ) -> Result<()> {
+        self.driver.ensure_no_robot_fault()?;
         let frames = build_joint_position_frames(positions);
         self.driver.send_reliable_package_confirmed(frames, timeout)?;
         Ok(())
     }
 
+    /// 批量发送位置命令，返回发送完成时的 host 单调时间戳（用于判定反馈新鲜度）
+    pub(crate) fn send_position_command_batch_commit_marker(
+        &self,
+        positions: &JointArray<Rad>,
+        timeout: Duration,
+    ) -> Result<u64> {
+        self.driver.ensure_no_robot_fault()?;
+        let frames = build_joint_position_frames(positions);
+        Ok(self.driver.send_reliable_package_confirmed_commit_marker(frames, timeout)?)
+    }
+
     /// 控制夹爪（无锁）
     pub(crate) fn send_gripper_command(&self, position: f64, effort: f64) -> Result<()> {
         self.send_gripper_command_with_enable(position, effort, true)
@@ -143,7 +158,17 @@ impl<'a> RawCommander<'a> {
     ) -> Result<()> {
         let position_mm = position * GRIPPER_POSITION_SCALE;
         let torque_nm = effort * GRIPPER_FORCE_SCALE;
-        let cmd = GripperControlCommand::new(position_mm, torque_nm, enable);
+        self.send_gripper_control(position_mm, torque_nm, enable)
+    }
+
+    /// 以物理量控制夹爪（行程 mm，扭矩 N·m）
+    pub(crate) fn send_gripper_control(
+        &self,
+        travel_mm: f64,
+        torque_nm: f64,
+        enable: bool,
+    ) -> Result<()> {
+        let cmd = GripperControlCommand::new(travel_mm, torque_nm, enable);
         self.driver.send_reliable(cmd.to_frame())?;
         Ok(())
     }
@@ -168,6 +193,18 @@ impl<'a> RawCommander<'a> {
         Ok(self.driver.enqueue_shutdown(frame, deadline)?)
     }
 
+    /// 发送拖动示教指令（0x150 Byte 2），返回发送完成时的 host 单调时间戳
+    pub(crate) fn send_teach_command(
+        &self,
+        command: TeachCommand,
+        timeout: Duration,
+    ) -> Result<u64> {
+        let cmd = EmergencyStopCommand::teach(command);
+        Ok(self
+            .driver
+            .send_reliable_frame_confirmed_commit_marker(cmd.to_frame(), timeout)?)
+    }
+
     /// 内部辅助：构建末端位姿的 3 个 CAN 帧
     ///
     /// 将帧生成逻辑提取出来，以便可以组合进不同的 Package。
@@ -213,6 +250,7 @@ impl<'a> RawCommander<'a> {
         orientation: EulerAngles,
         timeout: Duration,
     ) -> Result<()> {
+        self.driver.ensure_no_robot_fault()?;
         let frames = Self::build_end_pose_frames(&position, &orientation);
         self.driver.send_reliable_package_confirmed(frames, timeout)?;
         Ok(())
@@ -266,6 +304,7 @@ impl<'a> RawCommander<'a> {
         target_orientation: EulerAngles,
         timeout: Duration,
     ) -> Result<()> {
+        self.driver.ensure_no_robot_fault()?;
         use piper_protocol::control::{ArcPointCommand, ArcPointIndex};
 
         // 构建中间点位姿帧（3帧）
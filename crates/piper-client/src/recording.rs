@@ -27,6 +27,8 @@
 //!         notes: "Test recording".to_string(),
 //!         operator: "Alice".to_string(),
 //!     },
+//!     mode: Default::default(),
+//!     compression: Default::default(),
 //! })?;
 //!
 //! // 执行操作（会被录制，包含控制指令帧）
@@ -54,17 +56,29 @@
 //! # }
 //! ```
 
+use crate::observer::JointState;
+use crate::types::{JointArray, NewtonMeter, Rad, RadPerSecond};
 use piper_can::CanId;
 use piper_driver::recording::{
     RecordedFrameDirection, RecordedFrameEvent, TimestampProvenance, TimestampedFrame,
 };
-use piper_driver::{FrameCallback, HookHandle, HookManager};
-use std::path::PathBuf;
+use piper_driver::{FeedbackReplayer, FrameCallback, HookHandle, HookManager, JointDynamicState};
+pub use piper_tools::RecordingCompression;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, OnceLock};
 use std::sync::{Mutex, RwLock};
+use std::thread::JoinHandle;
 use std::time::{Duration, Instant};
 
+/// Buffered 模式的内存队列容量（帧）
+const BUFFERED_QUEUE_CAPACITY: usize = 100_000;
+
+/// 流式写线程在队列空闲时 flush 未满块的间隔
+const STREAMING_IDLE_FLUSH: Duration = Duration::from_millis(20);
+
 /// 录制句柄（用于控制和监控）
 ///
 /// # Drop 语义
@@ -72,7 +86,8 @@ use std::time::{Duration, Instant};
 /// 当 `RecordingHandle` 被丢弃时：
 /// - ✅ 自动请求停止录制
 /// - ✅ 自动解绑 Driver 侧 recording hook
-/// - ❌ 不会自动保存文件（需要显式调用 `stop_recording()`）
+/// - ❌ 不会自动保存文件（需要显式调用 `stop_recording()`）；
+///   流式模式下写线程会在后台写完已入队的帧并关闭文件
 ///
 /// # Panics
 ///
@@ -97,6 +112,9 @@ pub struct RecordingHandle {
     /// 输出文件路径
     output_path: PathBuf,
 
+    /// 输出文件压缩方式
+    compression: RecordingCompression,
+
     /// 用户提供的录制元数据。
     metadata: RecordingMetadata,
 
@@ -108,6 +126,9 @@ pub struct RecordingHandle {
 
     /// Driver hook 注册信息，用于在 stop_recording/Drop 时解绑 callback。
     hook_registration: Mutex<Option<(Arc<RwLock<HookManager>>, HookHandle)>>,
+
+    /// 流式写线程（仅 `RecordingMode::Streaming`）
+    streaming_worker: Option<StreamingRecordingWorker>,
 }
 
 pub(super) struct RecordingHandleParts {
@@ -117,11 +138,13 @@ pub(super) struct RecordingHandleParts {
     pub stop_requested: Arc<AtomicBool>,
     pub gate: Arc<Mutex<RecordingGate>>,
     pub output_path: PathBuf,
+    pub compression: RecordingCompression,
     pub metadata: RecordingMetadata,
     pub start_time_unix_secs: u64,
     pub start_time: Instant,
     pub hook_manager: Arc<RwLock<HookManager>>,
     pub hook_handle: HookHandle,
+    pub streaming_worker: Option<StreamingRecordingWorker>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -217,10 +240,19 @@ pub(super) struct ClientRecordingHook {
 }
 
 impl ClientRecordingHook {
+    #[cfg(test)]
     pub(super) fn new(
         condition: RecordingStopCondition,
     ) -> (Self, crossbeam_channel::Receiver<TimestampedFrame>) {
-        let (tx, rx) = crossbeam_channel::bounded(100_000);
+        Self::with_queue_capacity(condition, BUFFERED_QUEUE_CAPACITY)
+    }
+
+    /// 指定内存队列容量；队列满时新帧被丢弃并计入 `dropped_frames`
+    pub(super) fn with_queue_capacity(
+        condition: RecordingStopCondition,
+        queue_capacity: usize,
+    ) -> (Self, crossbeam_channel::Receiver<TimestampedFrame>) {
+        let (tx, rx) = crossbeam_channel::bounded(queue_capacity.max(1));
         let hook = Self {
             tx,
             dropped_frames: Arc::new(AtomicU64::new(0)),
@@ -352,6 +384,183 @@ pub(super) fn map_source(source: TimestampProvenance) -> Option<piper_tools::Tim
     }
 }
 
+/// 转换 piper_driver 录制帧为 piper_tools 文件格式帧
+pub(super) fn to_tools_frame(frame: TimestampedFrame) -> piper_tools::TimestampedFrame {
+    let direction = match frame.direction {
+        RecordedFrameDirection::Rx => piper_tools::RecordedFrameDirection::Rx,
+        RecordedFrameDirection::Tx => piper_tools::RecordedFrameDirection::Tx,
+    };
+    piper_tools::TimestampedFrame::new(
+        frame.frame,
+        direction,
+        map_source(frame.timestamp_provenance),
+    )
+}
+
+/// 组装写入文件的录制元数据
+pub(super) fn tools_metadata(
+    interface: String,
+    bus_speed: u32,
+    start_time_unix_secs: u64,
+    metadata: &RecordingMetadata,
+) -> piper_tools::RecordingMetadata {
+    let mut tools_metadata = piper_tools::RecordingMetadata::new(interface, bus_speed);
+    tools_metadata.start_time = start_time_unix_secs;
+    tools_metadata.notes = metadata.notes.clone();
+    tools_metadata.operator = metadata.operator.clone();
+    tools_metadata
+}
+
+/// 流式写盘使用的文件写入器
+enum StreamingFileWriter {
+    Plain(piper_tools::recording::v3::StreamingRecordingWriter<BufWriter<File>>),
+    Compressed(piper_tools::recording::CompressedRecordingWriter<BufWriter<File>>),
+}
+
+impl StreamingFileWriter {
+    fn push_frame(&mut self, frame: &piper_tools::TimestampedFrame) -> Result<(), String> {
+        match self {
+            Self::Plain(writer) => writer.push_frame(frame),
+            Self::Compressed(writer) => writer.push_frame(frame),
+        }
+        .map_err(|e| format!("{e:#}"))
+    }
+
+    /// 压缩模式下同时结束当前块，保证已 flush 的帧在崩溃后可恢复
+    fn flush(&mut self) -> Result<(), String> {
+        match self {
+            Self::Plain(writer) => writer.flush(),
+            Self::Compressed(writer) => writer.flush(),
+        }
+        .map_err(|e| format!("{e:#}"))
+    }
+
+    fn finish(self) -> Result<(), String> {
+        match self {
+            Self::Plain(writer) => writer.finish().map(drop),
+            Self::Compressed(writer) => writer.finish().map(drop),
+        }
+        .map_err(|e| format!("{e:#}"))
+    }
+}
+
+/// 流式录制写线程
+///
+/// 从有界队列取帧，每满 `chunk_frames` 帧（或队列空闲 `STREAMING_IDLE_FLUSH`）
+/// 写盘并 flush 一次。写盘慢时队列被填满，由 RX 线程侧的 `try_send` 丢帧计数，
+/// 不会阻塞 RX 线程。
+pub(super) struct StreamingRecordingWorker {
+    /// Hook 已解绑、队列不会再有新帧时置位，写线程排空队列后收尾
+    finish: Arc<AtomicBool>,
+    join: JoinHandle<Result<u64, String>>,
+}
+
+impl StreamingRecordingWorker {
+    /// 创建输出文件并启动写线程
+    ///
+    /// 文件头与元数据在调用线程中写出，因此路径不可写等错误会在 `start_recording()` 时返回。
+    pub(super) fn spawn(
+        rx: crossbeam_channel::Receiver<TimestampedFrame>,
+        output_path: &Path,
+        metadata: &piper_tools::RecordingMetadata,
+        chunk_frames: usize,
+        compression: RecordingCompression,
+        stop_requested: Arc<AtomicBool>,
+    ) -> crate::Result<Self> {
+        let io_error = |message: String| {
+            crate::RobotError::Infrastructure(piper_driver::DriverError::IoThread(message))
+        };
+
+        let file = File::create(output_path)
+            .map_err(|e| io_error(format!("create {}: {e}", output_path.display())))?;
+        let mut writer = match compression {
+            RecordingCompression::None => {
+                piper_tools::recording::v3::StreamingRecordingWriter::new(
+                    BufWriter::new(file),
+                    metadata,
+                )
+                .map(StreamingFileWriter::Plain)
+            },
+            RecordingCompression::Zstd { level } => {
+                piper_tools::recording::CompressedRecordingWriter::new(
+                    BufWriter::new(file),
+                    metadata,
+                    level,
+                )
+                .map(StreamingFileWriter::Compressed)
+            },
+        }
+        .map_err(|e| io_error(format!("{e:#}")))?;
+        writer.flush().map_err(io_error)?;
+
+        let chunk_frames = chunk_frames.max(1);
+        let finish = Arc::new(AtomicBool::new(false));
+        let worker_finish = finish.clone();
+        let join = std::thread::Builder::new()
+            .name("piper-recording-writer".to_string())
+            .spawn(move || {
+                let mut chunk = Vec::with_capacity(chunk_frames);
+                let mut written = 0u64;
+
+                let mut write_chunk =
+                    |chunk: &mut Vec<piper_tools::TimestampedFrame>| -> Result<(), String> {
+                        for frame in chunk.drain(..) {
+                            writer.push_frame(&frame)?;
+                            written += 1;
+                        }
+                        writer.flush()
+                    };
+
+                let result = loop {
+                    match rx.recv_timeout(STREAMING_IDLE_FLUSH) {
+                        Ok(frame) => {
+                            chunk.push(to_tools_frame(frame));
+                            if chunk.len() >= chunk_frames
+                                && let Err(error) = write_chunk(&mut chunk)
+                            {
+                                break Err(error);
+                            }
+                        },
+                        Err(crossbeam_channel::RecvTimeoutError::Timeout) => {
+                            if !chunk.is_empty()
+                                && let Err(error) = write_chunk(&mut chunk)
+                            {
+                                break Err(error);
+                            }
+                            if worker_finish.load(Ordering::Acquire) && rx.is_empty() {
+                                break Ok(());
+                            }
+                        },
+                        Err(crossbeam_channel::RecvTimeoutError::Disconnected) => break Ok(()),
+                    }
+                };
+                let result = result.and_then(|()| write_chunk(&mut chunk));
+
+                if let Err(error) = result {
+                    // 写盘失败：停止接收新帧，尽量把已写部分收尾为合法文件
+                    stop_requested.store(true, Ordering::Release);
+                    let _ = writer.finish();
+                    return Err(error);
+                }
+                writer.finish()?;
+                Ok(written)
+            })
+            .map_err(|e| io_error(format!("spawn recording writer: {e}")))?;
+
+        Ok(Self { finish, join })
+    }
+
+    fn signal_finish(&self) {
+        self.finish.store(true, Ordering::Release);
+    }
+
+    /// 通知写线程收尾并等待，返回写入的帧数
+    fn finish(self) -> Result<u64, String> {
+        self.signal_finish();
+        self.join.join().map_err(|_| "recording writer thread panicked".to_string())?
+    }
+}
+
 impl RecordingHandle {
     /// 创建新的录制句柄（内部使用）
     ///
@@ -368,10 +577,12 @@ impl RecordingHandle {
             stop_requested: parts.stop_requested,
             gate: parts.gate,
             output_path: parts.output_path,
+            compression: parts.compression,
             metadata: parts.metadata,
             start_time_unix_secs: parts.start_time_unix_secs,
             start_time: parts.start_time,
             hook_registration: Mutex::new(Some((parts.hook_manager, parts.hook_handle))),
+            streaming_worker: parts.streaming_worker,
         }
     }
 
@@ -413,6 +624,10 @@ impl RecordingHandle {
         &self.output_path
     }
 
+    pub(super) fn compression(&self) -> RecordingCompression {
+        self.compression
+    }
+
     pub(super) fn metadata(&self) -> &RecordingMetadata {
         &self.metadata
     }
@@ -426,6 +641,23 @@ impl RecordingHandle {
         &self.rx
     }
 
+    /// 流式模式：等待写线程写完剩余帧并关闭文件，返回录制统计
+    ///
+    /// 必须在 `stop()` 与 `detach_hook()` 之后调用。Buffered 模式返回 `None`。
+    pub(super) fn finish_streaming(&mut self) -> Option<crate::Result<RecordingStats>> {
+        let worker = self.streaming_worker.take()?;
+        let result = worker
+            .finish()
+            .map(|written| RecordingStats {
+                frame_count: written as usize,
+                duration: self.elapsed(),
+                dropped_frames: self.dropped_count(),
+                output_path: self.output_path.clone(),
+            })
+            .map_err(|e| crate::RobotError::Infrastructure(piper_driver::DriverError::IoThread(e)));
+        Some(result)
+    }
+
     /// 解绑当前录制 hook。重复调用是幂等的。
     pub(super) fn detach_hook(&self) {
         let registration = match self.hook_registration.lock() {
@@ -453,6 +685,10 @@ impl Drop for RecordingHandle {
             Err(poisoned) => poisoned.into_inner().close(),
         }
         self.detach_hook();
+        if let Some(worker) = &self.streaming_worker {
+            // 写线程在后台排空队列并关闭文件（不在 Drop 中等待）
+            worker.signal_finish();
+        }
         tracing::debug!("RecordingHandle dropped, callback removed");
     }
 }
@@ -468,6 +704,53 @@ pub struct RecordingConfig {
 
     /// 元数据
     pub metadata: RecordingMetadata,
+
+    /// 写入模式（内存缓存后一次写盘，或流式写盘）
+    pub mode: RecordingMode,
+
+    /// 文件压缩（默认不压缩）
+    ///
+    /// `RecordingCompression::zstd()` 按块压缩写盘，`PiperRecording::load()` 自动识别；
+    /// 进程崩溃时可恢复到最后一个已 flush 的块。
+    pub compression: RecordingCompression,
+}
+
+/// 录制写入模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RecordingMode {
+    /// 帧缓存在内存队列中（最多 100,000 帧），`stop_recording()` 时一次性写盘
+    ///
+    /// 适合短录制；超过队列容量的帧会被丢弃并计入 `dropped_frames`。
+    #[default]
+    Buffered,
+
+    /// 后台写线程按块流式写盘，内存只保留有界队列
+    ///
+    /// 适合长时间录制。写盘跟不上时新帧被丢弃（不阻塞 RX 线程），
+    /// 丢帧数见 [`RecordingStats::dropped_frames`]。
+    Streaming {
+        /// 每次写盘并 flush 的帧数
+        chunk_frames: usize,
+        /// 内存队列容量（帧）
+        queue_capacity: usize,
+    },
+}
+
+impl RecordingMode {
+    /// 流式模式的默认参数：每 1024 帧 flush 一次，队列容量 65,536 帧（约 1.5 MB）
+    pub const fn streaming() -> Self {
+        Self::Streaming {
+            chunk_frames: 1024,
+            queue_capacity: 65_536,
+        }
+    }
+
+    pub(super) fn queue_capacity(&self) -> usize {
+        match self {
+            Self::Buffered => BUFFERED_QUEUE_CAPACITY,
+            Self::Streaming { queue_capacity, .. } => *queue_capacity,
+        }
+    }
 }
 
 /// 停止条件
@@ -496,18 +779,67 @@ pub struct RecordingMetadata {
 /// 录制统计
 #[derive(Debug, Clone)]
 pub struct RecordingStats {
+    /// 写入文件的帧数
     pub frame_count: usize,
+    /// 录制时长
     pub duration: std::time::Duration,
+    /// 因内存队列满（写盘跟不上）而丢弃的帧数
     pub dropped_frames: u64,
+    /// 输出文件路径
     pub output_path: PathBuf,
 }
 
+/// 从录制文件重建关节状态时间线
+///
+/// `piper-tools` 不依赖驱动层，因此在这里为 [`piper_tools::PiperRecording`] 提供扩展：
+/// 按录制顺序把 RX 帧送入驱动的 [`piper_driver::FeedbackReplayer`]，复用与实时
+/// IO 线程相同的帧组聚合/提交逻辑，而不是在分析工具里另写一套。
+pub trait RecordingStateExt {
+    /// 每个完整反馈周期（关节位置帧组 + 6 轴动态反馈）产出一个 `(timestamp_us, JointState)`
+    ///
+    /// - `timestamp_us`：位置帧组的硬件时间戳（与 `JointState::timestamp_us` 相同）；
+    /// - `JointState::generation`：周期序号（从 1 开始）；
+    /// - 离线重放没有"当前时间"，`feedback_age` 恒为 0、`stale` 恒为 `false`；
+    /// - TX 帧被忽略。
+    fn reconstruct_states(&self) -> Vec<(u64, JointState)>;
+}
+
+impl RecordingStateExt for piper_tools::PiperRecording {
+    fn reconstruct_states(&self) -> Vec<(u64, JointState)> {
+        let mut replayer = FeedbackReplayer::default();
+        self.frames
+            .iter()
+            .filter(|frame| frame.direction == piper_tools::RecordedFrameDirection::Rx)
+            .filter_map(|frame| replayer.feed(frame.frame))
+            .zip(1..)
+            .map(|(state, generation)| {
+                let joint_state = JointState {
+                    position: JointArray::new(state.joint_pos.map(Rad)),
+                    velocity: JointArray::new(state.joint_vel.map(RadPerSecond)),
+                    effort: JointArray::new(std::array::from_fn(|index| {
+                        NewtonMeter(JointDynamicState::calculate_torque(
+                            index,
+                            state.joint_current[index],
+                        ))
+                    })),
+                    timestamp_us: state.position_timestamp_us,
+                    feedback_age: Duration::ZERO,
+                    stale: false,
+                    generation,
+                };
+                (state.position_timestamp_us, joint_state)
+            })
+            .collect()
+    }
+}
+
 // 以下方法将在 state/machine.rs 的 impl 中实现
 // 因为它们需要访问私有字段
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::Joint;
 
     #[test]
     fn test_stop_condition_duration() {
@@ -574,6 +906,8 @@ mod tests {
                 notes: "Test".to_string(),
                 operator: "Bob".to_string(),
             },
+            mode: Default::default(),
+            compression: Default::default(),
         };
 
         assert_eq!(
@@ -603,6 +937,70 @@ mod tests {
         assert_eq!(stats.output_path, std::path::PathBuf::from("/tmp/test.bin"));
     }
 
+    fn recorded_rx(id: u32, data: [u8; 8], timestamp_us: u64) -> piper_tools::TimestampedFrame {
+        piper_tools::TimestampedFrame::new(
+            piper_can::PiperFrame::new_standard(id, data)
+                .unwrap()
+                .with_timestamp_us(timestamp_us),
+            piper_tools::RecordedFrameDirection::Rx,
+            Some(piper_tools::TimestampSource::Hardware),
+        )
+    }
+
+    fn joint_feedback_data(first_deg: f64, second_deg: f64) -> [u8; 8] {
+        let mut data = [0u8; 8];
+        data[0..4].copy_from_slice(&((first_deg * 1000.0) as i32).to_be_bytes());
+        data[4..8].copy_from_slice(&((second_deg * 1000.0) as i32).to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn reconstruct_states_yields_one_joint_state_per_feedback_cycle() {
+        let mut recording = piper_tools::PiperRecording::new(piper_tools::RecordingMetadata::new(
+            "can0".to_string(),
+            1_000_000,
+        ));
+        for cycle in 0..3u64 {
+            let base_us = 1_000 + cycle * 5_000;
+            let degrees = 10.0 * (cycle + 1) as f64;
+            for (offset, id) in [0x2A5u32, 0x2A6, 0x2A7].into_iter().enumerate() {
+                recording.add_frame(recorded_rx(
+                    id,
+                    joint_feedback_data(degrees, degrees),
+                    base_us + offset as u64 * 100,
+                ));
+            }
+            // TX 帧不参与重建
+            recording.add_frame(piper_tools::TimestampedFrame::new(
+                piper_can::PiperFrame::new_standard(0x155, [0; 8])
+                    .unwrap()
+                    .with_timestamp_us(base_us + 250),
+                piper_tools::RecordedFrameDirection::Tx,
+                None,
+            ));
+            for joint in 0..6u32 {
+                let mut data = [0u8; 8];
+                data[0..2].copy_from_slice(&1000i16.to_be_bytes());
+                recording.add_frame(recorded_rx(
+                    0x251 + joint,
+                    data,
+                    base_us + 300 + u64::from(joint),
+                ));
+            }
+        }
+
+        let states = recording.reconstruct_states();
+        assert_eq!(states.len(), 3);
+        for (cycle, (timestamp_us, state)) in states.iter().enumerate() {
+            assert_eq!(*timestamp_us, 1_200 + cycle as u64 * 5_000);
+            assert_eq!(state.timestamp_us, *timestamp_us);
+            assert_eq!(state.generation, cycle as u64 + 1);
+            let expected = (10.0 * (cycle + 1) as f64).to_radians();
+            assert!((state.position[Joint::J6].0 - expected).abs() < 1e-9);
+            assert!((state.velocity[Joint::J1].0 - 1.0).abs() < 1e-9);
+        }
+    }
+
     #[test]
     fn test_recording_stats_clone() {
         let stats = RecordingStats {
@@ -146,6 +146,7 @@ mod recording_gate_tests {
     use piper_can::{CanId, PiperFrame, TimestampProvenance};
     use piper_driver::FrameCallback;
     use piper_driver::recording::{RecordedFrameDirection, RecordedFrameEvent};
+    use std::sync::atomic::Ordering;
     use std::time::Duration;
 
     fn event(frame: PiperFrame, direction: RecordedFrameDirection) -> RecordedFrameEvent {
@@ -261,4 +262,19 @@ mod recording_gate_tests {
         assert_eq!(frames[0].frame.raw_id(), 0x151);
         assert!(hook.is_stop_requested());
     }
+    #[test]
+    fn full_queue_drops_new_frames_without_blocking_and_counts_them() {
+        let (hook, rx) =
+            ClientRecordingHook::with_queue_capacity(RecordingStopCondition::Manual, 2);
+
+        for (index, id) in [0x151, 0x152, 0x153, 0x154].into_iter().enumerate() {
+            hook.on_frame(rx_event(standard(id, 10_000 + index as u64)));
+        }
+
+        let frames: Vec<_> = rx.try_iter().collect();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[1].frame.raw_id(), 0x152);
+        assert_eq!(hook.dropped_frames().load(Ordering::Relaxed), 2);
+        assert_eq!(hook.frame_counter().load(Ordering::Relaxed), 2);
+    }
 }
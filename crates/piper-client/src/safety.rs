@@ -0,0 +1,406 @@
+//! SafetyMonitor - 基于反馈的碰撞检测
+//!
+//! 静态限位（[`SafetyLimits`](crate::SafetyLimits)）只能约束下发的命令；本模块在后台线程中
+//! 订阅 [`Observer`] 的状态更新，由相邻反馈样本推算各关节的加加速度（jerk）与力矩变化率，
+//! 超过 [`CollisionSettings`] 阈值时调用用户回调（通常在回调中触发急停）。
+//!
+//! # 去抖
+//!
+//! 导数按跨 `debounce_samples` 个样本的差分计算，超限也需要连续持续 `debounce_samples`
+//! 个样本才视为一次碰撞事件：
+//! - 单帧毛刺（数值跳变后立即恢复）产生的超限样本不连续，会被忽略
+//! - 阶跃变化（碰撞后力矩持续升高）产生连续超限，回调触发一次
+//!
+//! 触发后直到某个样本不再超限才会重新布防，因此同一事件只回调一次。
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use crate::observer::{JointState, Observer};
+use crate::state::CapabilityMarker;
+use crate::types::{Joint, Result, RobotError};
+use piper_tools::{CollisionSettings, SafetyConfig};
+use tracing::{debug, warn};
+
+/// 等待状态更新的超时（同时也是关闭标志的检查间隔）
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// 超限的物理量
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SafetyViolationKind {
+    /// 关节加加速度（rad/s³）
+    Jerk,
+    /// 关节力矩变化率（N·m/s）
+    TorqueRate,
+}
+
+/// 碰撞事件
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SafetyViolation {
+    /// 超限关节（多个关节同时超限时取超出比例最大者）
+    pub joint: Joint,
+    /// 超限的物理量
+    pub kind: SafetyViolationKind,
+    /// 推算值
+    pub value: f64,
+    /// 阈值（绝对值）
+    pub threshold: f64,
+    /// 确认事件时的反馈时间戳（微秒）
+    pub timestamp_us: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    timestamp_us: u64,
+    values: [f64; 6],
+}
+
+/// 跨 `window` 个样本的差分：`(latest - oldest) / dt`
+fn windowed_rate(history: &VecDeque<Sample>) -> Option<[f64; 6]> {
+    let (oldest, latest) = (history.front()?, history.back()?);
+    let dt = latest.timestamp_us.checked_sub(oldest.timestamp_us)? as f64 / 1_000_000.0;
+    if dt <= 0.0 {
+        return None;
+    }
+    Some(std::array::from_fn(|index| {
+        (latest.values[index] - oldest.values[index]) / dt
+    }))
+}
+
+/// 碰撞检测器（纯计算，不持有线程）
+pub(crate) struct CollisionDetector {
+    settings: CollisionSettings,
+    window: usize,
+    velocities: VecDeque<Sample>,
+    accelerations: VecDeque<Sample>,
+    torques: VecDeque<Sample>,
+    consecutive: usize,
+    latched: bool,
+}
+
+impl CollisionDetector {
+    pub(crate) fn new(settings: CollisionSettings) -> Self {
+        let window = settings.debounce_samples.max(1);
+        Self {
+            settings,
+            window,
+            velocities: VecDeque::with_capacity(window + 1),
+            accelerations: VecDeque::with_capacity(window + 1),
+            torques: VecDeque::with_capacity(window + 1),
+            consecutive: 0,
+            latched: false,
+        }
+    }
+
+    fn push(history: &mut VecDeque<Sample>, sample: Sample, window: usize) -> bool {
+        history.push_back(sample);
+        if history.len() > window + 1 {
+            history.pop_front();
+        }
+        history.len() == window + 1
+    }
+
+    /// 输入一份反馈样本；确认新的碰撞事件时返回 `Some`
+    ///
+    /// 时间戳未前进的样本（同一反馈周期的重复提交）被忽略。
+    pub(crate) fn update(&mut self, state: &JointState) -> Option<SafetyViolation> {
+        if self
+            .velocities
+            .back()
+            .is_some_and(|last| state.timestamp_us <= last.timestamp_us)
+        {
+            return None;
+        }
+
+        let timestamp_us = state.timestamp_us;
+        let window = self.window;
+        let mut worst: Option<(f64, SafetyViolation)> = None;
+        let mut consider = |kind, rates: [f64; 6], threshold: f64| {
+            for joint in Joint::ALL {
+                let value = rates[joint.index()];
+                let ratio = value.abs() / threshold;
+                if ratio > 1.0 && worst.is_none_or(|(worst_ratio, _)| ratio > worst_ratio) {
+                    worst = Some((
+                        ratio,
+                        SafetyViolation {
+                            joint,
+                            kind,
+                            value,
+                            threshold,
+                            timestamp_us,
+                        },
+                    ));
+                }
+            }
+        };
+
+        let torque = Sample {
+            timestamp_us,
+            values: std::array::from_fn(|index| state.effort[Joint::ALL[index]].0),
+        };
+        if Self::push(&mut self.torques, torque, window)
+            && let Some(rates) = windowed_rate(&self.torques)
+        {
+            consider(
+                SafetyViolationKind::TorqueRate,
+                rates,
+                self.settings.max_torque_rate_nm_s,
+            );
+        }
+
+        let velocity = Sample {
+            timestamp_us,
+            values: std::array::from_fn(|index| state.velocity[Joint::ALL[index]].0),
+        };
+        if Self::push(&mut self.velocities, velocity, window)
+            && let Some(values) = windowed_rate(&self.velocities)
+        {
+            let acceleration = Sample {
+                timestamp_us,
+                values,
+            };
+            if Self::push(&mut self.accelerations, acceleration, window)
+                && let Some(rates) = windowed_rate(&self.accelerations)
+            {
+                consider(
+                    SafetyViolationKind::Jerk,
+                    rates,
+                    self.settings.max_jerk_rad_s3,
+                );
+            }
+        }
+
+        let Some((_, violation)) = worst else {
+            self.consecutive = 0;
+            self.latched = false;
+            return None;
+        };
+        self.consecutive += 1;
+        if self.consecutive >= window && !self.latched {
+            self.latched = true;
+            return Some(violation);
+        }
+        None
+    }
+}
+
+/// 碰撞监测器
+///
+/// 在后台线程中检测反馈异常并调用 `on_violation`。drop 或 [`SafetyMonitor::shutdown`]
+/// 时停止线程。
+pub struct SafetyMonitor {
+    handle: Option<thread::JoinHandle<()>>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl SafetyMonitor {
+    /// 启动监测线程
+    ///
+    /// 阈值取自 `config.collision`。
+    ///
+    /// # 错误
+    ///
+    /// - `RobotError::ConfigError`: 碰撞检测阈值不是正的有限值
+    /// - `RobotError::HardwareFailure`: 无法创建监测线程
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// # use piper_client::safety::SafetyMonitor;
+    /// # use piper_tools::SafetyConfig;
+    /// let estop = commander.emergency_stop_handle();
+    /// let monitor = SafetyMonitor::start(
+    ///     robot.observer().clone(),
+    ///     &SafetyConfig::default_config(),
+    ///     move |violation| {
+    ///         eprintln!("collision suspected: {violation:?}");
+    ///         let _ = estop.trigger();
+    ///     },
+    /// )?;
+    /// ```
+    pub fn start<Capability, F>(
+        observer: Observer<Capability>,
+        config: &SafetyConfig,
+        on_violation: F,
+    ) -> Result<Self>
+    where
+        Capability: CapabilityMarker,
+        F: FnMut(&SafetyViolation) + Send + 'static,
+    {
+        config.collision.validate().map_err(|error| {
+            RobotError::ConfigError(format!("invalid collision settings: {error}"))
+        })?;
+
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let shutdown_clone = shutdown.clone();
+        let detector = CollisionDetector::new(config.collision.clone());
+
+        let handle = thread::Builder::new()
+            .name("piper-safety-monitor".to_string())
+            .spawn(move || {
+                Self::monitor_loop(observer, detector, on_violation, shutdown_clone);
+            })
+            .map_err(|error| {
+                RobotError::hardware_failure(format!(
+                    "failed to spawn safety monitor thread: {error}"
+                ))
+            })?;
+
+        Ok(SafetyMonitor {
+            handle: Some(handle),
+            shutdown,
+        })
+    }
+
+    /// 监测循环
+    fn monitor_loop<Capability, F>(
+        observer: Observer<Capability>,
+        mut detector: CollisionDetector,
+        mut on_violation: F,
+        shutdown: Arc<AtomicBool>,
+    ) where
+        Capability: CapabilityMarker,
+        F: FnMut(&SafetyViolation),
+    {
+        let mut generation = observer.state_generation();
+        while !shutdown.load(Ordering::Relaxed) {
+            let state = match observer.wait_for_update(generation, POLL_INTERVAL) {
+                Ok(state) => state,
+                Err(RobotError::Timeout { .. }) => continue,
+                Err(error) => {
+                    // 状态尚不完整（如刚连接），稍后重试
+                    debug!("Safety monitor skipped update: {}", error);
+                    generation = observer.state_generation();
+                    thread::sleep(POLL_INTERVAL);
+                    continue;
+                },
+            };
+            generation = state.generation;
+
+            if let Some(violation) = detector.update(&state) {
+                warn!(
+                    "Safety monitor: {} {:?} {:.3} exceeds {:.3}",
+                    violation.joint, violation.kind, violation.value, violation.threshold
+                );
+                on_violation(&violation);
+            }
+        }
+    }
+
+    /// 停止监测线程并等待其退出
+    pub fn shutdown(mut self) {
+        self.stop();
+    }
+
+    /// 检查监测线程是否在运行
+    pub fn is_running(&self) -> bool {
+        !self.shutdown.load(Ordering::Relaxed)
+    }
+
+    fn stop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for SafetyMonitor {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{JointArray, NewtonMeter, Rad, RadPerSecond};
+
+    const PERIOD_US: u64 = 5_000;
+
+    fn state(index: u64, velocity: f64, torque: f64) -> JointState {
+        let mut velocities = JointArray::splat(RadPerSecond(0.0));
+        velocities[Joint::J2] = RadPerSecond(velocity);
+        let mut efforts = JointArray::splat(NewtonMeter(0.0));
+        efforts[Joint::J3] = NewtonMeter(torque);
+        JointState {
+            position: JointArray::splat(Rad(0.0)),
+            velocity: velocities,
+            effort: efforts,
+            timestamp_us: 1_000_000 + index * PERIOD_US,
+            feedback_age: Duration::ZERO,
+            stale: false,
+            generation: index + 1,
+        }
+    }
+
+    fn run(detector: &mut CollisionDetector, samples: &[(f64, f64)]) -> Vec<SafetyViolation> {
+        samples
+            .iter()
+            .enumerate()
+            .filter_map(|(index, &(velocity, torque))| {
+                detector.update(&state(index as u64, velocity, torque))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn torque_step_fires_exactly_once_per_event() {
+        let mut detector = CollisionDetector::new(CollisionSettings::default());
+        // 两次力矩阶跃（0 → 5 → 0 N·m），每次之间保持足够长的平稳段
+        let mut samples = vec![(0.0, 0.0); 10];
+        samples.extend(vec![(0.0, 5.0); 10]);
+        samples.extend(vec![(0.0, 0.0); 10]);
+
+        let violations = run(&mut detector, &samples);
+        assert_eq!(violations.len(), 2, "{violations:?}");
+        for violation in &violations {
+            assert_eq!(violation.joint, Joint::J3);
+            assert_eq!(violation.kind, SafetyViolationKind::TorqueRate);
+            assert_eq!(violation.threshold, 200.0);
+        }
+        assert!(violations[0].value > 0.0);
+        assert!(violations[1].value < 0.0);
+        // 去抖：阶跃后第 3 个样本确认事件
+        assert_eq!(violations[0].timestamp_us, state(12, 0.0, 0.0).timestamp_us);
+    }
+
+    #[test]
+    fn velocity_step_fires_jerk_violation_once() {
+        let mut detector = CollisionDetector::new(CollisionSettings::default());
+        let mut samples = vec![(0.0, 0.0); 10];
+        samples.extend(vec![(0.5, 0.0); 20]);
+
+        let violations = run(&mut detector, &samples);
+        assert_eq!(violations.len(), 1, "{violations:?}");
+        assert_eq!(violations[0].joint, Joint::J2);
+        assert_eq!(violations[0].kind, SafetyViolationKind::Jerk);
+    }
+
+    #[test]
+    fn single_sample_spikes_are_debounced() {
+        let mut detector = CollisionDetector::new(CollisionSettings::default());
+        let mut samples = vec![(0.0, 0.0); 30];
+        samples[10] = (0.0, 5.0);
+        samples[20] = (0.5, 0.0);
+
+        assert!(run(&mut detector, &samples).is_empty());
+    }
+
+    #[test]
+    fn repeated_timestamps_are_ignored() {
+        let mut detector = CollisionDetector::new(CollisionSettings::default());
+        for index in 0..10 {
+            assert!(detector.update(&state(index, 0.0, 0.0)).is_none());
+            assert!(detector.update(&state(index, 0.0, 0.0)).is_none());
+        }
+        assert_eq!(detector.torques.len(), detector.window + 1);
+        assert_eq!(
+            detector.torques.back().map(|sample| sample.timestamp_us),
+            Some(state(9, 0.0, 0.0).timestamp_us)
+        );
+    }
+}
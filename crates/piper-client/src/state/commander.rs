@@ -0,0 +1,132 @@
+//! 读写分离：`MotionCommander` + `Observer`
+//!
+//! `Piper<Active<M>>::split()` 把一次连接拆成两个可独立移动到不同线程的句柄：
+//!
+//! - [`MotionCommander`]：独占命令通道，持有 `Piper<Active<M>>` 的全部所有权；
+//!   Drop 时与 `Piper<Active<M>>` 相同，best-effort 失能全部关节。
+//! - [`Observer`]：`Clone + Send + Sync`，直接读取 driver 的无锁状态，
+//!   可任意克隆给日志、UI 等只读线程，不会重新解析原始帧。
+//! - [`EmergencyStopHandle`]：`Clone + Send + Sync`，任意线程都可触发急停；
+//!   急停帧走 driver 的 shutdown lane，抢占所有已排队的实时/可靠命令。
+//!
+//! # 示例
+//!
+//! ```rust,ignore
+//! # use piper_client::state::*;
+//! # use piper_client::types::*;
+//! # fn example(robot: Piper<Active<MitMode>, StrictRealtime>) -> Result<()> {
+//! let (commander, observer) = robot.split();
+//!
+//! let logger = std::thread::spawn(move || {
+//!     for _ in 0..100 {
+//!         if let Ok(snapshot) = observer.control_snapshot(ControlReadPolicy::default()) {
+//!             println!("q = {:?}", snapshot.position);
+//!         }
+//!         std::thread::sleep(std::time::Duration::from_millis(10));
+//!     }
+//! });
+//!
+//! let control = std::thread::spawn(move || {
+//!     // ... 控制循环 ...
+//!     // commander 在此 drop：自动失能全部关节
+//!     drop(commander);
+//! });
+//! # Ok(())
+//! # }
+//! ```
+
+use super::capability::MotionCapability;
+use super::machine::{Active, EMERGENCY_STOP_LANE_TIMEOUT, ErrorState, Piper};
+use crate::observer::Observer;
+use crate::raw_commander::RawCommander;
+use crate::types::Result;
+use piper_driver::Piper as RobotPiper;
+use std::ops::Deref;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// 运动命令句柄（读写分离中的"写"端）
+///
+/// 通过 `Piper<Active<M>>::split()` 获取。通过 `Deref` 暴露当前模式下
+/// `Piper<Active<M>>` 的全部命令方法；需要状态转换（如 `disable()`）时，
+/// 先调用 [`MotionCommander::into_inner`] 取回 `Piper`。
+///
+/// **安全性：** 不提供 `Clone`，命令通道始终只有一个所有者。
+/// Drop 时会 best-effort 失能全部关节，与直接 drop `Piper<Active<M>>` 一致；
+/// 已 split 出去的 `Observer` 仍可继续读取失能后的状态。
+pub struct MotionCommander<M, Capability> {
+    piper: Piper<Active<M>, Capability>,
+}
+
+impl<M, Capability> MotionCommander<M, Capability>
+where
+    Capability: MotionCapability,
+{
+    /// 取回底层 `Piper<Active<M>>`，用于状态转换
+    pub fn into_inner(self) -> Piper<Active<M>, Capability> {
+        self.piper
+    }
+
+    /// 急停并进入 `ErrorState`
+    ///
+    /// 等价于 `into_inner().emergency_stop()`：急停帧经 shutdown lane 发送，
+    /// 排在它前面的命令会被丢弃。之后需 `recover_from_emergency_stop()` 才能回到 `Standby`。
+    pub fn estop(self) -> Result<Piper<ErrorState, Capability>> {
+        self.piper.emergency_stop()
+    }
+}
+
+impl<M, Capability> Deref for MotionCommander<M, Capability> {
+    type Target = Piper<Active<M>, Capability>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.piper
+    }
+}
+
+impl<M, Capability> Piper<Active<M>, Capability>
+where
+    Capability: MotionCapability,
+{
+    /// 拆分为命令句柄和只读观察器
+    ///
+    /// 返回的 `Observer` 可自由克隆并跨线程共享；`MotionCommander` 独占命令通道，
+    /// drop 时自动失能全部关节。
+    pub fn split(self) -> (MotionCommander<M, Capability>, Observer<Capability>) {
+        let observer = self.observer.clone();
+        (MotionCommander { piper: self }, observer)
+    }
+
+    /// 获取可跨线程共享的急停句柄
+    pub fn emergency_stop_handle(&self) -> EmergencyStopHandle {
+        EmergencyStopHandle {
+            driver: Arc::clone(&self.driver),
+        }
+    }
+}
+
+/// 急停句柄（不持有命令通道）
+///
+/// 通过 `Piper<Active<M>>::emergency_stop_handle()` 获取，可克隆给看门狗、UI 等线程。
+/// `trigger()` 会先锁存故障（清空待发的实时命令与可靠队列、拒绝后续控制命令），
+/// 再经 shutdown lane 发送急停帧并等待 TX 线程确认。
+///
+/// 句柄无法改变持有者的 type-state：触发后 `MotionCommander` / `Piper<Active<M>>`
+/// 的控制命令会返回错误，持有者应调用 `estop()` / `emergency_stop()` 进入 `ErrorState`。
+#[derive(Clone)]
+pub struct EmergencyStopHandle {
+    driver: Arc<RobotPiper>,
+}
+
+impl EmergencyStopHandle {
+    /// 立即急停
+    ///
+    /// 可重复调用；每次都会重新发送急停帧。
+    pub fn trigger(&self) -> Result<()> {
+        self.driver.latch_fault();
+        let receipt = RawCommander::new(&self.driver)
+            .emergency_stop_enqueue(Instant::now() + EMERGENCY_STOP_LANE_TIMEOUT)?;
+        receipt.wait()?;
+        Ok(())
+    }
+}
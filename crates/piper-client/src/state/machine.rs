@@ -2,14 +2,20 @@
 //!
 //! 使用零大小类型（ZST）标记实现状态机，在编译期防止非法状态转换。
 
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use crate::connection::{InitialMotionState, InitializedConnection, initialize_connected_driver};
+use crate::control::ZeroingSession;
+use crate::gripper::GripperCommander;
+use crate::kinematics::PiperKinematics;
 use crate::state::capability::{
     CapabilityMarker, MonitorOnly, MotionCapability, SoftRealtime, StrictCapability,
     StrictRealtime, UnspecifiedCapability,
 };
+use crate::teach::{TeachRecording, TeachRecordingConfig, TeachTrajectory};
+use crate::trajectory_streamer::{TrajectoryStreamConfig, TrajectoryStreamer};
 use crate::types::*;
 use crate::{
     observer::{CollisionProtectionSnapshot, MonitorReadPolicy, Observer, RuntimeHealthSnapshot},
@@ -20,15 +26,17 @@ use piper_driver::{
     RuntimeFaultKind, SettingResponseState,
 };
 use piper_protocol::control::{InstallPosition, MitControlCommand, MitMode as ProtocolMitMode};
-use piper_protocol::feedback::{ControlMode, MoveMode, RobotStatus};
+use piper_protocol::feedback::{ControlMode, FirmwareFeature, MotionStatus, MoveMode, RobotStatus};
+use piper_tools::{SafetyLimitMode, SafetyLimits};
 use tracing::{debug, info, trace, warn};
 
 const COLLISION_QUERY_POLL_INTERVAL: Duration = Duration::from_millis(10);
 const ZERO_SETTING_CONFIRM_TIMEOUT: Duration = Duration::from_secs(2);
 const ZERO_SETTING_POLL_INTERVAL: Duration = Duration::from_millis(10);
 const STATE_TRANSITION_SEND_TIMEOUT: Duration = Duration::from_millis(50);
-const EMERGENCY_STOP_LANE_TIMEOUT: Duration = Duration::from_millis(20);
+pub(super) const EMERGENCY_STOP_LANE_TIMEOUT: Duration = Duration::from_millis(20);
 const RECOVERY_STATE_POLL_INTERVAL: Duration = Duration::from_millis(10);
+const MOTION_ARRIVAL_POLL_INTERVAL: Duration = Duration::from_millis(5);
 
 // ==================== 状态类型（零大小类型）====================
 
@@ -68,7 +76,54 @@ pub struct ConfirmedMitBatch {
 /// MIT 模式
 ///
 /// 支持位置、速度、力矩的混合控制。
-pub struct MitMode;
+pub struct MitMode {
+    /// 使能时确认的 0x151 速度百分比（切换失败回滚时原样下发）
+    pub(crate) speed_percent: u8,
+    /// 最近一次成功提交的 MIT 批命令（切换到位置模式时作为降力矩起点）
+    pub(crate) last_command: Mutex<Option<MitCommandSnapshot>>,
+}
+
+impl MitMode {
+    pub(crate) fn new(speed_percent: u8) -> Self {
+        Self {
+            speed_percent,
+            last_command: Mutex::new(None),
+        }
+    }
+
+    fn record_command(&self, command: MitCommandSnapshot) {
+        *self.last_command.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(command);
+    }
+
+    fn last_command(&self) -> Option<MitCommandSnapshot> {
+        *self.last_command.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// 一批 MIT 命令的用户坐标系参数（速度不参与降力矩，不记录）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct MitCommandSnapshot {
+    pub(crate) positions: JointArray<Rad>,
+    pub(crate) kp: JointArray<f64>,
+    pub(crate) kd: JointArray<f64>,
+    pub(crate) torques: JointArray<NewtonMeter>,
+}
+
+impl MitCommandSnapshot {
+    fn new(
+        positions: &JointArray<Rad>,
+        kp: &JointArray<f64>,
+        kd: &JointArray<f64>,
+        torques: &JointArray<NewtonMeter>,
+    ) -> Self {
+        Self {
+            positions: *positions,
+            kp: *kp,
+            kd: *kd,
+            torques: *torques,
+        }
+    }
+}
 
 /// SoftRealtime MIT 透传模式
 ///
@@ -81,6 +136,9 @@ pub struct MitPassthroughMode;
 pub struct PositionMode {
     pub(crate) command_timeout: Duration,
     pub(crate) motion_type: MotionType,
+    pub(crate) joint_limits: Option<JointLimits>,
+    /// 最近一次确认的 0x151 速度百分比（单独修改安装位置时需要原样下发）
+    pub(crate) speed_percent: AtomicU8,
 }
 
 /// 错误状态
@@ -192,6 +250,19 @@ fn format_optional_joint_mask(mask: Option<u8>) -> String {
     }
 }
 
+/// 拒绝 NaN/∞ 命令值
+///
+/// `f64::max`/`min` 会忽略 NaN，限幅会把 NaN 变成边界值下发，因此必须在限幅之前检查。
+fn ensure_finite_command(operation: &str, joint: Joint, field: &str, value: f64) -> Result<()> {
+    if value.is_finite() {
+        return Ok(());
+    }
+    Err(RobotError::InvalidParameter {
+        param: format!("{operation} {joint} {field}"),
+        reason: format!("must be finite, got {value}"),
+    })
+}
+
 fn enabled_mask_from_low_speed_complete(driver_state: &piper_driver::JointDriverLowSpeed) -> u8 {
     driver_state.joints.iter().enumerate().fold(0, |mask, (index, joint)| {
         if joint.enabled {
@@ -438,10 +509,15 @@ pub struct PositionModeConfig {
     /// - `Joint`: 使用 `send_position_command()` 或 `command_position_from_snapshot()`
     /// - `Cartesian`/`Linear`: 使用 `command_cartesian_pose()`
     /// - `Circular`: 使用 `move_circular()` 方法
-    /// - `ContinuousPositionVelocity`: 待实现
+    /// - `ContinuousPositionVelocity`: 使用 `trajectory_streamer()`
     pub motion_type: MotionType,
     /// 多帧任务型运动命令的整包发送超时。
     pub command_timeout: Duration,
+    /// 关节位置命令的客户端软限位
+    ///
+    /// 设置后 `send_position_command()` / `move_and_wait()` 会把超出范围的目标
+    /// 限制到限位内并输出警告，避免触发控制器的角度超限故障。默认 `None`（不处理）。
+    pub joint_limits: Option<JointLimits>,
 }
 
 impl Default for PositionModeConfig {
@@ -454,6 +530,49 @@ impl Default for PositionModeConfig {
             install_position: InstallPosition::Invalid, // 默认无效值（不设置安装位置）
             motion_type: MotionType::Joint,             // ✅ 默认关节模式，向后兼容
             command_timeout: Duration::from_millis(20),
+            joint_limits: None,
+        }
+    }
+}
+
+impl PositionModeConfig {
+    /// 检查客户端软限位是否合法（进入位置模式前调用，避免每条位置命令都带着非法限位）
+    fn validate(&self) -> Result<()> {
+        if let Some(limits) = &self.joint_limits {
+            limits.validate().map_err(|error| {
+                RobotError::ConfigError(format!("invalid joint limits: {error}"))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// MIT → 位置模式热切换配置
+///
+/// 用于 [`Piper::switch_to_position`]：先在当前位置以低增益保持、线性撤掉前馈力矩，
+/// 再下发 0x151 切换到位置模式。
+#[derive(Debug, Clone)]
+pub struct MitToPositionConfig {
+    /// 目标位置模式配置（0x151 参数、确认超时等）
+    pub position: PositionModeConfig,
+    /// 降力矩阶段的保持增益 Kp
+    pub hold_kp: JointArray<f64>,
+    /// 降力矩阶段的保持增益 Kd
+    pub hold_kd: JointArray<f64>,
+    /// 前馈力矩线性降到 0 的时长
+    pub ramp_duration: Duration,
+    /// 降力矩阶段的命令周期
+    pub ramp_period: Duration,
+}
+
+impl Default for MitToPositionConfig {
+    fn default() -> Self {
+        Self {
+            position: PositionModeConfig::default(),
+            hold_kp: JointArray::splat(5.0),
+            hold_kd: JointArray::splat(0.8),
+            ramp_duration: Duration::from_millis(200),
+            ramp_period: Duration::from_millis(5),
         }
     }
 }
@@ -496,6 +615,10 @@ pub struct Piper<State = Disconnected, Capability = UnspecifiedCapability> {
     pub(crate) driver: Arc<piper_driver::Piper>,
     pub(crate) observer: Observer<Capability>,
     pub(crate) quirks: DeviceQuirks,
+    /// 用户配置的关节力矩 / 速度限制（`None` 表示不额外限制）
+    pub(crate) safety_limits: Option<Arc<SafetyLimits>>,
+    /// 用户配置的使能确认超时（`None` 表示使用各模式配置中的 `timeout`）
+    pub(crate) enable_timeout: Option<Duration>,
     pub(crate) drop_policy: DropPolicy,
     pub(crate) driver_mode_drop_policy: DriverModeDropPolicy,
     pub(crate) _state: State, // 改为直接存储状态（不再使用 PhantomData）
@@ -507,6 +630,19 @@ pub(crate) enum DropPolicy {
     DisableAll,
 }
 
+impl DropPolicy {
+    /// 与该策略对应的 driver 级 drop 安全停止动作
+    ///
+    /// 客户端句柄被泄漏或 driver 比句柄活得更久时，driver 的 drop 兜底
+    /// 与客户端策略保持一致；`Noop` 状态下 driver 不会主动失能机械臂。
+    pub(crate) fn driver_stop_action(self) -> piper_driver::DropStopAction {
+        match self {
+            Self::Noop => piper_driver::DropStopAction::None,
+            Self::DisableAll => piper_driver::DropStopAction::Disable,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum DriverModeDropPolicy {
     Preserve,
@@ -596,6 +732,8 @@ where
     driver: Arc<piper_driver::Piper>,
     observer: Observer<Capability>,
     quirks: DeviceQuirks,
+    safety_limits: Option<Arc<SafetyLimits>>,
+    enable_timeout: Option<Duration>,
 }
 
 impl<Capability> PiperTransitionParts<Capability>
@@ -608,10 +746,13 @@ where
         drop_policy: DropPolicy,
         driver_mode_drop_policy: DriverModeDropPolicy,
     ) -> Piper<NewState, Capability> {
+        self.driver.set_drop_stop_action(drop_policy.driver_stop_action());
         Piper {
             driver: self.driver,
             observer: self.observer,
             quirks: self.quirks,
+            safety_limits: self.safety_limits,
+            enable_timeout: self.enable_timeout,
             drop_policy,
             driver_mode_drop_policy,
             _state: new_state,
@@ -656,6 +797,8 @@ where
     let driver = unsafe { std::ptr::read(&this.driver) };
     let observer = unsafe { std::ptr::read(&this.observer) };
     let quirks = unsafe { std::ptr::read(&this.quirks) };
+    let safety_limits = unsafe { std::ptr::read(&this.safety_limits) };
+    let enable_timeout = unsafe { std::ptr::read(&this.enable_timeout) };
     let state = unsafe { std::ptr::read(&this._state) };
 
     (
@@ -663,6 +806,8 @@ where
             driver,
             observer,
             quirks,
+            safety_limits,
+            enable_timeout,
         },
         state,
     )
@@ -768,6 +913,8 @@ where
 fn build_motion_connected_state<Capability>(
     driver: Arc<piper_driver::Piper>,
     quirks: DeviceQuirks,
+    safety_limits: Option<Arc<SafetyLimits>>,
+    enable_timeout: Option<Duration>,
     initial_state: InitialMotionState,
 ) -> MotionConnectedState<Capability>
 where
@@ -778,19 +925,26 @@ where
             observer: Observer::<Capability>::new(driver.clone()),
             driver,
             quirks,
+            safety_limits,
+            enable_timeout,
             drop_policy: DropPolicy::Noop,
             driver_mode_drop_policy: DriverModeDropPolicy::Preserve,
             _state: Standby,
         }),
-        InitialMotionState::Maintenance { .. } => MotionConnectedState::Maintenance(Piper {
-            observer: Observer::<Capability>::new(driver.clone()),
-            driver,
-            quirks,
-            drop_policy: DropPolicy::DisableAll,
-            driver_mode_drop_policy: DriverModeDropPolicy::Preserve,
-            _state: Maintenance {
-                pending_disable_commit_host_mono_us: None,
-            },
+        InitialMotionState::Maintenance { .. } => MotionConnectedState::Maintenance({
+            driver.set_drop_stop_action(DropPolicy::DisableAll.driver_stop_action());
+            Piper {
+                observer: Observer::<Capability>::new(driver.clone()),
+                driver,
+                quirks,
+                safety_limits,
+                enable_timeout,
+                drop_policy: DropPolicy::DisableAll,
+                driver_mode_drop_policy: DriverModeDropPolicy::Preserve,
+                _state: Maintenance {
+                    pending_disable_commit_host_mono_us: None,
+                },
+            }
         }),
     }
 }
@@ -801,6 +955,8 @@ pub(crate) fn connected_piper_from_driver(
 ) -> Result<ConnectedPiper> {
     let InitializedConnection {
         quirks,
+        safety_limits,
+        enable_timeout,
         initial_state,
     } = initialized;
 
@@ -809,14 +965,22 @@ pub(crate) fn connected_piper_from_driver(
             Ok(ConnectedPiper::Strict(build_motion_connected_state::<
                 StrictRealtime,
             >(
-                driver, quirks, initial_state
+                driver,
+                quirks,
+                safety_limits,
+                enable_timeout,
+                initial_state,
             )))
         },
         BackendCapability::SoftRealtime => {
             Ok(ConnectedPiper::Soft(build_motion_connected_state::<
                 SoftRealtime,
             >(
-                driver, quirks, initial_state
+                driver,
+                quirks,
+                safety_limits,
+                enable_timeout,
+                initial_state,
             )))
         },
         BackendCapability::MonitorOnly => match initial_state {
@@ -824,6 +988,8 @@ pub(crate) fn connected_piper_from_driver(
                 observer: Observer::<MonitorOnly>::new(driver.clone()),
                 driver,
                 quirks,
+                safety_limits,
+                enable_timeout,
                 drop_policy: DropPolicy::Noop,
                 driver_mode_drop_policy: DriverModeDropPolicy::Preserve,
                 _state: Standby,
@@ -941,6 +1107,27 @@ impl Piper<Disconnected, UnspecifiedCapability> {
 
 // ==================== Standby 状态 ====================
 
+impl<Capability> Piper<Standby, Capability>
+where
+    Capability: MotionCapability,
+{
+    /// 进入拖动示教并开始录制关节轨迹
+    ///
+    /// 发送 0x150 "开始示教记录"，等待 0x2A1 上报 `TeachStatus::StartRecord` 后，
+    /// 后台线程按 `sample_interval` 从 `Observer` 采样关节位置。
+    ///
+    /// # 错误
+    ///
+    /// - `RobotError::TeachModeRejected`: 控制器在超时内未进入拖动示教
+    pub fn start_teach_recording(
+        &self,
+        config: TeachRecordingConfig,
+    ) -> Result<TeachRecording<'_>> {
+        self.ensure_runtime_health_healthy()?;
+        TeachRecording::start(&self.driver, &self.observer, config)
+    }
+}
+
 impl<Capability> Piper<Standby, Capability>
 where
     Capability: CapabilityMarker,
@@ -951,6 +1138,7 @@ where
     ///
     /// - `RobotError::Timeout`: 使能超时
     /// - `RobotError::HardwareError`: 硬件响应异常
+    /// - `RobotError::Infrastructure(DriverError::UnsupportedByFirmware)`: 固件不支持所需 MOVE 模式
     ///
     /// # 示例
     ///
@@ -974,9 +1162,13 @@ where
 
         debug!("Enabling MIT mode (speed_percent={})", config.speed_percent);
 
+        self.quirks.ensure_move_mode_supported(MoveMode::MoveM)?;
+
         // === PHASE 1: All operations that can panic ===
 
         // 1. 发送使能指令
+        // 使能确认要求 0x2A1 为 Normal，进入新的 Active 前清除旧的故障锁存
+        self.driver.clear_robot_fault();
         let enable_cmd = MotorEnableCommand::enable_all();
         let enable_commit_host_mono_us = self
             .driver
@@ -1025,7 +1217,7 @@ where
 
         Ok(transition_piper_state(
             self,
-            Active(MitMode),
+            Active(self::MitMode::new(config.speed_percent)),
             DropPolicy::DisableAll,
             DriverModeDropPolicy::Preserve,
         ))
@@ -1037,6 +1229,7 @@ where
     ///
     /// - `RobotError::Timeout`: 使能超时
     /// - `RobotError::HardwareError`: 硬件响应异常
+    /// - `RobotError::Infrastructure(DriverError::UnsupportedByFirmware)`: 固件不支持所需 MOVE 模式
     pub fn enable_position_mode(
         self,
         config: PositionModeConfig,
@@ -1049,15 +1242,14 @@ where
             config.motion_type, config.speed_percent
         );
 
-        if config.motion_type == MotionType::ContinuousPositionVelocity {
-            return Err(RobotError::ConfigError(
-                "MotionType::ContinuousPositionVelocity is not implemented yet".to_string(),
-            ));
-        }
+        self.quirks.ensure_move_mode_supported(config.motion_type.into())?;
+        config.validate()?;
 
         // === PHASE 1: All operations that can panic ===
 
         // 1. 发送使能指令
+        // 使能确认要求 0x2A1 为 Normal，进入新的 Active 前清除旧的故障锁存
+        self.driver.clear_robot_fault();
         let enable_cmd = piper_protocol::control::MotorEnableCommand::enable_all();
         let enable_commit_host_mono_us = self
             .driver
@@ -1081,6 +1273,8 @@ where
             Active(PositionMode {
                 command_timeout: config.command_timeout,
                 motion_type: config.motion_type,
+                joint_limits: config.joint_limits,
+                speed_percent: AtomicU8::new(config.speed_percent),
             }),
             DropPolicy::DisableAll,
             DriverModeDropPolicy::Preserve,
@@ -1145,6 +1339,7 @@ where
         debounce_threshold: usize,
         poll_interval: Duration,
     ) -> Result<()> {
+        let timeout = self.enable_timeout.unwrap_or(timeout);
         let start = Instant::now();
         let mut stable_count = 0;
 
@@ -1208,6 +1403,8 @@ where
     ///         notes: "Test recording".to_string(),
     ///         operator: "Alice".to_string(),
     ///     },
+    ///     mode: Default::default(),
+    ///     compression: Default::default(),
     /// })?;
     ///
     /// // 执行操作（会被录制）
@@ -1223,8 +1420,8 @@ where
         config: crate::recording::RecordingConfig,
     ) -> Result<(Self, crate::recording::RecordingHandle)> {
         use crate::recording::{
-            ClientRecordingHook, RecordingHandle, RecordingHandleParts, RecordingStopCondition,
-            StopCondition,
+            ClientRecordingHook, RecordingHandle, RecordingHandleParts, RecordingMode,
+            RecordingStopCondition, StopCondition, StreamingRecordingWorker,
         };
 
         let stop_condition = match &config.stop_condition {
@@ -1236,13 +1433,37 @@ where
             StopCondition::FrameCount(count) => RecordingStopCondition::FrameCount(*count as u64),
         };
 
-        let (hook, rx) = ClientRecordingHook::new(stop_condition);
+        let (hook, rx) =
+            ClientRecordingHook::with_queue_capacity(stop_condition, config.mode.queue_capacity());
 
         let dropped = hook.dropped_frames().clone();
         let counter = hook.frame_counter().clone();
         let stop_requested = hook.stop_requested().clone();
         let gate = hook.gate().clone();
 
+        let start_time_unix_secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        // 流式模式：先创建文件并启动写线程，失败时不注册钩子
+        let streaming_worker = match config.mode {
+            RecordingMode::Buffered => None,
+            RecordingMode::Streaming { chunk_frames, .. } => Some(StreamingRecordingWorker::spawn(
+                rx.clone(),
+                &config.output_path,
+                &crate::recording::tools_metadata(
+                    self.driver.interface(),
+                    self.driver.bus_speed(),
+                    start_time_unix_secs,
+                    &config.metadata,
+                ),
+                chunk_frames,
+                config.compression,
+                stop_requested.clone(),
+            )?),
+        };
+
         // 注册钩子
         let callback = std::sync::Arc::new(hook) as std::sync::Arc<dyn piper_driver::FrameCallback>;
         let hook_manager = self.driver.hooks();
@@ -1260,14 +1481,13 @@ where
             stop_requested,
             gate,
             output_path: config.output_path.clone(),
+            compression: config.compression,
             metadata: config.metadata.clone(),
-            start_time_unix_secs: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs(),
+            start_time_unix_secs,
             start_time: std::time::Instant::now(),
             hook_manager,
             hook_handle,
+            streaming_worker,
         });
 
         tracing::info!("Recording started: {:?}", config.output_path);
@@ -1308,7 +1528,7 @@ where
     /// ```
     pub fn stop_recording(
         self,
-        handle: crate::recording::RecordingHandle,
+        mut handle: crate::recording::RecordingHandle,
     ) -> Result<(Self, crate::recording::RecordingStats)> {
         use piper_tools::{
             PiperRecording, RecordedFrameDirection as ToolsRecordedFrameDirection, TimestampedFrame,
@@ -1317,6 +1537,18 @@ where
         handle.stop();
         handle.detach_hook();
 
+        // 流式模式：帧已在写盘，等待写线程收尾即可
+        if let Some(result) = handle.finish_streaming() {
+            let stats = result?;
+            tracing::info!(
+                "Recording streamed: {} frames, {:.2}s, {} dropped",
+                stats.frame_count,
+                stats.duration.as_secs_f64(),
+                stats.dropped_frames
+            );
+            return Ok((self, stats));
+        }
+
         // 创建录制对象
         let mut recording = PiperRecording::new(piper_tools::RecordingMetadata::new(
             self.driver.interface(),
@@ -1348,9 +1580,13 @@ where
         }
 
         // 保存文件
-        recording.save(handle.output_path()).map_err(|e| {
-            crate::RobotError::Infrastructure(piper_driver::DriverError::IoThread(e.to_string()))
-        })?;
+        recording
+            .save_with_compression(handle.output_path(), handle.compression())
+            .map_err(|e| {
+                crate::RobotError::Infrastructure(piper_driver::DriverError::IoThread(
+                    e.to_string(),
+                ))
+            })?;
 
         let stats = crate::recording::RecordingStats {
             frame_count,
@@ -1556,6 +1792,14 @@ where
             ))
         }
     }
+
+    /// 开始逐关节归零流程
+    ///
+    /// 返回的会话按 J1 → J6 逐个引导关节到零点参考位置，凭 `ZeroingConfirmToken`
+    /// 写入零点，并在结束时给出 `ZeroingReport`。详见 [`crate::control::zeroing`]。
+    pub fn begin_zeroing(&self) -> ZeroingSession<'_, Capability> {
+        ZeroingSession::new(self)
+    }
 }
 
 impl<Capability> Piper<Maintenance, Capability>
@@ -1637,6 +1881,10 @@ impl Piper<Standby, SoftRealtime> {
             config.speed_percent
         );
 
+        self.quirks.ensure_move_mode_supported(MoveMode::MoveM)?;
+
+        // 使能确认要求 0x2A1 为 Normal，进入新的 Active 前清除旧的故障锁存
+        self.driver.clear_robot_fault();
         let enable_cmd = MotorEnableCommand::enable_all();
         let enable_commit_host_mono_us = self
             .driver
@@ -1789,10 +2037,9 @@ where
     {
         use piper_protocol::control::{ControlModeCommand, ControlModeCommandFrame, MitMode};
 
-        if config.motion_type == MotionType::ContinuousPositionVelocity {
-            return Err(RobotError::ConfigError(
-                "MotionType::ContinuousPositionVelocity is not implemented yet".to_string(),
-            ));
+        self.quirks.ensure_move_mode_supported(config.motion_type.into())?;
+        if config.install_position != InstallPosition::Invalid {
+            self.quirks.ensure_feature_supported(FirmwareFeature::InstallPosition)?;
         }
 
         let move_mode: MoveMode = config.motion_type.into();
@@ -1888,6 +2135,32 @@ where
         self.observer.runtime_health()
     }
 
+    /// 当前锁存的机械臂故障状态
+    ///
+    /// 0x2A1 上报硬故障（急停、碰撞、角度超限等）后锁存，锁存期间 Active 状态下的运动命令
+    /// 返回 `DriverError::RobotFault`；重新进入 Active 或调用 [`Self::clear_robot_fault`]
+    /// 时清除。无解、奇异点等瞬时状态不会锁存。
+    pub fn robot_fault(&self) -> Option<RobotStatus> {
+        self.driver.robot_fault()
+    }
+
+    /// 清除锁存的机械臂故障
+    ///
+    /// 调用方确认故障原因已排除后调用；若机械臂仍处于故障状态，下一帧 0x2A1 会再次锁存。
+    pub fn clear_robot_fault(&self) {
+        self.driver.clear_robot_fault();
+    }
+
+    /// 注册机械臂故障回调
+    ///
+    /// 回调在 RX 线程中、故障被锁存时立即调用，必须快速返回（例如只设置一个标志位）。
+    pub fn on_fault<F>(&self, callback: F)
+    where
+        F: Fn(RobotStatus) + Send + Sync + 'static,
+    {
+        self.driver.set_on_fault(callback);
+    }
+
     fn build_validated_mit_command_batch(
         &self,
         positions: &JointArray<Rad>,
@@ -1900,7 +2173,104 @@ where
             .map(|(commands, _)| commands)
     }
 
-    fn build_validated_mit_command_batch_with_t_refs(
+    /// 按用户配置的 `SafetyLimits` 处理整批 MIT 命令（在 quirks 修正之前，使用用户坐标系数值）
+    ///
+    /// - `SafetyLimitMode::Clamp`：超限的位置/速度/力矩被限制到配置范围，并输出警告
+    /// - `SafetyLimitMode::Reject`：任一关节超限时拒绝整批命令，避免部分关节被下发
+    ///
+    /// NaN/∞ 在两种模式下都会被拒绝。
+    fn apply_safety_limits(
+        &self,
+        positions: &JointArray<Rad>,
+        velocities: &JointArray<f64>,
+        torques: &JointArray<NewtonMeter>,
+    ) -> Result<(JointArray<Rad>, JointArray<f64>, JointArray<NewtonMeter>)> {
+        for joint in Joint::ALL {
+            ensure_finite_command("MIT command", joint, "velocity", velocities[joint])?;
+            ensure_finite_command("MIT command", joint, "torque", torques[joint].0)?;
+        }
+        let positions = self.apply_position_safety_limits(positions, "MIT command")?;
+        let mut velocities = *velocities;
+        let mut torques = *torques;
+        let Some(limits) = self.safety_limits.as_deref() else {
+            return Ok((positions, velocities, torques));
+        };
+
+        for joint in Joint::ALL {
+            let index = joint.index();
+            if !limits.check_joint_velocity(index, velocities[joint]) {
+                let limit = limits.joint_velocity_limit(index).unwrap_or(f64::INFINITY);
+                if limits.mode == SafetyLimitMode::Reject {
+                    return Err(RobotError::velocity_limit(joint, velocities[joint], limit));
+                }
+                // 不用 `f64::clamp`：非法配置不能在控制循环里 panic
+                let clamped = velocities[joint].max(-limit).min(limit);
+                warn!(
+                    "safety limits: {} velocity {:.4} rad/s clamped to {:.4} rad/s",
+                    joint, velocities[joint], clamped
+                );
+                velocities[joint] = clamped;
+            }
+            if !limits.check_joint_torque(index, torques[joint].0) {
+                let limit = limits.joint_torque_limit(index).unwrap_or(f64::INFINITY);
+                if limits.mode == SafetyLimitMode::Reject {
+                    return Err(RobotError::torque_limit(
+                        joint,
+                        torques[joint].0,
+                        -limit,
+                        limit,
+                    ));
+                }
+                let clamped = torques[joint].0.max(-limit).min(limit);
+                warn!(
+                    "safety limits: {} torque {:.4} N·m clamped to {:.4} N·m",
+                    joint, torques[joint].0, clamped
+                );
+                torques[joint] = NewtonMeter(clamped);
+            }
+        }
+
+        Ok((positions, velocities, torques))
+    }
+
+    /// 按 `SafetyLimits` 的关节位置范围处理位置目标（MIT `pos_ref` 与位置模式共用）
+    ///
+    /// NaN/∞ 在两种模式下都会被拒绝；`SafetyLimitMode::Reject` 下超出范围时返回错误，
+    /// `SafetyLimitMode::Clamp` 下限制到范围边界并输出警告。
+    fn apply_position_safety_limits(
+        &self,
+        positions: &JointArray<Rad>,
+        operation: &str,
+    ) -> Result<JointArray<Rad>> {
+        for joint in Joint::ALL {
+            ensure_finite_command(operation, joint, "position", positions[joint].0)?;
+        }
+        let mut clamped = *positions;
+        let Some(limits) = self.safety_limits.as_deref() else {
+            return Ok(clamped);
+        };
+        for joint in Joint::ALL {
+            let Some((min, max)) = limits.joint_position_limits(joint.index()) else {
+                continue;
+            };
+            let value = clamped[joint].0;
+            if (min..=max).contains(&value) {
+                continue;
+            }
+            let bound = value.max(min).min(max);
+            if limits.mode == SafetyLimitMode::Reject {
+                return Err(RobotError::joint_limit(joint, value, bound));
+            }
+            warn!(
+                "{}: {} target {:.4} rad clamped to safety limit {:.4} rad",
+                operation, joint, value, bound
+            );
+            clamped[joint] = Rad(bound);
+        }
+        Ok(clamped)
+    }
+
+    pub(crate) fn build_validated_mit_command_batch_with_t_refs(
         &self,
         positions: &JointArray<Rad>,
         velocities: &JointArray<f64>,
@@ -1908,6 +2278,9 @@ where
         kd: &JointArray<f64>,
         torques: &JointArray<NewtonMeter>,
     ) -> Result<([MitControlCommand; 6], [f64; 6])> {
+        let (positions, velocities, torques) =
+            self.apply_safety_limits(positions, velocities, torques)?;
+
         let mut commands = [MitControlCommand::try_new(1, 0.0, 0.0, 0.0, 0.0, 0.0)?; 6];
         let mut t_refs = [0.0; 6];
 
@@ -2068,6 +2441,23 @@ impl<M, Capability> Piper<Active<M>, Capability>
 where
     Capability: MotionCapability,
 {
+    /// 获取夹爪控制器（物理单位，自动钳位到夹爪行程范围）
+    pub fn gripper(&self) -> GripperCommander<'_> {
+        GripperCommander::new(&self.driver)
+    }
+
+    /// 喂软件看门狗（见 `PiperBuilder::with_watchdog`）
+    ///
+    /// 控制循环每次迭代调用一次；未配置看门狗时为空操作。
+    pub fn pet_watchdog(&self) {
+        self.driver.pet_watchdog();
+    }
+
+    /// 解除看门狗计时，直到下一次 `pet_watchdog()`
+    pub fn disarm_watchdog(&self) {
+        self.driver.disarm_watchdog();
+    }
+
     /// 请求立即失能全部关节，并进入 Maintenance。
     ///
     /// 这是急停/人工接管路径：只发送 disable 请求，不伪装成已确认失能的 Standby。
@@ -2225,6 +2615,8 @@ where
     ///         notes: "Test recording".to_string(),
     ///         operator: "Alice".to_string(),
     ///     },
+    ///     mode: Default::default(),
+    ///     compression: Default::default(),
     /// })?;
     ///
     /// // 执行操作（会被录制，包含控制指令帧）
@@ -2240,8 +2632,8 @@ where
         config: crate::recording::RecordingConfig,
     ) -> Result<(Self, crate::recording::RecordingHandle)> {
         use crate::recording::{
-            ClientRecordingHook, RecordingHandle, RecordingHandleParts, RecordingStopCondition,
-            StopCondition,
+            ClientRecordingHook, RecordingHandle, RecordingHandleParts, RecordingMode,
+            RecordingStopCondition, StopCondition, StreamingRecordingWorker,
         };
 
         let stop_condition = match &config.stop_condition {
@@ -2253,13 +2645,37 @@ where
             StopCondition::FrameCount(count) => RecordingStopCondition::FrameCount(*count as u64),
         };
 
-        let (hook, rx) = ClientRecordingHook::new(stop_condition);
+        let (hook, rx) =
+            ClientRecordingHook::with_queue_capacity(stop_condition, config.mode.queue_capacity());
 
         let dropped = hook.dropped_frames().clone();
         let counter = hook.frame_counter().clone();
         let stop_requested = hook.stop_requested().clone();
         let gate = hook.gate().clone();
 
+        let start_time_unix_secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        // 流式模式：先创建文件并启动写线程，失败时不注册钩子
+        let streaming_worker = match config.mode {
+            RecordingMode::Buffered => None,
+            RecordingMode::Streaming { chunk_frames, .. } => Some(StreamingRecordingWorker::spawn(
+                rx.clone(),
+                &config.output_path,
+                &crate::recording::tools_metadata(
+                    self.driver.interface(),
+                    self.driver.bus_speed(),
+                    start_time_unix_secs,
+                    &config.metadata,
+                ),
+                chunk_frames,
+                config.compression,
+                stop_requested.clone(),
+            )?),
+        };
+
         // 注册钩子
         let callback = std::sync::Arc::new(hook) as std::sync::Arc<dyn piper_driver::FrameCallback>;
         let hook_manager = self.driver.hooks();
@@ -2277,14 +2693,13 @@ where
             stop_requested,
             gate,
             output_path: config.output_path.clone(),
+            compression: config.compression,
             metadata: config.metadata.clone(),
-            start_time_unix_secs: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs(),
+            start_time_unix_secs,
             start_time: std::time::Instant::now(),
             hook_manager,
             hook_handle,
+            streaming_worker,
         });
 
         tracing::info!("Recording started (Active): {:?}", config.output_path);
@@ -2326,7 +2741,7 @@ where
     /// ```
     pub fn stop_recording(
         self,
-        handle: crate::recording::RecordingHandle,
+        mut handle: crate::recording::RecordingHandle,
     ) -> Result<(Self, crate::recording::RecordingStats)> {
         use piper_tools::{
             PiperRecording, RecordedFrameDirection as ToolsRecordedFrameDirection, TimestampedFrame,
@@ -2335,6 +2750,18 @@ where
         handle.stop();
         handle.detach_hook();
 
+        // 流式模式：帧已在写盘，等待写线程收尾即可
+        if let Some(result) = handle.finish_streaming() {
+            let stats = result?;
+            tracing::info!(
+                "Recording streamed: {} frames, {:.2}s, {} dropped",
+                stats.frame_count,
+                stats.duration.as_secs_f64(),
+                stats.dropped_frames
+            );
+            return Ok((self, stats));
+        }
+
         // 创建录制对象
         let mut recording = PiperRecording::new(piper_tools::RecordingMetadata::new(
             self.driver.interface(),
@@ -2366,9 +2793,13 @@ where
         }
 
         // 保存文件
-        recording.save(handle.output_path()).map_err(|e| {
-            crate::RobotError::Infrastructure(piper_driver::DriverError::IoThread(e.to_string()))
-        })?;
+        recording
+            .save_with_compression(handle.output_path(), handle.compression())
+            .map_err(|e| {
+                crate::RobotError::Infrastructure(piper_driver::DriverError::IoThread(
+                    e.to_string(),
+                ))
+            })?;
 
         let stats = crate::recording::RecordingStats {
             frame_count,
@@ -2479,7 +2910,9 @@ where
         let raw = RawCommander::new(&self.driver);
         let commands =
             self.build_validated_mit_command_batch(positions, velocities, kp, kd, torques)?;
-        raw.send_validated_mit_command_batch(commands)
+        raw.send_validated_mit_command_batch(commands)?;
+        self.record_mit_command(positions, kp, kd, torques);
+        Ok(())
     }
 
     /// 发送 MIT 模式控制指令，并等待 TX 线程确认实际发送结果。
@@ -2495,7 +2928,9 @@ where
         let raw = RawCommander::new(&self.driver);
         let commands =
             self.build_validated_mit_command_batch(positions, velocities, kp, kd, torques)?;
-        raw.send_validated_mit_command_batch_confirmed(commands, timeout)
+        raw.send_validated_mit_command_batch_confirmed(commands, timeout)?;
+        self.record_mit_command(positions, kp, kd, torques);
+        Ok(())
     }
 
     /// 发送 MIT 模式控制指令，等待 strict-realtime TX 线程完成整包发送，并返回批命令回执。
@@ -2521,6 +2956,7 @@ where
         )?;
         let tx_finished =
             raw.send_validated_mit_command_batch_confirmed_finished(commands, timeout)?;
+        self.record_mit_command(positions, kp, kd, torques);
 
         Ok(ConfirmedMitBatch {
             tx_finished,
@@ -2580,6 +3016,152 @@ where
         self.set_gripper(0.0, effort)
     }
 
+    /// 不失能直接切换到位置模式
+    ///
+    /// 1. 在 `ramp_duration` 内从上一批 MIT 命令线性过渡到以 `hold_kp`/`hold_kd` 保持当前位置，
+    ///    同时把前馈力矩降到 0；
+    /// 2. 下发 0x151（`CanControl` + 位置模式），等待 0x2A1 机器人状态确认；
+    /// 3. `MotionType::Joint` 时立即以当前位置作为首个位置目标，避免切换后跳回旧目标。
+    ///
+    /// 任一步失败时返回原 `Active<MitMode>` 与错误；若 0x151 已发出，会先 best-effort
+    /// 重新下发 MIT 模式的 0x151。
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// # use piper_client::state::*;
+    /// # fn example(robot: Piper<Active<MitMode>>) -> Result<()> {
+    /// let robot = match robot.switch_to_position(MitToPositionConfig::default()) {
+    ///     Ok(robot) => robot, // Piper<Active<PositionMode>>
+    ///     Err((robot, error)) => return Err(error), // robot 仍是 Active<MitMode>
+    /// };
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::result_large_err)]
+    pub fn switch_to_position(
+        self,
+        config: MitToPositionConfig,
+    ) -> std::result::Result<Piper<Active<PositionMode>, Capability>, (Self, RobotError)> {
+        debug!(
+            "Switching MIT -> Position mode (motion_type={:?}, speed_percent={})",
+            config.position.motion_type, config.position.speed_percent
+        );
+
+        if let Err(error) = config.position.validate() {
+            return Err((self, error));
+        }
+
+        let hold_positions = match self.ramp_down_feedforward_torque(&config) {
+            Ok(positions) => positions,
+            Err(error) => return Err((self, error)),
+        };
+
+        if let Err(error) = self.apply_position_mode_control_config(&config.position) {
+            self.restore_mit_control_mode(config.position.timeout);
+            return Err((self, error));
+        }
+
+        if config.position.motion_type == MotionType::Joint {
+            let raw = RawCommander::new(&self.driver);
+            if let Err(error) =
+                raw.send_position_command_batch(&hold_positions, config.position.command_timeout)
+            {
+                self.restore_mit_control_mode(config.position.timeout);
+                return Err((self, error));
+            }
+        }
+
+        info!("Robot switched - Active<PositionMode>");
+        Ok(transition_piper_state(
+            self,
+            Active(PositionMode {
+                command_timeout: config.position.command_timeout,
+                motion_type: config.position.motion_type,
+                joint_limits: config.position.joint_limits,
+                speed_percent: AtomicU8::new(config.position.speed_percent),
+            }),
+            DropPolicy::DisableAll,
+            DriverModeDropPolicy::Preserve,
+        ))
+    }
+
+    /// 记录已提交的 MIT 批命令。
+    ///
+    /// 记录的是安全限幅前的用户输入：降力矩时重新经过同一套限幅，
+    /// Clamp 模式下得到与原批次相同的下发值，Reject 模式下被拒的批次不会被记录。
+    fn record_mit_command(
+        &self,
+        positions: &JointArray<Rad>,
+        kp: &JointArray<f64>,
+        kd: &JointArray<f64>,
+        torques: &JointArray<NewtonMeter>,
+    ) {
+        self._state
+            .0
+            .record_command(MitCommandSnapshot::new(positions, kp, kd, torques));
+    }
+
+    /// 从最近一次下发的 MIT 批命令出发，线性过渡到当前位置的保持命令，返回保持位置
+    ///
+    /// 位置目标与 Kp/Kd 在 `ramp_duration` 内从上一批命令插值到当前位置与
+    /// `hold_kp`/`hold_kd`，前馈力矩同步降到 0，避免增益或力矩在单个周期内跳变。
+    /// 会话内尚未下发过 MIT 命令时，直接以保持增益、零前馈起步。
+    fn ramp_down_feedforward_torque(
+        &self,
+        config: &MitToPositionConfig,
+    ) -> Result<JointArray<Rad>> {
+        let hold_positions = self.observer.joint_positions()?;
+        let start = self._state.0.last_command().unwrap_or(MitCommandSnapshot {
+            positions: hold_positions,
+            kp: config.hold_kp,
+            kd: config.hold_kd,
+            torques: JointArray::splat(NewtonMeter(0.0)),
+        });
+        let zero_velocities = JointArray::splat(0.0);
+        let period = config.ramp_period.max(Duration::from_millis(1));
+        let steps = (config.ramp_duration.as_micros() / period.as_micros()).max(1) as u32;
+
+        for step in 1..=steps {
+            let alpha = f64::from(step) / f64::from(steps);
+            self.command_torques_confirmed(
+                &(start.positions + (hold_positions - start.positions) * alpha),
+                &zero_velocities,
+                &(start.kp + (config.hold_kp - start.kp) * alpha),
+                &(start.kd + (config.hold_kd - start.kd) * alpha),
+                &(start.torques * (1.0 - alpha)),
+                config.position.timeout,
+            )?;
+            if step < steps {
+                std::thread::sleep(period);
+            }
+        }
+        Ok(hold_positions)
+    }
+
+    /// best-effort 按使能时的速度重新下发 MIT 模式的 0x151（切换失败回滚用，不等待确认）
+    fn restore_mit_control_mode(&self, timeout: Duration) {
+        use piper_protocol::control::{ControlModeCommand, ControlModeCommandFrame};
+
+        let control_cmd = ControlModeCommandFrame::new(
+            ControlModeCommand::CanControl,
+            MoveMode::MoveM,
+            self._state.0.speed_percent,
+            ProtocolMitMode::Mit,
+            0,
+            InstallPosition::Invalid,
+        );
+        if let Err(error) = self
+            .driver
+            .send_reliable_frame_confirmed_commit_marker(control_cmd.to_frame(), timeout)
+        {
+            warn!(
+                "Failed to restore MIT control mode after switch failure: {}",
+                error
+            );
+        }
+    }
+
     /// 获取 Observer（只读）
     pub fn observer(&self) -> &Observer<Capability> {
         &self.observer
@@ -2734,36 +3316,269 @@ where
             effective_config.speed_percent,
             effective_config.install_position
         );
-        self.apply_position_mode_control_config(&effective_config)
+        self.apply_position_mode_control_config(&effective_config)?;
+        position_mode
+            .speed_percent
+            .store(effective_config.speed_percent, Ordering::Release);
+        Ok(())
     }
 
-    /// 发送位置命令（批量发送所有关节）
+    /// 设置安装位置（0x151 Byte 5），并等待控制器确认
     ///
-    /// 一次性发送所有 6 个关节的目标位置。
+    /// 沿用当前位置模式的运动类型和速度百分比重新下发 0x151，随后回读 0x2A1
+    /// 机器人状态确认控制器已接受该指令；收到 0x151 回显时还会校验回显中的安装位置。
     ///
-    /// # 参数
+    /// # 错误
     ///
-    /// - `positions`: 各关节目标位置
+    /// - `RobotError::ConfigError`: `InstallPosition::Invalid` 不是可设置的安装方向
+    /// - `RobotError::Infrastructure(DriverError::UnsupportedByFirmware)`: 固件早于 V1.5-2
+    /// - `RobotError::Timeout`: 控制器未在超时内确认
     ///
     /// # 示例
     ///
     /// ```rust,ignore
     /// # use piper_client::state::*;
-    /// # use piper_client::types::*;
+    /// # use piper_protocol::control::InstallPosition;
     /// # fn example(robot: Piper<Active<PositionMode>>) -> Result<()> {
-    /// let positions = JointArray::from([
-    ///     Rad(1.0), Rad(0.5), Rad(0.0), Rad(0.0), Rad(0.0), Rad(0.0)
-    /// ]);
-    /// robot.send_position_command(&positions)?;
+    /// robot.set_install_position(InstallPosition::SideLeft)?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn send_position_command(&self, positions: &JointArray<Rad>) -> Result<()> {
-        let position_mode =
-            self.ensure_position_motion_type(MotionType::Joint, "send_position_command")?;
-        let raw = RawCommander::new(&self.driver);
-        raw.send_position_command_batch(positions, position_mode.command_timeout)
-    }
+    pub fn set_install_position(&self, install_position: InstallPosition) -> Result<()> {
+        if install_position == InstallPosition::Invalid {
+            return Err(RobotError::ConfigError(
+                "InstallPosition::Invalid is not a mounting orientation; use Horizontal, SideLeft or SideRight"
+                    .to_string(),
+            ));
+        }
+
+        let position_mode = &self._state.0;
+        let config = PositionModeConfig {
+            speed_percent: position_mode.speed_percent.load(Ordering::Acquire),
+            install_position,
+            motion_type: position_mode.motion_type,
+            command_timeout: position_mode.command_timeout,
+            ..PositionModeConfig::default()
+        };
+
+        debug!("Setting install position to {:?}", install_position);
+        self.apply_position_mode_control_config(&config)?;
+        info!("Install position confirmed: {:?}", install_position);
+        Ok(())
+    }
+
+    /// 发送位置命令（批量发送所有关节）
+    ///
+    /// 一次性发送所有 6 个关节的目标位置。
+    ///
+    /// # 参数
+    ///
+    /// - `positions`: 各关节目标位置
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// # use piper_client::state::*;
+    /// # use piper_client::types::*;
+    /// # fn example(robot: Piper<Active<PositionMode>>) -> Result<()> {
+    /// let positions = JointArray::from([
+    ///     Rad(1.0), Rad(0.5), Rad(0.0), Rad(0.0), Rad(0.0), Rad(0.0)
+    /// ]);
+    /// robot.send_position_command(&positions)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn send_position_command(&self, positions: &JointArray<Rad>) -> Result<()> {
+        let position_mode =
+            self.ensure_position_motion_type(MotionType::Joint, "send_position_command")?;
+        let positions = self.clamp_position_target(positions, "send_position_command")?;
+        let raw = RawCommander::new(&self.driver);
+        raw.send_position_command_batch(&positions, position_mode.command_timeout)
+    }
+
+    /// 获取 MoveCpv 轨迹流下发器
+    ///
+    /// # 错误
+    ///
+    /// - `RobotError::ConfigError`: 当前不是 `MotionType::ContinuousPositionVelocity`，
+    ///   或 `config` 的周期 / 缓冲区容量为 0
+    pub fn trajectory_streamer(
+        &self,
+        config: TrajectoryStreamConfig,
+    ) -> Result<TrajectoryStreamer<'_, Capability>> {
+        self.ensure_position_motion_type(
+            MotionType::ContinuousPositionVelocity,
+            "trajectory_streamer",
+        )?;
+        TrajectoryStreamer::new(self, config)
+    }
+
+    /// 下发 MoveCpv 设定点的位置部分（0x155-0x157）
+    pub(crate) fn send_cpv_position_target(&self, positions: &JointArray<Rad>) -> Result<()> {
+        let position_mode = self.ensure_position_motion_type(
+            MotionType::ContinuousPositionVelocity,
+            "trajectory streaming",
+        )?;
+        let positions = self.clamp_position_target(positions, "trajectory streaming")?;
+        let raw = RawCommander::new(&self.driver);
+        raw.send_position_command_batch(&positions, position_mode.command_timeout)
+    }
+
+    /// 按 `PositionModeConfig::joint_limits` 与 `SafetyLimits` 位置范围限制关节目标
+    ///
+    /// 超限关节输出警告；`SafetyLimitMode::Reject` 下超出 `SafetyLimits` 范围、
+    /// 或目标为 NaN/∞ 时返回错误。
+    fn clamp_position_target(
+        &self,
+        positions: &JointArray<Rad>,
+        operation: &str,
+    ) -> Result<JointArray<Rad>> {
+        let mut clamped = *positions;
+        if let Some(limits) = &self._state.0.joint_limits {
+            let out_of_range;
+            (clamped, out_of_range) = positions.clamp_to_limits_checked(limits);
+            for joint in out_of_range {
+                warn!(
+                    "{}: {} target {:.4} rad clamped to {:.4} rad",
+                    operation, joint, positions[joint].0, clamped[joint].0
+                );
+            }
+        }
+
+        self.apply_position_safety_limits(&clamped, operation)
+    }
+
+    /// 默认到位容差（约 0.57°）
+    pub const DEFAULT_ARRIVAL_TOLERANCE: Rad = Rad(0.01);
+
+    /// 发送关节位置命令并阻塞等待到位
+    ///
+    /// 到位条件：命令发出后收到的 0x2A1 上报 `MotionStatus::Arrived`，且各关节位置与
+    /// 目标的偏差不超过 [`Self::DEFAULT_ARRIVAL_TOLERANCE`]。
+    ///
+    /// **前提条件**：必须使用 `MotionType::Joint` 配置。
+    ///
+    /// # 错误
+    ///
+    /// - `MotionError::Timeout`：超时前未到位
+    /// - `MotionError::Fault`：等待期间机械臂状态不是 `RobotStatus::Normal`
+    /// - `MotionError::Robot`：命令发送失败或运行时健康异常
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// # use piper_client::state::*;
+    /// # use piper_client::types::*;
+    /// # use std::time::Duration;
+    /// # fn example(robot: Piper<Active<PositionMode>>) -> std::result::Result<(), MotionError> {
+    /// let target = JointArray::splat(Rad(0.0));
+    /// robot.move_and_wait(&target, Duration::from_secs(5))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn move_and_wait(
+        &self,
+        target: &JointArray<Rad>,
+        timeout: Duration,
+    ) -> std::result::Result<(), MotionError> {
+        self.move_and_wait_with_tolerance(target, Self::DEFAULT_ARRIVAL_TOLERANCE, timeout)
+    }
+
+    /// 同 [`Self::move_and_wait`]，使用自定义到位容差
+    pub fn move_and_wait_with_tolerance(
+        &self,
+        target: &JointArray<Rad>,
+        tolerance: Rad,
+        timeout: Duration,
+    ) -> std::result::Result<(), MotionError> {
+        if !(tolerance.0.is_finite() && tolerance.0 >= 0.0) {
+            return Err(RobotError::ConfigError(format!(
+                "Arrival tolerance must be finite and non-negative, got {}",
+                tolerance.0
+            ))
+            .into());
+        }
+
+        let position_mode = self.ensure_position_motion_type(MotionType::Joint, "move_and_wait")?;
+        let target = &self.clamp_position_target(target, "move_and_wait")?;
+        let start = Instant::now();
+        let commit_host_mono_us = RawCommander::new(&self.driver)
+            .send_position_command_batch_commit_marker(target, position_mode.command_timeout)?;
+
+        loop {
+            self.ensure_runtime_health_healthy()?;
+
+            let control = self.driver.get_robot_control();
+            if control.host_rx_mono_us > commit_host_mono_us {
+                let status = RobotStatus::from(control.robot_status);
+                if status != RobotStatus::Normal {
+                    warn!("Robot reported {:?} while waiting for arrival", status);
+                    return Err(MotionError::Fault(status));
+                }
+
+                if control.motion_status == MotionStatus::Arrived as u8
+                    && let Ok(positions) = self.observer().joint_positions()
+                    && Joint::ALL
+                        .iter()
+                        .all(|&joint| (positions[joint].0 - target[joint].0).abs() <= tolerance.0)
+                {
+                    debug!("Arrived at target after {:?}", start.elapsed());
+                    return Ok(());
+                }
+            }
+
+            let remaining = timeout.saturating_sub(start.elapsed());
+            if remaining.is_zero() {
+                return Err(MotionError::Timeout {
+                    timeout_ms: timeout.as_millis() as u64,
+                });
+            }
+            std::thread::sleep(MOTION_ARRIVAL_POLL_INTERVAL.min(remaining));
+        }
+    }
+
+    /// 回放拖动示教轨迹
+    ///
+    /// 先以 `approach_timeout` 为超时移动到轨迹起点并等待到位，再按录制时的时间间隔
+    /// 逐点下发关节位置目标。位置目标按 `PositionModeConfig::joint_limits` 钳位。
+    ///
+    /// **前提条件**：必须使用 `MotionType::Joint` 配置。
+    ///
+    /// # 错误
+    ///
+    /// - `MotionError::Robot(RobotError::ConfigError)`：轨迹为空或运动类型不是 `Joint`
+    /// - 其余同 [`Self::move_and_wait`]
+    pub fn play_teach(
+        &self,
+        trajectory: &TeachTrajectory,
+        approach_timeout: Duration,
+    ) -> std::result::Result<(), MotionError> {
+        let position_mode = self.ensure_position_motion_type(MotionType::Joint, "play_teach")?;
+        let Some((first, rest)) = trajectory.samples().split_first() else {
+            return Err(RobotError::ConfigError("teach trajectory is empty".to_string()).into());
+        };
+
+        self.move_and_wait(&first.position, approach_timeout)?;
+        debug!(
+            "Replaying teach trajectory ({} samples, {:?})",
+            trajectory.len(),
+            trajectory.duration()
+        );
+
+        let raw = RawCommander::new(&self.driver);
+        let start = Instant::now();
+        for sample in rest {
+            let due = start + (sample.elapsed - first.elapsed);
+            let now = Instant::now();
+            if now < due {
+                std::thread::sleep(due - now);
+            }
+            self.ensure_runtime_health_healthy()?;
+            let target = self.clamp_position_target(&sample.position, "play_teach")?;
+            raw.send_position_command_batch(&target, position_mode.command_timeout)?;
+        }
+        Ok(())
+    }
 
     /// 发送末端位姿命令（笛卡尔空间控制）
     ///
@@ -2832,6 +3647,49 @@ where
         raw.send_end_pose_command(position, orientation, position_mode.command_timeout)
     }
 
+    /// 运动到目标末端位姿（基于逆运动学）
+    ///
+    /// 以当前关节位置为初值求解逆运动学，确认目标可达后再下发：
+    /// - `MotionType::Joint`（MoveJ）：下发逆解得到的关节角
+    /// - `MotionType::Cartesian`（MoveP）：下发末端位姿，由控制器规划
+    ///
+    /// 返回逆解得到的关节角。其他运动类型返回 `RobotError::ConfigError`；
+    /// 目标不可达时返回 `RobotError::Kinematics`，不会下发任何指令。
+    ///
+    /// # 示例
+    ///
+    /// ```rust,ignore
+    /// let target = CartesianPose::from_position_euler(0.25, 0.0, 0.2, Rad(0.0), Rad(1.57), Rad(0.0));
+    /// let joints = robot.move_to_pose(target)?;
+    /// ```
+    pub fn move_to_pose(&self, target: CartesianPose) -> Result<JointArray<Rad>> {
+        let motion_type = self._state.0.motion_type;
+        if !matches!(motion_type, MotionType::Joint | MotionType::Cartesian) {
+            return Err(RobotError::ConfigError(format!(
+                "move_to_pose requires MotionType::Joint or MotionType::Cartesian, but this PositionMode is MotionType::{motion_type:?}"
+            )));
+        }
+
+        let seed = self.observer.joint_positions()?.into_array();
+        let joints = JointArray::from(PiperKinematics::default().inverse(&target, &seed)?);
+
+        if motion_type == MotionType::Joint {
+            self.send_position_command(&joints)?;
+        } else {
+            let (roll, pitch, yaw) = target.orientation.to_euler();
+            self.command_cartesian_pose(
+                target.position,
+                EulerAngles::new(
+                    roll.0.to_degrees(),
+                    pitch.0.to_degrees(),
+                    yaw.0.to_degrees(),
+                ),
+            )?;
+        }
+
+        Ok(joints)
+    }
+
     /// 发送圆弧运动命令
     ///
     /// 末端沿圆弧轨迹运动，需要指定中间点和终点。
@@ -2994,6 +3852,86 @@ where
 
 // ==================== ReplayMode 状态 ====================
 
+/// 回放速度倍数上限
+const REPLAY_MAX_SPEED_FACTOR: f64 = 5.0;
+/// 回放推荐速度倍数上限
+const REPLAY_RECOMMENDED_SPEED_FACTOR: f64 = 2.0;
+
+/// 回放选项
+///
+/// 与 `replay_recording_with_cancel` 使用同一套时间调度：相邻 TX 帧的录制间隔按
+/// `speed_factor` 缩放。循环回放时，每一轮的首帧紧接上一轮末帧发送，
+/// 因此录制的首尾姿态应当一致。
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayOptions {
+    /// 速度倍数（1.0 = 原始速度，最大 5.0）
+    pub speed_factor: f64,
+    /// 回放轮数（至少 1）
+    pub loop_count: u32,
+    /// 只回放这些 CAN ID 的 TX 帧（`None` = 全部 TX 帧）
+    pub id_filter: Option<Vec<u32>>,
+}
+
+impl Default for ReplayOptions {
+    fn default() -> Self {
+        Self {
+            speed_factor: 1.0,
+            loop_count: 1,
+            id_filter: None,
+        }
+    }
+}
+
+impl ReplayOptions {
+    /// 以指定速度倍数创建（单轮、不过滤）
+    pub fn new(speed_factor: f64) -> Self {
+        Self {
+            speed_factor,
+            ..Self::default()
+        }
+    }
+
+    /// 设置回放轮数
+    pub fn loop_count(mut self, loop_count: u32) -> Self {
+        self.loop_count = loop_count;
+        self
+    }
+
+    /// 只回放指定 CAN ID 的 TX 帧
+    pub fn id_filter(mut self, ids: impl IntoIterator<Item = u32>) -> Self {
+        self.id_filter = Some(ids.into_iter().collect());
+        self
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.speed_factor.is_nan() || self.speed_factor <= 0.0 {
+            return Err(crate::RobotError::InvalidParameter {
+                param: "speed_factor".to_string(),
+                reason: "must be positive".to_string(),
+            });
+        }
+        if self.speed_factor > REPLAY_MAX_SPEED_FACTOR {
+            return Err(crate::RobotError::InvalidParameter {
+                param: "speed_factor".to_string(),
+                reason: format!("exceeds maximum {}", REPLAY_MAX_SPEED_FACTOR),
+            });
+        }
+        if self.loop_count == 0 {
+            return Err(crate::RobotError::InvalidParameter {
+                param: "loop_count".to_string(),
+                reason: "must be at least 1".to_string(),
+            });
+        }
+        if self.id_filter.as_ref().is_some_and(Vec::is_empty) {
+            return Err(crate::RobotError::InvalidParameter {
+                param: "id_filter".to_string(),
+                reason: "must contain at least one CAN ID".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
 struct ReplayScheduleItem<'a> {
     file_index: usize,
     recorded: &'a piper_tools::TimestampedFrame,
@@ -3056,12 +3994,14 @@ where
     fn build_replay_schedule<'a>(
         recording: &'a piper_tools::PiperRecording,
         speed_factor: f64,
+        id_filter: Option<&[u32]>,
     ) -> Result<Vec<ReplayScheduleItem<'a>>> {
         let selected_tx: Vec<_> = recording
             .frames
             .iter()
             .enumerate()
             .filter(|(_, frame)| frame.direction == piper_tools::RecordedFrameDirection::Tx)
+            .filter(|(_, frame)| id_filter.is_none_or(|ids| ids.contains(&frame.frame.raw_id())))
             .collect();
 
         let Some((first_index, first_tx)) = selected_tx.first() else {
@@ -3212,7 +4152,7 @@ where
 
         // === 回放帧序列 ===
 
-        let schedule = Self::build_replay_schedule(&recording, speed_factor)?;
+        let schedule = Self::build_replay_schedule(&recording, speed_factor, None)?;
         if schedule.is_empty() {
             tracing::warn!("Recording file has no TX frames to replay");
             return Ok(self.exit_replay_mode_to_standby());
@@ -3317,6 +4257,28 @@ where
         recording_path: impl AsRef<std::path::Path>,
         speed_factor: f64,
         cancel_signal: &std::sync::atomic::AtomicBool,
+    ) -> Result<Piper<Standby, Capability>> {
+        self.replay_recording_with_options(
+            recording_path,
+            &ReplayOptions::new(speed_factor),
+            cancel_signal,
+        )
+    }
+
+    /// 按 [`ReplayOptions`] 回放录制（带取消支持）
+    ///
+    /// 在 `replay_recording_with_cancel` 的基础上支持循环回放与 CAN ID 过滤；
+    /// 取消语义相同：`cancel_signal` 变为 `false` 后在下一帧前停止并返回 Standby。
+    ///
+    /// # 错误
+    ///
+    /// - `RobotError::InvalidParameter`：速度倍数越界、`loop_count` 为 0 或过滤列表为空
+    /// - `RobotError::ConfigError`：选中的 TX 帧时间戳为 0 或倒退
+    pub fn replay_recording_with_options(
+        self,
+        recording_path: impl AsRef<std::path::Path>,
+        options: &ReplayOptions,
+        cancel_signal: &std::sync::atomic::AtomicBool,
     ) -> Result<Piper<Standby, Capability>> {
         use piper_tools::PiperRecording;
         use std::time::Duration;
@@ -3324,37 +4286,23 @@ where
 
         // === 安全检查 ===
 
-        // 速度限制验证
-        const MAX_SPEED_FACTOR: f64 = 5.0;
-        const RECOMMENDED_SPEED_FACTOR: f64 = 2.0;
-
-        if speed_factor <= 0.0 {
-            return Err(crate::RobotError::InvalidParameter {
-                param: "speed_factor".to_string(),
-                reason: "must be positive".to_string(),
-            });
-        }
-
-        if speed_factor > MAX_SPEED_FACTOR {
-            return Err(crate::RobotError::InvalidParameter {
-                param: "speed_factor".to_string(),
-                reason: format!("exceeds maximum {}", MAX_SPEED_FACTOR),
-            });
-        }
+        options.validate()?;
+        let speed_factor = options.speed_factor;
 
-        if speed_factor > RECOMMENDED_SPEED_FACTOR {
+        if speed_factor > REPLAY_RECOMMENDED_SPEED_FACTOR {
             tracing::warn!(
                 "Speed factor {} exceeds recommended limit {}. \
                  Ensure safe environment and emergency stop ready.",
                 speed_factor,
-                RECOMMENDED_SPEED_FACTOR
+                REPLAY_RECOMMENDED_SPEED_FACTOR
             );
         }
 
         tracing::info!(
-            "Starting replay (with cancel support): file={:?}, speed={:.2}x",
+            "Starting replay (with cancel support): file={:?}, speed={:.2}x, loops={}",
             recording_path.as_ref(),
-            speed_factor
+            speed_factor,
+            options.loop_count
         );
 
         // === 加载录制文件 ===
@@ -3376,51 +4324,58 @@ where
 
         // === 回放帧序列（带取消检查） ===
 
-        let schedule = Self::build_replay_schedule(&recording, speed_factor)?;
+        let schedule =
+            Self::build_replay_schedule(&recording, speed_factor, options.id_filter.as_deref())?;
         if schedule.is_empty() {
             tracing::warn!("Recording file has no TX frames to replay");
             return Ok(self.exit_replay_mode_to_standby());
         }
 
-        for item in schedule {
-            // ✅ 每一帧都检查取消信号
-            if Self::replay_cancel_requested(cancel_signal) {
-                tracing::warn!("Replay cancelled by user signal");
-                return Ok(self.exit_replay_mode_to_standby());
-            }
-
-            // 等待适当的延迟
-            if !item.delay.is_zero()
-                && !Self::wait_replay_delay_or_cancel(item.delay, cancel_signal)
-            {
-                tracing::warn!("Replay cancelled by user signal");
-                return Ok(self.exit_replay_mode_to_standby());
+        for iteration in 1..=options.loop_count {
+            if options.loop_count > 1 {
+                tracing::info!("Replay loop {}/{}", iteration, options.loop_count);
             }
 
-            if Self::replay_cancel_requested(cancel_signal) {
-                tracing::warn!("Replay cancelled by user signal");
-                return Ok(self.exit_replay_mode_to_standby());
-            }
+            for item in &schedule {
+                // ✅ 每一帧都检查取消信号
+                if Self::replay_cancel_requested(cancel_signal) {
+                    tracing::warn!("Replay cancelled by user signal");
+                    return Ok(self.exit_replay_mode_to_standby());
+                }
 
-            // 发送帧
-            let piper_frame = Self::recording_frame_to_piper_frame(item.recorded)?;
+                // 等待适当的延迟
+                if !item.delay.is_zero()
+                    && !Self::wait_replay_delay_or_cancel(item.delay, cancel_signal)
+                {
+                    tracing::warn!("Replay cancelled by user signal");
+                    return Ok(self.exit_replay_mode_to_standby());
+                }
 
-            self.driver
-                .send_replay_frame_confirmed(piper_frame, REPLAY_FRAME_COMMIT_TIMEOUT)
-                .map_err(|e| {
-                    crate::RobotError::Infrastructure(piper_driver::DriverError::IoThread(
-                        e.to_string(),
-                    ))
-                })?;
+                if Self::replay_cancel_requested(cancel_signal) {
+                    tracing::warn!("Replay cancelled by user signal");
+                    return Ok(self.exit_replay_mode_to_standby());
+                }
 
-            // 跟踪进度（每 1000 帧打印一次）
-            let timestamp = item.recorded.frame.timestamp_us();
-            if timestamp % 1_000_000 < 1000 {
-                trace!(
-                    "Replayed frame {} at {:.3}s",
-                    item.file_index,
-                    timestamp as f64 / 1_000_000.0
-                );
+                // 发送帧
+                let piper_frame = Self::recording_frame_to_piper_frame(item.recorded)?;
+
+                self.driver
+                    .send_replay_frame_confirmed(piper_frame, REPLAY_FRAME_COMMIT_TIMEOUT)
+                    .map_err(|e| {
+                        crate::RobotError::Infrastructure(piper_driver::DriverError::IoThread(
+                            e.to_string(),
+                        ))
+                    })?;
+
+                // 跟踪进度（每 1000 帧打印一次）
+                let timestamp = item.recorded.frame.timestamp_us();
+                if timestamp % 1_000_000 < 1000 {
+                    trace!(
+                        "Replayed frame {} at {:.3}s",
+                        item.file_index,
+                        timestamp as f64 / 1_000_000.0
+                    );
+                }
             }
         }
 
@@ -4018,9 +4973,11 @@ mod tests {
             driver,
             observer,
             quirks,
+            safety_limits: None,
+            enable_timeout: None,
             drop_policy: DropPolicy::DisableAll,
             driver_mode_drop_policy: DriverModeDropPolicy::Preserve,
-            _state: Active(MitMode),
+            _state: Active(MitMode::new(MitModeConfig::default().speed_percent)),
         }
     }
 
@@ -4040,11 +4997,15 @@ mod tests {
             driver,
             observer,
             quirks: DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+            safety_limits: None,
+            enable_timeout: None,
             drop_policy: DropPolicy::DisableAll,
             driver_mode_drop_policy: DriverModeDropPolicy::Preserve,
             _state: Active(PositionMode {
                 command_timeout: Duration::from_millis(20),
                 motion_type,
+                joint_limits: None,
+                speed_percent: AtomicU8::new(50),
             }),
         }
     }
@@ -4081,6 +5042,8 @@ mod tests {
             driver,
             observer,
             quirks: DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+            safety_limits: None,
+            enable_timeout: None,
             drop_policy: DropPolicy::Noop,
             driver_mode_drop_policy: DriverModeDropPolicy::Preserve,
             _state: Standby,
@@ -4105,6 +5068,8 @@ mod tests {
             driver,
             observer,
             quirks: DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+            safety_limits: None,
+            enable_timeout: None,
             drop_policy: DropPolicy::Noop,
             driver_mode_drop_policy: DriverModeDropPolicy::Preserve,
             _state: Standby,
@@ -4132,6 +5097,8 @@ mod tests {
             driver,
             observer,
             quirks: DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+            safety_limits: None,
+            enable_timeout: None,
             drop_policy: DropPolicy::Noop,
             driver_mode_drop_policy: DriverModeDropPolicy::Preserve,
             _state: Standby,
@@ -4350,19 +5317,20 @@ mod tests {
         // 大部分状态类型是 ZST（零大小类型）
         assert_eq!(std::mem::size_of::<Disconnected>(), 0);
         assert_eq!(std::mem::size_of::<Standby>(), 0);
-        assert_eq!(std::mem::size_of::<MitMode>(), 0);
         assert_eq!(std::mem::size_of::<ErrorState>(), 0);
 
-        // Active<MitMode> 包含 MitMode（ZST），所以也是 ZST
-        assert_eq!(std::mem::size_of::<Active<MitMode>>(), 0);
+        assert_eq!(
+            std::mem::size_of::<Active<MitMode>>(),
+            std::mem::size_of::<MitMode>()
+        );
 
         assert_eq!(
             std::mem::size_of::<PositionMode>(),
-            std::mem::size_of::<(Duration, MotionType)>()
+            std::mem::size_of::<(Duration, MotionType, Option<JointLimits>)>()
         );
         assert_eq!(
             std::mem::size_of::<Active<PositionMode>>(),
-            std::mem::size_of::<(Duration, MotionType)>()
+            std::mem::size_of::<(Duration, MotionType, Option<JointLimits>)>()
         );
     }
 
@@ -4452,6 +5420,7 @@ mod tests {
                 install_position: InstallPosition::Invalid,
                 motion_type: MotionType::Joint,
                 command_timeout: Duration::from_millis(20),
+                joint_limits: None,
             })
             .expect(
                 "fresh matching 0x2A1 should allow Active<PositionMode> even without 0x151 echo",
@@ -4488,6 +5457,7 @@ mod tests {
                 install_position: InstallPosition::Invalid,
                 motion_type: MotionType::Joint,
                 command_timeout: Duration::from_millis(20),
+                joint_limits: None,
             })
             .expect("matching robot status should be sufficient when no 0x151 echo is observable");
 
@@ -4537,6 +5507,7 @@ mod tests {
                 install_position: InstallPosition::Invalid,
                 motion_type: MotionType::Joint,
                 command_timeout: Duration::from_millis(20),
+                joint_limits: None,
             })
             .expect("matching 0x2A1 should allow Active<PositionMode>");
 
@@ -4549,6 +5520,7 @@ mod tests {
                 install_position: InstallPosition::SideLeft,
                 motion_type: MotionType::Joint,
                 command_timeout: Duration::from_millis(20),
+                joint_limits: None,
             })
             .expect("borrowed Active<PositionMode> should confirm a fresh 0x151 update");
 
@@ -4609,6 +5581,7 @@ mod tests {
                 install_position: InstallPosition::Invalid,
                 motion_type: MotionType::Joint,
                 command_timeout: Duration::from_millis(75),
+                joint_limits: None,
             })
             .expect(
                 "matching 0x2A1 should allow Active<PositionMode> with non-default command_timeout",
@@ -4623,6 +5596,7 @@ mod tests {
                 install_position: InstallPosition::SideRight,
                 motion_type: MotionType::Joint,
                 command_timeout: Duration::from_millis(20),
+                joint_limits: None,
             })
             .expect("borrowed reapply should ignore incoming command_timeout mismatch");
 
@@ -4650,315 +5624,566 @@ mod tests {
     }
 
     #[test]
-    fn active_position_mode_reapply_config_rejects_motion_type_change() {
+    fn active_position_mode_set_install_position_keeps_applied_speed() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
         let mut frames = enabled_joint_frames_after(Duration::from_millis(10));
         frames.push(TimedFrame {
             delay: Duration::from_millis(15),
             frame: robot_status_frame(ControlMode::CanControl, MoveMode::MoveJ, 100),
         });
+        frames.push(TimedFrame {
+            delay: Duration::from_millis(5),
+            frame: robot_status_frame(ControlMode::CanControl, MoveMode::MoveJ, 200),
+        });
+        frames.push(TimedFrame {
+            delay: Duration::from_millis(1),
+            frame: control_mode_echo_frame(
+                piper_protocol::control::ControlModeCommand::CanControl,
+                MoveMode::MoveJ,
+                10,
+                piper_protocol::control::MitMode::PositionVelocity,
+                InstallPosition::SideRight,
+                201,
+            ),
+        });
 
         let standby = build_standby_piper(PacedRxAdapter::new(frames), sent_frames.clone());
         let active = standby
             .enable_position_mode(PositionModeConfig {
-                timeout: Duration::from_millis(80),
+                timeout: TEST_EVENTUALLY_TIMEOUT,
                 debounce_threshold: 1,
                 poll_interval: Duration::from_millis(1),
                 speed_percent: 10,
                 install_position: InstallPosition::Invalid,
                 motion_type: MotionType::Joint,
                 command_timeout: Duration::from_millis(20),
+                joint_limits: None,
             })
             .expect("matching 0x2A1 should allow Active<PositionMode>");
 
-        let error = active
-            .reapply_position_mode_config(PositionModeConfig {
-                motion_type: MotionType::Linear,
-                ..PositionModeConfig::default()
-            })
-            .expect_err("borrowed Active<PositionMode> must not silently change motion type");
+        active
+            .set_install_position(InstallPosition::SideRight)
+            .expect("install position update should be confirmed");
 
-        assert!(matches!(error, RobotError::ConfigError(_)));
-        let control_mode_frames = sent_frames
+        let control_mode_frames: Vec<_> = sent_frames
             .lock()
             .expect("sent frames lock")
             .iter()
             .filter(|frame| frame.id() == piper_protocol::ids::ID_CONTROL_MODE.into())
-            .count();
+            .copied()
+            .collect();
+
+        assert_eq!(control_mode_frames.len(), 2);
         assert_eq!(
-            control_mode_frames, 1,
-            "rejected reconfiguration must not send an extra 0x151 frame"
+            control_mode_frames[1],
+            piper_protocol::control::ControlModeCommandFrame::new(
+                piper_protocol::control::ControlModeCommand::CanControl,
+                MoveMode::MoveJ,
+                10,
+                piper_protocol::control::MitMode::PositionVelocity,
+                0,
+                InstallPosition::SideRight,
+            )
+            .to_frame()
         );
     }
 
-    fn assert_enable_position_mode_succeeds_for_motion_type(
-        motion_type: MotionType,
-        expected_move_mode: MoveMode,
-    ) {
+    #[test]
+    fn active_position_mode_set_install_position_rejects_invalid_orientation() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
-        let mut frames = enabled_joint_frames_after(Duration::from_millis(10));
+        let driver = Arc::new(
+            RobotPiper::new_dual_thread_parts(
+                IdleRxAdapter::new(),
+                RecordingTxAdapter::new(sent_frames.clone()),
+                None,
+            )
+            .expect("driver should start"),
+        );
+        let robot = build_active_position_piper(driver);
+
+        let error = robot
+            .set_install_position(InstallPosition::Invalid)
+            .expect_err("Invalid is not a mounting orientation");
+
+        assert!(matches!(error, RobotError::ConfigError(_)));
+        assert!(sent_frames.lock().expect("sent frames lock").is_empty());
+    }
+
+    #[test]
+    fn move_and_wait_returns_after_fresh_arrival_within_tolerance() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let mut frames = vec![TimedFrame {
+            delay: Duration::from_millis(30),
+            frame: joint_feedback_frame(ID_JOINT_FEEDBACK_12.raw().into(), 0, 0, 1_000),
+        }];
+        frames.extend(control_snapshot_frames(1_000).into_iter().skip(1).take(2));
         frames.push(TimedFrame {
-            delay: Duration::from_millis(15),
-            frame: robot_status_frame(ControlMode::CanControl, expected_move_mode, 100),
+            delay: Duration::ZERO,
+            frame: robot_status_frame(ControlMode::CanControl, MoveMode::MoveJ, 1_001),
         });
+        let driver = Arc::new(
+            RobotPiper::new_dual_thread_parts(
+                PacedRxAdapter::new(frames),
+                RecordingTxAdapter::new(sent_frames.clone()),
+                None,
+            )
+            .expect("driver should start"),
+        );
+        let robot = build_active_position_piper(driver);
 
-        let standby = build_standby_piper(PacedRxAdapter::new(frames), sent_frames.clone());
-        let active = standby
-            .enable_position_mode(PositionModeConfig {
-                timeout: TEST_EVENTUALLY_TIMEOUT,
-                debounce_threshold: 1,
-                poll_interval: Duration::from_millis(1),
-                speed_percent: 10,
-                install_position: InstallPosition::Invalid,
-                motion_type,
-                command_timeout: Duration::from_millis(20),
-            })
-            .expect("matching 0x2A1 should allow Active<PositionMode>");
+        robot
+            .move_and_wait(&JointArray::splat(Rad(0.005)), TEST_EVENTUALLY_TIMEOUT)
+            .expect("fresh Arrived status within tolerance should complete the move");
 
-        assert_eq!(active._state.0.motion_type, motion_type);
-        let control = active.driver.get_robot_control();
-        assert_eq!(control.control_mode, ControlMode::CanControl as u8);
-        assert_eq!(control.move_mode, expected_move_mode as u8);
-        assert!(control.is_fully_enabled_confirmed());
-        assert!(
-            sent_frames
-                .lock()
-                .expect("sent frames lock")
-                .iter()
-                .any(|frame| frame.id() == piper_protocol::ids::ID_CONTROL_MODE.into()),
-            "mode switch command should be sent before confirmation succeeds"
+        let sent = sent_frames.lock().expect("sent frames lock");
+        assert_eq!(
+            sent.len(),
+            3,
+            "move_and_wait should send one joint position package"
         );
     }
 
     #[test]
-    fn enable_position_mode_cartesian_succeeds_with_matching_robot_status_move_p() {
-        assert_enable_position_mode_succeeds_for_motion_type(
-            MotionType::Cartesian,
-            MoveMode::MoveP,
+    fn play_teach_approaches_first_sample_then_replays_remaining_samples() {
+        use crate::teach::{TeachSample, TeachTrajectory};
+
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let mut frames = vec![TimedFrame {
+            delay: Duration::from_millis(30),
+            frame: joint_feedback_frame(ID_JOINT_FEEDBACK_12.raw().into(), 0, 0, 1_000),
+        }];
+        frames.extend(control_snapshot_frames(1_000).into_iter().skip(1).take(2));
+        frames.push(TimedFrame {
+            delay: Duration::ZERO,
+            frame: robot_status_frame(ControlMode::CanControl, MoveMode::MoveJ, 1_001),
+        });
+        let driver = Arc::new(
+            RobotPiper::new_dual_thread_parts(
+                PacedRxAdapter::new(frames),
+                RecordingTxAdapter::new(sent_frames.clone()),
+                None,
+            )
+            .expect("driver should start"),
+        );
+        let robot = build_active_position_piper(driver);
+
+        assert!(matches!(
+            robot.play_teach(&TeachTrajectory::default(), TEST_EVENTUALLY_TIMEOUT),
+            Err(MotionError::Robot(RobotError::ConfigError(_)))
+        ));
+
+        let trajectory = TeachTrajectory::new(
+            [0.0, 0.05, 0.1]
+                .into_iter()
+                .enumerate()
+                .map(|(index, q)| TeachSample {
+                    elapsed: Duration::from_millis(10 * index as u64),
+                    position: JointArray::splat(Rad(q)),
+                })
+                .collect(),
+        )
+        .expect("ordered samples");
+        let start = Instant::now();
+        robot
+            .play_teach(&trajectory, TEST_EVENTUALLY_TIMEOUT)
+            .expect("arrival at the first sample should start the replay");
+        assert!(start.elapsed() >= Duration::from_millis(20));
+
+        let sent = sent_frames.lock().expect("sent frames lock");
+        assert_eq!(
+            sent.len(),
+            9,
+            "approach + 2 replayed joint position packages"
         );
     }
 
-    #[test]
-    fn enable_position_mode_linear_succeeds_with_matching_robot_status_move_l() {
-        assert_enable_position_mode_succeeds_for_motion_type(MotionType::Linear, MoveMode::MoveL);
+    fn teach_status_frame(teach_status: piper_protocol::feedback::TeachStatus) -> PiperFrame {
+        PiperFrame::new_standard(
+            piper_protocol::ids::ID_ROBOT_STATUS.raw().into(),
+            [
+                ControlMode::Teach as u8,
+                RobotStatus::Normal as u8,
+                MoveMode::MoveJ as u8,
+                teach_status as u8,
+                piper_protocol::feedback::MotionStatus::Arrived as u8,
+                0,
+                0,
+                0,
+            ],
+        )
+        .unwrap()
+        .with_timestamp_us(2_000)
     }
 
     #[test]
-    fn enable_position_mode_circular_succeeds_with_matching_robot_status_move_c() {
-        assert_enable_position_mode_succeeds_for_motion_type(MotionType::Circular, MoveMode::MoveC);
+    fn teach_recording_samples_joint_positions_until_stopped() {
+        use crate::teach::TeachRecordingConfig;
+        use piper_protocol::control::{EmergencyStopCommand, TeachCommand};
+        use piper_protocol::feedback::TeachStatus;
+
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let mut frames = vec![TimedFrame {
+            delay: Duration::from_millis(30),
+            frame: teach_status_frame(TeachStatus::StartRecord),
+        }];
+        frames.extend(control_snapshot_frames(2_001));
+        let standby = build_standby_piper(PacedRxAdapter::new(frames), sent_frames.clone());
+
+        let recording = standby
+            .start_teach_recording(TeachRecordingConfig {
+                sample_interval: Duration::from_millis(2),
+                timeout: TEST_EVENTUALLY_TIMEOUT,
+                poll_interval: Duration::from_millis(1),
+            })
+            .expect("0x2A1 StartRecord should confirm drag teach");
+        thread::sleep(Duration::from_millis(50));
+        let trajectory = recording.stop().expect("stopping the recording should succeed");
+
+        assert!(!trajectory.is_empty());
+        assert!(
+            trajectory
+                .samples()
+                .iter()
+                .all(|sample| sample.position == JointArray::splat(Rad(0.0)))
+        );
+        assert_eq!(
+            *sent_frames.lock().expect("sent frames lock"),
+            vec![
+                EmergencyStopCommand::teach(TeachCommand::StartRecord).to_frame(),
+                EmergencyStopCommand::teach(TeachCommand::EndRecord).to_frame(),
+            ]
+        );
     }
 
     #[test]
-    fn enable_position_mode_rejects_mismatched_control_mode_echo() {
-        let sent_frames = Arc::new(Mutex::new(Vec::new()));
-        let mut frames = enabled_joint_frames_after(Duration::from_millis(5));
-        frames.push(TimedFrame {
-            delay: Duration::from_millis(15),
-            frame: control_mode_echo_frame(
-                piper_protocol::control::ControlModeCommand::CanControl,
-                MoveMode::MoveL,
-                40,
-                piper_protocol::control::MitMode::PositionVelocity,
-                InstallPosition::Invalid,
-                101,
-            ),
-        });
-        frames.push(TimedFrame {
-            delay: Duration::ZERO,
-            frame: robot_status_frame(ControlMode::CanControl, MoveMode::MoveL, 100),
-        });
+    fn teach_recording_reports_rejection_when_teach_status_does_not_change() {
+        use crate::teach::TeachRecordingConfig;
+        use piper_protocol::feedback::TeachStatus;
+
+        let frames = vec![TimedFrame {
+            delay: Duration::from_millis(10),
+            frame: teach_status_frame(TeachStatus::Closed),
+        }];
+        let standby = build_standby_piper(
+            PacedRxAdapter::new(frames),
+            Arc::new(Mutex::new(Vec::new())),
+        );
 
-        let standby = build_standby_piper(PacedRxAdapter::new(frames), sent_frames);
-        let error = match standby.enable_position_mode(PositionModeConfig {
+        let error = match standby.start_teach_recording(TeachRecordingConfig {
             timeout: Duration::from_millis(50),
-            debounce_threshold: 1,
             poll_interval: Duration::from_millis(1),
-            speed_percent: 40,
-            install_position: InstallPosition::Horizontal,
-            motion_type: MotionType::Linear,
-            command_timeout: Duration::from_millis(20),
+            ..TeachRecordingConfig::default()
         }) {
-            Ok(_) => panic!("mismatched 0x151 echo must reject Active<PositionMode>"),
+            Ok(_) => panic!("unchanged teach status must reject the recording"),
             Err(error) => error,
         };
-
-        assert!(matches!(error, RobotError::Timeout { .. }));
+        assert!(matches!(
+            error,
+            RobotError::TeachModeRejected {
+                expected: TeachStatus::StartRecord,
+                actual: TeachStatus::Closed,
+            }
+        ));
     }
 
     #[test]
-    fn enable_timeout_diagnostics_summary_reports_partial_low_speed_state() {
+    fn move_and_wait_reports_fault_status() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
-        let standby = build_standby_piper(
-            PacedRxAdapter::new(vec![TimedFrame {
-                delay: Duration::ZERO,
-                frame: joint_driver_enabled_frame(1, 1_000),
-            }]),
-            sent_frames,
-        );
-
-        wait_until(
-            Duration::from_millis(100),
-            || standby.observer().joint_enabled_mask() == 0b000001,
-            "partial low-speed state should become visible",
+        let frames = vec![TimedFrame {
+            delay: Duration::from_millis(30),
+            frame: robot_status_frame_with_status(
+                ControlMode::CanControl,
+                RobotStatus::EmergencyStop,
+                MoveMode::MoveJ,
+                1_000,
+            ),
+        }];
+        let driver = Arc::new(
+            RobotPiper::new_dual_thread_parts(
+                PacedRxAdapter::new(frames),
+                RecordingTxAdapter::new(sent_frames),
+                None,
+            )
+            .expect("driver should start"),
         );
+        let robot = build_active_position_piper(driver);
 
-        let summary = enable_timeout_diagnostics_summary(&standby.driver, 500);
+        let error = robot
+            .move_and_wait(&JointArray::splat(Rad(0.0)), TEST_EVENTUALLY_TIMEOUT)
+            .expect_err("emergency stop must abort the wait");
 
-        assert!(summary.contains("driver_enabled_mask=000001"), "{summary}");
-        assert!(summary.contains("confirmed_after_commit=None"), "{summary}");
-        assert!(summary.contains("low_speed=partial"), "{summary}");
+        assert!(matches!(
+            error,
+            MotionError::Fault(RobotStatus::EmergencyStop)
+        ));
     }
 
     #[test]
-    fn mode_confirmation_timeout_diagnostics_summary_reports_echo_predicates() {
+    fn robot_fault_status_rejects_next_command_and_invokes_callback() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
-        let mut frames = enabled_joint_frames_after(Duration::from_millis(5));
-        frames.push(TimedFrame {
-            delay: Duration::from_millis(15),
-            frame: robot_status_frame(ControlMode::CanControl, MoveMode::MoveJ, 100),
+        let frames = vec![TimedFrame {
+            delay: Duration::from_millis(10),
+            frame: robot_status_frame_with_status(
+                ControlMode::CanControl,
+                RobotStatus::Collision,
+                MoveMode::MoveJ,
+                1_000,
+            ),
+        }];
+        let driver = Arc::new(
+            RobotPiper::new_dual_thread_parts(
+                PacedRxAdapter::new(frames),
+                RecordingTxAdapter::new(sent_frames.clone()),
+                None,
+            )
+            .expect("driver should start"),
+        );
+        let robot = build_active_position_piper(driver);
+        let (fault_tx, fault_rx) = std::sync::mpsc::channel();
+        robot.on_fault(move |status| {
+            let _ = fault_tx.send(status);
         });
 
-        let standby = build_standby_piper(PacedRxAdapter::new(frames), sent_frames);
-        wait_until(
-            Duration::from_millis(200),
-            || standby.observer().is_all_enabled_confirmed(),
-            "enabled low-speed feedback should be confirmed",
-        );
-        wait_until(
-            Duration::from_millis(200),
-            || standby.driver.get_robot_control().control_mode == ControlMode::CanControl as u8,
-            "robot status frame should update control mode",
+        assert_eq!(
+            fault_rx.recv_timeout(TEST_EVENTUALLY_TIMEOUT),
+            Ok(RobotStatus::Collision)
         );
+        assert_eq!(robot.robot_fault(), Some(RobotStatus::Collision));
 
-        let summary = mode_confirmation_timeout_diagnostics_summary(
-            &standby.driver,
-            0,
-            ModeConfirmationExpectation {
-                control_mode: ControlMode::CanControl as u8,
-                move_mode: MoveMode::MoveJ as u8,
-                speed_percent: 10,
-                mit_mode: ProtocolMitMode::PositionVelocity as u8,
-                install_position: InstallPosition::Invalid as u8,
-                trajectory_stay_time: 0,
+        let error = robot
+            .send_position_command(&JointArray::splat(Rad(0.0)))
+            .expect_err("latched fault must reject motion commands");
+        assert!(matches!(
+            error,
+            RobotError::Infrastructure(DriverError::RobotFault(RobotStatus::Collision))
+        ));
+        assert!(sent_frames.lock().expect("sent frames lock").is_empty());
+    }
+
+    #[test]
+    fn transient_no_solution_status_does_not_block_next_command() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let frames = vec![TimedFrame {
+            delay: Duration::from_millis(10),
+            frame: robot_status_frame_with_status(
+                ControlMode::CanControl,
+                RobotStatus::NoSolution,
+                MoveMode::MoveP,
+                1_000,
+            ),
+        }];
+        let driver = Arc::new(
+            RobotPiper::new_dual_thread_parts(
+                PacedRxAdapter::new(frames),
+                RecordingTxAdapter::new(sent_frames.clone()),
+                None,
+            )
+            .expect("driver should start"),
+        );
+        wait_until(
+            TEST_EVENTUALLY_TIMEOUT,
+            || {
+                RobotStatus::from(driver.get_robot_control().robot_status)
+                    == RobotStatus::NoSolution
             },
+            "NoSolution status should be observed",
         );
+        let robot = build_active_position_piper(driver);
 
-        assert!(summary.contains("robot_state_match=true"), "{summary}");
-        assert!(summary.contains("mode_echo_fresh=false"), "{summary}");
-        assert!(summary.contains("mode_echo_match=false"), "{summary}");
+        assert_eq!(robot.robot_fault(), None);
+        robot
+            .send_position_command(&JointArray::splat(Rad(0.0)))
+            .expect("transient NoSolution must not latch a fault");
+        assert!(!sent_frames.lock().expect("sent frames lock").is_empty());
     }
 
     #[test]
-    fn enable_mit_mode_succeeds_with_fresh_matching_robot_and_echo() {
+    fn clear_robot_fault_unblocks_motion_commands() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let frames = vec![TimedFrame {
+            delay: Duration::from_millis(10),
+            frame: robot_status_frame_with_status(
+                ControlMode::CanControl,
+                RobotStatus::Collision,
+                MoveMode::MoveJ,
+                1_000,
+            ),
+        }];
+        let driver = Arc::new(
+            RobotPiper::new_dual_thread_parts(
+                PacedRxAdapter::new(frames),
+                RecordingTxAdapter::new(sent_frames.clone()),
+                None,
+            )
+            .expect("driver should start"),
+        );
+        let robot = build_active_position_piper(driver);
+        wait_until(
+            TEST_EVENTUALLY_TIMEOUT,
+            || robot.robot_fault().is_some(),
+            "collision should be latched",
+        );
+
+        robot.clear_robot_fault();
+        assert_eq!(robot.robot_fault(), None);
+        robot
+            .send_position_command(&JointArray::splat(Rad(0.0)))
+            .expect("cleared fault must allow motion commands");
+        assert!(!sent_frames.lock().expect("sent frames lock").is_empty());
+    }
+
+    #[test]
+    fn move_and_wait_times_out_without_arrival() {
+        let driver = Arc::new(
+            RobotPiper::new_dual_thread_parts(
+                IdleRxAdapter::new(),
+                RecordingTxAdapter::new(Arc::new(Mutex::new(Vec::new()))),
+                None,
+            )
+            .expect("driver should start"),
+        );
+        let robot = build_active_position_piper(driver);
+
+        let error = robot
+            .move_and_wait(&JointArray::splat(Rad(0.0)), Duration::from_millis(30))
+            .expect_err("no feedback must time out");
+
+        assert!(matches!(error, MotionError::Timeout { timeout_ms: 30 }));
+    }
+
+    #[test]
+    fn active_position_mode_reapply_config_rejects_motion_type_change() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
         let mut frames = enabled_joint_frames_after(Duration::from_millis(10));
         frames.push(TimedFrame {
-            delay: Duration::from_millis(20),
-            frame: robot_status_frame(ControlMode::CanControl, MoveMode::MoveM, 100),
-        });
-        frames.push(TimedFrame {
-            delay: Duration::from_millis(5),
-            frame: control_mode_echo_frame(
-                piper_protocol::control::ControlModeCommand::CanControl,
-                MoveMode::MoveM,
-                80,
-                piper_protocol::control::MitMode::Mit,
-                InstallPosition::Invalid,
-                101,
-            ),
+            delay: Duration::from_millis(15),
+            frame: robot_status_frame(ControlMode::CanControl, MoveMode::MoveJ, 100),
         });
 
-        let standby = build_standby_piper(PacedRxAdapter::new(frames), sent_frames);
+        let standby = build_standby_piper(PacedRxAdapter::new(frames), sent_frames.clone());
         let active = standby
-            .enable_mit_mode(MitModeConfig {
+            .enable_position_mode(PositionModeConfig {
                 timeout: Duration::from_millis(80),
                 debounce_threshold: 1,
                 poll_interval: Duration::from_millis(1),
-                speed_percent: 80,
+                speed_percent: 10,
+                install_position: InstallPosition::Invalid,
+                motion_type: MotionType::Joint,
+                command_timeout: Duration::from_millis(20),
+                joint_limits: None,
             })
-            .expect("fresh matching 0x2A1 + 0x151 should allow Active<MitMode>");
-
-        assert!(active.observer().is_all_enabled());
-        assert!(active.observer().is_all_enabled_confirmed());
-    }
-
-    #[test]
-    fn enable_mit_mode_timeout_after_enable_dispatch_sends_disable_all() {
-        use piper_protocol::control::MotorEnableCommand;
+            .expect("matching 0x2A1 should allow Active<PositionMode>");
 
-        let sent = Arc::new(Mutex::new(Vec::new()));
-        let standby = build_standby_piper(IdleRxAdapter::new(), sent.clone());
-        let config = MitModeConfig {
-            timeout: Duration::from_millis(20),
-            poll_interval: Duration::from_millis(1),
-            ..MitModeConfig::default()
-        };
-        let enable_all = MotorEnableCommand::enable_all().to_frame();
+        let error = active
+            .reapply_position_mode_config(PositionModeConfig {
+                motion_type: MotionType::Linear,
+                ..PositionModeConfig::default()
+            })
+            .expect_err("borrowed Active<PositionMode> must not silently change motion type");
 
-        let enable_handle = thread::spawn(move || standby.enable_mit_mode(config));
-        wait_until(
-            Duration::from_secs(1),
-            || sent.lock().expect("sent frames lock").contains(&enable_all),
-            "enable_all must be dispatched before forcing confirmation timeout",
+        assert!(matches!(error, RobotError::ConfigError(_)));
+        let control_mode_frames = sent_frames
+            .lock()
+            .expect("sent frames lock")
+            .iter()
+            .filter(|frame| frame.id() == piper_protocol::ids::ID_CONTROL_MODE.into())
+            .count();
+        assert_eq!(
+            control_mode_frames, 1,
+            "rejected reconfiguration must not send an extra 0x151 frame"
         );
+    }
 
-        let err = match enable_handle.join().expect("enable_mit_mode thread should finish") {
-            Ok(_) => panic!("enable confirmation timeout must fail"),
-            Err(err) => err,
-        };
+    fn assert_enable_position_mode_succeeds_for_motion_type(
+        motion_type: MotionType,
+        expected_move_mode: MoveMode,
+    ) {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let mut frames = enabled_joint_frames_after(Duration::from_millis(10));
+        frames.push(TimedFrame {
+            delay: Duration::from_millis(15),
+            frame: robot_status_frame(ControlMode::CanControl, expected_move_mode, 100),
+        });
 
-        assert!(matches!(err, RobotError::Timeout { .. }));
-        let frames = wait_for_sent_frames(&sent, 2);
+        let standby = build_standby_piper(PacedRxAdapter::new(frames), sent_frames.clone());
+        let active = standby
+            .enable_position_mode(PositionModeConfig {
+                timeout: TEST_EVENTUALLY_TIMEOUT,
+                debounce_threshold: 1,
+                poll_interval: Duration::from_millis(1),
+                speed_percent: 10,
+                install_position: InstallPosition::Invalid,
+                motion_type,
+                command_timeout: Duration::from_millis(20),
+                joint_limits: None,
+            })
+            .expect("matching 0x2A1 should allow Active<PositionMode>");
+
+        assert_eq!(active._state.0.motion_type, motion_type);
+        let control = active.driver.get_robot_control();
+        assert_eq!(control.control_mode, ControlMode::CanControl as u8);
+        assert_eq!(control.move_mode, expected_move_mode as u8);
+        assert!(control.is_fully_enabled_confirmed());
         assert!(
-            frames
+            sent_frames
+                .lock()
+                .expect("sent frames lock")
                 .iter()
-                .any(|frame| *frame == MotorEnableCommand::disable_all().to_frame()),
-            "failed MIT enable after dispatch must send disable_all"
+                .any(|frame| frame.id() == piper_protocol::ids::ID_CONTROL_MODE.into()),
+            "mode switch command should be sent before confirmation succeeds"
         );
     }
 
     #[test]
-    fn enable_mit_mode_rejects_stale_historical_enabled_feedback_before_confirmation() {
+    fn enable_position_mode_cartesian_succeeds_with_matching_robot_status_move_p() {
+        assert_enable_position_mode_succeeds_for_motion_type(
+            MotionType::Cartesian,
+            MoveMode::MoveP,
+        );
+    }
+
+    #[test]
+    fn enable_position_mode_linear_succeeds_with_matching_robot_status_move_l() {
+        assert_enable_position_mode_succeeds_for_motion_type(MotionType::Linear, MoveMode::MoveL);
+    }
+
+    #[test]
+    fn enable_position_mode_circular_succeeds_with_matching_robot_status_move_c() {
+        assert_enable_position_mode_succeeds_for_motion_type(MotionType::Circular, MoveMode::MoveC);
+    }
+
+    #[test]
+    fn enable_position_mode_rejects_mismatched_control_mode_echo() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
         let mut frames = enabled_joint_frames_after(Duration::from_millis(5));
         frames.push(TimedFrame {
-            delay: Duration::from_millis(80),
-            frame: robot_status_frame(ControlMode::CanControl, MoveMode::MoveM, 100),
-        });
-        frames.push(TimedFrame {
-            delay: Duration::from_millis(5),
+            delay: Duration::from_millis(15),
             frame: control_mode_echo_frame(
                 piper_protocol::control::ControlModeCommand::CanControl,
-                MoveMode::MoveM,
-                80,
-                piper_protocol::control::MitMode::Mit,
+                MoveMode::MoveL,
+                40,
+                piper_protocol::control::MitMode::PositionVelocity,
                 InstallPosition::Invalid,
                 101,
             ),
         });
+        frames.push(TimedFrame {
+            delay: Duration::ZERO,
+            frame: robot_status_frame(ControlMode::CanControl, MoveMode::MoveL, 100),
+        });
 
-        let standby = build_standby_piper_with_config(
-            PacedRxAdapter::new(frames),
-            sent_frames,
-            Some(piper_driver::PipelineConfig {
-                low_speed_drive_state_freshness_ms: 20,
-                ..piper_driver::PipelineConfig::default()
-            }),
-        );
-        wait_until(
-            Duration::from_millis(100),
-            || standby.observer().joint_enabled_mask() == 0b11_1111,
-            "historical enabled frames should arrive before staleness check",
-        );
-        thread::sleep(Duration::from_millis(40));
-
-        let error = match standby.enable_mit_mode(MitModeConfig {
-            timeout: Duration::from_millis(120),
+        let standby = build_standby_piper(PacedRxAdapter::new(frames), sent_frames);
+        let error = match standby.enable_position_mode(PositionModeConfig {
+            timeout: Duration::from_millis(50),
             debounce_threshold: 1,
             poll_interval: Duration::from_millis(1),
-            speed_percent: 80,
+            speed_percent: 40,
+            install_position: InstallPosition::Horizontal,
+            motion_type: MotionType::Linear,
+            command_timeout: Duration::from_millis(20),
+            joint_limits: None,
         }) {
-            Ok(_) => panic!("stale historical enabled bits must not satisfy wait_for_enabled"),
+            Ok(_) => panic!("mismatched 0x151 echo must reject Active<PositionMode>"),
             Err(error) => error,
         };
 
@@ -4966,61 +6191,78 @@ mod tests {
     }
 
     #[test]
-    fn enable_mit_mode_rejects_stale_enabled_state_during_mode_confirmation() {
+    fn enable_timeout_diagnostics_summary_reports_partial_low_speed_state() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
-        let mut frames = enabled_joint_frames_after(Duration::from_millis(5));
-        frames.push(TimedFrame {
-            delay: Duration::from_millis(40),
-            frame: robot_status_frame(ControlMode::CanControl, MoveMode::MoveM, 100),
-        });
-        frames.push(TimedFrame {
-            delay: Duration::from_millis(5),
-            frame: control_mode_echo_frame(
-                piper_protocol::control::ControlModeCommand::CanControl,
-                MoveMode::MoveM,
-                80,
-                piper_protocol::control::MitMode::Mit,
-                InstallPosition::Invalid,
-                101,
-            ),
-        });
-
-        let standby = build_standby_piper_with_config(
-            PacedRxAdapter::new(frames),
+        let standby = build_standby_piper(
+            PacedRxAdapter::new(vec![TimedFrame {
+                delay: Duration::ZERO,
+                frame: joint_driver_enabled_frame(1, 1_000),
+            }]),
             sent_frames,
-            Some(piper_driver::PipelineConfig {
-                low_speed_drive_state_freshness_ms: 20,
-                ..piper_driver::PipelineConfig::default()
-            }),
         );
 
-        let error = match standby.enable_mit_mode(MitModeConfig {
-            timeout: Duration::from_millis(120),
-            debounce_threshold: 1,
-            poll_interval: Duration::from_millis(1),
-            speed_percent: 80,
-        }) {
-            Ok(_) => panic!(
-                "stale enabled bits must not satisfy wait_for_mode_confirmation after wait_for_enabled"
-            ),
-            Err(error) => error,
-        };
+        wait_until(
+            Duration::from_millis(100),
+            || standby.observer().joint_enabled_mask() == 0b000001,
+            "partial low-speed state should become visible",
+        );
 
-        assert!(matches!(error, RobotError::Timeout { .. }));
+        let summary = enable_timeout_diagnostics_summary(&standby.driver, 500);
+
+        assert!(summary.contains("driver_enabled_mask=000001"), "{summary}");
+        assert!(summary.contains("confirmed_after_commit=None"), "{summary}");
+        assert!(summary.contains("low_speed=partial"), "{summary}");
     }
 
     #[test]
-    fn enable_mit_mode_rejects_matching_mode_feedback_that_arrives_before_commit() {
+    fn mode_confirmation_timeout_diagnostics_summary_reports_echo_predicates() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
-        let (started_tx, started_rx) = mpsc::channel();
-        let (release_tx, release_rx) = mpsc::channel();
-        let mut frames = enabled_joint_frames();
+        let mut frames = enabled_joint_frames_after(Duration::from_millis(5));
         frames.push(TimedFrame {
-            delay: Duration::from_millis(5),
+            delay: Duration::from_millis(15),
+            frame: robot_status_frame(ControlMode::CanControl, MoveMode::MoveJ, 100),
+        });
+
+        let standby = build_standby_piper(PacedRxAdapter::new(frames), sent_frames);
+        wait_until(
+            Duration::from_millis(200),
+            || standby.observer().is_all_enabled_confirmed(),
+            "enabled low-speed feedback should be confirmed",
+        );
+        wait_until(
+            Duration::from_millis(200),
+            || standby.driver.get_robot_control().control_mode == ControlMode::CanControl as u8,
+            "robot status frame should update control mode",
+        );
+
+        let summary = mode_confirmation_timeout_diagnostics_summary(
+            &standby.driver,
+            0,
+            ModeConfirmationExpectation {
+                control_mode: ControlMode::CanControl as u8,
+                move_mode: MoveMode::MoveJ as u8,
+                speed_percent: 10,
+                mit_mode: ProtocolMitMode::PositionVelocity as u8,
+                install_position: InstallPosition::Invalid as u8,
+                trajectory_stay_time: 0,
+            },
+        );
+
+        assert!(summary.contains("robot_state_match=true"), "{summary}");
+        assert!(summary.contains("mode_echo_fresh=false"), "{summary}");
+        assert!(summary.contains("mode_echo_match=false"), "{summary}");
+    }
+
+    #[test]
+    fn enable_mit_mode_succeeds_with_fresh_matching_robot_and_echo() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let mut frames = enabled_joint_frames_after(Duration::from_millis(10));
+        frames.push(TimedFrame {
+            delay: Duration::from_millis(20),
             frame: robot_status_frame(ControlMode::CanControl, MoveMode::MoveM, 100),
         });
         frames.push(TimedFrame {
-            delay: Duration::ZERO,
+            delay: Duration::from_millis(5),
             frame: control_mode_echo_frame(
                 piper_protocol::control::ControlModeCommand::CanControl,
                 MoveMode::MoveM,
@@ -5031,175 +6273,222 @@ mod tests {
             ),
         });
 
-        let standby = build_standby_piper_with_tx(
-            PacedRxAdapter::new(frames),
-            BlockingFirstControlTxAdapter {
-                sent_frames: sent_frames.clone(),
-                started_tx,
-                release_rx,
-                sends: 0,
-            },
-        );
-        let driver = Arc::clone(&standby.driver);
-        driver
-            .send_reliable(PiperFrame::new_standard(0x221, [0x01]).unwrap())
-            .expect("blocking reliable frame should enqueue before mode switch");
-
-        let standby_handle = thread::spawn(move || {
-            standby.enable_mit_mode(MitModeConfig {
-                timeout: Duration::from_millis(120),
+        let standby = build_standby_piper(PacedRxAdapter::new(frames), sent_frames);
+        let active = standby
+            .enable_mit_mode(MitModeConfig {
+                timeout: Duration::from_millis(80),
                 debounce_threshold: 1,
                 poll_interval: Duration::from_millis(1),
                 speed_percent: 80,
             })
-        });
-
-        started_rx
-            .recv_timeout(Duration::from_millis(200))
-            .expect("mode command should block at tx.send_control before commit");
-        thread::sleep(Duration::from_millis(20));
-        let _ = release_tx.send(());
+            .expect("fresh matching 0x2A1 + 0x151 should allow Active<MitMode>");
 
-        let error = match standby_handle.join().expect("enable_mit_mode thread should finish") {
-            Ok(_) => panic!("pre-commit matching feedback must not satisfy mode confirmation"),
-            Err(error) => error,
-        };
-        assert!(matches!(error, RobotError::Timeout { .. }));
+        assert!(active.observer().is_all_enabled());
+        assert!(active.observer().is_all_enabled_confirmed());
     }
 
-    #[test]
-    fn enable_mit_mode_rejects_enabled_feedback_that_arrives_before_enable_commit() {
-        let sent_frames = Arc::new(Mutex::new(Vec::new()));
-        let (started_tx, started_rx) = mpsc::channel();
-        let (release_tx, release_rx) = mpsc::channel();
+    fn active_mit_mode_frames() -> Vec<TimedFrame> {
+        let mut frames = enabled_joint_frames_after(Duration::from_millis(10));
+        frames.extend(control_snapshot_frames(50));
+        frames.push(TimedFrame {
+            delay: Duration::from_millis(20),
+            frame: robot_status_frame(ControlMode::CanControl, MoveMode::MoveM, 100),
+        });
+        frames
+    }
 
-        let standby = build_standby_piper_with_tx(
-            PacedRxAdapter::new(enabled_joint_frames()),
-            BlockingFirstControlTxAdapter {
-                sent_frames: sent_frames.clone(),
-                started_tx,
-                release_rx,
-                sends: 0,
+    fn mit_to_position_test_config(timeout: Duration) -> MitToPositionConfig {
+        MitToPositionConfig {
+            position: PositionModeConfig {
+                timeout,
+                debounce_threshold: 1,
+                poll_interval: Duration::from_millis(1),
+                ..PositionModeConfig::default()
             },
-        );
-        let driver = Arc::clone(&standby.driver);
-        driver
-            .send_reliable(PiperFrame::new_standard(0x221, [0x02]).unwrap())
-            .expect("blocking reliable frame should enqueue before enable_all");
+            ramp_duration: Duration::from_millis(10),
+            ramp_period: Duration::from_millis(5),
+            ..MitToPositionConfig::default()
+        }
+    }
 
-        let standby_handle = thread::spawn(move || {
-            standby.enable_mit_mode(MitModeConfig {
-                timeout: Duration::from_millis(120),
+    fn enable_test_mit_mode(
+        standby: Piper<Standby, StrictRealtime>,
+    ) -> Piper<Active<MitMode>, StrictRealtime> {
+        enable_test_mit_mode_with_speed(standby, 100)
+    }
+
+    fn enable_test_mit_mode_with_speed(
+        standby: Piper<Standby, StrictRealtime>,
+        speed_percent: u8,
+    ) -> Piper<Active<MitMode>, StrictRealtime> {
+        standby
+            .enable_mit_mode(MitModeConfig {
+                timeout: TEST_EVENTUALLY_TIMEOUT,
                 debounce_threshold: 1,
                 poll_interval: Duration::from_millis(1),
-                speed_percent: 80,
+                speed_percent,
             })
-        });
+            .expect("matching 0x2A1 should allow Active<MitMode>")
+    }
 
-        started_rx
-            .recv_timeout(Duration::from_millis(200))
-            .expect("blocking reliable frame should reach the adapter first");
-        wait_until(
-            Duration::from_millis(200),
-            || driver.get_robot_control().confirmed_driver_enabled_mask == Some(0b11_1111),
-            "enabled low-speed feedback should land before enable_all reaches TX commit",
-        );
+    fn sent_control_mode_frames(sent_frames: &Mutex<Vec<PiperFrame>>) -> Vec<PiperFrame> {
+        sent_frames
+            .lock()
+            .expect("sent frames lock")
+            .iter()
+            .filter(|frame| frame.id() == piper_protocol::ids::ID_CONTROL_MODE.into())
+            .copied()
+            .collect()
+    }
 
-        release_tx.send(()).expect("blocked reliable frame should release");
-        let error = match standby_handle.join().expect("enable_mit_mode thread should finish") {
-            Ok(_) => panic!("pre-commit enabled feedback must not satisfy wait_for_enabled"),
-            Err(error) => error,
-        };
+    #[test]
+    fn switch_to_position_ramps_torque_and_confirms_position_mode() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let mut frames = active_mit_mode_frames();
+        frames.extend(enabled_joint_frames_after(Duration::from_millis(80)));
+        frames.push(TimedFrame {
+            delay: Duration::ZERO,
+            frame: robot_status_frame(ControlMode::CanControl, MoveMode::MoveJ, 200),
+        });
 
-        assert!(matches!(error, RobotError::Timeout { .. }));
+        let standby = build_standby_piper(PacedRxAdapter::new(frames), sent_frames.clone());
+        let active = enable_test_mit_mode(standby);
+        let position = active
+            .switch_to_position(mit_to_position_test_config(TEST_EVENTUALLY_TIMEOUT))
+            .map_err(|(_, error)| error)
+            .expect("matching 0x2A1 should confirm the switch to Active<PositionMode>");
+
+        let control_mode_frames = sent_control_mode_frames(&sent_frames);
+        assert_eq!(control_mode_frames.len(), 2);
+        assert_eq!(
+            control_mode_frames[1],
+            piper_protocol::control::ControlModeCommandFrame::new(
+                piper_protocol::control::ControlModeCommand::CanControl,
+                MoveMode::MoveJ,
+                50,
+                piper_protocol::control::MitMode::PositionVelocity,
+                0,
+                InstallPosition::Invalid,
+            )
+            .to_frame()
+        );
+        let sent = sent_frames.lock().expect("sent frames lock").clone();
+        let mode_switch_index = sent
+            .iter()
+            .position(|frame| *frame == control_mode_frames[1])
+            .expect("position-mode 0x151 should be sent");
         assert!(
-            !sent_frames
-                .lock()
-                .expect("sent frames lock")
+            sent[..mode_switch_index]
                 .iter()
-                .any(|frame| frame.id() == piper_protocol::ids::ID_CONTROL_MODE.into()),
-            "mode switch must not start when enable confirmation only existed before commit",
+                .any(|frame| (0x15A..=0x15F).contains(&frame.raw_id())),
+            "torque ramp should be sent before the mode switch"
+        );
+        assert!(
+            sent[mode_switch_index..]
+                .iter()
+                .any(|frame| frame.id() == ID_JOINT_CONTROL_12.into()),
+            "joint position hold target should follow the mode switch"
         );
+        assert!(position.observer().is_all_enabled());
     }
 
     #[test]
-    fn enable_mit_mode_rejects_non_normal_robot_status_even_if_drives_are_enabled() {
+    fn switch_to_position_timeout_returns_mit_mode_and_restores_mit_control_mode() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
-        let mut frames = enabled_joint_frames();
-        frames.push(TimedFrame {
-            delay: Duration::from_millis(15),
-            frame: robot_status_frame_with_status(
-                ControlMode::CanControl,
-                RobotStatus::EmergencyStop,
-                MoveMode::MoveM,
-                100,
-            ),
-        });
-        frames.push(TimedFrame {
-            delay: Duration::from_millis(5),
-            frame: control_mode_echo_frame(
+        let standby = build_standby_piper(
+            PacedRxAdapter::new(active_mit_mode_frames()),
+            sent_frames.clone(),
+        );
+        let active = enable_test_mit_mode_with_speed(standby, 60);
+
+        let (active, error) = match active
+            .switch_to_position(mit_to_position_test_config(Duration::from_millis(50)))
+        {
+            Ok(_) => panic!("switch without 0x2A1 confirmation must fail"),
+            Err(failure) => failure,
+        };
+        assert!(matches!(error, RobotError::Timeout { .. }), "{error:?}");
+
+        let control_mode_frames = sent_control_mode_frames(&sent_frames);
+        assert_eq!(control_mode_frames.len(), 3);
+        assert_eq!(control_mode_frames[2], control_mode_frames[0]);
+        assert_eq!(
+            control_mode_frames[2],
+            piper_protocol::control::ControlModeCommandFrame::new(
                 piper_protocol::control::ControlModeCommand::CanControl,
                 MoveMode::MoveM,
-                80,
+                60,
                 piper_protocol::control::MitMode::Mit,
+                0,
                 InstallPosition::Invalid,
-                101,
-            ),
-        });
-
-        let standby = build_standby_piper(PacedRxAdapter::new(frames), sent_frames);
-        let error = match standby.enable_mit_mode(MitModeConfig {
-            timeout: Duration::from_millis(80),
-            debounce_threshold: 1,
-            poll_interval: Duration::from_millis(1),
-            speed_percent: 80,
-        }) {
-            Ok(_) => panic!("non-Normal 0x2A1 must prevent Active<MitMode>"),
-            Err(error) => error,
-        };
-
-        assert!(matches!(error, RobotError::Timeout { .. }));
+            )
+            .to_frame(),
+            "rollback must restore the speed the MIT session was enabled with"
+        );
+        assert!(active.observer().is_all_enabled());
     }
 
     #[test]
-    fn enable_mit_passthrough_succeeds_with_fresh_matching_robot_and_echo() {
+    fn switch_to_position_ramps_from_last_commanded_mit_batch() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
-        let mut frames = enabled_joint_frames_after(Duration::from_millis(5));
-        frames.push(TimedFrame {
-            delay: Duration::from_millis(15),
-            frame: robot_status_frame(ControlMode::CanControl, MoveMode::MoveM, 100),
-        });
-        frames.push(TimedFrame {
-            delay: Duration::ZERO,
-            frame: control_mode_echo_frame(
-                piper_protocol::control::ControlModeCommand::CanControl,
-                MoveMode::MoveM,
-                70,
-                piper_protocol::control::MitMode::Mit,
-                InstallPosition::Invalid,
-                101,
-            ),
-        });
+        let standby = build_standby_piper(
+            PacedRxAdapter::new(active_mit_mode_frames()),
+            sent_frames.clone(),
+        );
+        let active = enable_test_mit_mode(standby);
+        active
+            .command_torques_confirmed(
+                &JointArray::splat(Rad(0.0)),
+                &JointArray::splat(0.0),
+                &JointArray::splat(50.0),
+                &JointArray::splat(2.0),
+                &JointArray::splat(NewtonMeter(4.0)),
+                TEST_EVENTUALLY_TIMEOUT,
+            )
+            .expect("MIT command should be sent");
 
-        let standby = build_soft_standby_piper(PacedRxAdapter::new(frames), sent_frames);
-        let active = standby
-            .enable_mit_passthrough(MitModeConfig {
-                timeout: Duration::from_millis(80),
-                debounce_threshold: 1,
-                poll_interval: Duration::from_millis(1),
-                speed_percent: 70,
-            })
-            .expect("fresh matching 0x2A1 + 0x151 should allow MIT passthrough");
+        // 10 ms / 5 ms：两步，第一步处于用户批次与保持命令的中点
+        assert!(
+            active
+                .switch_to_position(mit_to_position_test_config(Duration::from_millis(50)))
+                .is_err(),
+            "switch without 0x2A1 confirmation must fail"
+        );
 
-        assert!(matches!(active._state, Active(MitPassthroughMode)));
+        let j1_commands: Vec<MitControlCommand> = sent_frames
+            .lock()
+            .expect("sent frames lock")
+            .iter()
+            .filter(|frame| frame.raw_id() == 0x15A)
+            .map(|frame| MitControlCommand::try_from(*frame).expect("valid MIT frame"))
+            .collect();
+        assert_eq!(j1_commands.len(), 3, "{j1_commands:?}");
+        let [user, midpoint, hold] = [j1_commands[0], j1_commands[1], j1_commands[2]];
+
+        let hold_kp = MitToPositionConfig::default().hold_kp[Joint::J1] as f32;
+        let hold_kd = MitToPositionConfig::default().hold_kd[Joint::J1] as f32;
+        assert!(
+            (midpoint.kp() - (user.kp() + hold_kp) / 2.0).abs() < 0.2,
+            "{midpoint:?}"
+        );
+        assert!(
+            (midpoint.kd() - (user.kd() + hold_kd) / 2.0).abs() < 0.01,
+            "{midpoint:?}"
+        );
+        assert!(
+            (midpoint.t_ref() - user.t_ref() / 2.0).abs() < 0.1,
+            "{midpoint:?}"
+        );
+        assert!((hold.kp() - hold_kp).abs() < 0.2, "{hold:?}");
+        assert!(hold.t_ref().abs() < 0.1, "{hold:?}");
     }
 
     #[test]
-    fn enable_mit_passthrough_timeout_after_enable_dispatch_sends_disable_all() {
+    fn enable_mit_mode_timeout_after_enable_dispatch_sends_disable_all() {
         use piper_protocol::control::MotorEnableCommand;
 
         let sent = Arc::new(Mutex::new(Vec::new()));
-        let standby = build_soft_standby_piper(IdleRxAdapter::new(), sent.clone());
+        let standby = build_standby_piper(IdleRxAdapter::new(), sent.clone());
         let config = MitModeConfig {
             timeout: Duration::from_millis(20),
             poll_interval: Duration::from_millis(1),
@@ -5207,15 +6496,15 @@ mod tests {
         };
         let enable_all = MotorEnableCommand::enable_all().to_frame();
 
-        let enable_handle = thread::spawn(move || standby.enable_mit_passthrough(config));
+        let enable_handle = thread::spawn(move || standby.enable_mit_mode(config));
         wait_until(
             Duration::from_secs(1),
             || sent.lock().expect("sent frames lock").contains(&enable_all),
-            "enable_all must be dispatched before forcing passthrough confirmation timeout",
+            "enable_all must be dispatched before forcing confirmation timeout",
         );
 
-        let err = match enable_handle.join().expect("enable_mit_passthrough thread should finish") {
-            Ok(_) => panic!("passthrough enable confirmation timeout must fail"),
+        let err = match enable_handle.join().expect("enable_mit_mode thread should finish") {
+            Ok(_) => panic!("enable confirmation timeout must fail"),
             Err(err) => err,
         };
 
@@ -5225,92 +6514,120 @@ mod tests {
             frames
                 .iter()
                 .any(|frame| *frame == MotorEnableCommand::disable_all().to_frame()),
-            "failed SoftRealtime MIT passthrough enable after dispatch must send disable_all"
+            "failed MIT enable after dispatch must send disable_all"
         );
     }
 
     #[test]
-    fn soft_mit_passthrough_command_torques_confirmed_applies_firmware_quirks() {
+    fn enable_timeout_override_bounds_wait_when_drives_never_report_enabled() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let mut standby = build_standby_piper(IdleRxAdapter::new(), sent);
+        standby.enable_timeout = Some(Duration::from_millis(30));
+        let config = MitModeConfig {
+            timeout: Duration::from_secs(10),
+            poll_interval: Duration::from_millis(1),
+            ..MitModeConfig::default()
+        };
+
+        let start = Instant::now();
+        let err = match standby.enable_mit_mode(config) {
+            Ok(_) => panic!("drives that never report enabled must time out"),
+            Err(err) => err,
+        };
+
+        assert!(
+            matches!(err, RobotError::Timeout { timeout_ms: 30 }),
+            "{err:?}"
+        );
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn enable_mit_mode_rejects_stale_historical_enabled_feedback_before_confirmation() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
         let mut frames = enabled_joint_frames_after(Duration::from_millis(5));
         frames.push(TimedFrame {
-            delay: Duration::from_millis(15),
+            delay: Duration::from_millis(80),
             frame: robot_status_frame(ControlMode::CanControl, MoveMode::MoveM, 100),
         });
         frames.push(TimedFrame {
-            delay: Duration::ZERO,
+            delay: Duration::from_millis(5),
             frame: control_mode_echo_frame(
                 piper_protocol::control::ControlModeCommand::CanControl,
                 MoveMode::MoveM,
-                70,
+                80,
                 piper_protocol::control::MitMode::Mit,
                 InstallPosition::Invalid,
                 101,
             ),
         });
 
-        let mut standby =
-            build_soft_standby_piper(PacedRxAdapter::new(frames), sent_frames.clone());
-        standby.quirks = DeviceQuirks::from_firmware_version(Version::new(1, 7, 2));
-        let active = standby
-            .enable_mit_passthrough(MitModeConfig {
-                timeout: Duration::from_millis(80),
-                debounce_threshold: 1,
-                poll_interval: Duration::from_millis(1),
-                speed_percent: 70,
-            })
-            .expect("fresh matching 0x2A1 + 0x151 should allow MIT passthrough");
-        sent_frames.lock().expect("sent frames lock").clear();
-
-        let positions =
-            JointArray::from([Rad(1.0), Rad(0.0), Rad(0.0), Rad(0.0), Rad(0.0), Rad(0.0)]);
-        let velocities = JointArray::splat(0.0);
-        let kp = JointArray::splat(8.0);
-        let kd = JointArray::splat(1.0);
-        let torques = JointArray::from([
-            NewtonMeter(4.0),
-            NewtonMeter(0.0),
-            NewtonMeter(0.0),
-            NewtonMeter(0.0),
-            NewtonMeter(0.0),
-            NewtonMeter(0.0),
-        ]);
-
-        active
-            .command_torques_confirmed(
-                &positions,
-                &velocities,
-                &kp,
-                &kd,
-                &torques,
-                Duration::from_millis(20),
-            )
-            .expect("soft passthrough torque command should send");
+        let standby = build_standby_piper_with_config(
+            PacedRxAdapter::new(frames),
+            sent_frames,
+            Some(piper_driver::PipelineConfig {
+                low_speed_drive_state_freshness_ms: 20,
+                ..piper_driver::PipelineConfig::default()
+            }),
+        );
+        wait_until(
+            Duration::from_millis(100),
+            || standby.observer().joint_enabled_mask() == 0b11_1111,
+            "historical enabled frames should arrive before staleness check",
+        );
+        thread::sleep(Duration::from_millis(40));
 
-        let frames = wait_for_sent_frames(&sent_frames, 6);
-        assert_eq!(frames.len(), 6);
+        let error = match standby.enable_mit_mode(MitModeConfig {
+            timeout: Duration::from_millis(120),
+            debounce_threshold: 1,
+            poll_interval: Duration::from_millis(1),
+            speed_percent: 80,
+        }) {
+            Ok(_) => panic!("stale historical enabled bits must not satisfy wait_for_enabled"),
+            Err(error) => error,
+        };
 
-        let expected = MitControlCommand::try_new(1, -1.0, 0.0, 8.0, 1.0, -1.0)
-            .expect("expected command should be valid")
-            .to_frame();
-        assert_eq!(frames[0].id(), expected.id());
-        assert_eq!(frames[0].data(), expected.data());
+        assert!(matches!(error, RobotError::Timeout { .. }));
     }
 
     #[test]
-    fn disable_rejects_unknown_drive_state_without_confirmed_feedback() {
+    fn enable_mit_mode_rejects_stale_enabled_state_during_mode_confirmation() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
-        let active = build_active_mit_piper(
-            DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+        let mut frames = enabled_joint_frames_after(Duration::from_millis(5));
+        frames.push(TimedFrame {
+            delay: Duration::from_millis(40),
+            frame: robot_status_frame(ControlMode::CanControl, MoveMode::MoveM, 100),
+        });
+        frames.push(TimedFrame {
+            delay: Duration::from_millis(5),
+            frame: control_mode_echo_frame(
+                piper_protocol::control::ControlModeCommand::CanControl,
+                MoveMode::MoveM,
+                80,
+                piper_protocol::control::MitMode::Mit,
+                InstallPosition::Invalid,
+                101,
+            ),
+        });
+
+        let standby = build_standby_piper_with_config(
+            PacedRxAdapter::new(frames),
             sent_frames,
+            Some(piper_driver::PipelineConfig {
+                low_speed_drive_state_freshness_ms: 20,
+                ..piper_driver::PipelineConfig::default()
+            }),
         );
 
-        let error = match active.disable(DisableConfig {
-            timeout: Duration::from_millis(30),
+        let error = match standby.enable_mit_mode(MitModeConfig {
+            timeout: Duration::from_millis(120),
             debounce_threshold: 1,
             poll_interval: Duration::from_millis(1),
+            speed_percent: 80,
         }) {
-            Ok(_) => panic!("unknown low-speed disable state must not satisfy wait_for_disabled"),
+            Ok(_) => panic!(
+                "stale enabled bits must not satisfy wait_for_mode_confirmation after wait_for_enabled"
+            ),
             Err(error) => error,
         };
 
@@ -5318,234 +6635,321 @@ mod tests {
     }
 
     #[test]
-    fn disable_succeeds_with_fresh_confirmed_disabled_feedback() {
+    fn enable_mit_mode_rejects_matching_mode_feedback_that_arrives_before_commit() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
-        let frames = disabled_joint_frames_after(Duration::from_millis(10), 1);
-        let driver = Arc::new(
-            RobotPiper::new_dual_thread_parts(
-                PacedRxAdapter::new(frames),
-                RecordingTxAdapter::new(sent_frames),
-                None,
-            )
-            .expect("driver should start"),
-        );
-        let active = build_active_mit_piper_with_driver(
-            driver,
-            DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+        let (started_tx, started_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel();
+        let mut frames = enabled_joint_frames();
+        frames.push(TimedFrame {
+            delay: Duration::from_millis(5),
+            frame: robot_status_frame(ControlMode::CanControl, MoveMode::MoveM, 100),
+        });
+        frames.push(TimedFrame {
+            delay: Duration::ZERO,
+            frame: control_mode_echo_frame(
+                piper_protocol::control::ControlModeCommand::CanControl,
+                MoveMode::MoveM,
+                80,
+                piper_protocol::control::MitMode::Mit,
+                InstallPosition::Invalid,
+                101,
+            ),
+        });
+
+        let standby = build_standby_piper_with_tx(
+            PacedRxAdapter::new(frames),
+            BlockingFirstControlTxAdapter {
+                sent_frames: sent_frames.clone(),
+                started_tx,
+                release_rx,
+                sends: 0,
+            },
         );
+        let driver = Arc::clone(&standby.driver);
+        driver
+            .send_reliable(PiperFrame::new_standard(0x221, [0x01]).unwrap())
+            .expect("blocking reliable frame should enqueue before mode switch");
 
-        active
-            .disable(DisableConfig {
-                timeout: Duration::from_millis(80),
+        let standby_handle = thread::spawn(move || {
+            standby.enable_mit_mode(MitModeConfig {
+                timeout: Duration::from_millis(120),
                 debounce_threshold: 1,
                 poll_interval: Duration::from_millis(1),
+                speed_percent: 80,
             })
-            .expect("fresh confirmed disabled feedback should allow disable transition");
+        });
+
+        started_rx
+            .recv_timeout(Duration::from_millis(200))
+            .expect("mode command should block at tx.send_control before commit");
+        thread::sleep(Duration::from_millis(20));
+        let _ = release_tx.send(());
+
+        let error = match standby_handle.join().expect("enable_mit_mode thread should finish") {
+            Ok(_) => panic!("pre-commit matching feedback must not satisfy mode confirmation"),
+            Err(error) => error,
+        };
+        assert!(matches!(error, RobotError::Timeout { .. }));
     }
 
     #[test]
-    fn active_request_disable_all_transitions_via_maintenance_to_standby() {
+    fn enable_mit_mode_rejects_enabled_feedback_that_arrives_before_enable_commit() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
-        let frames = disabled_joint_frames_after(Duration::from_millis(10), 1);
-        let driver = Arc::new(
-            RobotPiper::new_dual_thread_parts(
-                PacedRxAdapter::new(frames),
-                RecordingTxAdapter::new(sent_frames.clone()),
-                None,
-            )
-            .expect("driver should start"),
-        );
-        let active = build_active_mit_piper_with_driver(
-            driver,
-            DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+        let (started_tx, started_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel();
+
+        let standby = build_standby_piper_with_tx(
+            PacedRxAdapter::new(enabled_joint_frames()),
+            BlockingFirstControlTxAdapter {
+                sent_frames: sent_frames.clone(),
+                started_tx,
+                release_rx,
+                sends: 0,
+            },
         );
+        let driver = Arc::clone(&standby.driver);
+        driver
+            .send_reliable(PiperFrame::new_standard(0x221, [0x02]).unwrap())
+            .expect("blocking reliable frame should enqueue before enable_all");
 
-        let maintenance =
-            active.request_disable_all().expect("disable request should enter maintenance");
-        let standby = maintenance
-            .wait_until_disabled(DisableConfig {
-                timeout: Duration::from_millis(80),
+        let standby_handle = thread::spawn(move || {
+            standby.enable_mit_mode(MitModeConfig {
+                timeout: Duration::from_millis(120),
                 debounce_threshold: 1,
                 poll_interval: Duration::from_millis(1),
+                speed_percent: 80,
             })
-            .expect("fresh disabled feedback should converge to standby");
+        });
 
-        assert!(matches!(standby._state, Standby));
-        let sent = sent_frames.lock().expect("sent frames lock");
-        assert_eq!(
-            sent.first().copied(),
-            Some(piper_protocol::control::MotorEnableCommand::disable_all().to_frame())
+        started_rx
+            .recv_timeout(Duration::from_millis(200))
+            .expect("blocking reliable frame should reach the adapter first");
+        wait_until(
+            Duration::from_millis(200),
+            || driver.get_robot_control().confirmed_driver_enabled_mask == Some(0b11_1111),
+            "enabled low-speed feedback should land before enable_all reaches TX commit",
+        );
+
+        release_tx.send(()).expect("blocked reliable frame should release");
+        let error = match standby_handle.join().expect("enable_mit_mode thread should finish") {
+            Ok(_) => panic!("pre-commit enabled feedback must not satisfy wait_for_enabled"),
+            Err(error) => error,
+        };
+
+        assert!(matches!(error, RobotError::Timeout { .. }));
+        assert!(
+            !sent_frames
+                .lock()
+                .expect("sent frames lock")
+                .iter()
+                .any(|frame| frame.id() == piper_protocol::ids::ID_CONTROL_MODE.into()),
+            "mode switch must not start when enable confirmation only existed before commit",
         );
     }
 
     #[test]
-    fn active_request_disable_all_keeps_shared_driver_control_closed_until_disabled_feedback() {
+    fn enable_mit_mode_rejects_non_normal_robot_status_even_if_drives_are_enabled() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
-        let frames = disabled_joint_frames_after(Duration::from_millis(10), 1);
-        let driver = Arc::new(
-            RobotPiper::new_dual_thread_parts(
-                PacedRxAdapter::new(frames),
-                RecordingTxAdapter::new(sent_frames),
-                None,
-            )
-            .expect("driver should start"),
-        );
-        let active = build_active_mit_piper_with_driver(
-            driver.clone(),
-            DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
-        );
-
-        let maintenance =
-            active.request_disable_all().expect("disable request should enter maintenance");
-
-        assert!(matches!(
-            maintenance
-                .driver
-                .send_reliable(PiperFrame::new_standard(0x151, [0x09]).unwrap()),
-            Err(piper_driver::DriverError::ControlPathClosed)
-        ));
+        let mut frames = enabled_joint_frames();
+        frames.push(TimedFrame {
+            delay: Duration::from_millis(15),
+            frame: robot_status_frame_with_status(
+                ControlMode::CanControl,
+                RobotStatus::EmergencyStop,
+                MoveMode::MoveM,
+                100,
+            ),
+        });
+        frames.push(TimedFrame {
+            delay: Duration::from_millis(5),
+            frame: control_mode_echo_frame(
+                piper_protocol::control::ControlModeCommand::CanControl,
+                MoveMode::MoveM,
+                80,
+                piper_protocol::control::MitMode::Mit,
+                InstallPosition::Invalid,
+                101,
+            ),
+        });
 
-        maintenance
-            .wait_until_disabled(DisableConfig {
-                timeout: Duration::from_millis(80),
-                debounce_threshold: 1,
-                poll_interval: Duration::from_millis(1),
-            })
-            .expect("fresh disabled feedback should eventually reopen the control path");
+        let standby = build_standby_piper(PacedRxAdapter::new(frames), sent_frames);
+        let error = match standby.enable_mit_mode(MitModeConfig {
+            timeout: Duration::from_millis(80),
+            debounce_threshold: 1,
+            poll_interval: Duration::from_millis(1),
+            speed_percent: 80,
+        }) {
+            Ok(_) => panic!("non-Normal 0x2A1 must prevent Active<MitMode>"),
+            Err(error) => error,
+        };
 
-        driver
-            .send_reliable(PiperFrame::new_standard(0x151, [0x0A]).unwrap())
-            .expect("shared driver should reopen once disabled feedback is confirmed");
+        assert!(matches!(error, RobotError::Timeout { .. }));
     }
 
     #[test]
-    fn active_request_disable_all_reopens_driver_when_disabled_feedback_arrives_before_send_returns()
-     {
+    fn enable_mit_passthrough_succeeds_with_fresh_matching_robot_and_echo() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
-        let (started_tx, started_rx) = mpsc::channel();
-        let (release_tx, release_rx) = mpsc::channel();
-        let frames = disabled_joint_frames_after(Duration::from_millis(20), 100);
-        let driver = Arc::new(
-            RobotPiper::new_dual_thread_parts(
-                PacedRxAdapter::new(frames),
-                BlockingFirstControlTxAdapter {
-                    sent_frames: sent_frames.clone(),
-                    started_tx,
-                    release_rx,
-                    sends: 0,
-                },
-                None,
-            )
-            .expect("driver should start"),
-        );
-        let active = build_active_mit_piper_with_driver(
-            driver.clone(),
-            DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
-        );
-
-        let disable_handle = thread::spawn(move || active.request_disable_all());
-
-        started_rx
-            .recv_timeout(Duration::from_millis(200))
-            .expect("disable frame should enter the adapter before the release gate opens");
-        wait_until(
-            Duration::from_millis(200),
-            || driver.get_robot_control().confirmed_driver_enabled_mask == Some(0),
-            "disabled low-speed feedback should land before the control send is released",
-        );
-
-        release_tx.send(()).expect("blocked control send should release cleanly");
-        let maintenance = disable_handle
-            .join()
-            .expect("disable request thread should finish")
-            .expect("disable request should still enter maintenance");
+        let mut frames = enabled_joint_frames_after(Duration::from_millis(5));
+        frames.push(TimedFrame {
+            delay: Duration::from_millis(15),
+            frame: robot_status_frame(ControlMode::CanControl, MoveMode::MoveM, 100),
+        });
+        frames.push(TimedFrame {
+            delay: Duration::ZERO,
+            frame: control_mode_echo_frame(
+                piper_protocol::control::ControlModeCommand::CanControl,
+                MoveMode::MoveM,
+                70,
+                piper_protocol::control::MitMode::Mit,
+                InstallPosition::Invalid,
+                101,
+            ),
+        });
 
-        let standby = maintenance
-            .wait_until_disabled(DisableConfig {
+        let standby = build_soft_standby_piper(PacedRxAdapter::new(frames), sent_frames);
+        let active = standby
+            .enable_mit_passthrough(MitModeConfig {
                 timeout: Duration::from_millis(80),
                 debounce_threshold: 1,
                 poll_interval: Duration::from_millis(1),
+                speed_percent: 70,
             })
-            .expect("already-confirmed disabled feedback should still converge to standby");
+            .expect("fresh matching 0x2A1 + 0x151 should allow MIT passthrough");
 
-        assert!(matches!(standby._state, Standby));
-        driver
-            .send_reliable(PiperFrame::new_standard(0x151, [0x0B]).unwrap())
-            .expect(
-                "driver should not remain stuck in StateTransitionClosed after request_disable_all returns against already-confirmed disabled feedback",
-            );
+        assert!(matches!(active._state, Active(MitPassthroughMode)));
     }
 
     #[test]
-    fn maintenance_partial_joint_power_commands_send_expected_frames() {
-        let sent_frames = Arc::new(Mutex::new(Vec::new()));
-        let standby = build_standby_piper(IdleRxAdapter::new(), sent_frames.clone());
+    fn enable_mit_passthrough_timeout_after_enable_dispatch_sends_disable_all() {
+        use piper_protocol::control::MotorEnableCommand;
 
-        let maintenance = standby
-            .into_maintenance()
-            .enable_joint(Joint::J2)
-            .expect("maintenance enable should succeed");
-        let _maintenance = maintenance
-            .disable_joint(Joint::J2)
-            .expect("maintenance disable should succeed");
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let standby = build_soft_standby_piper(IdleRxAdapter::new(), sent.clone());
+        let config = MitModeConfig {
+            timeout: Duration::from_millis(20),
+            poll_interval: Duration::from_millis(1),
+            ..MitModeConfig::default()
+        };
+        let enable_all = MotorEnableCommand::enable_all().to_frame();
 
+        let enable_handle = thread::spawn(move || standby.enable_mit_passthrough(config));
         wait_until(
-            Duration::from_millis(200),
-            || sent_frames.lock().expect("sent frames lock").len() >= 2,
-            "maintenance partial power commands should be sent",
+            Duration::from_secs(1),
+            || sent.lock().expect("sent frames lock").contains(&enable_all),
+            "enable_all must be dispatched before forcing passthrough confirmation timeout",
         );
 
-        let sent = sent_frames.lock().expect("sent frames lock").clone();
-        assert_eq!(
-            sent,
-            vec![
-                piper_protocol::control::MotorEnableCommand::enable(2).to_frame(),
-                piper_protocol::control::MotorEnableCommand::disable(2).to_frame(),
-            ]
+        let err = match enable_handle.join().expect("enable_mit_passthrough thread should finish") {
+            Ok(_) => panic!("passthrough enable confirmation timeout must fail"),
+            Err(err) => err,
+        };
+
+        assert!(matches!(err, RobotError::Timeout { .. }));
+        let frames = wait_for_sent_frames(&sent, 2);
+        assert!(
+            frames
+                .iter()
+                .any(|frame| *frame == MotorEnableCommand::disable_all().to_frame()),
+            "failed SoftRealtime MIT passthrough enable after dispatch must send disable_all"
         );
     }
 
     #[test]
-    fn maintenance_wait_until_disabled_fails_fast_when_runtime_fault_latches() {
+    fn soft_mit_passthrough_command_torques_confirmed_applies_firmware_quirks() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
-        let active = build_active_mit_piper(
-            DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
-            sent_frames,
-        );
-        let maintenance =
-            active.request_disable_all().expect("disable request should enter maintenance");
-        let driver = Arc::clone(&maintenance.driver);
-        let latch_handle = thread::spawn(move || {
-            thread::sleep(Duration::from_millis(5));
-            driver.latch_fault();
+        let mut frames = enabled_joint_frames_after(Duration::from_millis(5));
+        frames.push(TimedFrame {
+            delay: Duration::from_millis(15),
+            frame: robot_status_frame(ControlMode::CanControl, MoveMode::MoveM, 100),
+        });
+        frames.push(TimedFrame {
+            delay: Duration::ZERO,
+            frame: control_mode_echo_frame(
+                piper_protocol::control::ControlModeCommand::CanControl,
+                MoveMode::MoveM,
+                70,
+                piper_protocol::control::MitMode::Mit,
+                InstallPosition::Invalid,
+                101,
+            ),
         });
 
-        let error = match maintenance.wait_until_disabled(DisableConfig {
-            timeout: Duration::from_millis(200),
+        let mut standby =
+            build_soft_standby_piper(PacedRxAdapter::new(frames), sent_frames.clone());
+        standby.quirks = DeviceQuirks::from_firmware_version(Version::new(1, 7, 2));
+        let active = standby
+            .enable_mit_passthrough(MitModeConfig {
+                timeout: Duration::from_millis(80),
+                debounce_threshold: 1,
+                poll_interval: Duration::from_millis(1),
+                speed_percent: 70,
+            })
+            .expect("fresh matching 0x2A1 + 0x151 should allow MIT passthrough");
+        sent_frames.lock().expect("sent frames lock").clear();
+
+        let positions =
+            JointArray::from([Rad(1.0), Rad(0.0), Rad(0.0), Rad(0.0), Rad(0.0), Rad(0.0)]);
+        let velocities = JointArray::splat(0.0);
+        let kp = JointArray::splat(8.0);
+        let kd = JointArray::splat(1.0);
+        let torques = JointArray::from([
+            NewtonMeter(4.0),
+            NewtonMeter(0.0),
+            NewtonMeter(0.0),
+            NewtonMeter(0.0),
+            NewtonMeter(0.0),
+            NewtonMeter(0.0),
+        ]);
+
+        active
+            .command_torques_confirmed(
+                &positions,
+                &velocities,
+                &kp,
+                &kd,
+                &torques,
+                Duration::from_millis(20),
+            )
+            .expect("soft passthrough torque command should send");
+
+        let frames = wait_for_sent_frames(&sent_frames, 6);
+        assert_eq!(frames.len(), 6);
+
+        let expected = MitControlCommand::try_new(1, -1.0, 0.0, 8.0, 1.0, -1.0)
+            .expect("expected command should be valid")
+            .to_frame();
+        assert_eq!(frames[0].id(), expected.id());
+        assert_eq!(frames[0].data(), expected.data());
+    }
+
+    #[test]
+    fn disable_rejects_unknown_drive_state_without_confirmed_feedback() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let active = build_active_mit_piper(
+            DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+            sent_frames,
+        );
+
+        let error = match active.disable(DisableConfig {
+            timeout: Duration::from_millis(30),
             debounce_threshold: 1,
             poll_interval: Duration::from_millis(1),
         }) {
-            Ok(_) => panic!("runtime fault should fail fast during disable wait"),
+            Ok(_) => panic!("unknown low-speed disable state must not satisfy wait_for_disabled"),
             Err(error) => error,
         };
-        latch_handle.join().expect("fault latch helper should finish cleanly");
 
-        assert!(matches!(
-            error,
-            RobotError::RuntimeHealthUnhealthy {
-                fault: Some(RuntimeFaultKind::ManualFault),
-                ..
-            }
-        ));
+        assert!(matches!(error, RobotError::Timeout { .. }));
     }
 
     #[test]
-    fn shutdown_only_sends_disable_all_for_active_mit() {
+    fn disable_succeeds_with_fresh_confirmed_disabled_feedback() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let frames = disabled_joint_frames_after(Duration::from_millis(10), 1);
         let driver = Arc::new(
             RobotPiper::new_dual_thread_parts(
-                PacedRxAdapter::new(disabled_joint_frames_after(Duration::from_millis(10), 10)),
-                RecordingTxAdapter::new(sent_frames.clone()),
+                PacedRxAdapter::new(frames),
+                RecordingTxAdapter::new(sent_frames),
                 None,
             )
             .expect("driver should start"),
@@ -5556,61 +6960,57 @@ mod tests {
         );
 
         active
-            .shutdown()
-            .expect("shutdown should succeed once disabled feedback is confirmed");
-
-        let sent = sent_frames.lock().expect("sent frames lock").clone();
-        assert_eq!(
-            sent,
-            vec![piper_protocol::control::MotorEnableCommand::disable_all().to_frame()]
-        );
-        assert!(
-            !sent.contains(
-                &piper_protocol::control::EmergencyStopCommand::emergency_stop().to_frame()
-            ),
-            "normal shutdown must not emit emergency-stop semantics",
-        );
+            .disable(DisableConfig {
+                timeout: Duration::from_millis(80),
+                debounce_threshold: 1,
+                poll_interval: Duration::from_millis(1),
+            })
+            .expect("fresh confirmed disabled feedback should allow disable transition");
     }
 
     #[test]
-    fn shutdown_only_sends_disable_all_for_active_position() {
+    fn active_request_disable_all_transitions_via_maintenance_to_standby() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let frames = disabled_joint_frames_after(Duration::from_millis(10), 1);
         let driver = Arc::new(
             RobotPiper::new_dual_thread_parts(
-                PacedRxAdapter::new(disabled_joint_frames_after(Duration::from_millis(10), 40)),
+                PacedRxAdapter::new(frames),
                 RecordingTxAdapter::new(sent_frames.clone()),
                 None,
             )
             .expect("driver should start"),
         );
-        let active = build_active_position_piper(driver);
+        let active = build_active_mit_piper_with_driver(
+            driver,
+            DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+        );
 
-        active
-            .shutdown()
-            .expect("position-mode shutdown should succeed once disabled feedback is confirmed");
+        let maintenance =
+            active.request_disable_all().expect("disable request should enter maintenance");
+        let standby = maintenance
+            .wait_until_disabled(DisableConfig {
+                timeout: Duration::from_millis(80),
+                debounce_threshold: 1,
+                poll_interval: Duration::from_millis(1),
+            })
+            .expect("fresh disabled feedback should converge to standby");
 
-        let sent = sent_frames.lock().expect("sent frames lock").clone();
+        assert!(matches!(standby._state, Standby));
+        let sent = sent_frames.lock().expect("sent frames lock");
         assert_eq!(
-            sent,
-            vec![piper_protocol::control::MotorEnableCommand::disable_all().to_frame()]
-        );
-        assert!(
-            !sent.contains(
-                &piper_protocol::control::EmergencyStopCommand::emergency_stop().to_frame()
-            ),
-            "normal shutdown must not emit emergency-stop semantics",
+            sent.first().copied(),
+            Some(piper_protocol::control::MotorEnableCommand::disable_all().to_frame())
         );
     }
 
     #[test]
-    fn shutdown_timeout_latches_transport_fault_and_closes_control_path() {
+    fn active_request_disable_all_keeps_shared_driver_control_closed_until_disabled_feedback() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let frames = disabled_joint_frames_after(Duration::from_millis(10), 1);
         let driver = Arc::new(
             RobotPiper::new_dual_thread_parts(
-                IdleRxAdapter::new(),
-                TimeoutControlTxAdapter {
-                    sent_frames: sent_frames.clone(),
-                },
+                PacedRxAdapter::new(frames),
+                RecordingTxAdapter::new(sent_frames),
                 None,
             )
             .expect("driver should start"),
@@ -5620,38 +7020,44 @@ mod tests {
             DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
         );
 
-        let error = match active.shutdown() {
-            Ok(_) => panic!("state-transition disable timeout must fail closed"),
-            Err(error) => error,
-        };
+        let maintenance =
+            active.request_disable_all().expect("disable request should enter maintenance");
 
         assert!(matches!(
-            error,
-            RobotError::Infrastructure(piper_driver::DriverError::Timeout)
-        ));
-        assert_eq!(
-            driver.health().fault,
-            Some(RuntimeFaultKind::TransportError),
-            "shutdown timeout must latch a transport fault before returning control",
-        );
-        assert!(matches!(
-            driver.send_reliable(PiperFrame::new_standard(0x151, [0x08]).unwrap()),
+            maintenance
+                .driver
+                .send_reliable(PiperFrame::new_standard(0x151, [0x09]).unwrap()),
             Err(piper_driver::DriverError::ControlPathClosed)
         ));
-        assert_eq!(
-            driver.maintenance_lease_snapshot().state(),
-            piper_driver::MaintenanceGateState::DeniedFaulted
-        );
+
+        maintenance
+            .wait_until_disabled(DisableConfig {
+                timeout: Duration::from_millis(80),
+                debounce_threshold: 1,
+                poll_interval: Duration::from_millis(1),
+            })
+            .expect("fresh disabled feedback should eventually reopen the control path");
+
+        driver
+            .send_reliable(PiperFrame::new_standard(0x151, [0x0A]).unwrap())
+            .expect("shared driver should reopen once disabled feedback is confirmed");
     }
 
     #[test]
-    fn request_disable_all_timeout_latches_transport_fault_and_closes_control_path() {
+    fn active_request_disable_all_reopens_driver_when_disabled_feedback_arrives_before_send_returns()
+     {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let (started_tx, started_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel();
+        let frames = disabled_joint_frames_after(Duration::from_millis(20), 100);
         let driver = Arc::new(
             RobotPiper::new_dual_thread_parts(
-                IdleRxAdapter::new(),
-                TimeoutControlTxAdapter {
+                PacedRxAdapter::new(frames),
+                BlockingFirstControlTxAdapter {
                     sent_frames: sent_frames.clone(),
+                    started_tx,
+                    release_rx,
+                    sends: 0,
                 },
                 None,
             )
@@ -5662,49 +7068,268 @@ mod tests {
             DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
         );
 
-        let error = match active.request_disable_all() {
-            Ok(_) => panic!("disable request timeout must fail closed"),
-            Err(error) => error,
-        };
-
-        assert!(matches!(
-            error,
-            RobotError::Infrastructure(piper_driver::DriverError::Timeout)
-        ));
-        assert_eq!(
-            driver.health().fault,
-            Some(RuntimeFaultKind::TransportError),
-            "disable request timeout must latch a transport fault before returning control",
-        );
-        assert!(matches!(
-            driver.send_reliable(PiperFrame::new_standard(0x151, [0x09]).unwrap()),
-            Err(piper_driver::DriverError::ControlPathClosed)
-        ));
-        assert_eq!(
-            driver.maintenance_lease_snapshot().state(),
-            piper_driver::MaintenanceGateState::DeniedFaulted
-        );
-    }
+        let disable_handle = thread::spawn(move || active.request_disable_all());
 
-    #[test]
-    fn mit_controller_move_to_rest_requires_explicit_rest_position() {
-        let sent_frames = Arc::new(Mutex::new(Vec::new()));
-        let driver = Arc::new(
-            RobotPiper::new_dual_thread_parts(
-                PacedRxAdapter::new(control_snapshot_frames(1_000)),
-                RecordingTxAdapter::new(sent_frames.clone()),
-                None,
-            )
-            .expect("driver should start"),
-        );
-        let active = build_active_mit_piper_with_driver(
-            driver,
-            DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+        started_rx
+            .recv_timeout(Duration::from_millis(200))
+            .expect("disable frame should enter the adapter before the release gate opens");
+        wait_until(
+            Duration::from_millis(200),
+            || driver.get_robot_control().confirmed_driver_enabled_mask == Some(0),
+            "disabled low-speed feedback should land before the control send is released",
         );
-        let mut controller = MitController::new(active, MitControllerConfig::default())
-            .expect("strict realtime driver should support MitController");
 
-        let error = controller
+        release_tx.send(()).expect("blocked control send should release cleanly");
+        let maintenance = disable_handle
+            .join()
+            .expect("disable request thread should finish")
+            .expect("disable request should still enter maintenance");
+
+        let standby = maintenance
+            .wait_until_disabled(DisableConfig {
+                timeout: Duration::from_millis(80),
+                debounce_threshold: 1,
+                poll_interval: Duration::from_millis(1),
+            })
+            .expect("already-confirmed disabled feedback should still converge to standby");
+
+        assert!(matches!(standby._state, Standby));
+        driver
+            .send_reliable(PiperFrame::new_standard(0x151, [0x0B]).unwrap())
+            .expect(
+                "driver should not remain stuck in StateTransitionClosed after request_disable_all returns against already-confirmed disabled feedback",
+            );
+    }
+
+    #[test]
+    fn maintenance_partial_joint_power_commands_send_expected_frames() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let standby = build_standby_piper(IdleRxAdapter::new(), sent_frames.clone());
+
+        let maintenance = standby
+            .into_maintenance()
+            .enable_joint(Joint::J2)
+            .expect("maintenance enable should succeed");
+        let _maintenance = maintenance
+            .disable_joint(Joint::J2)
+            .expect("maintenance disable should succeed");
+
+        wait_until(
+            Duration::from_millis(200),
+            || sent_frames.lock().expect("sent frames lock").len() >= 2,
+            "maintenance partial power commands should be sent",
+        );
+
+        let sent = sent_frames.lock().expect("sent frames lock").clone();
+        assert_eq!(
+            sent,
+            vec![
+                piper_protocol::control::MotorEnableCommand::enable(2).to_frame(),
+                piper_protocol::control::MotorEnableCommand::disable(2).to_frame(),
+            ]
+        );
+    }
+
+    #[test]
+    fn maintenance_wait_until_disabled_fails_fast_when_runtime_fault_latches() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let active = build_active_mit_piper(
+            DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+            sent_frames,
+        );
+        let maintenance =
+            active.request_disable_all().expect("disable request should enter maintenance");
+        let driver = Arc::clone(&maintenance.driver);
+        let latch_handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(5));
+            driver.latch_fault();
+        });
+
+        let error = match maintenance.wait_until_disabled(DisableConfig {
+            timeout: Duration::from_millis(200),
+            debounce_threshold: 1,
+            poll_interval: Duration::from_millis(1),
+        }) {
+            Ok(_) => panic!("runtime fault should fail fast during disable wait"),
+            Err(error) => error,
+        };
+        latch_handle.join().expect("fault latch helper should finish cleanly");
+
+        assert!(matches!(
+            error,
+            RobotError::RuntimeHealthUnhealthy {
+                fault: Some(RuntimeFaultKind::ManualFault),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn shutdown_only_sends_disable_all_for_active_mit() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let driver = Arc::new(
+            RobotPiper::new_dual_thread_parts(
+                PacedRxAdapter::new(disabled_joint_frames_after(Duration::from_millis(10), 10)),
+                RecordingTxAdapter::new(sent_frames.clone()),
+                None,
+            )
+            .expect("driver should start"),
+        );
+        let active = build_active_mit_piper_with_driver(
+            driver,
+            DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+        );
+
+        active
+            .shutdown()
+            .expect("shutdown should succeed once disabled feedback is confirmed");
+
+        let sent = sent_frames.lock().expect("sent frames lock").clone();
+        assert_eq!(
+            sent,
+            vec![piper_protocol::control::MotorEnableCommand::disable_all().to_frame()]
+        );
+        assert!(
+            !sent.contains(
+                &piper_protocol::control::EmergencyStopCommand::emergency_stop().to_frame()
+            ),
+            "normal shutdown must not emit emergency-stop semantics",
+        );
+    }
+
+    #[test]
+    fn shutdown_only_sends_disable_all_for_active_position() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let driver = Arc::new(
+            RobotPiper::new_dual_thread_parts(
+                PacedRxAdapter::new(disabled_joint_frames_after(Duration::from_millis(10), 40)),
+                RecordingTxAdapter::new(sent_frames.clone()),
+                None,
+            )
+            .expect("driver should start"),
+        );
+        let active = build_active_position_piper(driver);
+
+        active
+            .shutdown()
+            .expect("position-mode shutdown should succeed once disabled feedback is confirmed");
+
+        let sent = sent_frames.lock().expect("sent frames lock").clone();
+        assert_eq!(
+            sent,
+            vec![piper_protocol::control::MotorEnableCommand::disable_all().to_frame()]
+        );
+        assert!(
+            !sent.contains(
+                &piper_protocol::control::EmergencyStopCommand::emergency_stop().to_frame()
+            ),
+            "normal shutdown must not emit emergency-stop semantics",
+        );
+    }
+
+    #[test]
+    fn shutdown_timeout_latches_transport_fault_and_closes_control_path() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let driver = Arc::new(
+            RobotPiper::new_dual_thread_parts(
+                IdleRxAdapter::new(),
+                TimeoutControlTxAdapter {
+                    sent_frames: sent_frames.clone(),
+                },
+                None,
+            )
+            .expect("driver should start"),
+        );
+        let active = build_active_mit_piper_with_driver(
+            driver.clone(),
+            DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+        );
+
+        let error = match active.shutdown() {
+            Ok(_) => panic!("state-transition disable timeout must fail closed"),
+            Err(error) => error,
+        };
+
+        assert!(matches!(
+            error,
+            RobotError::Infrastructure(piper_driver::DriverError::Timeout)
+        ));
+        assert_eq!(
+            driver.health().fault,
+            Some(RuntimeFaultKind::TransportError),
+            "shutdown timeout must latch a transport fault before returning control",
+        );
+        assert!(matches!(
+            driver.send_reliable(PiperFrame::new_standard(0x151, [0x08]).unwrap()),
+            Err(piper_driver::DriverError::ControlPathClosed)
+        ));
+        assert_eq!(
+            driver.maintenance_lease_snapshot().state(),
+            piper_driver::MaintenanceGateState::DeniedFaulted
+        );
+    }
+
+    #[test]
+    fn request_disable_all_timeout_latches_transport_fault_and_closes_control_path() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let driver = Arc::new(
+            RobotPiper::new_dual_thread_parts(
+                IdleRxAdapter::new(),
+                TimeoutControlTxAdapter {
+                    sent_frames: sent_frames.clone(),
+                },
+                None,
+            )
+            .expect("driver should start"),
+        );
+        let active = build_active_mit_piper_with_driver(
+            driver.clone(),
+            DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+        );
+
+        let error = match active.request_disable_all() {
+            Ok(_) => panic!("disable request timeout must fail closed"),
+            Err(error) => error,
+        };
+
+        assert!(matches!(
+            error,
+            RobotError::Infrastructure(piper_driver::DriverError::Timeout)
+        ));
+        assert_eq!(
+            driver.health().fault,
+            Some(RuntimeFaultKind::TransportError),
+            "disable request timeout must latch a transport fault before returning control",
+        );
+        assert!(matches!(
+            driver.send_reliable(PiperFrame::new_standard(0x151, [0x09]).unwrap()),
+            Err(piper_driver::DriverError::ControlPathClosed)
+        ));
+        assert_eq!(
+            driver.maintenance_lease_snapshot().state(),
+            piper_driver::MaintenanceGateState::DeniedFaulted
+        );
+    }
+
+    #[test]
+    fn mit_controller_move_to_rest_requires_explicit_rest_position() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let driver = Arc::new(
+            RobotPiper::new_dual_thread_parts(
+                PacedRxAdapter::new(control_snapshot_frames(1_000)),
+                RecordingTxAdapter::new(sent_frames.clone()),
+                None,
+            )
+            .expect("driver should start"),
+        );
+        let active = build_active_mit_piper_with_driver(
+            driver,
+            DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+        );
+        let mut controller = MitController::new(active, MitControllerConfig::default())
+            .expect("strict realtime driver should support MitController");
+
+        let error = controller
             .move_to_rest(Rad(0.01), Duration::from_millis(50))
             .expect_err("move_to_rest must fail closed when rest_position is absent");
 
@@ -6304,11 +7929,87 @@ mod tests {
     }
 
     #[test]
-    fn set_joint_zero_positions_fails_fast_when_runtime_fault_latches() {
+    fn zeroing_session_commits_each_joint_and_reports_progress() {
+        use crate::control::{JointZeroingStatus, ZeroingConfirmToken, ZeroingEvent};
+
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
-        let standby = build_standby_piper(IdleRxAdapter::new(), sent_frames);
-        let driver = Arc::clone(&standby.driver);
-        let latch_handle = thread::spawn(move || {
+        let standby = build_standby_piper(
+            PacedRxAdapter::new(vec![TimedFrame {
+                delay: Duration::from_millis(5),
+                frame: setting_response_frame(0x75, true, 10),
+            }]),
+            sent_frames.clone(),
+        );
+        let events = std::cell::RefCell::new(Vec::new());
+
+        let mut session = standby
+            .begin_zeroing()
+            .joints(&[Joint::J1, Joint::J2, Joint::J1])
+            .on_progress(|event| events.borrow_mut().push(event.clone()));
+        assert_eq!(session.advance(), Some(Joint::J1));
+        assert_eq!(session.skip(), Some(Joint::J1));
+        assert_eq!(session.advance(), Some(Joint::J2));
+        session
+            .commit(ZeroingConfirmToken::confirm_for_test())
+            .expect("zeroing J2 should wait for 0x476 confirmation");
+        assert_eq!(session.advance(), None);
+        let report = session.finish();
+
+        assert!(!report.is_complete());
+        assert_eq!(report.zeroed_joints(), vec![Joint::J2]);
+        assert_eq!(report.joints[0].status, JointZeroingStatus::Skipped);
+        let sent = sent_frames.lock().expect("sent frames lock").clone();
+        assert_eq!(
+            sent[0],
+            piper_protocol::config::JointSettingCommand::set_zero_point(2).to_frame()
+        );
+
+        let events = events.into_inner();
+        assert_eq!(
+            events.first(),
+            Some(&ZeroingEvent::JointStarted {
+                joint: Joint::J1,
+                step: 1,
+                total: 2,
+            })
+        );
+        assert!(events.contains(&ZeroingEvent::JointSkipped { joint: Joint::J1 }));
+        assert!(matches!(
+            events[3],
+            ZeroingEvent::JointZeroed {
+                joint: Joint::J2,
+                ..
+            }
+        ));
+        assert_eq!(
+            events.last(),
+            Some(&ZeroingEvent::Finished {
+                zeroed: 1,
+                total: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn zeroing_session_commit_requires_selected_joint() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let standby = build_standby_piper(IdleRxAdapter::new(), sent_frames.clone());
+
+        let mut session = standby.begin_zeroing();
+        let error = session
+            .commit(crate::control::ZeroingConfirmToken::confirm_for_test())
+            .expect_err("commit without advance should be rejected");
+
+        assert!(matches!(error, RobotError::ConfigError(_)));
+        assert!(sent_frames.lock().expect("sent frames lock").is_empty());
+    }
+
+    #[test]
+    fn set_joint_zero_positions_fails_fast_when_runtime_fault_latches() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let standby = build_standby_piper(IdleRxAdapter::new(), sent_frames);
+        let driver = Arc::clone(&standby.driver);
+        let latch_handle = thread::spawn(move || {
             thread::sleep(Duration::from_millis(5));
             driver.latch_fault();
         });
@@ -6327,6 +8028,51 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn gripper_commander_clamps_to_travel_and_effort_range() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let driver = Arc::new(
+            RobotPiper::new_dual_thread_parts(
+                IdleRxAdapter::new(),
+                RecordingTxAdapter::new(sent_frames.clone()),
+                None,
+            )
+            .expect("driver should start"),
+        );
+        let robot = build_active_position_piper(driver);
+        let gripper = robot.gripper().with_effort(NewtonMeter(2.0));
+
+        gripper
+            .set_position(Millimeter(250.0), NewtonMeter(9.0))
+            .expect("out-of-range travel should be clamped");
+        gripper.close().expect("close should succeed");
+        gripper.disable().expect("disable should succeed");
+        assert!(matches!(
+            gripper.set_position(Millimeter(f64::NAN), NewtonMeter(1.0)),
+            Err(RobotError::ConfigError(_))
+        ));
+
+        wait_until(
+            Duration::from_millis(200),
+            || sent_frames.lock().expect("sent frames lock").len() >= 3,
+            "gripper frames should be sent",
+        );
+        let sent = sent_frames.lock().expect("sent frames lock").clone();
+        assert_eq!(sent.len(), 3);
+        assert_eq!(
+            sent[0],
+            piper_protocol::control::GripperControlCommand::new(100.0, 5.0, true).to_frame()
+        );
+        assert_eq!(
+            sent[1],
+            piper_protocol::control::GripperControlCommand::new(0.0, 2.0, true).to_frame()
+        );
+        assert_eq!(
+            sent[2],
+            piper_protocol::control::GripperControlCommand::new(0.0, 0.0, false).to_frame()
+        );
+    }
+
     #[test]
     fn command_position_from_snapshot_does_not_require_driver_feedback() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
@@ -6365,6 +8111,8 @@ mod tests {
                     notes: "test".to_string(),
                     operator: "tester".to_string(),
                 },
+                mode: Default::default(),
+                compression: Default::default(),
             })
             .expect("recording should start");
 
@@ -6400,6 +8148,8 @@ mod tests {
                     notes: "test".to_string(),
                     operator: "tester".to_string(),
                 },
+                mode: Default::default(),
+                compression: Default::default(),
             })
             .expect("recording should start");
 
@@ -6435,6 +8185,8 @@ mod tests {
                     notes: "test".to_string(),
                     operator: "tester".to_string(),
                 },
+                mode: Default::default(),
+                compression: Default::default(),
             })
             .expect("recording should start");
 
@@ -6470,6 +8222,8 @@ mod tests {
                     notes: "test".to_string(),
                     operator: "tester".to_string(),
                 },
+                mode: Default::default(),
+                compression: Default::default(),
             })
             .expect("recording should start");
 
@@ -6517,6 +8271,8 @@ mod tests {
                     notes: "metadata note".to_string(),
                     operator: "metadata operator".to_string(),
                 },
+                mode: Default::default(),
+                compression: Default::default(),
             })
             .expect("recording should start");
 
@@ -6559,6 +8315,8 @@ mod tests {
                     notes: "start-time".to_string(),
                     operator: "tester".to_string(),
                 },
+                mode: Default::default(),
+                compression: Default::default(),
             })
             .expect("recording should start");
 
@@ -6599,6 +8357,8 @@ mod tests {
                     notes: "test".to_string(),
                     operator: "tester".to_string(),
                 },
+                mode: Default::default(),
+                compression: Default::default(),
             })
             .expect("recording should start");
 
@@ -6641,6 +8401,8 @@ mod tests {
                     notes: "test".to_string(),
                     operator: "tester".to_string(),
                 },
+                mode: Default::default(),
+                compression: Default::default(),
             })
             .expect("recording should start");
 
@@ -6672,6 +8434,144 @@ mod tests {
         let _ = std::fs::remove_file(output_path);
     }
 
+    #[test]
+    fn streaming_recording_flushes_chunks_to_disk_before_stop() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let standby = build_standby_piper(IdleRxAdapter::new(), sent_frames);
+        let driver = Arc::clone(&standby.driver);
+        let output_path = temp_recording_path("recording-streaming");
+
+        let (standby, handle) = standby
+            .start_recording(crate::recording::RecordingConfig {
+                output_path: output_path.clone(),
+                stop_condition: crate::recording::StopCondition::Manual,
+                metadata: crate::recording::RecordingMetadata {
+                    notes: "streaming".to_string(),
+                    operator: "tester".to_string(),
+                },
+                mode: crate::recording::RecordingMode::Streaming {
+                    chunk_frames: 2,
+                    queue_capacity: 16,
+                },
+                compression: Default::default(),
+            })
+            .expect("streaming recording should start");
+        let header_len = std::fs::metadata(&output_path)
+            .expect("streaming recording creates the file on start")
+            .len();
+
+        driver
+            .send_reliable(PiperFrame::new_standard(0x151, [0x11]).unwrap())
+            .expect("driver should send first frame");
+        driver
+            .send_reliable(PiperFrame::new_standard(0x152, [0x22]).unwrap())
+            .expect("driver should send second frame");
+        wait_until(
+            Duration::from_millis(500),
+            || std::fs::metadata(&output_path).is_ok_and(|m| m.len() > header_len),
+            "a full chunk should reach the file before stop_recording",
+        );
+
+        driver
+            .send_reliable(PiperFrame::new_standard(0x153, [0x33]).unwrap())
+            .expect("driver should send trailing partial-chunk frame");
+        wait_until(
+            Duration::from_millis(200),
+            || handle.frame_count() == 3,
+            "recording should accept the trailing frame",
+        );
+
+        let (_standby, stats) =
+            standby.stop_recording(handle).expect("streaming recording should stop cleanly");
+        assert_eq!(stats.frame_count, 3);
+        assert_eq!(stats.dropped_frames, 0);
+
+        let saved = PiperRecording::load(&output_path).expect("streamed recording should load");
+        assert_eq!(saved.metadata.notes, "streaming");
+        assert_eq!(
+            saved.frames.iter().map(|frame| frame.frame.raw_id()).collect::<Vec<_>>(),
+            vec![0x151, 0x152, 0x153]
+        );
+
+        let _ = std::fs::remove_file(output_path);
+    }
+
+    #[test]
+    fn compressed_streaming_recording_loads_with_auto_detection() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let standby = build_standby_piper(IdleRxAdapter::new(), sent_frames);
+        let driver = Arc::clone(&standby.driver);
+        let output_path = temp_recording_path("recording-streaming-zstd");
+
+        let (standby, handle) = standby
+            .start_recording(crate::recording::RecordingConfig {
+                output_path: output_path.clone(),
+                stop_condition: crate::recording::StopCondition::Manual,
+                metadata: crate::recording::RecordingMetadata {
+                    notes: "compressed".to_string(),
+                    operator: "tester".to_string(),
+                },
+                mode: crate::recording::RecordingMode::Streaming {
+                    chunk_frames: 2,
+                    queue_capacity: 16,
+                },
+                compression: crate::recording::RecordingCompression::zstd(),
+            })
+            .expect("compressed streaming recording should start");
+
+        for (id, byte) in [(0x151, 0x11), (0x152, 0x22), (0x153, 0x33)] {
+            driver
+                .send_reliable(PiperFrame::new_standard(id, [byte]).unwrap())
+                .expect("driver should send frame");
+        }
+        wait_until(
+            Duration::from_millis(200),
+            || handle.frame_count() == 3,
+            "recording should accept all frames",
+        );
+
+        let (_standby, stats) = standby
+            .stop_recording(handle)
+            .expect("compressed streaming recording should stop cleanly");
+        assert_eq!(stats.frame_count, 3);
+
+        let bytes = std::fs::read(&output_path).expect("compressed recording should exist");
+        assert!(bytes.starts_with(b"PIPERZS\0"));
+        let saved = PiperRecording::load(&output_path).expect("compressed recording should load");
+        assert_eq!(saved.metadata.notes, "compressed");
+        assert_eq!(
+            saved.frames.iter().map(|frame| frame.frame.raw_id()).collect::<Vec<_>>(),
+            vec![0x151, 0x152, 0x153]
+        );
+
+        let _ = std::fs::remove_file(output_path);
+    }
+
+    #[test]
+    fn streaming_recording_reports_unwritable_output_on_start() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let standby = build_standby_piper(IdleRxAdapter::new(), sent_frames);
+        let driver = Arc::clone(&standby.driver);
+
+        let result = standby.start_recording(crate::recording::RecordingConfig {
+            output_path: std::env::temp_dir().join("piper-missing-dir").join("rec.bin"),
+            stop_condition: crate::recording::StopCondition::Manual,
+            metadata: crate::recording::RecordingMetadata {
+                notes: String::new(),
+                operator: String::new(),
+            },
+            mode: crate::recording::RecordingMode::streaming(),
+            compression: Default::default(),
+        });
+
+        assert!(result.is_err());
+        assert_eq!(
+            driver.hooks().read().expect("hooks read lock").len(),
+            0,
+            "failed streaming start must not leave a hook registered"
+        );
+    }
+
     #[test]
     fn command_position_with_policy_rejects_stale_feedback_without_sending() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
@@ -6768,6 +8668,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn transition_piper_state_sets_driver_drop_stop_action_from_drop_policy() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let standby = build_standby_piper(IdleRxAdapter::new(), sent_frames);
+        assert_eq!(
+            standby.driver.drop_stop_action(),
+            piper_driver::DropStopAction::None
+        );
+
+        let armed = transition_piper_state(
+            standby,
+            Standby,
+            DropPolicy::DisableAll,
+            DriverModeDropPolicy::Preserve,
+        );
+        assert_eq!(
+            armed.driver.drop_stop_action(),
+            piper_driver::DropStopAction::Disable
+        );
+
+        let standby = transition_piper_state(
+            armed,
+            Standby,
+            DropPolicy::Noop,
+            DriverModeDropPolicy::Preserve,
+        );
+        assert_eq!(
+            standby.driver.drop_stop_action(),
+            piper_driver::DropStopAction::None
+        );
+    }
+
     #[test]
     fn active_drop_sends_disable_all_but_standby_replay_error_and_monitor_do_not() {
         use piper_driver::mode::DriverMode;
@@ -6853,6 +8785,8 @@ mod tests {
             monitor_driver,
             InitializedConnection {
                 quirks: DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+                safety_limits: None,
+                enable_timeout: None,
                 initial_state: InitialMotionState::Standby,
             },
         )
@@ -6875,6 +8809,82 @@ mod tests {
         );
     }
 
+    #[test]
+    fn split_commander_drop_disables_while_observer_keeps_reading() {
+        use piper_protocol::control::MotorEnableCommand;
+
+        fn assert_send<T: Send>() {}
+        fn assert_clone_send_sync<T: Clone + Send + Sync>() {}
+        assert_send::<crate::state::MotionCommander<MitMode, StrictRealtime>>();
+        assert_clone_send_sync::<Observer<StrictRealtime>>();
+
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let active = build_active_mit_piper(
+            DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+            sent.clone(),
+        );
+        let (commander, observer) = active.split();
+        let reader = observer.clone();
+        let handle = thread::spawn(move || reader.joint_enabled_mask());
+        handle.join().expect("observer thread should not panic");
+
+        assert!(
+            commander.robot_fault().is_none(),
+            "Deref should expose Piper methods"
+        );
+        thread::spawn(move || drop(commander))
+            .join()
+            .expect("commander thread should not panic");
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(
+            sent.lock().expect("sent frames lock").as_slice(),
+            &[MotorEnableCommand::disable_all().to_frame()],
+            "dropping MotionCommander must best-effort disable all motors once"
+        );
+        let _ = observer.joint_enabled_mask();
+    }
+
+    #[test]
+    fn emergency_stop_handle_preempts_commander_from_another_thread() {
+        let emergency_stop_frame =
+            piper_protocol::control::EmergencyStopCommand::emergency_stop().to_frame();
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let active = build_active_mit_piper(
+            DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+            sent.clone(),
+        );
+        let (commander, _observer) = active.split();
+        let handle = commander.emergency_stop_handle();
+
+        thread::spawn(move || handle.trigger())
+            .join()
+            .expect("estop thread should not panic")
+            .expect("emergency stop should be confirmed by the TX thread");
+        assert_eq!(
+            sent.lock().expect("sent frames lock").as_slice(),
+            &[emergency_stop_frame]
+        );
+        assert!(
+            commander
+                .command_torques(
+                    &JointArray::splat(Rad(0.0)),
+                    &JointArray::splat(0.0),
+                    &JointArray::splat(0.0),
+                    &JointArray::splat(0.0),
+                    &JointArray::splat(NewtonMeter(0.0)),
+                )
+                .is_err(),
+            "commands after a handle-triggered estop must be rejected"
+        );
+
+        let error_state = commander.estop().expect("estop should still reach the shutdown lane");
+        assert!(error_state.is_error_state());
+        assert_eq!(
+            sent.lock().expect("sent frames lock").as_slice(),
+            &[emergency_stop_frame, emergency_stop_frame]
+        );
+    }
+
     #[test]
     fn active_drop_fault_latched_sends_bounded_shutdown_lane_emergency_stop() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
@@ -6950,6 +8960,8 @@ mod tests {
             driver,
             InitializedConnection {
                 quirks: DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+                safety_limits: None,
+                enable_timeout: None,
                 initial_state: InitialMotionState::Standby,
             },
         )
@@ -6976,6 +8988,8 @@ mod tests {
             driver,
             InitializedConnection {
                 quirks: DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+                safety_limits: None,
+                enable_timeout: None,
                 initial_state: InitialMotionState::Maintenance {
                     confirmed_mask: Some(0b000001),
                 },
@@ -7005,6 +9019,8 @@ mod tests {
             driver,
             InitializedConnection {
                 quirks: DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+                safety_limits: None,
+                enable_timeout: None,
                 initial_state: InitialMotionState::Maintenance {
                     confirmed_mask: None,
                 },
@@ -7298,6 +9314,44 @@ mod tests {
         let _ = std::fs::remove_file(recording_path);
     }
 
+    #[test]
+    fn replay_recording_with_options_loops_and_filters_ids() {
+        use std::sync::atomic::AtomicBool;
+
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let recording_path = write_test_recording(&[
+            (1_000, 0x155, &[0x01]),
+            (2_000, 0x156, &[0x02]),
+            (3_000, 0x157, &[0x03]),
+        ]);
+        let replay = build_standby_piper(IdleRxAdapter::new(), sent_frames.clone())
+            .enter_replay_mode()
+            .expect("enter_replay_mode should succeed");
+        let running = AtomicBool::new(true);
+        let options = ReplayOptions::new(2.0).loop_count(2).id_filter([0x155, 0x157]);
+
+        let standby = replay
+            .replay_recording_with_options(&recording_path, &options, &running)
+            .expect("filtered looping replay should complete");
+
+        let sent = sent_frames.lock().expect("sent frames lock");
+        assert_eq!(
+            sent.iter().map(PiperFrame::raw_id).collect::<Vec<_>>(),
+            vec![0x155, 0x157, 0x155, 0x157]
+        );
+        drop(sent);
+        drop(standby);
+        let _ = std::fs::remove_file(recording_path);
+    }
+
+    #[test]
+    fn replay_options_reject_zero_loops_and_empty_filter() {
+        assert!(ReplayOptions::new(1.0).loop_count(0).validate().is_err());
+        assert!(ReplayOptions::new(1.0).id_filter([]).validate().is_err());
+        assert!(ReplayOptions::new(f64::NAN).validate().is_err());
+        assert!(ReplayOptions::new(0.5).loop_count(3).validate().is_ok());
+    }
+
     #[test]
     fn replay_recording_with_cancel_returns_standby_and_restores_driver_mode() {
         use piper_driver::mode::DriverMode;
@@ -7324,169 +9378,619 @@ mod tests {
             sent_frames.lock().expect("sent frames lock").is_empty(),
             "cancelled replay before first frame must not emit any frame"
         );
-        drop(standby);
-        let _ = std::fs::remove_file(recording_path);
+        drop(standby);
+        let _ = std::fs::remove_file(recording_path);
+    }
+
+    #[test]
+    fn replay_recording_with_cancel_stops_before_next_frame_after_inter_frame_cancel() {
+        use piper_driver::mode::DriverMode;
+        use std::sync::atomic::AtomicBool;
+
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let recording_path =
+            write_test_recording(&[(1_000, 0x155, &[0x01]), (101_000, 0x156, &[0x02])]);
+        let replay = build_standby_piper(IdleRxAdapter::new(), sent_frames.clone())
+            .enter_replay_mode()
+            .expect("enter_replay_mode should succeed");
+        let driver = replay.driver.clone();
+        let cancel_signal = Arc::new(AtomicBool::new(true));
+        let cancel_signal_worker = Arc::clone(&cancel_signal);
+        let recording_path_worker = recording_path.clone();
+
+        let handle = thread::spawn(move || {
+            replay.replay_recording_with_cancel(
+                &recording_path_worker,
+                1.0,
+                cancel_signal_worker.as_ref(),
+            )
+        });
+
+        wait_until(
+            Duration::from_millis(200),
+            || sent_frames.lock().expect("sent frames lock").len() == 1,
+            "first replay frame should be emitted before cancellation",
+        );
+        cancel_signal.store(false, std::sync::atomic::Ordering::Release);
+
+        let standby = handle
+            .join()
+            .expect("replay worker should finish")
+            .expect("cancelled replay should return Standby");
+
+        assert_eq!(
+            driver.mode(),
+            DriverMode::Normal,
+            "cancelled replay must restore driver mode to Normal"
+        );
+        let sent = sent_frames.lock().expect("sent frames lock");
+        assert_eq!(
+            sent.len(),
+            1,
+            "cancel observed during inter-frame wait must prevent the next replay frame"
+        );
+        assert_eq!(sent[0].raw_id(), 0x155);
+        assert_eq!(sent[0].dlc(), 1);
+        assert_eq!(sent[0].data()[0], 0x01);
+        assert_eq!(sent[0].timestamp_us(), 0);
+
+        drop(standby);
+        let _ = std::fs::remove_file(recording_path);
+    }
+
+    #[test]
+    fn replay_recording_clears_source_timestamps_before_sending() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let recording_path = write_test_recording(&[(123_456, 0x155, &[0x01, 0x02])]);
+        let replay = build_standby_piper(IdleRxAdapter::new(), sent_frames.clone())
+            .enter_replay_mode()
+            .expect("enter_replay_mode should succeed");
+
+        let standby = replay
+            .replay_recording(&recording_path, 1.0)
+            .expect("replay should complete successfully");
+        assert!(matches!(standby._state, Standby));
+
+        let sent = sent_frames.lock().expect("sent frames lock");
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].raw_id(), 0x155);
+        assert_eq!(sent[0].dlc(), 2);
+        assert_eq!(sent[0].data()[0], 0x01);
+        assert_eq!(sent[0].data()[1], 0x02);
+        assert_eq!(sent[0].timestamp_us(), 0);
+
+        drop(standby);
+        let _ = std::fs::remove_file(recording_path);
+    }
+
+    #[test]
+    fn enable_position_mode_rejects_continuous_position_velocity_on_old_firmware_without_sending_any_frame()
+     {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let mut standby = build_standby_piper(IdleRxAdapter::new(), sent_frames.clone());
+        standby.quirks = DeviceQuirks::from_firmware_version(Version::new(1, 7, 3));
+
+        let error = match standby.enable_position_mode(PositionModeConfig {
+            motion_type: MotionType::ContinuousPositionVelocity,
+            ..Default::default()
+        }) {
+            Ok(_) => panic!("ContinuousPositionVelocity should be rejected before mode switching"),
+            Err(error) => error,
+        };
+
+        assert!(
+            matches!(
+                error,
+                RobotError::Infrastructure(DriverError::UnsupportedByFirmware { .. })
+            ),
+            "{error:?}"
+        );
+        assert!(
+            sent_frames.lock().expect("sent frames lock").is_empty(),
+            "unsupported motion type must not emit enable or mode-switch frames"
+        );
+    }
+
+    #[test]
+    fn enable_position_mode_rejects_invalid_joint_limits_without_sending_any_frame() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let standby = build_standby_piper(IdleRxAdapter::new(), sent_frames.clone());
+        let mut limits = JointLimits::default();
+        limits.min[Joint::J3] = Rad(1.0);
+        limits.max[Joint::J3] = Rad(-1.0);
+
+        let error = match standby.enable_position_mode(PositionModeConfig {
+            joint_limits: Some(limits),
+            ..Default::default()
+        }) {
+            Ok(_) => panic!("inverted joint limits should be rejected before enabling"),
+            Err(error) => error,
+        };
+
+        assert!(matches!(error, RobotError::ConfigError(_)), "{error:?}");
+        assert!(
+            sent_frames.lock().expect("sent frames lock").is_empty(),
+            "invalid joint limits must not emit enable or mode-switch frames"
+        );
+    }
+
+    #[test]
+    fn position_mode_runtime_motion_type_guard_rejects_mismatched_helpers_without_sending() {
+        let joint_sent = Arc::new(Mutex::new(Vec::new()));
+        let joint_driver = Arc::new(
+            RobotPiper::new_dual_thread_parts(
+                IdleRxAdapter::new(),
+                RecordingTxAdapter::new(joint_sent.clone()),
+                None,
+            )
+            .expect("joint driver should start"),
+        );
+        let joint_robot =
+            build_active_position_piper_with_motion_type(joint_driver, MotionType::Joint);
+        let zero_position = Position3D::new(0.0, 0.0, 0.0);
+        let zero_orientation = EulerAngles::new(0.0, 0.0, 0.0);
+
+        assert!(matches!(
+            joint_robot.command_cartesian_pose(zero_position, zero_orientation),
+            Err(RobotError::ConfigError(_))
+        ));
+        assert!(matches!(
+            joint_robot.move_linear(zero_position, zero_orientation),
+            Err(RobotError::ConfigError(_))
+        ));
+        assert!(matches!(
+            joint_robot.move_circular(
+                zero_position,
+                zero_orientation,
+                zero_position,
+                zero_orientation
+            ),
+            Err(RobotError::ConfigError(_))
+        ));
+        assert!(
+            joint_sent.lock().expect("joint sent frames lock").is_empty(),
+            "mismatched Cartesian helpers must not emit any CAN frame in joint mode"
+        );
+
+        let cartesian_sent = Arc::new(Mutex::new(Vec::new()));
+        let cartesian_driver = Arc::new(
+            RobotPiper::new_dual_thread_parts(
+                IdleRxAdapter::new(),
+                RecordingTxAdapter::new(cartesian_sent.clone()),
+                None,
+            )
+            .expect("cartesian driver should start"),
+        );
+        let cartesian_robot =
+            build_active_position_piper_with_motion_type(cartesian_driver, MotionType::Cartesian);
+
+        assert!(matches!(
+            cartesian_robot.send_position_command(&JointArray::splat(Rad(0.0))),
+            Err(RobotError::ConfigError(_))
+        ));
+        assert!(
+            cartesian_sent.lock().expect("cartesian sent frames lock").is_empty(),
+            "joint helper must not emit frames in cartesian mode"
+        );
+    }
+
+    fn build_position_piper_with_zero_feedback(
+        motion_type: MotionType,
+        sent_frames: Arc<Mutex<Vec<PiperFrame>>>,
+    ) -> Piper<Active<PositionMode>, StrictRealtime> {
+        let driver = Arc::new(
+            RobotPiper::new_dual_thread_parts(
+                ScriptedRxAdapter::new(vec![
+                    joint_feedback_frame(ID_JOINT_FEEDBACK_12.raw().into(), 0, 0, 1_000),
+                    joint_feedback_frame(ID_JOINT_FEEDBACK_34.raw().into(), 0, 0, 1_000),
+                    joint_feedback_frame(ID_JOINT_FEEDBACK_56.raw().into(), 0, 0, 1_000),
+                ]),
+                RecordingTxAdapter::new(sent_frames),
+                None,
+            )
+            .expect("driver should start"),
+        );
+        driver
+            .wait_for_feedback(Duration::from_millis(200))
+            .expect("feedback should arrive");
+        build_active_position_piper_with_motion_type(driver, motion_type)
+    }
+
+    #[test]
+    fn move_to_pose_solves_ik_and_dispatches_by_motion_type() {
+        let kinematics = PiperKinematics::default();
+        let expected = [Rad(0.3), Rad(1.2), Rad(-1.0), Rad(0.2), Rad(0.4), Rad(0.1)];
+        let target = kinematics.forward(&expected);
+
+        let joint_sent = Arc::new(Mutex::new(Vec::new()));
+        let joint_robot =
+            build_position_piper_with_zero_feedback(MotionType::Joint, joint_sent.clone());
+        let solved = joint_robot.move_to_pose(target).expect("target should be reachable");
+        let reached = kinematics.forward(&solved.into_array());
+        assert!(
+            (reached.position.x - target.position.x).abs() < 1e-3
+                && (reached.position.y - target.position.y).abs() < 1e-3
+                && (reached.position.z - target.position.z).abs() < 1e-3
+        );
+        thread::sleep(Duration::from_millis(50));
+        let joint_ids: Vec<u32> = joint_sent
+            .lock()
+            .expect("joint sent frames lock")
+            .iter()
+            .map(|frame| frame.raw_id())
+            .collect();
+        assert_eq!(
+            joint_ids,
+            vec![
+                u32::from(ID_JOINT_CONTROL_12.raw()),
+                u32::from(ID_JOINT_CONTROL_34.raw()),
+                u32::from(ID_JOINT_CONTROL_56.raw())
+            ]
+        );
+
+        let cartesian_sent = Arc::new(Mutex::new(Vec::new()));
+        let cartesian_robot =
+            build_position_piper_with_zero_feedback(MotionType::Cartesian, cartesian_sent.clone());
+        cartesian_robot.move_to_pose(target).expect("target should be reachable");
+        thread::sleep(Duration::from_millis(50));
+        let cartesian_ids: Vec<u32> = cartesian_sent
+            .lock()
+            .expect("cartesian sent frames lock")
+            .iter()
+            .map(|frame| frame.raw_id())
+            .collect();
+        assert_eq!(cartesian_ids, vec![0x152, 0x153, 0x154]);
+    }
+
+    #[test]
+    fn move_to_pose_rejects_unreachable_target_without_sending() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let robot = build_position_piper_with_zero_feedback(MotionType::Joint, sent_frames.clone());
+        let target =
+            CartesianPose::from_position_euler(1.0, 0.0, 0.2, Rad(0.0), Rad(0.0), Rad(0.0));
+
+        let error = robot.move_to_pose(target).expect_err("1m is out of reach");
+
+        assert!(matches!(
+            error,
+            RobotError::Kinematics(crate::kinematics::KinematicsError::OutOfReach { .. })
+        ));
+        thread::sleep(Duration::from_millis(50));
+        assert!(sent_frames.lock().expect("sent frames lock").is_empty());
+    }
+
+    #[test]
+    fn send_position_command_clamps_to_configured_joint_limits() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let driver = Arc::new(
+            RobotPiper::new_dual_thread_parts(
+                IdleRxAdapter::new(),
+                RecordingTxAdapter::new(sent_frames.clone()),
+                None,
+            )
+            .expect("driver should start"),
+        );
+        let mut robot = build_active_position_piper(driver);
+        robot._state.0.joint_limits = Some(JointLimits::default());
+
+        let mut target = JointArray::splat(Rad(0.0));
+        target[Joint::J1] = Rad(3.0);
+        target[Joint::J2] = Rad(1.0);
+        robot.send_position_command(&target).expect("joint command should be sent");
+        thread::sleep(Duration::from_millis(50));
+
+        let frames = sent_frames.lock().expect("sent frames lock");
+        let joint_12 = frames
+            .iter()
+            .find(|frame| frame.raw_id() == u32::from(ID_JOINT_CONTROL_12.raw()))
+            .expect("0x155 should be sent");
+        let data = joint_12.data();
+        let j1 = i32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+        let j2 = i32::from_be_bytes([data[4], data[5], data[6], data[7]]);
+        assert_eq!(j1, JointLimits::PIPER.max[Joint::J1].to_millidegrees());
+        assert_eq!(j2, Rad(1.0).to_millidegrees());
+    }
+
+    #[test]
+    fn send_position_command_applies_safety_limit_mode() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let driver = Arc::new(
+            RobotPiper::new_dual_thread_parts(
+                IdleRxAdapter::new(),
+                RecordingTxAdapter::new(sent_frames.clone()),
+                None,
+            )
+            .expect("driver should start"),
+        );
+        let mut robot = build_active_position_piper(driver);
+        let mut limits = SafetyLimits::default();
+        limits.joints_min[1] = -0.5;
+        limits.joints_max[1] = 0.5;
+        robot.safety_limits = Some(Arc::new(limits.clone()));
+
+        let mut target = JointArray::splat(Rad(0.0));
+        target[Joint::J1] = Rad(0.25);
+        target[Joint::J2] = Rad(1.0);
+        robot.send_position_command(&target).expect("clamped command should be sent");
+        thread::sleep(Duration::from_millis(50));
+
+        {
+            let frames = sent_frames.lock().expect("sent frames lock");
+            let joint_12 = frames
+                .iter()
+                .find(|frame| frame.raw_id() == u32::from(ID_JOINT_CONTROL_12.raw()))
+                .expect("0x155 should be sent");
+            let data = joint_12.data();
+            let j1 = i32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+            let j2 = i32::from_be_bytes([data[4], data[5], data[6], data[7]]);
+            assert_eq!(j1, Rad(0.25).to_millidegrees());
+            assert_eq!(j2, Rad(0.5).to_millidegrees());
+        }
+
+        limits.mode = SafetyLimitMode::Reject;
+        robot.safety_limits = Some(Arc::new(limits));
+        sent_frames.lock().expect("sent frames lock").clear();
+        let error = robot
+            .send_position_command(&target)
+            .expect_err("reject mode should refuse out-of-range targets");
+        assert!(matches!(
+            error,
+            RobotError::JointLimitExceeded {
+                joint: Joint::J2,
+                limit,
+                ..
+            } if limit == 0.5
+        ));
+        thread::sleep(Duration::from_millis(50));
+        assert!(sent_frames.lock().expect("sent frames lock").is_empty());
     }
 
     #[test]
-    fn replay_recording_with_cancel_stops_before_next_frame_after_inter_frame_cancel() {
-        use piper_driver::mode::DriverMode;
-        use std::sync::atomic::AtomicBool;
-
+    fn send_position_command_rejects_non_finite_targets() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
-        let recording_path =
-            write_test_recording(&[(1_000, 0x155, &[0x01]), (101_000, 0x156, &[0x02])]);
-        let replay = build_standby_piper(IdleRxAdapter::new(), sent_frames.clone())
-            .enter_replay_mode()
-            .expect("enter_replay_mode should succeed");
-        let driver = replay.driver.clone();
-        let cancel_signal = Arc::new(AtomicBool::new(true));
-        let cancel_signal_worker = Arc::clone(&cancel_signal);
-        let recording_path_worker = recording_path.clone();
-
-        let handle = thread::spawn(move || {
-            replay.replay_recording_with_cancel(
-                &recording_path_worker,
-                1.0,
-                cancel_signal_worker.as_ref(),
+        let driver = Arc::new(
+            RobotPiper::new_dual_thread_parts(
+                IdleRxAdapter::new(),
+                RecordingTxAdapter::new(sent_frames.clone()),
+                None,
             )
-        });
-
-        wait_until(
-            Duration::from_millis(200),
-            || sent_frames.lock().expect("sent frames lock").len() == 1,
-            "first replay frame should be emitted before cancellation",
+            .expect("driver should start"),
         );
-        cancel_signal.store(false, std::sync::atomic::Ordering::Release);
-
-        let standby = handle
-            .join()
-            .expect("replay worker should finish")
-            .expect("cancelled replay should return Standby");
+        let mut robot = build_active_position_piper(driver);
+        robot.safety_limits = Some(Arc::new(SafetyLimits::default()));
 
-        assert_eq!(
-            driver.mode(),
-            DriverMode::Normal,
-            "cancelled replay must restore driver mode to Normal"
+        let mut target = JointArray::splat(Rad(0.0));
+        target[Joint::J2] = Rad(f64::NAN);
+        let error = robot
+            .send_position_command(&target)
+            .expect_err("NaN target must not be clamped to a joint limit");
+        assert!(
+            matches!(error, RobotError::InvalidParameter { .. }),
+            "{error:?}"
         );
-        let sent = sent_frames.lock().expect("sent frames lock");
-        assert_eq!(
-            sent.len(),
-            1,
-            "cancel observed during inter-frame wait must prevent the next replay frame"
+        thread::sleep(Duration::from_millis(50));
+        assert!(sent_frames.lock().expect("sent frames lock").is_empty());
+    }
+
+    #[test]
+    fn command_torques_rejects_non_finite_inputs_in_clamp_mode() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let mut robot = build_active_mit_piper(
+            DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+            sent_frames.clone(),
         );
-        assert_eq!(sent[0].raw_id(), 0x155);
-        assert_eq!(sent[0].dlc(), 1);
-        assert_eq!(sent[0].data()[0], 0x01);
-        assert_eq!(sent[0].timestamp_us(), 0);
+        robot.safety_limits = Some(Arc::new(SafetyLimits {
+            max_torque_nm: vec![2.0; 6],
+            max_velocity_rad_s: vec![1.0; 6],
+            ..SafetyLimits::default()
+        }));
 
-        drop(standby);
-        let _ = std::fs::remove_file(recording_path);
+        let zeros = JointArray::splat(0.0);
+        let positions = JointArray::splat(Rad(0.0));
+        let torques = JointArray::splat(NewtonMeter(0.0));
+        let mut nan_positions = positions;
+        nan_positions[Joint::J1] = Rad(f64::NAN);
+        let mut nan_velocities = zeros;
+        nan_velocities[Joint::J2] = f64::NAN;
+        let mut nan_torques = torques;
+        nan_torques[Joint::J3] = NewtonMeter(f64::NAN);
+
+        for (positions, velocities, torques) in [
+            (nan_positions, zeros, torques),
+            (positions, nan_velocities, torques),
+            (positions, zeros, nan_torques),
+        ] {
+            let error = robot
+                .command_torques(&positions, &velocities, &zeros, &zeros, &torques)
+                .expect_err("NaN must not be clamped to a safety limit");
+            assert!(
+                matches!(error, RobotError::InvalidParameter { .. }),
+                "{error:?}"
+            );
+        }
+        thread::sleep(Duration::from_millis(50));
+        assert!(sent_frames.lock().expect("sent frames lock").is_empty());
     }
 
     #[test]
-    fn replay_recording_clears_source_timestamps_before_sending() {
+    fn command_torques_applies_safety_position_limits_to_pos_ref() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
-        let recording_path = write_test_recording(&[(123_456, 0x155, &[0x01, 0x02])]);
-        let replay = build_standby_piper(IdleRxAdapter::new(), sent_frames.clone())
-            .enter_replay_mode()
-            .expect("enter_replay_mode should succeed");
+        let mut robot = build_active_mit_piper(
+            DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+            sent_frames.clone(),
+        );
+        let mut limits = SafetyLimits::default();
+        limits.joints_min[1] = -0.5;
+        limits.joints_max[1] = 0.5;
+        robot.safety_limits = Some(Arc::new(limits.clone()));
 
-        let standby = replay
-            .replay_recording(&recording_path, 1.0)
-            .expect("replay should complete successfully");
-        assert!(matches!(standby._state, Standby));
+        let mut positions = JointArray::splat(Rad(0.0));
+        positions[Joint::J2] = Rad(1.0);
+        let zeros = JointArray::splat(0.0);
+        let torques = JointArray::splat(NewtonMeter(0.0));
+        robot
+            .command_torques_confirmed(
+                &positions,
+                &zeros,
+                &zeros,
+                &zeros,
+                &torques,
+                Duration::from_millis(200),
+            )
+            .expect("clamp mode should send the clamped batch");
 
-        let sent = sent_frames.lock().expect("sent frames lock");
-        assert_eq!(sent.len(), 1);
-        assert_eq!(sent[0].raw_id(), 0x155);
-        assert_eq!(sent[0].dlc(), 2);
-        assert_eq!(sent[0].data()[0], 0x01);
-        assert_eq!(sent[0].data()[1], 0x02);
-        assert_eq!(sent[0].timestamp_us(), 0);
+        let j2 = sent_frames
+            .lock()
+            .expect("sent frames lock")
+            .iter()
+            .find(|frame| frame.raw_id() == 0x15B)
+            .map(|frame| MitControlCommand::try_from(*frame).expect("valid MIT frame"))
+            .expect("J2 MIT frame should be sent");
+        assert!((j2.pos_ref().abs() - 0.5).abs() < 1e-3, "{j2:?}");
 
-        drop(standby);
-        let _ = std::fs::remove_file(recording_path);
+        limits.mode = SafetyLimitMode::Reject;
+        robot.safety_limits = Some(Arc::new(limits));
+        sent_frames.lock().expect("sent frames lock").clear();
+        let error = robot
+            .command_torques(&positions, &zeros, &zeros, &zeros, &torques)
+            .expect_err("reject mode should refuse out-of-range pos_ref");
+        assert!(matches!(
+            error,
+            RobotError::JointLimitExceeded {
+                joint: Joint::J2,
+                limit,
+                ..
+            } if limit == 0.5
+        ));
+        thread::sleep(Duration::from_millis(50));
+        assert!(sent_frames.lock().expect("sent frames lock").is_empty());
     }
 
     #[test]
-    fn enable_position_mode_rejects_continuous_position_velocity_without_sending_any_frame() {
+    fn command_torques_clamps_to_safety_limits_by_default() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
-        let standby = build_standby_piper(IdleRxAdapter::new(), sent_frames.clone());
-
-        let error = match standby.enable_position_mode(PositionModeConfig {
-            motion_type: MotionType::ContinuousPositionVelocity,
-            ..Default::default()
-        }) {
-            Ok(_) => panic!("ContinuousPositionVelocity should be rejected before mode switching"),
-            Err(error) => error,
+        let mut robot = build_active_mit_piper(
+            DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+            sent_frames.clone(),
+        );
+        let limits = SafetyLimits {
+            max_torque_nm: vec![2.0; 6],
+            max_velocity_rad_s: vec![1.0; 6],
+            ..SafetyLimits::default()
         };
-
-        assert!(matches!(error, RobotError::ConfigError(_)));
-        assert!(
-            sent_frames.lock().expect("sent frames lock").is_empty(),
-            "unsupported motion type must not emit enable or mode-switch frames"
+        robot.safety_limits = Some(Arc::new(limits));
+
+        let mut velocities = JointArray::splat(0.0);
+        velocities[Joint::J2] = -1.5;
+        let mut torques = JointArray::splat(NewtonMeter(0.0));
+        torques[Joint::J5] = NewtonMeter(-2.5);
+        let zeros = JointArray::splat(0.0);
+        let (commands, _) = robot
+            .build_validated_mit_command_batch_with_t_refs(
+                &JointArray::splat(Rad(0.0)),
+                &velocities,
+                &zeros,
+                &zeros,
+                &torques,
+            )
+            .expect("clamp mode should accept out-of-range batches");
+        let (clamped_commands, _) = robot
+            .build_validated_mit_command_batch_with_t_refs(
+                &JointArray::splat(Rad(0.0)),
+                &JointArray::from([0.0, -1.0, 0.0, 0.0, 0.0, 0.0]),
+                &zeros,
+                &zeros,
+                &JointArray::from([
+                    NewtonMeter(0.0),
+                    NewtonMeter(0.0),
+                    NewtonMeter(0.0),
+                    NewtonMeter(0.0),
+                    NewtonMeter(-2.0),
+                    NewtonMeter(0.0),
+                ]),
+            )
+            .expect("batch at the limits should be accepted");
+        assert_eq!(
+            commands.map(MitControlCommand::to_frame),
+            clamped_commands.map(MitControlCommand::to_frame)
         );
+
+        robot
+            .command_torques(
+                &JointArray::splat(Rad(0.0)),
+                &velocities,
+                &zeros,
+                &zeros,
+                &torques,
+            )
+            .expect("clamped batch should be sent");
+        thread::sleep(Duration::from_millis(50));
+        assert!(!sent_frames.lock().expect("sent frames lock").is_empty());
     }
 
     #[test]
-    fn position_mode_runtime_motion_type_guard_rejects_mismatched_helpers_without_sending() {
-        let joint_sent = Arc::new(Mutex::new(Vec::new()));
-        let joint_driver = Arc::new(
+    fn trajectory_streamer_sends_buffered_setpoints_and_detects_underrun() {
+        use crate::trajectory_streamer::{StreamTick, TrajectorySetpoint, TrajectoryStreamConfig};
+
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let driver = Arc::new(
             RobotPiper::new_dual_thread_parts(
                 IdleRxAdapter::new(),
-                RecordingTxAdapter::new(joint_sent.clone()),
+                RecordingTxAdapter::new(sent.clone()),
                 None,
             )
-            .expect("joint driver should start"),
+            .expect("driver should start"),
         );
         let joint_robot =
-            build_active_position_piper_with_motion_type(joint_driver, MotionType::Joint);
-        let zero_position = Position3D::new(0.0, 0.0, 0.0);
-        let zero_orientation = EulerAngles::new(0.0, 0.0, 0.0);
-
+            build_active_position_piper_with_motion_type(driver.clone(), MotionType::Joint);
         assert!(matches!(
-            joint_robot.command_cartesian_pose(zero_position, zero_orientation),
+            joint_robot.trajectory_streamer(TrajectoryStreamConfig::default()),
             Err(RobotError::ConfigError(_))
         ));
+
+        let robot = build_active_position_piper_with_motion_type(
+            driver,
+            MotionType::ContinuousPositionVelocity,
+        );
+        let mut streamer = robot
+            .trajectory_streamer(TrajectoryStreamConfig {
+                period: Duration::from_millis(1),
+                buffer_capacity: 2,
+            })
+            .expect("CPV position mode should allow trajectory streaming");
+        assert_eq!(streamer.tick().unwrap(), StreamTick::Idle);
+
+        let moving =
+            |q: f64| TrajectorySetpoint::new(JointArray::splat(Rad(q)), JointArray::splat(0.5));
+        streamer.push(moving(0.0)).unwrap();
+        streamer.push(moving(0.1)).unwrap();
         assert!(matches!(
-            joint_robot.move_linear(zero_position, zero_orientation),
+            streamer.push(moving(0.2)),
             Err(RobotError::ConfigError(_))
         ));
         assert!(matches!(
-            joint_robot.move_circular(
-                zero_position,
-                zero_orientation,
-                zero_position,
-                zero_orientation
-            ),
+            streamer.push(moving(f64::NAN)),
             Err(RobotError::ConfigError(_))
         ));
-        assert!(
-            joint_sent.lock().expect("joint sent frames lock").is_empty(),
-            "mismatched Cartesian helpers must not emit any CAN frame in joint mode"
-        );
 
-        let cartesian_sent = Arc::new(Mutex::new(Vec::new()));
-        let cartesian_driver = Arc::new(
-            RobotPiper::new_dual_thread_parts(
-                IdleRxAdapter::new(),
-                RecordingTxAdapter::new(cartesian_sent.clone()),
-                None,
-            )
-            .expect("cartesian driver should start"),
-        );
-        let cartesian_robot =
-            build_active_position_piper_with_motion_type(cartesian_driver, MotionType::Cartesian);
+        let stats = streamer.run([moving(0.2), moving(0.3)]).expect("streaming should succeed");
+        assert_eq!(stats.setpoints_sent, 4);
+        assert_eq!(stats.underruns, 1, "trajectory ended while still moving");
+        assert_eq!(streamer.buffered(), 0);
 
-        assert!(matches!(
-            cartesian_robot.send_position_command(&JointArray::splat(Rad(0.0))),
-            Err(RobotError::ConfigError(_))
-        ));
-        assert!(
-            cartesian_sent.lock().expect("cartesian sent frames lock").is_empty(),
-            "joint helper must not emit frames in cartesian mode"
-        );
+        streamer.push(TrajectorySetpoint::at_rest(JointArray::splat(Rad(0.4)))).unwrap();
+        assert_eq!(streamer.tick().unwrap(), StreamTick::Sent);
+        assert_eq!(streamer.tick().unwrap(), StreamTick::Idle);
+        assert_eq!(streamer.stats().underruns, 1);
+
+        thread::sleep(Duration::from_millis(50));
+        let joint_control_frames = sent
+            .lock()
+            .expect("sent frames lock")
+            .iter()
+            .filter(|frame| frame.raw_id() == u32::from(ID_JOINT_CONTROL_12.raw()))
+            .count();
+        assert_eq!(joint_control_frames, 6, "5 setpoints + 1 underrun hold");
     }
 
     #[test]
@@ -7517,9 +10021,9 @@ mod tests {
         assert_eq!(
             joint_ids,
             vec![
-                ID_JOINT_CONTROL_12.raw().into(),
-                ID_JOINT_CONTROL_34.raw().into(),
-                ID_JOINT_CONTROL_56.raw().into()
+                u32::from(ID_JOINT_CONTROL_12.raw()),
+                u32::from(ID_JOINT_CONTROL_34.raw()),
+                u32::from(ID_JOINT_CONTROL_56.raw())
             ]
         );
 
@@ -7691,6 +10195,82 @@ mod tests {
         );
     }
 
+    #[test]
+    fn command_torques_rejects_batch_exceeding_configured_safety_limits() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let mut robot = build_active_mit_piper(
+            DeviceQuirks::from_firmware_version(Version::new(1, 8, 3)),
+            sent_frames.clone(),
+        );
+        let limits = SafetyLimits {
+            max_torque_nm: vec![8.0, 8.0, 8.0, 2.0, 2.0, 2.0],
+            max_velocity_rad_s: vec![1.0; 6],
+            mode: SafetyLimitMode::Reject,
+            ..SafetyLimits::default()
+        };
+        robot.safety_limits = Some(Arc::new(limits));
+
+        let positions = JointArray::splat(Rad(0.0));
+        let kp = JointArray::splat(0.0);
+        let kd = JointArray::splat(0.0);
+        let torques = JointArray::from([
+            NewtonMeter(0.0),
+            NewtonMeter(0.0),
+            NewtonMeter(0.0),
+            NewtonMeter(0.0),
+            NewtonMeter(-2.5),
+            NewtonMeter(0.0),
+        ]);
+
+        let error = robot
+            .command_torques(&positions, &JointArray::splat(0.0), &kp, &kd, &torques)
+            .expect_err("torque above configured limit should fail the whole batch");
+        assert!(matches!(
+            error,
+            RobotError::TorqueLimitExceeded {
+                joint: Joint::J5,
+                min,
+                max,
+                ..
+            } if min == -2.0 && max == 2.0
+        ));
+
+        let velocities = JointArray::from([0.0, 1.5, 0.0, 0.0, 0.0, 0.0]);
+        let error = robot
+            .command_torques(
+                &positions,
+                &velocities,
+                &kp,
+                &kd,
+                &JointArray::splat(NewtonMeter(0.0)),
+            )
+            .expect_err("velocity above configured limit should fail the whole batch");
+        assert!(matches!(
+            error,
+            RobotError::VelocityLimitExceeded {
+                joint: Joint::J2,
+                limit,
+                ..
+            } if limit == 1.0
+        ));
+
+        thread::sleep(Duration::from_millis(50));
+        assert!(
+            sent_frames.lock().expect("sent frames lock").is_empty(),
+            "no frames should be sent when the batch exceeds safety limits"
+        );
+
+        robot
+            .command_torques(
+                &positions,
+                &JointArray::splat(1.0),
+                &kp,
+                &kd,
+                &JointArray::splat(NewtonMeter(2.0)),
+            )
+            .expect("commands at the configured limits should be accepted");
+    }
+
     #[test]
     fn command_torques_confirmed_applies_firmware_quirks_before_encoding() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
@@ -58,12 +58,14 @@
 //! ```
 
 pub mod capability;
+pub mod commander;
 pub mod machine;
 
 pub use capability::{
     CapabilityMarker, MonitorOnly, MotionCapability, SoftRealtime, StrictCapability,
     StrictRealtime, UnspecifiedCapability,
 };
+pub use commander::{EmergencyStopHandle, MotionCommander};
 pub use machine::{
     Active,
     ConnectedPiper,
@@ -78,11 +80,13 @@ pub use machine::{
     MitMode,
     MitModeConfig,
     MitPassthroughMode,
+    MitToPositionConfig,
     MotionConnectedPiper,
     MotionConnectedState,
     Piper,
     PositionMode,
     PositionModeConfig,
     ReplayMode,
+    ReplayOptions,
     Standby,
 };
@@ -0,0 +1,255 @@
+//! 拖动示教：录制与回放
+//!
+//! 基于 0x150 拖动示教指令与 0x2A1 上报的示教状态：
+//!
+//! - [`Piper::start_teach_recording`]：在 `Standby` 下发送"开始示教记录"，等待 0x2A1
+//!   确认进入拖动示教后，由后台线程按固定间隔从 `Observer` 采样关节位置；
+//! - [`TeachRecording::stop`]：发送"结束示教记录"并返回录制到的 [`TeachTrajectory`]；
+//! - `Piper<Active<PositionMode>>::play_teach()`：先移动到轨迹起点，再按录制时的节拍
+//!   逐点下发关节位置目标（需 `MotionType::Joint`）。
+//!
+//! 轨迹在主机侧录制与回放，不依赖控制器内部保存的示教轨迹。
+//!
+//! # 示例
+//!
+//! ```rust,ignore
+//! # use piper_client::state::*;
+//! # use piper_client::types::*;
+//! # use piper_client::TeachRecordingConfig;
+//! # use std::time::Duration;
+//! # fn example(standby: Piper<Standby>) -> std::result::Result<(), MotionError> {
+//! let recording = standby.start_teach_recording(TeachRecordingConfig::default())?;
+//! std::thread::sleep(Duration::from_secs(10)); // 手动拖动机械臂
+//! let trajectory = recording.stop()?;
+//!
+//! let robot = standby.enable_position_mode(PositionModeConfig::default())?;
+//! robot.play_teach(&trajectory, Duration::from_secs(10))?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Piper::start_teach_recording`]: crate::state::Piper::start_teach_recording
+
+use crate::observer::Observer;
+use crate::raw_commander::RawCommander;
+use crate::state::capability::MotionCapability;
+use crate::types::{JointArray, Rad, Result, RobotError};
+use piper_driver::Piper as RobotPiper;
+use piper_protocol::control::TeachCommand;
+use piper_protocol::feedback::TeachStatus;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// 示教录制配置
+#[derive(Debug, Clone)]
+pub struct TeachRecordingConfig {
+    /// 关节位置采样间隔
+    pub sample_interval: Duration,
+    /// 等待 0x2A1 确认示教状态的超时
+    pub timeout: Duration,
+    /// 确认阶段的轮询间隔
+    pub poll_interval: Duration,
+}
+
+impl Default for TeachRecordingConfig {
+    fn default() -> Self {
+        Self {
+            sample_interval: Duration::from_millis(10), // 100Hz
+            timeout: Duration::from_secs(1),
+            poll_interval: Duration::from_millis(10),
+        }
+    }
+}
+
+/// 示教轨迹采样点
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TeachSample {
+    /// 相对录制开始的时间
+    pub elapsed: Duration,
+    /// 关节位置
+    pub position: JointArray<Rad>,
+}
+
+/// 录制得到的示教轨迹
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TeachTrajectory {
+    samples: Vec<TeachSample>,
+}
+
+impl TeachTrajectory {
+    /// 由采样点构造（采样时间必须单调不减）
+    pub fn new(samples: Vec<TeachSample>) -> Result<Self> {
+        if samples.windows(2).any(|pair| pair[1].elapsed < pair[0].elapsed) {
+            return Err(RobotError::ConfigError(
+                "teach trajectory samples must be ordered by elapsed time".to_string(),
+            ));
+        }
+        Ok(Self { samples })
+    }
+
+    pub fn samples(&self) -> &[TeachSample] {
+        &self.samples
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// 轨迹时长（最后一个采样点的时间）
+    pub fn duration(&self) -> Duration {
+        self.samples.last().map_or(Duration::ZERO, |sample| sample.elapsed)
+    }
+}
+
+/// 正在进行的示教录制
+///
+/// 通过 `Piper<Standby>::start_teach_recording()` 获取，借用期间机械臂不能切换状态。
+/// 调用 [`TeachRecording::stop`] 结束录制；直接 drop 时会 best-effort 发送"结束示教记录"
+/// 并丢弃已录制的数据。
+pub struct TeachRecording<'a> {
+    driver: &'a RobotPiper,
+    timeout: Duration,
+    stop: Arc<AtomicBool>,
+    sampler: Option<JoinHandle<Vec<TeachSample>>>,
+}
+
+impl<'a> TeachRecording<'a> {
+    /// 发送"开始示教记录"，确认后启动采样线程
+    pub(crate) fn start<Capability>(
+        driver: &'a RobotPiper,
+        observer: &Observer<Capability>,
+        config: TeachRecordingConfig,
+    ) -> Result<Self>
+    where
+        Capability: MotionCapability,
+    {
+        if config.sample_interval.is_zero() {
+            return Err(RobotError::ConfigError(
+                "teach sample_interval must be non-zero".to_string(),
+            ));
+        }
+
+        send_teach_command_and_confirm(
+            driver,
+            TeachCommand::StartRecord,
+            TeachStatus::StartRecord,
+            &config,
+        )?;
+        debug!("Drag teach recording started");
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let sampler = {
+            let stop = Arc::clone(&stop);
+            let observer = observer.clone();
+            let interval = config.sample_interval;
+            std::thread::Builder::new()
+                .name("piper-teach-recorder".to_string())
+                .spawn(move || sample_joint_positions(&observer, &stop, interval))
+                .map_err(|error| {
+                    RobotError::hardware_failure(format!(
+                        "failed to spawn teach recorder thread: {error}"
+                    ))
+                })?
+        };
+
+        Ok(Self {
+            driver,
+            timeout: config.timeout,
+            stop,
+            sampler: Some(sampler),
+        })
+    }
+
+    /// 结束录制并返回轨迹
+    pub fn stop(mut self) -> Result<TeachTrajectory> {
+        let samples = self.join_sampler();
+        RawCommander::new(self.driver).send_teach_command(TeachCommand::EndRecord, self.timeout)?;
+        debug!("Drag teach recording stopped ({} samples)", samples.len());
+        TeachTrajectory::new(samples)
+    }
+
+    fn join_sampler(&mut self) -> Vec<TeachSample> {
+        self.stop.store(true, Ordering::Release);
+        self.sampler.take().and_then(|sampler| sampler.join().ok()).unwrap_or_default()
+    }
+}
+
+impl Drop for TeachRecording<'_> {
+    fn drop(&mut self) {
+        if self.sampler.is_none() {
+            return;
+        }
+        self.join_sampler();
+        if let Err(error) =
+            RawCommander::new(self.driver).send_teach_command(TeachCommand::EndRecord, self.timeout)
+        {
+            warn!("Failed to end drag teach recording on drop: {}", error);
+        }
+    }
+}
+
+fn sample_joint_positions<Capability>(
+    observer: &Observer<Capability>,
+    stop: &AtomicBool,
+    interval: Duration,
+) -> Vec<TeachSample>
+where
+    Capability: MotionCapability,
+{
+    let start = Instant::now();
+    let mut deadline = start;
+    let mut samples = Vec::new();
+
+    while !stop.load(Ordering::Acquire) {
+        if let Ok(position) = observer.joint_positions() {
+            samples.push(TeachSample {
+                elapsed: start.elapsed(),
+                position,
+            });
+        }
+        deadline += interval;
+        let now = Instant::now();
+        if now < deadline {
+            std::thread::sleep(deadline - now);
+        } else {
+            deadline = now;
+        }
+    }
+    samples
+}
+
+/// 发送示教指令并等待 0x2A1 上报期望的示教状态
+fn send_teach_command_and_confirm(
+    driver: &RobotPiper,
+    command: TeachCommand,
+    expected: TeachStatus,
+    config: &TeachRecordingConfig,
+) -> Result<()> {
+    let start = Instant::now();
+    let commit_host_mono_us =
+        RawCommander::new(driver).send_teach_command(command, config.timeout)?;
+
+    loop {
+        let control = driver.get_robot_control();
+        let fresh = control.host_rx_mono_us > commit_host_mono_us;
+        if fresh && control.teach_status == expected as u8 {
+            return Ok(());
+        }
+        if start.elapsed() >= config.timeout {
+            let actual = TeachStatus::from(control.teach_status);
+            warn!(
+                "Controller did not confirm {:?} within {:?} (teach status {:?})",
+                expected, config.timeout, actual
+            );
+            return Err(RobotError::TeachModeRejected { expected, actual });
+        }
+        std::thread::sleep(config.poll_interval);
+    }
+}
@@ -0,0 +1,254 @@
+//! 连续位置速度（MoveCpv）轨迹流式下发
+//!
+//! 在 `MotionType::ContinuousPositionVelocity`（0x151 MoveMode = 0x05，V1.8-1+）下，
+//! 按固定控制周期把预先计算好的 (位置, 速度) 设定点逐个下发给固件，由固件自身的
+//! 连续模式完成平滑插补，而不是逐段发送位置目标再等待到位。
+//!
+//! - 设定点先进入有界缓冲区，每个控制周期 `tick()` 取出一个并经 0x155-0x157 下发；
+//! - 缓冲区为空而上一个设定点速度不为 0 时记为欠载（underrun）：重发上一个位置保持，
+//!   避免固件在运动中途失去目标；速度为 0 时视为轨迹正常结束；
+//! - 0x155-0x157 只携带关节角度，速度只用于判定轨迹是否停在静止点。
+//!
+//! # 示例
+//!
+//! ```rust,ignore
+//! # use piper_client::state::*;
+//! # use piper_client::types::*;
+//! # use piper_client::control::TrajectoryPlanner;
+//! # use piper_client::{TrajectorySetpoint, TrajectoryStreamConfig};
+//! # fn example(robot: Piper<Active<PositionMode>>, start: JointArray<Rad>, goal: JointArray<Rad>) -> Result<()> {
+//! // robot 以 MotionType::ContinuousPositionVelocity 使能
+//! let config = TrajectoryStreamConfig::default();
+//! let planner = TrajectoryPlanner::new(start, goal, std::time::Duration::from_secs(2), config.rate_hz());
+//!
+//! let mut streamer = robot.trajectory_streamer(config)?;
+//! let stats = streamer.run(planner.map(|(position, velocity)| TrajectorySetpoint::new(position, velocity)))?;
+//! println!("sent {} setpoints, {} underruns", stats.setpoints_sent, stats.underruns);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::state::capability::MotionCapability;
+use crate::state::machine::{Active, Piper, PositionMode};
+use crate::types::{JointArray, Rad, Result, RobotError};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// 判定设定点为静止的速度阈值（rad/s）
+const REST_VELOCITY_EPSILON: f64 = 1e-6;
+
+/// 单个轨迹设定点
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrajectorySetpoint {
+    /// 关节目标位置
+    pub position: JointArray<Rad>,
+    /// 关节目标速度（rad/s）
+    pub velocity: JointArray<f64>,
+}
+
+impl TrajectorySetpoint {
+    pub fn new(position: JointArray<Rad>, velocity: JointArray<f64>) -> Self {
+        Self { position, velocity }
+    }
+
+    /// 全部关节速度为 0 的静止点
+    pub fn at_rest(position: JointArray<Rad>) -> Self {
+        Self::new(position, JointArray::splat(0.0))
+    }
+
+    fn is_at_rest(&self) -> bool {
+        self.velocity.iter().all(|velocity| velocity.abs() <= REST_VELOCITY_EPSILON)
+    }
+
+    fn validate(&self) -> Result<()> {
+        let finite = self.position.iter().all(|position| position.0.is_finite())
+            && self.velocity.iter().all(|velocity| velocity.is_finite());
+        if !finite {
+            return Err(RobotError::ConfigError(
+                "trajectory setpoint must contain only finite positions and velocities".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 轨迹流配置
+#[derive(Debug, Clone)]
+pub struct TrajectoryStreamConfig {
+    /// 控制周期（每个周期下发一个设定点）
+    pub period: Duration,
+    /// 设定点缓冲区容量
+    pub buffer_capacity: usize,
+}
+
+impl TrajectoryStreamConfig {
+    /// 控制频率（Hz）
+    pub fn rate_hz(&self) -> f64 {
+        1.0 / self.period.as_secs_f64()
+    }
+}
+
+impl Default for TrajectoryStreamConfig {
+    fn default() -> Self {
+        Self {
+            period: Duration::from_millis(5), // 200Hz
+            buffer_capacity: 64,
+        }
+    }
+}
+
+/// 单个控制周期的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamTick {
+    /// 下发了缓冲区中的下一个设定点
+    Sent,
+    /// 缓冲区为空且上一个设定点仍在运动：重发上一个位置保持
+    Underrun,
+    /// 缓冲区为空且轨迹停在静止点（或尚未下发任何设定点），本周期不发送
+    Idle,
+}
+
+/// 轨迹流统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TrajectoryStreamStats {
+    /// 已下发的设定点数（不含欠载时的保持重发）
+    pub setpoints_sent: u64,
+    /// 欠载周期数
+    pub underruns: u64,
+    /// `run()` 中错过截止时间的周期数
+    pub late_ticks: u64,
+}
+
+/// MoveCpv 轨迹流下发器
+///
+/// 通过 `Piper<Active<PositionMode>>::trajectory_streamer()` 获取，要求位置模式以
+/// `MotionType::ContinuousPositionVelocity` 使能。位置目标会按
+/// `PositionModeConfig::joint_limits` 钳位。
+pub struct TrajectoryStreamer<'a, Capability>
+where
+    Capability: MotionCapability,
+{
+    piper: &'a Piper<Active<PositionMode>, Capability>,
+    config: TrajectoryStreamConfig,
+    buffer: VecDeque<TrajectorySetpoint>,
+    last_sent: Option<TrajectorySetpoint>,
+    stats: TrajectoryStreamStats,
+}
+
+impl<'a, Capability> TrajectoryStreamer<'a, Capability>
+where
+    Capability: MotionCapability,
+{
+    pub(crate) fn new(
+        piper: &'a Piper<Active<PositionMode>, Capability>,
+        config: TrajectoryStreamConfig,
+    ) -> Result<Self> {
+        if config.period.is_zero() || config.buffer_capacity == 0 {
+            return Err(RobotError::ConfigError(
+                "trajectory stream period and buffer capacity must be non-zero".to_string(),
+            ));
+        }
+        Ok(Self {
+            piper,
+            buffer: VecDeque::with_capacity(config.buffer_capacity),
+            config,
+            last_sent: None,
+            stats: TrajectoryStreamStats::default(),
+        })
+    }
+
+    /// 追加一个设定点
+    ///
+    /// # 错误
+    ///
+    /// - `RobotError::ConfigError`: 设定点含 NaN / 无穷大，或缓冲区已满
+    pub fn push(&mut self, setpoint: TrajectorySetpoint) -> Result<()> {
+        setpoint.validate()?;
+        if self.buffer.len() >= self.config.buffer_capacity {
+            return Err(RobotError::ConfigError(format!(
+                "trajectory stream buffer is full ({} setpoints)",
+                self.config.buffer_capacity
+            )));
+        }
+        self.buffer.push_back(setpoint);
+        Ok(())
+    }
+
+    /// 缓冲区中待下发的设定点数
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// 缓冲区剩余容量
+    pub fn available(&self) -> usize {
+        self.config.buffer_capacity - self.buffer.len()
+    }
+
+    pub fn stats(&self) -> TrajectoryStreamStats {
+        self.stats
+    }
+
+    /// 执行一个控制周期（不做节拍控制，由调用方按 `period` 调用）
+    pub fn tick(&mut self) -> Result<StreamTick> {
+        if let Some(setpoint) = self.buffer.pop_front() {
+            self.send(&setpoint.position)?;
+            self.last_sent = Some(setpoint);
+            self.stats.setpoints_sent += 1;
+            return Ok(StreamTick::Sent);
+        }
+
+        match self.last_sent {
+            Some(last) if !last.is_at_rest() => {
+                self.stats.underruns += 1;
+                warn!(
+                    "trajectory stream underrun: buffer empty while moving (underruns={})",
+                    self.stats.underruns
+                );
+                self.send(&last.position)?;
+                Ok(StreamTick::Underrun)
+            },
+            _ => Ok(StreamTick::Idle),
+        }
+    }
+
+    /// 按控制周期下发整条轨迹，直到设定点耗尽
+    ///
+    /// 每个周期先从 `setpoints` 补满缓冲区再下发一个设定点；截止时间按绝对时间推进，
+    /// 落后超过一个周期时计入 `late_ticks` 并重新对齐，不会连发追赶。
+    /// 轨迹最后一个设定点速度不为 0 时，结束前会记一次欠载。
+    pub fn run<I>(&mut self, setpoints: I) -> Result<TrajectoryStreamStats>
+    where
+        I: IntoIterator<Item = TrajectorySetpoint>,
+    {
+        let mut setpoints = setpoints.into_iter();
+        let mut deadline = Instant::now();
+
+        loop {
+            while self.available() > 0 {
+                match setpoints.next() {
+                    Some(setpoint) => self.push(setpoint)?,
+                    None => break,
+                }
+            }
+
+            match self.tick()? {
+                StreamTick::Sent => {},
+                StreamTick::Underrun | StreamTick::Idle => return Ok(self.stats),
+            }
+
+            deadline += self.config.period;
+            let now = Instant::now();
+            if now < deadline {
+                std::thread::sleep(deadline - now);
+            } else if now - deadline > self.config.period {
+                self.stats.late_ticks += 1;
+                deadline = now;
+            }
+        }
+    }
+
+    fn send(&self, position: &JointArray<Rad>) -> Result<()> {
+        self.piper.send_cpv_position_target(position)
+    }
+}
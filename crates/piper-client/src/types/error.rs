@@ -30,7 +30,7 @@
 
 use super::joint::Joint;
 use piper_driver::RuntimeFaultKind;
-use piper_protocol::{MitControlField, ProtocolError};
+use piper_protocol::{MitControlField, ProtocolError, RobotStatus, TeachStatus};
 use std::time::Duration;
 use thiserror::Error;
 
@@ -43,6 +43,8 @@ pub enum MonitorStateSource {
     EndPose,
     /// 关节动态组（J1-J6 高速反馈）
     JointDynamic,
+    /// 关节驱动器低速反馈（0x261-0x266）
+    JointDriverLowSpeed,
 }
 
 impl std::fmt::Display for MonitorStateSource {
@@ -51,6 +53,7 @@ impl std::fmt::Display for MonitorStateSource {
             Self::JointPosition => f.write_str("joint position"),
             Self::EndPose => f.write_str("end pose"),
             Self::JointDynamic => f.write_str("joint dynamic"),
+            Self::JointDriverLowSpeed => f.write_str("joint driver low speed"),
         }
     }
 }
@@ -88,6 +91,17 @@ pub enum RobotError {
         timeout_ms: u64,
     },
 
+    /// 控制循环连续超出周期
+    #[error(
+        "Control loop overran its {period_us}us period for {consecutive} consecutive iterations"
+    )]
+    ControlLoopOverrun {
+        /// 连续超时次数
+        consecutive: u64,
+        /// 标称周期（微秒）
+        period_us: u64,
+    },
+
     /// 无效的状态转换
     #[error("Invalid state transition: {from} -> {to}")]
     InvalidTransition {
@@ -176,6 +190,15 @@ pub enum RobotError {
         confirmed_mask: Option<u8>,
     },
 
+    /// 控制器未接受拖动示教指令（0x2A1 示教状态未切换到期望值）
+    #[error("Teach mode rejected: expected teach status {expected:?}, robot reports {actual:?}")]
+    TeachModeRejected {
+        /// 期望的示教状态
+        expected: TeachStatus,
+        /// 超时前最后一次上报的示教状态
+        actual: TeachStatus,
+    },
+
     /// 运行时健康状态异常
     #[error("Runtime health unhealthy: rx_alive={rx_alive}, tx_alive={tx_alive}, fault={fault:?}")]
     RuntimeHealthUnhealthy {
@@ -300,6 +323,10 @@ pub enum RobotError {
         actual: usize,
     },
 
+    /// 运动学求解失败（逆解无解或超出工作空间）
+    #[error("Kinematics error: {0}")]
+    Kinematics(#[from] crate::kinematics::KinematicsError),
+
     // ==================== Configuration Errors ====================
     /// 配置错误
     #[error("Configuration error: {0}")]
@@ -360,6 +387,7 @@ impl RobotError {
                 | Self::InvalidParameter { .. }
                 | Self::RealtimeUnsupported { .. }
                 | Self::MaintenanceRequired { .. }
+                | Self::TeachModeRejected { .. }
         )
     }
 
@@ -573,6 +601,25 @@ impl From<ProtocolError> for RobotError {
 /// Result 类型别名
 pub type Result<T> = std::result::Result<T, RobotError>;
 
+/// 阻塞式运动（`move_and_wait`）的错误类型
+#[derive(Debug, Error)]
+pub enum MotionError {
+    /// 超时前未到达目标
+    #[error("Motion did not arrive within {timeout_ms}ms")]
+    Timeout {
+        /// 超时时间（毫秒）
+        timeout_ms: u64,
+    },
+
+    /// 运动过程中机械臂上报非正常状态
+    #[error("Robot reported {0:?} during motion")]
+    Fault(RobotStatus),
+
+    /// 发送命令或读取反馈失败
+    #[error(transparent)]
+    Robot(#[from] RobotError),
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
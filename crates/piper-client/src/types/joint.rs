@@ -32,8 +32,9 @@
 //! ```
 
 use super::units::Rad;
+use crate::kinematics::PIPER_JOINT_LIMITS;
 use std::fmt;
-use std::ops::{Index, IndexMut};
+use std::ops::{Add, Index, IndexMut, Mul, Neg, Sub};
 
 /// 关节枚举
 ///
@@ -291,6 +292,139 @@ impl<'a, T> IntoIterator for &'a mut JointArray<T> {
     }
 }
 
+// 逐元素运算
+impl<T: Add<Output = T>> Add for JointArray<T> {
+    type Output = Self;
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        self.map_with(rhs, |a, b| a + b)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for JointArray<T> {
+    type Output = Self;
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        self.map_with(rhs, |a, b| a - b)
+    }
+}
+
+impl<T: Mul<f64, Output = T>> Mul<f64> for JointArray<T> {
+    type Output = Self;
+    #[inline]
+    fn mul(self, rhs: f64) -> Self {
+        self.map(|a| a * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for JointArray<T> {
+    type Output = Self;
+    #[inline]
+    fn neg(self) -> Self {
+        self.map(|a| -a)
+    }
+}
+
+impl JointArray<Rad> {
+    /// 从协议原始关节角（0.001°）创建
+    pub fn from_millidegrees(raw: [i32; 6]) -> Self {
+        JointArray::new(raw).map(Rad::from_millidegrees)
+    }
+
+    /// 转换为协议原始关节角（0.001°）
+    pub fn to_millidegrees(self) -> [i32; 6] {
+        self.map(Rad::to_millidegrees).into_array()
+    }
+
+    /// 将各关节限制在 `limits` 范围内
+    pub fn clamp_to_limits(self, limits: &JointLimits) -> Self {
+        self.clamp_to_limits_checked(limits).0
+    }
+
+    /// 同 [`Self::clamp_to_limits`]，并返回超出范围（被修正）的关节
+    pub fn clamp_to_limits_checked(self, limits: &JointLimits) -> (Self, Vec<Joint>) {
+        let mut clamped = self;
+        let mut out_of_range = Vec::new();
+        for joint in Joint::ALL {
+            // 不用 `f64::clamp`：非法限位（min > max 或 NaN）不能在控制循环里 panic
+            let value = Rad(self[joint].0.max(limits.min[joint].0).min(limits.max[joint].0));
+            if value != self[joint] {
+                out_of_range.push(joint);
+            }
+            clamped[joint] = value;
+        }
+        (clamped, out_of_range)
+    }
+}
+
+/// 关节软限位（弧度）
+///
+/// 默认值为 Piper 文档给出的机械范围（与 [`PIPER_JOINT_LIMITS`] 一致）。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JointLimits {
+    /// 各关节下限
+    pub min: JointArray<Rad>,
+    /// 各关节上限
+    pub max: JointArray<Rad>,
+}
+
+impl JointLimits {
+    /// Piper 机械限位
+    pub const PIPER: Self = {
+        let l = PIPER_JOINT_LIMITS;
+        JointLimits {
+            min: JointArray::new([
+                Rad(l[0].0),
+                Rad(l[1].0),
+                Rad(l[2].0),
+                Rad(l[3].0),
+                Rad(l[4].0),
+                Rad(l[5].0),
+            ]),
+            max: JointArray::new([
+                Rad(l[0].1),
+                Rad(l[1].1),
+                Rad(l[2].1),
+                Rad(l[3].1),
+                Rad(l[4].1),
+                Rad(l[5].1),
+            ]),
+        }
+    };
+
+    /// 检查限位本身是否合法
+    ///
+    /// 每个关节的上下限都必须是有限值，且满足 `min <= max`。
+    pub fn validate(&self) -> Result<(), String> {
+        for joint in Joint::ALL {
+            let (min, max) = (self.min[joint].0, self.max[joint].0);
+            if !min.is_finite() || !max.is_finite() {
+                return Err(format!("{joint} limits must be finite, got [{min}, {max}]"));
+            }
+            if min > max {
+                return Err(format!(
+                    "{joint} limits are inverted: min {min} > max {max}"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// 检查位置是否在限位内
+    pub fn contains(&self, positions: &JointArray<Rad>) -> bool {
+        Joint::ALL
+            .iter()
+            .all(|&joint| (self.min[joint]..=self.max[joint]).contains(&positions[joint]))
+    }
+}
+
+impl Default for JointLimits {
+    fn default() -> Self {
+        Self::PIPER
+    }
+}
+
 /// 关节位置（弧度）
 pub type JointPositions = JointArray<Rad>;
 
@@ -420,6 +554,60 @@ mod tests {
         assert_eq!(positions[Joint::J1], Rad(0.0));
     }
 
+    #[test]
+    fn test_joint_array_elementwise_ops() {
+        let a = JointArray::new([Rad(1.0), Rad(2.0), Rad(3.0), Rad(4.0), Rad(5.0), Rad(6.0)]);
+        let b = JointArray::splat(Rad(0.5));
+
+        assert_eq!((a + b)[Joint::J1], Rad(1.5));
+        assert_eq!((a - b)[Joint::J6], Rad(5.5));
+        assert_eq!((a * 2.0)[Joint::J3], Rad(6.0));
+        assert_eq!((-a)[Joint::J2], Rad(-2.0));
+    }
+
+    #[test]
+    fn test_joint_array_millidegrees() {
+        let raw = [0, 90_000, -45_500, 180_000, 1, -1];
+        let positions = JointPositions::from_millidegrees(raw);
+        assert!((positions[Joint::J2].0 - std::f64::consts::FRAC_PI_2).abs() < 1e-10);
+        assert_eq!(positions.to_millidegrees(), raw);
+    }
+
+    #[test]
+    fn test_clamp_to_limits_reports_out_of_range_joints() {
+        let limits = JointLimits::default();
+        let target = JointArray::new([Rad(3.0), Rad(0.5), Rad(0.1), Rad(0.0), Rad(-2.0), Rad(0.0)]);
+        assert!(!limits.contains(&target));
+
+        let (clamped, out_of_range) = target.clamp_to_limits_checked(&limits);
+        assert_eq!(out_of_range, vec![Joint::J1, Joint::J3, Joint::J5]);
+        assert_eq!(clamped[Joint::J1], Rad(2.618));
+        assert_eq!(clamped[Joint::J2], Rad(0.5));
+        assert_eq!(clamped[Joint::J3], Rad(0.0));
+        assert_eq!(clamped[Joint::J5], Rad(-1.22));
+        assert!(limits.contains(&clamped));
+        assert_eq!(clamped.clamp_to_limits(&limits), clamped);
+    }
+
+    #[test]
+    fn test_joint_limits_validate_and_clamp_do_not_panic_on_invalid_limits() {
+        assert!(JointLimits::default().validate().is_ok());
+
+        let mut inverted = JointLimits::default();
+        inverted.min[Joint::J2] = Rad(1.0);
+        inverted.max[Joint::J2] = Rad(-1.0);
+        assert!(inverted.validate().unwrap_err().contains("J2"));
+
+        let mut nan = JointLimits::default();
+        nan.max[Joint::J4] = Rad(f64::NAN);
+        assert!(nan.validate().unwrap_err().contains("J4"));
+
+        // 即使绕过校验，限位也不能让控制循环 panic
+        let target = JointArray::splat(Rad(0.0));
+        let _ = target.clamp_to_limits_checked(&inverted);
+        let _ = target.clamp_to_limits_checked(&nan);
+    }
+
     #[test]
     fn test_from_into_array() {
         let data = [1, 2, 3, 4, 5, 6];
@@ -25,6 +25,8 @@
 //! ```
 
 use crate::types::Joint;
+use piper_driver::DriverError;
+use piper_protocol::{FirmwareCapabilities, FirmwareFeature, FirmwareVersion, MoveMode};
 use semver::Version;
 
 /// 固件特性（在连接时确定，之后只读）
@@ -37,6 +39,7 @@ use semver::Version;
 /// - `firmware_version`: 固件版本号
 /// - `joint_flip_map`: 关节 flip 标志（v1.7-3 之前有 bug）
 /// - `torque_scaling`: 力矩缩放因子（旧固件 J1-3 力矩被放大 4x）
+/// - `capabilities`: 固件能力集合（如 `MoveM` 需要 V1.5-2+，`MoveCpv` 需要 V1.8-1+）
 ///
 /// # 性能
 ///
@@ -58,6 +61,11 @@ pub struct DeviceQuirks {
     /// v1.8-2 及之前：J1-3 的命令力矩被固件执行为 4x
     /// v1.8-2 之后：所有关节无缩放（因子 = 1.0）
     pub torque_scaling: [f64; 6],
+
+    /// 固件能力集合（按能力表从固件版本推导，可由 `PiperBuilder` 覆盖）
+    ///
+    /// 使能时据此拒绝固件不支持的 MOVE 模式，而不是下发一个被机械臂静默忽略的帧。
+    pub capabilities: FirmwareCapabilities,
 }
 
 impl DeviceQuirks {
@@ -99,10 +107,53 @@ impl DeviceQuirks {
             [1.0; 6]
         };
 
+        let capabilities = FirmwareCapabilities::from_version(FirmwareVersion::new(
+            saturate_version_part(version.major),
+            saturate_version_part(version.minor),
+            saturate_version_part(version.patch),
+            0,
+        ));
+
         Self {
             firmware_version: version,
             joint_flip_map,
             torque_scaling,
+            capabilities,
+        }
+    }
+
+    /// 覆盖固件能力集合（用于测试或非标准固件）
+    pub fn with_capabilities(mut self, capabilities: FirmwareCapabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// 检查已连接固件是否支持指定 MOVE 模式
+    ///
+    /// # 错误
+    ///
+    /// - `DriverError::UnsupportedByFirmware`：固件版本早于该模式要求的最低版本
+    pub fn ensure_move_mode_supported(&self, move_mode: MoveMode) -> Result<(), DriverError> {
+        match FirmwareFeature::for_move_mode(move_mode) {
+            Some(feature) => self.ensure_feature_supported(feature),
+            None => Ok(()),
+        }
+    }
+
+    /// 检查已连接固件是否支持指定功能
+    ///
+    /// # 错误
+    ///
+    /// - `DriverError::UnsupportedByFirmware`：固件版本早于该功能要求的最低版本
+    pub fn ensure_feature_supported(&self, feature: FirmwareFeature) -> Result<(), DriverError> {
+        if self.capabilities.supports(feature) {
+            Ok(())
+        } else {
+            Err(DriverError::UnsupportedByFirmware {
+                feature,
+                required: feature.min_version(),
+                detected: self.capabilities.version(),
+            })
         }
     }
 
@@ -211,6 +262,10 @@ impl DeviceQuirks {
     }
 }
 
+fn saturate_version_part(part: u64) -> u16 {
+    u16::try_from(part).unwrap_or(u16::MAX)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -303,4 +358,25 @@ mod tests {
         assert_eq!(pos, -1.0); // flip
         assert_eq!(torque_scaled, -0.5); // flip + scale (2.0 * -0.25 = -0.5)
     }
+
+    #[test]
+    fn test_device_quirks_capabilities_gate_move_modes() {
+        let quirks = DeviceQuirks::from_firmware_version(Version::new(1, 5, 1));
+        let error = quirks.ensure_move_mode_supported(MoveMode::MoveM).unwrap_err();
+        assert!(matches!(
+            error,
+            DriverError::UnsupportedByFirmware {
+                feature: FirmwareFeature::MoveM,
+                ..
+            }
+        ));
+        assert!(quirks.ensure_move_mode_supported(MoveMode::MoveJ).is_ok());
+
+        let quirks = DeviceQuirks::from_firmware_version(Version::new(1, 7, 2));
+        assert!(quirks.ensure_move_mode_supported(MoveMode::MoveM).is_ok());
+        assert!(quirks.ensure_move_mode_supported(MoveMode::MoveCpv).is_err());
+
+        let quirks = quirks.with_capabilities(FirmwareCapabilities::all());
+        assert!(quirks.ensure_move_mode_supported(MoveMode::MoveCpv).is_ok());
+    }
 }
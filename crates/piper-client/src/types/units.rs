@@ -59,6 +59,18 @@ impl Rad {
         Deg(self.0.to_degrees())
     }
 
+    /// 从协议原始值（0.001°）创建
+    #[inline]
+    pub fn from_millidegrees(raw: i32) -> Self {
+        Deg::from_millidegrees(raw).to_rad()
+    }
+
+    /// 转换为协议原始值（0.001°，四舍五入并饱和到 `i32`）
+    #[inline]
+    pub fn to_millidegrees(self) -> i32 {
+        self.to_deg().to_millidegrees()
+    }
+
     /// 获取原始值
     #[inline]
     pub fn value(self) -> f64 {
@@ -233,6 +245,18 @@ impl Deg {
         Rad(self.0.to_radians())
     }
 
+    /// 从协议原始值（0.001°）创建
+    #[inline]
+    pub fn from_millidegrees(raw: i32) -> Self {
+        Deg(raw as f64 / 1000.0)
+    }
+
+    /// 转换为协议原始值（0.001°，四舍五入并饱和到 `i32`）
+    #[inline]
+    pub fn to_millidegrees(self) -> i32 {
+        (self.0 * 1000.0).round() as i32
+    }
+
     /// 获取原始值
     #[inline]
     pub fn value(self) -> f64 {
@@ -354,6 +378,20 @@ impl DivAssign<f64> for Deg {
     }
 }
 
+impl From<Deg> for Rad {
+    #[inline]
+    fn from(deg: Deg) -> Self {
+        deg.to_rad()
+    }
+}
+
+impl From<Rad> for Deg {
+    #[inline]
+    fn from(rad: Rad) -> Self {
+        rad.to_deg()
+    }
+}
+
 /// 牛顿·米（力矩单位）
 ///
 /// 表示力矩值。使用 NewType 模式提供类型安全。
@@ -622,6 +660,74 @@ impl Div<std::time::Duration> for RadPerSecond {
     }
 }
 
+/// 毫米（长度单位）
+///
+/// 表示夹爪行程等线性距离。使用 NewType 模式提供类型安全。
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Millimeter(pub f64);
+
+impl Millimeter {
+    /// 零长度常量
+    pub const ZERO: Self = Millimeter(0.0);
+
+    /// 创建新的长度值
+    #[inline]
+    pub const fn new(value: f64) -> Self {
+        Millimeter(value)
+    }
+
+    /// 获取原始值
+    #[inline]
+    pub fn value(self) -> f64 {
+        self.0
+    }
+
+    /// 限制范围
+    #[inline]
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        Millimeter(self.0.clamp(min.0, max.0))
+    }
+}
+
+impl fmt::Display for Millimeter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.3} mm", self.0)
+    }
+}
+
+impl Add for Millimeter {
+    type Output = Self;
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Millimeter(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Millimeter {
+    type Output = Self;
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Millimeter(self.0 - rhs.0)
+    }
+}
+
+impl Mul<f64> for Millimeter {
+    type Output = Self;
+    #[inline]
+    fn mul(self, rhs: f64) -> Self {
+        Millimeter(self.0 * rhs)
+    }
+}
+
+impl Div<f64> for Millimeter {
+    type Output = Self;
+    #[inline]
+    fn div(self, rhs: f64) -> Self {
+        Millimeter(self.0 / rhs)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -641,6 +747,23 @@ mod tests {
         assert!((rad.0 - std::f64::consts::PI).abs() < 1e-6);
     }
 
+    #[test]
+    fn test_rad_deg_from_into() {
+        let deg: Deg = Rad(std::f64::consts::FRAC_PI_2).into();
+        assert!((deg.0 - 90.0).abs() < 1e-10);
+        let rad = Rad::from(Deg(180.0));
+        assert!((rad.0 - std::f64::consts::PI).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_millidegrees_round_trip() {
+        assert_eq!(Deg::from_millidegrees(90_500), Deg(90.5));
+        assert_eq!(Deg(-12.3456).to_millidegrees(), -12_346);
+        assert_eq!(Rad::from_millidegrees(180_000).to_millidegrees(), 180_000);
+        assert_eq!(Rad(std::f64::consts::FRAC_PI_2).to_millidegrees(), 90_000);
+        assert_eq!(Deg(f64::MAX).to_millidegrees(), i32::MAX);
+    }
+
     #[test]
     fn test_rad_operations() {
         let r1 = Rad(1.0);
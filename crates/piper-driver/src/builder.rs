@@ -4,7 +4,8 @@
 
 use crate::error::DriverError;
 use crate::pipeline::PipelineConfig;
-use crate::piper::{Piper, StartupValidationDeadline};
+use crate::piper::{DropStopAction, Piper, StartupValidationDeadline};
+use crate::watchdog::WatchdogConfig;
 #[cfg(all(
     target_os = "linux",
     any(feature = "socketcan", feature = "auto-backend")
@@ -15,10 +16,11 @@ use piper_can::gs_usb::GsUsbCanAdapter;
 #[cfg(any(feature = "gs_usb", feature = "auto-backend"))]
 use piper_can::gs_usb::device::GsUsbDeviceSelector;
 use piper_can::{
-    CanAdapter, CanDeviceError, CanDeviceErrorKind, CanError, RealtimeTxAdapter, RxAdapter,
-    SplittableAdapter,
+    BackendCapability, CanAdapter, CanDeviceError, CanDeviceErrorKind, CanError, PiperFrame,
+    RealtimeTxAdapter, ReceivedFrame, RxAdapter, SplittableAdapter, TxEchoStats,
 };
-use std::time::Duration;
+use piper_protocol::ids::NodeId;
+use std::time::{Duration, Instant};
 
 /// 类型化的连接目标。
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
@@ -75,6 +77,78 @@ impl BuiltBackend {
     }
 }
 
+/// 按节点 ID 过滤并还原 RX 帧：只放行发给本节点的帧，ID 还原为标准协议 ID。
+struct NodeIdRxAdapter<R> {
+    inner: R,
+    node_id: NodeId,
+}
+
+impl<R: RxAdapter> RxAdapter for NodeIdRxAdapter<R> {
+    fn receive(&mut self) -> Result<ReceivedFrame, CanError> {
+        loop {
+            let received = self.inner.receive()?;
+            if let Some(frame) = self.node_id.to_canonical(received.frame) {
+                return Ok(ReceivedFrame { frame, ..received });
+            }
+        }
+    }
+
+    fn backend_capability(&self) -> BackendCapability {
+        self.inner.backend_capability()
+    }
+
+    fn startup_probe_until(
+        &mut self,
+        deadline: Instant,
+    ) -> Result<Option<BackendCapability>, CanError> {
+        self.inner.startup_probe_until(deadline)
+    }
+
+    fn tx_echo_stats(&self) -> Option<TxEchoStats> {
+        self.inner.tx_echo_stats()
+    }
+}
+
+/// 将 TX 帧的标准协议 ID 平移到本节点的总线 ID。
+struct NodeIdTxAdapter<T> {
+    inner: T,
+    node_id: NodeId,
+}
+
+impl<T: RealtimeTxAdapter> RealtimeTxAdapter for NodeIdTxAdapter<T> {
+    fn send_control(&mut self, frame: PiperFrame, budget: Duration) -> Result<(), CanError> {
+        self.inner.send_control(self.node_id.to_bus(frame), budget)
+    }
+
+    fn send_shutdown_until(
+        &mut self,
+        frame: PiperFrame,
+        deadline: Instant,
+    ) -> Result<(), CanError> {
+        self.inner.send_shutdown_until(self.node_id.to_bus(frame), deadline)
+    }
+}
+
+impl BuiltBackend {
+    /// 非默认节点时在 RX/TX 两侧套上 ID 平移适配器。
+    fn with_node_id(self, node_id: NodeId) -> Self {
+        if node_id.is_default() {
+            return self;
+        }
+        Self {
+            rx: Box::new(NodeIdRxAdapter {
+                inner: self.rx,
+                node_id,
+            }),
+            tx: Box::new(NodeIdTxAdapter {
+                inner: self.tx,
+                node_id,
+            }),
+            ..self
+        }
+    }
+}
+
 trait BackendFactory {
     fn open_socketcan(
         &self,
@@ -173,6 +247,7 @@ pub struct PiperBuilder {
     baud_rate: u32,
     pipeline_config: PipelineConfig,
     startup_validation_timeout: Duration,
+    node_id: NodeId,
 }
 
 impl PiperBuilder {
@@ -183,6 +258,7 @@ impl PiperBuilder {
             baud_rate: 1_000_000,
             pipeline_config: PipelineConfig::default(),
             startup_validation_timeout: crate::piper::STRICT_TIMESTAMP_VALIDATION_TIMEOUT,
+            node_id: NodeId::DEFAULT,
         }
     }
 
@@ -232,6 +308,26 @@ impl PiperBuilder {
         self
     }
 
+    /// 启用软件看门狗（设置 [`PipelineConfig::watchdog`]）。
+    ///
+    /// 第一次 `Piper::pet_watchdog()` 之后，若超过 `interval` 未再次喂狗，
+    /// TX 线程会锁存故障并发送 `action` 对应的停止帧。
+    /// 之后再调用 [`PiperBuilder::pipeline_config`] 会覆盖此设置。
+    pub fn with_watchdog(mut self, interval: Duration, action: DropStopAction) -> Self {
+        self.pipeline_config.watchdog = Some(WatchdogConfig::new(interval, action));
+        self
+    }
+
+    /// 选择本实例控制的机械臂节点（多臂共享同一 CAN 总线）。
+    ///
+    /// 非默认节点时，driver 只解码 ID 属于该节点的反馈帧（ID 还原为标准协议 ID 后再解析），
+    /// 发出的命令帧 ID 按节点偏移平移。钩子、录制与飞行记录器看到的都是还原后的标准 ID。
+    /// 默认 [`NodeId::DEFAULT`]（无偏移）。
+    pub fn with_node_id(mut self, node_id: NodeId) -> Self {
+        self.node_id = node_id;
+        self
+    }
+
     /// 设置整个启动验收流程的总超时预算。
     ///
     /// 该预算覆盖：
@@ -248,6 +344,23 @@ impl PiperBuilder {
         self.build_with_factory(&RealBackendFactory)
     }
 
+    /// 使用调用方提供的 CAN 适配器构建 Piper 实例。
+    ///
+    /// 用于注入 mock/回放适配器或本 crate 未内置的第三方后端。连接目标与波特率
+    /// 由适配器自身决定（`target()`/`baud_rate()` 被忽略，`bus_speed()` 报告 builder 的波特率），
+    /// Pipeline 配置、节点 ID 与启动验收超时照常生效。
+    pub fn build_with_adapter<A>(self, adapter: A) -> Result<Piper, DriverError>
+    where
+        A: SplittableAdapter + Send + 'static,
+        A::RxAdapter: Send + 'static,
+        A::TxAdapter: Send + 'static,
+    {
+        let startup_deadline = StartupValidationDeadline::after(self.startup_validation_timeout);
+        let (rx, tx) = adapter.split().map_err(DriverError::Can)?;
+        let backend = BuiltBackend::new(rx, tx, "custom", self.baud_rate);
+        self.build_backend_until_deadline(backend, startup_deadline)
+    }
+
     fn build_with_factory(self, factory: &impl BackendFactory) -> Result<Piper, DriverError> {
         let receive_timeout = Duration::from_millis(self.pipeline_config.receive_timeout_ms);
         let startup_deadline = StartupValidationDeadline::after(self.startup_validation_timeout);
@@ -336,6 +449,7 @@ impl PiperBuilder {
             )));
         }
 
+        let backend = backend.with_node_id(self.node_id);
         let interface = backend.interface;
         let bus_speed = backend.bus_speed;
         Piper::new_dual_thread_parts_with_startup_deadline(
@@ -520,8 +634,9 @@ impl Default for PiperBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::Mutex;
-    use std::time::Instant;
+    use crate::pipeline::FrameCommitPolicy;
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
 
     fn received(frame: piper_can::PiperFrame) -> piper_can::ReceivedFrame {
         piper_can::ReceivedFrame::new(frame, piper_can::TimestampProvenance::None)
@@ -895,6 +1010,121 @@ mod tests {
         );
     }
 
+    struct ScriptedRxAdapter {
+        frames: VecDeque<PiperFrame>,
+    }
+
+    impl RxAdapter for ScriptedRxAdapter {
+        fn receive(&mut self) -> Result<piper_can::ReceivedFrame, CanError> {
+            self.frames.pop_front().map(received).ok_or(CanError::Timeout)
+        }
+    }
+
+    struct CapturingTxAdapter {
+        sent: Arc<Mutex<Vec<PiperFrame>>>,
+    }
+
+    impl RealtimeTxAdapter for CapturingTxAdapter {
+        fn send_control(&mut self, frame: PiperFrame, _budget: Duration) -> Result<(), CanError> {
+            self.sent.lock().unwrap().push(frame);
+            Ok(())
+        }
+
+        fn send_shutdown_until(
+            &mut self,
+            frame: PiperFrame,
+            _deadline: Instant,
+        ) -> Result<(), CanError> {
+            self.sent.lock().unwrap().push(frame);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_node_id_backend_decodes_only_own_node_and_shifts_commands() {
+        let node_id = NodeId::new(0x300).unwrap();
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let rx = ScriptedRxAdapter {
+            frames: [
+                PiperFrame::new_standard(0x2A1, [1; 8]).unwrap(),
+                PiperFrame::new_standard(0x5A1, [2; 8]).unwrap(),
+                PiperFrame::new_standard(0x566, [3; 8]).unwrap(),
+            ]
+            .into(),
+        };
+        let tx = CapturingTxAdapter { sent: sent.clone() };
+        let mut backend =
+            BuiltBackend::new(rx, tx, "socketcan:can0", 1_000_000).with_node_id(node_id);
+
+        let first = backend.rx.receive().unwrap().frame;
+        assert_eq!(first.raw_id(), 0x2A1);
+        assert_eq!(first.data(), &[2; 8]);
+        assert_eq!(backend.rx.receive().unwrap().frame.raw_id(), 0x266);
+        assert!(matches!(backend.rx.receive(), Err(CanError::Timeout)));
+
+        let command = PiperFrame::new_standard(0x15A, [0; 8]).unwrap();
+        let stop = PiperFrame::new_standard(0x150, [0x01]).unwrap();
+        backend.tx.send_control(command, Duration::from_millis(1)).unwrap();
+        backend
+            .tx
+            .send_shutdown_until(stop, Instant::now() + Duration::from_millis(1))
+            .unwrap();
+        let sent_ids: Vec<_> = sent.lock().unwrap().iter().map(PiperFrame::raw_id).collect();
+        assert_eq!(sent_ids, vec![0x45A, 0x450]);
+    }
+
+    #[test]
+    fn test_builder_with_watchdog_sets_pipeline_config() {
+        let builder = PiperBuilder::new()
+            .with_watchdog(Duration::from_millis(50), DropStopAction::EmergencyStop);
+
+        assert_eq!(
+            builder.pipeline_config.watchdog,
+            Some(WatchdogConfig::new(
+                Duration::from_millis(50),
+                DropStopAction::EmergencyStop
+            ))
+        );
+    }
+
+    struct SplittableTestAdapter;
+
+    impl CanAdapter for SplittableTestAdapter {
+        fn send(&mut self, _frame: PiperFrame) -> Result<(), CanError> {
+            Ok(())
+        }
+
+        fn receive(&mut self) -> Result<piper_can::ReceivedFrame, CanError> {
+            Err(CanError::Timeout)
+        }
+    }
+
+    impl SplittableAdapter for SplittableTestAdapter {
+        type RxAdapter = SoftBootstrapRxAdapter;
+        type TxAdapter = TestTxAdapter;
+
+        fn split(self) -> Result<(Self::RxAdapter, Self::TxAdapter), CanError> {
+            Ok((SoftBootstrapRxAdapter::new(), TestTxAdapter))
+        }
+    }
+
+    #[test]
+    fn test_build_with_adapter_uses_injected_backend() {
+        let piper = PiperBuilder::new()
+            .socketcan("can-does-not-exist")
+            .baud_rate(500_000)
+            .startup_validation_timeout(Duration::from_millis(200))
+            .build_with_adapter(SplittableTestAdapter)
+            .expect("custom adapter should bypass target selection");
+
+        assert_eq!(piper.interface(), "custom");
+        assert_eq!(piper.bus_speed(), 500_000);
+        assert_eq!(
+            piper.backend_capability(),
+            piper_can::BackendCapability::SoftRealtime
+        );
+    }
+
     #[test]
     fn test_builder_chain() {
         let config = PipelineConfig {
@@ -902,12 +1132,20 @@ mod tests {
             frame_group_timeout_ms: 20,
             velocity_buffer_timeout_us: 15_000,
             low_speed_drive_state_freshness_ms: 150,
+            joint_position_consistency_window_us: 5_000,
+            commit_policy: FrameCommitPolicy::Timeout,
+            commit_window_us: 1_000,
+            max_tx_rate_hz: None,
+            flight_recorder_capacity: 64,
+            watchdog: None,
+            keepalive_rate_hz: None,
         };
         let builder = PiperBuilder::new()
             .gs_usb_bus_address(1, 12)
             .baud_rate(500_000)
             .pipeline_config(config.clone())
-            .startup_validation_timeout(Duration::from_millis(25));
+            .startup_validation_timeout(Duration::from_millis(25))
+            .with_node_id(NodeId::new(0x300).unwrap());
 
         assert_eq!(
             builder.target,
@@ -922,6 +1160,7 @@ mod tests {
             builder.startup_validation_timeout,
             Duration::from_millis(25)
         );
+        assert_eq!(builder.node_id.id_offset(), 0x300);
     }
 
     #[test]
@@ -6,6 +6,7 @@ use crate::DriverError;
 use crossbeam_channel::Sender;
 use piper_can::PiperFrame;
 use smallvec::SmallVec;
+use std::collections::VecDeque;
 use std::sync::Mutex;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Instant;
@@ -368,6 +369,11 @@ impl SoftRealtimeMailbox {
         }
     }
 
+    /// 已发布、等待 TX 线程取走的命令数
+    pub(crate) fn len(&self) -> usize {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).ready_len
+    }
+
     pub(crate) fn try_recv(&self) -> Result<SoftRealtimeCommand, SoftRealtimeTryRecvError> {
         let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
         if state.ready_len == 0 {
@@ -385,6 +391,20 @@ impl SoftRealtimeMailbox {
         state.slots[slot].take().ok_or(SoftRealtimeTryRecvError::Disconnected)
     }
 
+    /// 取出全部已发布、尚未被 TX 线程取走的命令
+    pub(crate) fn drain_ready(&self) -> Vec<SoftRealtimeCommand> {
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut drained = Vec::with_capacity(state.ready_len);
+        while state.ready_len > 0 {
+            let slot = state.ready_queue[state.ready_head];
+            state.ready_head = (state.ready_head + 1) % SOFT_REALTIME_MAILBOX_CAPACITY;
+            state.ready_len -= 1;
+            state.slot_states[slot] = SoftRealtimeSlotState::Vacant;
+            drained.extend(state.slots[slot].take());
+        }
+        drained
+    }
+
     fn publish_reserved(
         &self,
         slot: usize,
@@ -710,7 +730,9 @@ impl ReliableCommand {
 /// 命令优先级
 ///
 /// 用于区分不同类型的命令，优化发送策略。
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// 按重要性排序：`RealtimeControl`（可丢弃）< `ReliableCommand`（不可丢弃），
+/// 见 [`Piper::drain_commands`](crate::Piper::drain_commands)。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum CommandPriority {
     /// 实时控制命令（可丢弃）
     ///
@@ -779,6 +801,120 @@ impl From<PiperCommand> for PiperFrame {
     }
 }
 
+/// [`CommandMailbox`] 每个优先级最多缓存的不同 CAN ID 数量
+pub(crate) const COMMAND_MAILBOX_CAPACITY_PER_PRIORITY: usize = 64;
+
+impl CommandPriority {
+    /// 邮箱出队顺序（越小越先发送）
+    #[inline]
+    fn lane(self) -> usize {
+        match self {
+            CommandPriority::RealtimeControl => 0,
+            CommandPriority::ReliableCommand => 1,
+        }
+    }
+}
+
+/// [`CommandMailbox::push`] 的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CommandMailboxPush {
+    /// 新的 (CAN ID, 优先级) 条目已入队
+    Queued,
+    /// 覆盖了同一 (CAN ID, 优先级) 尚未发送的旧命令
+    Replaced,
+    /// 该优先级已缓存 [`COMMAND_MAILBOX_CAPACITY_PER_PRIORITY`] 个不同 CAN ID
+    Full,
+}
+
+/// 最新值优先（Latest Wins）的合并命令邮箱
+///
+/// 按 (CAN ID, 优先级) 只保留最新一帧：
+/// - 同一 key 的新命令直接覆盖旧命令，并沿用旧命令的排队位置（不同 ID 之间保持公平）
+/// - 按优先级分别出队：TX 线程在实时路径上取 `RealtimeControl`，`ReliableCommand`
+///   则交给可靠通道发送（与 [`ReliableCommand`] 相同的发送/确认语义）
+///
+/// 控制循环产生命令的速度超过总线排空速度时，过期的设定值会被丢弃而不是排队，
+/// 避免总线上执行几个周期之前的目标位置。
+#[derive(Debug, Default)]
+pub(crate) struct CommandMailbox {
+    lanes: Mutex<[VecDeque<PiperFrame>; 2]>,
+}
+
+impl CommandMailbox {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn push(&self, command: PiperCommand) -> CommandMailboxPush {
+        let mut lanes = self.lanes.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let lane = &mut lanes[command.priority.lane()];
+        let id = command.frame.id();
+        if let Some(queued) = lane.iter_mut().find(|queued| queued.id() == id) {
+            *queued = command.frame;
+            return CommandMailboxPush::Replaced;
+        }
+        if lane.len() >= COMMAND_MAILBOX_CAPACITY_PER_PRIORITY {
+            return CommandMailboxPush::Full;
+        }
+        lane.push_back(command.frame);
+        CommandMailboxPush::Queued
+    }
+
+    /// 取出指定优先级最早排队的一帧
+    pub(crate) fn pop(&self, priority: CommandPriority) -> Option<PiperFrame> {
+        let mut lanes = self.lanes.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        lanes[priority.lane()].pop_front()
+    }
+
+    /// 实时通道已排空时取出最早排队的 `ReliableCommand` 帧
+    ///
+    /// 保证邮箱内 `RealtimeControl` 全部先于 `ReliableCommand` 发送，即使两者
+    /// 由 TX 线程的不同通道消费。
+    pub(crate) fn pop_reliable_after_realtime(&self) -> Option<PiperFrame> {
+        let mut lanes = self.lanes.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let [realtime, reliable] = &mut *lanes;
+        if realtime.is_empty() {
+            reliable.pop_front()
+        } else {
+            None
+        }
+    }
+
+    /// 清空邮箱，返回按 `[RealtimeControl, ReliableCommand]` 被丢弃的命令数
+    pub(crate) fn clear(&self) -> [usize; 2] {
+        let mut lanes = self.lanes.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let [realtime, reliable] = &mut *lanes;
+        [realtime.drain(..).count(), reliable.drain(..).count()]
+    }
+
+    /// 清空优先级低于 `min_priority` 的通道，返回按 `[RealtimeControl, ReliableCommand]`
+    /// 被丢弃的命令数
+    pub(crate) fn clear_below(&self, min_priority: CommandPriority) -> [usize; 2] {
+        let mut lanes = self.lanes.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut dropped = [0; 2];
+        for priority in [
+            CommandPriority::RealtimeControl,
+            CommandPriority::ReliableCommand,
+        ] {
+            if priority < min_priority {
+                dropped[priority.lane()] = lanes[priority.lane()].drain(..).count();
+            }
+        }
+        dropped
+    }
+
+    /// 按 `[RealtimeControl, ReliableCommand]` 的排队深度
+    pub(crate) fn depths(&self) -> [usize; 2] {
+        let lanes = self.lanes.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        [lanes[0].len(), lanes[1].len()]
+    }
+
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.depths().iter().sum()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -804,6 +940,84 @@ mod tests {
         assert_eq!(cmd.frame().raw_id(), 0x123);
     }
 
+    #[test]
+    fn test_command_mailbox_keeps_latest_per_id_and_drains_realtime_first() {
+        let mailbox = CommandMailbox::new();
+        let old = PiperFrame::new_standard(0x15A, [1]).unwrap();
+        let new = PiperFrame::new_standard(0x15A, [2]).unwrap();
+        let other = PiperFrame::new_standard(0x15B, [3]).unwrap();
+        let reliable = PiperFrame::new_standard(0x15A, [4]).unwrap();
+
+        assert_eq!(
+            mailbox.push(PiperCommand::reliable(reliable)),
+            CommandMailboxPush::Queued
+        );
+        assert_eq!(
+            mailbox.push(PiperCommand::realtime(old)),
+            CommandMailboxPush::Queued
+        );
+        assert_eq!(
+            mailbox.push(PiperCommand::realtime(other)),
+            CommandMailboxPush::Queued
+        );
+        assert_eq!(
+            mailbox.push(PiperCommand::realtime(new)),
+            CommandMailboxPush::Replaced
+        );
+        assert_eq!(mailbox.len(), 3);
+
+        assert_eq!(mailbox.pop_reliable_after_realtime(), None);
+        let realtime: Vec<_> =
+            std::iter::from_fn(|| mailbox.pop(CommandPriority::RealtimeControl)).collect();
+        assert_eq!(realtime, vec![new, other]);
+        assert_eq!(mailbox.pop_reliable_after_realtime(), Some(reliable));
+        assert_eq!(mailbox.len(), 0);
+    }
+
+    #[test]
+    fn test_command_mailbox_clear_below_keeps_higher_priorities() {
+        let mailbox = CommandMailbox::new();
+        for id in [0x155, 0x156, 0x157] {
+            let frame = PiperFrame::new_standard(id, [1]).unwrap();
+            mailbox.push(PiperCommand::realtime(frame));
+        }
+        let reliable = PiperFrame::new_standard(0x151, [2]).unwrap();
+        mailbox.push(PiperCommand::reliable(reliable));
+
+        assert_eq!(
+            mailbox.clear_below(CommandPriority::RealtimeControl),
+            [0, 0]
+        );
+        assert_eq!(
+            mailbox.clear_below(CommandPriority::ReliableCommand),
+            [3, 0]
+        );
+        assert_eq!(mailbox.depths(), [0, 1]);
+        assert_eq!(
+            mailbox.pop(CommandPriority::ReliableCommand),
+            Some(reliable)
+        );
+    }
+
+    #[test]
+    fn test_command_mailbox_rejects_new_ids_when_full() {
+        let mailbox = CommandMailbox::new();
+        for id in 0..COMMAND_MAILBOX_CAPACITY_PER_PRIORITY as u32 {
+            let frame = PiperFrame::new_standard(0x100 + id, [0]).unwrap();
+            assert_eq!(
+                mailbox.push(PiperCommand::realtime(frame)),
+                CommandMailboxPush::Queued
+            );
+        }
+        let overflow = PiperFrame::new_standard(0x7FF, [0]).unwrap();
+        assert_eq!(
+            mailbox.push(PiperCommand::realtime(overflow)),
+            CommandMailboxPush::Full
+        );
+        assert_eq!(mailbox.clear(), [COMMAND_MAILBOX_CAPACITY_PER_PRIORITY, 0]);
+        assert!(mailbox.pop(CommandPriority::RealtimeControl).is_none());
+    }
+
     #[test]
     fn test_command_to_frame() {
         let frame = PiperFrame::new_standard(0x123, [1, 2, 3]).unwrap();
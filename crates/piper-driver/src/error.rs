@@ -130,6 +130,21 @@ pub enum DriverError {
     /// 已确认的实时命令等待 TX 线程确认超时
     #[error("Realtime delivery confirmation timed out")]
     RealtimeDeliveryTimeout,
+
+    /// 已连接固件不支持该命令（固件版本早于能力表中的最低版本，或能力被手动关闭）
+    #[error("{feature} is not supported by firmware {detected} (requires {required} or newer)")]
+    UnsupportedByFirmware {
+        /// 缺失的功能
+        feature: piper_protocol::FirmwareFeature,
+        /// 该功能要求的最低固件版本
+        required: piper_protocol::FirmwareVersion,
+        /// 检测到的固件版本
+        detected: piper_protocol::FirmwareVersion,
+    },
+
+    /// 机械臂上报了故障状态（0x2A1），故障锁存清除前拒绝运动命令
+    #[error("Robot reported fault status: {0:?}")]
+    RobotFault(piper_protocol::RobotStatus),
 }
 
 #[derive(Error, Debug)]
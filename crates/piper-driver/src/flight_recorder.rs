@@ -0,0 +1,269 @@
+//! 飞行记录器：保留最近 N 帧 CAN 流量（RX + TX），用于现场故障诊断
+//!
+//! RX 线程在收到帧后、TX 线程在发送成功后各写入一次，写入只涉及原子操作，
+//! 不加锁、不分配内存。读取（[`FlightRecorder::snapshot`]）按写入顺序返回仍在
+//! 缓冲区内的帧。
+//!
+//! 发生致命 CAN 错误（`BusOff`、`BufferOverflow`、设备错误）时，RX 线程会冻结一份
+//! [`FatalFrameDump`] 并写入日志，便于事后查看故障前的总线流量。
+
+use crate::recording::{RecordedFrameDirection, TimestampedFrame};
+use piper_can::{CanError, PiperFrame, TimestampProvenance};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering, fence};
+use tracing::error;
+
+/// 默认保留的帧数
+pub const DEFAULT_FLIGHT_RECORDER_CAPACITY: usize = 256;
+
+const META_EXTENDED_BIT: u64 = 1 << 32;
+const META_LEN_SHIFT: u32 = 33;
+const META_LEN_MASK: u64 = 0xF;
+const META_TX_BIT: u64 = 1 << 37;
+
+/// 致命错误时冻结的帧记录
+#[derive(Debug, Clone)]
+pub struct FatalFrameDump {
+    /// 触发冻结的错误（`CanError` 的 Display 文本）
+    pub error: String,
+    /// 冻结时的主机单调时间（微秒）
+    pub host_mono_us: u64,
+    /// 故障前的最近帧（按时间顺序）
+    pub frames: Vec<TimestampedFrame>,
+}
+
+/// 单个槽位：seqlock 协议，`seq` 为奇数表示正在写入
+#[derive(Default)]
+struct FlightRecorderSlot {
+    seq: AtomicU64,
+    meta: AtomicU64,
+    data: AtomicU64,
+    timestamp_us: AtomicU64,
+}
+
+/// 固定容量的无锁环形缓冲，保留最近 N 帧
+///
+/// 每个槽位用序号（seqlock）标记写入的是第几帧，读者只接受序号与期望位置一致且
+/// 前后两次读取不变的槽位，因此快照不会包含被覆盖或写了一半的帧。
+///
+/// 写入方为 RX、TX 两个线程；只有当一个线程在写入某槽位的过程中被挂起、而另一个
+/// 线程在此期间写满整整一圈缓冲区时才可能读到拼接帧，这对诊断用途可以接受。
+///
+/// 记录的帧时间戳统一为主机单调时钟（微秒，`TimestampProvenance::Userspace`），
+/// 两个方向在同一时钟域内可直接比较。
+pub struct FlightRecorder {
+    slots: Box<[FlightRecorderSlot]>,
+    next_seq: AtomicU64,
+    fatal_dump: Mutex<Option<FatalFrameDump>>,
+}
+
+impl FlightRecorder {
+    /// 创建保留最近 `capacity` 帧的记录器；`capacity == 0` 表示关闭记录
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            slots: (0..capacity).map(|_| FlightRecorderSlot::default()).collect(),
+            next_seq: AtomicU64::new(0),
+            fatal_dump: Mutex::new(None),
+        }
+    }
+
+    /// 缓冲区容量（帧数）
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// 记录一帧（热路径，无锁）
+    pub fn record(&self, frame: &PiperFrame, direction: RecordedFrameDirection, host_mono_us: u64) {
+        if self.slots.is_empty() {
+            return;
+        }
+
+        let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+        let slot = &self.slots[(seq % self.slots.len() as u64) as usize];
+        let mut meta = u64::from(frame.raw_id()) | (u64::from(frame.dlc()) << META_LEN_SHIFT);
+        if frame.is_extended() {
+            meta |= META_EXTENDED_BIT;
+        }
+        if direction == RecordedFrameDirection::Tx {
+            meta |= META_TX_BIT;
+        }
+
+        slot.seq.store(seq.wrapping_mul(2).wrapping_add(1), Ordering::Relaxed);
+        fence(Ordering::Release);
+        slot.meta.store(meta, Ordering::Relaxed);
+        slot.data.store(u64::from_le_bytes(*frame.data_padded()), Ordering::Relaxed);
+        slot.timestamp_us.store(host_mono_us, Ordering::Relaxed);
+        slot.seq.store(seq.wrapping_mul(2).wrapping_add(2), Ordering::Release);
+    }
+
+    /// 按时间顺序返回缓冲区内的帧（最旧在前）
+    pub fn snapshot(&self) -> Vec<TimestampedFrame> {
+        if self.slots.is_empty() {
+            return Vec::new();
+        }
+
+        let end = self.next_seq.load(Ordering::Acquire);
+        let start = end.saturating_sub(self.slots.len() as u64);
+        let mut frames = Vec::with_capacity((end - start) as usize);
+        for seq in start..end {
+            let slot = &self.slots[(seq % self.slots.len() as u64) as usize];
+            let expected = seq.wrapping_mul(2).wrapping_add(2);
+            if slot.seq.load(Ordering::Acquire) != expected {
+                continue;
+            }
+            let meta = slot.meta.load(Ordering::Relaxed);
+            let data = slot.data.load(Ordering::Relaxed);
+            let timestamp_us = slot.timestamp_us.load(Ordering::Relaxed);
+            fence(Ordering::Acquire);
+            if slot.seq.load(Ordering::Relaxed) != expected {
+                continue;
+            }
+            if let Some(frame) = decode_frame(meta, data, timestamp_us) {
+                frames.push(frame);
+            }
+        }
+        frames
+    }
+
+    /// 冻结当前缓冲区并写入错误日志
+    pub(crate) fn capture_fatal(&self, error: &CanError, host_mono_us: u64) {
+        let frames = self.snapshot();
+        error!(
+            "Flight recorder: fatal CAN error `{}`, dumping last {} frame(s)",
+            error,
+            frames.len()
+        );
+        for frame in &frames {
+            error!(
+                "  [{:>12}us] {:?} 0x{:03X} {:02X?}",
+                frame.timestamp_us(),
+                frame.direction,
+                frame.raw_id(),
+                frame.data()
+            );
+        }
+
+        *self.fatal_dump.lock().unwrap_or_else(|poison| poison.into_inner()) =
+            Some(FatalFrameDump {
+                error: error.to_string(),
+                host_mono_us,
+                frames,
+            });
+    }
+
+    /// 最近一次致命错误时冻结的帧记录
+    pub fn fatal_dump(&self) -> Option<FatalFrameDump> {
+        self.fatal_dump.lock().unwrap_or_else(|poison| poison.into_inner()).clone()
+    }
+}
+
+impl Default for FlightRecorder {
+    fn default() -> Self {
+        Self::new(DEFAULT_FLIGHT_RECORDER_CAPACITY)
+    }
+}
+
+impl std::fmt::Debug for FlightRecorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FlightRecorder")
+            .field("capacity", &self.slots.len())
+            .field("recorded_total", &self.next_seq.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
+
+fn decode_frame(meta: u64, data: u64, timestamp_us: u64) -> Option<TimestampedFrame> {
+    let raw_id = meta as u32;
+    let len = ((meta >> META_LEN_SHIFT) & META_LEN_MASK) as usize;
+    let bytes = data.to_le_bytes();
+    let payload = bytes.get(..len)?;
+    let frame = if meta & META_EXTENDED_BIT != 0 {
+        PiperFrame::new_extended(raw_id, payload)
+    } else {
+        PiperFrame::new_standard(raw_id, payload)
+    }
+    .ok()?
+    .with_timestamp_us(timestamp_us);
+
+    Some(TimestampedFrame {
+        frame,
+        direction: if meta & META_TX_BIT != 0 {
+            RecordedFrameDirection::Tx
+        } else {
+            RecordedFrameDirection::Rx
+        },
+        timestamp_provenance: TimestampProvenance::Userspace,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(id: u32, byte: u8) -> PiperFrame {
+        PiperFrame::new_standard(id, [byte, 0xAA]).unwrap()
+    }
+
+    #[test]
+    fn flight_recorder_keeps_last_n_frames_in_order_across_directions() {
+        let recorder = FlightRecorder::new(3);
+        for i in 0..5u8 {
+            let direction = if i % 2 == 0 {
+                RecordedFrameDirection::Rx
+            } else {
+                RecordedFrameDirection::Tx
+            };
+            recorder.record(
+                &frame(0x2A5 + u32::from(i), i),
+                direction,
+                100 + u64::from(i),
+            );
+        }
+        recorder.record(
+            &PiperFrame::new_extended(0x1234_5678, [9; 8]).unwrap(),
+            RecordedFrameDirection::Tx,
+            200,
+        );
+
+        let frames = recorder.snapshot();
+        let summary: Vec<_> = frames
+            .iter()
+            .map(|frame| (frame.raw_id(), frame.direction, frame.timestamp_us()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (0x2A8, RecordedFrameDirection::Tx, 103),
+                (0x2A9, RecordedFrameDirection::Rx, 104),
+                (0x1234_5678, RecordedFrameDirection::Tx, 200),
+            ]
+        );
+        assert_eq!(frames[0].data(), &[3, 0xAA]);
+        assert!(frames[2].frame.is_extended());
+        assert_eq!(frames[2].data(), &[9; 8]);
+    }
+
+    #[test]
+    fn flight_recorder_with_zero_capacity_records_nothing() {
+        let recorder = FlightRecorder::new(0);
+        recorder.record(&frame(0x2A5, 1), RecordedFrameDirection::Rx, 1);
+        assert!(recorder.snapshot().is_empty());
+    }
+
+    #[test]
+    fn flight_recorder_freezes_dump_on_fatal_error() {
+        let recorder = FlightRecorder::new(4);
+        recorder.record(&frame(0x155, 1), RecordedFrameDirection::Tx, 10);
+        recorder.record(&frame(0x2A5, 2), RecordedFrameDirection::Rx, 11);
+
+        recorder.capture_fatal(&CanError::BusOff, 12);
+        recorder.record(&frame(0x2A6, 3), RecordedFrameDirection::Rx, 13);
+
+        let dump = recorder.fatal_dump().expect("fatal dump should be captured");
+        assert_eq!(dump.error, "Bus off");
+        assert_eq!(dump.host_mono_us, 12);
+        let ids: Vec<_> = dump.frames.iter().map(TimestampedFrame::raw_id).collect();
+        assert_eq!(ids, vec![0x155, 0x2A5]);
+        assert_eq!(recorder.snapshot().len(), 3);
+    }
+}
@@ -6,10 +6,22 @@
 //! - Uses monotonic time anchored to application start
 //! - Unaffected by system clock changes (NTP, manual adjustments)
 //! - Safe to store in AtomicU64 for lock-free access
+//!
+//! **Link health**: [`ConnectionMonitor::health`] classifies the link as
+//! [`LinkHealth::Healthy`], [`LinkHealth::Degraded`] (N consecutive expected
+//! feedback frames missed) or [`LinkHealth::Lost`] (timeout expired), and
+//! [`ConnectionMonitor::on_health_change`] notifies on transitions.
 
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU64, Ordering};
 use std::time::Duration;
 
+/// Default expected feedback period (the robot streams feedback at ~200 Hz).
+pub const DEFAULT_EXPECTED_FEEDBACK_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Default number of consecutive missed feedback periods before the link is degraded.
+pub const DEFAULT_DEGRADED_AFTER_MISSED: u32 = 3;
+
 /// Get monotonic time as microseconds since app start
 ///
 /// This is guaranteed to be:
@@ -20,6 +32,33 @@ pub fn monotonic_micros() -> u64 {
     piper_can::monotonic_micros()
 }
 
+/// Structured link health derived from feedback timing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkHealth {
+    /// Feedback is arriving at the expected rate.
+    Healthy,
+    /// At least the configured number of consecutive expected feedback frames were missed,
+    /// but the timeout has not expired yet.
+    Degraded {
+        /// Expected feedback periods elapsed since the last feedback frame.
+        missed: u32,
+    },
+    /// No feedback within the timeout, or no feedback received yet.
+    Lost,
+}
+
+impl LinkHealth {
+    fn kind(self) -> u8 {
+        match self {
+            Self::Healthy => 0,
+            Self::Degraded { .. } => 1,
+            Self::Lost => 2,
+        }
+    }
+}
+
+type HealthCallback = Box<dyn Fn(LinkHealth) + Send + Sync>;
+
 /// Connection health monitor
 ///
 /// Tracks the time since last feedback was received from the robot.
@@ -27,6 +66,11 @@ pub struct ConnectionMonitor {
     last_feedback: AtomicU64,
     seen_feedback: AtomicBool,
     timeout: Duration,
+    expected_interval: Duration,
+    degraded_after_missed: u32,
+    /// `LinkHealth::kind()` of the last state reported by `poll_health`.
+    last_health: AtomicU8,
+    health_callbacks: Mutex<Vec<HealthCallback>>,
 }
 
 impl ConnectionMonitor {
@@ -46,9 +90,78 @@ impl ConnectionMonitor {
             last_feedback: AtomicU64::new(0),
             seen_feedback: AtomicBool::new(false),
             timeout,
+            expected_interval: DEFAULT_EXPECTED_FEEDBACK_INTERVAL,
+            degraded_after_missed: DEFAULT_DEGRADED_AFTER_MISSED,
+            last_health: AtomicU8::new(LinkHealth::Lost.kind()),
+            health_callbacks: Mutex::new(Vec::new()),
         }
     }
 
+    /// Set the expected feedback period and how many consecutive missed periods
+    /// make the link [`LinkHealth::Degraded`].
+    ///
+    /// Defaults are [`DEFAULT_EXPECTED_FEEDBACK_INTERVAL`] and [`DEFAULT_DEGRADED_AFTER_MISSED`].
+    pub fn with_degraded_threshold(
+        mut self,
+        expected_interval: Duration,
+        degraded_after_missed: u32,
+    ) -> Self {
+        self.expected_interval = expected_interval.max(Duration::from_micros(1));
+        self.degraded_after_missed = degraded_after_missed.max(1);
+        self
+    }
+
+    /// Current link health.
+    ///
+    /// `Lost` once the timeout expires (or before any feedback), `Degraded` once
+    /// `degraded_after_missed` expected feedback periods pass without feedback,
+    /// otherwise `Healthy`.
+    pub fn health(&self) -> LinkHealth {
+        if !self.check_connection() {
+            return LinkHealth::Lost;
+        }
+
+        let elapsed = self.time_since_last_feedback();
+        let missed =
+            (elapsed.as_micros() / self.expected_interval.as_micros()).min(u32::MAX as u128) as u32;
+        if missed >= self.degraded_after_missed {
+            LinkHealth::Degraded { missed }
+        } else {
+            LinkHealth::Healthy
+        }
+    }
+
+    /// Register a callback invoked when the health changes between `Healthy`,
+    /// `Degraded` and `Lost`.
+    ///
+    /// Changes of the `missed` count within `Degraded` are not reported. Callbacks
+    /// run on the driver RX thread, so they must return quickly (e.g. set a flag or
+    /// send on a channel) and must not call back into the monitor registration.
+    pub fn on_health_change(&self, callback: impl Fn(LinkHealth) + Send + Sync + 'static) {
+        match self.health_callbacks.lock() {
+            Ok(mut callbacks) => callbacks.push(Box::new(callback)),
+            Err(poisoned) => poisoned.into_inner().push(Box::new(callback)),
+        }
+    }
+
+    /// Re-evaluate health and notify callbacks on a state change.
+    ///
+    /// Called by the RX thread after every received frame and every receive timeout.
+    pub fn poll_health(&self) -> LinkHealth {
+        let health = self.health();
+        let previous = self.last_health.swap(health.kind(), Ordering::AcqRel);
+        if previous != health.kind() {
+            let callbacks = match self.health_callbacks.lock() {
+                Ok(callbacks) => callbacks,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            for callback in callbacks.iter() {
+                callback(health);
+            }
+        }
+        health
+    }
+
     /// Check if connection is still alive
     ///
     /// Returns true if feedback received within timeout window
@@ -212,4 +325,55 @@ mod tests {
         assert!(driver_before <= can_now);
         assert!(can_now <= driver_after);
     }
+
+    #[test]
+    fn test_link_health_degrades_then_lost() {
+        let monitor = ConnectionMonitor::new(Duration::from_millis(200))
+            .with_degraded_threshold(Duration::from_millis(10), 3);
+        assert_eq!(monitor.health(), LinkHealth::Lost);
+
+        monitor.register_feedback();
+        assert_eq!(monitor.health(), LinkHealth::Healthy);
+
+        std::thread::sleep(Duration::from_millis(50));
+        match monitor.health() {
+            LinkHealth::Degraded { missed } => assert!((3..20).contains(&missed), "{missed}"),
+            other => panic!("expected Degraded, got {other:?}"),
+        }
+
+        std::thread::sleep(Duration::from_millis(200));
+        assert_eq!(monitor.health(), LinkHealth::Lost);
+
+        monitor.register_feedback();
+        assert_eq!(monitor.health(), LinkHealth::Healthy);
+    }
+
+    #[test]
+    fn test_on_health_change_reports_only_state_transitions() {
+        use std::sync::Arc;
+
+        let monitor = ConnectionMonitor::new(Duration::from_millis(200))
+            .with_degraded_threshold(Duration::from_millis(10), 3);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        monitor.on_health_change(move |health| sink.lock().unwrap().push(health));
+
+        // Initial Lost state is not a transition.
+        monitor.poll_health();
+        monitor.register_feedback();
+        monitor.poll_health();
+        monitor.poll_health();
+        std::thread::sleep(Duration::from_millis(40));
+        monitor.poll_health();
+        std::thread::sleep(Duration::from_millis(20));
+        monitor.poll_health();
+        std::thread::sleep(Duration::from_millis(200));
+        monitor.poll_health();
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 3, "{seen:?}");
+        assert_eq!(seen[0], LinkHealth::Healthy);
+        assert!(matches!(seen[1], LinkHealth::Degraded { missed } if missed >= 3));
+        assert_eq!(seen[2], LinkHealth::Lost);
+    }
 }
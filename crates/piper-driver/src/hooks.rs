@@ -33,8 +33,16 @@
 
 use crate::recording::{RecordedFrameDirection, RecordedFrameEvent, TimestampProvenance};
 use piper_can::{PiperFrame, ReceivedFrame};
+use std::ops::{Bound, RangeBounds};
 use std::sync::Arc;
 
+/// 帧方向（RX/TX）
+///
+/// 每个 [`RecordedFrameEvent`] 都带有方向：RX 帧由 `rx_loop` 触发，TX 帧在 `tx_loop`
+/// 发送成功后触发。同一个回调默认两个方向都会收到，因此单个录制钩子即可得到
+/// 按时间顺序合并的 TX+RX 帧流；需要只看一个方向时用 [`HookFilter::direction`] 注册。
+pub use crate::recording::RecordedFrameDirection as FrameDirection;
+
 /// 帧回调 Trait
 ///
 /// 定义 CAN 帧回调接口，用于在接收或发送 CAN 帧时执行自定义逻辑。
@@ -82,8 +90,113 @@ pub trait FrameCallback: Send + Sync {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct HookHandle(u64);
 
+/// 回调的触发条件：CAN ID 区间与帧方向
+///
+/// 默认（[`HookFilter::all`]）匹配所有 ID、两个方向。
+///
+/// # 示例
+///
+/// ```rust
+/// use piper_driver::hooks::{FrameDirection, HookFilter, HookManager};
+/// use piper_driver::recording::AsyncRecordingHook;
+/// use std::sync::Arc;
+///
+/// let mut hooks = HookManager::new();
+/// let (hook, _rx) = AsyncRecordingHook::new();
+/// // 只录制发出的 MIT 控制帧
+/// let filter = HookFilter::range(0x15A..=0x15F).direction(FrameDirection::Tx);
+/// hooks.add_filtered(filter, Arc::new(hook));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HookFilter {
+    /// 匹配的原始 CAN ID 闭区间 `[first_id, last_id]`（`first_id > last_id` 表示不匹配任何帧）
+    first_id: u32,
+    last_id: u32,
+    rx: bool,
+    tx: bool,
+}
+
+impl Default for HookFilter {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl HookFilter {
+    /// 匹配所有帧（两个方向）
+    #[must_use]
+    pub const fn all() -> Self {
+        Self {
+            first_id: 0,
+            last_id: u32::MAX,
+            rx: true,
+            tx: true,
+        }
+    }
+
+    /// 只匹配指定原始 CAN ID（[`PiperFrame::raw_id`]）
+    #[must_use]
+    pub const fn id(id: u32) -> Self {
+        Self {
+            first_id: id,
+            last_id: id,
+            ..Self::all()
+        }
+    }
+
+    /// 只匹配 ID 落在 `range` 内的帧
+    ///
+    /// 支持任意整数区间写法（`0x251..=0x256`、`0x2A5..0x2A8`、`0x400..` 等）；
+    /// 空区间不匹配任何帧。
+    #[must_use]
+    pub fn range(range: impl RangeBounds<u32>) -> Self {
+        let first_id = match range.start_bound() {
+            Bound::Included(&id) => Some(id),
+            Bound::Excluded(&id) => id.checked_add(1),
+            Bound::Unbounded => Some(0),
+        };
+        let last_id = match range.end_bound() {
+            Bound::Included(&id) => Some(id),
+            Bound::Excluded(&id) => id.checked_sub(1),
+            Bound::Unbounded => Some(u32::MAX),
+        };
+        match (first_id, last_id) {
+            (Some(first_id), Some(last_id)) => Self {
+                first_id,
+                last_id,
+                ..Self::all()
+            },
+            _ => Self {
+                first_id: 1,
+                last_id: 0,
+                ..Self::all()
+            },
+        }
+    }
+
+    /// 只匹配一个方向的帧
+    #[must_use]
+    pub const fn direction(self, direction: FrameDirection) -> Self {
+        Self {
+            rx: matches!(direction, FrameDirection::Rx),
+            tx: matches!(direction, FrameDirection::Tx),
+            ..self
+        }
+    }
+
+    #[inline]
+    fn matches(&self, raw_id: u32, direction: FrameDirection) -> bool {
+        let direction_enabled = match direction {
+            FrameDirection::Rx => self.rx,
+            FrameDirection::Tx => self.tx,
+        };
+        direction_enabled && self.first_id <= raw_id && raw_id <= self.last_id
+    }
+}
+
 struct HookEntry {
     handle: HookHandle,
+    filter: HookFilter,
     callback: Arc<dyn FrameCallback>,
 }
 
@@ -169,12 +282,80 @@ impl HookManager {
     /// hooks.add_callback(callback);
     /// ```
     pub fn add_callback(&mut self, callback: Arc<dyn FrameCallback>) -> HookHandle {
+        self.add_filtered(HookFilter::all(), callback)
+    }
+
+    /// 添加只在 `filter` 匹配时触发的回调
+    ///
+    /// 分发时只做整数与布尔比较，不分配内存。
+    pub fn add_filtered(
+        &mut self,
+        filter: HookFilter,
+        callback: Arc<dyn FrameCallback>,
+    ) -> HookHandle {
         let handle = HookHandle(self.next_handle);
         self.next_handle = self.next_handle.wrapping_add(1).max(1);
-        self.callbacks.push(HookEntry { handle, callback });
+        self.callbacks.push(HookEntry {
+            handle,
+            filter,
+            callback,
+        });
         handle
     }
 
+    /// 添加只对一个方向（RX 或 TX）触发的回调
+    pub fn on_direction(
+        &mut self,
+        direction: FrameDirection,
+        callback: Arc<dyn FrameCallback>,
+    ) -> HookHandle {
+        self.add_filtered(HookFilter::all().direction(direction), callback)
+    }
+
+    /// 添加只对指定 CAN ID 触发的回调（两个方向）
+    ///
+    /// 按原始 ID（[`PiperFrame::raw_id`]）匹配，见 [`HookFilter::id`]。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use piper_driver::hooks::HookManager;
+    /// use piper_driver::recording::AsyncRecordingHook;
+    /// use std::sync::Arc;
+    ///
+    /// let mut hooks = HookManager::new();
+    /// let (hook, _rx) = AsyncRecordingHook::new();
+    /// // 只观察机械臂状态帧
+    /// hooks.on_frame_id(0x2A1, Arc::new(hook));
+    /// ```
+    pub fn on_frame_id(&mut self, id: u32, callback: Arc<dyn FrameCallback>) -> HookHandle {
+        self.add_filtered(HookFilter::id(id), callback)
+    }
+
+    /// 添加只对 ID 落在 `range` 内的帧触发的回调（两个方向）
+    ///
+    /// 区间语义见 [`HookFilter::range`]。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use piper_driver::hooks::HookManager;
+    /// use piper_driver::recording::AsyncRecordingHook;
+    /// use std::sync::Arc;
+    ///
+    /// let mut hooks = HookManager::new();
+    /// let (hook, _rx) = AsyncRecordingHook::new();
+    /// // 只观察 6 个关节驱动器的高速反馈帧
+    /// hooks.on_frame_range(0x251..=0x256, Arc::new(hook));
+    /// ```
+    pub fn on_frame_range(
+        &mut self,
+        range: impl RangeBounds<u32>,
+        callback: Arc<dyn FrameCallback>,
+    ) -> HookHandle {
+        self.add_filtered(HookFilter::range(range), callback)
+    }
+
     /// 移除指定回调。
     pub fn remove_callback(&mut self, handle: HookHandle) -> bool {
         if let Some(index) = self.callbacks.iter().position(|entry| entry.handle == handle) {
@@ -230,7 +411,12 @@ impl HookManager {
             direction: RecordedFrameDirection::Rx,
             timestamp_provenance: received.timestamp_provenance,
         };
-        for entry in self.callbacks.iter() {
+        let raw_id = event.frame.raw_id();
+        for entry in self
+            .callbacks
+            .iter()
+            .filter(|entry| entry.filter.matches(raw_id, event.direction))
+        {
             entry.callback.on_frame(event);
             // ^^^^ 使用 try_send，<1μs，非阻塞
         }
@@ -265,7 +451,12 @@ impl HookManager {
             direction: RecordedFrameDirection::Tx,
             timestamp_provenance: TimestampProvenance::Userspace,
         };
-        for entry in self.callbacks.iter() {
+        let raw_id = frame.raw_id();
+        for entry in self
+            .callbacks
+            .iter()
+            .filter(|entry| entry.filter.matches(raw_id, event.direction))
+        {
             entry.callback.on_frame(event);
         }
     }
@@ -375,6 +566,87 @@ mod tests {
         assert_eq!(event.timestamp_provenance, TimestampProvenance::Userspace);
     }
 
+    #[test]
+    fn test_hook_manager_routes_callbacks_by_frame_id_and_range() {
+        let mut hooks = HookManager::new();
+        let counter = || {
+            let (tx, _rx) = bounded(16);
+            let count = Arc::new(AtomicU64::new(0));
+            (
+                Arc::new(TestCallback {
+                    tx,
+                    count: count.clone(),
+                }),
+                count,
+            )
+        };
+        let (status, status_count) = counter();
+        let (drivers, drivers_count) = counter();
+        let (all, all_count) = counter();
+        let (empty, empty_count) = counter();
+
+        hooks.on_frame_id(0x2A1, status);
+        hooks.on_frame_range(0x251..=0x256, drivers);
+        hooks.add_callback(all);
+        hooks.on_frame_range(0x100..0x100, empty);
+
+        for raw_id in [0x2A1, 0x251, 0x256, 0x257, 0x100] {
+            let frame = PiperFrame::new_standard(raw_id, [0]).unwrap();
+            hooks.trigger_all(ReceivedFrame::new(frame, TimestampProvenance::None));
+        }
+        hooks.trigger_all_sent(&PiperFrame::new_standard(0x2A1, [0]).unwrap());
+
+        assert_eq!(status_count.load(Ordering::Relaxed), 2);
+        assert_eq!(drivers_count.load(Ordering::Relaxed), 2);
+        assert_eq!(all_count.load(Ordering::Relaxed), 6);
+        assert_eq!(empty_count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_hook_manager_routes_callbacks_by_direction() {
+        let mut hooks = HookManager::new();
+        let (tx, rx) = bounded::<RecordedFrameEvent>(16);
+        let count = Arc::new(AtomicU64::new(0));
+        let both = Arc::new(TestCallback {
+            tx,
+            count: count.clone(),
+        });
+        let (tx_only_tx, _tx_only_rx) = bounded(16);
+        let tx_only_count = Arc::new(AtomicU64::new(0));
+        let tx_only = Arc::new(TestCallback {
+            tx: tx_only_tx,
+            count: tx_only_count.clone(),
+        });
+        let (rx_mit_tx, _rx_mit_rx) = bounded(16);
+        let rx_mit_count = Arc::new(AtomicU64::new(0));
+        let rx_mit = Arc::new(TestCallback {
+            tx: rx_mit_tx,
+            count: rx_mit_count.clone(),
+        });
+
+        hooks.add_callback(both);
+        hooks.on_direction(FrameDirection::Tx, tx_only);
+        hooks.add_filtered(
+            HookFilter::range(0x15A..=0x15F).direction(FrameDirection::Rx),
+            rx_mit,
+        );
+
+        let command = PiperFrame::new_standard(0x15A, [1]).unwrap();
+        let feedback = PiperFrame::new_standard(0x2A5, [2]).unwrap();
+        hooks.trigger_all_sent(&command);
+        hooks.trigger_all(ReceivedFrame::new(feedback, TimestampProvenance::None));
+        hooks.trigger_all(ReceivedFrame::new(command, TimestampProvenance::None));
+
+        let merged: Vec<_> = rx.try_iter().map(|event| event.direction).collect();
+        assert_eq!(
+            merged,
+            vec![FrameDirection::Tx, FrameDirection::Rx, FrameDirection::Rx]
+        );
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+        assert_eq!(tx_only_count.load(Ordering::Relaxed), 1);
+        assert_eq!(rx_mit_count.load(Ordering::Relaxed), 1);
+    }
+
     #[test]
     fn test_hook_manager_clear() {
         let mut hooks = HookManager::new();
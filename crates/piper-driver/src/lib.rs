@@ -16,6 +16,7 @@ mod builder;
 pub mod command;
 pub mod diagnostics;
 mod error;
+pub mod flight_recorder;
 mod fps_stats;
 pub mod heartbeat;
 pub mod hooks;
@@ -31,28 +32,36 @@ pub mod recording;
 pub mod state;
 #[cfg(test)]
 mod test_support;
+pub mod watchdog;
 
 pub use builder::{ConnectionTarget, PiperBuilder};
 pub use command::{CommandPriority, PiperCommand};
 pub use diagnostics::{DiagnosticBuffer, DiagnosticEvent, QueryDiagnostic};
 pub use error::{DriverError, WaitError}; // 原 DriverError
+pub use flight_recorder::{DEFAULT_FLIGHT_RECORDER_CAPACITY, FatalFrameDump, FlightRecorder};
 pub use fps_stats::{FpsCounts, FpsResult};
-pub use heartbeat::ConnectionMonitor;
-pub use hooks::{FrameCallback, HookHandle, HookManager};
-pub use metrics::{FamilyObservationMetrics, MetricsSnapshot, ObservationMetrics, PiperMetrics};
+pub use heartbeat::{ConnectionMonitor, LinkHealth};
+pub use hooks::{FrameCallback, FrameDirection, HookFilter, HookHandle, HookManager};
+pub use metrics::{
+    CommandQueueSnapshot, CommandQueueStats, FamilyObservationMetrics, LatencyPercentiles,
+    MetricsSnapshot, ObservationMetrics, PiperMetrics, PrometheusLabels,
+};
 pub use mode::{AtomicDriverMode, DriverMode};
-pub use pipeline::{PipelineConfig, rx_loop};
+pub use pipeline::{FeedbackReplayer, FrameCommitPolicy, PipelineConfig, rx_loop};
 pub use piper::{
-    HealthStatus, MaintenanceGate, MaintenanceGateState, MaintenanceLeaseAcquireResult,
-    MaintenanceLeaseGate, MaintenanceLeaseSnapshot, MaintenanceRevocationEvent,
-    MaintenanceRevocationReason, MaintenanceStateSignal, ManualFaultRecoveryResult,
-    MitBatchTxFinished, NormalSendGate, Piper, RuntimeFaultKind, ShutdownLane, ShutdownReceipt,
+    DROP_SAFE_STOP_TIMEOUT, DropStopAction, HealthStatus, MaintenanceGate, MaintenanceGateState,
+    MaintenanceLeaseAcquireResult, MaintenanceLeaseGate, MaintenanceLeaseSnapshot,
+    MaintenanceRevocationEvent, MaintenanceRevocationReason, MaintenanceStateSignal,
+    ManualFaultRecoveryResult, MitBatchTxFinished, NormalSendGate, Piper, RuntimeFaultKind,
+    ShutdownLane, ShutdownReceipt,
 };
 pub use piper_can::BackendCapability;
 pub use piper_protocol::ProtocolDiagnostic;
+pub use piper_protocol::ids::NodeId;
 pub use query_coordinator::{ActiveQuery, QueryCoordinator, QueryError, QueryGuard, QueryKind};
 pub use recording::{
     AsyncRecordingHook, RecordedFrameDirection, RecordedFrameEvent, TimestampProvenance,
     TimestampedFrame,
 };
 pub use state::*;
+pub use watchdog::{Watchdog, WatchdogConfig};
@@ -2,12 +2,38 @@
 //!
 //! 提供零开销的原子计数器，用于监控 IO 链路的健康状态和性能。
 //! 所有计数器都使用原子操作，可以在任何线程安全地读取，不会引入锁竞争。
+//! 快照可通过 [`MetricsSnapshot::to_prometheus`] 导出为 Prometheus 文本格式。
 
-use std::sync::atomic::{AtomicU8, AtomicU64, Ordering};
+mod prometheus;
+
+pub use prometheus::PrometheusLabels;
+
+use crate::command::CommandPriority;
+use piper_can::{PiperFrame, TxEchoStats};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
 use std::time::{Duration, Instant};
 
 const LOW_SPEED_CYCLE_FULL_MASK: u8 = 0b11_1111;
 
+/// 标准帧 ID 空间大小（11 位）
+const STANDARD_ID_SPACE: usize = 0x800;
+
+/// 单 CAN ID 帧率的滑动窗口长度
+pub const PER_ID_FPS_WINDOW: Duration = Duration::from_secs(1);
+
+/// 后台采样线程的采样周期
+const PER_ID_FPS_SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 直方图每个 2 的幂区间的线性子桶数（相对误差 ≤ 1/16）
+const HISTOGRAM_SUB_BUCKETS: u64 = 16;
+const HISTOGRAM_SUB_BUCKET_BITS: u32 = 4;
+/// 直方图可区分的最大值的最高位（约 19 小时，更大的值计入最后一个桶）
+const HISTOGRAM_MAX_MSB: u32 = 35;
+const HISTOGRAM_BUCKETS: usize =
+    ((HISTOGRAM_MAX_MSB - HISTOGRAM_SUB_BUCKET_BITS + 2) as u64 * HISTOGRAM_SUB_BUCKETS) as usize;
+
 /// 重建观察族指标的单族快照。
 ///
 /// 这些指标明确区分：
@@ -277,6 +303,11 @@ pub struct PiperMetrics {
     /// 或者总线/设备存在瓶颈。
     pub tx_realtime_overwrites_total: AtomicU64,
 
+    /// 合并邮箱（[`Piper::send_coalesced`](crate::Piper::send_coalesced)）丢弃的过期命令数
+    ///
+    /// 包括被同一 (CAN ID, 优先级) 新命令覆盖的命令，以及故障/回放/停止时清空的命令。
+    pub tx_coalesced_dropped_total: AtomicU64,
+
     /// TX 普通可靠命令成功进入 FIFO 的总次数
     pub tx_reliable_enqueued_total: AtomicU64,
 
@@ -302,6 +333,10 @@ pub struct PiperMetrics {
     pub tx_drop_shutdown_timeout_total: AtomicU64,
     /// fault-latched Drop 路径因 TX 不可用或 runtime 已停止而跳过的次数
     pub tx_drop_shutdown_skipped_total: AtomicU64,
+    /// 软件看门狗到期触发停机的次数
+    pub tx_watchdog_trips_total: AtomicU64,
+    /// 命令保活重发的帧包数
+    pub tx_keepalive_resent_total: AtomicU64,
 
     /// 因故障锁存或停止阶段而被主动中止的普通控制命令总次数
     pub tx_fault_aborts_total: AtomicU64,
@@ -325,6 +360,10 @@ pub struct PiperMetrics {
     pub tx_packages_transport_failed_total: AtomicU64,
     /// 关节位置完整组因缺帧/超时而被丢弃的次数
     pub rx_joint_position_incomplete_groups_dropped_total: AtomicU64,
+    /// 关节位置不完整帧组按 `FrameCommitPolicy` 提交的次数
+    pub rx_joint_position_partial_groups_committed_total: AtomicU64,
+    /// 不完整帧组（关节位置/末端位姿/关节动态）中缺失的反馈帧累计数
+    pub rx_feedback_frames_missed_total: AtomicU64,
     /// 关节位置完整组不满足控制级跨度约束而被拒绝的次数
     pub rx_joint_position_control_grade_rejected_total: AtomicU64,
     /// 末端位姿完整组因缺帧/超时而被丢弃的次数
@@ -345,6 +384,18 @@ pub struct PiperMetrics {
     pub tx_soft_deadline_miss_total: AtomicU64,
     /// SoftRealtime 连续 deadline miss 续增总次数
     pub tx_soft_consecutive_deadline_miss_total: AtomicU64,
+
+    /// 按 CAN ID 统计的 RX 有效帧计数与滑动窗口帧率
+    pub(crate) per_id: PerIdFrameRates,
+
+    /// 帧间隔与命令→反馈延迟直方图
+    pub(crate) timing: FrameTimingHistograms,
+
+    /// 按优先级的命令队列深度与合并邮箱入队/丢弃计数
+    pub(crate) command_queue: CommandQueueMetrics,
+
+    /// 适配器 TX Echo 统计的镜像（RX 线程周期性拷贝）
+    pub(crate) tx_echo: TxEchoMetrics,
 }
 
 impl PiperMetrics {
@@ -372,6 +423,7 @@ impl PiperMetrics {
             tx_frames_sent_total: self.tx_frames_sent_total.load(Ordering::Relaxed),
             tx_realtime_enqueued_total: self.tx_realtime_enqueued_total.load(Ordering::Relaxed),
             tx_realtime_overwrites_total: self.tx_realtime_overwrites_total.load(Ordering::Relaxed),
+            tx_coalesced_dropped_total: self.tx_coalesced_dropped_total.load(Ordering::Relaxed),
             tx_reliable_enqueued_total: self.tx_reliable_enqueued_total.load(Ordering::Relaxed),
             tx_reliable_queue_full_total: self.tx_reliable_queue_full_total.load(Ordering::Relaxed),
             tx_shutdown_requests_total: self.tx_shutdown_requests_total.load(Ordering::Relaxed),
@@ -390,6 +442,8 @@ impl PiperMetrics {
             tx_drop_shutdown_skipped_total: self
                 .tx_drop_shutdown_skipped_total
                 .load(Ordering::Relaxed),
+            tx_watchdog_trips_total: self.tx_watchdog_trips_total.load(Ordering::Relaxed),
+            tx_keepalive_resent_total: self.tx_keepalive_resent_total.load(Ordering::Relaxed),
             tx_fault_aborts_total: self.tx_fault_aborts_total.load(Ordering::Relaxed),
             device_errors: self.device_errors.load(Ordering::Relaxed),
             rx_timeouts: self.rx_timeouts.load(Ordering::Relaxed),
@@ -405,6 +459,12 @@ impl PiperMetrics {
             rx_joint_position_incomplete_groups_dropped_total: self
                 .rx_joint_position_incomplete_groups_dropped_total
                 .load(Ordering::Relaxed),
+            rx_joint_position_partial_groups_committed_total: self
+                .rx_joint_position_partial_groups_committed_total
+                .load(Ordering::Relaxed),
+            rx_feedback_frames_missed_total: self
+                .rx_feedback_frames_missed_total
+                .load(Ordering::Relaxed),
             rx_joint_position_control_grade_rejected_total: self
                 .rx_joint_position_control_grade_rejected_total
                 .load(Ordering::Relaxed),
@@ -430,6 +490,37 @@ impl PiperMetrics {
             tx_soft_consecutive_deadline_miss_total: self
                 .tx_soft_consecutive_deadline_miss_total
                 .load(Ordering::Relaxed),
+            rx_interval_us: self.timing.rx_interval.percentiles(),
+            command_feedback_latency_us: self.timing.command_feedback_latency.percentiles(),
+            command_queue: self.command_queue_snapshot(),
+            tx_echo: self.tx_echo.snapshot(),
+        }
+    }
+
+    fn command_queue_snapshot(&self) -> CommandQueueSnapshot {
+        let queue = &self.command_queue;
+        let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        CommandQueueSnapshot {
+            shutdown: CommandQueueStats {
+                depth: load(&queue.shutdown_depth),
+                enqueued_total: load(&self.tx_shutdown_requests_total),
+                dropped_total: load(&self.tx_shutdown_conflicts_total),
+            },
+            realtime: CommandQueueStats {
+                depth: load(&queue.realtime_depth),
+                enqueued_total: load(&self.tx_realtime_enqueued_total)
+                    + load(&queue.coalesced_enqueued[0]),
+                dropped_total: load(&self.tx_realtime_overwrites_total)
+                    + load(&queue.coalesced_dropped[0]),
+            },
+            reliable: CommandQueueStats {
+                depth: load(&queue.reliable_depth),
+                enqueued_total: load(&self.tx_reliable_enqueued_total)
+                    + load(&queue.coalesced_enqueued[1]),
+                dropped_total: load(&self.tx_reliable_queue_full_total)
+                    + load(&queue.coalesced_dropped[1]),
+            },
+            shutdown_behind_backlog_total: load(&queue.shutdown_behind_backlog_total),
         }
     }
 
@@ -446,6 +537,7 @@ impl PiperMetrics {
         self.tx_frames_sent_total.store(0, Ordering::Relaxed);
         self.tx_realtime_enqueued_total.store(0, Ordering::Relaxed);
         self.tx_realtime_overwrites_total.store(0, Ordering::Relaxed);
+        self.tx_coalesced_dropped_total.store(0, Ordering::Relaxed);
         self.tx_reliable_enqueued_total.store(0, Ordering::Relaxed);
         self.tx_reliable_queue_full_total.store(0, Ordering::Relaxed);
         self.tx_shutdown_requests_total.store(0, Ordering::Relaxed);
@@ -456,6 +548,8 @@ impl PiperMetrics {
         self.tx_drop_shutdown_success_total.store(0, Ordering::Relaxed);
         self.tx_drop_shutdown_timeout_total.store(0, Ordering::Relaxed);
         self.tx_drop_shutdown_skipped_total.store(0, Ordering::Relaxed);
+        self.tx_watchdog_trips_total.store(0, Ordering::Relaxed);
+        self.tx_keepalive_resent_total.store(0, Ordering::Relaxed);
         self.tx_fault_aborts_total.store(0, Ordering::Relaxed);
         self.device_errors.store(0, Ordering::Relaxed);
         self.rx_timeouts.store(0, Ordering::Relaxed);
@@ -466,6 +560,9 @@ impl PiperMetrics {
         self.tx_packages_transport_failed_total.store(0, Ordering::Relaxed);
         self.rx_joint_position_incomplete_groups_dropped_total
             .store(0, Ordering::Relaxed);
+        self.rx_joint_position_partial_groups_committed_total
+            .store(0, Ordering::Relaxed);
+        self.rx_feedback_frames_missed_total.store(0, Ordering::Relaxed);
         self.rx_joint_position_control_grade_rejected_total.store(0, Ordering::Relaxed);
         self.rx_end_pose_incomplete_groups_dropped_total.store(0, Ordering::Relaxed);
         self.rx_joint_dynamic_groups_dropped_total.store(0, Ordering::Relaxed);
@@ -475,6 +572,443 @@ impl PiperMetrics {
         self.tx_soft_admission_timeout_total.store(0, Ordering::Relaxed);
         self.tx_soft_deadline_miss_total.store(0, Ordering::Relaxed);
         self.tx_soft_consecutive_deadline_miss_total.store(0, Ordering::Relaxed);
+        self.per_id.reset();
+        self.timing.reset();
+        self.command_queue.reset();
+        self.tx_echo.reset();
+    }
+
+    /// 各 CAN ID 的 RX 帧率（Hz），基于最近 [`PER_ID_FPS_WINDOW`] 的滑动窗口
+    ///
+    /// 包含自启动（或上次 `reset()`）以来出现过的所有标准帧 ID；某个 ID 停发后
+    /// 其帧率会在一个窗口内降到 0，而不是从结果中消失。
+    /// 用于定位单个关节驱动器的问题，例如 `0x2A1` 从 200Hz 掉到 50Hz 而关节反馈正常。
+    ///
+    /// 帧率由后台采样线程每 100ms 更新一次，本方法只读取最近一次的结果。
+    pub fn per_id_fps(&self) -> HashMap<u32, f32> {
+        self.per_id.rates()
+    }
+}
+
+/// 按 CAN ID 的帧计数器
+///
+/// 热路径（RX 线程）每帧只做一次原子自增；帧率由后台线程周期性采样累计值计算，
+/// 读取方不会与 RX 线程竞争锁。扩展帧 ID 不参与统计（Piper 协议仅使用标准帧）。
+#[derive(Debug)]
+pub(crate) struct PerIdFrameRates {
+    counts: Box<[AtomicU64]>,
+    rates: Mutex<HashMap<u32, f32>>,
+}
+
+impl Default for PerIdFrameRates {
+    fn default() -> Self {
+        Self {
+            counts: (0..STANDARD_ID_SPACE).map(|_| AtomicU64::new(0)).collect(),
+            rates: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl PerIdFrameRates {
+    /// 记录一帧（RX 热路径，无锁）
+    #[inline]
+    pub(crate) fn record(&self, frame: &PiperFrame) {
+        if let Some(id) = frame.id().as_standard() {
+            self.counts[id.raw() as usize].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn rates(&self) -> HashMap<u32, f32> {
+        match self.rates.lock() {
+            Ok(rates) => rates.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    fn reset(&self) {
+        for count in self.counts.iter() {
+            count.store(0, Ordering::Relaxed);
+        }
+        match self.rates.lock() {
+            Ok(mut rates) => rates.clear(),
+            Err(poisoned) => poisoned.into_inner().clear(),
+        }
+    }
+
+    /// 采样当前累计值并更新滑动窗口帧率
+    ///
+    /// `history` 由采样线程持有；窗口起点取不晚于 `now - PER_ID_FPS_WINDOW` 的最新样本。
+    fn sample(&self, history: &mut VecDeque<(Instant, HashMap<u32, u64>)>, now: Instant) {
+        let current: HashMap<u32, u64> = self
+            .counts
+            .iter()
+            .enumerate()
+            .filter_map(|(id, count)| {
+                let count = count.load(Ordering::Relaxed);
+                (count > 0).then_some((id as u32, count))
+            })
+            .collect();
+
+        while history
+            .get(1)
+            .is_some_and(|(at, _)| now.saturating_duration_since(*at) >= PER_ID_FPS_WINDOW)
+        {
+            history.pop_front();
+        }
+
+        let rates = match history.front() {
+            Some((start, start_counts)) if now > *start => {
+                let elapsed = now.duration_since(*start).as_secs_f32();
+                current
+                    .iter()
+                    .map(|(id, count)| {
+                        let start = start_counts.get(id).copied().unwrap_or(0);
+                        (*id, count.saturating_sub(start) as f32 / elapsed)
+                    })
+                    .collect()
+            },
+            _ => HashMap::new(),
+        };
+
+        history.push_back((now, current));
+        match self.rates.lock() {
+            Ok(mut guard) => *guard = rates,
+            Err(poisoned) => *poisoned.into_inner() = rates,
+        }
+    }
+}
+
+/// 无锁 HDR 风格直方图（单位：微秒）
+///
+/// 对数-线性分桶：小于 16 的值精确记录，之后每个 2 的幂区间均分为 16 个子桶，
+/// 相对误差不超过 1/16。记录只做一次原子自增，可在 RX/TX 热路径上使用。
+#[derive(Debug)]
+pub(crate) struct LatencyHistogram {
+    buckets: Box<[AtomicU64]>,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self {
+            buckets: (0..HISTOGRAM_BUCKETS).map(|_| AtomicU64::new(0)).collect(),
+        }
+    }
+}
+
+impl LatencyHistogram {
+    #[inline]
+    pub(crate) fn record(&self, value_us: u64) {
+        self.buckets[histogram_bucket(value_us)].fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn reset(&self) {
+        for bucket in self.buckets.iter() {
+            bucket.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// 计算分位数；各分位数取所在桶的上界（保守估计，适合做上限断言）
+    pub(crate) fn percentiles(&self) -> LatencyPercentiles {
+        let counts: Vec<u64> =
+            self.buckets.iter().map(|bucket| bucket.load(Ordering::Relaxed)).collect();
+        let count: u64 = counts.iter().sum();
+        if count == 0 {
+            return LatencyPercentiles::default();
+        }
+
+        let rank = |quantile: f64| ((quantile * count as f64).ceil() as u64).clamp(1, count);
+        let value_at = |rank: u64| {
+            let mut cumulative = 0;
+            for (index, bucket_count) in counts.iter().enumerate() {
+                cumulative += bucket_count;
+                if cumulative >= rank {
+                    return histogram_bucket_upper_bound(index);
+                }
+            }
+            histogram_bucket_upper_bound(HISTOGRAM_BUCKETS - 1)
+        };
+
+        LatencyPercentiles {
+            count,
+            p50_us: value_at(rank(0.50)),
+            p99_us: value_at(rank(0.99)),
+            p999_us: value_at(rank(0.999)),
+            max_us: value_at(count),
+        }
+    }
+}
+
+fn histogram_bucket(value: u64) -> usize {
+    if value < HISTOGRAM_SUB_BUCKETS {
+        return value as usize;
+    }
+    let msb = (63 - value.leading_zeros()).min(HISTOGRAM_MAX_MSB);
+    let value = value.min((1u64 << (HISTOGRAM_MAX_MSB + 1)) - 1);
+    let shift = msb - HISTOGRAM_SUB_BUCKET_BITS;
+    let sub_bucket = (value >> shift) & (HISTOGRAM_SUB_BUCKETS - 1);
+    ((msb - HISTOGRAM_SUB_BUCKET_BITS + 1) as u64 * HISTOGRAM_SUB_BUCKETS + sub_bucket) as usize
+}
+
+fn histogram_bucket_upper_bound(index: usize) -> u64 {
+    let index = index as u64;
+    if index < HISTOGRAM_SUB_BUCKETS {
+        return index;
+    }
+    let shift = (index / HISTOGRAM_SUB_BUCKETS - 1) as u32;
+    let sub_bucket = index % HISTOGRAM_SUB_BUCKETS;
+    ((HISTOGRAM_SUB_BUCKETS + sub_bucket + 1) << shift) - 1
+}
+
+/// 直方图分位数（单位：微秒）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LatencyPercentiles {
+    /// 样本数
+    pub count: u64,
+    /// 中位数
+    pub p50_us: u64,
+    /// 99 分位
+    pub p99_us: u64,
+    /// 99.9 分位
+    pub p999_us: u64,
+    /// 最大值
+    pub max_us: u64,
+}
+
+/// 帧时序直方图
+///
+/// - 帧间隔：同一 CAN ID 相邻两帧的间隔。优先使用帧的硬件时间戳 `timestamp_us`，
+///   无硬件时间戳（为 0）时回退到主机单调时钟。时间戳回绕（后帧早于前帧）的样本被跳过。
+/// - 命令→反馈延迟：控制帧成功发送到下一帧机器人反馈到达之间的主机单调时间。
+///   Piper 协议没有逐命令应答，因此以下一帧反馈作为应答；多条命令未被应答时，
+///   从最早的一条开始计时。
+#[derive(Debug)]
+pub(crate) struct FrameTimingHistograms {
+    last_rx_us: Box<[AtomicU64]>,
+    /// 最早一条尚未等到反馈的控制帧发送时间（0 表示无）
+    pending_command_mono_us: AtomicU64,
+    rx_interval: LatencyHistogram,
+    command_feedback_latency: LatencyHistogram,
+}
+
+impl Default for FrameTimingHistograms {
+    fn default() -> Self {
+        Self {
+            last_rx_us: (0..STANDARD_ID_SPACE).map(|_| AtomicU64::new(0)).collect(),
+            pending_command_mono_us: AtomicU64::new(0),
+            rx_interval: LatencyHistogram::default(),
+            command_feedback_latency: LatencyHistogram::default(),
+        }
+    }
+}
+
+impl FrameTimingHistograms {
+    /// 记录一帧 RX 的到达时间（RX 线程）
+    ///
+    /// `host_mono_us` 为主机单调时钟（微秒，非 0），仅在帧没有硬件时间戳时使用。
+    #[inline]
+    pub(crate) fn record_rx(&self, frame: &PiperFrame, host_mono_us: u64) {
+        let Some(id) = frame.id().as_standard() else {
+            return;
+        };
+        let now_us = match frame.timestamp_us() {
+            0 => host_mono_us,
+            hardware_us => hardware_us,
+        };
+        let last_us = self.last_rx_us[id.raw() as usize].swap(now_us, Ordering::Relaxed);
+        if last_us != 0 && now_us >= last_us {
+            self.rx_interval.record(now_us - last_us);
+        }
+    }
+
+    /// 记录控制帧已发送（TX 线程）
+    #[inline]
+    pub(crate) fn record_command_sent(&self, host_mono_us: u64) {
+        let _ = self.pending_command_mono_us.compare_exchange(
+            0,
+            host_mono_us,
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+        );
+    }
+
+    /// 记录机器人反馈到达（RX 线程），结束当前命令的计时
+    #[inline]
+    pub(crate) fn record_feedback(&self, host_mono_us: u64) {
+        let sent_us = self.pending_command_mono_us.swap(0, Ordering::Relaxed);
+        if sent_us != 0 {
+            self.command_feedback_latency.record(host_mono_us.saturating_sub(sent_us));
+        }
+    }
+
+    fn reset(&self) {
+        for last in self.last_rx_us.iter() {
+            last.store(0, Ordering::Relaxed);
+        }
+        self.pending_command_mono_us.store(0, Ordering::Relaxed);
+        self.rx_interval.reset();
+        self.command_feedback_latency.reset();
+    }
+}
+
+/// 启动按 CAN ID 帧率的后台采样线程
+///
+/// 线程只持有 `Weak` 引用，`PiperMetrics` 释放后在下一个采样周期自行退出，无需 join。
+pub(crate) fn spawn_per_id_fps_sampler(metrics: &Arc<PiperMetrics>) {
+    let metrics: Weak<PiperMetrics> = Arc::downgrade(metrics);
+    let spawned = std::thread::Builder::new().name("piper-metrics".to_string()).spawn(move || {
+        let mut history = VecDeque::new();
+        loop {
+            std::thread::sleep(PER_ID_FPS_SAMPLE_INTERVAL);
+            let Some(metrics) = metrics.upgrade() else {
+                break;
+            };
+            metrics.per_id.sample(&mut history, Instant::now());
+        }
+    });
+    if let Err(error) = spawned {
+        tracing::warn!("failed to spawn per-CAN-ID FPS sampler: {error}");
+    }
+}
+
+/// 命令队列深度与合并邮箱计数（TX 线程维护）
+///
+/// 深度是 TX 线程每次调度前采样的瞬时值（仪表盘语义），不是累计值。
+/// 合并邮箱（[`Piper::send_coalesced`](crate::Piper::send_coalesced)）的计数按
+/// `[RealtimeControl, ReliableCommand]` 存放，其余入队/丢弃计数复用已有的通道计数器。
+#[derive(Debug, Default)]
+pub(crate) struct CommandQueueMetrics {
+    shutdown_depth: AtomicU64,
+    realtime_depth: AtomicU64,
+    reliable_depth: AtomicU64,
+    coalesced_enqueued: [AtomicU64; 2],
+    coalesced_dropped: [AtomicU64; 2],
+    shutdown_behind_backlog_total: AtomicU64,
+}
+
+impl CommandQueueMetrics {
+    #[inline]
+    fn lane(priority: CommandPriority) -> usize {
+        match priority {
+            CommandPriority::RealtimeControl => 0,
+            CommandPriority::ReliableCommand => 1,
+        }
+    }
+
+    /// 更新各优先级的排队深度（TX 线程）
+    #[inline]
+    pub(crate) fn set_depths(&self, shutdown: usize, realtime: usize, reliable: usize) {
+        self.shutdown_depth.store(shutdown as u64, Ordering::Relaxed);
+        self.realtime_depth.store(realtime as u64, Ordering::Relaxed);
+        self.reliable_depth.store(reliable as u64, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn record_coalesced_enqueue(&self, priority: CommandPriority) {
+        self.coalesced_enqueued[Self::lane(priority)].fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn record_coalesced_drop(&self, priority: CommandPriority, count: usize) {
+        self.coalesced_dropped[Self::lane(priority)].fetch_add(count as u64, Ordering::Relaxed);
+    }
+
+    /// 急停帧出队时仍有普通控制命令排队（急停曾等待在积压之后）
+    #[inline]
+    pub(crate) fn record_shutdown_behind_backlog(&self) {
+        self.shutdown_behind_backlog_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn reset(&self) {
+        self.set_depths(0, 0, 0);
+        for counter in self.coalesced_enqueued.iter().chain(&self.coalesced_dropped) {
+            counter.store(0, Ordering::Relaxed);
+        }
+        self.shutdown_behind_backlog_total.store(0, Ordering::Relaxed);
+    }
+}
+
+/// 单个优先级队列的深度与计数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CommandQueueStats {
+    /// 当前排队的命令数（采样值）
+    pub depth: u64,
+    /// 累计入队次数
+    pub enqueued_total: u64,
+    /// 累计丢弃次数（被覆盖/合并、队列满或冲突被拒）
+    pub dropped_total: u64,
+}
+
+/// 按优先级的命令队列快照
+///
+/// - `shutdown`：急停通道（单飞），丢弃为冲突被拒的请求
+/// - `realtime`：实时插槽、SoftRealtime 邮箱与合并邮箱中的 `RealtimeControl` 命令
+/// - `reliable`：可靠 FIFO 与合并邮箱中的 `ReliableCommand` 命令
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CommandQueueSnapshot {
+    pub shutdown: CommandQueueStats,
+    pub realtime: CommandQueueStats,
+    pub reliable: CommandQueueStats,
+    /// 急停帧出队时仍有普通控制命令积压的次数
+    ///
+    /// 持续增长说明急停经常排在位置命令积压之后，需要调整合并邮箱或限速配置。
+    pub shutdown_behind_backlog_total: u64,
+}
+
+impl CommandQueueSnapshot {
+    /// 当前是否有急停命令正等待在普通控制命令积压之后
+    pub fn shutdown_waiting_behind_backlog(&self) -> bool {
+        self.shutdown.depth > 0 && self.realtime.depth + self.reliable.depth > 0
+    }
+}
+
+/// TX Echo 统计镜像（RX 线程维护）
+///
+/// 在途表属于适配器，split 后驱动只持有 RX / TX 两半，因此由 RX 线程经
+/// [`RxAdapter::tx_echo_stats`](piper_can::RxAdapter::tx_echo_stats) 周期性拷贝累计值。
+/// 后端不跟踪 TX Echo 时保持不可用，快照中为 `None`。`reset()` 只清空镜像，
+/// 下一次拷贝会恢复适配器的累计值。
+#[derive(Debug, Default)]
+pub(crate) struct TxEchoMetrics {
+    available: AtomicBool,
+    completed: AtomicU64,
+    lost: AtomicU64,
+    unmatched: AtomicU64,
+    in_flight: AtomicU64,
+    last_latency_us: AtomicU64,
+    max_latency_us: AtomicU64,
+    mean_latency_us: AtomicU64,
+}
+
+impl TxEchoMetrics {
+    /// 拷贝适配器的最新统计（RX 线程）
+    pub(crate) fn publish(&self, stats: TxEchoStats) {
+        self.completed.store(stats.completed, Ordering::Relaxed);
+        self.lost.store(stats.lost, Ordering::Relaxed);
+        self.unmatched.store(stats.unmatched, Ordering::Relaxed);
+        self.in_flight.store(stats.in_flight as u64, Ordering::Relaxed);
+        self.last_latency_us.store(stats.last_latency_us, Ordering::Relaxed);
+        self.max_latency_us.store(stats.max_latency_us, Ordering::Relaxed);
+        self.mean_latency_us.store(stats.mean_latency_us, Ordering::Relaxed);
+        self.available.store(true, Ordering::Release);
+    }
+
+    fn snapshot(&self) -> Option<TxEchoStats> {
+        if !self.available.load(Ordering::Acquire) {
+            return None;
+        }
+        Some(TxEchoStats {
+            completed: self.completed.load(Ordering::Relaxed),
+            lost: self.lost.load(Ordering::Relaxed),
+            unmatched: self.unmatched.load(Ordering::Relaxed),
+            in_flight: self.in_flight.load(Ordering::Relaxed) as usize,
+            last_latency_us: self.last_latency_us.load(Ordering::Relaxed),
+            max_latency_us: self.max_latency_us.load(Ordering::Relaxed),
+            mean_latency_us: self.mean_latency_us.load(Ordering::Relaxed),
+        })
+    }
+
+    fn reset(&self) {
+        self.available.store(false, Ordering::Relaxed);
     }
 }
 
@@ -501,6 +1035,8 @@ pub struct MetricsSnapshot {
     pub tx_realtime_enqueued_total: u64,
     /// TX 实时队列覆盖次数
     pub tx_realtime_overwrites_total: u64,
+    /// TX 合并邮箱丢弃的过期命令数
+    pub tx_coalesced_dropped_total: u64,
     /// TX 普通可靠命令入队总次数
     pub tx_reliable_enqueued_total: u64,
     /// TX 普通可靠队列满次数
@@ -521,6 +1057,10 @@ pub struct MetricsSnapshot {
     pub tx_drop_shutdown_timeout_total: u64,
     /// fault-latched Drop 路径因 TX 不可用或 runtime 已停止而跳过的次数
     pub tx_drop_shutdown_skipped_total: u64,
+    /// 软件看门狗到期触发停机的次数
+    pub tx_watchdog_trips_total: u64,
+    /// 命令保活重发的帧包数
+    pub tx_keepalive_resent_total: u64,
     /// 因故障锁存或停止阶段被主动中止的普通控制命令总次数
     pub tx_fault_aborts_total: u64,
     /// 设备错误次数
@@ -539,6 +1079,10 @@ pub struct MetricsSnapshot {
     pub tx_packages_transport_failed_total: u64,
     /// 关节位置完整组因缺帧/超时而被丢弃的次数
     pub rx_joint_position_incomplete_groups_dropped_total: u64,
+    /// 关节位置不完整帧组按 `FrameCommitPolicy` 提交的次数
+    pub rx_joint_position_partial_groups_committed_total: u64,
+    /// 不完整帧组（关节位置/末端位姿/关节动态）中缺失的反馈帧累计数
+    pub rx_feedback_frames_missed_total: u64,
     /// 关节位置完整组不满足控制级跨度约束而被拒绝的次数
     pub rx_joint_position_control_grade_rejected_total: u64,
     /// 末端位姿完整组因缺帧/超时而被丢弃的次数
@@ -559,6 +1103,16 @@ pub struct MetricsSnapshot {
     pub tx_soft_deadline_miss_total: u64,
     /// SoftRealtime 连续 deadline miss 续增总次数
     pub tx_soft_consecutive_deadline_miss_total: u64,
+    /// 同一 CAN ID 相邻 RX 帧的间隔分布（控制环抖动）
+    ///
+    /// 优先基于硬件时间戳，无硬件时间戳时基于主机单调时钟。
+    pub rx_interval_us: LatencyPercentiles,
+    /// 控制帧发送到下一帧机器人反馈到达的延迟分布（主机单调时钟）
+    pub command_feedback_latency_us: LatencyPercentiles,
+    /// 按优先级的命令队列深度与入队/丢弃计数
+    pub command_queue: CommandQueueSnapshot,
+    /// TX→Echo 延迟与丢失统计（仅 GS-USB 等跟踪 TX Echo 的后端提供）
+    pub tx_echo: Option<TxEchoStats>,
 }
 
 impl MetricsSnapshot {
@@ -740,6 +1294,8 @@ mod tests {
             tx_shutdown_conflicts_total: 0,
             tx_shutdown_sent_total: 0,
             tx_fault_aborts_total: 0,
+            tx_watchdog_trips_total: 0,
+            tx_keepalive_resent_total: 0,
             device_errors: 0,
             rx_timeouts: 10,
             tx_timeouts: 0,
@@ -748,6 +1304,8 @@ mod tests {
             tx_packages_fault_aborted_total: 0,
             tx_packages_transport_failed_total: 0,
             rx_joint_position_incomplete_groups_dropped_total: 0,
+            rx_joint_position_partial_groups_committed_total: 0,
+            rx_feedback_frames_missed_total: 0,
             rx_joint_position_control_grade_rejected_total: 0,
             rx_end_pose_incomplete_groups_dropped_total: 0,
             rx_joint_dynamic_groups_dropped_total: 0,
@@ -775,6 +1333,8 @@ mod tests {
             tx_shutdown_conflicts_total: 0,
             tx_shutdown_sent_total: 0,
             tx_fault_aborts_total: 0,
+            tx_watchdog_trips_total: 0,
+            tx_keepalive_resent_total: 0,
             device_errors: 0,
             rx_timeouts: 0,
             tx_timeouts: 0,
@@ -783,6 +1343,8 @@ mod tests {
             tx_packages_fault_aborted_total: 0,
             tx_packages_transport_failed_total: 0,
             rx_joint_position_incomplete_groups_dropped_total: 0,
+            rx_joint_position_partial_groups_committed_total: 0,
+            rx_feedback_frames_missed_total: 0,
             rx_joint_position_control_grade_rejected_total: 0,
             rx_end_pose_incomplete_groups_dropped_total: 0,
             rx_joint_dynamic_groups_dropped_total: 0,
@@ -847,6 +1409,8 @@ mod tests {
             tx_shutdown_conflicts_total: 0,
             tx_shutdown_sent_total: 0,
             tx_fault_aborts_total: 0,
+            tx_watchdog_trips_total: 0,
+            tx_keepalive_resent_total: 0,
             device_errors: 0,
             rx_timeouts: 0,
             tx_timeouts: 0,
@@ -855,6 +1419,8 @@ mod tests {
             tx_packages_fault_aborted_total: 0,
             tx_packages_transport_failed_total: 0,
             rx_joint_position_incomplete_groups_dropped_total: 0,
+            rx_joint_position_partial_groups_committed_total: 0,
+            rx_feedback_frames_missed_total: 0,
             rx_joint_position_control_grade_rejected_total: 0,
             rx_end_pose_incomplete_groups_dropped_total: 0,
             rx_joint_dynamic_groups_dropped_total: 0,
@@ -891,6 +1457,8 @@ mod tests {
             tx_shutdown_conflicts_total: 0,
             tx_shutdown_sent_total: 0,
             tx_fault_aborts_total: 0,
+            tx_watchdog_trips_total: 0,
+            tx_keepalive_resent_total: 0,
             device_errors: 0,
             rx_timeouts: 0,
             tx_timeouts: 0,
@@ -899,6 +1467,8 @@ mod tests {
             tx_packages_fault_aborted_total: 0,
             tx_packages_transport_failed_total: 0,
             rx_joint_position_incomplete_groups_dropped_total: 0,
+            rx_joint_position_partial_groups_committed_total: 0,
+            rx_feedback_frames_missed_total: 0,
             rx_joint_position_control_grade_rejected_total: 0,
             rx_end_pose_incomplete_groups_dropped_total: 0,
             rx_joint_dynamic_groups_dropped_total: 0,
@@ -926,6 +1496,8 @@ mod tests {
             tx_shutdown_conflicts_total: 0,
             tx_shutdown_sent_total: 2,
             tx_fault_aborts_total: 7,
+            tx_watchdog_trips_total: 0,
+            tx_keepalive_resent_total: 0,
             device_errors: 0,
             rx_timeouts: 0,
             tx_timeouts: 0,
@@ -934,6 +1506,8 @@ mod tests {
             tx_packages_fault_aborted_total: 0,
             tx_packages_transport_failed_total: 0,
             rx_joint_position_incomplete_groups_dropped_total: 0,
+            rx_joint_position_partial_groups_committed_total: 0,
+            rx_feedback_frames_missed_total: 0,
             rx_joint_position_control_grade_rejected_total: 0,
             rx_end_pose_incomplete_groups_dropped_total: 0,
             rx_joint_dynamic_groups_dropped_total: 0,
@@ -960,6 +1534,8 @@ mod tests {
             tx_shutdown_conflicts_total: 0,
             tx_shutdown_sent_total: 0,
             tx_fault_aborts_total: 0,
+            tx_watchdog_trips_total: 0,
+            tx_keepalive_resent_total: 0,
             device_errors: 0,
             rx_timeouts: 0,
             tx_timeouts: 0,
@@ -968,6 +1544,8 @@ mod tests {
             tx_packages_fault_aborted_total: 0,
             tx_packages_transport_failed_total: 0,
             rx_joint_position_incomplete_groups_dropped_total: 0,
+            rx_joint_position_partial_groups_committed_total: 0,
+            rx_feedback_frames_missed_total: 0,
             rx_joint_position_control_grade_rejected_total: 0,
             rx_end_pose_incomplete_groups_dropped_total: 0,
             rx_joint_dynamic_groups_dropped_total: 0,
@@ -989,4 +1567,182 @@ mod tests {
         };
         assert!(abnormal.is_overwrite_rate_abnormal());
     }
+
+    fn record_n(rates: &PerIdFrameRates, id: u32, n: usize) {
+        let frame = PiperFrame::new_standard(id, [0u8; 8]).unwrap();
+        for _ in 0..n {
+            rates.record(&frame);
+        }
+    }
+
+    #[test]
+    fn test_per_id_fps_tracks_each_id_over_sliding_window() {
+        let rates = PerIdFrameRates::default();
+        let mut history = VecDeque::new();
+        let t0 = Instant::now();
+
+        rates.sample(&mut history, t0);
+        assert!(rates.rates().is_empty());
+
+        // 第一秒：0x2A1 200Hz，0x2A5 500Hz
+        for step in 1..=10 {
+            record_n(&rates, 0x2A1, 20);
+            record_n(&rates, 0x2A5, 50);
+            rates.sample(&mut history, t0 + Duration::from_millis(100 * step));
+        }
+        let fps = rates.rates();
+        assert!((fps[&0x2A1] - 200.0).abs() < 1.0, "{fps:?}");
+        assert!((fps[&0x2A5] - 500.0).abs() < 1.0, "{fps:?}");
+
+        // 第二秒：0x2A1 掉到 50Hz，0x2A5 不受影响
+        for step in 11..=20 {
+            record_n(&rates, 0x2A1, 5);
+            record_n(&rates, 0x2A5, 50);
+            rates.sample(&mut history, t0 + Duration::from_millis(100 * step));
+        }
+        let fps = rates.rates();
+        assert!((fps[&0x2A1] - 50.0).abs() < 1.0, "{fps:?}");
+        assert!((fps[&0x2A5] - 500.0).abs() < 1.0, "{fps:?}");
+
+        // 第三秒：0x2A1 停发，帧率降为 0 但仍保留在结果中
+        for step in 21..=30 {
+            record_n(&rates, 0x2A5, 50);
+            rates.sample(&mut history, t0 + Duration::from_millis(100 * step));
+        }
+        assert_eq!(rates.rates()[&0x2A1], 0.0);
+        assert!(history.len() <= 11);
+    }
+
+    #[test]
+    fn test_per_id_fps_ignores_extended_ids_and_clears_on_reset() {
+        let metrics = PiperMetrics::new();
+        let mut history = VecDeque::new();
+        let t0 = Instant::now();
+        metrics.per_id.sample(&mut history, t0);
+
+        metrics.per_id.record(&PiperFrame::new_extended(0x2A1, [0u8; 8]).unwrap());
+        record_n(&metrics.per_id, 0x151, 10);
+        metrics.per_id.sample(&mut history, t0 + Duration::from_millis(100));
+        assert_eq!(
+            metrics.per_id_fps().keys().copied().collect::<Vec<_>>(),
+            vec![0x151]
+        );
+
+        metrics.reset();
+        assert!(metrics.per_id_fps().is_empty());
+    }
+
+    #[test]
+    fn test_latency_histogram_bucket_bounds_cover_values() {
+        for value in [
+            0,
+            1,
+            15,
+            16,
+            17,
+            31,
+            32,
+            1_000,
+            4_999,
+            5_000,
+            123_456,
+            u64::MAX >> 30,
+        ] {
+            let upper = histogram_bucket_upper_bound(histogram_bucket(value));
+            assert!(upper >= value, "value {value} upper {upper}");
+            assert!(upper - value <= value / 16, "value {value} upper {upper}");
+        }
+        assert_eq!(histogram_bucket(u64::MAX), HISTOGRAM_BUCKETS - 1);
+    }
+
+    #[test]
+    fn test_latency_histogram_percentiles() {
+        let histogram = LatencyHistogram::default();
+        assert_eq!(histogram.percentiles(), LatencyPercentiles::default());
+
+        for _ in 0..989 {
+            histogram.record(5_000);
+        }
+        for _ in 0..10 {
+            histogram.record(6_000);
+        }
+        histogram.record(20_000);
+
+        let percentiles = histogram.percentiles();
+        assert_eq!(percentiles.count, 1_000);
+        assert!((5_000..5_000 + 5_000 / 16).contains(&percentiles.p50_us));
+        assert!((6_000..6_000 + 6_000 / 16).contains(&percentiles.p99_us));
+        assert_eq!(percentiles.p999_us, percentiles.p99_us);
+        assert!((20_000..20_000 + 20_000 / 16).contains(&percentiles.max_us));
+    }
+
+    #[test]
+    fn test_rx_interval_prefers_hardware_timestamp_and_falls_back_to_monotonic() {
+        let metrics = PiperMetrics::new();
+        let hw = |id, ts| PiperFrame::new_standard(id, [0u8; 8]).unwrap().with_timestamp_us(ts);
+
+        // 硬件时间戳：主机时钟不参与计算
+        metrics.timing.record_rx(&hw(0x2A5, 10_000), 1);
+        metrics.timing.record_rx(&hw(0x2A6, 10_100), 2);
+        metrics.timing.record_rx(&hw(0x2A5, 15_000), 3);
+        // 时间戳回绕的样本被跳过
+        metrics.timing.record_rx(&hw(0x2A6, 50), 4);
+        // 无硬件时间戳：使用主机单调时钟
+        let plain = PiperFrame::new_standard(0x151, [0u8; 8]).unwrap();
+        metrics.timing.record_rx(&plain, 100_000);
+        metrics.timing.record_rx(&plain, 105_000);
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.rx_interval_us.count, 2);
+        assert!((5_000..5_000 + 5_000 / 16).contains(&snapshot.rx_interval_us.max_us));
+        assert!((5_000..5_000 + 5_000 / 16).contains(&snapshot.rx_interval_us.p50_us));
+
+        metrics.reset();
+        assert_eq!(
+            metrics.snapshot().rx_interval_us,
+            LatencyPercentiles::default()
+        );
+    }
+
+    #[test]
+    fn test_command_feedback_latency_measures_from_oldest_unacknowledged_command() {
+        let metrics = PiperMetrics::new();
+
+        // 没有待应答命令时反馈不产生样本
+        metrics.timing.record_feedback(500);
+        metrics.timing.record_command_sent(1_000);
+        metrics.timing.record_command_sent(1_400);
+        metrics.timing.record_feedback(1_800);
+        metrics.timing.record_feedback(2_000);
+
+        let latency = metrics.snapshot().command_feedback_latency_us;
+        assert_eq!(latency.count, 1);
+        assert!((800..800 + 800 / 16).contains(&latency.p50_us));
+    }
+
+    #[test]
+    fn test_command_queue_snapshot_combines_channel_and_coalesced_counters() {
+        let metrics = PiperMetrics::new();
+        metrics.tx_realtime_enqueued_total.store(10, Ordering::Relaxed);
+        metrics.tx_realtime_overwrites_total.store(4, Ordering::Relaxed);
+        metrics.tx_reliable_queue_full_total.store(1, Ordering::Relaxed);
+        metrics.command_queue.record_coalesced_enqueue(CommandPriority::RealtimeControl);
+        metrics.command_queue.record_coalesced_drop(CommandPriority::RealtimeControl, 2);
+        metrics.command_queue.record_coalesced_enqueue(CommandPriority::ReliableCommand);
+        metrics.command_queue.set_depths(1, 0, 3);
+
+        let queue = metrics.snapshot().command_queue;
+        assert_eq!(queue.realtime.enqueued_total, 11);
+        assert_eq!(queue.realtime.dropped_total, 6);
+        assert_eq!(queue.reliable.enqueued_total, 1);
+        assert_eq!(queue.reliable.dropped_total, 1);
+        assert_eq!(queue.shutdown.depth, 1);
+        assert!(queue.shutdown_waiting_behind_backlog());
+
+        metrics.reset();
+        assert_eq!(
+            metrics.snapshot().command_queue,
+            CommandQueueSnapshot::default()
+        );
+    }
 }
@@ -0,0 +1,519 @@
+//! Prometheus 文本格式导出
+//!
+//! 纯函数：只读取 [`MetricsSnapshot`]（以及可选的按 CAN ID 帧率），不依赖任何 HTTP 框架，
+//! 结果可直接作为任意 `/metrics` 端点的响应体（`text/plain; version=0.0.4`）。
+
+use super::{CommandQueueSnapshot, CommandQueueStats, LatencyPercentiles, MetricsSnapshot};
+use piper_can::TxEchoStats;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// 导出时附加的标签与按 CAN ID 数据
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrometheusLabels<'a> {
+    /// CAN 接口名，作为所有指标的 `interface` 标签
+    pub interface: Option<&'a str>,
+    /// 各 CAN ID 的帧率（见 [`PiperMetrics::per_id_fps`](super::PiperMetrics::per_id_fps)），
+    /// 导出为 `piper_fps{can_id="0x2A5"}`
+    pub per_id_fps: Option<&'a HashMap<u32, f32>>,
+}
+
+type CounterField = fn(&MetricsSnapshot) -> u64;
+type QueueField = fn(&CommandQueueStats) -> u64;
+
+/// (指标名, 说明, 取值)；指标名已带 `piper_` 前缀与 `_total` 后缀
+const COUNTERS: &[(&str, &str, CounterField)] = &[
+    (
+        "piper_frames_received_total",
+        "CAN frames received, including filtered echo frames.",
+        |s| s.rx_frames_total,
+    ),
+    (
+        "piper_frames_valid_total",
+        "CAN frames received after echo filtering.",
+        |s| s.rx_frames_valid,
+    ),
+    (
+        "piper_error_frames_total",
+        "Transport error frames received.",
+        |s| s.rx_error_frames_total,
+    ),
+    ("piper_bus_off_total", "Bus-off conditions detected.", |s| {
+        s.rx_bus_off_total
+    }),
+    (
+        "piper_error_passive_total",
+        "Error-passive conditions detected.",
+        |s| s.rx_error_passive_total,
+    ),
+    (
+        "piper_echo_filtered_total",
+        "Echo frames filtered on receive.",
+        |s| s.rx_echo_filtered,
+    ),
+    (
+        "piper_frames_sent_total",
+        "CAN frames handed to the adapter.",
+        |s| s.tx_frames_sent_total,
+    ),
+    (
+        "piper_realtime_enqueued_total",
+        "Realtime commands enqueued.",
+        |s| s.tx_realtime_enqueued_total,
+    ),
+    (
+        "piper_realtime_overwrites_total",
+        "Realtime mailbox overwrites.",
+        |s| s.tx_realtime_overwrites_total,
+    ),
+    (
+        "piper_coalesced_dropped_total",
+        "Stale commands dropped by the coalescing mailbox.",
+        |s| s.tx_coalesced_dropped_total,
+    ),
+    (
+        "piper_reliable_enqueued_total",
+        "Reliable commands enqueued.",
+        |s| s.tx_reliable_enqueued_total,
+    ),
+    (
+        "piper_reliable_queue_full_total",
+        "Reliable queue full rejections.",
+        |s| s.tx_reliable_queue_full_total,
+    ),
+    (
+        "piper_shutdown_requests_total",
+        "Emergency shutdown requests.",
+        |s| s.tx_shutdown_requests_total,
+    ),
+    (
+        "piper_shutdown_coalesced_total",
+        "Shutdown requests coalesced into an in-flight one.",
+        |s| s.tx_shutdown_coalesced_total,
+    ),
+    (
+        "piper_shutdown_conflicts_total",
+        "Shutdown requests rejected due to conflicting frames.",
+        |s| s.tx_shutdown_conflicts_total,
+    ),
+    ("piper_shutdown_sent_total", "Shutdown frames sent.", |s| {
+        s.tx_shutdown_sent_total
+    }),
+    (
+        "piper_drop_shutdown_attempt_total",
+        "Bounded shutdowns attempted on fault-latched drop.",
+        |s| s.tx_drop_shutdown_attempt_total,
+    ),
+    (
+        "piper_drop_shutdown_success_total",
+        "Bounded shutdowns completed on fault-latched drop.",
+        |s| s.tx_drop_shutdown_success_total,
+    ),
+    (
+        "piper_drop_shutdown_timeout_total",
+        "Bounded shutdowns timed out on fault-latched drop.",
+        |s| s.tx_drop_shutdown_timeout_total,
+    ),
+    (
+        "piper_drop_shutdown_skipped_total",
+        "Bounded shutdowns skipped on fault-latched drop.",
+        |s| s.tx_drop_shutdown_skipped_total,
+    ),
+    (
+        "piper_watchdog_trips_total",
+        "Software watchdog expiries that stopped the arm.",
+        |s| s.tx_watchdog_trips_total,
+    ),
+    (
+        "piper_keepalive_resent_total",
+        "Realtime packages resent by the command keepalive.",
+        |s| s.tx_keepalive_resent_total,
+    ),
+    (
+        "piper_fault_aborts_total",
+        "Normal control commands aborted by fault latch or stop.",
+        |s| s.tx_fault_aborts_total,
+    ),
+    ("piper_device_errors_total", "Adapter device errors.", |s| {
+        s.device_errors
+    }),
+    (
+        "piper_rx_timeouts_total",
+        "Receive timeouts (idle bus).",
+        |s| s.rx_timeouts,
+    ),
+    ("piper_tx_timeouts_total", "Transmit timeouts.", |s| {
+        s.tx_timeouts
+    }),
+    (
+        "piper_packages_completed_total",
+        "Multi-frame packages fully sent.",
+        |s| s.tx_packages_completed_total,
+    ),
+    (
+        "piper_packages_partial_total",
+        "Multi-frame packages partially sent.",
+        |s| s.tx_packages_partial_total,
+    ),
+    (
+        "piper_packages_fault_aborted_total",
+        "Multi-frame packages aborted by fault latch.",
+        |s| s.tx_packages_fault_aborted_total,
+    ),
+    (
+        "piper_packages_transport_failed_total",
+        "Multi-frame packages failed before any frame.",
+        |s| s.tx_packages_transport_failed_total,
+    ),
+    (
+        "piper_joint_position_incomplete_groups_dropped_total",
+        "Joint position frame groups dropped as incomplete.",
+        |s| s.rx_joint_position_incomplete_groups_dropped_total,
+    ),
+    (
+        "piper_joint_position_partial_groups_committed_total",
+        "Incomplete joint position frame groups committed by the commit policy.",
+        |s| s.rx_joint_position_partial_groups_committed_total,
+    ),
+    (
+        "piper_feedback_frames_missed_total",
+        "Feedback frames missing from incomplete frame groups.",
+        |s| s.rx_feedback_frames_missed_total,
+    ),
+    (
+        "piper_joint_position_control_grade_rejected_total",
+        "Joint position groups rejected by the control-grade span check.",
+        |s| s.rx_joint_position_control_grade_rejected_total,
+    ),
+    (
+        "piper_end_pose_incomplete_groups_dropped_total",
+        "End pose frame groups dropped as incomplete.",
+        |s| s.rx_end_pose_incomplete_groups_dropped_total,
+    ),
+    (
+        "piper_joint_dynamic_groups_dropped_total",
+        "Partial joint dynamic frame groups dropped.",
+        |s| s.rx_joint_dynamic_groups_dropped_total,
+    ),
+    (
+        "piper_joint_dynamic_control_grade_rejected_total",
+        "Joint dynamic groups rejected by the control-grade span check.",
+        |s| s.rx_joint_dynamic_control_grade_rejected_total,
+    ),
+    (
+        "piper_hot_snapshot_publish_skipped_total",
+        "Hot snapshot publications skipped for lack of a free slot.",
+        |s| s.rx_hot_snapshot_publish_skipped_total,
+    ),
+    (
+        "piper_control_pair_generation_invalidated_total",
+        "Control-grade generations invalidated by one-sided jumps.",
+        |s| s.rx_control_pair_generation_invalidated_total,
+    ),
+    (
+        "piper_soft_admission_timeout_total",
+        "SoftRealtime commands rejected at admission with an expired budget.",
+        |s| s.tx_soft_admission_timeout_total,
+    ),
+    (
+        "piper_soft_deadline_miss_total",
+        "SoftRealtime send deadline misses.",
+        |s| s.tx_soft_deadline_miss_total,
+    ),
+    (
+        "piper_soft_consecutive_deadline_miss_total",
+        "SoftRealtime consecutive deadline miss increments.",
+        |s| s.tx_soft_consecutive_deadline_miss_total,
+    ),
+    (
+        "piper_shutdown_behind_backlog_total",
+        "Emergency-stop dispatches that waited behind queued control commands.",
+        |s| s.command_queue.shutdown_behind_backlog_total,
+    ),
+];
+
+impl MetricsSnapshot {
+    /// 导出为 Prometheus 文本格式（无标签）
+    ///
+    /// 计数器以 `piper_*_total` 导出，延迟分布以 summary（单位秒）导出。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// # use piper_driver::metrics::PiperMetrics;
+    /// let body = PiperMetrics::new().snapshot().to_prometheus();
+    /// assert!(body.contains("piper_frames_received_total 0"));
+    /// ```
+    pub fn to_prometheus(self) -> String {
+        self.to_prometheus_with(PrometheusLabels::default())
+    }
+
+    /// 导出为 Prometheus 文本格式，附带 `interface` 标签与按 CAN ID 的 `piper_fps`
+    pub fn to_prometheus_with(self, labels: PrometheusLabels<'_>) -> String {
+        let base = labels
+            .interface
+            .map(|interface| format!("interface=\"{}\"", escape_label_value(interface)));
+        let mut out = String::with_capacity(8 * 1024);
+
+        for (name, help, value) in COUNTERS {
+            write_header(&mut out, name, help, "counter");
+            write_sample(&mut out, name, base.as_deref(), None, value(&self));
+        }
+
+        write_summary(
+            &mut out,
+            "piper_rx_interval_seconds",
+            "Interval between consecutive frames with the same CAN ID.",
+            base.as_deref(),
+            &self.rx_interval_us,
+        );
+        write_summary(
+            &mut out,
+            "piper_command_feedback_latency_seconds",
+            "Latency from a control frame send to the next robot feedback frame.",
+            base.as_deref(),
+            &self.command_feedback_latency_us,
+        );
+
+        write_command_queue(&mut out, base.as_deref(), &self.command_queue);
+
+        if let Some(tx_echo) = &self.tx_echo {
+            write_tx_echo(&mut out, base.as_deref(), tx_echo);
+        }
+
+        if let Some(per_id_fps) = labels.per_id_fps {
+            write_header(
+                &mut out,
+                "piper_fps",
+                "Receive frame rate per CAN ID.",
+                "gauge",
+            );
+            let mut ids: Vec<_> = per_id_fps.iter().collect();
+            ids.sort_unstable_by_key(|(id, _)| **id);
+            for (id, fps) in ids {
+                let can_id = format!("can_id=\"0x{id:03X}\"");
+                write_sample(&mut out, "piper_fps", base.as_deref(), Some(&can_id), *fps);
+            }
+        }
+
+        out
+    }
+}
+
+fn write_header(out: &mut String, name: &str, help: &str, kind: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn write_sample(
+    out: &mut String,
+    name: &str,
+    base: Option<&str>,
+    extra: Option<&str>,
+    value: impl std::fmt::Display,
+) {
+    out.push_str(name);
+    match (base, extra) {
+        (None, None) => {},
+        (Some(label), None) | (None, Some(label)) => {
+            let _ = write!(out, "{{{label}}}");
+        },
+        (Some(base), Some(extra)) => {
+            let _ = write!(out, "{{{base},{extra}}}");
+        },
+    }
+    let _ = writeln!(out, " {value}");
+}
+
+fn write_summary(
+    out: &mut String,
+    name: &str,
+    help: &str,
+    base: Option<&str>,
+    percentiles: &LatencyPercentiles,
+) {
+    write_header(out, name, help, "summary");
+    for (quantile, value_us) in [
+        ("0.5", percentiles.p50_us),
+        ("0.99", percentiles.p99_us),
+        ("0.999", percentiles.p999_us),
+    ] {
+        let quantile = format!("quantile=\"{quantile}\"");
+        write_sample(out, name, base, Some(&quantile), value_us as f64 / 1e6);
+    }
+    write_sample(out, &format!("{name}_count"), base, None, percentiles.count);
+}
+
+fn write_command_queue(out: &mut String, base: Option<&str>, queue: &CommandQueueSnapshot) {
+    let lanes = [
+        ("shutdown", &queue.shutdown),
+        ("realtime", &queue.realtime),
+        ("reliable", &queue.reliable),
+    ];
+    let series: [(&str, &str, &str, QueueField); 3] = [
+        (
+            "piper_command_queue_depth",
+            "Commands currently queued per priority.",
+            "gauge",
+            |stats| stats.depth,
+        ),
+        (
+            "piper_command_queue_enqueued_total",
+            "Commands enqueued per priority.",
+            "counter",
+            |stats| stats.enqueued_total,
+        ),
+        (
+            "piper_command_queue_dropped_total",
+            "Commands dropped per priority (overwritten, coalesced, queue full or rejected).",
+            "counter",
+            |stats| stats.dropped_total,
+        ),
+    ];
+    for (name, help, kind, value) in series {
+        write_header(out, name, help, kind);
+        for (priority, stats) in lanes {
+            let priority = format!("priority=\"{priority}\"");
+            write_sample(out, name, base, Some(&priority), value(stats));
+        }
+    }
+}
+
+fn write_tx_echo(out: &mut String, base: Option<&str>, stats: &TxEchoStats) {
+    let counters: [(&str, &str, u64); 3] = [
+        (
+            "piper_tx_echo_completed_total",
+            "Sent frames confirmed by a matching TX echo.",
+            stats.completed,
+        ),
+        (
+            "piper_tx_echo_lost_total",
+            "Sent frames whose echo slot was reused before the echo arrived.",
+            stats.lost,
+        ),
+        (
+            "piper_tx_echo_unmatched_total",
+            "TX echoes that matched no in-flight frame.",
+            stats.unmatched,
+        ),
+    ];
+    for (name, help, value) in counters {
+        write_header(out, name, help, "counter");
+        write_sample(out, name, base, None, value);
+    }
+
+    let name = "piper_tx_echo_in_flight";
+    write_header(
+        out,
+        name,
+        "Sent frames still waiting for their TX echo.",
+        "gauge",
+    );
+    write_sample(out, name, base, None, stats.in_flight);
+
+    let name = "piper_tx_echo_latency_seconds";
+    write_header(out, name, "Latency from USB send to TX echo.", "gauge");
+    for (stat, value_us) in [
+        ("last", stats.last_latency_us),
+        ("max", stats.max_latency_us),
+        ("mean", stats.mean_latency_us),
+    ] {
+        let stat = format!("stat=\"{stat}\"");
+        write_sample(out, name, base, Some(&stat), value_us as f64 / 1e6);
+    }
+}
+
+/// 按文本格式规范转义标签值中的 `\`、`"` 与换行
+fn escape_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prometheus_export_includes_counters_summaries_and_per_id_fps() {
+        let snapshot = MetricsSnapshot {
+            rx_frames_total: 1_200,
+            rx_bus_off_total: 2,
+            rx_interval_us: LatencyPercentiles {
+                count: 1_000,
+                p50_us: 5_000,
+                p99_us: 5_500,
+                p999_us: 7_000,
+                max_us: 9_000,
+            },
+            command_queue: CommandQueueSnapshot {
+                realtime: CommandQueueStats {
+                    depth: 3,
+                    enqueued_total: 40,
+                    dropped_total: 7,
+                },
+                ..Default::default()
+            },
+            tx_echo: Some(TxEchoStats {
+                completed: 500,
+                lost: 1,
+                in_flight: 2,
+                max_latency_us: 1_500,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let per_id_fps = HashMap::from([(0x2A5, 200.0f32), (0x2A1, 50.0)]);
+
+        let body = snapshot.to_prometheus_with(PrometheusLabels {
+            interface: Some("can0"),
+            per_id_fps: Some(&per_id_fps),
+        });
+
+        assert!(body.contains("# TYPE piper_frames_received_total counter\n"));
+        assert!(body.contains("piper_frames_received_total{interface=\"can0\"} 1200\n"));
+        assert!(body.contains("piper_bus_off_total{interface=\"can0\"} 2\n"));
+        assert!(
+            body.contains(
+                "piper_rx_interval_seconds{interface=\"can0\",quantile=\"0.99\"} 0.0055\n"
+            )
+        );
+        assert!(body.contains("piper_rx_interval_seconds_count{interface=\"can0\"} 1000\n"));
+        assert!(body.contains("# TYPE piper_command_queue_depth gauge\n"));
+        assert!(
+            body.contains(
+                "piper_command_queue_depth{interface=\"can0\",priority=\"realtime\"} 3\n"
+            )
+        );
+        assert!(body.contains(
+            "piper_command_queue_dropped_total{interface=\"can0\",priority=\"realtime\"} 7\n"
+        ));
+        assert!(body.contains("piper_tx_echo_completed_total{interface=\"can0\"} 500\n"));
+        assert!(body.contains("piper_tx_echo_lost_total{interface=\"can0\"} 1\n"));
+        assert!(body.contains("piper_tx_echo_in_flight{interface=\"can0\"} 2\n"));
+        assert!(
+            body.contains(
+                "piper_tx_echo_latency_seconds{interface=\"can0\",stat=\"max\"} 0.0015\n"
+            )
+        );
+        let fps_2a1 = body.find("piper_fps{interface=\"can0\",can_id=\"0x2A1\"} 50\n").unwrap();
+        let fps_2a5 = body.find("piper_fps{interface=\"can0\",can_id=\"0x2A5\"} 200\n").unwrap();
+        assert!(fps_2a1 < fps_2a5, "per-ID samples are sorted by CAN ID");
+    }
+
+    #[test]
+    fn prometheus_export_without_labels_and_with_escaped_interface() {
+        let body = MetricsSnapshot::default().to_prometheus();
+        assert!(body.contains("piper_device_errors_total 0\n"));
+        assert!(!body.contains("piper_fps"));
+        assert!(
+            !body.contains("piper_tx_echo"),
+            "backends without TX echo tracking export nothing"
+        );
+        assert!(body.lines().all(|line| line.starts_with('#') || line.starts_with("piper_")));
+
+        let body = MetricsSnapshot::default().to_prometheus_with(PrometheusLabels {
+            interface: Some("a\"b\\c"),
+            per_id_fps: None,
+        });
+        assert!(body.contains("piper_tx_timeouts_total{interface=\"a\\\"b\\\\c\"} 0\n"));
+    }
+}
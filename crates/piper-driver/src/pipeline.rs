@@ -2,7 +2,7 @@
 //!
 //! 负责后台 IO 线程的 CAN 帧接收、解析和状态更新逻辑。
 
-use crate::command::SoftRealtimeMailbox;
+use crate::command::{CommandPriority, SoftRealtimeMailbox};
 use crate::diagnostics::{DiagnosticEvent, QueryDiagnostic};
 use crate::heartbeat::monotonic_micros;
 use crate::metrics::PiperMetrics;
@@ -12,7 +12,9 @@ use crate::piper::{
     NormalSendGateDenyReason, RuntimeFaultKind, RuntimePhase, SOFT_CONTROL_SEND_BUDGET,
     SOFT_DEADLINE_MISS_FAULT_THRESHOLD, ShutdownDispatch, ShutdownLane,
 };
+use crate::recording::RecordedFrameDirection;
 use crate::state::*;
+use crate::watchdog::WatchdogConfig;
 use crossbeam_channel::Receiver;
 #[cfg(test)]
 use piper_can::CanAdapter;
@@ -26,7 +28,7 @@ use std::collections::VecDeque;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 use std::time::{Duration, Instant};
-use tracing::{debug, error, trace, warn};
+use tracing::{debug, error, trace, trace_span, warn};
 
 // 使用 spin_sleep 提供微秒级延迟精度（相比 std::thread::sleep 的 1-2ms）
 use spin_sleep;
@@ -37,8 +39,12 @@ const ALL_DRIVES_ENABLED_MASK: u8 = 0b11_1111;
 const TX_IDLE_BACKOFF_MIN_US: u64 = 50;
 const TX_IDLE_BACKOFF_RUNNING_MAX_US: u64 = 200;
 const TX_IDLE_BACKOFF_FAULT_LATCHED_MAX_US: u64 = 1_000;
+/// 令牌桶容量：允许一次突发发送一个完整的 MIT 帧包（6 帧）
+const TX_RATE_LIMIT_BURST_FRAMES: f64 = 6.0;
 const ID_JOINT_DRIVER_HIGH_SPEED_BASE_RAW: u32 = 0x251;
 const ID_JOINT_DRIVER_LOW_SPEED_BASE_RAW: u32 = 0x261;
+/// RX 线程拷贝适配器 TX Echo 统计到 metrics 的周期
+const TX_ECHO_PUBLISH_INTERVAL_US: u64 = 100_000;
 
 #[inline]
 fn tx_idle_backoff(min_us: u64, current: u64, max_us: u64) -> (Duration, u64) {
@@ -47,6 +53,89 @@ fn tx_idle_backoff(min_us: u64, current: u64, max_us: u64) -> (Duration, u64) {
     (Duration::from_micros(sleep_us), next_us)
 }
 
+/// TX 令牌桶限速器（单调时钟）
+///
+/// 令牌按 `rate_hz` 匀速补充，最多积累 [`TX_RATE_LIMIT_BURST_FRAMES`] 个。
+/// 按 `tx_frames_sent_total` 的增量扣除令牌，允许透支：一个帧包整体发出后，
+/// 下一次调度按欠额顺延，长期平均速率不超过上限。
+#[derive(Debug)]
+struct TxRateLimiter {
+    rate_hz: f64,
+    tokens: f64,
+    last_refill_us: u64,
+    charged_frames: u64,
+}
+
+impl TxRateLimiter {
+    fn new(max_tx_rate_hz: Option<u32>, now_us: u64, sent_frames: u64) -> Option<Self> {
+        let rate_hz = max_tx_rate_hz.filter(|rate| *rate > 0)?;
+        Some(Self {
+            rate_hz: f64::from(rate_hz),
+            tokens: TX_RATE_LIMIT_BURST_FRAMES,
+            last_refill_us: now_us,
+            charged_frames: sent_frames,
+        })
+    }
+
+    /// 返回距离下一次允许调度还需等待的时间；`None` 表示可以立即发送。
+    fn wait_before_next(&mut self, now_us: u64, sent_frames: u64) -> Option<Duration> {
+        // 指标被 reset 时计数回退，此时只重新对齐，不扣令牌
+        let sent = sent_frames.saturating_sub(self.charged_frames);
+        self.charged_frames = sent_frames;
+        self.tokens -= sent as f64;
+
+        let elapsed_us = now_us.saturating_sub(self.last_refill_us);
+        self.last_refill_us = now_us;
+        self.tokens = (self.tokens + elapsed_us as f64 * self.rate_hz / 1_000_000.0)
+            .min(TX_RATE_LIMIT_BURST_FRAMES);
+
+        if self.tokens >= 1.0 {
+            None
+        } else {
+            let deficit = 1.0 - self.tokens;
+            Some(Duration::from_secs_f64(deficit / self.rate_hz))
+        }
+    }
+}
+
+/// 命令保活：控制流中断时按固定速率重发最近一次完整发出的实时帧包
+///
+/// 只记录来自实时插槽 / 软实时队列的完整帧包；任何可靠命令、维护帧、急停帧、
+/// 故障锁存或回放模式都会清除记录，避免在状态切换后重发旧的控制命令。
+#[derive(Debug)]
+struct CommandKeepalive {
+    period_us: u64,
+    last_package: Option<crate::command::FrameBuffer>,
+    last_sent_us: u64,
+}
+
+impl CommandKeepalive {
+    fn new(keepalive_rate_hz: Option<u32>) -> Option<Self> {
+        let rate_hz = keepalive_rate_hz.filter(|rate| *rate > 0)?;
+        Some(Self {
+            period_us: (1_000_000 / u64::from(rate_hz)).max(1),
+            last_package: None,
+            last_sent_us: 0,
+        })
+    }
+
+    fn record(&mut self, frames: crate::command::FrameBuffer, now_us: u64) {
+        self.last_package = Some(frames);
+        self.last_sent_us = now_us;
+    }
+
+    fn forget(&mut self) {
+        self.last_package = None;
+    }
+
+    /// 距离上次发送已超过保活周期时返回需要重发的帧包
+    fn due(&self, now_us: u64) -> Option<&crate::command::FrameBuffer> {
+        self.last_package
+            .as_ref()
+            .filter(|_| now_us.saturating_sub(self.last_sent_us) >= self.period_us)
+    }
+}
+
 #[inline]
 fn backend_tx_frame(frame: PiperFrame) -> PiperFrame {
     frame.with_timestamp_us(0)
@@ -54,6 +143,7 @@ fn backend_tx_frame(frame: PiperFrame) -> PiperFrame {
 
 #[inline]
 fn record_sent_frame(ctx: &Arc<PiperContext>, frame: &PiperFrame) {
+    ctx.flight_recorder.record(frame, RecordedFrameDirection::Tx, host_rx_mono_us());
     if let Ok(hooks) = ctx.hooks.try_read() {
         hooks.trigger_all_sent(frame);
     }
@@ -63,11 +153,13 @@ fn record_sent_frame(ctx: &Arc<PiperContext>, frame: &PiperFrame) {
 fn send_control_and_record(
     tx: &mut impl RealtimeTxAdapter,
     ctx: &Arc<PiperContext>,
+    metrics: &PiperMetrics,
     frame: PiperFrame,
     budget: Duration,
 ) -> Result<(), CanError> {
     let backend_frame = backend_tx_frame(frame);
     tx.send_control(backend_frame, budget)?;
+    metrics.timing.record_command_sent(host_rx_mono_us());
     record_sent_frame(ctx, &backend_frame);
     Ok(())
 }
@@ -661,6 +753,22 @@ fn refresh_local_maintenance_tx_state(
     *maintenance_tx_state = MaintenanceTxState::from_snapshot(maintenance_gate.snapshot());
 }
 
+/// 关节位置帧组（0x2A5-0x2A7）的提交策略
+///
+/// 不完整帧组提交时，缺失关节沿用上一份完整快照的值，`frame_valid_mask` 标记本次实际收到的帧；
+/// 这类快照只进入监控/运动快照，不会进入控制级状态。在第一份完整帧组提交之前，
+/// 不完整帧组一律丢弃。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FrameCommitPolicy {
+    /// 只提交完整帧组（默认）；不完整帧组在 `frame_group_timeout_ms` 后丢弃
+    #[default]
+    WaitAll,
+    /// 收到帧组最后一帧（0x2A7）时立即提交，即使前面的帧缺失
+    OnLast,
+    /// 帧组首帧到达后等待 `commit_window_us`，超时仍不完整则提交已收到的部分
+    Timeout,
+}
+
 /// Pipeline 配置
 ///
 /// 控制 IO 线程的行为，包括接收超时和帧组超时设置。
@@ -668,7 +776,7 @@ fn refresh_local_maintenance_tx_state(
 /// # Example
 ///
 /// ```
-/// use piper_driver::PipelineConfig;
+/// use piper_driver::{FrameCommitPolicy, PipelineConfig};
 ///
 /// // 使用默认配置（2ms 接收超时，10ms 帧组超时）
 /// let config = PipelineConfig::default();
@@ -679,6 +787,13 @@ fn refresh_local_maintenance_tx_state(
 ///     frame_group_timeout_ms: 20,
 ///     velocity_buffer_timeout_us: 20_000,
 ///     low_speed_drive_state_freshness_ms: 100,
+///     joint_position_consistency_window_us: 5_000,
+///     commit_policy: FrameCommitPolicy::WaitAll,
+///     commit_window_us: 2_000,
+///     max_tx_rate_hz: Some(4_000),
+///     flight_recorder_capacity: 512,
+///     watchdog: None,
+///     keepalive_rate_hz: None,
 /// };
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -694,6 +809,40 @@ pub struct PipelineConfig {
     /// 低速驱动状态新鲜度窗口（毫秒）
     /// 只有在收到完整且新鲜的 6 轴低速反馈后，maintenance gate 才会认为驱动使能状态已确认
     pub low_speed_drive_state_freshness_ms: u64,
+    /// 关节位置帧组一致性窗口（微秒）
+    /// 0x2A5/0x2A6/0x2A7 的时间戳差值超过此窗口时，快照标记为 `StateConsistency::Torn`
+    pub joint_position_consistency_window_us: u64,
+    /// 关节位置帧组提交策略
+    pub commit_policy: FrameCommitPolicy,
+    /// 关节位置帧组提交窗口（微秒）
+    ///
+    /// 仅 `FrameCommitPolicy::Timeout` 使用：帧组首帧到达后超过此时间仍不完整，则提交部分快照。
+    /// 应小于 `frame_group_timeout_ms`，否则不完整帧组会先被丢弃。
+    pub commit_window_us: u64,
+    /// TX 发送速率上限（帧/秒）
+    ///
+    /// `None`（默认）或 `Some(0)` 表示不限速。设置后 TX 线程按单调时钟令牌桶
+    /// 调度普通控制帧：令牌不足时不再取新命令，期间写入实时插槽/合并邮箱的命令
+    /// 按最新值优先合并，调用方不会被阻塞。急停帧不受限速约束。
+    pub max_tx_rate_hz: Option<u32>,
+    /// 飞行记录器容量（帧）
+    ///
+    /// 保留最近 N 帧 RX/TX 流量，可通过 `Piper::recent_frames()` 读取；
+    /// 发生致命 CAN 错误时冻结一份并写入日志。`0` 表示关闭。
+    pub flight_recorder_capacity: usize,
+    /// 软件看门狗
+    ///
+    /// `Some` 时控制线程需在 `interval` 内调用 `Piper::pet_watchdog()`；到期后 TX 线程
+    /// 锁存 `RuntimeFaultKind::WatchdogExpired` 并发送 `action` 对应的停止帧。
+    /// `None`（默认）表示关闭。
+    pub watchdog: Option<WatchdogConfig>,
+    /// 命令保活速率（Hz）
+    ///
+    /// 设置后，若实时控制流中断（例如上层轨迹生成器卡顿），TX 线程按此速率重发
+    /// 最近一次完整发出的实时帧包，防止固件因收不到命令而失能。任何可靠命令、
+    /// 状态切换、急停或故障锁存都会停止保活，直到下一个实时帧包。
+    /// `None`（默认）或 `Some(0)` 表示关闭。
+    pub keepalive_rate_hz: Option<u32>,
 }
 
 impl Default for PipelineConfig {
@@ -703,6 +852,13 @@ impl Default for PipelineConfig {
             frame_group_timeout_ms: 10,
             velocity_buffer_timeout_us: 10_000, // 10ms (consistent with frame group timeout)
             low_speed_drive_state_freshness_ms: 100,
+            joint_position_consistency_window_us: 5_000,
+            commit_policy: FrameCommitPolicy::WaitAll,
+            commit_window_us: 2_000,
+            max_tx_rate_hz: None,
+            flight_recorder_capacity: crate::flight_recorder::DEFAULT_FLIGHT_RECORDER_CAPACITY,
+            watchdog: None,
+            keepalive_rate_hz: None,
         }
     }
 }
@@ -813,6 +969,10 @@ pub struct ParserState<'a> {
     /// 关节位置帧组元数据（mask、每槽位时间戳、组起始时间）
     joint_pos_group: PendingFrameGroup<3>,
     joint_pos_raw_timings: [Option<RawFeedbackTiming>; 3],
+    /// 关节位置各帧的 `timestamp_us`（用于一致性检查，0 表示未收到）
+    joint_pos_frame_timestamps_us: [Option<u64>; 3],
+    /// 上一份已提交的关节位置（不完整帧组提交时补齐缺失关节；`None` 表示尚无完整帧组）
+    last_committed_joint_pos: Option<[f64; 6]>,
 
     // === 末端位姿状态：帧组同步（0x2A2-0x2A4） ===
     /// 待提交的末端位姿数据（6个自由度：x, y, z, rx, ry, rz）
@@ -856,6 +1016,8 @@ impl<'a> ParserState<'a> {
             pending_joint_pos: [0.0; 6],
             joint_pos_group: PendingFrameGroup::new(),
             joint_pos_raw_timings: [None; 3],
+            joint_pos_frame_timestamps_us: [None; 3],
+            last_committed_joint_pos: None,
             pending_end_pose: [0.0; 6],
             end_pose_group: PendingFrameGroup::new(),
             pending_joint_dynamic: JointDynamicState::default(),
@@ -876,6 +1038,79 @@ impl<'a> Default for ParserState<'a> {
     }
 }
 
+/// 离线反馈重放器
+///
+/// 把录制下来的 RX 帧按顺序送入与 IO 线程相同的解析/帧组提交逻辑，每当控制级
+/// 关节位置与关节动态状态凑成新的一对（即一个完整反馈周期）时产出一个
+/// [`AlignedMotionState`]。帧组按 `StrictRealtime` 语义以帧自身的时间戳对齐；
+/// `*_host_rx_mono_us` 字段是重放时的主机时间，不代表录制时的接收时间。
+///
+/// # Example
+///
+/// ```
+/// # use piper_driver::pipeline::{FeedbackReplayer, PipelineConfig};
+/// # let frames: Vec<piper_can::PiperFrame> = Vec::new();
+/// let mut replayer = FeedbackReplayer::new(PipelineConfig::default());
+/// for frame in frames {
+///     if let Some(state) = replayer.feed(frame) {
+///         println!("{} {:?}", state.position_timestamp_us, state.joint_pos);
+///     }
+/// }
+/// ```
+pub struct FeedbackReplayer {
+    ctx: Arc<PiperContext>,
+    config: PipelineConfig,
+    state: ParserState<'static>,
+    metrics: Arc<PiperMetrics>,
+    last_pair_sequences: (u64, u64),
+}
+
+impl FeedbackReplayer {
+    pub fn new(config: PipelineConfig) -> Self {
+        Self {
+            ctx: Arc::new(PiperContext::new()),
+            config,
+            state: ParserState::new(),
+            metrics: Arc::new(PiperMetrics::new()),
+            last_pair_sequences: (0, 0),
+        }
+    }
+
+    /// 送入一帧；本帧完成了新的控制级位置+动态状态对时返回该周期的状态
+    pub fn feed(&mut self, frame: PiperFrame) -> Option<AlignedMotionState> {
+        let received =
+            piper_can::ReceivedFrame::new(frame, piper_can::TimestampProvenance::Hardware);
+        parse_and_update_state(
+            &received,
+            BackendCapability::StrictRealtime,
+            &self.ctx,
+            &self.config,
+            &mut self.state,
+            &self.metrics,
+        );
+
+        let pair = self.ctx.capture_control_pair();
+        let sequences = (pair.position_sequence, pair.dynamic_sequence);
+        if sequences.0 == 0 || sequences.1 == 0 || sequences == self.last_pair_sequences {
+            return None;
+        }
+        self.last_pair_sequences = sequences;
+        let state = AlignedMotionState::from_control_pair(&pair);
+        state.is_complete().then_some(state)
+    }
+
+    /// 重放用的状态上下文（可读取末端位姿、夹爪等其余反馈）
+    pub fn context(&self) -> &Arc<PiperContext> {
+        &self.ctx
+    }
+}
+
+impl Default for FeedbackReplayer {
+    fn default() -> Self {
+        Self::new(PipelineConfig::default())
+    }
+}
+
 fn reset_pending_velocity(state: &mut ParserState) {
     state.pending_joint_dynamic = JointDynamicState::default();
     state.pending_joint_dynamic_raw_timings = [None; 6];
@@ -888,6 +1123,34 @@ fn reset_pending_joint_position(state: &mut ParserState) {
     state.pending_joint_pos = [0.0; 6];
     state.joint_pos_group.reset();
     state.joint_pos_raw_timings = [None; 3];
+    state.joint_pos_frame_timestamps_us = [None; 3];
+}
+
+/// 帧组一致性判定使用的单帧时间戳
+///
+/// 后端不提供帧时间戳（`TimestampProvenance::None`，或 `timestamp_us` 为 0，例如关闭硬件
+/// 时间戳的 GS-USB）时退回主机接收时间，否则撕裂读永远无法被识别。
+fn consistency_timestamp_us(received: &piper_can::ReceivedFrame, host_rx_mono_us: u64) -> u64 {
+    let frame_timestamp_us = received.frame.timestamp_us();
+    if received.timestamp_provenance == piper_can::TimestampProvenance::None
+        || frame_timestamp_us == 0
+    {
+        host_rx_mono_us
+    } else {
+        frame_timestamp_us
+    }
+}
+
+/// 帧组内已收到各帧时间戳的最大差值（忽略未收到的槽位）
+fn frame_timestamp_span_us<const N: usize>(timestamps: &[Option<u64>; N]) -> u64 {
+    let mut received = timestamps.iter().flatten().copied();
+    let Some(first) = received.next() else {
+        return 0;
+    };
+    let (min_ts, max_ts) = received.fold((first, first), |(min_ts, max_ts), timestamp| {
+        (min_ts.min(timestamp), max_ts.max(timestamp))
+    });
+    max_ts - min_ts
 }
 
 fn reset_pending_end_pose(state: &mut ParserState) {
@@ -904,6 +1167,14 @@ fn complete_group_ready(mask: u8) -> bool {
     mask == 0b0000_0111
 }
 
+/// 累计不完整帧组中缺失的反馈帧数
+fn record_missed_feedback_frames(metrics: &PiperMetrics, expected_mask: u8, received_mask: u8) {
+    let missed = (expected_mask & !received_mask).count_ones();
+    metrics
+        .rx_feedback_frames_missed_total
+        .fetch_add(u64::from(missed), Ordering::Relaxed);
+}
+
 #[inline]
 fn control_grade_group_ready(
     group: &PendingFrameGroup<3>,
@@ -1015,10 +1286,68 @@ fn maybe_reset_joint_position_group(
         metrics
             .rx_joint_position_incomplete_groups_dropped_total
             .fetch_add(1, Ordering::Relaxed);
+        record_missed_feedback_frames(metrics, 0b111, state.joint_pos_group.mask);
         reset_pending_joint_position(state);
     }
 }
 
+/// 提交不完整的关节位置帧组（`FrameCommitPolicy::OnLast` / `Timeout`）
+///
+/// 缺失关节沿用上一份已提交的值，只发布到监控/运动快照，不进入控制级状态。
+/// 尚无完整帧组时不提交，返回 `false`。
+fn commit_partial_joint_position(
+    ctx: &Arc<PiperContext>,
+    config: &PipelineConfig,
+    state: &mut ParserState,
+    metrics: &Arc<PiperMetrics>,
+) -> bool {
+    let Some(mut joint_pos) = state.last_committed_joint_pos else {
+        return false;
+    };
+    if state.joint_pos_group.is_empty() {
+        return false;
+    }
+    for slot in 0..3 {
+        if state.joint_pos_group.contains_slot(slot) {
+            joint_pos[slot * 2..slot * 2 + 2]
+                .copy_from_slice(&state.pending_joint_pos[slot * 2..slot * 2 + 2]);
+        }
+    }
+
+    ctx.publish_joint_position(JointPositionState {
+        hardware_timestamp_us: state.joint_pos_group.max_alignment_timestamp_us(),
+        host_rx_mono_us: state.joint_pos_group.max_host_rx_mono_us(),
+        raw_feedback_timing: newest_raw_feedback_timing(&state.joint_pos_raw_timings),
+        joint_pos,
+        frame_valid_mask: state.joint_pos_group.mask,
+        consistency: StateConsistency::from_span(
+            frame_timestamp_span_us(&state.joint_pos_frame_timestamps_us),
+            config.joint_position_consistency_window_us,
+        ),
+    });
+    metrics
+        .rx_joint_position_partial_groups_committed_total
+        .fetch_add(1, Ordering::Relaxed);
+    record_missed_feedback_frames(metrics, 0b111, state.joint_pos_group.mask);
+    state.last_committed_joint_pos = Some(joint_pos);
+    reset_pending_joint_position(state);
+    true
+}
+
+/// `FrameCommitPolicy::Timeout`：帧组超过提交窗口仍不完整时，提交已收到的部分
+fn maybe_commit_joint_position_window(
+    ctx: &Arc<PiperContext>,
+    config: &PipelineConfig,
+    state: &mut ParserState,
+    metrics: &Arc<PiperMetrics>,
+) {
+    if config.commit_policy == FrameCommitPolicy::Timeout
+        && state.joint_pos_group.timed_out(Duration::from_micros(config.commit_window_us))
+    {
+        commit_partial_joint_position(ctx, config, state, metrics);
+    }
+}
+
 fn maybe_reset_end_pose_group(
     state: &mut ParserState,
     metrics: &Arc<PiperMetrics>,
@@ -1032,6 +1361,7 @@ fn maybe_reset_end_pose_group(
         metrics
             .rx_end_pose_incomplete_groups_dropped_total
             .fetch_add(1, Ordering::Relaxed);
+        record_missed_feedback_frames(metrics, 0b111, state.end_pose_group.mask);
         reset_pending_end_pose(state);
     }
 }
@@ -1063,12 +1393,14 @@ fn drop_timed_out_motion_groups(
         metrics
             .rx_joint_position_incomplete_groups_dropped_total
             .fetch_add(1, Ordering::Relaxed);
+        record_missed_feedback_frames(metrics, 0b111, state.joint_pos_group.mask);
         reset_pending_joint_position(state);
     }
     if state.end_pose_group.timed_out(timeout) {
         metrics
             .rx_end_pose_incomplete_groups_dropped_total
             .fetch_add(1, Ordering::Relaxed);
+        record_missed_feedback_frames(metrics, 0b111, state.end_pose_group.mask);
         reset_pending_end_pose(state);
     }
     if state.joint_control_group.timed_out(timeout) {
@@ -1116,6 +1448,7 @@ fn commit_pending_velocity(
         }
     } else {
         metrics.rx_joint_dynamic_groups_dropped_total.fetch_add(1, Ordering::Relaxed);
+        record_missed_feedback_frames(metrics, 0b11_1111, commit_mask);
     }
 
     if let Some(message) = warning {
@@ -1125,6 +1458,22 @@ fn commit_pending_velocity(
     reset_pending_velocity(state);
 }
 
+/// 节流地把适配器的 TX Echo 统计拷贝到 metrics（不跟踪 TX Echo 的后端不产生数据）
+fn maybe_publish_tx_echo_stats(
+    rx: &impl RxAdapter,
+    metrics: &PiperMetrics,
+    now_us: u64,
+    next_publish_us: &mut u64,
+) {
+    if now_us < *next_publish_us {
+        return;
+    }
+    *next_publish_us = now_us + TX_ECHO_PUBLISH_INTERVAL_US;
+    if let Some(stats) = rx.tx_echo_stats() {
+        metrics.tx_echo.publish(stats);
+    }
+}
+
 fn flush_pending_velocity_on_idle(
     ctx: &Arc<PiperContext>,
     backend_capability: BackendCapability,
@@ -1194,6 +1543,7 @@ pub(crate) fn io_loop(
             Err(CanError::Timeout) => {
                 // 超时是正常情况，检查各个 pending 状态的年龄
 
+                maybe_commit_joint_position_window(&ctx, &config, &mut state, &metrics);
                 drop_timed_out_motion_groups(&mut state, frame_group_timeout, &metrics);
 
                 // === 检查速度帧缓冲区超时（关键：避免僵尸缓冲区） ===
@@ -1353,6 +1703,7 @@ pub fn rx_loop(
     let mut state = ParserState::new();
 
     let frame_group_timeout = Duration::from_millis(config.frame_group_timeout_ms);
+    let mut next_tx_echo_publish_us = 0;
 
     loop {
         // 检查运行标志
@@ -1373,7 +1724,14 @@ pub fn rx_loop(
             Err(CanError::Timeout) => {
                 // 超时是正常情况，检查各个 pending 状态的年龄
                 metrics.rx_timeouts.fetch_add(1, Ordering::Relaxed);
+                maybe_publish_tx_echo_stats(
+                    &rx,
+                    &metrics,
+                    host_rx_mono_us(),
+                    &mut next_tx_echo_publish_us,
+                );
 
+                maybe_commit_joint_position_window(&ctx, &config, &mut state, &metrics);
                 drop_timed_out_motion_groups(&mut state, frame_group_timeout, &metrics);
 
                 // === 检查速度帧缓冲区超时 ===
@@ -1385,6 +1743,9 @@ pub fn rx_loop(
                     &metrics,
                 );
 
+                // 静默期间也要推进连接健康状态（Degraded / Lost 回调）
+                ctx.connection_monitor.poll_health();
+
                 if load_runtime_phase(&runtime_phase) == RuntimePhase::Running {
                     refresh_maintenance_gate_state(
                         &maintenance_gate,
@@ -1414,6 +1775,7 @@ pub fn rx_loop(
 
                 if is_fatal {
                     error!("RX thread: Fatal error detected, latching runtime fault");
+                    ctx.flight_recorder.capture_fatal(&e, host_rx_mono_us());
                     latch_runtime_fault_with_maintenance(
                         &runtime_phase,
                         &normal_send_gate,
@@ -1432,6 +1794,11 @@ pub fn rx_loop(
         let frame = received.frame;
 
         metrics.rx_frames_valid.fetch_add(1, Ordering::Relaxed);
+        metrics.per_id.record(&frame);
+        let rx_mono_us = host_rx_mono_us();
+        metrics.timing.record_rx(&frame, rx_mono_us);
+        ctx.flight_recorder.record(&frame, RecordedFrameDirection::Rx, rx_mono_us);
+        maybe_publish_tx_echo_stats(&rx, &metrics, rx_mono_us, &mut next_tx_echo_publish_us);
 
         // ============================================================
         // 2. 触发 RX 回调（v1.2.1: 非阻塞，<1μs）
@@ -1463,7 +1830,9 @@ pub fn rx_loop(
         // 会永远基于初始状态判断。
         if parsed.counts_as_robot_feedback {
             ctx.connection_monitor.register_feedback();
+            metrics.timing.record_feedback(rx_mono_us);
         }
+        ctx.connection_monitor.poll_health();
         if parsed.maintenance_gate_may_have_changed
             || maintenance_gate.current_state() == MaintenanceGateState::DeniedTransportDown
         {
@@ -1510,6 +1879,7 @@ pub fn rx_loop(
 /// # 参数
 /// - `tx`: TX 适配器（只写）
 /// - `realtime_slot`: 实时命令邮箱（共享插槽）
+/// - `command_mailbox`: 按 (CAN ID, 优先级) 合并的最新值优先邮箱（次于实时插槽）
 /// - `shutdown_lane`: 单飞急停通道（最高优先级）
 /// - `reliable_rx`: 可靠命令队列接收端（容量 10）
 /// - `workers_running`: worker 生命周期标志
@@ -1522,6 +1892,7 @@ pub(crate) fn tx_loop_mailbox(
     backend_capability: BackendCapability,
     config: PipelineConfig,
     realtime_slot: Arc<std::sync::Mutex<Option<crate::command::RealtimeCommand>>>,
+    command_mailbox: Arc<crate::command::CommandMailbox>,
     soft_realtime_rx: Arc<SoftRealtimeMailbox>,
     shutdown_lane: Arc<ShutdownLane>,
     reliable_rx: Receiver<crate::command::ReliableCommand>,
@@ -1545,7 +1916,15 @@ pub(crate) fn tx_loop_mailbox(
     let mut pending_maintenance_sends = VecDeque::new();
     let mut pending_reliable_commands = VecDeque::new();
     let mut running_idle_backoff_us = TX_IDLE_BACKOFF_MIN_US;
+    // 控制周期序号（实时帧包 / 软实时帧包各计一次），用于 `control_cycle` span
+    let mut control_cycle_seq = 0u64;
     let mut fault_latched_idle_backoff_us = TX_IDLE_BACKOFF_MIN_US;
+    let mut keepalive = CommandKeepalive::new(config.keepalive_rate_hz);
+    let mut rate_limiter = TxRateLimiter::new(
+        config.max_tx_rate_hz,
+        host_rx_mono_us(),
+        metrics.tx_frames_sent_total.load(Ordering::Relaxed),
+    );
 
     loop {
         let phase = load_runtime_phase(&runtime_phase);
@@ -1559,7 +1938,43 @@ pub(crate) fn tx_loop_mailbox(
             break;
         }
 
+        let normal_backlog = sample_command_queue_depths(
+            &shutdown_lane,
+            &realtime_slot,
+            &command_mailbox,
+            &soft_realtime_rx,
+            &reliable_rx,
+            pending_reliable_commands.len(),
+            &metrics,
+        );
+
+        if phase == RuntimePhase::Running
+            && let Some(action) = ctx.watchdog.poll_expired(host_rx_mono_us())
+        {
+            let should_break = trip_watchdog(
+                &mut tx,
+                action,
+                &runtime_phase,
+                &normal_send_gate,
+                &metrics,
+                &ctx,
+                &last_fault,
+                &maintenance_gate,
+                &mut maintenance_tx_state,
+            );
+            if should_break {
+                break;
+            }
+            continue;
+        }
+
         if let Some(dispatch) = shutdown_lane.take_pending() {
+            if let Some(keepalive) = keepalive.as_mut() {
+                keepalive.forget();
+            }
+            if normal_backlog > 0 {
+                metrics.command_queue.record_shutdown_behind_backlog();
+            }
             let should_break = send_shutdown_dispatch(
                 &mut tx,
                 dispatch,
@@ -1581,6 +1996,17 @@ pub(crate) fn tx_loop_mailbox(
             continue;
         }
 
+        // 限速：令牌不足时不取新命令，短暂休眠后回到循环顶部（保证急停帧仍被及时处理）
+        if let Some(limiter) = rate_limiter.as_mut()
+            && let Some(wait) = limiter.wait_before_next(
+                host_rx_mono_us(),
+                metrics.tx_frames_sent_total.load(Ordering::Relaxed),
+            )
+        {
+            spin_sleep::sleep(wait.min(Duration::from_micros(TX_IDLE_BACKOFF_RUNNING_MAX_US)));
+            continue;
+        }
+
         pending_maintenance_sends.extend(drain_maintenance_lane(
             &maintenance_lane_rx,
             &mut maintenance_tx_state,
@@ -1593,6 +2019,9 @@ pub(crate) fn tx_loop_mailbox(
         ));
 
         if let Some(dispatch) = pending_maintenance_sends.pop_front() {
+            if let Some(keepalive) = keepalive.as_mut() {
+                keepalive.forget();
+            }
             if driver_mode.get(Ordering::Acquire).is_replay() {
                 restore_state_transition_gate_after_dispatch(
                     &dispatch,
@@ -1635,6 +2064,7 @@ pub(crate) fn tx_loop_mailbox(
                             match send_control_and_record(
                                 &mut tx,
                                 &ctx,
+                                &metrics,
                                 dispatch.frame,
                                 normal_send_budget,
                             ) {
@@ -1740,8 +2170,13 @@ pub(crate) fn tx_loop_mailbox(
                     Err(denied)
                 } else {
                     maintenance_dispatch_committed(&dispatch);
-                    match send_control_and_record(&mut tx, &ctx, dispatch.frame, normal_send_budget)
-                    {
+                    match send_control_and_record(
+                        &mut tx,
+                        &ctx,
+                        &metrics,
+                        dispatch.frame,
+                        normal_send_budget,
+                    ) {
                         Ok(_) => {
                             soft_deadline_miss_streak = 0;
                             metrics.tx_frames_sent_total.fetch_add(1, Ordering::Relaxed);
@@ -1816,7 +2251,11 @@ pub(crate) fn tx_loop_mailbox(
         }
 
         if phase == RuntimePhase::FaultLatched {
+            if let Some(keepalive) = keepalive.as_mut() {
+                keepalive.forget();
+            }
             abort_realtime_slot_fault(&realtime_slot, &metrics);
+            drop_coalesced_commands(&command_mailbox, &metrics);
             drain_soft_realtime_queue(&soft_realtime_rx, &metrics, true, true);
             drain_reliable_queue(
                 &reliable_rx,
@@ -1838,19 +2277,32 @@ pub(crate) fn tx_loop_mailbox(
         fault_latched_idle_backoff_us = TX_IDLE_BACKOFF_MIN_US;
 
         if driver_mode.get(Ordering::Acquire).is_replay() {
+            if let Some(keepalive) = keepalive.as_mut() {
+                keepalive.forget();
+            }
             reject_replay_mode_dispatches(&realtime_slot, &soft_realtime_rx, &metrics);
+            drop_coalesced_commands(&command_mailbox, &metrics);
         }
 
-        let realtime_command = if backend_capability.is_strict_realtime() {
-            match realtime_slot.lock() {
+        let (realtime_command, from_realtime_slot) = if backend_capability.is_strict_realtime() {
+            let slot_command = match realtime_slot.lock() {
                 Ok(mut slot) => slot.take(),
                 Err(_) => {
                     error!("TX thread: Realtime slot lock poisoned");
                     None
                 },
+            };
+            match slot_command {
+                Some(command) => (Some(command), true),
+                None => (
+                    command_mailbox
+                        .pop(CommandPriority::RealtimeControl)
+                        .map(crate::command::RealtimeCommand::single),
+                    false,
+                ),
             }
         } else {
-            None
+            (None, false)
         };
 
         if let Some(mut command) = realtime_command {
@@ -1858,7 +2310,17 @@ pub(crate) fn tx_loop_mailbox(
             let deadline = command.deadline();
             let mut ack = command.take_ack();
             let frames = command.into_frames();
+            let keepalive_frames =
+                keepalive.as_ref().filter(|_| from_realtime_slot).map(|_| frames.clone());
             let total_frames = frames.len();
+            control_cycle_seq += 1;
+            let _cycle_span = trace_span!(
+                "control_cycle",
+                cycle.seq = control_cycle_seq,
+                cycle.lane = "realtime",
+                cycle.frames = total_frames,
+            )
+            .entered();
             let mut sent_count = 0;
             let mut delivery_error = None;
             let mut transport_error = false;
@@ -1898,12 +2360,22 @@ pub(crate) fn tx_loop_mailbox(
                     committed = true;
                 }
 
-                match send_control_and_record(&mut tx, &ctx, frame, NORMAL_FRAME_SEND_BUDGET) {
+                match send_control_and_record(
+                    &mut tx,
+                    &ctx,
+                    &metrics,
+                    frame,
+                    NORMAL_FRAME_SEND_BUDGET,
+                ) {
                     Ok(_) => {
                         sent_count += 1;
                         metrics.tx_frames_sent_total.fetch_add(1, Ordering::Relaxed);
 
                         if let Some(dispatch) = shutdown_lane.take_pending() {
+                            if sent_count < total_frames {
+                                // 急停等待在未发完的实时帧包之后
+                                metrics.command_queue.record_shutdown_behind_backlog();
+                            }
                             let should_break = send_shutdown_dispatch(
                                 &mut tx,
                                 dispatch,
@@ -1989,6 +2461,16 @@ pub(crate) fn tx_loop_mailbox(
                 count_package_completed(&metrics);
             }
 
+            if let Some(keepalive) = keepalive.as_mut() {
+                match keepalive_frames {
+                    Some(frames) if no_delivery_error && sent_count == total_frames => {
+                        keepalive.record(frames, host_rx_mono_us());
+                    },
+                    Some(_) => keepalive.forget(),
+                    None => {},
+                }
+            }
+
             if transport_error {
                 break;
             }
@@ -2002,6 +2484,17 @@ pub(crate) fn tx_loop_mailbox(
             running_idle_backoff_us = TX_IDLE_BACKOFF_MIN_US;
             let total_frames = command.len();
             let (frames, deadline, ack) = command.into_parts();
+            let keepalive_frames = keepalive
+                .as_ref()
+                .map(|_| frames.iter().copied().collect::<crate::command::FrameBuffer>());
+            control_cycle_seq += 1;
+            let _cycle_span = trace_span!(
+                "control_cycle",
+                cycle.seq = control_cycle_seq,
+                cycle.lane = "soft_realtime",
+                cycle.frames = total_frames,
+            )
+            .entered();
             let mut sent_count = 0usize;
             let mut send_result = Ok(());
             let mut should_break = false;
@@ -2044,7 +2537,7 @@ pub(crate) fn tx_loop_mailbox(
                     break;
                 };
 
-                match send_control_and_record(&mut tx, &ctx, frame, remaining) {
+                match send_control_and_record(&mut tx, &ctx, &metrics, frame, remaining) {
                     Ok(_) => {
                         sent_count += 1;
                         metrics.tx_frames_sent_total.fetch_add(1, Ordering::Relaxed);
@@ -2103,6 +2596,15 @@ pub(crate) fn tx_loop_mailbox(
             } else {
                 crate::command::DeliveryReceipt::none()
             };
+            if let Some(keepalive) = keepalive.as_mut()
+                && let Some(frames) = keepalive_frames
+            {
+                if send_result.is_ok() && sent_count == total_frames {
+                    keepalive.record(frames, host_rx_mono_us());
+                } else {
+                    keepalive.forget();
+                }
+            }
             let _ = ack.send(send_result.map(|_| receipt));
             if deadline_missed {
                 record_soft_deadline_miss(
@@ -2124,10 +2626,21 @@ pub(crate) fn tx_loop_mailbox(
             continue;
         }
 
-        if let Some(command) =
-            pending_reliable_commands.pop_front().or_else(|| reliable_rx.try_recv().ok())
+        // 邮箱中的 `ReliableCommand` 走可靠通道（门控/截止/确认语义与可靠队列一致），
+        // 而不是实时路径
+        if let Some(command) = pending_reliable_commands
+            .pop_front()
+            .or_else(|| reliable_rx.try_recv().ok())
+            .or_else(|| {
+                command_mailbox
+                    .pop_reliable_after_realtime()
+                    .map(crate::command::ReliableCommand::single)
+            })
         {
             running_idle_backoff_us = TX_IDLE_BACKOFF_MIN_US;
+            if let Some(keepalive) = keepalive.as_mut() {
+                keepalive.forget();
+            }
             let total_frames = command.len();
             let package_command = total_frames > 1;
             let (frames, mut ack, kind, commit_point, maintenance, deadline) = command.into_parts();
@@ -2213,7 +2726,7 @@ pub(crate) fn tx_loop_mailbox(
                     committed = true;
                 }
 
-                match send_control_and_record(&mut tx, &ctx, frame, normal_send_budget) {
+                match send_control_and_record(&mut tx, &ctx, &metrics, frame, normal_send_budget) {
                     Ok(_) => {
                         sent_count += 1;
                         metrics.tx_frames_sent_total.fetch_add(1, Ordering::Relaxed);
@@ -2391,6 +2904,41 @@ pub(crate) fn tx_loop_mailbox(
             continue;
         }
 
+        if let Some(keepalive) = keepalive.as_mut()
+            && let Some(frames) = keepalive.due(host_rx_mono_us()).cloned()
+        {
+            match resend_keepalive_package(
+                &mut tx,
+                &frames,
+                normal_send_budget,
+                &normal_send_gate,
+                &metrics,
+                &ctx,
+            ) {
+                Ok(true) => keepalive.record(frames, host_rx_mono_us()),
+                Ok(false) => keepalive.forget(),
+                Err(e) => {
+                    error!("TX thread: Failed to resend keepalive package: {}", e);
+                    keepalive.forget();
+                    if matches!(e, CanError::Timeout) {
+                        metrics.tx_timeouts.fetch_add(1, Ordering::Relaxed);
+                    } else {
+                        metrics.device_errors.fetch_add(1, Ordering::Relaxed);
+                        latch_runtime_fault_with_maintenance(
+                            &runtime_phase,
+                            &normal_send_gate,
+                            &last_fault,
+                            RuntimeFaultKind::TransportError,
+                            &maintenance_gate,
+                            Some(&mut maintenance_tx_state),
+                        );
+                        break;
+                    }
+                },
+            }
+            continue;
+        }
+
         // 都没有数据，避免忙等待
         let (sleep_duration, next_backoff_us) = tx_idle_backoff(
             TX_IDLE_BACKOFF_MIN_US,
@@ -2425,6 +2973,70 @@ pub(crate) fn tx_loop_mailbox(
     trace!("TX thread: loop exited");
 }
 
+/// 重发保活帧包；返回 `Ok(false)` 表示普通发送门已关闭（不再保活）
+fn resend_keepalive_package(
+    tx: &mut impl RealtimeTxAdapter,
+    frames: &crate::command::FrameBuffer,
+    budget: Duration,
+    normal_send_gate: &Arc<NormalSendGate>,
+    metrics: &Arc<PiperMetrics>,
+    ctx: &Arc<PiperContext>,
+) -> Result<bool, CanError> {
+    for frame in frames {
+        let Ok(permit) = normal_send_gate.acquire_normal() else {
+            return Ok(false);
+        };
+        if permit.send_allowed().is_err() {
+            return Ok(false);
+        }
+        send_control_and_record(tx, ctx, metrics, *frame, budget)?;
+        metrics.tx_frames_sent_total.fetch_add(1, Ordering::Relaxed);
+    }
+    metrics.tx_keepalive_resent_total.fetch_add(1, Ordering::Relaxed);
+    Ok(true)
+}
+
+pub(crate) fn drop_coalesced_commands(
+    command_mailbox: &crate::command::CommandMailbox,
+    metrics: &PiperMetrics,
+) {
+    record_coalesced_drops(metrics, command_mailbox.clear());
+}
+
+pub(crate) fn record_coalesced_drops(metrics: &PiperMetrics, [realtime, reliable]: [usize; 2]) {
+    if realtime + reliable > 0 {
+        metrics
+            .tx_coalesced_dropped_total
+            .fetch_add((realtime + reliable) as u64, Ordering::Relaxed);
+        metrics
+            .command_queue
+            .record_coalesced_drop(CommandPriority::RealtimeControl, realtime);
+        metrics
+            .command_queue
+            .record_coalesced_drop(CommandPriority::ReliableCommand, reliable);
+    }
+}
+
+/// 采样各优先级排队深度并写入指标，返回普通控制命令（实时 + 可靠）的积压数
+fn sample_command_queue_depths(
+    shutdown_lane: &ShutdownLane,
+    realtime_slot: &std::sync::Mutex<Option<crate::command::RealtimeCommand>>,
+    command_mailbox: &crate::command::CommandMailbox,
+    soft_realtime_rx: &SoftRealtimeMailbox,
+    reliable_rx: &Receiver<crate::command::ReliableCommand>,
+    pending_reliable: usize,
+    metrics: &PiperMetrics,
+) -> usize {
+    let [coalesced_realtime, coalesced_reliable] = command_mailbox.depths();
+    let realtime_slot_depth = realtime_slot.lock().map_or(0, |slot| usize::from(slot.is_some()));
+    let realtime = realtime_slot_depth + soft_realtime_rx.len() + coalesced_realtime;
+    let reliable = reliable_rx.len() + pending_reliable + coalesced_reliable;
+    metrics
+        .command_queue
+        .set_depths(usize::from(shutdown_lane.has_pending()), realtime, reliable);
+    realtime + reliable
+}
+
 fn abort_realtime_slot_fault(
     realtime_slot: &Arc<std::sync::Mutex<Option<crate::command::RealtimeCommand>>>,
     metrics: &Arc<PiperMetrics>,
@@ -2622,6 +3234,66 @@ fn send_shutdown_dispatch(
     should_break
 }
 
+/// 看门狗到期：锁存故障（丢弃排队中的控制命令）后直接发送停止帧
+///
+/// 最近的低速反馈显示全部关节已失能时不停机。返回 `true` 表示 TX 线程应退出。
+#[allow(clippy::too_many_arguments)]
+fn trip_watchdog(
+    tx: &mut impl RealtimeTxAdapter,
+    action: crate::piper::DropStopAction,
+    runtime_phase: &Arc<AtomicU8>,
+    normal_send_gate: &Arc<NormalSendGate>,
+    metrics: &Arc<PiperMetrics>,
+    ctx: &Arc<PiperContext>,
+    last_fault: &Arc<AtomicU8>,
+    maintenance_gate: &Arc<MaintenanceGate>,
+    maintenance_tx_state: &mut MaintenanceTxState,
+) -> bool {
+    if ctx.joint_driver_low_speed.load().driver_enabled_mask == 0 {
+        debug!("Watchdog expired while all joints are disabled; disarming without stop");
+        return false;
+    }
+
+    error!("Watchdog expired: control thread stopped petting, sending {action:?}");
+    metrics.tx_watchdog_trips_total.fetch_add(1, Ordering::Relaxed);
+    latch_runtime_fault_with_maintenance(
+        runtime_phase,
+        normal_send_gate,
+        last_fault,
+        RuntimeFaultKind::WatchdogExpired,
+        maintenance_gate,
+        Some(maintenance_tx_state),
+    );
+
+    let deadline = Instant::now() + crate::piper::DROP_SAFE_STOP_TIMEOUT;
+    for frame in action.stop_frames() {
+        match send_shutdown_and_record(tx, ctx, frame, deadline) {
+            Ok(()) => {
+                metrics.tx_frames_sent_total.fetch_add(1, Ordering::Relaxed);
+                metrics.tx_shutdown_sent_total.fetch_add(1, Ordering::Relaxed);
+            },
+            Err(e) => {
+                error!("TX thread: Failed to send watchdog stop frame: {}", e);
+                if matches!(e, CanError::Timeout) {
+                    metrics.tx_timeouts.fetch_add(1, Ordering::Relaxed);
+                    return false;
+                }
+                metrics.device_errors.fetch_add(1, Ordering::Relaxed);
+                latch_runtime_fault_with_maintenance(
+                    runtime_phase,
+                    normal_send_gate,
+                    last_fault,
+                    RuntimeFaultKind::TransportError,
+                    maintenance_gate,
+                    Some(maintenance_tx_state),
+                );
+                return true;
+            },
+        }
+    }
+    false
+}
+
 /// 辅助函数：解析帧并更新状态
 ///
 /// 从 `io_loop` 中提取的帧解析逻辑，供 `rx_loop` 复用。
@@ -2670,6 +3342,7 @@ fn parse_and_update_state(
                 let host_rx_mono_us = receive_host_rx_mono_us;
                 let alignment_timestamp_us =
                     group_alignment_timestamp(frame, host_rx_mono_us, backend_capability);
+                maybe_commit_joint_position_window(ctx, config, state, metrics);
                 maybe_reset_joint_position_group(
                     state,
                     metrics,
@@ -2681,6 +3354,8 @@ fn parse_and_update_state(
                 state.pending_joint_pos[1] = feedback.j2_rad();
                 state.joint_pos_group.write_slot(0, alignment_timestamp_us, host_rx_mono_us);
                 state.joint_pos_raw_timings[0] = raw_feedback;
+                state.joint_pos_frame_timestamps_us[0] =
+                    Some(consistency_timestamp_us(received, host_rx_mono_us));
 
                 ctx.publish_raw_joint_position(JointPositionState {
                     hardware_timestamp_us: state.joint_pos_group.max_alignment_timestamp_us(),
@@ -2688,6 +3363,10 @@ fn parse_and_update_state(
                     raw_feedback_timing: newest_raw_feedback_timing(&state.joint_pos_raw_timings),
                     joint_pos: state.pending_joint_pos,
                     frame_valid_mask: state.joint_pos_group.mask,
+                    consistency: StateConsistency::from_span(
+                        frame_timestamp_span_us(&state.joint_pos_frame_timestamps_us),
+                        config.joint_position_consistency_window_us,
+                    ),
                 });
             } else {
                 warn!(
@@ -2702,6 +3381,7 @@ fn parse_and_update_state(
                 let host_rx_mono_us = receive_host_rx_mono_us;
                 let alignment_timestamp_us =
                     group_alignment_timestamp(frame, host_rx_mono_us, backend_capability);
+                maybe_commit_joint_position_window(ctx, config, state, metrics);
                 maybe_reset_joint_position_group(
                     state,
                     metrics,
@@ -2713,6 +3393,8 @@ fn parse_and_update_state(
                 state.pending_joint_pos[3] = feedback.j4_rad();
                 state.joint_pos_group.write_slot(1, alignment_timestamp_us, host_rx_mono_us);
                 state.joint_pos_raw_timings[1] = raw_feedback;
+                state.joint_pos_frame_timestamps_us[1] =
+                    Some(consistency_timestamp_us(received, host_rx_mono_us));
 
                 ctx.publish_raw_joint_position(JointPositionState {
                     hardware_timestamp_us: state.joint_pos_group.max_alignment_timestamp_us(),
@@ -2720,6 +3402,10 @@ fn parse_and_update_state(
                     raw_feedback_timing: newest_raw_feedback_timing(&state.joint_pos_raw_timings),
                     joint_pos: state.pending_joint_pos,
                     frame_valid_mask: state.joint_pos_group.mask,
+                    consistency: StateConsistency::from_span(
+                        frame_timestamp_span_us(&state.joint_pos_frame_timestamps_us),
+                        config.joint_position_consistency_window_us,
+                    ),
                 });
             } else {
                 warn!(
@@ -2734,6 +3420,7 @@ fn parse_and_update_state(
                 let host_rx_mono_us = receive_host_rx_mono_us;
                 let alignment_timestamp_us =
                     group_alignment_timestamp(frame, host_rx_mono_us, backend_capability);
+                maybe_commit_joint_position_window(ctx, config, state, metrics);
                 maybe_reset_joint_position_group(
                     state,
                     metrics,
@@ -2745,6 +3432,8 @@ fn parse_and_update_state(
                 state.pending_joint_pos[5] = feedback.j6_rad();
                 state.joint_pos_group.write_slot(2, alignment_timestamp_us, host_rx_mono_us);
                 state.joint_pos_raw_timings[2] = raw_feedback;
+                state.joint_pos_frame_timestamps_us[2] =
+                    Some(consistency_timestamp_us(received, host_rx_mono_us));
 
                 let new_joint_pos_state = JointPositionState {
                     hardware_timestamp_us: state.joint_pos_group.max_alignment_timestamp_us(),
@@ -2752,6 +3441,10 @@ fn parse_and_update_state(
                     raw_feedback_timing: newest_raw_feedback_timing(&state.joint_pos_raw_timings),
                     joint_pos: state.pending_joint_pos,
                     frame_valid_mask: state.joint_pos_group.mask,
+                    consistency: StateConsistency::from_span(
+                        frame_timestamp_span_us(&state.joint_pos_frame_timestamps_us),
+                        config.joint_position_consistency_window_us,
+                    ),
                 };
                 if complete_group_ready(state.joint_pos_group.mask) {
                     ctx.publish_joint_position(new_joint_pos_state);
@@ -2772,7 +3465,18 @@ fn parse_and_update_state(
                             .rx_joint_position_control_grade_rejected_total
                             .fetch_add(1, Ordering::Relaxed);
                     }
+                    state.last_committed_joint_pos = Some(state.pending_joint_pos);
                     reset_pending_joint_position(state);
+                } else if config.commit_policy == FrameCommitPolicy::OnLast {
+                    // 0x2A7 总是结束本周期：无法提交时丢弃不完整帧组
+                    if !commit_partial_joint_position(ctx, config, state, metrics) {
+                        ctx.publish_raw_joint_position(new_joint_pos_state);
+                        metrics
+                            .rx_joint_position_incomplete_groups_dropped_total
+                            .fetch_add(1, Ordering::Relaxed);
+                        record_missed_feedback_frames(metrics, 0b111, state.joint_pos_group.mask);
+                        reset_pending_joint_position(state);
+                    }
                 } else {
                     ctx.publish_raw_joint_position(new_joint_pos_state);
                 }
@@ -3029,6 +3733,7 @@ fn parse_and_update_state(
                 };
 
                 ctx.robot_control.store(Arc::new(new_robot_control_state.clone()));
+                ctx.robot_fault.observe(feedback.robot_status);
                 ctx.fps_stats
                     .load()
                     .robot_control_updates
@@ -3730,7 +4435,15 @@ mod tests {
         let command_frame =
             PiperFrame::new_standard(0x1A1, [1, 2, 3, 4]).unwrap().with_timestamp_us(88_000);
 
-        send_control_and_record(&mut tx, &ctx, command_frame, Duration::from_millis(1)).unwrap();
+        let metrics = PiperMetrics::new();
+        send_control_and_record(
+            &mut tx,
+            &ctx,
+            &metrics,
+            command_frame,
+            Duration::from_millis(1),
+        )
+        .unwrap();
 
         assert_eq!(tx.sent_control.len(), 1);
         assert_eq!(
@@ -3767,6 +4480,13 @@ mod tests {
             frame_group_timeout_ms: 20,
             velocity_buffer_timeout_us: 10_000,
             low_speed_drive_state_freshness_ms: 250,
+            joint_position_consistency_window_us: 5_000,
+            commit_policy: FrameCommitPolicy::OnLast,
+            commit_window_us: 2_000,
+            max_tx_rate_hz: Some(2_000),
+            flight_recorder_capacity: 0,
+            watchdog: None,
+            keepalive_rate_hz: None,
         };
         assert_eq!(config.receive_timeout_ms, 5);
         assert_eq!(config.frame_group_timeout_ms, 20);
@@ -3774,6 +4494,52 @@ mod tests {
         assert_eq!(config.low_speed_drive_state_freshness_ms, 250);
     }
 
+    #[test]
+    fn test_command_keepalive_is_due_only_after_period_and_until_forgotten() {
+        assert!(CommandKeepalive::new(None).is_none());
+        assert!(CommandKeepalive::new(Some(0)).is_none());
+
+        let mut keepalive = CommandKeepalive::new(Some(100)).unwrap();
+        assert!(keepalive.due(u64::MAX).is_none(), "nothing recorded yet");
+
+        let package: crate::command::FrameBuffer =
+            [PiperFrame::new_standard(0x15A, [0x01]).unwrap()].into_iter().collect();
+        keepalive.record(package.clone(), 1_000);
+        assert!(keepalive.due(10_999).is_none());
+        assert_eq!(keepalive.due(11_000), Some(&package));
+
+        keepalive.forget();
+        assert!(keepalive.due(u64::MAX).is_none());
+    }
+
+    #[test]
+    fn test_tx_rate_limiter_paces_after_burst_and_allows_package_debt() {
+        assert!(TxRateLimiter::new(None, 1, 0).is_none());
+        assert!(TxRateLimiter::new(Some(0), 1, 0).is_none());
+
+        // 1000 Hz：每帧 1ms
+        let mut limiter = TxRateLimiter::new(Some(1_000), 1_000, 10).unwrap();
+        assert_eq!(limiter.wait_before_next(1_000, 10), None);
+
+        // 一次性发出 6 帧的帧包后令牌为 0，需要等待 1 帧的时间
+        let wait = limiter.wait_before_next(1_000, 16).unwrap();
+        assert_eq!(wait, Duration::from_millis(1));
+        assert_eq!(limiter.wait_before_next(2_000, 16), None);
+
+        // 透支：再发 6 帧后欠 5 个令牌，需等待 6ms
+        let wait = limiter.wait_before_next(2_000, 22).unwrap();
+        assert_eq!(wait, Duration::from_millis(6));
+        assert_eq!(limiter.wait_before_next(8_000, 22), None);
+
+        // 长时间空闲后令牌不超过突发容量
+        assert_eq!(limiter.wait_before_next(1_000_000, 22), None);
+        assert!(limiter.wait_before_next(1_000_000, 28).is_some());
+
+        // 指标 reset 导致计数回退时不会扣令牌
+        let mut limiter = TxRateLimiter::new(Some(1_000), 0, 100).unwrap();
+        assert_eq!(limiter.wait_before_next(0, 0), None);
+    }
+
     #[test]
     fn test_tx_idle_backoff_grows_and_saturates() {
         let mut current = TX_IDLE_BACKOFF_MIN_US;
@@ -4110,6 +4876,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn feedback_replayer_yields_one_state_per_complete_feedback_cycle() {
+        let mut replayer = FeedbackReplayer::default();
+        let mut states = Vec::new();
+        for cycle in 0..2u64 {
+            let base_us = 10_000 + cycle * 5_000;
+            let mut frames = vec![
+                joint_feedback_frame(ID_JOINT_FEEDBACK_12, 10.0, 20.0, base_us),
+                joint_feedback_frame(ID_JOINT_FEEDBACK_34, 30.0, 40.0, base_us + 100),
+                joint_feedback_frame(ID_JOINT_FEEDBACK_56, 50.0, 60.0, base_us + 200),
+            ];
+            frames.extend((1..=6).map(|joint_index| {
+                joint_driver_high_speed_frame(joint_index, base_us + 300 + u64::from(joint_index))
+            }));
+            for frame in frames {
+                states.extend(replayer.feed(frame));
+            }
+        }
+
+        assert_eq!(states.len(), 2);
+        assert_eq!(states[0].position_timestamp_us, 10_200);
+        assert_eq!(states[1].position_timestamp_us, 15_200);
+        assert!((states[1].joint_pos[5] - 60.0_f64.to_radians()).abs() < 1e-9);
+        assert!(states.iter().all(AlignedMotionState::is_complete));
+    }
+
     #[test]
     fn test_joint_pos_frame_commit_complete() {
         let ctx = Arc::new(PiperContext::new());
@@ -4221,6 +5013,260 @@ mod tests {
         // 这个测试主要验证不会崩溃
     }
 
+    fn parse_joint_position_group(
+        ctx: &Arc<PiperContext>,
+        config: &PipelineConfig,
+        timestamps_us: [u64; 3],
+    ) {
+        let metrics = Arc::new(PiperMetrics::new());
+        let mut state = ParserState::new();
+        for (id, timestamp_us) in [
+            ID_JOINT_FEEDBACK_12,
+            ID_JOINT_FEEDBACK_34,
+            ID_JOINT_FEEDBACK_56,
+        ]
+        .into_iter()
+        .zip(timestamps_us)
+        {
+            parse_frame_for_test(
+                ctx,
+                &mut state,
+                &metrics,
+                config,
+                joint_feedback_frame(id, 1.0, 2.0, timestamp_us),
+            );
+        }
+    }
+
+    #[test]
+    fn test_joint_position_group_within_window_is_consistent() {
+        let ctx = Arc::new(PiperContext::new());
+        parse_joint_position_group(&ctx, &PipelineConfig::default(), [1_000, 1_400, 1_800]);
+
+        let snapshot = ctx.capture_joint_position_monitor_snapshot();
+        let complete = snapshot.latest_complete().expect("complete group should publish");
+        assert_eq!(complete.consistency, StateConsistency::Consistent);
+        assert!(complete.consistency.is_consistent());
+    }
+
+    #[test]
+    fn test_joint_position_group_flags_torn_snapshot_outside_window() {
+        let ctx = Arc::new(PiperContext::new());
+        let config = PipelineConfig {
+            joint_position_consistency_window_us: 500,
+            ..PipelineConfig::default()
+        };
+        parse_joint_position_group(&ctx, &config, [1_000, 1_100, 1_900]);
+
+        let snapshot = ctx.capture_joint_position_monitor_snapshot();
+        let complete = snapshot.latest_complete().expect("complete group should publish");
+        assert_eq!(
+            complete.consistency,
+            StateConsistency::Torn { span_us: 900 }
+        );
+        assert!(!complete.consistency.is_consistent());
+    }
+
+    #[test]
+    fn test_joint_position_group_without_frame_timestamps_uses_host_rx_time() {
+        let ctx = Arc::new(PiperContext::new());
+        let metrics = Arc::new(PiperMetrics::new());
+        let mut state = ParserState::new();
+        let config = PipelineConfig {
+            joint_position_consistency_window_us: 500,
+            ..PipelineConfig::default()
+        };
+        for (id, host_rx_mono_us) in [
+            ID_JOINT_FEEDBACK_12,
+            ID_JOINT_FEEDBACK_34,
+            ID_JOINT_FEEDBACK_56,
+        ]
+        .into_iter()
+        .zip([10_000, 10_100, 10_900])
+        {
+            // 后端不提供帧时间戳：timestamp_us 为 0、来源为 None
+            let received = piper_can::ReceivedFrame::new(
+                joint_feedback_frame(id, 1.0, 2.0, 0),
+                piper_can::TimestampProvenance::None,
+            )
+            .with_raw_timestamp(piper_can::RawTimestampInfo {
+                can_id: u32::from(id.raw()),
+                host_rx_mono_us,
+                system_ts_us: None,
+                hw_trans_us: None,
+                hw_raw_us: None,
+            });
+            parse_and_update_state(
+                &received,
+                BackendCapability::SoftRealtime,
+                &ctx,
+                &config,
+                &mut state,
+                &metrics,
+            );
+        }
+
+        let snapshot = ctx.capture_joint_position_monitor_snapshot();
+        let complete = snapshot.latest_complete().expect("complete group should publish");
+        assert_eq!(
+            complete.consistency,
+            StateConsistency::Torn { span_us: 900 }
+        );
+    }
+
+    fn parse_complete_joint_position_group(
+        ctx: &Arc<PiperContext>,
+        state: &mut ParserState,
+        metrics: &Arc<PiperMetrics>,
+        config: &PipelineConfig,
+    ) {
+        for (id, first, second, timestamp_us) in [
+            (ID_JOINT_FEEDBACK_12, 1.0, 2.0, 1_000),
+            (ID_JOINT_FEEDBACK_34, 3.0, 4.0, 1_100),
+            (ID_JOINT_FEEDBACK_56, 5.0, 6.0, 1_200),
+        ] {
+            parse_frame_for_test(
+                ctx,
+                state,
+                metrics,
+                config,
+                joint_feedback_frame(id, first, second, timestamp_us),
+            );
+        }
+    }
+
+    #[test]
+    fn test_on_last_commit_policy_commits_partial_group_with_previous_joints() {
+        let ctx = Arc::new(PiperContext::new());
+        let metrics = Arc::new(PiperMetrics::new());
+        let config = PipelineConfig {
+            commit_policy: FrameCommitPolicy::OnLast,
+            ..PipelineConfig::default()
+        };
+        let mut state = ParserState::new();
+
+        parse_frame_for_test(
+            &ctx,
+            &mut state,
+            &metrics,
+            &config,
+            joint_feedback_frame(ID_JOINT_FEEDBACK_56, 50.0, 60.0, 500),
+        );
+        assert!(
+            ctx.capture_joint_position_monitor_snapshot().latest_complete().is_none(),
+            "no partial commit before the first complete group"
+        );
+
+        parse_complete_joint_position_group(&ctx, &mut state, &metrics, &config);
+        let control_before = ctx.capture_control_pair().joint_position.hardware_timestamp_us;
+        parse_frame_for_test(
+            &ctx,
+            &mut state,
+            &metrics,
+            &config,
+            joint_feedback_frame(ID_JOINT_FEEDBACK_34, 30.0, 40.0, 6_000),
+        );
+        parse_frame_for_test(
+            &ctx,
+            &mut state,
+            &metrics,
+            &config,
+            joint_feedback_frame(ID_JOINT_FEEDBACK_56, 50.0, 60.0, 6_100),
+        );
+
+        let snapshot = ctx.capture_joint_position_monitor_snapshot();
+        let complete = snapshot.latest_complete().expect("partial group should commit on 0x2A7");
+        assert_eq!(complete.frame_valid_mask, 0b110);
+        assert_eq!(complete.missed_ids(), vec![ID_JOINT_FEEDBACK_12]);
+        assert!((complete.joint_pos[0] - 1.0f64.to_radians()).abs() < 1e-3);
+        assert!((complete.joint_pos[2] - 30.0f64.to_radians()).abs() < 1e-3);
+        assert!((complete.joint_pos[5] - 60.0f64.to_radians()).abs() < 1e-3);
+        assert_eq!(
+            ctx.capture_control_pair().joint_position.hardware_timestamp_us,
+            control_before,
+            "partial groups never become control-grade"
+        );
+        assert_eq!(
+            metrics.rx_joint_position_partial_groups_committed_total.load(Ordering::Relaxed),
+            1
+        );
+        // 首个孤立 0x2A7 帧组被丢弃（缺 2 帧），之后的部分帧组缺 1 帧
+        assert_eq!(
+            metrics.rx_feedback_frames_missed_total.load(Ordering::Relaxed),
+            3
+        );
+    }
+
+    #[test]
+    fn test_timeout_commit_policy_commits_partial_group_after_window() {
+        let ctx = Arc::new(PiperContext::new());
+        let metrics = Arc::new(PiperMetrics::new());
+        let config = PipelineConfig {
+            commit_policy: FrameCommitPolicy::Timeout,
+            commit_window_us: 1_000,
+            frame_group_timeout_ms: 1_000,
+            ..PipelineConfig::default()
+        };
+        let mut state = ParserState::new();
+
+        parse_complete_joint_position_group(&ctx, &mut state, &metrics, &config);
+        parse_frame_for_test(
+            &ctx,
+            &mut state,
+            &metrics,
+            &config,
+            joint_feedback_frame(ID_JOINT_FEEDBACK_12, 10.0, 20.0, 6_000),
+        );
+        maybe_commit_joint_position_window(&ctx, &config, &mut state, &metrics);
+        assert_eq!(
+            ctx.capture_joint_position_monitor_snapshot()
+                .latest_complete()
+                .map(|complete| complete.frame_valid_mask),
+            Some(0b111),
+            "window has not elapsed yet"
+        );
+
+        std::thread::sleep(Duration::from_millis(5));
+        maybe_commit_joint_position_window(&ctx, &config, &mut state, &metrics);
+
+        let snapshot = ctx.capture_joint_position_monitor_snapshot();
+        let complete = snapshot.latest_complete().expect("complete snapshot");
+        assert_eq!(complete.frame_valid_mask, 0b001);
+        assert!((complete.joint_pos[0] - 10.0f64.to_radians()).abs() < 1e-3);
+        assert!((complete.joint_pos[4] - 5.0f64.to_radians()).abs() < 1e-3);
+        assert!(state.joint_pos_group.is_empty());
+        assert_eq!(
+            metrics.rx_joint_position_partial_groups_committed_total.load(Ordering::Relaxed),
+            1
+        );
+    }
+
+    #[test]
+    fn test_joint_position_partial_group_consistency_ignores_missing_slots() {
+        let ctx = Arc::new(PiperContext::new());
+        let metrics = Arc::new(PiperMetrics::new());
+        let config = PipelineConfig {
+            joint_position_consistency_window_us: 500,
+            ..PipelineConfig::default()
+        };
+        let mut state = ParserState::new();
+
+        parse_frame_for_test(
+            &ctx,
+            &mut state,
+            &metrics,
+            &config,
+            joint_feedback_frame(ID_JOINT_FEEDBACK_34, 3.0, 4.0, 1_000),
+        );
+
+        let snapshot = ctx.capture_joint_position_monitor_snapshot();
+        assert_eq!(snapshot.latest_raw().frame_valid_mask, 0b010);
+        assert_eq!(
+            snapshot.latest_raw().consistency,
+            StateConsistency::Consistent
+        );
+    }
+
     #[test]
     fn test_joint_position_group_rejects_stale_partial_after_missing_first_frame() {
         let ctx = Arc::new(PiperContext::new());
@@ -5094,4 +6140,55 @@ mod tests {
             1
         );
     }
+
+    #[test]
+    fn rx_thread_publishes_adapter_tx_echo_stats_into_metrics_with_throttling() {
+        struct EchoTrackingRx(piper_can::TxEchoStats);
+        struct PlainRx;
+
+        impl RxAdapter for PlainRx {
+            fn receive(&mut self) -> Result<piper_can::ReceivedFrame, CanError> {
+                Err(CanError::Timeout)
+            }
+        }
+
+        impl RxAdapter for EchoTrackingRx {
+            fn receive(&mut self) -> Result<piper_can::ReceivedFrame, CanError> {
+                Err(CanError::Timeout)
+            }
+
+            fn tx_echo_stats(&self) -> Option<piper_can::TxEchoStats> {
+                Some(self.0)
+            }
+        }
+
+        let metrics = PiperMetrics::new();
+        let mut next_publish_us = 0;
+        let mut rx = EchoTrackingRx(piper_can::TxEchoStats {
+            completed: 10,
+            max_latency_us: 900,
+            ..Default::default()
+        });
+
+        maybe_publish_tx_echo_stats(&rx, &metrics, 1_000, &mut next_publish_us);
+        assert_eq!(metrics.snapshot().tx_echo, Some(rx.0));
+
+        // 周期内不重复拷贝
+        rx.0.completed = 11;
+        maybe_publish_tx_echo_stats(&rx, &metrics, 50_000, &mut next_publish_us);
+        assert_eq!(metrics.snapshot().tx_echo.unwrap().completed, 10);
+        maybe_publish_tx_echo_stats(
+            &rx,
+            &metrics,
+            1_000 + TX_ECHO_PUBLISH_INTERVAL_US,
+            &mut next_publish_us,
+        );
+        assert_eq!(metrics.snapshot().tx_echo.unwrap().completed, 11);
+
+        // 不跟踪 TX Echo 的后端不产生数据
+        let metrics = PiperMetrics::new();
+        let mut next_publish_us = 0;
+        maybe_publish_tx_echo_stats(&PlainRx, &metrics, 1_000, &mut next_publish_us);
+        assert_eq!(metrics.snapshot().tx_echo, None);
+    }
 }
@@ -5,9 +5,10 @@
 use crate::ProtocolDiagnostic;
 use crate::WaitError;
 use crate::command::{
-    CommandPriority, DeliveryPhase, DeliveryReceipt, MaintenanceCommandMeta, PiperCommand,
-    RealtimeCommand, ReliableCommand, ReliableCommandKind, SoftRealtimeCommand,
-    SoftRealtimeMailbox, SoftRealtimeTryReserveError, SoftRealtimeTrySendError,
+    CommandMailbox, CommandMailboxPush, CommandPriority, DeliveryPhase, DeliveryReceipt,
+    MaintenanceCommandMeta, PiperCommand, RealtimeCommand, ReliableCommand, ReliableCommandKind,
+    SoftRealtimeCommand, SoftRealtimeMailbox, SoftRealtimeTryReserveError,
+    SoftRealtimeTrySendError,
 };
 use crate::diagnostics::{DiagnosticEvent, QueryDiagnostic};
 use crate::error::DriverError;
@@ -21,6 +22,7 @@ use crossbeam_channel::{Receiver, Sender};
 use piper_can::{
     BackendCapability, CanError, PiperFrame, RealtimeTxAdapter, RxAdapter, SplittableAdapter,
 };
+use piper_protocol::feedback::RobotStatus;
 use std::mem::ManuallyDrop;
 use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU32, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Condvar, Mutex};
@@ -83,6 +85,8 @@ pub enum RuntimeFaultKind {
     TxExited = 2,
     TransportError = 3,
     ManualFault = 4,
+    /// 软件看门狗到期（控制线程未按时喂狗）
+    WatchdogExpired = 5,
 }
 
 impl RuntimeFaultKind {
@@ -92,6 +96,7 @@ impl RuntimeFaultKind {
             2 => Some(Self::TxExited),
             3 => Some(Self::TransportError),
             4 => Some(Self::ManualFault),
+            5 => Some(Self::WatchdogExpired),
             _ => None,
         }
     }
@@ -116,6 +121,59 @@ pub(crate) enum RuntimePhase {
     Stopping = 2,
 }
 
+/// Driver 被 drop（包括 panic 展开）时发送的安全停止动作。
+///
+/// 仅当最近一次低速反馈显示仍有驱动器处于使能状态时才会发送，
+/// 通过急停通道（shutdown lane）有界等待 [`DROP_SAFE_STOP_TIMEOUT`]。
+///
+/// 默认为 [`DropStopAction::None`]：只读/旁路的 driver（监控、录制、bridge host）
+/// 不应在 drop 时失能由其他进程控制的机械臂。控制端（如 `piper-client`）
+/// 在进入会下发运动命令的状态时自行设置该动作。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum DropStopAction {
+    /// 不发送任何帧，只停止 IO 线程（默认）。
+    #[default]
+    None = 0,
+    /// 对 6 个关节发送零增益、零力矩的 MIT 控制帧。
+    MitZero = 1,
+    /// 失能全部电机。
+    Disable = 2,
+    /// 发送急停帧。
+    EmergencyStop = 3,
+}
+
+impl DropStopAction {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::None,
+            1 => Self::MitZero,
+            3 => Self::EmergencyStop,
+            _ => Self::Disable,
+        }
+    }
+
+    /// 该动作对应的停止帧（`None` 为空）
+    pub(crate) fn stop_frames(self) -> Vec<PiperFrame> {
+        use piper_protocol::control::{
+            EmergencyStopCommand, MitControlCommand, MotorEnableCommand,
+        };
+
+        match self {
+            Self::None => Vec::new(),
+            Self::MitZero => (1..=6u8)
+                .filter_map(|joint| MitControlCommand::try_new(joint, 0.0, 0.0, 0.0, 0.0, 0.0).ok())
+                .map(|command| command.to_frame())
+                .collect(),
+            Self::Disable => vec![MotorEnableCommand::disable_all().to_frame()],
+            Self::EmergencyStop => vec![EmergencyStopCommand::emergency_stop().to_frame()],
+        }
+    }
+}
+
+/// Drop 安全停止帧的总等待预算。
+pub const DROP_SAFE_STOP_TIMEOUT: Duration = Duration::from_millis(200);
+
 pub(crate) const NORMAL_FRAME_SEND_BUDGET: Duration = Duration::from_micros(500);
 pub(crate) const SOFT_CONTROL_SEND_BUDGET: Duration = Duration::from_millis(5);
 pub(crate) const SOFT_DEADLINE_MISS_FAULT_THRESHOLD: u32 = 3;
@@ -890,6 +948,7 @@ pub enum MaintenanceRevocationReason {
     ControllerStateChanged,
     SessionReplaced,
     SessionClosed,
+    IdleTimeout,
 }
 
 #[doc(hidden)]
@@ -1761,6 +1820,16 @@ fn clone_driver_error(error: &DriverError) -> DriverError {
             DriverError::MaintenanceWriteDenied(message.clone())
         },
         DriverError::RealtimeDeliveryTimeout => DriverError::RealtimeDeliveryTimeout,
+        DriverError::UnsupportedByFirmware {
+            feature,
+            required,
+            detected,
+        } => DriverError::UnsupportedByFirmware {
+            feature: *feature,
+            required: *required,
+            detected: *detected,
+        },
+        DriverError::RobotFault(status) => DriverError::RobotFault(*status),
     }
 }
 
@@ -1807,6 +1876,17 @@ fn clone_protocol_error(error: &piper_protocol::ProtocolError) -> piper_protocol
             min: *min,
             max: *max,
         },
+        piper_protocol::ProtocolError::ValueOutOfRange {
+            field,
+            value,
+            min,
+            max,
+        } => piper_protocol::ProtocolError::ValueOutOfRange {
+            field,
+            value: *value,
+            min: *min,
+            max: *max,
+        },
         piper_protocol::ProtocolError::ParseError(message) => {
             piper_protocol::ProtocolError::ParseError(message.clone())
         },
@@ -1841,6 +1921,8 @@ pub struct Piper {
     shutdown_lane: Arc<ShutdownLane>,
     /// 实时命令插槽（邮箱模式，Overwrite）
     realtime_slot: Arc<std::sync::Mutex<Option<RealtimeCommand>>>,
+    /// 按 (CAN ID, 优先级) 合并的最新值优先邮箱
+    command_mailbox: Arc<CommandMailbox>,
     /// 共享状态上下文
     ctx: Arc<PiperContext>,
     /// 统一管理的 worker 句柄。
@@ -1878,6 +1960,8 @@ pub struct Piper {
     soft_realtime_post_check_barrier: Mutex<Option<SoftRealtimeAdmissionBarrier>>,
     /// Capability of the active backend.
     backend_capability: BackendCapability,
+    /// Drop 时的安全停止动作（[`DropStopAction`]）。
+    drop_stop_action: AtomicU8,
 }
 
 impl Piper {
@@ -2239,12 +2323,18 @@ impl Piper {
     ) -> Result<Self, CanError> {
         let pipeline_config = config.unwrap_or_default();
         let realtime_slot = Arc::new(std::sync::Mutex::new(None::<RealtimeCommand>));
+        let command_mailbox = Arc::new(CommandMailbox::new());
         let (reliable_tx, reliable_rx) = crossbeam_channel::bounded::<ReliableCommand>(10);
         let soft_realtime_tx = Arc::new(SoftRealtimeMailbox::new());
         let soft_realtime_rx = soft_realtime_tx.clone();
         let shutdown_lane = Arc::new(ShutdownLane::new());
         let metrics = Arc::new(PiperMetrics::new());
-        let ctx = Arc::new(PiperContext::with_metrics(metrics.clone()));
+        crate::metrics::spawn_per_id_fps_sampler(&metrics);
+        let ctx = Arc::new(
+            PiperContext::with_metrics(metrics.clone())
+                .with_flight_recorder_capacity(pipeline_config.flight_recorder_capacity)
+                .with_watchdog(pipeline_config.watchdog),
+        );
         let workers_running = Arc::new(AtomicBool::new(true));
         let runtime_phase = Arc::new(AtomicU8::new(RuntimePhase::Running as u8));
         let normal_send_gate = Arc::new(NormalSendGate::new());
@@ -2290,6 +2380,7 @@ impl Piper {
         let normal_send_gate_tx = normal_send_gate.clone();
         let metrics_tx = metrics.clone();
         let realtime_slot_tx = realtime_slot.clone();
+        let command_mailbox_tx = command_mailbox.clone();
         let runtime_fault_tx = runtime_fault.clone();
         let shutdown_lane_tx = shutdown_lane.clone();
         let maintenance_gate_tx = maintenance_gate.clone();
@@ -2303,6 +2394,7 @@ impl Piper {
                 backend_capability_tx,
                 config_tx,
                 realtime_slot_tx,
+                command_mailbox_tx,
                 soft_realtime_rx,
                 shutdown_lane_tx,
                 reliable_rx,
@@ -2324,6 +2416,7 @@ impl Piper {
             soft_realtime_tx,
             shutdown_lane,
             realtime_slot,
+            command_mailbox,
             ctx,
             workers: RuntimeWorkers {
                 rx_thread: Some(rx_thread),
@@ -2347,6 +2440,7 @@ impl Piper {
             #[cfg(test)]
             soft_realtime_post_check_barrier: Mutex::new(None),
             backend_capability,
+            drop_stop_action: AtomicU8::new(DropStopAction::default() as u8),
         })
     }
 
@@ -2644,6 +2738,83 @@ impl Piper {
         }
     }
 
+    /// 设置 Driver 被 drop 时的安全停止动作
+    ///
+    /// 默认为 [`DropStopAction::None`]。
+    pub fn set_drop_stop_action(&self, action: DropStopAction) {
+        self.drop_stop_action.store(action as u8, Ordering::Release);
+    }
+
+    /// 获取 Driver 被 drop 时的安全停止动作
+    pub fn drop_stop_action(&self) -> DropStopAction {
+        DropStopAction::from_u8(self.drop_stop_action.load(Ordering::Acquire))
+    }
+
+    /// 喂软件看门狗（见 [`PipelineConfig::watchdog`]）
+    ///
+    /// 第一次调用后看门狗开始计时；未配置看门狗时为空操作。
+    pub fn pet_watchdog(&self) {
+        self.ctx.watchdog.pet(crate::heartbeat::monotonic_micros());
+    }
+
+    /// 解除看门狗计时，直到下一次 [`Piper::pet_watchdog`]
+    ///
+    /// 用于有意暂停控制循环（例如切换到位置模式等待）之前。
+    pub fn disarm_watchdog(&self) {
+        self.ctx.watchdog.disarm();
+    }
+
+    /// 当前状态代数（每次完整/控制级状态提交加一）
+    pub fn state_generation(&self) -> u64 {
+        self.ctx.state_updates.generation()
+    }
+
+    /// 阻塞等待状态代数超过 `since`，返回新代数；`timeout` 内无新提交返回 `None`
+    ///
+    /// 用于 UI 等只需在状态变化时刷新的线程，避免高频轮询。
+    pub fn wait_for_state_update(&self, since: u64, timeout: Duration) -> Option<u64> {
+        self.ctx.state_updates.wait_since(since, timeout)
+    }
+
+    /// Drop 时的最后一道安全停止：机械臂可能仍处于使能状态时，
+    /// 在关闭 IO 线程之前经急停通道发送配置的停止帧。
+    fn safe_stop_on_drop(&self, timeout: Duration) {
+        let action = self.drop_stop_action();
+        if action == DropStopAction::None
+            || self.runtime_phase() == RuntimePhase::Stopping
+            || self.ctx.joint_driver_low_speed.load().driver_enabled_mask == 0
+        {
+            return;
+        }
+        if !self.tx_thread_alive() || !self.shutdown_lane_open() {
+            self.metrics.tx_drop_shutdown_skipped_total.fetch_add(1, Ordering::Relaxed);
+            warn!("Drop safe-stop ({action:?}) skipped because TX worker is no longer alive");
+            return;
+        }
+
+        let frames = action.stop_frames();
+
+        self.metrics.tx_drop_shutdown_attempt_total.fetch_add(1, Ordering::Relaxed);
+        let deadline = Instant::now() + timeout;
+        for frame in frames {
+            match self.enqueue_shutdown(frame, deadline).and_then(|receipt| receipt.wait()) {
+                Ok(()) => {},
+                Err(DriverError::Timeout) => {
+                    self.metrics.tx_drop_shutdown_timeout_total.fetch_add(1, Ordering::Relaxed);
+                    warn!("Drop safe-stop ({action:?}) timed out after {timeout:?}");
+                    return;
+                },
+                Err(error) => {
+                    self.metrics.tx_drop_shutdown_skipped_total.fetch_add(1, Ordering::Relaxed);
+                    warn!("Drop safe-stop ({action:?}) failed before completion: {error}");
+                    return;
+                },
+            }
+        }
+        self.metrics.tx_drop_shutdown_success_total.fetch_add(1, Ordering::Relaxed);
+        info!("Drop safe-stop sent {action:?} through shutdown lane");
+    }
+
     fn best_effort_fault_shutdown_on_drop(&self, shutdown_timeout: Duration) {
         use piper_protocol::control::EmergencyStopCommand;
 
@@ -2687,6 +2858,24 @@ impl Piper {
         self.metrics.snapshot()
     }
 
+    /// 获取各 CAN ID 的 RX 帧率（Hz，1 秒滑动窗口）
+    ///
+    /// 见 [`PiperMetrics::per_id_fps`]。
+    pub fn get_per_id_fps(&self) -> std::collections::HashMap<u32, f32> {
+        self.metrics.per_id_fps()
+    }
+
+    /// 以 Prometheus 文本格式导出当前指标
+    ///
+    /// 带 `interface` 标签，并包含按 CAN ID 的 `piper_fps`。
+    pub fn get_metrics_prometheus(&self) -> String {
+        let per_id_fps = self.metrics.per_id_fps();
+        self.metrics.snapshot().to_prometheus_with(crate::metrics::PrometheusLabels {
+            interface: Some(&self.interface),
+            per_id_fps: Some(&per_id_fps),
+        })
+    }
+
     /// 获取重建观察族的专用指标快照。
     pub fn get_observation_metrics(&self) -> ObservationMetrics {
         self.ctx.observation_metrics.snapshot()
@@ -2872,6 +3061,40 @@ impl Piper {
         control
     }
 
+    /// 当前锁存的机械臂故障状态（未锁存时为 `None`）
+    pub fn robot_fault(&self) -> Option<RobotStatus> {
+        self.ctx.robot_fault.get()
+    }
+
+    /// 清除机械臂故障锁存
+    pub fn clear_robot_fault(&self) {
+        self.ctx.robot_fault.clear();
+    }
+
+    /// 检查故障锁存，已锁存时返回 `DriverError::RobotFault`
+    pub fn ensure_no_robot_fault(&self) -> Result<(), DriverError> {
+        match self.robot_fault() {
+            Some(status) => Err(DriverError::RobotFault(status)),
+            None => Ok(()),
+        }
+    }
+
+    /// 注册机械臂故障回调
+    ///
+    /// 回调在 RX 线程中、故障状态被锁存的同一帧内调用，必须快速返回且不能阻塞；
+    /// 只有锁存值变化时才会触发。再次注册会替换之前的回调。
+    pub fn set_on_fault<F>(&self, callback: F)
+    where
+        F: Fn(RobotStatus) + Send + Sync + 'static,
+    {
+        self.ctx.robot_fault.set_callback(Some(Arc::new(callback)));
+    }
+
+    /// 移除机械臂故障回调
+    pub fn clear_on_fault(&self) {
+        self.ctx.robot_fault.set_callback(None);
+    }
+
     /// 获取夹爪状态（无锁）
     ///
     /// 包含夹爪行程、扭矩、状态码等（100Hz更新）。
@@ -2947,6 +3170,17 @@ impl Piper {
         }
     }
 
+    /// 发送查询并阻塞等待结构化固件版本。
+    ///
+    /// 固件返回的版本字符串无法解析时返回 `DriverError::Protocol(ProtocolError::ParseError)`。
+    pub fn query_firmware_version(
+        &self,
+        timeout: Duration,
+    ) -> Result<piper_protocol::FirmwareVersion, DriverError> {
+        let version = self.read_firmware_version(timeout)?;
+        version.parse().map_err(DriverError::Protocol)
+    }
+
     fn wait_for_cached_update<T, F>(
         &self,
         deadline: Instant,
@@ -3568,23 +3802,7 @@ impl Piper {
         let pair = view.pair;
         let joint_position = pair.joint_position;
         let joint_dynamic = pair.joint_dynamic;
-
-        let state = AlignedMotionState {
-            joint_pos: joint_position.joint_pos,
-            joint_vel: joint_dynamic.joint_vel,
-            joint_current: joint_dynamic.joint_current,
-            position_timestamp_us: joint_position.hardware_timestamp_us,
-            dynamic_timestamp_us: joint_dynamic.group_timestamp_us,
-            position_host_rx_mono_us: joint_position.host_rx_mono_us,
-            dynamic_host_rx_mono_us: joint_dynamic.group_host_rx_mono_us,
-            position_raw_feedback_timing: joint_position.raw_feedback_timing,
-            dynamic_raw_feedback_timing: joint_dynamic.raw_feedback_timing,
-            position_frame_valid_mask: joint_position.frame_valid_mask,
-            dynamic_valid_mask: joint_dynamic.valid_mask,
-            dynamic_group_span_us: joint_dynamic.group_span_us(),
-            skew_us: (joint_dynamic.group_timestamp_us as i64)
-                - (joint_position.hardware_timestamp_us as i64),
-        };
+        let state = AlignedMotionState::from_control_pair(&pair);
 
         let max_feedback_age_us = max_feedback_age.as_micros().min(u128::from(u64::MAX)) as u64;
         let feedback_age_us = state.feedback_age().as_micros().min(u128::from(u64::MAX)) as u64;
@@ -3772,6 +3990,26 @@ impl Piper {
         self.ctx.connection_monitor.check_connection()
     }
 
+    /// 获取结构化的连接健康状态
+    ///
+    /// - `Healthy`: 反馈按预期频率到达
+    /// - `Degraded { missed }`: 连续错过若干个预期反馈周期（默认 3 × 5ms），可用于 UI 置灰
+    /// - `Lost`: 超过连接超时（1 秒）未收到反馈，应触发安全停止
+    pub fn link_health(&self) -> crate::heartbeat::LinkHealth {
+        self.ctx.connection_monitor.health()
+    }
+
+    /// 注册连接健康状态变化回调
+    ///
+    /// 仅在 `Healthy` / `Degraded` / `Lost` 之间切换时触发。回调在 RX 线程中执行，
+    /// 必须快速返回（例如只设置标志或发送到 channel）。
+    pub fn on_link_health_change(
+        &self,
+        callback: impl Fn(crate::heartbeat::LinkHealth) + Send + Sync + 'static,
+    ) {
+        self.ctx.connection_monitor.on_health_change(callback);
+    }
+
     /// 获取自上次反馈以来的时间
     ///
     /// 返回自上次成功处理 CAN 帧以来的时间。
@@ -3874,6 +4112,21 @@ impl Piper {
         Arc::clone(&self.ctx.hooks)
     }
 
+    /// 获取飞行记录器中的最近帧（RX + TX，最旧在前）
+    ///
+    /// 容量由 [`PipelineConfig::flight_recorder_capacity`] 决定；时间戳为主机单调时钟
+    /// （微秒），两个方向可直接比较先后。
+    pub fn recent_frames(&self) -> Vec<crate::recording::TimestampedFrame> {
+        self.ctx.flight_recorder.snapshot()
+    }
+
+    /// 获取致命 CAN 错误（BusOff、BufferOverflow、设备错误）发生时冻结的帧记录
+    ///
+    /// 冻结内容同时会以 `error` 级别写入日志；未发生致命错误时返回 `None`。
+    pub fn fatal_frame_dump(&self) -> Option<crate::flight_recorder::FatalFrameDump> {
+        self.ctx.flight_recorder.fatal_dump()
+    }
+
     /// 获取 CAN 接口名称
     ///
     /// # 返回值
@@ -4477,6 +4730,7 @@ impl Piper {
             }
             command.complete(Err(reason));
         }
+        crate::pipeline::drop_coalesced_commands(&self.command_mailbox, &self.metrics);
     }
 
     /// 发送可靠命令（FIFO 策略）
@@ -4925,6 +5179,85 @@ impl Piper {
         }
     }
 
+    /// 丢弃所有优先级低于 `min_priority` 且尚未发送的命令，返回丢弃的命令数
+    ///
+    /// 用于急停、模式切换前清除排队中的低优先级命令（例如未发完的轨迹）。
+    /// 清理实时插槽、SoftRealtime 邮箱与合并邮箱中低于 `min_priority` 的部分；
+    /// 被丢弃的带确认命令以 `DriverError::CommandAbortedByStateTransition` 完成。
+    /// 每个队列都在与 TX 线程共享的锁内清理：一条命令要么已被 TX 线程取走，要么被丢弃。
+    ///
+    /// 可靠命令 FIFO 与急停通道不受影响（`ReliableCommand` 是最高的普通优先级）。
+    pub fn drain_commands(&self, min_priority: CommandPriority) -> usize {
+        let mut dropped = 0;
+        if CommandPriority::RealtimeControl < min_priority {
+            if let Ok(mut slot) = self.realtime_slot.lock()
+                && let Some(command) = slot.take()
+            {
+                command.complete(Err(DriverError::CommandAbortedByStateTransition));
+                dropped += 1;
+            }
+            for command in self.soft_realtime_tx.drain_ready() {
+                command.complete(Err(DriverError::CommandAbortedByStateTransition));
+                dropped += 1;
+            }
+        }
+
+        let coalesced = self.command_mailbox.clear_below(min_priority);
+        crate::pipeline::record_coalesced_drops(&self.metrics, coalesced);
+        dropped += coalesced.iter().sum::<usize>();
+        if dropped > 0 {
+            info!("Drained {dropped} queued commands below {min_priority:?}");
+        }
+        dropped
+    }
+
+    /// 发送命令到合并邮箱（最新值优先）
+    ///
+    /// 邮箱按 (CAN ID, 优先级) 只保留最新一帧：控制循环产生命令快于总线排空速度时，
+    /// 尚未发送的旧设定值直接被新命令替换，而不是排队。TX 线程在实时插槽之后
+    /// 消费邮箱，先发送 `RealtimeControl`，再发送 `ReliableCommand`；后者经可靠通道
+    /// 发送，与 [`Self::send_reliable`] 的门控语义一致。
+    ///
+    /// 被替换的命令计入 [`MetricsSnapshot::tx_coalesced_dropped_total`]。
+    /// 与 [`Self::send_realtime`] 相同，不等待发送结果。
+    ///
+    /// # 错误
+    /// - `DriverError::InvalidInput`: 非 StrictRealtime 后端
+    /// - `DriverError::ReplayModeActive`: 处于回放模式
+    /// - `DriverError::ChannelClosed`: TX 线程已退出
+    /// - `DriverError::ControlPathClosed`: 普通控制路径已关闭（故障/停止）
+    /// - `DriverError::ChannelFull`: 同一优先级下待发送的不同 CAN ID 过多
+    pub fn send_coalesced(&self, command: PiperCommand) -> Result<(), DriverError> {
+        if !self.backend_capability.is_strict_realtime() {
+            return Err(DriverError::InvalidInput(
+                "coalesced delivery is only available on StrictRealtime backends".to_string(),
+            ));
+        }
+        if self.replay_mode_active() || self.replay_barrier_active() {
+            return Err(DriverError::ReplayModeActive);
+        }
+        if !self.tx_thread_alive() {
+            return Err(DriverError::ChannelClosed);
+        }
+        if !self.normal_control_open() {
+            return Err(DriverError::ControlPathClosed);
+        }
+        let queue = &self.metrics.command_queue;
+        match self.command_mailbox.push(command) {
+            CommandMailboxPush::Queued => {
+                queue.record_coalesced_enqueue(command.priority());
+                Ok(())
+            },
+            CommandMailboxPush::Replaced => {
+                queue.record_coalesced_enqueue(command.priority());
+                queue.record_coalesced_drop(command.priority(), 1);
+                self.metrics.tx_coalesced_dropped_total.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            },
+            CommandMailboxPush::Full => Err(DriverError::ChannelFull),
+        }
+    }
+
     /// 发送可靠命令（阻塞，带超时）
     ///
     /// 如果队列满，阻塞等待直到有空闲位置或超时。
@@ -5042,6 +5375,8 @@ impl Piper {
 
 impl Drop for Piper {
     fn drop(&mut self) {
+        self.safe_stop_on_drop(DROP_SAFE_STOP_TIMEOUT);
+
         self.runtime_phase.store(RuntimePhase::Stopping as u8, Ordering::Release);
         self.normal_send_gate.close_for_stop();
         self.workers_running.store(false, Ordering::Release);
@@ -5082,6 +5417,7 @@ mod tests {
     use super::*;
     use crate::DriverMode;
     use crate::observation::{Available, Complete, Freshness, Observation, ObservationPayload};
+    use crate::recording::RecordedFrameDirection;
     use crate::{DiagnosticEvent, ProtocolDiagnostic, QueryError, WaitError};
     use piper_can::{CanAdapter, PiperFrame, SplittableAdapter};
     use std::collections::VecDeque;
@@ -5348,6 +5684,26 @@ mod tests {
         }
     }
 
+    struct BusOffAfterFramesRxAdapter {
+        frames: VecDeque<PiperFrame>,
+        trigger: Arc<std::sync::atomic::AtomicBool>,
+        tripped: bool,
+    }
+
+    impl piper_can::RxAdapter for BusOffAfterFramesRxAdapter {
+        fn receive(&mut self) -> Result<piper_can::ReceivedFrame, CanError> {
+            if let Some(frame) = self.frames.pop_front() {
+                return Ok(received(frame));
+            }
+            if !self.tripped && self.trigger.load(std::sync::atomic::Ordering::Acquire) {
+                self.tripped = true;
+                return Err(CanError::BusOff);
+            }
+            std::thread::sleep(Duration::from_millis(1));
+            Err(CanError::Timeout)
+        }
+    }
+
     struct PanickingRxAdapter;
 
     impl piper_can::RxAdapter for PanickingRxAdapter {
@@ -6059,6 +6415,299 @@ mod tests {
         }
     }
 
+    fn drop_enabled_piper_and_collect(action: Option<DropStopAction>) -> Vec<PiperFrame> {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let piper = Piper::new_dual_thread_parts_unvalidated(
+            MockRxAdapter,
+            RecordingTxAdapter {
+                sent_frames: sent_frames.clone(),
+            },
+            None,
+        )
+        .unwrap();
+        if let Some(action) = action {
+            piper.set_drop_stop_action(action);
+        }
+        publish_confirmed_driver_mask(&piper, 0b11_1111);
+        drop(piper);
+        sent_frames.lock().expect("sent frames lock").clone()
+    }
+
+    #[test]
+    fn test_drop_while_enabled_sends_configured_safe_stop_frame() {
+        use piper_protocol::control::{EmergencyStopCommand, MotorEnableCommand};
+
+        assert_eq!(
+            drop_enabled_piper_and_collect(Some(DropStopAction::Disable)),
+            vec![MotorEnableCommand::disable_all().to_frame()]
+        );
+        assert_eq!(
+            drop_enabled_piper_and_collect(Some(DropStopAction::EmergencyStop)),
+            vec![EmergencyStopCommand::emergency_stop().to_frame()]
+        );
+
+        let mit_zero = drop_enabled_piper_and_collect(Some(DropStopAction::MitZero));
+        let ids: Vec<_> = mit_zero
+            .iter()
+            .filter_map(|frame| frame.id().as_standard())
+            .map(|id| id.raw())
+            .collect();
+        assert_eq!(ids, vec![0x15A, 0x15B, 0x15C, 0x15D, 0x15E, 0x15F]);
+
+        assert!(drop_enabled_piper_and_collect(Some(DropStopAction::None)).is_empty());
+    }
+
+    #[test]
+    fn test_drop_monitor_only_driver_while_enabled_sends_nothing() {
+        // 未设置停止动作的 driver（监控/录制）不能失能其他进程控制的机械臂
+        assert!(drop_enabled_piper_and_collect(None).is_empty());
+    }
+
+    #[test]
+    fn test_drop_without_enabled_feedback_sends_nothing() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let piper = Piper::new_dual_thread_parts_unvalidated(
+            MockRxAdapter,
+            RecordingTxAdapter {
+                sent_frames: sent_frames.clone(),
+            },
+            None,
+        )
+        .unwrap();
+        drop(piper);
+        assert!(sent_frames.lock().expect("sent frames lock").is_empty());
+    }
+
+    #[test]
+    fn test_send_coalesced_drops_stale_setpoints_and_drains_realtime_first() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let piper = Piper::new_dual_thread_parts_unvalidated(
+            MockRxAdapter,
+            RecordingTxAdapter {
+                sent_frames: sent_frames.clone(),
+            },
+            None,
+        )
+        .unwrap();
+        let (reached_rx, release_tx) = install_tx_loop_barrier(&piper);
+        reached_rx
+            .recv_timeout(Duration::from_secs(1))
+            .expect("TX loop should reach dispatch barrier");
+
+        let stale = PiperFrame::new_standard(0x15A, [1]).unwrap();
+        let latest = PiperFrame::new_standard(0x15A, [2]).unwrap();
+        let other_joint = PiperFrame::new_standard(0x15B, [3]).unwrap();
+        let reliable = PiperFrame::new_standard(0x15A, [4]).unwrap();
+        piper.send_coalesced(PiperCommand::reliable(reliable)).unwrap();
+        piper.send_coalesced(PiperCommand::realtime(stale)).unwrap();
+        piper.send_coalesced(PiperCommand::realtime(other_joint)).unwrap();
+        piper.send_coalesced(PiperCommand::realtime(latest)).unwrap();
+
+        let _ = release_tx.send(());
+        wait_until(
+            Duration::from_millis(500),
+            || sent_frames.lock().expect("sent frames lock").len() == 3,
+            "coalesced commands should be drained",
+        );
+
+        let sent = sent_frames.lock().expect("sent frames lock").clone();
+        assert_eq!(sent, vec![latest, other_joint, reliable]);
+        assert_eq!(piper.get_metrics().tx_coalesced_dropped_total, 1);
+    }
+
+    #[test]
+    fn test_send_coalesced_reliable_entry_is_sent_on_reliable_lane() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let piper = Piper::new_dual_thread_parts_unvalidated(
+            MockRxAdapter,
+            RecordingTxAdapter {
+                sent_frames: sent_frames.clone(),
+            },
+            None,
+        )
+        .unwrap();
+
+        let reliable = PiperFrame::new_standard(0x151, [1]).unwrap();
+        piper.send_coalesced(PiperCommand::reliable(reliable)).unwrap();
+        wait_until(
+            Duration::from_millis(500),
+            || !sent_frames.lock().expect("sent frames lock").is_empty(),
+            "coalesced reliable command should be sent",
+        );
+
+        assert_eq!(
+            *sent_frames.lock().expect("sent frames lock"),
+            vec![reliable]
+        );
+        // 实时路径会把每帧计为一个实时包；可靠通道不会
+        assert_eq!(piper.get_metrics().tx_packages_completed_total, 0);
+    }
+
+    #[test]
+    fn test_send_coalesced_transmits_only_latest_of_many_updates_to_one_id() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let piper = Piper::new_dual_thread_parts_unvalidated(
+            MockRxAdapter,
+            RecordingTxAdapter {
+                sent_frames: sent_frames.clone(),
+            },
+            None,
+        )
+        .unwrap();
+        let (reached_rx, release_tx) = install_tx_loop_barrier(&piper);
+        reached_rx
+            .recv_timeout(Duration::from_secs(1))
+            .expect("TX loop should reach dispatch barrier");
+
+        // TX 线程停在屏障处，模拟跟不上的消费者
+        let updates: Vec<PiperFrame> = (0..100u8)
+            .map(|value| PiperFrame::new_standard(0x155, [value]).unwrap())
+            .collect();
+        for frame in &updates {
+            piper.send_coalesced(PiperCommand::realtime(*frame)).unwrap();
+        }
+
+        let _ = release_tx.send(());
+        wait_until(
+            Duration::from_millis(500),
+            || !sent_frames.lock().expect("sent frames lock").is_empty(),
+            "latest setpoint should be sent",
+        );
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(
+            *sent_frames.lock().expect("sent frames lock"),
+            vec![*updates.last().unwrap()]
+        );
+        assert_eq!(piper.get_metrics().tx_coalesced_dropped_total, 99);
+    }
+
+    #[test]
+    fn test_drain_commands_drops_queued_realtime_commands_and_keeps_reliable() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let piper = Piper::new_dual_thread_parts_unvalidated(
+            MockRxAdapter,
+            RecordingTxAdapter {
+                sent_frames: sent_frames.clone(),
+            },
+            None,
+        )
+        .unwrap();
+        let (reached_rx, release_tx) = install_tx_loop_barrier(&piper);
+        reached_rx
+            .recv_timeout(Duration::from_secs(1))
+            .expect("TX loop should reach dispatch barrier");
+
+        let reliable = PiperFrame::new_standard(0x151, [1]).unwrap();
+        piper
+            .send_coalesced(PiperCommand::realtime(
+                PiperFrame::new_standard(0x155, [2]).unwrap(),
+            ))
+            .unwrap();
+        piper
+            .send_coalesced(PiperCommand::realtime(
+                PiperFrame::new_standard(0x156, [3]).unwrap(),
+            ))
+            .unwrap();
+        piper.send_coalesced(PiperCommand::reliable(reliable)).unwrap();
+        piper.send_realtime(PiperFrame::new_standard(0x157, [4]).unwrap()).unwrap();
+
+        assert_eq!(piper.drain_commands(CommandPriority::RealtimeControl), 0);
+        assert_eq!(piper.drain_commands(CommandPriority::ReliableCommand), 3);
+        assert_eq!(piper.drain_commands(CommandPriority::ReliableCommand), 0);
+
+        let _ = release_tx.send(());
+        wait_until(
+            Duration::from_millis(500),
+            || !sent_frames.lock().expect("sent frames lock").is_empty(),
+            "reliable command should still be sent",
+        );
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(
+            *sent_frames.lock().expect("sent frames lock"),
+            vec![reliable]
+        );
+    }
+
+    #[test]
+    fn test_command_queue_metrics_report_depth_and_shutdown_behind_backlog() {
+        use piper_protocol::control::EmergencyStopCommand;
+
+        let piper = Piper::new_dual_thread_parts_unvalidated(
+            MockRxAdapter,
+            RecordingTxAdapter {
+                sent_frames: Arc::new(Mutex::new(Vec::new())),
+            },
+            None,
+        )
+        .unwrap();
+        let (reached_rx, release_tx) = install_tx_loop_barrier(&piper);
+        reached_rx
+            .recv_timeout(Duration::from_secs(1))
+            .expect("TX loop should reach dispatch barrier");
+
+        for value in 0..3u8 {
+            let frame = PiperFrame::new_standard(0x15A, [value]).unwrap();
+            piper.send_coalesced(PiperCommand::realtime(frame)).unwrap();
+        }
+        let reliable = PiperFrame::new_standard(0x151, [0x01]).unwrap();
+        piper.send_coalesced(PiperCommand::reliable(reliable)).unwrap();
+        let receipt = piper
+            .enqueue_shutdown(
+                EmergencyStopCommand::emergency_stop().to_frame(),
+                Instant::now() + Duration::from_millis(500),
+            )
+            .unwrap();
+
+        let _ = release_tx.send(());
+        receipt.wait().expect("emergency stop should be sent");
+
+        let queue = piper.get_metrics().command_queue;
+        assert_eq!(queue.shutdown_behind_backlog_total, 1);
+        assert_eq!(queue.shutdown.enqueued_total, 1);
+        assert_eq!(queue.realtime.enqueued_total, 3);
+        assert_eq!(queue.realtime.dropped_total, 2);
+        assert_eq!(queue.reliable.enqueued_total, 1);
+    }
+
+    #[test]
+    fn test_max_tx_rate_paces_realtime_frames_and_keeps_latest() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let piper = Piper::new_dual_thread_parts_unvalidated(
+            MockRxAdapter,
+            RecordingTxAdapter {
+                sent_frames: sent_frames.clone(),
+            },
+            Some(PipelineConfig {
+                max_tx_rate_hz: Some(1_000),
+                ..PipelineConfig::default()
+            }),
+        )
+        .unwrap();
+
+        let start = Instant::now();
+        let mut latest = None;
+        for value in 0..64u8 {
+            let frame = PiperFrame::new_standard(0x15A, [value]).unwrap();
+            piper.send_realtime(frame).unwrap();
+            latest = Some(frame);
+        }
+        wait_until(
+            Duration::from_millis(100),
+            || sent_frames.lock().expect("sent frames lock").last().copied() == latest,
+            "latest realtime frame should eventually be sent",
+        );
+
+        let elapsed = start.elapsed();
+
+        let sent = sent_frames.lock().expect("sent frames lock").len();
+        let budget = 6.0 + elapsed.as_secs_f64() * 1_000.0 + 1.0;
+        assert!(
+            (sent as f64) <= budget,
+            "sent {sent} frames in {elapsed:?}, budget {budget}"
+        );
+        assert!(piper.get_metrics().tx_realtime_overwrites_total > 0);
+    }
+
     #[test]
     fn test_replay_mode_rejects_normal_control_paths_but_allows_replay_frames() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
@@ -6950,6 +7599,7 @@ mod tests {
             raw_feedback_timing: None,
             joint_pos: [1.0; 6],
             frame_valid_mask: 0b111,
+            consistency: StateConsistency::Consistent,
         });
         piper.ctx.publish_control_joint_dynamic(JointDynamicState {
             group_timestamp_us: 1_000,
@@ -7003,6 +7653,7 @@ mod tests {
             raw_feedback_timing: Some(position_raw),
             joint_pos: [0.0; 6],
             frame_valid_mask: 0b0000_0111,
+            consistency: StateConsistency::Consistent,
         });
         piper.ctx.publish_control_joint_dynamic(JointDynamicState {
             group_timestamp_us: 1_001,
@@ -7033,6 +7684,7 @@ mod tests {
             raw_feedback_timing: None,
             joint_pos: [0.0; 6],
             frame_valid_mask: 0b111,
+            consistency: StateConsistency::Consistent,
         });
         piper.ctx.publish_control_joint_dynamic(JointDynamicState {
             group_timestamp_us: 2_000,
@@ -8150,6 +8802,27 @@ mod tests {
         assert_eq!(piper.firmware_version_cached().as_deref(), Some("S-V1.6-3"));
     }
 
+    #[test]
+    fn test_query_firmware_version_parses_structured_version() {
+        let frame = PiperFrame::new_standard(
+            u32::from(piper_protocol::ids::ID_FIRMWARE_READ.raw()),
+            b"S-V1.8-1",
+        )
+        .unwrap();
+        let piper = Piper::new_dual_thread_parts(
+            ScriptedRxAdapter::new(vec![frame], Duration::from_millis(20)),
+            MockTxAdapter,
+            None,
+        )
+        .unwrap();
+
+        let version = piper
+            .query_firmware_version(Duration::from_millis(200))
+            .expect("firmware version should parse");
+        assert_eq!(version, piper_protocol::FirmwareVersion::new(1, 8, 1, 0));
+        assert!(version >= piper_protocol::FirmwareVersion::new(1, 5, 2, 0));
+    }
+
     #[test]
     fn test_query_collision_protection_returns_fresh_feedback_and_sends_query_frame() {
         let mut data = [0u8; 8];
@@ -8830,6 +9503,7 @@ mod tests {
             raw_feedback_timing: None,
             joint_pos: [0.0; 6],
             frame_valid_mask: 0b101,
+            consistency: StateConsistency::Consistent,
         });
         piper.ctx.publish_control_joint_dynamic(JointDynamicState {
             group_timestamp_us: 1_000,
@@ -8871,6 +9545,7 @@ mod tests {
             raw_feedback_timing: None,
             joint_pos: [1.0; 6],
             frame_valid_mask: 0b111,
+            consistency: StateConsistency::Consistent,
         });
 
         assert!(piper.get_control_joint_dynamic(Duration::from_millis(1)).is_none());
@@ -8903,6 +9578,7 @@ mod tests {
             raw_feedback_timing: None,
             joint_pos: [1.0; 6],
             frame_valid_mask: 0b111,
+            consistency: StateConsistency::Consistent,
         });
         piper.ctx.publish_control_joint_dynamic(JointDynamicState {
             group_timestamp_us: 1_000,
@@ -8955,6 +9631,7 @@ mod tests {
             raw_feedback_timing: None,
             joint_pos: [3.0; 6],
             frame_valid_mask: 0b111,
+            consistency: StateConsistency::Consistent,
         });
 
         let after_both_advanced = match piper.get_aligned_motion(5_000, Duration::from_secs(3600)) {
@@ -8986,6 +9663,7 @@ mod tests {
             raw_feedback_timing: None,
             joint_pos: [1.0; 6],
             frame_valid_mask: 0b111,
+            consistency: StateConsistency::Consistent,
         });
         piper.ctx.publish_control_joint_dynamic(JointDynamicState {
             group_timestamp_us: 1_000,
@@ -9014,6 +9692,7 @@ mod tests {
             raw_feedback_timing: None,
             joint_pos: [1.0; 6],
             frame_valid_mask: 0b111,
+            consistency: StateConsistency::Consistent,
         });
         piper.ctx.publish_control_joint_dynamic(JointDynamicState {
             group_timestamp_us: 2_000,
@@ -9041,6 +9720,7 @@ mod tests {
             raw_feedback_timing: None,
             joint_pos: [1.0; 6],
             frame_valid_mask: 0b111,
+            consistency: StateConsistency::Consistent,
         });
         piper.ctx.publish_control_joint_dynamic(JointDynamicState {
             group_timestamp_us: 3_000,
@@ -9067,6 +9747,7 @@ mod tests {
             raw_feedback_timing: None,
             joint_pos: [1.0; 6],
             frame_valid_mask: 0b111,
+            consistency: StateConsistency::Consistent,
         });
         piper.ctx.publish_control_joint_dynamic(JointDynamicState {
             group_timestamp_us: 4_000,
@@ -9457,6 +10138,139 @@ mod tests {
             .expect("ready ack should still be observed after the shared deadline passes");
     }
 
+    fn watchdog_config(interval: Duration, action: DropStopAction) -> PipelineConfig {
+        PipelineConfig {
+            watchdog: Some(crate::watchdog::WatchdogConfig::new(interval, action)),
+            ..PipelineConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_watchdog_expiry_latches_fault_and_sends_stop_frame() {
+        use piper_protocol::control::MotorEnableCommand;
+
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let piper = Piper::new_dual_thread_parts_unvalidated(
+            MockRxAdapter,
+            RecordingTxAdapter {
+                sent_frames: sent_frames.clone(),
+            },
+            Some(watchdog_config(
+                Duration::from_millis(20),
+                DropStopAction::Disable,
+            )),
+        )
+        .unwrap();
+        publish_confirmed_driver_mask(&piper, 0b11_1111);
+
+        piper.pet_watchdog();
+        wait_until(
+            Duration::from_millis(500),
+            || piper.health().fault.is_some(),
+            "watchdog should trip after the control thread stops petting",
+        );
+
+        assert_eq!(
+            piper.health().fault,
+            Some(RuntimeFaultKind::WatchdogExpired)
+        );
+        assert!(matches!(
+            piper.send_realtime(PiperFrame::new_standard(0x155, [0x01]).unwrap()),
+            Err(DriverError::ControlPathClosed)
+        ));
+        assert_eq!(
+            sent_frames.lock().expect("sent frames lock").as_slice(),
+            &[MotorEnableCommand::disable_all().to_frame()]
+        );
+        assert_eq!(piper.get_metrics().tx_watchdog_trips_total, 1);
+    }
+
+    #[test]
+    fn test_watchdog_does_not_trip_when_unarmed_or_joints_disabled() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let piper = Piper::new_dual_thread_parts_unvalidated(
+            MockRxAdapter,
+            RecordingTxAdapter {
+                sent_frames: sent_frames.clone(),
+            },
+            Some(watchdog_config(
+                Duration::from_millis(5),
+                DropStopAction::EmergencyStop,
+            )),
+        )
+        .unwrap();
+
+        publish_confirmed_driver_mask(&piper, 0b11_1111);
+        thread::sleep(Duration::from_millis(30));
+        assert_eq!(
+            piper.health().fault,
+            None,
+            "never petted: watchdog is not armed"
+        );
+
+        publish_confirmed_driver_mask(&piper, 0);
+        piper.pet_watchdog();
+        wait_until(
+            Duration::from_millis(500),
+            || !piper.ctx.watchdog.is_armed(),
+            "expired watchdog should disarm",
+        );
+        assert_eq!(piper.health().fault, None);
+        assert!(sent_frames.lock().expect("sent frames lock").is_empty());
+        assert_eq!(piper.get_metrics().tx_watchdog_trips_total, 0);
+    }
+
+    #[test]
+    fn test_keepalive_resends_last_realtime_package_until_reliable_command() {
+        let sent_frames = Arc::new(Mutex::new(Vec::new()));
+        let piper = Piper::new_dual_thread_parts_unvalidated(
+            MockRxAdapter,
+            RecordingTxAdapter {
+                sent_frames: sent_frames.clone(),
+            },
+            Some(PipelineConfig {
+                keepalive_rate_hz: Some(500),
+                ..PipelineConfig::default()
+            }),
+        )
+        .unwrap();
+        let package = [
+            PiperFrame::new_standard(0x15A, [0x01]).unwrap(),
+            PiperFrame::new_standard(0x15B, [0x02]).unwrap(),
+        ];
+
+        piper.send_realtime_package(package).expect("realtime package should queue");
+        wait_until(
+            Duration::from_millis(500),
+            || piper.get_metrics().tx_keepalive_resent_total >= 2,
+            "idle TX loop should resend the last realtime package",
+        );
+        {
+            let sent = sent_frames.lock().expect("sent frames lock");
+            assert!(sent.len() >= 6);
+            assert!(sent.chunks(2).all(|chunk| chunk == package.as_slice()));
+        }
+
+        let reliable_frame = PiperFrame::new_standard(0x472, [0x03]).unwrap();
+        piper.send_reliable(reliable_frame).expect("reliable frame should queue");
+        wait_until(
+            Duration::from_millis(500),
+            || sent_frames.lock().expect("sent frames lock").contains(&reliable_frame),
+            "reliable frame should be sent",
+        );
+        let resent_after_reliable = piper.get_metrics().tx_keepalive_resent_total;
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(
+            piper.get_metrics().tx_keepalive_resent_total,
+            resent_after_reliable,
+            "a reliable command must stop the keepalive"
+        );
+        assert_eq!(
+            sent_frames.lock().expect("sent frames lock").last(),
+            Some(&reliable_frame)
+        );
+    }
+
     #[test]
     fn test_latch_fault_closes_normal_control_path_but_keeps_shutdown_lane() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
@@ -9804,6 +10618,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_flight_recorder_dumps_recent_rx_and_tx_frames_on_bus_off() {
+        let trigger = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let feedback = [
+            PiperFrame::new_standard(0x2A5, [1; 8]).unwrap(),
+            PiperFrame::new_standard(0x2A6, [2; 8]).unwrap(),
+        ];
+        let piper = Piper::new_dual_thread_parts_unvalidated(
+            BusOffAfterFramesRxAdapter {
+                frames: feedback.into_iter().collect(),
+                trigger: trigger.clone(),
+                tripped: false,
+            },
+            RecordingTxAdapter {
+                sent_frames: Arc::new(Mutex::new(Vec::new())),
+            },
+            Some(PipelineConfig {
+                flight_recorder_capacity: 8,
+                ..PipelineConfig::default()
+            }),
+        )
+        .unwrap();
+
+        wait_until(
+            Duration::from_millis(500),
+            || piper.recent_frames().len() == 2,
+            "rx frames should reach the flight recorder",
+        );
+        piper.send_reliable(PiperFrame::new_standard(0x472, [0x02]).unwrap()).unwrap();
+        wait_until(
+            Duration::from_millis(500),
+            || piper.recent_frames().len() == 3,
+            "sent frame should reach the flight recorder",
+        );
+        assert!(piper.fatal_frame_dump().is_none());
+
+        trigger.store(true, std::sync::atomic::Ordering::Release);
+        wait_until(
+            Duration::from_millis(500),
+            || piper.fatal_frame_dump().is_some(),
+            "bus-off should freeze a flight recorder dump",
+        );
+
+        let dump = piper.fatal_frame_dump().unwrap();
+        assert_eq!(dump.error, CanError::BusOff.to_string());
+        let frames: Vec<_> =
+            dump.frames.iter().map(|frame| (frame.raw_id(), frame.direction)).collect();
+        assert_eq!(
+            frames,
+            vec![
+                (0x2A5, RecordedFrameDirection::Rx),
+                (0x2A6, RecordedFrameDirection::Rx),
+                (0x472, RecordedFrameDirection::Tx),
+            ]
+        );
+        assert!(
+            dump.frames
+                .windows(2)
+                .all(|pair| pair[0].timestamp_us() <= pair[1].timestamp_us())
+        );
+    }
+
     #[test]
     fn test_rx_fatal_keeps_shutdown_lane_available_while_tx_is_alive() {
         let sent_frames = Arc::new(Mutex::new(Vec::new()));
@@ -1,6 +1,7 @@
 //! Driver 模块状态结构定义
 
 use crate::diagnostics::DiagnosticBuffer;
+use crate::flight_recorder::FlightRecorder;
 use crate::fps_stats::FpsStatistics;
 use crate::metrics::{ObservationMetricsStore, PiperMetrics};
 use crate::observation::{
@@ -8,10 +9,20 @@ use crate::observation::{
     ObservationPayload, ObservationSource, PartialPayload,
 };
 use crate::query_coordinator::QueryCoordinator;
+use crate::watchdog::{Watchdog, WatchdogConfig};
 use arc_swap::ArcSwap;
+use piper_protocol::StandardCanId;
+use piper_protocol::feedback::RobotStatus;
+use piper_protocol::ids::{
+    ID_END_POSE_1, ID_END_POSE_2, ID_END_POSE_3, ID_JOINT_DRIVER_HIGH_SPEED_1,
+    ID_JOINT_DRIVER_HIGH_SPEED_2, ID_JOINT_DRIVER_HIGH_SPEED_3, ID_JOINT_DRIVER_HIGH_SPEED_4,
+    ID_JOINT_DRIVER_HIGH_SPEED_5, ID_JOINT_DRIVER_HIGH_SPEED_6, ID_JOINT_FEEDBACK_12,
+    ID_JOINT_FEEDBACK_34, ID_JOINT_FEEDBACK_56,
+};
 use std::cell::UnsafeCell;
-use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
-use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicU8, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
 /// 固定槽位实时快照单元。
 ///
@@ -255,6 +266,39 @@ impl RawFeedbackTiming {
     }
 }
 
+/// 帧组时间一致性
+///
+/// 描述一个帧组快照内各帧时间戳的最大差值是否在配置窗口内
+/// （见 `PipelineConfig::joint_position_consistency_window_us`）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StateConsistency {
+    /// 各帧时间戳差值在窗口内（或帧组只包含一帧）
+    #[default]
+    Consistent,
+    /// 各帧时间戳差值超出窗口：快照可能是"撕裂读"，
+    /// 例如 J1/J2 比 J5/J6 新一个控制周期
+    Torn {
+        /// 帧组内最新与最旧帧的时间戳差值（微秒）
+        span_us: u64,
+    },
+}
+
+impl StateConsistency {
+    /// 根据帧组时间跨度与窗口判定一致性
+    pub fn from_span(span_us: u64, window_us: u64) -> Self {
+        if span_us > window_us {
+            Self::Torn { span_us }
+        } else {
+            Self::Consistent
+        }
+    }
+
+    /// 是否一致（未撕裂）
+    pub fn is_consistent(&self) -> bool {
+        matches!(self, Self::Consistent)
+    }
+}
+
 /// 关节位置状态（帧组同步）
 ///
 /// 更新频率：~500Hz
@@ -281,6 +325,11 @@ pub struct JointPositionState {
     /// - 1 表示该CAN帧已收到
     /// - 0 表示该CAN帧未收到（可能丢包）
     pub frame_valid_mask: u8,
+
+    /// 帧组时间一致性（0x2A5/0x2A6/0x2A7 时间戳差值是否在窗口内）
+    ///
+    /// 控制回路可据此跳过撕裂读。
+    pub consistency: StateConsistency,
 }
 
 impl JointPositionState {
@@ -299,6 +348,18 @@ impl JointPositionState {
     pub fn missing_frames(&self) -> Vec<usize> {
         (0..3).filter(|&i| (self.frame_valid_mask & (1 << i)) == 0).collect()
     }
+
+    /// 本周期缺失的反馈帧 CAN ID（对应关节沿用上一份快照的值）
+    pub fn missed_ids(&self) -> Vec<StandardCanId> {
+        missed_ids(
+            self.frame_valid_mask,
+            &[
+                ID_JOINT_FEEDBACK_12,
+                ID_JOINT_FEEDBACK_34,
+                ID_JOINT_FEEDBACK_56,
+            ],
+        )
+    }
 }
 
 /// 末端位姿状态（帧组同步）
@@ -339,6 +400,23 @@ impl EndPoseState {
     pub fn missing_frames(&self) -> Vec<usize> {
         (0..3).filter(|&i| (self.frame_valid_mask & (1 << i)) == 0).collect()
     }
+
+    /// 本周期缺失的反馈帧 CAN ID
+    pub fn missed_ids(&self) -> Vec<StandardCanId> {
+        missed_ids(
+            self.frame_valid_mask,
+            &[ID_END_POSE_1, ID_END_POSE_2, ID_END_POSE_3],
+        )
+    }
+}
+
+/// 按有效性掩码列出未收到的 CAN ID（Bit i 对应 `ids[i]`）
+fn missed_ids(valid_mask: u8, ids: &[StandardCanId]) -> Vec<StandardCanId> {
+    ids.iter()
+        .enumerate()
+        .filter(|(i, _)| (valid_mask & (1 << i)) == 0)
+        .map(|(_, id)| *id)
+        .collect()
 }
 
 macro_rules! define_monitor_snapshot {
@@ -608,6 +686,21 @@ impl JointDynamicState {
     pub fn missing_joints(&self) -> Vec<usize> {
         (0..6).filter(|&i| (self.valid_mask & (1 << i)) == 0).collect()
     }
+
+    /// 本周期缺失的高速反馈帧 CAN ID（0x251~0x256）
+    pub fn missed_ids(&self) -> Vec<StandardCanId> {
+        missed_ids(
+            self.valid_mask,
+            &[
+                ID_JOINT_DRIVER_HIGH_SPEED_1,
+                ID_JOINT_DRIVER_HIGH_SPEED_2,
+                ID_JOINT_DRIVER_HIGH_SPEED_3,
+                ID_JOINT_DRIVER_HIGH_SPEED_4,
+                ID_JOINT_DRIVER_HIGH_SPEED_5,
+                ID_JOINT_DRIVER_HIGH_SPEED_6,
+            ],
+        )
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -927,6 +1020,66 @@ impl RobotControlState {
     }
 }
 
+/// 机械臂故障回调（在 RX 线程中调用，必须快速返回）
+pub type RobotFaultCallback = Arc<dyn Fn(RobotStatus) + Send + Sync>;
+
+/// 机械臂故障锁存
+///
+/// RX 线程收到硬故障状态（[`RobotStatus::is_latching_fault`]）的 0x2A1 后锁存，
+/// 直到调用方显式清除；后续 0x2A1 恢复正常不会自动解除。
+/// 锁存值变化时（首次故障或故障类型改变）立即调用已注册的回调。
+pub struct RobotFaultLatch {
+    status: AtomicU8,
+    callback: RwLock<Option<RobotFaultCallback>>,
+}
+
+impl RobotFaultLatch {
+    fn new() -> Self {
+        Self {
+            status: AtomicU8::new(RobotStatus::Normal as u8),
+            callback: RwLock::new(None),
+        }
+    }
+
+    /// 当前锁存的故障状态
+    pub fn get(&self) -> Option<RobotStatus> {
+        let status = RobotStatus::from(self.status.load(Ordering::Acquire));
+        status.is_latching_fault().then_some(status)
+    }
+
+    /// 清除故障锁存
+    pub fn clear(&self) {
+        self.status.store(RobotStatus::Normal as u8, Ordering::Release);
+    }
+
+    /// 注册（或以 `None` 移除）故障回调
+    pub fn set_callback(&self, callback: Option<RobotFaultCallback>) {
+        match self.callback.write() {
+            Ok(mut guard) => *guard = callback,
+            Err(poisoned) => *poisoned.into_inner() = callback,
+        }
+    }
+
+    /// RX 线程观察到新的机械臂状态
+    pub(crate) fn observe(&self, status: RobotStatus) {
+        if !status.is_latching_fault() {
+            return;
+        }
+        let previous = self.status.swap(status as u8, Ordering::AcqRel);
+        if previous == status as u8 {
+            return;
+        }
+
+        let callback = match self.callback.read() {
+            Ok(guard) => guard.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        };
+        if let Some(callback) = callback {
+            callback(status);
+        }
+    }
+}
+
 /// 夹爪状态
 ///
 /// 更新频率：~200Hz
@@ -2236,7 +2389,69 @@ impl MasterSlaveGripperControlState {
 /// `piper.hooks()` 获取同一份 `HookManager`，而不是直接依赖 `PiperContext` 的内部结构。
 use crate::hooks::HookManager;
 #[cfg(test)]
-use std::sync::{Mutex, mpsc};
+use std::sync::mpsc;
+
+/// 状态提交通知（代数计数 + 条件变量）
+///
+/// RX 线程每提交一份完整（或控制级）的关节位置/末端位姿/关节动态快照，代数加一；
+/// 只有存在等待者时才会加锁唤醒，热路径只多一次原子自增。
+#[derive(Debug, Default)]
+pub struct StateUpdateNotifier {
+    generation: AtomicU64,
+    waiters: AtomicUsize,
+    lock: Mutex<()>,
+    changed: Condvar,
+}
+
+impl StateUpdateNotifier {
+    /// 当前状态代数（单调递增，从 0 开始）
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// 记录一次状态提交并唤醒等待者
+    pub fn notify(&self) {
+        // 与 `wait_since` 构成 store-buffering：两侧“先写后读另一个变量”都必须是 SeqCst，
+        // 否则双方可能同时读到旧值，丢失唤醒直到超时
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        if self.waiters.load(Ordering::SeqCst) != 0 {
+            let _guard = self.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            self.changed.notify_all();
+        }
+    }
+
+    /// 阻塞等待代数超过 `since`，返回新代数；超时返回 `None`
+    pub fn wait_since(&self, since: u64, timeout: Duration) -> Option<u64> {
+        let current = self.generation();
+        if current > since {
+            return Some(current);
+        }
+
+        let deadline = Instant::now().checked_add(timeout);
+        self.waiters.fetch_add(1, Ordering::SeqCst);
+        let mut guard = self.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let result = loop {
+            let current = self.generation.load(Ordering::SeqCst);
+            if current > since {
+                break Some(current);
+            }
+            let remaining = match deadline {
+                Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+                None => Duration::MAX,
+            };
+            if remaining.is_zero() {
+                break None;
+            }
+            guard = match self.changed.wait_timeout(guard, remaining) {
+                Ok((guard, _)) => guard,
+                Err(poisoned) => poisoned.into_inner().0,
+            };
+        };
+        drop(guard);
+        self.waiters.fetch_sub(1, Ordering::AcqRel);
+        result
+    }
+}
 
 /// Piper 上下文（所有状态的聚合）
 pub struct PiperContext {
@@ -2287,6 +2502,12 @@ pub struct PiperContext {
 
     /// Task 4 rebuilt-family diagnostics buffer.
     pub diagnostics: DiagnosticBuffer,
+    /// 飞行记录器（最近 N 帧 RX/TX 流量，无锁写入）
+    pub flight_recorder: FlightRecorder,
+    /// 软件看门狗（控制线程喂狗，TX 线程检查到期）
+    pub watchdog: Watchdog,
+    /// 完整状态提交通知（供观察者阻塞等待新状态）
+    pub state_updates: StateUpdateNotifier,
     /// Dedicated rebuilt-family observation metrics store.
     pub(crate) observation_metrics: Arc<ObservationMetricsStore>,
     /// Single-flight query coordinator for rebuilt query-backed families.
@@ -2333,6 +2554,8 @@ pub struct PiperContext {
     pub connection_monitor: crate::heartbeat::ConnectionMonitor,
     /// 第一次带可信设备时间戳的反馈到达主机的单调时间（微秒）。
     pub first_timestamped_feedback_host_rx_mono_us: AtomicU64,
+    /// 机械臂故障锁存（0x2A1 故障状态）
+    pub robot_fault: RobotFaultLatch,
     hot_snapshot_metrics: Option<Arc<PiperMetrics>>,
 
     /// Test-only barrier that pauses one Piper instance at the top of its TX dispatch loop.
@@ -2402,6 +2625,18 @@ impl PiperContext {
         Self::new_with_optional_metrics(Some(metrics))
     }
 
+    /// 以指定容量重建飞行记录器（`0` 表示关闭）
+    pub(crate) fn with_flight_recorder_capacity(mut self, capacity: usize) -> Self {
+        self.flight_recorder = FlightRecorder::new(capacity);
+        self
+    }
+
+    /// 按配置重建软件看门狗（`None` 表示关闭）
+    pub(crate) fn with_watchdog(mut self, config: Option<WatchdogConfig>) -> Self {
+        self.watchdog = Watchdog::new(config);
+        self
+    }
+
     fn new_with_optional_metrics(hot_snapshot_metrics: Option<Arc<PiperMetrics>>) -> Self {
         Self {
             // 热数据：固定槽位快照，无锁读取
@@ -2432,6 +2667,9 @@ impl PiperContext {
             joint_accel_config: Arc::new(RwLock::new(JointAccelConfigState::default())),
             end_limit_config: Arc::new(RwLock::new(EndLimitConfigState::default())),
             diagnostics: DiagnosticBuffer::new(256),
+            flight_recorder: FlightRecorder::default(),
+            watchdog: Watchdog::default(),
+            state_updates: StateUpdateNotifier::default(),
             observation_metrics: Arc::new(ObservationMetricsStore::new()),
             query_coordinator: Arc::new(QueryCoordinator::new()),
             collision_protection_observation: Arc::new(RwLock::new(
@@ -2467,6 +2705,7 @@ impl PiperContext {
                 std::time::Duration::from_secs(1),
             ),
             first_timestamped_feedback_host_rx_mono_us: AtomicU64::new(0),
+            robot_fault: RobotFaultLatch::new(),
             hot_snapshot_metrics,
             #[cfg(test)]
             tx_loop_dispatch_barrier: Mutex::new(None),
@@ -2632,6 +2871,7 @@ impl PiperContext {
             },
         );
         self.record_hot_snapshot_publish_skips(u64::from(!published));
+        self.state_updates.notify();
     }
 
     /// 发布新的原始关节位置，并与当前原始末端位姿组合成逻辑原子快照。
@@ -2668,12 +2908,14 @@ impl PiperContext {
             },
         );
         self.record_hot_snapshot_publish_skips(u64::from(!published));
+        self.state_updates.notify();
     }
 
     /// 发布新的控制级关节位置。
     pub fn publish_control_joint_position(&self, joint_position: JointPositionState) {
         let outcome = self.control_pair.publish_position(joint_position);
         self.record_control_pair_generation_invalidations(outcome.invalidated_generations);
+        self.state_updates.notify();
     }
 
     /// 发布新的原始末端位姿，并与当前原始关节位置组合成逻辑原子快照。
@@ -2698,12 +2940,14 @@ impl PiperContext {
             .joint_dynamic_monitor
             .try_store(JointDynamicMonitorSnapshot::from_complete(joint_dynamic));
         self.record_hot_snapshot_publish_skips(u64::from(!stored));
+        self.state_updates.notify();
     }
 
     /// 发布新的控制级关节动态状态。
     pub fn publish_control_joint_dynamic(&self, joint_dynamic: JointDynamicState) {
         let outcome = self.control_pair.publish_dynamic(joint_dynamic);
         self.record_control_pair_generation_invalidations(outcome.invalidated_generations);
+        self.state_updates.notify();
     }
 
     /// 发布新的原始关节动态状态。
@@ -2751,6 +2995,27 @@ pub struct AlignedMotionState {
 }
 
 impl AlignedMotionState {
+    pub(crate) fn from_control_pair(pair: &ControlPairSnapshot) -> Self {
+        let joint_position = pair.joint_position;
+        let joint_dynamic = pair.joint_dynamic;
+        Self {
+            joint_pos: joint_position.joint_pos,
+            joint_vel: joint_dynamic.joint_vel,
+            joint_current: joint_dynamic.joint_current,
+            position_timestamp_us: joint_position.hardware_timestamp_us,
+            dynamic_timestamp_us: joint_dynamic.group_timestamp_us,
+            position_host_rx_mono_us: joint_position.host_rx_mono_us,
+            dynamic_host_rx_mono_us: joint_dynamic.group_host_rx_mono_us,
+            position_raw_feedback_timing: joint_position.raw_feedback_timing,
+            dynamic_raw_feedback_timing: joint_dynamic.raw_feedback_timing,
+            position_frame_valid_mask: joint_position.frame_valid_mask,
+            dynamic_valid_mask: joint_dynamic.valid_mask,
+            dynamic_group_span_us: joint_dynamic.group_span_us(),
+            skew_us: (joint_dynamic.group_timestamp_us as i64)
+                - (joint_position.hardware_timestamp_us as i64),
+        }
+    }
+
     /// 位置反馈帧组是否完整（0x2A5-0x2A7 都已到达）。
     pub fn position_complete(&self) -> bool {
         self.position_frame_valid_mask == 0b0000_0111
@@ -2935,6 +3200,7 @@ mod tests {
             raw_feedback_timing: None,
             joint_pos: std::array::from_fn(|index| seq as f64 + index as f64),
             frame_valid_mask: mask,
+            consistency: StateConsistency::Consistent,
         }
     }
 
@@ -2969,6 +3235,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn robot_fault_latch_holds_until_cleared_and_notifies_on_change() {
+        let latch = RobotFaultLatch::new();
+        let notified = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = notified.clone();
+        latch.set_callback(Some(Arc::new(move |status| {
+            sink.lock().unwrap().push(status);
+        })));
+
+        latch.observe(RobotStatus::Normal);
+        latch.observe(RobotStatus::TeachExecute);
+        latch.observe(RobotStatus::NoSolution);
+        latch.observe(RobotStatus::Singularity);
+        assert_eq!(latch.get(), None);
+
+        latch.observe(RobotStatus::Collision);
+        latch.observe(RobotStatus::Collision);
+        latch.observe(RobotStatus::Normal);
+        assert_eq!(latch.get(), Some(RobotStatus::Collision));
+
+        latch.observe(RobotStatus::EmergencyStop);
+        assert_eq!(latch.get(), Some(RobotStatus::EmergencyStop));
+        assert_eq!(
+            *notified.lock().unwrap(),
+            vec![RobotStatus::Collision, RobotStatus::EmergencyStop]
+        );
+
+        latch.clear();
+        assert_eq!(latch.get(), None);
+    }
+
     #[test]
     fn raw_feedback_timing_defaults_to_none_and_selects_newest_by_host_time() {
         assert!(JointPositionState::default().raw_feedback_timing.is_none());
@@ -3354,6 +3651,7 @@ mod tests {
             raw_feedback_timing: None,
             joint_pos: [1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
             frame_valid_mask: 0b0000_0111, // Bit 0-2 全部为 1
+            consistency: StateConsistency::Consistent,
         };
         assert!(state.is_fully_valid());
 
@@ -3413,6 +3711,63 @@ mod tests {
         assert!(missing.contains(&2));
     }
 
+    #[test]
+    fn test_state_update_notifier_wakes_waiter_on_commit() {
+        let ctx = Arc::new(PiperContext::new());
+        let since = ctx.state_updates.generation();
+        assert_eq!(
+            ctx.state_updates.wait_since(since, Duration::from_millis(5)),
+            None
+        );
+
+        let publisher = {
+            let ctx = Arc::clone(&ctx);
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(20));
+                ctx.publish_joint_position(JointPositionState {
+                    frame_valid_mask: 0b111,
+                    ..Default::default()
+                });
+            })
+        };
+        let generation = ctx
+            .state_updates
+            .wait_since(since, Duration::from_secs(5))
+            .expect("commit should wake the waiter");
+        publisher.join().unwrap();
+
+        assert!(generation > since);
+        assert_eq!(
+            ctx.state_updates.wait_since(since, Duration::ZERO),
+            Some(generation),
+            "already-newer generation returns immediately"
+        );
+    }
+
+    #[test]
+    fn test_feedback_states_report_missed_can_ids() {
+        let joint_position = JointPositionState {
+            frame_valid_mask: 0b0000_0101, // 0x2A6（J3/J4）丢失
+            ..Default::default()
+        };
+        assert_eq!(joint_position.missed_ids(), vec![ID_JOINT_FEEDBACK_34]);
+
+        let end_pose = EndPoseState {
+            frame_valid_mask: 0b0000_0111,
+            ..Default::default()
+        };
+        assert!(end_pose.missed_ids().is_empty());
+
+        let joint_dynamic = JointDynamicState {
+            valid_mask: 0b011110,
+            ..Default::default()
+        };
+        assert_eq!(
+            joint_dynamic.missed_ids(),
+            vec![ID_JOINT_DRIVER_HIGH_SPEED_1, ID_JOINT_DRIVER_HIGH_SPEED_6]
+        );
+    }
+
     #[test]
     fn test_joint_position_state_clone() {
         let state = JointPositionState {
@@ -3421,6 +3776,7 @@ mod tests {
             raw_feedback_timing: None,
             joint_pos: [1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
             frame_valid_mask: 0b0000_0111,
+            consistency: StateConsistency::Consistent,
         };
         let cloned = state;
         assert_eq!(state.hardware_timestamp_us, cloned.hardware_timestamp_us);
@@ -3524,6 +3880,7 @@ mod tests {
                 raw_feedback_timing: None,
                 joint_pos: [1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
                 frame_valid_mask: 0b0000_0111,
+                consistency: StateConsistency::Consistent,
             },
             end_pose: EndPoseState {
                 hardware_timestamp_us: 1500,
@@ -3562,6 +3919,7 @@ mod tests {
             raw_feedback_timing: None,
             joint_pos: [1.0; 6],
             frame_valid_mask: 0b111,
+            consistency: StateConsistency::Consistent,
         };
         ctx.publish_joint_position(joint_position);
 
@@ -3676,6 +4034,7 @@ mod tests {
             raw_feedback_timing: None,
             joint_pos: [1.0; 6],
             frame_valid_mask: 0b111,
+            consistency: StateConsistency::Consistent,
         };
         ctx.publish_joint_position(complete);
 
@@ -3692,6 +4051,7 @@ mod tests {
             raw_feedback_timing: None,
             joint_pos: [2.0; 6],
             frame_valid_mask: 0b001,
+            consistency: StateConsistency::Consistent,
         });
 
         let second = ctx.capture_joint_position_monitor_snapshot();
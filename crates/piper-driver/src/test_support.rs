@@ -42,6 +42,7 @@ where
             backend_capability,
             config,
             realtime_slot,
+            Arc::new(crate::command::CommandMailbox::new()),
             soft_realtime_rx,
             shutdown_lane,
             reliable_rx,
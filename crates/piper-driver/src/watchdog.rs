@@ -0,0 +1,136 @@
+//! 软件看门狗：控制线程停止"喂狗"后由 TX 线程自动停机
+//!
+//! 控制线程通过 [`Piper::pet_watchdog`](crate::Piper::pet_watchdog) 定期喂狗；
+//! 第一次喂狗后看门狗才开始计时（armed）。TX 线程每轮循环检查一次，若距离上次
+//! 喂狗超过配置的间隔，则锁存 [`RuntimeFaultKind::WatchdogExpired`](crate::RuntimeFaultKind)
+//! （丢弃所有排队中的控制命令）并直接发送 [`WatchdogConfig::action`] 对应的停止帧，
+//! 避免机械臂在控制线程卡死时保持最后一帧力矩。
+//!
+//! 触发后看门狗自动解除计时，直到下一次喂狗；最近的低速反馈显示全部关节已失能时，
+//! 到期只解除计时而不停机。
+
+use crate::piper::DropStopAction;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// 看门狗配置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchdogConfig {
+    /// 两次喂狗之间允许的最长间隔
+    pub interval: Duration,
+    /// 到期后发送的停止动作（`DropStopAction::None` 表示只锁存故障）
+    pub action: DropStopAction,
+}
+
+impl WatchdogConfig {
+    pub fn new(interval: Duration, action: DropStopAction) -> Self {
+        Self { interval, action }
+    }
+}
+
+/// 看门狗运行时状态（RX/TX/控制线程共享，全部为原子操作）
+#[derive(Debug)]
+pub struct Watchdog {
+    /// 0 表示未配置
+    interval_us: u64,
+    action: DropStopAction,
+    /// 上次喂狗的主机单调时间（微秒）；0 表示未计时
+    last_pet_mono_us: AtomicU64,
+}
+
+impl Watchdog {
+    pub fn new(config: Option<WatchdogConfig>) -> Self {
+        let (interval_us, action) = match config {
+            Some(config) => ((config.interval.as_micros() as u64).max(1), config.action),
+            None => (0, DropStopAction::None),
+        };
+        Self {
+            interval_us,
+            action,
+            last_pet_mono_us: AtomicU64::new(0),
+        }
+    }
+
+    /// 是否配置了看门狗
+    pub fn is_enabled(&self) -> bool {
+        self.interval_us != 0
+    }
+
+    /// 是否正在计时（已喂狗且尚未触发）
+    pub fn is_armed(&self) -> bool {
+        self.is_enabled() && self.last_pet_mono_us.load(Ordering::Acquire) != 0
+    }
+
+    /// 喂狗；未配置看门狗时为空操作
+    pub fn pet(&self, now_mono_us: u64) {
+        if self.is_enabled() {
+            self.last_pet_mono_us.store(now_mono_us.max(1), Ordering::Release);
+        }
+    }
+
+    /// 解除计时，直到下一次喂狗
+    pub fn disarm(&self) {
+        self.last_pet_mono_us.store(0, Ordering::Release);
+    }
+
+    /// 到期检查：超时则解除计时并返回停止动作（每次到期只返回一次）
+    pub(crate) fn poll_expired(&self, now_mono_us: u64) -> Option<DropStopAction> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let last_pet = self.last_pet_mono_us.load(Ordering::Acquire);
+        if last_pet == 0 || now_mono_us.saturating_sub(last_pet) <= self.interval_us {
+            return None;
+        }
+        // 与并发喂狗竞争：只有在期间没有新的喂狗时才触发
+        self.last_pet_mono_us
+            .compare_exchange(last_pet, 0, Ordering::AcqRel, Ordering::Acquire)
+            .ok()
+            .map(|_| self.action)
+    }
+}
+
+impl Default for Watchdog {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unconfigured_watchdog_never_expires() {
+        let watchdog = Watchdog::default();
+        watchdog.pet(1_000);
+        assert!(!watchdog.is_armed());
+        assert_eq!(watchdog.poll_expired(u64::MAX), None);
+    }
+
+    #[test]
+    fn watchdog_expires_once_after_interval_without_pet() {
+        let watchdog = Watchdog::new(Some(WatchdogConfig::new(
+            Duration::from_millis(10),
+            DropStopAction::Disable,
+        )));
+        assert_eq!(
+            watchdog.poll_expired(1_000_000),
+            None,
+            "not armed before first pet"
+        );
+
+        watchdog.pet(1_000);
+        assert!(watchdog.is_armed());
+        assert_eq!(watchdog.poll_expired(11_000), None);
+        watchdog.pet(11_000);
+        assert_eq!(watchdog.poll_expired(21_000), None);
+        assert_eq!(watchdog.poll_expired(21_001), Some(DropStopAction::Disable));
+        assert!(!watchdog.is_armed());
+        assert_eq!(watchdog.poll_expired(100_000), None, "trips only once");
+
+        watchdog.pet(200_000);
+        watchdog.disarm();
+        assert_eq!(watchdog.poll_expired(1_000_000), None);
+    }
+}
@@ -12,6 +12,20 @@ pub const GRIPPER_POSITION_SCALE: f64 = 100.0;
 /// 将硬件值（N·m）转换为归一化值（0.0-1.0）
 pub const GRIPPER_FORCE_SCALE: f64 = 5.0;
 
+/// 夹爪行程上限（mm）
+///
+/// 与 `GRIPPER_POSITION_SCALE` 一致：归一化值 1.0 对应完全张开。
+pub const GRIPPER_TRAVEL_MAX_MM: f64 = GRIPPER_POSITION_SCALE;
+
+/// 夹爪扭矩上限（N·m）
+pub const GRIPPER_TORQUE_MAX_NM: f64 = GRIPPER_FORCE_SCALE;
+
+/// 关节控制指令（0x155-0x157）允许的角度绝对值上限（度）
+///
+/// 协议层只拦截明显非法的输入（NaN、超过一整圈）；
+/// 具体关节限位由上层 `SafetyLimits` 负责。
+pub const JOINT_CONTROL_MAX_ABS_DEG: f64 = 360.0;
+
 // 重新导出 CAN ID 常量（从 ids.rs）
 pub use crate::ids::{
     ID_CONTROL_MODE, ID_EMERGENCY_STOP, ID_GRIPPER_CONTROL, ID_JOINT_CONTROL_12,
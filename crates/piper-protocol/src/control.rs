@@ -3,7 +3,11 @@
 //! 包含所有控制指令帧的结构体，提供构建控制帧的方法
 //! 和转换为 `PiperFrame` 的方法。
 
-use crate::{CanData, PiperFrame, ProtocolError, i16_to_bytes_be, i32_to_bytes_be, ids::*};
+use crate::constants::{GRIPPER_TORQUE_MAX_NM, GRIPPER_TRAVEL_MAX_MM, JOINT_CONTROL_MAX_ABS_DEG};
+use crate::{
+    CanData, PiperFrame, ProtocolError, StandardCanId, bytes_to_i16_be, bytes_to_i32_be,
+    i16_to_bytes_be, i32_to_bytes_be, ids::*,
+};
 use bilge::prelude::*;
 use std::fmt;
 
@@ -330,11 +334,56 @@ mod tests {
 // 关节控制指令结构体
 // ============================================================================
 
+/// 校验物理量输入范围（含 NaN/Inf 检查）
+fn validate_physical_range(
+    field: &'static str,
+    value: f64,
+    min: f64,
+    max: f64,
+) -> Result<(), ProtocolError> {
+    if (min..=max).contains(&value) {
+        Ok(())
+    } else {
+        Err(ProtocolError::ValueOutOfRange {
+            field,
+            value,
+            min,
+            max,
+        })
+    }
+}
+
+/// 校验控制帧的 CAN ID 与数据长度（解码控制指令时使用）
+fn validate_control_frame(
+    frame: &PiperFrame,
+    expected_id: StandardCanId,
+    expected_len: u8,
+) -> Result<(), ProtocolError> {
+    if frame.id().as_standard() != Some(expected_id) {
+        return Err(ProtocolError::InvalidCanId { id: frame.raw_id() });
+    }
+    if frame.dlc() < expected_len {
+        return Err(ProtocolError::InvalidLength {
+            expected: expected_len as usize,
+            actual: frame.dlc() as usize,
+        });
+    }
+    Ok(())
+}
+
+fn read_i32_pair_be(frame: &PiperFrame) -> (i32, i32) {
+    let data = frame.data_padded();
+    (
+        bytes_to_i32_be([data[0], data[1], data[2], data[3]]),
+        bytes_to_i32_be([data[4], data[5], data[6], data[7]]),
+    )
+}
+
 /// 机械臂臂部关节控制指令12 (0x155)
 ///
 /// 用于控制 J1 和 J2 关节的目标角度。
 /// 单位：0.001°（原始值），可通过 `new()` 方法从物理量（度）创建。
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct JointControl12 {
     pub j1_deg: i32, // Byte 0-3: J1角度，单位 0.001°
     pub j2_deg: i32, // Byte 4-7: J2角度，单位 0.001°
@@ -352,6 +401,36 @@ impl JointControl12 {
         }
     }
 
+    /// 带范围校验的构造函数
+    ///
+    /// 角度（度）必须是有限值且绝对值不超过 `JOINT_CONTROL_MAX_ABS_DEG`，
+    /// 否则返回 `ProtocolError::ValueOutOfRange`。
+    pub fn try_new(j1: f64, j2: f64) -> Result<Self, ProtocolError> {
+        validate_physical_range(
+            "j1_deg",
+            j1,
+            -JOINT_CONTROL_MAX_ABS_DEG,
+            JOINT_CONTROL_MAX_ABS_DEG,
+        )?;
+        validate_physical_range(
+            "j2_deg",
+            j2,
+            -JOINT_CONTROL_MAX_ABS_DEG,
+            JOINT_CONTROL_MAX_ABS_DEG,
+        )?;
+        Ok(Self::new(j1, j2))
+    }
+
+    /// J1 目标角度（度）
+    pub fn j1(&self) -> f64 {
+        self.j1_deg as f64 / 1000.0
+    }
+
+    /// J2 目标角度（度）
+    pub fn j2(&self) -> f64 {
+        self.j2_deg as f64 / 1000.0
+    }
+
     /// 转换为 CAN 帧
     pub fn to_frame(self) -> PiperFrame {
         let mut data = [0u8; 8];
@@ -364,10 +443,20 @@ impl JointControl12 {
     }
 }
 
+impl TryFrom<PiperFrame> for JointControl12 {
+    type Error = ProtocolError;
+
+    fn try_from(frame: PiperFrame) -> Result<Self, Self::Error> {
+        validate_control_frame(&frame, ID_JOINT_CONTROL_12, 8)?;
+        let (j1_deg, j2_deg) = read_i32_pair_be(&frame);
+        Ok(Self { j1_deg, j2_deg })
+    }
+}
+
 /// 机械臂腕部关节控制指令34 (0x156)
 ///
 /// 用于控制 J3 和 J4 关节的目标角度。
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct JointControl34 {
     pub j3_deg: i32, // Byte 0-3: J3角度，单位 0.001°
     pub j4_deg: i32, // Byte 4-7: J4角度，单位 0.001°
@@ -385,6 +474,36 @@ impl JointControl34 {
         }
     }
 
+    /// 带范围校验的构造函数
+    ///
+    /// 角度（度）必须是有限值且绝对值不超过 `JOINT_CONTROL_MAX_ABS_DEG`，
+    /// 否则返回 `ProtocolError::ValueOutOfRange`。
+    pub fn try_new(j3: f64, j4: f64) -> Result<Self, ProtocolError> {
+        validate_physical_range(
+            "j3_deg",
+            j3,
+            -JOINT_CONTROL_MAX_ABS_DEG,
+            JOINT_CONTROL_MAX_ABS_DEG,
+        )?;
+        validate_physical_range(
+            "j4_deg",
+            j4,
+            -JOINT_CONTROL_MAX_ABS_DEG,
+            JOINT_CONTROL_MAX_ABS_DEG,
+        )?;
+        Ok(Self::new(j3, j4))
+    }
+
+    /// J3 目标角度（度）
+    pub fn j3(&self) -> f64 {
+        self.j3_deg as f64 / 1000.0
+    }
+
+    /// J4 目标角度（度）
+    pub fn j4(&self) -> f64 {
+        self.j4_deg as f64 / 1000.0
+    }
+
     /// 转换为 CAN 帧
     pub fn to_frame(self) -> PiperFrame {
         let mut data = [0u8; 8];
@@ -397,10 +516,20 @@ impl JointControl34 {
     }
 }
 
+impl TryFrom<PiperFrame> for JointControl34 {
+    type Error = ProtocolError;
+
+    fn try_from(frame: PiperFrame) -> Result<Self, Self::Error> {
+        validate_control_frame(&frame, ID_JOINT_CONTROL_34, 8)?;
+        let (j3_deg, j4_deg) = read_i32_pair_be(&frame);
+        Ok(Self { j3_deg, j4_deg })
+    }
+}
+
 /// 机械臂腕部关节控制指令56 (0x157)
 ///
 /// 用于控制 J5 和 J6 关节的目标角度。
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct JointControl56 {
     pub j5_deg: i32, // Byte 0-3: J5角度，单位 0.001°
     pub j6_deg: i32, // Byte 4-7: J6角度，单位 0.001°
@@ -418,6 +547,36 @@ impl JointControl56 {
         }
     }
 
+    /// 带范围校验的构造函数
+    ///
+    /// 角度（度）必须是有限值且绝对值不超过 `JOINT_CONTROL_MAX_ABS_DEG`，
+    /// 否则返回 `ProtocolError::ValueOutOfRange`。
+    pub fn try_new(j5: f64, j6: f64) -> Result<Self, ProtocolError> {
+        validate_physical_range(
+            "j5_deg",
+            j5,
+            -JOINT_CONTROL_MAX_ABS_DEG,
+            JOINT_CONTROL_MAX_ABS_DEG,
+        )?;
+        validate_physical_range(
+            "j6_deg",
+            j6,
+            -JOINT_CONTROL_MAX_ABS_DEG,
+            JOINT_CONTROL_MAX_ABS_DEG,
+        )?;
+        Ok(Self::new(j5, j6))
+    }
+
+    /// J5 目标角度（度）
+    pub fn j5(&self) -> f64 {
+        self.j5_deg as f64 / 1000.0
+    }
+
+    /// J6 目标角度（度）
+    pub fn j6(&self) -> f64 {
+        self.j6_deg as f64 / 1000.0
+    }
+
     /// 转换为 CAN 帧
     pub fn to_frame(self) -> PiperFrame {
         let mut data = [0u8; 8];
@@ -430,6 +589,16 @@ impl JointControl56 {
     }
 }
 
+impl TryFrom<PiperFrame> for JointControl56 {
+    type Error = ProtocolError;
+
+    fn try_from(frame: PiperFrame) -> Result<Self, Self::Error> {
+        validate_control_frame(&frame, ID_JOINT_CONTROL_56, 8)?;
+        let (j5_deg, j6_deg) = read_i32_pair_be(&frame);
+        Ok(Self { j5_deg, j6_deg })
+    }
+}
+
 #[cfg(test)]
 mod joint_control_tests {
     use super::*;
@@ -552,6 +721,56 @@ mod joint_control_tests {
         assert_eq!(j6_decoded, -90000);
     }
 
+    #[test]
+    fn test_joint_control_try_new_rejects_out_of_range() {
+        assert!(JointControl12::try_new(90.0, -45.0).is_ok());
+        assert!(matches!(
+            JointControl12::try_new(361.0, 0.0),
+            Err(ProtocolError::ValueOutOfRange {
+                field: "j1_deg",
+                ..
+            })
+        ));
+        assert!(matches!(
+            JointControl34::try_new(0.0, f64::NAN),
+            Err(ProtocolError::ValueOutOfRange {
+                field: "j4_deg",
+                ..
+            })
+        ));
+        assert!(matches!(
+            JointControl56::try_new(-400.0, 0.0),
+            Err(ProtocolError::ValueOutOfRange {
+                field: "j5_deg",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_joint_control_frame_roundtrip() {
+        let cmd12 = JointControl12::try_new(12.345, -67.891).unwrap();
+        assert_eq!(JointControl12::try_from(cmd12.to_frame()).unwrap(), cmd12);
+
+        let cmd34 = JointControl34::try_new(-0.001, 179.999).unwrap();
+        assert_eq!(JointControl34::try_from(cmd34.to_frame()).unwrap(), cmd34);
+
+        let cmd56 = JointControl56::try_new(75.0, -100.0).unwrap();
+        let decoded = JointControl56::try_from(cmd56.to_frame()).unwrap();
+        assert_eq!(decoded, cmd56);
+        assert_eq!(decoded.j5(), 75.0);
+        assert_eq!(decoded.j6(), -100.0);
+    }
+
+    #[test]
+    fn test_joint_control_decode_rejects_wrong_id() {
+        let frame = JointControl12::new(1.0, 2.0).to_frame();
+        assert!(matches!(
+            JointControl34::try_from(frame),
+            Err(ProtocolError::InvalidCanId { id: 0x155 })
+        ));
+    }
+
     #[test]
     fn test_joint_control_precision() {
         // 测试精度：0.5° = 500 (0.001° 单位)
@@ -723,6 +942,18 @@ impl EmergencyStopCommand {
         }
     }
 
+    /// 创建拖动示教指令
+    pub fn teach(teach_command: TeachCommand) -> Self {
+        Self {
+            emergency_stop: EmergencyStopAction::Invalid,
+            trajectory_command: TrajectoryCommand::Closed,
+            teach_command,
+            trajectory_index: 0,
+            name_index: 0,
+            crc16: 0,
+        }
+    }
+
     /// 创建轨迹传输指令（用于离线轨迹模式）
     pub fn trajectory_transmit(trajectory_index: u8, name_index: u16, crc16: u16) -> Self {
         Self {
@@ -842,6 +1073,14 @@ mod emergency_stop_tests {
         assert_eq!(name_index, 0x1234);
         assert_eq!(crc16, 0x5678);
     }
+
+    #[test]
+    fn test_emergency_stop_command_teach() {
+        let frame = EmergencyStopCommand::teach(TeachCommand::StartRecord).to_frame();
+
+        assert_eq!(frame.id().as_standard(), Some(ID_EMERGENCY_STOP));
+        assert_eq!(frame.data(), &[0x00, 0x00, 0x01, 0, 0, 0, 0, 0]);
+    }
 }
 
 // ============================================================================
@@ -958,7 +1197,7 @@ mod motor_enable_tests {
 /// - Bit 1: 置1清除错误
 /// - Bit 2-7: 保留
 #[bitsize(8)]
-#[derive(FromBits, DebugBits, Clone, Copy, Default)]
+#[derive(FromBits, DebugBits, Clone, Copy, Default, PartialEq, Eq)]
 pub struct GripperControlFlags {
     pub enable: bool,      // Bit 0: 置1使能，0失能
     pub clear_error: bool, // Bit 1: 置1清除错误
@@ -970,7 +1209,7 @@ pub struct GripperControlFlags {
 /// 用于控制夹爪的行程、扭矩、使能状态和零点设置。
 /// - 行程单位：0.001mm（原始值），0值表示完全闭合
 /// - 扭矩单位：0.001N·m（原始值）
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct GripperControlCommand {
     pub travel_mm: i32, // Byte 0-3: 夹爪行程，单位 0.001mm（0值表示完全闭合）
     pub torque_nm: i16, // Byte 4-5: 夹爪扭矩，单位 0.001N·m
@@ -993,6 +1232,28 @@ impl GripperControlCommand {
         }
     }
 
+    /// 带范围校验的构造函数
+    ///
+    /// - `travel_mm`: `0.0 ..= GRIPPER_TRAVEL_MAX_MM`
+    /// - `torque_nm`: `0.0 ..= GRIPPER_TORQUE_MAX_NM`
+    ///
+    /// 超出范围（或为 NaN）时返回 `ProtocolError::ValueOutOfRange`。
+    pub fn try_new(travel_mm: f64, torque_nm: f64, enable: bool) -> Result<Self, ProtocolError> {
+        validate_physical_range("travel_mm", travel_mm, 0.0, GRIPPER_TRAVEL_MAX_MM)?;
+        validate_physical_range("torque_nm", torque_nm, 0.0, GRIPPER_TORQUE_MAX_NM)?;
+        Ok(Self::new(travel_mm, torque_nm, enable))
+    }
+
+    /// 夹爪行程（mm）
+    pub fn travel(&self) -> f64 {
+        self.travel_mm as f64 / 1000.0
+    }
+
+    /// 夹爪扭矩（N·m）
+    pub fn torque(&self) -> f64 {
+        self.torque_nm as f64 / 1000.0
+    }
+
     /// 设置零点（设置当前为零点）
     pub fn set_zero_point(mut self) -> Self {
         self.zero_setting = 0xAE;
@@ -1029,6 +1290,21 @@ impl GripperControlCommand {
     }
 }
 
+impl TryFrom<PiperFrame> for GripperControlCommand {
+    type Error = ProtocolError;
+
+    fn try_from(frame: PiperFrame) -> Result<Self, Self::Error> {
+        validate_control_frame(&frame, ID_GRIPPER_CONTROL, 8)?;
+        let data = frame.data_padded();
+        Ok(Self {
+            travel_mm: bytes_to_i32_be([data[0], data[1], data[2], data[3]]),
+            torque_nm: bytes_to_i16_be([data[4], data[5]]),
+            control_flags: GripperControlFlags::from(u8::new(data[6])),
+            zero_setting: data[7],
+        })
+    }
+}
+
 #[cfg(test)]
 mod gripper_control_tests {
     use super::*;
@@ -1100,6 +1376,45 @@ mod gripper_control_tests {
         assert_eq!(frame.data()[6] & 0x03, 0x03); // Bit 0 和 Bit 1 都是 1
     }
 
+    #[test]
+    fn test_gripper_control_command_try_new_validates_range() {
+        assert!(GripperControlCommand::try_new(0.0, 0.0, true).is_ok());
+        assert!(
+            GripperControlCommand::try_new(GRIPPER_TRAVEL_MAX_MM, GRIPPER_TORQUE_MAX_NM, true)
+                .is_ok()
+        );
+        assert!(matches!(
+            GripperControlCommand::try_new(-1.0, 1.0, true),
+            Err(ProtocolError::ValueOutOfRange {
+                field: "travel_mm",
+                ..
+            })
+        ));
+        assert!(matches!(
+            GripperControlCommand::try_new(50.0, 5.5, true),
+            Err(ProtocolError::ValueOutOfRange {
+                field: "torque_nm",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_gripper_control_command_frame_roundtrip() {
+        let commands = [
+            GripperControlCommand::try_new(50.0, 2.5, true).unwrap(),
+            GripperControlCommand::try_new(12.5, 1.0, true).unwrap().clear_error(),
+            GripperControlCommand::new(0.0, 0.0, false).set_zero_point(),
+        ];
+        for cmd in commands {
+            let decoded = GripperControlCommand::try_from(cmd.to_frame()).unwrap();
+            assert_eq!(decoded, cmd);
+            assert_eq!(decoded.to_frame(), cmd.to_frame());
+        }
+        assert_eq!(commands[0].travel(), 50.0);
+        assert_eq!(commands[0].torque(), 2.5);
+    }
+
     #[test]
     fn test_gripper_control_command_fully_closed() {
         // 测试完全闭合（travel = 0）
@@ -1786,6 +2101,92 @@ impl MitControlCommand {
 
         PiperFrame::standard(mit_control_id(self.joint_index), CanData::from_array(data))
     }
+
+    /// 解码单个字段，保证重新编码得到相同的整数值
+    ///
+    /// `float_to_uint` 使用截断，`uint_to_float` 的浮点误差可能让结果落在
+    /// 上一个量化区间；此时向上微调到区间内。
+    fn decode_field(x_int: u32, x_min: f32, x_max: f32, bits: u32) -> f32 {
+        let mut value = Self::uint_to_float(x_int, x_min, x_max, bits);
+        while Self::float_to_uint(value, x_min, x_max, bits) < x_int {
+            value = value.next_up();
+        }
+        value
+    }
+
+    /// 关节序号 [1, 6]
+    pub fn joint_index(&self) -> JointIndex {
+        self.joint_index
+    }
+
+    /// 位置参考值（弧度）
+    pub fn pos_ref(&self) -> f32 {
+        self.pos_ref
+    }
+
+    /// 速度参考值（弧度/秒）
+    pub fn vel_ref(&self) -> f32 {
+        self.vel_ref
+    }
+
+    /// 比例增益
+    pub fn kp(&self) -> f32 {
+        self.kp
+    }
+
+    /// 微分增益
+    pub fn kd(&self) -> f32 {
+        self.kd
+    }
+
+    /// 力矩参考值（牛顿·米）
+    pub fn t_ref(&self) -> f32 {
+        self.t_ref
+    }
+}
+
+/// 从 CAN 帧解码 MIT 控制指令
+///
+/// 与 `to_frame` 对称：按相同的跨字节位域布局解包，并校验 4 位 CRC。
+/// 由于量化误差，解码得到的浮点值与原始输入存在最多一个量化步长的差异，
+/// 但重新编码后的帧与原始帧逐字节一致。
+impl TryFrom<PiperFrame> for MitControlCommand {
+    type Error = ProtocolError;
+
+    fn try_from(frame: PiperFrame) -> Result<Self, Self::Error> {
+        let joint_index = frame
+            .id()
+            .as_standard()
+            .and_then(mit_control_joint_index)
+            .ok_or(ProtocolError::InvalidCanId { id: frame.raw_id() })?;
+        validate_control_frame(&frame, mit_control_id(joint_index), 8)?;
+
+        let data = frame.data_padded();
+        let crc = Self::calculate_crc(data[0..7].try_into().unwrap(), joint_index.get());
+        if data[7] & 0x0F != crc {
+            return Err(ProtocolError::ParseError(format!(
+                "MIT control CRC mismatch for J{}: expected 0x{:X}, got 0x{:X}",
+                joint_index.get(),
+                crc,
+                data[7] & 0x0F
+            )));
+        }
+
+        let pos_ref_uint = ((data[0] as u32) << 8) | data[1] as u32;
+        let vel_ref_uint = ((data[2] as u32) << 4) | (data[3] as u32 >> 4);
+        let kp_uint = (((data[3] & 0x0F) as u32) << 8) | data[4] as u32;
+        let kd_uint = ((data[5] as u32) << 4) | (data[6] as u32 >> 4);
+        let t_ref_uint = (((data[6] & 0x0F) as u32) << 4) | (data[7] as u32 >> 4);
+
+        Ok(Self {
+            joint_index,
+            pos_ref: Self::decode_field(pos_ref_uint, Self::P_MIN, Self::P_MAX, 16),
+            vel_ref: Self::decode_field(vel_ref_uint, Self::V_MIN, Self::V_MAX, 12),
+            kp: Self::decode_field(kp_uint, Self::KP_MIN, Self::KP_MAX, 12),
+            kd: Self::decode_field(kd_uint, Self::KD_MIN, Self::KD_MAX, 12),
+            t_ref: Self::decode_field(t_ref_uint, Self::T_MIN, Self::T_MAX, 8),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -1950,6 +2351,58 @@ mod mit_control_tests {
         assert!((float_val - original).abs() < 0.01);
     }
 
+    #[test]
+    fn test_mit_control_command_frame_roundtrip() {
+        for joint_index in 1..=6 {
+            let cmd = MitControlCommand::try_new(joint_index, 1.25, -3.5, 10.0, 0.8, -2.0).unwrap();
+            let frame = cmd.to_frame();
+            let decoded = MitControlCommand::try_from(frame).unwrap();
+
+            assert_eq!(decoded.joint_index().get(), joint_index);
+            assert_eq!(decoded.to_frame(), frame);
+            assert!((decoded.pos_ref() - 1.25).abs() < 0.001);
+            assert!((decoded.vel_ref() - -3.5).abs() < 0.03);
+            assert!((decoded.kp() - 10.0).abs() < 0.2);
+            assert!((decoded.kd() - 0.8).abs() < 0.01);
+            assert!((decoded.t_ref() - -2.0).abs() < 0.07);
+        }
+    }
+
+    #[test]
+    fn test_mit_control_decode_reencodes_every_position_code() {
+        for code in 0..=u16::MAX {
+            let mut data = [0u8; 8];
+            data[0..2].copy_from_slice(&code.to_be_bytes());
+            let crc = MitControlCommand::calculate_crc(data[0..7].try_into().unwrap(), 1);
+            data[7] |= crc;
+            let frame = PiperFrame::standard(ID_MIT_CONTROL_1, CanData::from_array(data));
+
+            let decoded = MitControlCommand::try_from(frame).unwrap();
+            assert_eq!(decoded.to_frame(), frame, "position code {code}");
+        }
+    }
+
+    #[test]
+    fn test_mit_control_decode_rejects_bad_crc() {
+        let cmd = MitControlCommand::try_new(2, 0.5, 0.0, 10.0, 0.8, 0.0).unwrap();
+        let good_crc = cmd.to_frame().data()[7] & 0x0F;
+        let frame = cmd.to_frame_with_custom_crc(good_crc ^ 0x01);
+
+        assert!(matches!(
+            MitControlCommand::try_from(frame),
+            Err(ProtocolError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_mit_control_decode_rejects_non_mit_id() {
+        let frame = JointControl12::new(0.0, 0.0).to_frame();
+        assert!(matches!(
+            MitControlCommand::try_from(frame),
+            Err(ProtocolError::InvalidCanId { id: 0x155 })
+        ));
+    }
+
     #[test]
     fn test_mit_control_command_joint_ids_cover_full_range() {
         for joint_index in 1..=6 {
@@ -5,7 +5,8 @@
 
 use crate::control::{ControlModeCommand, InstallPosition, MitMode};
 use crate::{
-    JointIndex, PiperFrame, ProtocolError, StandardCanId, bytes_to_i16_be, bytes_to_i32_be, ids::*,
+    CanData, JointIndex, PiperFrame, ProtocolError, StandardCanId, bytes_to_i16_be,
+    bytes_to_i32_be, i16_to_bytes_be, i32_to_bytes_be, ids::*,
 };
 use bilge::prelude::*;
 
@@ -96,6 +97,28 @@ pub enum RobotStatus {
     ResistorOverTemp = 0x0F,
 }
 
+impl RobotStatus {
+    /// 是否为故障状态（正常与示教记录/执行/暂停之外的状态）
+    pub fn is_fault(self) -> bool {
+        !matches!(
+            self,
+            Self::Normal | Self::TeachRecord | Self::TeachExecute | Self::TeachPause
+        )
+    }
+
+    /// 是否为需要锁存的硬故障
+    ///
+    /// 无解、奇异点与拖动示教超速是固件针对单次不可达目标（末端位姿、圆弧）或示教速度
+    /// 给出的瞬时提示，下一条可达目标即恢复，不属于需要重新使能才能解除的故障。
+    pub fn is_latching_fault(self) -> bool {
+        self.is_fault()
+            && !matches!(
+                self,
+                Self::NoSolution | Self::Singularity | Self::TeachOverspeed
+            )
+    }
+}
+
 /// MOVE 模式
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, num_enum::FromPrimitive)]
 #[repr(u8)]
@@ -168,7 +191,7 @@ pub enum MotionStatus {
 /// 对于**单个字节内的位域**，协议明确 Bit 0 对应 1号关节，这是 LSB first（小端位序）。
 /// bilge 默认使用 LSB first 位序，与协议要求一致。
 #[bitsize(8)]
-#[derive(FromBits, DebugBits, Clone, Copy, Default)]
+#[derive(FromBits, DebugBits, Clone, Copy, Default, PartialEq, Eq)]
 pub struct FaultCodeAngleLimit {
     pub joint1_limit: bool, // Bit 0: 1号关节角度超限位
     pub joint2_limit: bool, // Bit 1: 2号关节角度超限位
@@ -190,7 +213,7 @@ pub struct FaultCodeAngleLimit {
 /// - Bit 5: 6号关节通信异常
 /// - Bit 6-7: 保留
 #[bitsize(8)]
-#[derive(FromBits, DebugBits, Clone, Copy, Default)]
+#[derive(FromBits, DebugBits, Clone, Copy, Default, PartialEq, Eq)]
 pub struct FaultCodeCommError {
     pub joint1_comm_error: bool, // Bit 0: 1号关节通信异常
     pub joint2_comm_error: bool, // Bit 1: 2号关节通信异常
@@ -209,7 +232,7 @@ pub struct FaultCodeCommError {
 ///
 /// 包含控制模式、机械臂状态、MOVE 模式、示教状态、运动状态、
 /// 轨迹点索引以及故障码位域。
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct RobotStatusFeedback {
     pub control_mode: ControlMode,                   // Byte 0
     pub robot_status: RobotStatus,                   // Byte 1
@@ -260,7 +283,7 @@ impl TryFrom<PiperFrame> for RobotStatusFeedback {
 ///
 /// 包含 J1 和 J2 关节的角度反馈。
 /// 单位：0.001°（原始值），可通过方法转换为度或弧度。
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct JointFeedback12 {
     pub j1_deg: i32, // Byte 0-3: J1角度，单位 0.001°
     pub j2_deg: i32, // Byte 4-7: J2角度，单位 0.001°
@@ -341,7 +364,7 @@ impl TryFrom<PiperFrame> for JointFeedback12 {
 ///
 /// 包含 J3 和 J4 关节的角度反馈。
 /// 单位：0.001°（原始值），可通过方法转换为度或弧度。
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct JointFeedback34 {
     pub j3_deg: i32, // Byte 0-3: J3角度，单位 0.001°
     pub j4_deg: i32, // Byte 4-7: J4角度，单位 0.001°
@@ -422,7 +445,7 @@ impl TryFrom<PiperFrame> for JointFeedback34 {
 ///
 /// 包含 J5 和 J6 关节的角度反馈。
 /// 单位：0.001°（原始值），可通过方法转换为度或弧度。
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct JointFeedback56 {
     pub j5_deg: i32, // Byte 0-3: J5角度，单位 0.001°
     pub j6_deg: i32, // Byte 4-7: J6角度，单位 0.001°
@@ -507,7 +530,7 @@ impl TryFrom<PiperFrame> for JointFeedback56 {
 ///
 /// 包含 X 和 Y 坐标反馈。
 /// 单位：0.001mm（原始值），可通过方法转换为 mm。
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct EndPoseFeedback1 {
     pub x_mm: i32, // Byte 0-3: X坐标，单位 0.001mm
     pub y_mm: i32, // Byte 4-7: Y坐标，单位 0.001mm
@@ -578,7 +601,7 @@ impl TryFrom<PiperFrame> for EndPoseFeedback1 {
 ///
 /// 包含 Z 坐标和 RX 角度反馈。
 /// 单位：Z 为 0.001mm（原始值），RX 为 0.001°（原始值）。
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct EndPoseFeedback2 {
     pub z_mm: i32,   // Byte 0-3: Z坐标，单位 0.001mm
     pub rx_deg: i32, // Byte 4-7: RX角度，单位 0.001°
@@ -654,7 +677,7 @@ impl TryFrom<PiperFrame> for EndPoseFeedback2 {
 ///
 /// 包含 RY 和 RZ 角度反馈。
 /// 单位：0.001°（原始值），可通过方法转换为度或弧度。
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct EndPoseFeedback3 {
     pub ry_deg: i32, // Byte 0-3: RY角度，单位 0.001°
     pub rz_deg: i32, // Byte 4-7: RZ角度，单位 0.001°
@@ -743,7 +766,7 @@ impl TryFrom<PiperFrame> for EndPoseFeedback3 {
 /// - 位置单位：rad（原始值）
 ///
 /// 注意：关节索引从 CAN ID 推导（0x251 -> 1, 0x252 -> 2, ..., 0x256 -> 6）
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct JointDriverHighSpeedFeedback {
     pub joint_index: u8,  // 从 ID 推导：0x251 -> 1, 0x252 -> 2, ...
     pub speed_rad_s: i16, // Byte 0-1: 速度，单位 0.001rad/s
@@ -935,7 +958,7 @@ impl TryFrom<PiperFrame> for JointDriverHighSpeedFeedback {
 /// - Bit 6: 驱动器使能状态（0：失能 1：使能）
 /// - Bit 7: 堵转保护状态（0：正常 1：触发保护）
 #[bitsize(8)]
-#[derive(FromBits, DebugBits, Clone, Copy, Default)]
+#[derive(FromBits, DebugBits, Clone, Copy, Default, PartialEq, Eq)]
 pub struct DriverStatus {
     pub voltage_low: bool,          // Bit 0: 0正常 1过低
     pub motor_over_temp: bool,      // Bit 1: 0正常 1过温
@@ -953,7 +976,7 @@ pub struct DriverStatus {
 /// - 电压单位：0.1V（原始值）
 /// - 温度单位：1℃（原始值）
 /// - 电流单位：0.001A（原始值）
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct JointDriverLowSpeedFeedback {
     pub joint_index: u8,      // 从 ID 推导：0x261 -> 1, 0x262 -> 2, ...
     pub voltage: u16,         // Byte 0-1: 电压，单位 0.1V
@@ -1059,7 +1082,7 @@ impl TryFrom<PiperFrame> for JointDriverLowSpeedFeedback {
 /// - 角加速度单位：0.001rad/s²（原始值）
 ///
 /// 注意：这是"末端"速度和加速度，不是关节本身的速度和加速度。
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct JointEndVelocityAccelFeedback {
     pub joint_index: u8,                 // 从 ID 推导：0x481 -> 1, 0x482 -> 2, ...
     pub linear_velocity_m_s_raw: u16,    // Byte 0-1: 末端线速度，单位 0.001m/s
@@ -1164,7 +1187,7 @@ impl TryFrom<PiperFrame> for JointEndVelocityAccelFeedback {
 /// - Bit 6: 驱动器使能状态（**1：使能 0：失能**，注意：反向逻辑）
 /// - Bit 7: 回零状态（0：没有回零 1：已经回零）
 #[bitsize(8)]
-#[derive(FromBits, DebugBits, Clone, Copy, Default)]
+#[derive(FromBits, DebugBits, Clone, Copy, Default, PartialEq, Eq)]
 pub struct GripperStatus {
     pub voltage_low: bool,         // Bit 0: 0正常 1过低
     pub motor_over_temp: bool,     // Bit 1: 0正常 1过温
@@ -1181,7 +1204,7 @@ pub struct GripperStatus {
 /// 包含夹爪行程、扭矩和状态反馈。
 /// - 行程单位：0.001mm（原始值）
 /// - 扭矩单位：0.001N·m（原始值）
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct GripperFeedback {
     pub travel_mm: i32, // Byte 0-3: 单位 0.001mm
     pub torque_nm: i16, // Byte 4-5: 单位 0.001N·m（牛·米）
@@ -1251,6 +1274,316 @@ impl TryFrom<PiperFrame> for GripperFeedback {
     }
 }
 
+// ============================================================================
+// 反馈帧编码（用于硬件仿真 / mock 适配器）
+// ============================================================================
+//
+// 与上面的 `TryFrom<PiperFrame>` 解析逻辑对称：
+// 多字节字段按大端字节序写回，位域按 bilge 的 LSB first 位序打包。
+
+fn feedback_frame(id: StandardCanId, data: [u8; 8]) -> PiperFrame {
+    PiperFrame::standard(id, CanData::from_array(data))
+}
+
+fn i32_pair_be(first: i32, second: i32) -> [u8; 8] {
+    let mut data = [0u8; 8];
+    data[0..4].copy_from_slice(&i32_to_bytes_be(first));
+    data[4..8].copy_from_slice(&i32_to_bytes_be(second));
+    data
+}
+
+impl From<RobotStatusFeedback> for PiperFrame {
+    fn from(feedback: RobotStatusFeedback) -> Self {
+        feedback_frame(
+            ID_ROBOT_STATUS,
+            [
+                feedback.control_mode as u8,
+                feedback.robot_status as u8,
+                feedback.move_mode as u8,
+                feedback.teach_status as u8,
+                feedback.motion_status as u8,
+                feedback.trajectory_point_index,
+                u8::from(feedback.fault_code_angle_limit).value(),
+                u8::from(feedback.fault_code_comm_error).value(),
+            ],
+        )
+    }
+}
+
+impl From<JointFeedback12> for PiperFrame {
+    fn from(feedback: JointFeedback12) -> Self {
+        feedback_frame(
+            ID_JOINT_FEEDBACK_12,
+            i32_pair_be(feedback.j1_deg, feedback.j2_deg),
+        )
+    }
+}
+
+impl From<JointFeedback34> for PiperFrame {
+    fn from(feedback: JointFeedback34) -> Self {
+        feedback_frame(
+            ID_JOINT_FEEDBACK_34,
+            i32_pair_be(feedback.j3_deg, feedback.j4_deg),
+        )
+    }
+}
+
+impl From<JointFeedback56> for PiperFrame {
+    fn from(feedback: JointFeedback56) -> Self {
+        feedback_frame(
+            ID_JOINT_FEEDBACK_56,
+            i32_pair_be(feedback.j5_deg, feedback.j6_deg),
+        )
+    }
+}
+
+impl From<EndPoseFeedback1> for PiperFrame {
+    fn from(feedback: EndPoseFeedback1) -> Self {
+        feedback_frame(ID_END_POSE_1, i32_pair_be(feedback.x_mm, feedback.y_mm))
+    }
+}
+
+impl From<EndPoseFeedback2> for PiperFrame {
+    fn from(feedback: EndPoseFeedback2) -> Self {
+        feedback_frame(ID_END_POSE_2, i32_pair_be(feedback.z_mm, feedback.rx_deg))
+    }
+}
+
+impl From<EndPoseFeedback3> for PiperFrame {
+    fn from(feedback: EndPoseFeedback3) -> Self {
+        feedback_frame(ID_END_POSE_3, i32_pair_be(feedback.ry_deg, feedback.rz_deg))
+    }
+}
+
+/// 关节驱动器反馈的 CAN ID 由 `joint_index` 决定，非法序号无法编码。
+impl TryFrom<JointDriverHighSpeedFeedback> for PiperFrame {
+    type Error = ProtocolError;
+
+    fn try_from(feedback: JointDriverHighSpeedFeedback) -> Result<Self, Self::Error> {
+        let joint = JointIndex::new(feedback.joint_index)?;
+        let mut data = [0u8; 8];
+        data[0..2].copy_from_slice(&i16_to_bytes_be(feedback.speed_rad_s));
+        data[2..4].copy_from_slice(&i16_to_bytes_be(feedback.current_a));
+        data[4..8].copy_from_slice(&i32_to_bytes_be(feedback.position_rad));
+        Ok(feedback_frame(joint_driver_high_speed_id(joint), data))
+    }
+}
+
+impl TryFrom<JointDriverLowSpeedFeedback> for PiperFrame {
+    type Error = ProtocolError;
+
+    fn try_from(feedback: JointDriverLowSpeedFeedback) -> Result<Self, Self::Error> {
+        let joint = JointIndex::new(feedback.joint_index)?;
+        let mut data = [0u8; 8];
+        data[0..2].copy_from_slice(&feedback.voltage.to_be_bytes());
+        data[2..4].copy_from_slice(&i16_to_bytes_be(feedback.driver_temp));
+        data[4] = feedback.motor_temp as u8;
+        data[5] = u8::from(feedback.status).value();
+        data[6..8].copy_from_slice(&feedback.bus_current.to_be_bytes());
+        Ok(feedback_frame(joint_driver_low_speed_id(joint), data))
+    }
+}
+
+impl TryFrom<JointEndVelocityAccelFeedback> for PiperFrame {
+    type Error = ProtocolError;
+
+    fn try_from(feedback: JointEndVelocityAccelFeedback) -> Result<Self, Self::Error> {
+        let joint = JointIndex::new(feedback.joint_index)?;
+        let mut data = [0u8; 8];
+        data[0..2].copy_from_slice(&feedback.linear_velocity_m_s_raw.to_be_bytes());
+        data[2..4].copy_from_slice(&feedback.angular_velocity_rad_s_raw.to_be_bytes());
+        data[4..6].copy_from_slice(&feedback.linear_accel_m_s2_raw.to_be_bytes());
+        data[6..8].copy_from_slice(&feedback.angular_accel_rad_s2_raw.to_be_bytes());
+        Ok(feedback_frame(joint_end_velocity_accel_id(joint), data))
+    }
+}
+
+impl From<GripperFeedback> for PiperFrame {
+    fn from(feedback: GripperFeedback) -> Self {
+        let mut data = [0u8; 8];
+        data[0..4].copy_from_slice(&i32_to_bytes_be(feedback.travel_mm));
+        data[4..6].copy_from_slice(&i16_to_bytes_be(feedback.torque_nm));
+        data[6] = u8::from(feedback.status).value();
+        // Byte 7: 保留，填 0
+        feedback_frame(ID_GRIPPER_FEEDBACK, data)
+    }
+}
+
+#[cfg(test)]
+mod encode_roundtrip_tests {
+    use super::*;
+
+    #[test]
+    fn test_robot_status_feedback_roundtrip() {
+        let mut angle_limit = FaultCodeAngleLimit::from(u8::new(0));
+        angle_limit.set_joint2_limit(true);
+        angle_limit.set_joint6_limit(true);
+        let mut comm_error = FaultCodeCommError::from(u8::new(0));
+        comm_error.set_joint1_comm_error(true);
+
+        let fb = RobotStatusFeedback {
+            control_mode: ControlMode::OfflineTrajectory,
+            robot_status: RobotStatus::Collision,
+            move_mode: MoveMode::MoveM,
+            teach_status: TeachStatus::Execute,
+            motion_status: MotionStatus::NotArrived,
+            trajectory_point_index: 42,
+            fault_code_angle_limit: angle_limit,
+            fault_code_comm_error: comm_error,
+        };
+
+        let frame = PiperFrame::from(fb);
+        assert_eq!(frame.id().as_standard(), Some(ID_ROBOT_STATUS));
+        assert_eq!(
+            frame.data(),
+            &[0x07, 0x07, 0x04, 0x03, 0x01, 42, 0b0010_0010, 0b0000_0001]
+        );
+        assert_eq!(RobotStatusFeedback::try_from(frame).unwrap(), fb);
+    }
+
+    #[test]
+    fn test_joint_feedback_roundtrip() {
+        let fb12 = JointFeedback12 {
+            j1_deg: 90_000,
+            j2_deg: -45_123,
+        };
+        let fb34 = JointFeedback34 {
+            j3_deg: i32::MIN,
+            j4_deg: i32::MAX,
+        };
+        let fb56 = JointFeedback56 {
+            j5_deg: -1,
+            j6_deg: 0,
+        };
+
+        let frame12 = PiperFrame::from(fb12);
+        assert_eq!(&frame12.data()[0..4], &90_000i32.to_be_bytes());
+        assert_eq!(JointFeedback12::try_from(frame12).unwrap(), fb12);
+        assert_eq!(
+            JointFeedback34::try_from(PiperFrame::from(fb34)).unwrap(),
+            fb34
+        );
+        assert_eq!(
+            JointFeedback56::try_from(PiperFrame::from(fb56)).unwrap(),
+            fb56
+        );
+    }
+
+    #[test]
+    fn test_end_pose_feedback_roundtrip() {
+        let fb1 = EndPoseFeedback1 {
+            x_mm: 250_000,
+            y_mm: -12_345,
+        };
+        let fb2 = EndPoseFeedback2 {
+            z_mm: 400_000,
+            rx_deg: 179_999,
+        };
+        let fb3 = EndPoseFeedback3 {
+            ry_deg: -90_000,
+            rz_deg: 1,
+        };
+
+        assert_eq!(
+            EndPoseFeedback1::try_from(PiperFrame::from(fb1)).unwrap(),
+            fb1
+        );
+        assert_eq!(
+            EndPoseFeedback2::try_from(PiperFrame::from(fb2)).unwrap(),
+            fb2
+        );
+        assert_eq!(
+            EndPoseFeedback3::try_from(PiperFrame::from(fb3)).unwrap(),
+            fb3
+        );
+    }
+
+    #[test]
+    fn test_joint_driver_feedback_roundtrip_all_joints() {
+        for joint_index in 1..=6 {
+            let high = JointDriverHighSpeedFeedback {
+                joint_index,
+                speed_rad_s: -1234,
+                current_a: 5678,
+                position_rad: -3_141_592,
+            };
+            let frame = PiperFrame::try_from(high).unwrap();
+            assert_eq!(
+                frame.raw_id(),
+                ID_JOINT_DRIVER_HIGH_SPEED_1.raw() as u32 + joint_index as u32 - 1
+            );
+            assert_eq!(JointDriverHighSpeedFeedback::try_from(frame).unwrap(), high);
+
+            let mut status = DriverStatus::from(u8::new(0));
+            status.set_enabled(true);
+            status.set_motor_over_temp(true);
+            let low = JointDriverLowSpeedFeedback {
+                joint_index,
+                voltage: 245,
+                driver_temp: -5,
+                motor_temp: -20,
+                status,
+                bus_current: 1500,
+            };
+            let frame = PiperFrame::try_from(low).unwrap();
+            assert_eq!(frame.data()[5], 0b0100_0010);
+            assert_eq!(JointDriverLowSpeedFeedback::try_from(frame).unwrap(), low);
+
+            let accel = JointEndVelocityAccelFeedback {
+                joint_index,
+                linear_velocity_m_s_raw: 1,
+                angular_velocity_rad_s_raw: 2,
+                linear_accel_m_s2_raw: 3,
+                angular_accel_rad_s2_raw: u16::MAX,
+            };
+            let frame = PiperFrame::try_from(accel).unwrap();
+            assert_eq!(
+                JointEndVelocityAccelFeedback::try_from(frame).unwrap(),
+                accel
+            );
+        }
+    }
+
+    #[test]
+    fn test_joint_driver_feedback_rejects_invalid_joint_index() {
+        let high = JointDriverHighSpeedFeedback {
+            joint_index: 0,
+            ..Default::default()
+        };
+        assert!(matches!(
+            PiperFrame::try_from(high),
+            Err(ProtocolError::InvalidJointIndex { joint_index: 0 })
+        ));
+
+        let low = JointDriverLowSpeedFeedback {
+            joint_index: 7,
+            ..Default::default()
+        };
+        assert!(matches!(
+            PiperFrame::try_from(low),
+            Err(ProtocolError::InvalidJointIndex { joint_index: 7 })
+        ));
+    }
+
+    #[test]
+    fn test_gripper_feedback_roundtrip() {
+        let mut status = GripperStatus::from(u8::new(0));
+        status.set_enabled(true);
+        status.set_homed(true);
+        let fb = GripperFeedback {
+            travel_mm: 35_500,
+            torque_nm: -1_200,
+            status,
+        };
+
+        let frame = PiperFrame::from(fb);
+        assert_eq!(frame.dlc(), 8);
+        assert_eq!(frame.data()[6], 0b1100_0000);
+        assert_eq!(frame.data()[7], 0x00);
+        assert_eq!(GripperFeedback::try_from(frame).unwrap(), fb);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1285,6 +1618,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_robot_status_is_fault() {
+        assert!(!RobotStatus::Normal.is_fault());
+        assert!(!RobotStatus::TeachExecute.is_fault());
+        assert!(RobotStatus::EmergencyStop.is_fault());
+        assert!(RobotStatus::Collision.is_fault());
+        assert!(RobotStatus::AngleLimitExceeded.is_fault());
+        assert!(RobotStatus::ResistorOverTemp.is_fault());
+    }
+
+    #[test]
+    fn test_robot_status_is_latching_fault() {
+        assert!(RobotStatus::EmergencyStop.is_latching_fault());
+        assert!(RobotStatus::Collision.is_latching_fault());
+        assert!(RobotStatus::JointCommError.is_latching_fault());
+        assert!(RobotStatus::MainControlOverTemp.is_latching_fault());
+        assert!(!RobotStatus::Normal.is_latching_fault());
+        assert!(!RobotStatus::NoSolution.is_latching_fault());
+        assert!(!RobotStatus::Singularity.is_latching_fault());
+        assert!(!RobotStatus::TeachOverspeed.is_latching_fault());
+    }
+
     #[test]
     fn test_move_mode_from_u8() {
         assert_eq!(MoveMode::from(0x00), MoveMode::MoveP);
@@ -2468,10 +2823,255 @@ impl TryFrom<PiperFrame> for FirmwareReadFeedback {
     }
 }
 
+/// 结构化固件版本
+///
+/// 固件上报的版本字符串形如 `S-V1.8-1`，对应 `major = 1`、`minor = 8`、`patch = 1`；
+/// 若固件附带第四段（`S-V1.8-1.2` / `S-V1.8-1-2`），解析为 `build`，否则为 0。
+/// 按 `(major, minor, patch, build)` 字典序比较，可直接用于功能门控：
+///
+/// ```
+/// use piper_protocol::FirmwareVersion;
+///
+/// let version = FirmwareVersion::parse("S-V1.8-1").unwrap();
+/// assert!(version >= FirmwareVersion::new(1, 5, 2, 0));
+/// assert_eq!(version.to_string(), "S-V1.8-1");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct FirmwareVersion {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: u16,
+    pub build: u16,
+}
+
+impl FirmwareVersion {
+    pub const fn new(major: u16, minor: u16, patch: u16, build: u16) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            build,
+        }
+    }
+
+    /// 解析固件版本字符串（`S-V` 前缀可省略，段之间可用 `.` 或 `-` 分隔）
+    pub fn parse(version: &str) -> Option<Self> {
+        let version = version.trim();
+        let version = version.strip_prefix("S-V").unwrap_or(version);
+        let version = version.strip_prefix('V').unwrap_or(version);
+
+        let mut parts = version.split(['.', '-']);
+        let mut next = || -> Option<Option<u16>> {
+            match parts.next() {
+                Some(part) => part.parse().ok().map(Some),
+                None => Some(None),
+            }
+        };
+        let major = next()??;
+        let minor = next()??;
+        let patch = next()??;
+        let build = next()?.unwrap_or(0);
+        if next()?.is_some() {
+            return None;
+        }
+
+        Some(Self::new(major, minor, patch, build))
+    }
+}
+
+impl std::fmt::Display for FirmwareVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "S-V{}.{}-{}", self.major, self.minor, self.patch)?;
+        if self.build != 0 {
+            write!(f, ".{}", self.build)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for FirmwareVersion {
+    type Err = ProtocolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+            .ok_or_else(|| ProtocolError::ParseError(format!("invalid firmware version: {s}")))
+    }
+}
+
+/// 依赖固件版本的功能
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FirmwareFeature {
+    /// MOVE M - MIT 模式（V1.5-2+）
+    MoveM,
+    /// MOVE CPV - 连续位置速度模式（V1.8-1+）
+    MoveCpv,
+    /// 0x151 Byte 5 安装位置设置（V1.5-2+）
+    InstallPosition,
+}
+
+impl FirmwareFeature {
+    /// 能力表中的全部功能
+    pub const ALL: [Self; 3] = [Self::MoveM, Self::MoveCpv, Self::InstallPosition];
+
+    /// 支持该功能的最低固件版本
+    pub const fn min_version(self) -> FirmwareVersion {
+        match self {
+            Self::MoveM => FirmwareVersion::new(1, 5, 2, 0),
+            Self::MoveCpv => FirmwareVersion::new(1, 8, 1, 0),
+            Self::InstallPosition => FirmwareVersion::new(1, 5, 2, 0),
+        }
+    }
+
+    /// 使用某个 MOVE 模式所需的功能（无版本要求的模式返回 `None`）
+    pub const fn for_move_mode(move_mode: MoveMode) -> Option<Self> {
+        match move_mode {
+            MoveMode::MoveM => Some(Self::MoveM),
+            MoveMode::MoveCpv => Some(Self::MoveCpv),
+            MoveMode::MoveP | MoveMode::MoveJ | MoveMode::MoveL | MoveMode::MoveC => None,
+        }
+    }
+
+    const fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+impl std::fmt::Display for FirmwareFeature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MoveM => f.write_str("MoveM"),
+            Self::MoveCpv => f.write_str("MoveCpv"),
+            Self::InstallPosition => f.write_str("InstallPosition"),
+        }
+    }
+}
+
+/// 固件能力集合
+///
+/// 通常由 [`FirmwareCapabilities::from_version`] 按能力表从检测到的固件版本推导；
+/// 测试或特殊固件可用 [`FirmwareCapabilities::with_feature`] 手动覆盖。
+///
+/// ```
+/// use piper_protocol::{FirmwareCapabilities, FirmwareFeature, FirmwareVersion};
+///
+/// let caps = FirmwareCapabilities::from_version(FirmwareVersion::new(1, 6, 3, 0));
+/// assert!(caps.supports(FirmwareFeature::MoveM));
+/// assert!(!caps.supports(FirmwareFeature::MoveCpv));
+///
+/// let caps = caps.with_feature(FirmwareFeature::MoveCpv, true);
+/// assert!(caps.supports(FirmwareFeature::MoveCpv));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FirmwareCapabilities {
+    version: FirmwareVersion,
+    supported: u8,
+}
+
+impl FirmwareCapabilities {
+    /// 按能力表推导指定固件版本支持的功能
+    pub fn from_version(version: FirmwareVersion) -> Self {
+        let supported = FirmwareFeature::ALL
+            .iter()
+            .filter(|feature| version >= feature.min_version())
+            .fold(0, |mask, feature| mask | feature.bit());
+        Self { version, supported }
+    }
+
+    /// 启用全部功能（版本号取能力表中最高的最低版本）
+    pub fn all() -> Self {
+        let version = FirmwareFeature::ALL
+            .iter()
+            .map(|feature| feature.min_version())
+            .max()
+            .unwrap_or_default();
+        Self::from_version(version)
+    }
+
+    /// 覆盖单个功能的支持状态
+    pub fn with_feature(mut self, feature: FirmwareFeature, supported: bool) -> Self {
+        if supported {
+            self.supported |= feature.bit();
+        } else {
+            self.supported &= !feature.bit();
+        }
+        self
+    }
+
+    /// 推导能力时使用的固件版本
+    pub fn version(&self) -> FirmwareVersion {
+        self.version
+    }
+
+    /// 是否支持指定功能
+    pub fn supports(&self, feature: FirmwareFeature) -> bool {
+        self.supported & feature.bit() != 0
+    }
+
+    /// 检查 MOVE 模式是否可用；不可用时返回所缺的功能
+    pub fn check_move_mode(&self, move_mode: MoveMode) -> Result<(), FirmwareFeature> {
+        match FirmwareFeature::for_move_mode(move_mode) {
+            Some(feature) if !self.supports(feature) => Err(feature),
+            _ => Ok(()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod firmware_read_tests {
     use super::*;
 
+    #[test]
+    fn test_firmware_version_parse_and_order() {
+        let version = FirmwareVersion::parse("S-V1.8-1").unwrap();
+        assert_eq!(version, FirmwareVersion::new(1, 8, 1, 0));
+        assert_eq!(
+            FirmwareVersion::parse(" S-V1.6-3.2 ").unwrap(),
+            FirmwareVersion::new(1, 6, 3, 2)
+        );
+        assert_eq!(
+            "V1.5-2".parse::<FirmwareVersion>().unwrap(),
+            FirmwareVersion::new(1, 5, 2, 0)
+        );
+
+        assert!(FirmwareVersion::new(1, 5, 2, 0) < version);
+        assert!(FirmwareVersion::new(1, 8, 1, 1) > version);
+        assert_eq!(version.to_string(), "S-V1.8-1");
+        assert_eq!(FirmwareVersion::new(1, 6, 3, 2).to_string(), "S-V1.6-3.2");
+
+        assert!(FirmwareVersion::parse("S-V1.8").is_none());
+        assert!(FirmwareVersion::parse("S-V1.x-1").is_none());
+        assert!(FirmwareVersion::parse("S-V1.8-1.2.3").is_none());
+        assert!("garbage".parse::<FirmwareVersion>().is_err());
+    }
+
+    #[test]
+    fn test_firmware_capabilities_follow_version_table() {
+        let old = FirmwareCapabilities::from_version(FirmwareVersion::new(1, 5, 1, 0));
+        assert!(!old.supports(FirmwareFeature::MoveM));
+        assert!(!old.supports(FirmwareFeature::MoveCpv));
+        assert_eq!(
+            old.check_move_mode(MoveMode::MoveM),
+            Err(FirmwareFeature::MoveM)
+        );
+        assert_eq!(old.check_move_mode(MoveMode::MoveJ), Ok(()));
+
+        let mid = FirmwareCapabilities::from_version(FirmwareVersion::new(1, 5, 2, 0));
+        assert!(mid.supports(FirmwareFeature::MoveM));
+        assert!(!mid.supports(FirmwareFeature::MoveCpv));
+
+        let new = FirmwareCapabilities::from_version(FirmwareVersion::new(1, 8, 1, 0));
+        assert!(new.supports(FirmwareFeature::MoveM));
+        assert!(new.supports(FirmwareFeature::MoveCpv));
+        assert_eq!(new.check_move_mode(MoveMode::MoveCpv), Ok(()));
+
+        let overridden = new.with_feature(FirmwareFeature::MoveM, false);
+        assert!(!overridden.supports(FirmwareFeature::MoveM));
+        assert_eq!(overridden.version(), FirmwareVersion::new(1, 8, 1, 0));
+
+        let all = FirmwareCapabilities::all();
+        assert!(FirmwareFeature::ALL.iter().all(|feature| all.supports(*feature)));
+    }
+
     #[test]
     fn test_firmware_read_feedback_parse() {
         // 测试数据：包含 "S-V1.6-3" 版本字符串
@@ -1,4 +1,6 @@
 use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
 
 use thiserror::Error;
 
@@ -22,6 +24,8 @@ pub enum FrameError {
     InvalidSerializedFrameFormat { format: u8 },
     #[error("noncanonical padding byte at {index}: 0x{value:02X}")]
     NonCanonicalPadding { index: usize, value: u8 },
+    #[error("invalid candump frame: {reason}")]
+    InvalidCandump { reason: &'static str },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -63,6 +67,18 @@ pub struct PiperFrame {
     timestamp_us: u64,
 }
 
+/// `PiperFrame` 的链式构造器
+///
+/// 默认为标准帧、ID 0、空负载；ID 宽度与负载长度在 [`PiperFrameBuilder::build`] 时校验，
+/// 不会把超过 11 位的 ID 截断进标准帧。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PiperFrameBuilder {
+    id: u32,
+    extended: bool,
+    data: Result<CanData, FrameError>,
+    timestamp_us: u64,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct JointIndex(u8);
 
@@ -328,6 +344,10 @@ impl PiperFrame {
         self.data.as_padded()
     }
 
+    /// 时间戳（微秒）
+    ///
+    /// 来源（硬件 / 内核 / 用户空间 / 无）由接收路径随帧给出，见
+    /// `piper_can::ReceivedFrame::timestamp_provenance`。
     pub fn timestamp_us(&self) -> u64 {
         self.timestamp_us
     }
@@ -336,6 +356,144 @@ impl PiperFrame {
         self.timestamp_us = timestamp_us;
         self
     }
+
+    pub fn with_id(mut self, id: CanId) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn builder() -> PiperFrameBuilder {
+        PiperFrameBuilder::default()
+    }
+
+    /// `candump -L` 风格的一行：`(秒.微秒) <interface> <id>#<data>`
+    pub fn to_candump_string(self, interface: &str) -> String {
+        format!(
+            "({}.{:06}) {} {}",
+            self.timestamp_us / 1_000_000,
+            self.timestamp_us % 1_000_000,
+            interface,
+            self
+        )
+    }
+}
+
+/// candump 帧格式：标准帧 `123#0102`，扩展帧 `12345678#0102`（十六进制大写）
+impl fmt::Display for PiperFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.id {
+            CanId::Standard(id) => write!(f, "{:03X}#", id.raw())?,
+            CanId::Extended(id) => write!(f, "{:08X}#", id.raw())?,
+        }
+        for byte in self.data() {
+            write!(f, "{byte:02X}")?;
+        }
+        Ok(())
+    }
+}
+
+/// 解析 candump 帧格式 `<id>#<data>`
+///
+/// 3 位十六进制 ID 为标准帧、8 位为扩展帧；数据允许 `.` 分隔（`DE.AD`）。
+/// 远程帧（`#R`）与 CAN FD 帧（`##`）无法用 `PiperFrame` 表示，返回错误。
+impl FromStr for PiperFrame {
+    type Err = FrameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason| FrameError::InvalidCandump { reason };
+        let (id, data) = s.trim().split_once('#').ok_or(invalid("missing '#' separator"))?;
+        if data.starts_with('#') {
+            return Err(invalid("CAN FD frames are not supported"));
+        }
+        if data.starts_with(['R', 'r']) {
+            return Err(invalid("remote frames are not supported"));
+        }
+
+        if !id.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(invalid("CAN ID must be hexadecimal"));
+        }
+        let raw_id = u32::from_str_radix(id, 16).map_err(|_| invalid("CAN ID out of range"))?;
+        let id = match id.len() {
+            3 => CanId::standard(raw_id)?,
+            8 => CanId::extended(raw_id)?,
+            _ => return Err(invalid("CAN ID must have 3 or 8 hex digits")),
+        };
+
+        let digits: Vec<u8> = data.bytes().filter(|&b| b != b'.').collect();
+        if !digits.len().is_multiple_of(2) {
+            return Err(invalid("odd number of hex digits in data"));
+        }
+        let bytes = digits
+            .chunks(2)
+            .map(|pair| {
+                std::str::from_utf8(pair)
+                    .ok()
+                    .filter(|pair| pair.bytes().all(|b| b.is_ascii_hexdigit()))
+                    .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                    .ok_or(invalid("invalid data byte"))
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+
+        Ok(Self {
+            id,
+            data: CanData::new(bytes)?,
+            timestamp_us: 0,
+        })
+    }
+}
+
+impl Default for PiperFrameBuilder {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            extended: false,
+            data: Ok(CanData::from_exact([0u8; 0])),
+            timestamp_us: 0,
+        }
+    }
+}
+
+impl PiperFrameBuilder {
+    pub fn id(mut self, id: u32) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// 使用 29 位扩展 ID
+    pub fn extended(mut self) -> Self {
+        self.extended = true;
+        self
+    }
+
+    /// 使用 11 位标准 ID（默认）
+    pub fn standard(mut self) -> Self {
+        self.extended = false;
+        self
+    }
+
+    pub fn data(mut self, data: impl AsRef<[u8]>) -> Self {
+        self.data = CanData::new(data);
+        self
+    }
+
+    pub fn timestamp_us(mut self, timestamp_us: u64) -> Self {
+        self.timestamp_us = timestamp_us;
+        self
+    }
+
+    pub fn build(self) -> Result<PiperFrame, FrameError> {
+        let id = if self.extended {
+            CanId::extended(self.id)?
+        } else {
+            CanId::standard(self.id)?
+        };
+        let frame = PiperFrame {
+            id,
+            data: self.data?,
+            timestamp_us: self.timestamp_us,
+        };
+        Ok(frame)
+    }
 }
 
 #[cfg(feature = "serde")]
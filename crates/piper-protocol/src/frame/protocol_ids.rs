@@ -167,6 +167,11 @@ pub fn mit_control_id(joint: JointIndex) -> StandardCanId {
     MIT_CONTROL_IDS[joint.zero_based() as usize]
 }
 
+pub fn mit_control_joint_index(id: StandardCanId) -> Option<JointIndex> {
+    let position = MIT_CONTROL_IDS.iter().position(|candidate| *candidate == id)?;
+    JointIndex::new(position as u8 + 1).ok()
+}
+
 pub const fn driver_rx_robot_feedback_ids() -> &'static [StandardCanId] {
     &DRIVER_RX_ROBOT_FEEDBACK_IDS
 }
@@ -3,6 +3,7 @@
 //! Public protocol IDs are typed `StandardCanId` values.
 
 pub use crate::frame::JointIndex;
+use crate::frame::PiperFrame;
 pub use crate::frame::protocol_ids::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,6 +20,113 @@ impl FrameType {
     }
 }
 
+/// Highest protocol ID used by the arm (`ID_FIRMWARE_READ`).
+const HIGHEST_PROTOCOL_ID: u16 = 0x4AF;
+
+/// Per-arm CAN ID offset for several arms sharing one bus.
+///
+/// Every protocol ID in this module is the canonical (node 0) value. An arm whose
+/// firmware shifts its IDs by `offset` sends feedback at `canonical + offset` and
+/// expects commands at `canonical + offset`. `NodeId` converts between the two so
+/// parsers (`RobotStatusFeedback::try_from`, ...) and command builders keep working on
+/// canonical IDs:
+///
+/// - [`NodeId::to_canonical`] maps a received frame back to canonical IDs, or returns `None`
+///   when the frame is not addressed to this node.
+/// - [`NodeId::to_bus`] shifts an outgoing canonical frame to this node's IDs.
+///
+/// Offsets must keep every node's ID block disjoint; the driver does not detect
+/// overlapping nodes on the same segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct NodeId(u16);
+
+impl NodeId {
+    /// Canonical IDs (no offset).
+    pub const DEFAULT: Self = Self(0);
+
+    /// Largest offset that keeps every protocol ID within the 11-bit standard range.
+    pub const MAX_ID_OFFSET: u16 = crate::frame::STANDARD_CAN_ID_MAX as u16 - HIGHEST_PROTOCOL_ID;
+
+    pub fn new(id_offset: u16) -> Result<Self, crate::ProtocolError> {
+        if id_offset > Self::MAX_ID_OFFSET {
+            return Err(crate::ProtocolError::ValueOutOfRange {
+                field: "node_id_offset",
+                value: f64::from(id_offset),
+                min: 0.0,
+                max: f64::from(Self::MAX_ID_OFFSET),
+            });
+        }
+        Ok(Self(id_offset))
+    }
+
+    pub const fn id_offset(self) -> u16 {
+        self.0
+    }
+
+    pub const fn is_default(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Resolves a canonical protocol ID to this node's bus ID.
+    pub fn resolve(self, id: crate::frame::StandardCanId) -> crate::frame::StandardCanId {
+        if !is_protocol_id(id.raw()) {
+            return id;
+        }
+        crate::frame::StandardCanId::new(u32::from(id.raw()) + u32::from(self.0)).unwrap_or(id)
+    }
+
+    /// Maps a bus ID back to its canonical protocol ID if it belongs to this node.
+    pub fn canonicalize(self, id: crate::frame::CanId) -> Option<crate::frame::StandardCanId> {
+        let raw = id.as_standard()?.raw().checked_sub(self.0)?;
+        if !is_protocol_id(raw) {
+            return None;
+        }
+        crate::frame::StandardCanId::new(u32::from(raw)).ok()
+    }
+
+    /// Shifts an outgoing canonical frame to this node's bus IDs.
+    ///
+    /// Extended frames and non-protocol IDs pass through unchanged.
+    pub fn to_bus(self, frame: PiperFrame) -> PiperFrame {
+        match frame.id().as_standard() {
+            Some(id) if !self.is_default() => frame.with_id(self.resolve(id).into()),
+            _ => frame,
+        }
+    }
+
+    /// Maps a received frame to canonical IDs, or `None` when it is addressed to another node.
+    ///
+    /// With [`NodeId::DEFAULT`] every frame passes through unchanged.
+    pub fn to_canonical(self, frame: PiperFrame) -> Option<PiperFrame> {
+        if self.is_default() {
+            return Some(frame);
+        }
+        let id = self.canonicalize(frame.id())?;
+        Some(frame.with_id(id.into()))
+    }
+
+    /// Decodes a received bus frame relative to this node.
+    ///
+    /// Frames addressed to another node are rejected with `ProtocolError::InvalidCanId`.
+    pub fn decode<T>(self, frame: PiperFrame) -> Result<T, crate::ProtocolError>
+    where
+        T: TryFrom<PiperFrame, Error = crate::ProtocolError>,
+    {
+        let canonical = self
+            .to_canonical(frame)
+            .ok_or(crate::ProtocolError::InvalidCanId { id: frame.raw_id() })?;
+        T::try_from(canonical)
+    }
+}
+
+fn is_protocol_id(raw: u16) -> bool {
+    if raw == ID_LIGHT_CONTROL.raw() || raw == ID_FIRMWARE_UPGRADE.raw() {
+        return true;
+    }
+    crate::frame::CanId::standard(u32::from(raw))
+        .is_ok_and(|id| FrameType::from_id(id) != FrameType::Unknown)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,6 +252,42 @@ mod tests {
         assert!(!driver_rx_robot_feedback_ids().contains(&ID_GRIPPER_TEACH_PARAMS_FEEDBACK));
     }
 
+    #[test]
+    fn node_id_shifts_protocol_ids_between_canonical_and_bus() {
+        let node = NodeId::new(0x300).unwrap();
+        let command = PiperFrame::new_standard(0x471, [0x07, 0x02]).unwrap();
+        let on_bus = node.to_bus(command);
+        assert_eq!(on_bus.raw_id(), 0x771);
+        assert_eq!(on_bus.data(), command.data());
+        assert_eq!(node.resolve(ID_FIRMWARE_READ).raw(), 0x7AF);
+
+        let feedback = PiperFrame::new_standard(0x5A1, [0; 8]).unwrap().with_timestamp_us(42);
+        let canonical = node.to_canonical(feedback).unwrap();
+        assert_eq!(canonical.id(), CanId::from(ID_ROBOT_STATUS));
+        assert_eq!(canonical.timestamp_us(), 42);
+        assert!(node.decode::<crate::feedback::RobotStatusFeedback>(feedback).is_ok());
+
+        // Canonical-ID traffic belongs to the default node, not this one.
+        let other_arm = PiperFrame::new_standard(0x2A1, [0; 8]).unwrap();
+        assert!(node.to_canonical(other_arm).is_none());
+        assert!(matches!(
+            node.decode::<crate::feedback::RobotStatusFeedback>(other_arm),
+            Err(crate::ProtocolError::InvalidCanId { id: 0x2A1 })
+        ));
+
+        let extended = PiperFrame::new_extended(0x2A1, [0; 8]).unwrap();
+        assert_eq!(node.to_bus(extended), extended);
+        assert_eq!(NodeId::DEFAULT.to_canonical(other_arm), Some(other_arm));
+        assert_eq!(NodeId::DEFAULT.to_bus(command), command);
+    }
+
+    #[test]
+    fn node_id_rejects_offsets_that_overflow_standard_ids() {
+        assert_eq!(NodeId::MAX_ID_OFFSET, 0x350);
+        assert!(NodeId::new(NodeId::MAX_ID_OFFSET).is_ok());
+        assert!(NodeId::new(NodeId::MAX_ID_OFFSET + 1).is_err());
+    }
+
     #[test]
     fn dynamic_id_accessors_match_protocol_values() {
         assert_eq!(
@@ -29,7 +29,10 @@ pub use constants::*;
 pub use control::*;
 pub use diagnostics::*;
 pub use feedback::*;
-pub use frame::{CanData, CanId, ExtendedCanId, FrameError, JointIndex, PiperFrame, StandardCanId};
+pub use frame::{
+    CanData, CanId, ExtendedCanId, FrameError, JointIndex, PiperFrame, PiperFrameBuilder,
+    StandardCanId,
+};
 pub use ids::*;
 
 use thiserror::Error;
@@ -57,6 +60,14 @@ pub enum ProtocolError {
         max: f32,
     },
 
+    #[error("Value out of range for field {field}: {value} not in [{min}, {max}]")]
+    ValueOutOfRange {
+        field: &'static str,
+        value: f64,
+        min: f64,
+        max: f64,
+    },
+
     #[error("Parse error: {0}")]
     ParseError(String),
 
@@ -1,5 +1,6 @@
 use piper_protocol::frame::{
-    CanData, CanId, ExtendedCanId, FrameError, JointIndex, PiperFrame, StandardCanId,
+    CanData, CanId, ExtendedCanId, FrameError, JointIndex, PiperFrame, PiperFrameBuilder,
+    StandardCanId,
 };
 use piper_protocol::ids::{self, FrameType};
 use std::collections::{BTreeSet, HashSet};
@@ -46,6 +47,70 @@ fn frame_constructors_reject_invalid_ids_at_public_boundary() {
     );
 }
 
+#[test]
+fn builder_validates_id_width_and_payload_length() {
+    let frame = PiperFrame::builder()
+        .id(0x1234_5678)
+        .extended()
+        .data([1, 2, 3])
+        .timestamp_us(42)
+        .build()
+        .unwrap();
+    assert_eq!(frame.id(), CanId::extended(0x1234_5678).unwrap());
+    assert_eq!(frame.data(), &[1, 2, 3]);
+    assert_eq!(frame.timestamp_us(), 42);
+
+    assert_eq!(
+        PiperFrame::builder().id(0x1234_5678).build(),
+        Err(FrameError::InvalidStandardId { id: 0x1234_5678 })
+    );
+    assert_eq!(
+        PiperFrame::builder().id(0x123).data([0u8; 9]).build(),
+        Err(FrameError::PayloadTooLong { len: 9, max: 8 })
+    );
+    assert_eq!(
+        PiperFrameBuilder::default().id(0x123).data([7]).build(),
+        PiperFrame::new_standard(0x123, [7])
+    );
+}
+
+#[test]
+fn candump_text_round_trips_through_display_and_from_str() {
+    let standard = PiperFrame::new_standard(0x2A5, [0x00, 0x03, 0xE8]).unwrap();
+    assert_eq!(standard.to_string(), "2A5#0003E8");
+    assert_eq!("2A5#0003E8".parse::<PiperFrame>(), Ok(standard));
+
+    let extended = PiperFrame::new_extended(0x1234_5678, [0xDE, 0xAD]).unwrap();
+    assert_eq!(extended.to_string(), "12345678#DEAD");
+    assert_eq!("12345678#de.ad".parse::<PiperFrame>(), Ok(extended));
+
+    let empty = PiperFrame::new_standard(0x001, [0u8; 0]).unwrap().with_timestamp_us(1_500_042);
+    assert_eq!(empty.to_candump_string("can0"), "(1.500042) can0 001#");
+}
+
+#[test]
+fn candump_parse_rejects_unrepresentable_frames() {
+    let reason = |text: &str| match text.parse::<PiperFrame>() {
+        Err(FrameError::InvalidCandump { reason }) => reason,
+        other => panic!("unexpected result for {text}: {other:?}"),
+    };
+    assert_eq!(reason("2A5##1AABB"), "CAN FD frames are not supported");
+    assert_eq!(reason("151#R"), "remote frames are not supported");
+    assert_eq!(reason("2A5"), "missing '#' separator");
+    assert_eq!(reason("2A#00"), "CAN ID must have 3 or 8 hex digits");
+    assert_eq!(reason("2A5#0"), "odd number of hex digits in data");
+    assert_eq!(reason("2A5#0G"), "invalid data byte");
+
+    assert_eq!(
+        "800#00".parse::<PiperFrame>(),
+        Err(FrameError::InvalidStandardId { id: 0x800 })
+    );
+    assert_eq!(
+        "2A5#001122334455667788".parse::<PiperFrame>(),
+        Err(FrameError::PayloadTooLong { len: 9, max: 8 })
+    );
+}
+
 #[test]
 fn padded_payload_normalizes_unused_bytes() {
     let data = CanData::from_padded([1, 2, 3, 0xAA, 0xBB, 0, 0, 0], 3).unwrap();
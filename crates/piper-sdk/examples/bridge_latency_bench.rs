@@ -143,21 +143,7 @@ fn resolved_endpoint_arg(
 }
 
 fn parse_endpoint(raw: &str) -> Result<BridgeEndpoint, String> {
-    if raw.starts_with('/') || raw.starts_with("unix:") {
-        #[cfg(unix)]
-        {
-            let path = raw.strip_prefix("unix:").unwrap_or(raw);
-            Ok(BridgeEndpoint::Unix(PathBuf::from(path)))
-        }
-        #[cfg(not(unix))]
-        {
-            Err("unix endpoints are not supported on this platform".to_string())
-        }
-    } else {
-        raw.parse()
-            .map(BridgeEndpoint::TcpTls)
-            .map_err(|err| format!("invalid TCP/TLS endpoint: {err}"))
-    }
+    raw.parse::<BridgeEndpoint>().map_err(|err| err.to_string())
 }
 
 fn maybe_tls_config(
@@ -235,6 +221,7 @@ fn connect_client(
         connect_timeout: Duration::from_secs(5),
         request_timeout: timeout,
         tcp_tls: maybe_tls_config(&endpoint, args)?,
+        ..Default::default()
     };
     let client = PiperBridgeClient::connect(endpoint, options)?;
     if required_role == BridgeRole::WriterCandidate
@@ -11,8 +11,9 @@
 
 use clap::Parser;
 use piper_sdk::{
-    BridgeHostConfig, BridgeRole, BridgeTlsClientPolicy, BridgeTlsServerConfig,
-    BridgeUdsListenerConfig, ConnectedPiper, MotionConnectedState, PiperBuilder,
+    BridgeHostConfig, BridgeRole, BridgeSendRateLimit, BridgeTlsClientPolicy,
+    BridgeTlsServerConfig, BridgeUdsListenerConfig, ConnectedPiper, MotionConnectedState,
+    PiperBuilder,
 };
 use std::path::PathBuf;
 
@@ -64,6 +65,18 @@ struct Args {
     /// Explicitly allow raw frame tap subscriptions.
     #[arg(long, default_value_t = false)]
     allow_raw_frame_tap: bool,
+
+    /// Per-session maintenance SendFrame limit in frames per second.
+    #[arg(long)]
+    send_rate_limit: Option<u32>,
+
+    /// Burst size for --send-rate-limit (defaults to the per-second rate).
+    #[arg(long, requires = "send_rate_limit")]
+    send_burst: Option<u32>,
+
+    /// Revoke the maintenance lease after its holder is idle for this many milliseconds.
+    #[arg(long)]
+    writer_lease_idle_timeout_ms: Option<u64>,
 }
 
 fn parse_bridge_role(raw: &str) -> Result<BridgeRole, Box<dyn std::error::Error>> {
@@ -147,6 +160,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }),
         tcp_tls,
         allow_raw_frame_tap: args.allow_raw_frame_tap,
+        send_rate_limit: args.send_rate_limit.map(|frames_per_sec| BridgeSendRateLimit {
+            frames_per_sec,
+            burst: args.send_burst.unwrap_or(frames_per_sec),
+        }),
+        writer_lease_idle_timeout: args
+            .writer_lease_idle_timeout_ms
+            .map(std::time::Duration::from_millis),
     };
     let host = match piper {
         ConnectedPiper::Strict(MotionConnectedState::Standby(piper)) => {
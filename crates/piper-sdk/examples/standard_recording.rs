@@ -92,6 +92,8 @@ where
             notes: args.notes.clone(),
             operator: args.operator.clone(),
         },
+        mode: Default::default(),
+        compression: Default::default(),
     })?;
 
     println!("✅ 录制已启动，开始执行操作...");
@@ -105,14 +105,18 @@ pub use client::{
     BridgeEvent,
     BridgeHostConfig,
     BridgeMaintenanceState,
+    BridgeReconnectPolicy,
     BridgeResult,
     BridgeRole,
+    BridgeSendRateLimit,
+    BridgeSessionInfo,
     BridgeStatus,
     BridgeTlsClientConfig,
     BridgeTlsClientPolicy,
     BridgeTlsServerConfig,
     BridgeUdsListenerConfig,
     CanIdFilter,
+    CanIdFilterMode,
     ConfirmedMitBatch,
     ConnectedPiper,
     DualArmActiveMit,
@@ -127,6 +131,7 @@ pub use client::{
     DualArmRuntimeHealth,
     DualArmSafetyConfig,
     DualArmSnapshot,
+    EmergencyStopHandle,
     GripperState,
     GripperTeleopConfig,
     JointMirrorMap,
@@ -136,6 +141,7 @@ pub use client::{
     MasterFollowerController,
     MonitorOnly,
     MonitorReadPolicy,
+    MotionCommander,
     MotionConnectedPiper,
     MotionConnectedState,
     Observer,
@@ -153,7 +159,8 @@ pub use client::{
 
 // 导出 recording 模块的常用类型
 pub use client::recording::{
-    RecordingConfig, RecordingHandle, RecordingMetadata, RecordingStats, StopCondition,
+    RecordingCompression, RecordingConfig, RecordingHandle, RecordingMetadata, RecordingMode,
+    RecordingStats, StopCondition,
 };
 
 use std::sync::{Mutex, OnceLock};
@@ -141,11 +141,9 @@ impl MockCanBus {
 
         // 根据 CAN ID 处理不同的命令
         match frame.id {
-            0x01 => {
+            0x01 if !state.emergency_stop => {
                 // 使能命令
-                if !state.emergency_stop {
-                    state.arm_state = MockArmState::Enabled;
-                }
+                state.arm_state = MockArmState::Enabled;
             },
             0x02 => {
                 // 失能命令
@@ -168,20 +166,18 @@ impl MockCanBus {
                     ]);
                 }
             },
-            0x20 => {
+            0x20 if frame.data.len() >= 8 => {
                 // 夹爪控制
-                if frame.data.len() >= 8 {
-                    state.gripper_position = f64::from_le_bytes([
-                        frame.data[0],
-                        frame.data[1],
-                        frame.data[2],
-                        frame.data[3],
-                        0,
-                        0,
-                        0,
-                        0,
-                    ]);
-                }
+                state.gripper_position = f64::from_le_bytes([
+                    frame.data[0],
+                    frame.data[1],
+                    frame.data[2],
+                    frame.data[3],
+                    0,
+                    0,
+                    0,
+                    0,
+                ]);
             },
             _ => {},
         }
@@ -27,6 +27,7 @@ fn test_joint_position_state_concurrent_read() {
                 raw_feedback_timing: None,
                 joint_pos: [i as f64; 6],
                 frame_valid_mask: 0b111,
+                consistency: StateConsistency::Consistent,
             };
             ctx_writer.publish_joint_position(new_state);
             thread::yield_now();
@@ -298,6 +299,7 @@ fn test_capture_motion_snapshot_concurrent() {
                 raw_feedback_timing: None,
                 joint_pos: [i as f64; 6],
                 frame_valid_mask: 0b111,
+                consistency: StateConsistency::Consistent,
             };
             ctx_writer.publish_joint_position(new_joint_pos);
 
@@ -404,6 +406,7 @@ fn test_multiple_states_concurrent_read() {
                 raw_feedback_timing: None,
                 joint_pos: [i as f64; 6],
                 frame_valid_mask: 0b111,
+                consistency: StateConsistency::Consistent,
             };
             ctx_writer.publish_joint_position(new_joint_pos);
 
@@ -506,6 +509,7 @@ fn test_no_deadlock() {
                         raw_feedback_timing: None,
                         joint_pos: [counter as f64; 6],
                         frame_valid_mask: 0b111,
+                        consistency: StateConsistency::Consistent,
                     };
                     ctx_clone.publish_joint_position(new_state);
                     counter += 1;
@@ -145,6 +145,7 @@ fn test_hot_snapshot_write_latency() {
         raw_feedback_timing: None,
         joint_pos: [0.0; 6],
         frame_valid_mask: 0b111,
+        consistency: StateConsistency::Consistent,
     };
     for _ in 0..1000 {
         ctx.publish_joint_position(initial_state);
@@ -159,6 +160,7 @@ fn test_hot_snapshot_write_latency() {
             raw_feedback_timing: None,
             joint_pos: [i as f64; 6],
             frame_valid_mask: 0b111,
+            consistency: StateConsistency::Consistent,
         };
         ctx.publish_joint_position(new_state);
     }
@@ -379,6 +381,7 @@ fn test_state_clone_performance() {
         raw_feedback_timing: None,
         joint_pos: [1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
         frame_valid_mask: 0b111,
+        consistency: StateConsistency::Consistent,
     };
 
     let iterations = 1_000_000;
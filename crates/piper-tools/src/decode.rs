@@ -0,0 +1,203 @@
+//! # Feedback frame decoding
+//!
+//! Turns a raw feedback [`PiperFrame`] into a named set of JSON fields, shared by
+//! the MCAP exporter and the CLI `decode` command.
+//!
+//! Units follow the protocol accessors (degrees, mm, rad/s, A, V, ℃); joint angles
+//! are additionally reported in radians.
+
+use piper_protocol::feedback::{
+    EndPoseFeedback1, EndPoseFeedback2, EndPoseFeedback3, GripperFeedback,
+    JointDriverHighSpeedFeedback, JointDriverLowSpeedFeedback, JointFeedback12, JointFeedback34,
+    JointFeedback56, RobotStatusFeedback,
+};
+use piper_protocol::frame::PiperFrame;
+use piper_protocol::ids::*;
+use serde_json::{Map, Value, json};
+
+/// A decoded feedback frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedFeedback {
+    /// Feedback struct name (e.g. `"JointFeedback12"`).
+    pub name: &'static str,
+    /// Decoded fields.
+    pub fields: Map<String, Value>,
+}
+
+fn object(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    }
+}
+
+/// 1-based joint numbers whose bit is set in a 6-bit fault mask.
+fn fault_joints(mask: u8) -> Vec<u8> {
+    (0..6).filter(|bit| mask & (1 << bit) != 0).map(|bit| bit + 1).collect()
+}
+
+fn joint_pair(first: (&str, f64), second: (&str, f64)) -> Value {
+    json!({
+        format!("{}_deg", first.0): first.1,
+        format!("{}_rad", first.0): first.1.to_radians(),
+        format!("{}_deg", second.0): second.1,
+        format!("{}_rad", second.0): second.1.to_radians(),
+    })
+}
+
+/// Decodes a known feedback frame, or returns `None` for other IDs and malformed payloads.
+pub fn decode_feedback(frame: &PiperFrame) -> Option<DecodedFeedback> {
+    let id = frame.id().as_standard()?;
+    let frame = *frame;
+
+    let (name, fields) = if id == ID_ROBOT_STATUS {
+        let f = RobotStatusFeedback::try_from(frame).ok()?;
+        let angle_limit = u8::from(f.fault_code_angle_limit);
+        let comm_error = u8::from(f.fault_code_comm_error);
+        (
+            "RobotStatusFeedback",
+            json!({
+                "control_mode": format!("{:?}", f.control_mode),
+                "robot_status": format!("{:?}", f.robot_status),
+                "move_mode": format!("{:?}", f.move_mode),
+                "teach_status": format!("{:?}", f.teach_status),
+                "motion_status": format!("{:?}", f.motion_status),
+                "trajectory_point_index": f.trajectory_point_index,
+                "fault_code_angle_limit": angle_limit,
+                "fault_code_comm_error": comm_error,
+                "angle_limit_joints": fault_joints(angle_limit),
+                "comm_error_joints": fault_joints(comm_error),
+            }),
+        )
+    } else if id == ID_END_POSE_1 {
+        let f = EndPoseFeedback1::try_from(frame).ok()?;
+        ("EndPoseFeedback1", json!({ "x_mm": f.x(), "y_mm": f.y() }))
+    } else if id == ID_END_POSE_2 {
+        let f = EndPoseFeedback2::try_from(frame).ok()?;
+        (
+            "EndPoseFeedback2",
+            json!({ "z_mm": f.z(), "rx_deg": f.rx() }),
+        )
+    } else if id == ID_END_POSE_3 {
+        let f = EndPoseFeedback3::try_from(frame).ok()?;
+        (
+            "EndPoseFeedback3",
+            json!({ "ry_deg": f.ry(), "rz_deg": f.rz() }),
+        )
+    } else if id == ID_JOINT_FEEDBACK_12 {
+        let f = JointFeedback12::try_from(frame).ok()?;
+        (
+            "JointFeedback12",
+            joint_pair(("j1", f.j1()), ("j2", f.j2())),
+        )
+    } else if id == ID_JOINT_FEEDBACK_34 {
+        let f = JointFeedback34::try_from(frame).ok()?;
+        (
+            "JointFeedback34",
+            joint_pair(("j3", f.j3()), ("j4", f.j4())),
+        )
+    } else if id == ID_JOINT_FEEDBACK_56 {
+        let f = JointFeedback56::try_from(frame).ok()?;
+        (
+            "JointFeedback56",
+            joint_pair(("j5", f.j5()), ("j6", f.j6())),
+        )
+    } else if id == ID_GRIPPER_FEEDBACK {
+        let f = GripperFeedback::try_from(frame).ok()?;
+        (
+            "GripperFeedback",
+            json!({
+                "travel_mm": f.travel(),
+                "torque_nm": f.torque(),
+                "status": u8::from(f.status),
+            }),
+        )
+    } else if (ID_JOINT_DRIVER_HIGH_SPEED_1.raw()..=ID_JOINT_DRIVER_HIGH_SPEED_6.raw())
+        .contains(&id.raw())
+    {
+        let f = JointDriverHighSpeedFeedback::try_from(frame).ok()?;
+        (
+            "JointDriverHighSpeedFeedback",
+            json!({
+                "joint_index": f.joint_index,
+                "speed_rad_s": f.speed(),
+                "current_a": f.current(),
+                "torque_nm": f.torque(None),
+                "position_raw": f.position_raw(),
+            }),
+        )
+    } else if (ID_JOINT_DRIVER_LOW_SPEED_1.raw()..=ID_JOINT_DRIVER_LOW_SPEED_6.raw())
+        .contains(&id.raw())
+    {
+        let f = JointDriverLowSpeedFeedback::try_from(frame).ok()?;
+        (
+            "JointDriverLowSpeedFeedback",
+            json!({
+                "joint_index": f.joint_index,
+                "voltage_v": f.voltage(),
+                "driver_temp_c": f.driver_temp(),
+                "motor_temp_c": f.motor_temp(),
+                "status": u8::from(f.status),
+                "voltage_low": f.status.voltage_low(),
+                "motor_over_temp": f.status.motor_over_temp(),
+                "driver_over_current": f.status.driver_over_current(),
+                "driver_over_temp": f.status.driver_over_temp(),
+                "collision_protection": f.status.collision_protection(),
+                "driver_error": f.status.driver_error(),
+                "enabled": f.status.enabled(),
+                "stall_protection": f.status.stall_protection(),
+                "bus_current_a": f.bus_current(),
+            }),
+        )
+    } else {
+        return None;
+    };
+
+    Some(DecodedFeedback {
+        name,
+        fields: object(fields),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_joint_feedback_reports_degrees_and_radians() {
+        let frame = PiperFrame::new_standard(
+            ID_JOINT_FEEDBACK_12.raw() as u32,
+            [0x00, 0x00, 0x46, 0x50, 0xFF, 0xFF, 0xFC, 0x18],
+        )
+        .unwrap();
+
+        let decoded = decode_feedback(&frame).expect("0x2A5 is a known feedback frame");
+
+        assert_eq!(decoded.name, "JointFeedback12");
+        assert_eq!(decoded.fields["j1_deg"], 18.0);
+        assert_eq!(decoded.fields["j2_deg"], -1.0);
+        let j1_rad = decoded.fields["j1_rad"].as_f64().unwrap();
+        assert!((j1_rad - 18.0f64.to_radians()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn decode_robot_status_lists_faulted_joints() {
+        let frame = PiperFrame::new_standard(
+            ID_ROBOT_STATUS.raw() as u32,
+            [0x01, 0x07, 0x01, 0x00, 0x00, 0x00, 0b0000_0101, 0b0010_0000],
+        )
+        .unwrap();
+
+        let decoded = decode_feedback(&frame).expect("0x2A1 is a known feedback frame");
+
+        assert_eq!(decoded.fields["robot_status"], "Collision");
+        assert_eq!(decoded.fields["angle_limit_joints"], json!([1, 3]));
+        assert_eq!(decoded.fields["comm_error_joints"], json!([6]));
+    }
+
+    #[test]
+    fn decode_unknown_id_returns_none() {
+        let frame = PiperFrame::new_standard(0x123, [0; 8]).unwrap();
+        assert!(decode_feedback(&frame).is_none());
+    }
+}
@@ -9,12 +9,15 @@
 //! - `statistics` - 统计算法（纯函数，可选）
 //! - `safety` - 安全配置（只读结构）
 //! - `timestamp` - 时间戳处理（纯函数）
+//! - `decode` - 反馈帧解码（可选）
 //!
 //! ## Feature Flags
 //!
 //! - `default` - 无默认 features
 //! - `full` - 启用所有功能（包含 statistics）
 //! - `statistics` - 启用统计模块
+//! - `mcap` - 启用 `PiperRecording::write_mcap`（MCAP 导出，隐含 `decode`）
+//! - `decode` - 启用 `decode` 模块（反馈帧解码为具名 JSON 字段）
 //!
 //! ## 使用示例
 //!
@@ -39,13 +42,19 @@ pub mod timestamp;
 #[cfg(feature = "statistics")]
 pub mod statistics;
 
+#[cfg(feature = "decode")]
+pub mod decode;
+
 pub mod safety;
 
 // 重新导出常用类型
 pub use raw_clock::{
     RawClockError, RawClockEstimator, RawClockHealth, RawClockSample, RawClockThresholds,
 };
-pub use recording::{PiperRecording, RecordedFrameDirection, RecordingMetadata, TimestampedFrame};
-pub use safety::{SafetyConfig, SafetyLimits};
-pub use timestamp::{TimestampSource, detect_timestamp_source};
+pub use recording::{
+    CompressedRecordingIndex, PiperRecording, RecordedFrameDirection, RecordingCompression,
+    RecordingMetadata, RecordingTimeIndex, TimestampedFrame,
+};
+pub use safety::{CollisionSettings, SafetyConfig, SafetyLimitMode, SafetyLimits};
+pub use timestamp::{TimestampNormalizer, TimestampSource, detect_timestamp_source};
 // extract_timestamp 已弃用，不导出（由 piper-can 层处理实际时间戳提取）
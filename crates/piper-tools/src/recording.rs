@@ -1,11 +1,24 @@
 //! # Recording format definitions
 //!
 //! Piper tools persist recordings as strict version 3 files. Historical v1/v2
-//! files and segmented legacy shapes are intentionally rejected.
-
+//! files and segmented legacy shapes are intentionally rejected. Recordings may
+//! also be stored zstd-compressed in blocks; loading detects this from the magic.
+
+mod asc;
+mod candump;
+mod compressed;
+mod index;
+#[cfg(feature = "mcap")]
+mod mcap;
 pub mod v3;
 
-use crate::timestamp::TimestampSource;
+pub use compressed::{
+    CompressedBlockIndexEntry, CompressedRecordingIndex, CompressedRecordingWriter,
+    RecordingCompression,
+};
+pub use index::RecordingTimeIndex;
+
+use crate::timestamp::{TimestampNormalizer, TimestampSource};
 use anyhow::Result;
 use piper_protocol::frame::PiperFrame;
 use serde::{Deserialize, Serialize};
@@ -52,6 +65,18 @@ impl PiperRecording {
         Some(Duration::from_micros(last.saturating_sub(first)))
     }
 
+    /// Returns each frame's timestamp relative to the session start on a single
+    /// monotonic time axis, regardless of which backend produced it.
+    ///
+    /// See [`TimestampNormalizer`] for how sources are aligned.
+    pub fn normalized_timestamps(&self) -> Vec<u64> {
+        let mut normalizer = TimestampNormalizer::new();
+        self.frames
+            .iter()
+            .map(|frame| normalizer.normalize(frame.timestamp_us(), frame.timestamp_source))
+            .collect()
+    }
+
     /// Filters frames by timestamp range, inclusive.
     pub fn filter_by_time(&self, start_us: u64, end_us: u64) -> PiperRecording {
         let mut filtered = PiperRecording::new(self.metadata.clone());
@@ -84,12 +109,26 @@ impl PiperRecording {
         v3::save_path(self, path.as_ref())
     }
 
-    /// Loads a strict v3 recording file.
+    /// Saves the recording, optionally as a block-compressed file.
+    pub fn save_with_compression<P: AsRef<Path>>(
+        &self,
+        path: P,
+        compression: RecordingCompression,
+    ) -> Result<()> {
+        match compression {
+            RecordingCompression::None => self.save(path),
+            RecordingCompression::Zstd { level } => {
+                compressed::save_path(self, path.as_ref(), level)
+            },
+        }
+    }
+
+    /// Loads a strict v3 recording file, plain or compressed.
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
         v3::load_path(path.as_ref())
     }
 
-    /// Loads a strict v3 recording file with caller-supplied limits.
+    /// Loads a strict v3 recording file, plain or compressed, with caller-supplied limits.
     pub fn load_with_limits<P: AsRef<Path>>(path: P, limits: v3::RecordingLimits) -> Result<Self> {
         v3::load_path_with_limits(path.as_ref(), limits)
     }
@@ -259,6 +298,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn normalized_timestamps_share_one_axis_across_sources() {
+        let mut recording = PiperRecording::new(metadata());
+        recording.add_frame(TimestampedFrame::new(
+            PiperFrame::new_standard(0x251, [1])
+                .unwrap()
+                .with_timestamp_us(1_700_000_000_000_000),
+            RecordedFrameDirection::Rx,
+            Some(TimestampSource::Kernel),
+        ));
+        recording.add_frame(standard_frame(900));
+        recording.add_frame(standard_frame(1_900));
+
+        assert_eq!(recording.normalized_timestamps(), vec![0, 0, 1_000]);
+    }
+
     #[test]
     fn save_and_load_roundtrip_uses_v3() {
         let mut recording = PiperRecording::new(metadata());
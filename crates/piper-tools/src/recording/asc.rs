@@ -0,0 +1,343 @@
+//! # Vector ASC log import/export
+//!
+//! Reads and writes classic CAN message lines of Vector ASCII logs (`.asc`):
+//!
+//! ```text
+//! date Tue Nov 14 10:13:19.123 pm 2023
+//! base hex  timestamps absolute
+//! internal events logged
+//! Begin Triggerblock Tue Nov 14 10:13:19.123 pm 2023
+//!    0.000000 1  2A5             Rx   d 8 00 00 03 E8 FF FF FC 18
+//!    0.000556 1  12345678x       Tx   d 2 01 02
+//! End TriggerBlock
+//! ```
+//!
+//! Timestamps are seconds relative to the measurement start. Events, error
+//! frames, remote frames and CAN FD lines are skipped on import.
+
+use super::candump::parse_decimal_seconds_us;
+use super::{PiperRecording, RecordedFrameDirection, RecordingMetadata, TimestampedFrame};
+use anyhow::{Context, Result, anyhow, bail};
+use piper_protocol::frame::PiperFrame;
+use std::io::{BufRead, Write};
+
+/// Nominal Piper CAN bit rate; ASC logs do not record the bus speed.
+const DEFAULT_BUS_SPEED: u32 = 1_000_000;
+
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+impl PiperRecording {
+    /// Parses a Vector ASC log.
+    ///
+    /// Both `base hex` and `base dec` are accepted. The `date` header, when
+    /// present, becomes the metadata start time; the interface is named after
+    /// the first frame's channel (`can0` for channel 1). Frame timestamps keep
+    /// the log's relative microseconds and are marked with an unknown source.
+    pub fn from_asc_log<R: BufRead>(reader: R) -> Result<Self> {
+        let mut radix = 16;
+        let mut start_time = 0;
+        let mut interface: Option<String> = None;
+        let mut frames = Vec::new();
+
+        for (index, line) in reader.lines().enumerate() {
+            let line_no = index + 1;
+            let line = line.with_context(|| format!("failed to read ASC line {line_no}"))?;
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+
+            match tokens.first() {
+                None => continue,
+                Some(&"base") => {
+                    radix = match tokens.get(1) {
+                        Some(&"hex") => 16,
+                        Some(&"dec") => 10,
+                        other => bail!("unsupported ASC base {other:?} on line {line_no}"),
+                    };
+                    continue;
+                },
+                Some(&"date") => {
+                    start_time = parse_asc_date(&tokens[1..]).unwrap_or(0);
+                    continue;
+                },
+                _ => {},
+            }
+
+            let parsed = parse_frame_line(&tokens, radix)
+                .with_context(|| format!("invalid ASC line {line_no}: {}", line.trim()))?;
+            if let Some((channel, frame)) = parsed {
+                interface.get_or_insert_with(|| format!("can{}", channel.saturating_sub(1)));
+                frames.push(frame);
+            }
+        }
+
+        let mut recording = PiperRecording::new(RecordingMetadata {
+            start_time,
+            interface: interface.unwrap_or_else(|| "can0".to_string()),
+            bus_speed: DEFAULT_BUS_SPEED,
+            platform: std::env::consts::OS.to_string(),
+            operator: String::new(),
+            notes: "imported from Vector ASC log".to_string(),
+        });
+        recording.frames = frames;
+        Ok(recording)
+    }
+
+    /// Writes the recording as a Vector ASC log on channel 1.
+    ///
+    /// Timestamps are written relative to the first frame; the `date` header is
+    /// taken from the metadata start time (UTC).
+    pub fn to_asc_log<W: Write>(&self, mut writer: W) -> Result<()> {
+        let date = format_asc_date(self.metadata.start_time);
+        writeln!(writer, "date {date}")?;
+        writeln!(writer, "base hex  timestamps absolute")?;
+        writeln!(writer, "internal events logged")?;
+        writeln!(writer, "Begin Triggerblock {date}")?;
+
+        let origin_us = self.frames.first().map_or(0, TimestampedFrame::timestamp_us);
+        for frame in &self.frames {
+            let relative_us = frame.timestamp_us().saturating_sub(origin_us);
+            let id = if frame.frame.is_extended() {
+                format!("{:X}x", frame.raw_id())
+            } else {
+                format!("{:X}", frame.raw_id())
+            };
+            let direction = match frame.direction {
+                RecordedFrameDirection::Rx => "Rx",
+                RecordedFrameDirection::Tx => "Tx",
+            };
+            write!(
+                writer,
+                "{:>4}.{:06} 1  {:<15} {}   d {}",
+                relative_us / 1_000_000,
+                relative_us % 1_000_000,
+                id,
+                direction,
+                frame.data().len()
+            )?;
+            for byte in frame.data() {
+                write!(writer, " {byte:02X}")?;
+            }
+            writeln!(writer)?;
+        }
+
+        writeln!(writer, "End TriggerBlock")?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Parses a message line; `Ok(None)` for header, event and skipped frame lines.
+fn parse_frame_line(tokens: &[&str], radix: u32) -> Result<Option<(u32, TimestampedFrame)>> {
+    // <time> <channel> <id> <Rx|Tx> <d|r> <dlc> <bytes...>
+    let [time, channel, id, direction, kind, rest @ ..] = tokens else {
+        return Ok(None);
+    };
+    let (Ok(timestamp_us), Ok(channel)) = (parse_decimal_seconds_us(time), channel.parse::<u32>())
+    else {
+        return Ok(None);
+    };
+    let direction = match *direction {
+        "Rx" => RecordedFrameDirection::Rx,
+        "Tx" => RecordedFrameDirection::Tx,
+        _ => return Ok(None),
+    };
+    if *kind != "d" {
+        // remote frames ("r") and anything else are not data frames
+        return Ok(None);
+    }
+
+    let (id, extended) = match id.strip_suffix(['x', 'X']) {
+        Some(id) => (id, true),
+        None => (*id, false),
+    };
+    let raw_id =
+        u32::from_str_radix(id, radix).with_context(|| format!("invalid CAN ID '{id}'"))?;
+
+    let (dlc, bytes) = rest.split_first().ok_or_else(|| anyhow!("missing DLC"))?;
+    let dlc: usize = dlc.parse().with_context(|| format!("invalid DLC '{dlc}'"))?;
+    let data = bytes
+        .get(..dlc)
+        .ok_or_else(|| anyhow!("expected {dlc} data bytes, found {}", bytes.len()))?
+        .iter()
+        .map(|byte| {
+            u8::from_str_radix(byte, radix).with_context(|| format!("invalid data byte '{byte}'"))
+        })
+        .collect::<Result<Vec<u8>>>()?;
+
+    let frame = if extended {
+        PiperFrame::new_extended(raw_id, data)
+    } else {
+        PiperFrame::new_standard(raw_id, data)
+    }
+    .map_err(|error| anyhow!("{error}"))?;
+
+    Ok(Some((
+        channel,
+        TimestampedFrame::new(frame.with_timestamp_us(timestamp_us), direction, None),
+    )))
+}
+
+/// Days since 1970-01-01 → (year, month 1-12, day 1-31).
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// (year, month 1-12, day 1-31) → days since 1970-01-01.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let month = i64::from(month);
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Formats Unix seconds as `Tue Nov 14 10:13:19.000 pm 2023` (UTC).
+fn format_asc_date(unix_secs: u64) -> String {
+    let days = (unix_secs / 86_400) as i64;
+    let secs_of_day = unix_secs % 86_400;
+    let (year, month, day) = civil_from_days(days);
+    let weekday = WEEKDAYS[(days + 4).rem_euclid(7) as usize];
+    let hour24 = secs_of_day / 3600;
+    let (hour12, meridiem) = match hour24 {
+        0 => (12, "am"),
+        1..=11 => (hour24, "am"),
+        12 => (12, "pm"),
+        _ => (hour24 - 12, "pm"),
+    };
+    format!(
+        "{weekday} {} {day:02} {hour12:02}:{:02}:{:02}.000 {meridiem} {year}",
+        MONTHS[month as usize - 1],
+        secs_of_day / 60 % 60,
+        secs_of_day % 60,
+    )
+}
+
+/// Parses the tokens after `date` (as written by [`format_asc_date`] and CANoe).
+fn parse_asc_date(tokens: &[&str]) -> Option<u64> {
+    let [_weekday, month, day, time, rest @ ..] = tokens else {
+        return None;
+    };
+    let (meridiem, year) = match rest {
+        [meridiem, year] => (Some(*meridiem), *year),
+        [year] => (None, *year),
+        _ => return None,
+    };
+
+    let month = MONTHS.iter().position(|name| name.eq_ignore_ascii_case(month))? as u32 + 1;
+    let day: u32 = day.parse().ok()?;
+    let year: i64 = year.parse().ok()?;
+    let time = time.split('.').next()?;
+    let mut hms = time.split(':').map(|part| part.parse::<u64>().ok());
+    let (hour, minute, second) = (hms.next()??, hms.next()??, hms.next()??);
+    let hour = match meridiem {
+        Some("am") => hour % 12,
+        Some("pm") => hour % 12 + 12,
+        _ => hour,
+    };
+
+    let days = u64::try_from(days_from_civil(year, month, day)).ok()?;
+    Some(days * 86_400 + hour * 3600 + minute * 60 + second)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "\
+date Tue Nov 14 10:13:19.123 pm 2023
+base hex  timestamps absolute
+internal events logged
+// version 9.0.0
+Begin Triggerblock Tue Nov 14 10:13:19.123 pm 2023
+   0.000000 Start of measurement
+   0.000000 1  2A5             Rx   d 8 00 00 03 E8 FF FF FC 18
+   0.000556 1  12345678x       Tx   d 2 01 02
+   0.001000 1  ErrorFrame
+   0.002000 1  151             Rx   r
+   0.003000 CANFD   1 Rx        2a5                                   1 0 8  8 00 00 00 00 00 00 00 00
+End TriggerBlock
+";
+
+    #[test]
+    fn from_asc_log_parses_data_frames_and_skips_events() {
+        let recording = PiperRecording::from_asc_log(LOG.as_bytes()).unwrap();
+
+        assert_eq!(recording.metadata.interface, "can0");
+        assert_eq!(recording.metadata.start_time, 1_699_999_999);
+        assert_eq!(recording.frame_count(), 2);
+
+        let first = &recording.frames[0];
+        assert_eq!(first.raw_id(), 0x2A5);
+        assert_eq!(
+            first.data(),
+            &[0x00, 0x00, 0x03, 0xE8, 0xFF, 0xFF, 0xFC, 0x18]
+        );
+        assert_eq!(first.timestamp_us(), 0);
+        assert_eq!(first.direction, RecordedFrameDirection::Rx);
+
+        let second = &recording.frames[1];
+        assert!(second.frame.is_extended());
+        assert_eq!(second.raw_id(), 0x1234_5678);
+        assert_eq!(second.timestamp_us(), 556);
+        assert_eq!(second.direction, RecordedFrameDirection::Tx);
+    }
+
+    #[test]
+    fn asc_log_round_trips() {
+        let recording = PiperRecording::from_asc_log(LOG.as_bytes()).unwrap();
+        let mut out = Vec::new();
+        recording.to_asc_log(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+
+        assert!(text.starts_with("date Tue Nov 14 10:13:19.000 pm 2023\n"));
+        assert!(text.contains("   0.000556 1  12345678x       Tx   d 2 01 02\n"));
+
+        let reparsed = PiperRecording::from_asc_log(text.as_bytes()).unwrap();
+        assert_eq!(reparsed.frames, recording.frames);
+        assert_eq!(reparsed.metadata.start_time, recording.metadata.start_time);
+    }
+
+    #[test]
+    fn from_asc_log_supports_decimal_base_and_reports_bad_lines() {
+        let dec = "base dec  timestamps absolute\n   1.5 2  677   Rx   d 2 1 255\n";
+        let recording = PiperRecording::from_asc_log(dec.as_bytes()).unwrap();
+        assert_eq!(recording.metadata.interface, "can1");
+        assert_eq!(recording.frames[0].raw_id(), 0x2A5);
+        assert_eq!(recording.frames[0].data(), &[1, 255]);
+        assert_eq!(recording.frames[0].timestamp_us(), 1_500_000);
+
+        let truncated = "   0.1 1  2A5   Rx   d 8 00 01\n";
+        let error = PiperRecording::from_asc_log(truncated.as_bytes()).unwrap_err();
+        assert!(format!("{error:#}").contains("line 1"));
+    }
+
+    #[test]
+    fn asc_date_conversion_matches_known_epochs() {
+        assert_eq!(format_asc_date(0), "Thu Jan 01 12:00:00.000 am 1970");
+        assert_eq!(
+            format_asc_date(951_782_400),
+            "Tue Feb 29 12:00:00.000 am 2000"
+        );
+        assert_eq!(
+            parse_asc_date(&["Tue", "Feb", "29", "12:00:00.000", "am", "2000"]),
+            Some(951_782_400)
+        );
+        assert_eq!(
+            parse_asc_date(&["Thu", "Jan", "1", "13:30:05", "1970"]),
+            Some(13 * 3600 + 30 * 60 + 5)
+        );
+    }
+}
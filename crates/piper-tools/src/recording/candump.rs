@@ -0,0 +1,239 @@
+//! # candump log import/export
+//!
+//! Reads and writes the classic `candump -L` text format used by SocketCAN
+//! tooling (`canplayer`, `log2asc`, ...):
+//!
+//! ```text
+//! (1699999999.123456) can0 2A5#000003E8FFFFFC18
+//! (1699999999.124012) can0 12345678#0102
+//! ```
+//!
+//! Timestamps are Unix wall-clock time with microsecond resolution. The format
+//! carries no direction or timestamp-source information, so imported frames are
+//! marked `Rx` with an unknown timestamp source.
+
+use super::{PiperRecording, RecordedFrameDirection, RecordingMetadata, TimestampedFrame};
+use anyhow::{Context, Result, anyhow, bail};
+use piper_protocol::frame::PiperFrame;
+use std::io::{BufRead, Write};
+
+/// `CAN_ERR_FLAG` as printed in the identifier of error frames.
+const CAN_ERR_FLAG: u32 = 0x2000_0000;
+
+/// Nominal Piper CAN bit rate; candump logs do not record the bus speed.
+const DEFAULT_BUS_SPEED: u32 = 1_000_000;
+
+/// Interface name written when the recording metadata has none.
+const DEFAULT_INTERFACE: &str = "can0";
+
+impl PiperRecording {
+    /// Parses a `candump -L` log.
+    ///
+    /// The interface name and start time of the returned metadata come from the
+    /// first frame line. Error frames and remote frames are skipped, matching
+    /// the adapters, which only deliver data frames. CAN FD frames (`##`) and
+    /// malformed lines are rejected with the offending line number.
+    pub fn from_candump_log<R: BufRead>(reader: R) -> Result<Self> {
+        let mut recording: Option<PiperRecording> = None;
+
+        for (index, line) in reader.lines().enumerate() {
+            let line_no = index + 1;
+            let line = line.with_context(|| format!("failed to read candump line {line_no}"))?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let parsed = parse_line(line)
+                .with_context(|| format!("invalid candump line {line_no}: {line}"))?;
+            let Some((interface, frame)) = parsed else {
+                continue;
+            };
+
+            let recording = recording.get_or_insert_with(|| {
+                PiperRecording::new(RecordingMetadata {
+                    start_time: frame.timestamp_us() / 1_000_000,
+                    interface: interface.to_string(),
+                    bus_speed: DEFAULT_BUS_SPEED,
+                    platform: std::env::consts::OS.to_string(),
+                    operator: String::new(),
+                    notes: "imported from candump log".to_string(),
+                })
+            });
+            recording.add_frame(TimestampedFrame::new(
+                frame,
+                RecordedFrameDirection::Rx,
+                None,
+            ));
+        }
+
+        Ok(recording.unwrap_or_else(|| {
+            PiperRecording::new(RecordingMetadata::new(
+                DEFAULT_INTERFACE.to_string(),
+                DEFAULT_BUS_SPEED,
+            ))
+        }))
+    }
+
+    /// Writes the recording as a `candump -L` log.
+    ///
+    /// Frame timestamps are written as-is (`seconds.micros`), so recordings with
+    /// hardware or monotonic timestamps produce logs that start near zero.
+    /// Direction and timestamp source are not representable and are dropped.
+    pub fn to_candump_log<W: Write>(&self, mut writer: W) -> Result<()> {
+        let interface = if self.metadata.interface.is_empty() {
+            DEFAULT_INTERFACE
+        } else {
+            self.metadata.interface.as_str()
+        };
+
+        for frame in &self.frames {
+            writeln!(writer, "{}", frame.frame.to_candump_string(interface))?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Parses one log line; `Ok(None)` for frames that are skipped on purpose.
+fn parse_line(line: &str) -> Result<Option<(&str, PiperFrame)>> {
+    let mut fields = line.split_whitespace();
+    let timestamp = fields.next().ok_or_else(|| anyhow!("missing timestamp"))?;
+    let interface = fields.next().ok_or_else(|| anyhow!("missing interface"))?;
+    let frame = fields.next().ok_or_else(|| anyhow!("missing frame"))?;
+
+    let timestamp_us = parse_timestamp(timestamp)?;
+
+    // Error and remote frames are skipped on purpose; the rest is parsed by PiperFrame.
+    let (id, data) = frame.split_once('#').ok_or_else(|| anyhow!("missing '#' separator"))?;
+    let is_error_frame =
+        id.len() == 8 && u32::from_str_radix(id, 16).is_ok_and(|raw_id| raw_id & CAN_ERR_FLAG != 0);
+    if is_error_frame || data.starts_with(['R', 'r']) {
+        return Ok(None);
+    }
+
+    let frame: PiperFrame = frame.parse().map_err(|error| anyhow!("{error}"))?;
+    Ok(Some((interface, frame.with_timestamp_us(timestamp_us))))
+}
+
+/// Parses `(seconds.fraction)` into microseconds.
+fn parse_timestamp(field: &str) -> Result<u64> {
+    let inner = field
+        .strip_prefix('(')
+        .and_then(|field| field.strip_suffix(')'))
+        .ok_or_else(|| anyhow!("timestamp '{field}' must be parenthesized"))?;
+    parse_decimal_seconds_us(inner)
+}
+
+/// Parses a non-negative decimal seconds value into microseconds without
+/// going through floating point (digits past microseconds are truncated).
+pub(super) fn parse_decimal_seconds_us(value: &str) -> Result<u64> {
+    let (secs, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if secs.is_empty() || !fraction.chars().all(|c| c.is_ascii_digit()) {
+        bail!("invalid timestamp '{value}'");
+    }
+    let secs: u64 = secs.parse().with_context(|| format!("invalid timestamp '{value}'"))?;
+    let micros = fraction
+        .chars()
+        .chain(std::iter::repeat('0'))
+        .take(6)
+        .fold(0u64, |acc, digit| acc * 10 + u64::from(digit as u8 - b'0'));
+    secs.checked_mul(1_000_000)
+        .and_then(|us| us.checked_add(micros))
+        .ok_or_else(|| anyhow!("timestamp '{value}' out of range"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timestamp::TimestampSource;
+
+    const LOG: &str = "\
+(1699999999.123456) can0 2A5#000003E8FFFFFC18
+(1699999999.124012) can0 12345678#0102
+
+(1699999999.125) can0 20000080#0000000000000000
+(1699999999.126000) can0 151#R
+(1699999999.127000) can0 2A8#
+";
+
+    #[test]
+    fn from_candump_log_parses_frames_and_skips_error_and_remote_frames() {
+        let recording = PiperRecording::from_candump_log(LOG.as_bytes()).unwrap();
+
+        assert_eq!(recording.metadata.interface, "can0");
+        assert_eq!(recording.metadata.start_time, 1_699_999_999);
+        assert_eq!(recording.frame_count(), 3);
+
+        let first = &recording.frames[0];
+        assert_eq!(first.raw_id(), 0x2A5);
+        assert!(first.frame.is_standard());
+        assert_eq!(
+            first.data(),
+            &[0x00, 0x00, 0x03, 0xE8, 0xFF, 0xFF, 0xFC, 0x18]
+        );
+        assert_eq!(first.timestamp_us(), 1_699_999_999_123_456);
+        assert_eq!(first.direction, RecordedFrameDirection::Rx);
+        assert_eq!(first.timestamp_source, None);
+
+        assert!(recording.frames[1].frame.is_extended());
+        assert_eq!(recording.frames[1].raw_id(), 0x1234_5678);
+        assert!(recording.frames[2].data().is_empty());
+    }
+
+    #[test]
+    fn candump_log_round_trips() {
+        let recording = PiperRecording::from_candump_log(LOG.as_bytes()).unwrap();
+        let mut out = Vec::new();
+        recording.to_candump_log(&mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out.clone()).unwrap(),
+            "\
+(1699999999.123456) can0 2A5#000003E8FFFFFC18
+(1699999999.124012) can0 12345678#0102
+(1699999999.127000) can0 2A8#
+"
+        );
+        let reparsed = PiperRecording::from_candump_log(out.as_slice()).unwrap();
+        assert_eq!(reparsed.frames, recording.frames);
+    }
+
+    #[test]
+    fn to_candump_log_drops_direction_and_source() {
+        let mut recording =
+            PiperRecording::new(RecordingMetadata::new("vcan1".to_string(), 1_000_000));
+        recording.add_frame(TimestampedFrame::new(
+            PiperFrame::new_standard(0x155, [1, 2, 3]).unwrap().with_timestamp_us(42),
+            RecordedFrameDirection::Tx,
+            Some(TimestampSource::Hardware),
+        ));
+
+        let mut out = Vec::new();
+        recording.to_candump_log(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "(0.000042) vcan1 155#010203\n"
+        );
+    }
+
+    #[test]
+    fn from_candump_log_rejects_fd_and_malformed_lines_with_line_number() {
+        let error =
+            PiperRecording::from_candump_log("(1.0) can0 2A5##1AABB\n".as_bytes()).unwrap_err();
+        assert!(format!("{error:#}").contains("line 1"));
+        assert!(format!("{error:#}").contains("CAN FD"));
+
+        let error =
+            PiperRecording::from_candump_log("(1.0) can0 2A5#00\n(2.0) can0 2A5#0\n".as_bytes())
+                .unwrap_err();
+        assert!(format!("{error:#}").contains("line 2"));
+
+        assert!(PiperRecording::from_candump_log("1.0 can0 2A5#00\n".as_bytes()).is_err());
+        assert!(
+            PiperRecording::from_candump_log("(1.0) can0 2A5#001122334455667788\n".as_bytes())
+                .is_err()
+        );
+    }
+}
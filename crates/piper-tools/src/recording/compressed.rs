@@ -0,0 +1,586 @@
+//! zstd-compressed recording format.
+//!
+//! Layout after the 8-byte [`COMPRESSED_MAGIC`] and the v3 body version byte is a
+//! sequence of independently compressed blocks:
+//!
+//! ```text
+//! u32 frame_count | u32 uncompressed_len | u32 compressed_len | zstd frame
+//! ```
+//!
+//! The first block (`frame_count == 0`) holds the bincode-encoded metadata; each
+//! following block holds `frame_count` v3-encoded frames. Because every block is
+//! self-contained, a file cut short by a crash still loads every frame up to the
+//! last complete block. [`PiperRecording::load`] detects the format from the magic.
+
+use super::v3::{
+    BincodeRecordedFrameV3, RECORDING_VERSION, RecordingLimits, v3_options,
+    validate_metadata_string,
+};
+use super::{PiperRecording, RecordingMetadata, TimestampedFrame};
+use anyhow::{Context, Result, bail};
+use bincode::Options;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Compressed recording file magic.
+pub const COMPRESSED_MAGIC: &[u8; 8] = b"PIPERZS\0";
+
+/// Default zstd level: fast enough for live capture at 1 kHz.
+pub const DEFAULT_ZSTD_LEVEL: i32 = 3;
+
+/// Uncompressed bytes collected before a block is closed automatically.
+const BLOCK_TARGET_BYTES: usize = 64 * 1024;
+
+/// Upper bound for a single decompressed block accepted by the reader.
+const MAX_BLOCK_BYTES: u32 = 16 * 1024 * 1024;
+
+const BLOCK_HEADER_BYTES: usize = 12;
+
+/// Recording file compression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RecordingCompression {
+    /// Plain v3 file.
+    #[default]
+    None,
+    /// Block-wise zstd compression at the given level (1-22).
+    Zstd { level: i32 },
+}
+
+impl RecordingCompression {
+    /// zstd at [`DEFAULT_ZSTD_LEVEL`].
+    pub const fn zstd() -> Self {
+        Self::Zstd {
+            level: DEFAULT_ZSTD_LEVEL,
+        }
+    }
+}
+
+/// Incrementally writes a compressed recording.
+///
+/// Frames are buffered until about 64 KiB of encoded data accumulate, then
+/// compressed and written as one block. [`flush`](Self::flush) closes the
+/// current block early so that everything pushed so far survives a crash.
+pub struct CompressedRecordingWriter<W: Write> {
+    writer: W,
+    level: i32,
+    block: Vec<u8>,
+    block_frames: u32,
+    frame_count: u64,
+    limits: RecordingLimits,
+}
+
+impl<W: Write> CompressedRecordingWriter<W> {
+    pub fn new(writer: W, metadata: &RecordingMetadata, level: i32) -> Result<Self> {
+        Self::new_with_limits(writer, metadata, level, RecordingLimits::default())
+    }
+
+    pub fn new_with_limits(
+        mut writer: W,
+        metadata: &RecordingMetadata,
+        level: i32,
+        limits: RecordingLimits,
+    ) -> Result<Self> {
+        validate_metadata_string("interface", &metadata.interface, limits)?;
+        validate_metadata_string("platform", &metadata.platform, limits)?;
+        validate_metadata_string("operator", &metadata.operator, limits)?;
+        validate_metadata_string("notes", &metadata.notes, limits)?;
+
+        writer.write_all(COMPRESSED_MAGIC).context("write recording magic")?;
+        writer.write_all(&[RECORDING_VERSION]).context("write recording version")?;
+        let metadata = v3_options().serialize(metadata).context("encode recording metadata")?;
+        write_block(&mut writer, 0, &metadata, level)?;
+
+        Ok(Self {
+            writer,
+            level,
+            block: Vec::with_capacity(BLOCK_TARGET_BYTES),
+            block_frames: 0,
+            frame_count: 0,
+            limits,
+        })
+    }
+
+    pub fn push_frame(&mut self, frame: &TimestampedFrame) -> Result<()> {
+        if self.frame_count as usize >= self.limits.max_frames {
+            bail!(
+                "recording contains more than {} frames",
+                self.limits.max_frames
+            );
+        }
+
+        v3_options()
+            .serialize_into(&mut self.block, &BincodeRecordedFrameV3::from(frame))
+            .context("encode recording frame")?;
+        self.block_frames += 1;
+        self.frame_count += 1;
+
+        if self.block.len() >= BLOCK_TARGET_BYTES {
+            self.close_block()?;
+        }
+        Ok(())
+    }
+
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// Closes the current block and flushes the underlying writer.
+    pub fn flush(&mut self) -> Result<()> {
+        self.close_block()?;
+        self.writer.flush().context("flush recording stream")
+    }
+
+    pub fn finish(mut self) -> Result<W> {
+        self.flush()?;
+        Ok(self.writer)
+    }
+
+    fn close_block(&mut self) -> Result<()> {
+        if self.block_frames == 0 {
+            return Ok(());
+        }
+        write_block(&mut self.writer, self.block_frames, &self.block, self.level)?;
+        self.block.clear();
+        self.block_frames = 0;
+        Ok(())
+    }
+}
+
+fn write_block<W: Write>(writer: &mut W, frame_count: u32, data: &[u8], level: i32) -> Result<()> {
+    let compressed = zstd::bulk::compress(data, level).context("compress recording block")?;
+    writer.write_all(&frame_count.to_le_bytes()).context("write block header")?;
+    writer
+        .write_all(&(data.len() as u32).to_le_bytes())
+        .context("write block header")?;
+    writer
+        .write_all(&(compressed.len() as u32).to_le_bytes())
+        .context("write block header")?;
+    writer.write_all(&compressed).context("write recording block")?;
+    Ok(())
+}
+
+pub fn save_path(recording: &PiperRecording, path: &Path, level: i32) -> Result<()> {
+    let file = File::create(path).context("create recording file")?;
+    let mut writer =
+        CompressedRecordingWriter::new(BufWriter::new(file), &recording.metadata, level)?;
+    for frame in &recording.frames {
+        writer.push_frame(frame)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+/// Reads a compressed recording whose magic has already been consumed.
+pub(super) fn read_after_magic<R: Read>(
+    reader: &mut R,
+    limits: RecordingLimits,
+) -> Result<PiperRecording> {
+    let (metadata, _) = read_header_after_magic(reader, limits)?;
+    let mut recording = PiperRecording::new(metadata);
+    // A truncated trailing block (crash during write) ends the recording.
+    while let Some((frame_count, block)) = read_block(reader)? {
+        if recording.frames.len().saturating_add(frame_count as usize) > limits.max_frames {
+            bail!("recording contains more than {} frames", limits.max_frames);
+        }
+        decode_block_frames(frame_count, &block, &mut recording.frames)?;
+    }
+
+    Ok(recording)
+}
+
+/// Reads the version byte and metadata block, returning the metadata and the
+/// number of bytes consumed after the magic.
+fn read_header_after_magic<R: Read>(
+    reader: &mut R,
+    limits: RecordingLimits,
+) -> Result<(RecordingMetadata, u64)> {
+    let mut version = [0u8; 1];
+    reader.read_exact(&mut version).context("read recording header version")?;
+    if version[0] != RECORDING_VERSION {
+        bail!("unsupported recording file version: {}", version[0]);
+    }
+
+    let (frame_count, metadata_block, metadata_len) =
+        read_block_with_len(reader)?.context("compressed recording has no metadata block")?;
+    if frame_count != 0 {
+        bail!("first compressed recording block must hold metadata");
+    }
+    let metadata: RecordingMetadata = v3_options()
+        .with_limit(u64::from(MAX_BLOCK_BYTES))
+        .reject_trailing_bytes()
+        .deserialize(&metadata_block)
+        .context("decode recording metadata")?;
+    validate_metadata_string("interface", &metadata.interface, limits)?;
+    validate_metadata_string("platform", &metadata.platform, limits)?;
+    validate_metadata_string("operator", &metadata.operator, limits)?;
+    validate_metadata_string("notes", &metadata.notes, limits)?;
+
+    Ok((metadata, 1 + metadata_len))
+}
+
+fn decode_block_frames(
+    frame_count: u32,
+    block: &[u8],
+    frames: &mut Vec<TimestampedFrame>,
+) -> Result<()> {
+    let mut data = block;
+    for _ in 0..frame_count {
+        let frame: BincodeRecordedFrameV3 =
+            v3_options().deserialize_from(&mut data).context("decode recording frame")?;
+        frames.push(TimestampedFrame::try_from(frame)?);
+    }
+    if !data.is_empty() {
+        bail!("compressed recording block has trailing bytes");
+    }
+    Ok(())
+}
+
+/// One frame block of a compressed recording, as seen by [`CompressedRecordingIndex`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressedBlockIndexEntry {
+    /// Byte offset of the block header in the file.
+    pub file_offset: u64,
+    /// Offset of the block's first frame in the whole recording.
+    pub first_frame: usize,
+    pub frame_count: u32,
+    pub min_timestamp_us: u64,
+    pub max_timestamp_us: u64,
+    /// Offset (in the whole recording) of the block's earliest frame.
+    min_timestamp_frame: usize,
+}
+
+/// Block-level time index over a compressed recording file.
+///
+/// Building the index decompresses every block once to record its time span;
+/// afterwards [`seek`](Self::seek) and [`frames_in_range`](Self::frames_in_range)
+/// only decompress the blocks that can hold matching frames.
+#[derive(Debug, Clone)]
+pub struct CompressedRecordingIndex {
+    path: PathBuf,
+    metadata: RecordingMetadata,
+    blocks: Vec<CompressedBlockIndexEntry>,
+    frame_count: usize,
+}
+
+impl CompressedRecordingIndex {
+    /// Indexes a compressed recording file.
+    pub fn build<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::build_with_limits(path, RecordingLimits::default())
+    }
+
+    pub fn build_with_limits<P: AsRef<Path>>(path: P, limits: RecordingLimits) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).context("open recording file")?;
+        let mut reader = BufReader::new(file);
+
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic).context("read recording magic")?;
+        if &magic != COMPRESSED_MAGIC {
+            bail!(
+                "not a compressed recording; load it and use PiperRecording::index_by_time instead"
+            );
+        }
+        let (metadata, header_len) = read_header_after_magic(&mut reader, limits)?;
+
+        let mut file_offset = COMPRESSED_MAGIC.len() as u64 + header_len;
+        let mut blocks = Vec::new();
+        let mut frame_count = 0usize;
+        let mut frames = Vec::new();
+        while let Some((block_frames, block, encoded_len)) = read_block_with_len(&mut reader)? {
+            if frame_count.saturating_add(block_frames as usize) > limits.max_frames {
+                bail!("recording contains more than {} frames", limits.max_frames);
+            }
+            frames.clear();
+            decode_block_frames(block_frames, &block, &mut frames)?;
+            if let Some((min_offset, min_frame)) = frames
+                .iter()
+                .enumerate()
+                .min_by_key(|(offset, frame)| (frame.timestamp_us(), *offset))
+            {
+                blocks.push(CompressedBlockIndexEntry {
+                    file_offset,
+                    first_frame: frame_count,
+                    frame_count: block_frames,
+                    min_timestamp_us: min_frame.timestamp_us(),
+                    max_timestamp_us: frames
+                        .iter()
+                        .map(TimestampedFrame::timestamp_us)
+                        .max()
+                        .unwrap_or_default(),
+                    min_timestamp_frame: frame_count + min_offset,
+                });
+            }
+            frame_count += block_frames as usize;
+            file_offset += encoded_len;
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            metadata,
+            blocks,
+            frame_count,
+        })
+    }
+
+    pub fn metadata(&self) -> &RecordingMetadata {
+        &self.metadata
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+
+    pub fn blocks(&self) -> &[CompressedBlockIndexEntry] {
+        &self.blocks
+    }
+
+    /// Returns the offset of the first frame (in time order) at or after
+    /// `timestamp_us`, or the frame count if every frame is earlier.
+    pub fn seek(&self, timestamp_us: u64) -> Result<usize> {
+        // Earliest candidate among blocks that lie entirely at or after the target.
+        let mut best = self
+            .blocks
+            .iter()
+            .filter(|block| block.min_timestamp_us >= timestamp_us)
+            .map(|block| (block.min_timestamp_us, block.min_timestamp_frame))
+            .min();
+
+        // Blocks straddling the target must be decompressed to find their candidate.
+        let straddling: Vec<_> = self
+            .blocks
+            .iter()
+            .filter(|block| {
+                block.min_timestamp_us < timestamp_us && block.max_timestamp_us >= timestamp_us
+            })
+            .collect();
+        for block in straddling {
+            let candidate = self
+                .read_block_frames(block)?
+                .iter()
+                .enumerate()
+                .filter(|(_, frame)| frame.timestamp_us() >= timestamp_us)
+                .map(|(offset, frame)| (frame.timestamp_us(), block.first_frame + offset))
+                .min();
+            if let Some(candidate) = candidate
+                && best.is_none_or(|best| candidate < best)
+            {
+                best = Some(candidate);
+            }
+        }
+
+        Ok(best.map_or(self.frame_count, |(_, offset)| offset))
+    }
+
+    /// Frames with `start_us <= timestamp <= end_us`, in time order.
+    pub fn frames_in_range(&self, start_us: u64, end_us: u64) -> Result<Vec<TimestampedFrame>> {
+        let mut matches = Vec::new();
+        for block in self
+            .blocks
+            .iter()
+            .filter(|block| block.max_timestamp_us >= start_us && block.min_timestamp_us <= end_us)
+        {
+            for (offset, frame) in self.read_block_frames(block)?.into_iter().enumerate() {
+                let timestamp_us = frame.timestamp_us();
+                if timestamp_us >= start_us && timestamp_us <= end_us {
+                    matches.push((timestamp_us, block.first_frame + offset, frame));
+                }
+            }
+        }
+        matches.sort_unstable_by_key(|&(timestamp_us, offset, _)| (timestamp_us, offset));
+        Ok(matches.into_iter().map(|(_, _, frame)| frame).collect())
+    }
+
+    fn read_block_frames(
+        &self,
+        block: &CompressedBlockIndexEntry,
+    ) -> Result<Vec<TimestampedFrame>> {
+        let mut file = File::open(&self.path).context("open recording file")?;
+        file.seek(SeekFrom::Start(block.file_offset)).context("seek recording block")?;
+        let mut reader = BufReader::new(file);
+        let (frame_count, data) =
+            read_block(&mut reader)?.context("indexed recording block is missing")?;
+        if frame_count != block.frame_count {
+            bail!("recording changed since it was indexed");
+        }
+        let mut frames = Vec::with_capacity(frame_count as usize);
+        decode_block_frames(frame_count, &data, &mut frames)?;
+        Ok(frames)
+    }
+}
+
+/// Reads one block; `Ok(None)` at end of file or on a truncated trailing block.
+fn read_block<R: Read>(reader: &mut R) -> Result<Option<(u32, Vec<u8>)>> {
+    Ok(read_block_with_len(reader)?.map(|(frame_count, block, _)| (frame_count, block)))
+}
+
+/// Like [`read_block`], also returning the block's encoded length in the file.
+fn read_block_with_len<R: Read>(reader: &mut R) -> Result<Option<(u32, Vec<u8>, u64)>> {
+    let mut header = [0u8; BLOCK_HEADER_BYTES];
+    if !read_exact_or_eof(reader, &mut header)? {
+        return Ok(None);
+    }
+    let frame_count = u32::from_le_bytes(header[0..4].try_into().expect("4-byte slice"));
+    let uncompressed_len = u32::from_le_bytes(header[4..8].try_into().expect("4-byte slice"));
+    let compressed_len = u32::from_le_bytes(header[8..12].try_into().expect("4-byte slice"));
+    if uncompressed_len > MAX_BLOCK_BYTES || compressed_len > MAX_BLOCK_BYTES {
+        bail!(
+            "compressed recording block of {uncompressed_len} bytes exceeds limit of {MAX_BLOCK_BYTES}"
+        );
+    }
+
+    let mut compressed = vec![0u8; compressed_len as usize];
+    if !read_exact_or_eof(reader, &mut compressed)? {
+        return Ok(None);
+    }
+    let block = zstd::bulk::decompress(&compressed, uncompressed_len as usize)
+        .context("decompress recording block")?;
+    if block.len() != uncompressed_len as usize {
+        bail!("compressed recording block length mismatch");
+    }
+    let encoded_len = BLOCK_HEADER_BYTES as u64 + u64::from(compressed_len);
+    Ok(Some((frame_count, block, encoded_len)))
+}
+
+/// `read_exact` that reports a clean or partial EOF as `Ok(false)`.
+fn read_exact_or_eof<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<bool> {
+    match reader.read_exact(buf) {
+        Ok(()) => Ok(true),
+        Err(error) if error.kind() == ErrorKind::UnexpectedEof => Ok(false),
+        Err(error) => Err(error).context("read compressed recording"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::RecordedFrameDirection;
+    use crate::timestamp::TimestampSource;
+    use piper_protocol::frame::PiperFrame;
+
+    fn metadata() -> RecordingMetadata {
+        RecordingMetadata {
+            start_time: 42,
+            interface: "can0".to_string(),
+            bus_speed: 1_000_000,
+            platform: "linux".to_string(),
+            operator: "op".to_string(),
+            notes: "note".to_string(),
+        }
+    }
+
+    /// Joint feedback-like traffic: six IDs cycling at 1 kHz with slowly changing payloads.
+    fn force_control_recording(frames: usize) -> PiperRecording {
+        let mut recording = PiperRecording::new(metadata());
+        for i in 0..frames {
+            let id = 0x2A5 + (i % 6) as u32;
+            let angle = ((i / 6) as i32 * 3).to_be_bytes();
+            recording.add_frame(TimestampedFrame::new(
+                PiperFrame::new_standard(id, [angle[0], angle[1], angle[2], angle[3], 0, 0, 1, 2])
+                    .unwrap()
+                    .with_timestamp_us(1_000 + i as u64 * 167),
+                RecordedFrameDirection::Rx,
+                Some(TimestampSource::Hardware),
+            ));
+        }
+        recording
+    }
+
+    fn temp_path(name: &str) -> tempfile::TempPath {
+        tempfile::Builder::new()
+            .prefix(name)
+            .suffix(".bin")
+            .tempfile()
+            .unwrap()
+            .into_temp_path()
+    }
+
+    #[test]
+    fn compressed_recording_round_trips_through_auto_detecting_load() {
+        let recording = force_control_recording(20_000);
+        let raw = temp_path("raw");
+        let zst = temp_path("zst");
+
+        recording.save(&raw).unwrap();
+        recording.save_with_compression(&zst, RecordingCompression::zstd()).unwrap();
+
+        let loaded = PiperRecording::load(&zst).unwrap();
+        assert_eq!(loaded.metadata, recording.metadata);
+        assert_eq!(loaded.frames, recording.frames);
+
+        let raw_len = std::fs::metadata(&raw).unwrap().len();
+        let zst_len = std::fs::metadata(&zst).unwrap().len();
+        assert!(
+            raw_len >= zst_len * 5,
+            "expected at least 5x compression, raw={raw_len} zst={zst_len}"
+        );
+    }
+
+    #[test]
+    fn truncated_compressed_recording_recovers_complete_blocks() {
+        let mut writer = CompressedRecordingWriter::new(Vec::new(), &metadata(), 3).unwrap();
+        let recording = force_control_recording(30);
+        for frame in &recording.frames[..10] {
+            writer.push_frame(frame).unwrap();
+        }
+        writer.flush().unwrap();
+        for frame in &recording.frames[10..] {
+            writer.push_frame(frame).unwrap();
+        }
+        let mut bytes = writer.finish().unwrap();
+        // Simulate a crash in the middle of writing the second block.
+        bytes.truncate(bytes.len() - 5);
+
+        let loaded = read_after_magic(
+            &mut &bytes[COMPRESSED_MAGIC.len()..],
+            RecordingLimits::default(),
+        )
+        .unwrap();
+        assert_eq!(loaded.frames, recording.frames[..10]);
+    }
+
+    #[test]
+    fn compressed_index_seeks_and_ranges_like_the_in_memory_index() {
+        let recording = force_control_recording(20_000);
+        let path = temp_path("index");
+        recording.save_with_compression(&path, RecordingCompression::zstd()).unwrap();
+
+        let index = CompressedRecordingIndex::build(&path).unwrap();
+        assert!(index.blocks().len() > 1, "expected several frame blocks");
+        assert_eq!(index.frame_count(), recording.frame_count());
+        assert_eq!(index.metadata(), &recording.metadata);
+
+        let memory_index = recording.index_by_time();
+        let last = recording.frames.last().unwrap().timestamp_us();
+        for timestamp_us in [0, 1_000, 1_100, 1_500_000, last, last + 1] {
+            assert_eq!(
+                index.seek(timestamp_us).unwrap(),
+                recording.seek(&memory_index, timestamp_us),
+                "seek({timestamp_us})"
+            );
+        }
+
+        let (start, end) = (1_200_000, 1_300_000);
+        let expected: Vec<_> =
+            recording.frames_in_range(&memory_index, start, end).cloned().collect();
+        assert!(!expected.is_empty());
+        assert_eq!(index.frames_in_range(start, end).unwrap(), expected);
+
+        let raw = temp_path("raw-index");
+        recording.save(&raw).unwrap();
+        assert!(CompressedRecordingIndex::build(&raw).is_err());
+    }
+
+    #[test]
+    fn compressed_recording_enforces_frame_limit() {
+        let recording = force_control_recording(10);
+        let path = temp_path("limit");
+        recording.save_with_compression(&path, RecordingCompression::zstd()).unwrap();
+
+        let limits = RecordingLimits {
+            max_frames: 5,
+            ..RecordingLimits::default()
+        };
+        assert!(PiperRecording::load_with_limits(&path, limits).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+//! Time index for random access into a loaded recording.
+//!
+//! Frames are usually stored in capture order, which is not guaranteed to be
+//! timestamp order (TX/RX interleaving, mixed timestamp sources). The index
+//! keeps `(timestamp_us, frame offset)` pairs sorted by time so that seeks and
+//! range queries are binary searches instead of linear scans.
+
+use super::PiperRecording;
+
+/// Sorted `(timestamp_us, frame offset)` index over [`PiperRecording::frames`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecordingTimeIndex {
+    entries: Vec<(u64, usize)>,
+}
+
+impl RecordingTimeIndex {
+    /// Builds the index; frames with equal timestamps keep their capture order.
+    pub fn build(recording: &PiperRecording) -> Self {
+        let mut entries: Vec<(u64, usize)> = recording
+            .frames
+            .iter()
+            .enumerate()
+            .map(|(offset, frame)| (frame.timestamp_us(), offset))
+            .collect();
+        entries.sort_unstable();
+        Self { entries }
+    }
+
+    /// Number of indexed frames.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the offset of the first frame (in time order) at or after
+    /// `timestamp_us`, or the frame count if every frame is earlier.
+    pub fn seek(&self, timestamp_us: u64) -> usize {
+        let position = self.lower_bound(timestamp_us);
+        self.entries.get(position).map_or(self.entries.len(), |&(_, offset)| offset)
+    }
+
+    /// Offsets of frames with `start_us <= timestamp <= end_us`, in time order.
+    pub fn frames_in_range(&self, start_us: u64, end_us: u64) -> impl Iterator<Item = usize> + '_ {
+        let start = self.lower_bound(start_us);
+        let end = self.entries.partition_point(|&(timestamp, _)| timestamp <= end_us);
+        self.entries[start..end.max(start)].iter().map(|&(_, offset)| offset)
+    }
+
+    fn lower_bound(&self, timestamp_us: u64) -> usize {
+        self.entries.partition_point(|&(timestamp, _)| timestamp < timestamp_us)
+    }
+}
+
+impl PiperRecording {
+    /// Builds a time index for [`seek`](Self::seek) and
+    /// [`frames_in_range`](Self::frames_in_range).
+    pub fn index_by_time(&self) -> RecordingTimeIndex {
+        RecordingTimeIndex::build(self)
+    }
+
+    /// Returns the offset of the first frame at or after `timestamp_us`.
+    ///
+    /// `index` must have been built from this recording.
+    pub fn seek(&self, index: &RecordingTimeIndex, timestamp_us: u64) -> usize {
+        index.seek(timestamp_us)
+    }
+
+    /// Frames with `start_us <= timestamp <= end_us`, in time order.
+    ///
+    /// `index` must have been built from this recording.
+    pub fn frames_in_range<'a>(
+        &'a self,
+        index: &'a RecordingTimeIndex,
+        start_us: u64,
+        end_us: u64,
+    ) -> impl Iterator<Item = &'a super::TimestampedFrame> + 'a {
+        index.frames_in_range(start_us, end_us).map(|offset| &self.frames[offset])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::{RecordedFrameDirection, RecordingMetadata, TimestampedFrame};
+    use crate::timestamp::TimestampSource;
+    use piper_protocol::frame::PiperFrame;
+
+    fn frame(timestamp_us: u64) -> TimestampedFrame {
+        TimestampedFrame::new(
+            PiperFrame::new_standard(0x2A5, [1]).unwrap().with_timestamp_us(timestamp_us),
+            RecordedFrameDirection::Rx,
+            Some(TimestampSource::Hardware),
+        )
+    }
+
+    #[test]
+    fn time_index_seeks_and_ranges_in_time_order() {
+        let mut recording = PiperRecording::new(RecordingMetadata::new("can0".to_string(), 1));
+        for timestamp_us in [1_000, 3_000, 2_000, 3_000, 5_000] {
+            recording.add_frame(frame(timestamp_us));
+        }
+        let index = recording.index_by_time();
+        assert_eq!(index.len(), 5);
+
+        assert_eq!(recording.seek(&index, 0), 0);
+        assert_eq!(recording.seek(&index, 1_500), 2);
+        assert_eq!(recording.seek(&index, 3_000), 1);
+        assert_eq!(recording.seek(&index, 5_001), 5);
+
+        let in_range: Vec<u64> = recording
+            .frames_in_range(&index, 2_000, 3_000)
+            .map(TimestampedFrame::timestamp_us)
+            .collect();
+        assert_eq!(in_range, vec![2_000, 3_000, 3_000]);
+        assert_eq!(index.frames_in_range(4_000, 4_500).count(), 0);
+        assert_eq!(index.frames_in_range(3_000, 1_000).count(), 0);
+    }
+}
@@ -0,0 +1,352 @@
+//! # MCAP export
+//!
+//! Writes a [`PiperRecording`] as an uncompressed, unchunked MCAP file that
+//! Foxglove and other MCAP tooling can open directly.
+//!
+//! - One channel per CAN ID (topic `/piper/can/0x2A5`, extended IDs use 8 hex digits).
+//! - One JSON schema per channel describing the raw frame fields plus the
+//!   decoded feedback struct (`decoded`), when the ID is a known feedback frame.
+//! - Messages are JSON encoded; `log_time` and `publish_time` are the recorded
+//!   frame timestamp (hardware timestamp when available) in nanoseconds.
+//!
+//! The writer emits no summary section or indexes; readers fall back to a
+//! linear scan, which is fine for recording-sized files.
+
+use super::{PiperRecording, RecordedFrameDirection, TimestampedFrame};
+use crate::decode::decode_feedback;
+use crate::timestamp::TimestampSource;
+use anyhow::Result;
+use piper_protocol::frame::PiperFrame;
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+use std::io::Write;
+
+/// MCAP file magic (format version 0).
+const MCAP_MAGIC: &[u8; 8] = b"\x89MCAP0\r\n";
+
+const OP_HEADER: u8 = 0x01;
+const OP_FOOTER: u8 = 0x02;
+const OP_SCHEMA: u8 = 0x03;
+const OP_CHANNEL: u8 = 0x04;
+const OP_MESSAGE: u8 = 0x05;
+const OP_DATA_END: u8 = 0x0F;
+
+/// MCAP profile left empty: messages are plain JSON, not ROS.
+const PROFILE: &str = "";
+const LIBRARY: &str = concat!("piper-tools ", env!("CARGO_PKG_VERSION"));
+
+/// Channel key: raw CAN ID and whether it is an extended ID.
+type ChannelKey = (u32, bool);
+
+struct ChannelInfo {
+    id: u16,
+    sequence: u32,
+}
+
+impl PiperRecording {
+    /// Writes the recording as an MCAP file.
+    ///
+    /// Each [`TimestampedFrame`] becomes one JSON message on the channel of
+    /// its CAN ID. Recordings with more than `u16::MAX` distinct CAN IDs are
+    /// rejected, since MCAP channel IDs are 16-bit.
+    pub fn write_mcap<W: Write>(&self, mut w: W) -> Result<()> {
+        w.write_all(MCAP_MAGIC)?;
+
+        let mut header = Vec::new();
+        put_str(&mut header, PROFILE);
+        put_str(&mut header, LIBRARY);
+        write_record(&mut w, OP_HEADER, &header)?;
+
+        // Schema and channel records must precede the messages that use them.
+        let mut channels: BTreeMap<ChannelKey, ChannelInfo> = BTreeMap::new();
+        for frame in &self.frames {
+            let key = channel_key(&frame.frame);
+            if channels.contains_key(&key) {
+                continue;
+            }
+            let id = u16::try_from(channels.len() + 1)
+                .map_err(|_| anyhow::anyhow!("too many distinct CAN IDs for MCAP export"))?;
+            write_schema(&mut w, id, &frame.frame)?;
+            write_channel(&mut w, id, key)?;
+            channels.insert(key, ChannelInfo { id, sequence: 0 });
+        }
+
+        for frame in &self.frames {
+            let channel = channels
+                .get_mut(&channel_key(&frame.frame))
+                .expect("channel registered in first pass");
+            let log_time = frame.timestamp_us().saturating_mul(1_000);
+            let payload = serde_json::to_vec(&frame_message(frame))?;
+
+            let mut message = Vec::with_capacity(22 + payload.len());
+            message.extend_from_slice(&channel.id.to_le_bytes());
+            message.extend_from_slice(&channel.sequence.to_le_bytes());
+            message.extend_from_slice(&log_time.to_le_bytes());
+            message.extend_from_slice(&log_time.to_le_bytes());
+            message.extend_from_slice(&payload);
+            write_record(&mut w, OP_MESSAGE, &message)?;
+            channel.sequence = channel.sequence.wrapping_add(1);
+        }
+
+        // data_section_crc = 0: CRC not computed.
+        write_record(&mut w, OP_DATA_END, &0u32.to_le_bytes())?;
+
+        // No summary section: summary_start, summary_offset_start, summary_crc all zero.
+        let mut footer = Vec::with_capacity(20);
+        footer.extend_from_slice(&0u64.to_le_bytes());
+        footer.extend_from_slice(&0u64.to_le_bytes());
+        footer.extend_from_slice(&0u32.to_le_bytes());
+        write_record(&mut w, OP_FOOTER, &footer)?;
+
+        w.write_all(MCAP_MAGIC)?;
+        w.flush()?;
+        Ok(())
+    }
+}
+
+fn channel_key(frame: &PiperFrame) -> ChannelKey {
+    (frame.raw_id(), frame.is_extended())
+}
+
+fn topic(key: ChannelKey) -> String {
+    match key {
+        (id, false) => format!("/piper/can/0x{id:03X}"),
+        (id, true) => format!("/piper/can/0x{id:08X}"),
+    }
+}
+
+fn write_record<W: Write>(w: &mut W, opcode: u8, content: &[u8]) -> Result<()> {
+    w.write_all(&[opcode])?;
+    w.write_all(&(content.len() as u64).to_le_bytes())?;
+    w.write_all(content)?;
+    Ok(())
+}
+
+fn put_str(buf: &mut Vec<u8>, s: &str) {
+    put_bytes(buf, s.as_bytes());
+}
+
+fn put_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    buf.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    buf.extend_from_slice(bytes);
+}
+
+fn write_schema<W: Write>(w: &mut W, id: u16, frame: &PiperFrame) -> Result<()> {
+    let (name, decoded) = match decode_feedback(frame) {
+        Some(decoded) => (format!("piper.{}", decoded.name), Some(decoded.fields)),
+        None => ("piper.CanFrame".to_string(), None),
+    };
+
+    let mut properties = json!({
+        "can_id": { "type": "integer" },
+        "extended": { "type": "boolean" },
+        "data": { "type": "array", "items": { "type": "integer" } },
+        "direction": { "type": "string", "enum": ["rx", "tx"] },
+        "timestamp_us": { "type": "integer" },
+        "timestamp_source": { "type": ["string", "null"] },
+    });
+    if let Some(decoded) = decoded {
+        let fields: Map<String, Value> = decoded
+            .iter()
+            .map(|(field, value)| (field.clone(), json!({ "type": json_type(value) })))
+            .collect();
+        properties["decoded"] = json!({ "type": "object", "properties": fields });
+    }
+    let schema = json!({
+        "title": name,
+        "type": "object",
+        "properties": properties,
+    });
+
+    let mut content = Vec::new();
+    content.extend_from_slice(&id.to_le_bytes());
+    put_str(&mut content, &name);
+    put_str(&mut content, "jsonschema");
+    put_bytes(&mut content, &serde_json::to_vec(&schema)?);
+    write_record(w, OP_SCHEMA, &content)
+}
+
+fn write_channel<W: Write>(w: &mut W, id: u16, key: ChannelKey) -> Result<()> {
+    let mut content = Vec::new();
+    content.extend_from_slice(&id.to_le_bytes());
+    // Schema IDs mirror channel IDs (one schema per channel).
+    content.extend_from_slice(&id.to_le_bytes());
+    put_str(&mut content, &topic(key));
+    put_str(&mut content, "json");
+    // Empty metadata map (byte length prefix only).
+    content.extend_from_slice(&0u32.to_le_bytes());
+    write_record(w, OP_CHANNEL, &content)
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        _ => "object",
+    }
+}
+
+fn frame_message(frame: &TimestampedFrame) -> Value {
+    let mut message = json!({
+        "can_id": frame.raw_id(),
+        "extended": frame.frame.is_extended(),
+        "data": frame.data(),
+        "direction": match frame.direction {
+            RecordedFrameDirection::Rx => "rx",
+            RecordedFrameDirection::Tx => "tx",
+        },
+        "timestamp_us": frame.timestamp_us(),
+        "timestamp_source": frame.timestamp_source.map(|source| match source {
+            TimestampSource::Hardware => "hardware",
+            TimestampSource::Kernel => "kernel",
+            TimestampSource::Userspace => "userspace",
+        }),
+    });
+    if let Some(decoded) = decode_feedback(&frame.frame) {
+        message["decoded"] = Value::Object(decoded.fields);
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::RecordingMetadata;
+    use piper_protocol::ids::*;
+
+    fn read_u16(bytes: &[u8]) -> u16 {
+        u16::from_le_bytes(bytes[..2].try_into().unwrap())
+    }
+
+    fn read_u32(bytes: &[u8]) -> u32 {
+        u32::from_le_bytes(bytes[..4].try_into().unwrap())
+    }
+
+    fn read_u64(bytes: &[u8]) -> u64 {
+        u64::from_le_bytes(bytes[..8].try_into().unwrap())
+    }
+
+    fn read_str(bytes: &[u8]) -> (String, &[u8]) {
+        let len = read_u32(bytes) as usize;
+        (
+            String::from_utf8(bytes[4..4 + len].to_vec()).unwrap(),
+            &bytes[4 + len..],
+        )
+    }
+
+    /// Splits an MCAP file into (opcode, content) records, checking both magics.
+    fn records(bytes: &[u8]) -> Vec<(u8, Vec<u8>)> {
+        assert_eq!(&bytes[..8], MCAP_MAGIC);
+        assert_eq!(&bytes[bytes.len() - 8..], MCAP_MAGIC);
+        let mut rest = &bytes[8..bytes.len() - 8];
+        let mut out = Vec::new();
+        while !rest.is_empty() {
+            let opcode = rest[0];
+            let len = read_u64(&rest[1..]) as usize;
+            out.push((opcode, rest[9..9 + len].to_vec()));
+            rest = &rest[9 + len..];
+        }
+        out
+    }
+
+    fn recording() -> PiperRecording {
+        let mut recording =
+            PiperRecording::new(RecordingMetadata::new("can0".to_string(), 1_000_000));
+        let joint12 = PiperFrame::new_standard(
+            ID_JOINT_FEEDBACK_12.raw() as u32,
+            [0x00, 0x00, 0x03, 0xE8, 0xFF, 0xFF, 0xFC, 0x18],
+        )
+        .unwrap();
+        recording.add_frame(TimestampedFrame::new(
+            joint12.with_timestamp_us(1_000),
+            RecordedFrameDirection::Rx,
+            Some(TimestampSource::Hardware),
+        ));
+        recording.add_frame(TimestampedFrame::new(
+            PiperFrame::new_extended(0x1234_5678, [1, 2]).unwrap().with_timestamp_us(1_500),
+            RecordedFrameDirection::Tx,
+            None,
+        ));
+        recording.add_frame(TimestampedFrame::new(
+            joint12.with_timestamp_us(2_000),
+            RecordedFrameDirection::Rx,
+            Some(TimestampSource::Hardware),
+        ));
+        recording
+    }
+
+    #[test]
+    fn write_mcap_emits_channel_per_can_id_with_hardware_log_time() {
+        let mut bytes = Vec::new();
+        recording().write_mcap(&mut bytes).unwrap();
+        let records = records(&bytes);
+
+        let opcodes: Vec<u8> = records.iter().map(|(op, _)| *op).collect();
+        assert_eq!(
+            opcodes,
+            vec![
+                OP_HEADER,
+                OP_SCHEMA,
+                OP_CHANNEL,
+                OP_SCHEMA,
+                OP_CHANNEL,
+                OP_MESSAGE,
+                OP_MESSAGE,
+                OP_MESSAGE,
+                OP_DATA_END,
+                OP_FOOTER,
+            ]
+        );
+
+        let (_, schema) = &records[1];
+        assert_eq!(read_u16(schema), 1);
+        let (name, rest) = read_str(&schema[2..]);
+        assert_eq!(name, "piper.JointFeedback12");
+        let (encoding, rest) = read_str(rest);
+        assert_eq!(encoding, "jsonschema");
+        let (schema_json, _) = read_str(rest);
+        let schema_json: Value = serde_json::from_str(&schema_json).unwrap();
+        assert_eq!(
+            schema_json["properties"]["decoded"]["properties"]["j1_deg"]["type"],
+            "number"
+        );
+
+        let (_, channel) = &records[4];
+        let (topic, rest) = read_str(&channel[4..]);
+        assert_eq!(topic, "/piper/can/0x12345678");
+        assert_eq!(read_str(rest).0, "json");
+
+        let messages: Vec<&Vec<u8>> = records
+            .iter()
+            .filter(|(op, _)| *op == OP_MESSAGE)
+            .map(|(_, content)| content)
+            .collect();
+        // Third message: second frame on channel 1.
+        assert_eq!(read_u16(messages[2]), 1);
+        assert_eq!(read_u32(&messages[2][2..]), 1);
+        assert_eq!(read_u64(&messages[2][6..]), 2_000_000);
+        assert_eq!(read_u64(&messages[2][14..]), 2_000_000);
+
+        let payload: Value = serde_json::from_slice(&messages[0][22..]).unwrap();
+        assert_eq!(payload["can_id"], 0x2A5);
+        assert_eq!(payload["timestamp_source"], "hardware");
+        assert_eq!(payload["decoded"]["j1_deg"], 1.0);
+        assert_eq!(payload["decoded"]["j2_deg"], -1.0);
+
+        let unknown: Value = serde_json::from_slice(&messages[1][22..]).unwrap();
+        assert_eq!(unknown["direction"], "tx");
+        assert!(unknown.get("decoded").is_none());
+    }
+
+    #[test]
+    fn write_mcap_of_empty_recording_is_valid() {
+        let recording = PiperRecording::new(RecordingMetadata::new("can0".to_string(), 1_000_000));
+        let mut bytes = Vec::new();
+        recording.write_mcap(&mut bytes).unwrap();
+
+        let opcodes: Vec<u8> = records(&bytes).iter().map(|(op, _)| *op).collect();
+        assert_eq!(opcodes, vec![OP_HEADER, OP_DATA_END, OP_FOOTER]);
+    }
+}
@@ -70,7 +70,7 @@ struct BincodeRecordingMetadata<'a> {
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-struct BincodeRecordedFrameV3 {
+pub(super) struct BincodeRecordedFrameV3 {
     frame: PiperFrame,
     direction: u8,
     timestamp_source: u8,
@@ -132,7 +132,7 @@ fn decode_timestamp_source(source: u8) -> Result<Option<TimestampSource>> {
     }
 }
 
-fn v3_options() -> impl Options {
+pub(super) fn v3_options() -> impl Options {
     bincode::DefaultOptions::new().with_little_endian().with_fixint_encoding()
 }
 
@@ -305,6 +305,14 @@ impl<W: Write + Seek> StreamingRecordingWriter<W> {
         self.frame_count
     }
 
+    /// Flushes buffered frames to the underlying writer.
+    ///
+    /// The frame-count field is only patched by [`finish`](Self::finish); a file
+    /// cut short after a flush still holds every flushed frame on disk.
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush().context("flush recording stream")
+    }
+
     pub fn finish(mut self) -> Result<W> {
         let end_offset = self.writer.stream_position().context("locate recording end")?;
         self.writer
@@ -332,6 +340,18 @@ pub fn load_path_with_limits(path: &Path, limits: RecordingLimits) -> Result<Pip
 
     let mut magic = [0u8; 8];
     reader.read_exact(&mut magic).context("read recording magic")?;
+    if &magic == super::compressed::COMPRESSED_MAGIC {
+        if let Some(file_len) = metadata_len
+            && file_len > limits.max_body_bytes
+        {
+            bail!(
+                "compressed recording is {} bytes, limit is {}",
+                file_len,
+                limits.max_body_bytes
+            );
+        }
+        return super::compressed::read_after_magic(&mut reader, limits);
+    }
     if &magic != MAGIC {
         bail!("invalid recording file magic");
     }
@@ -394,7 +414,11 @@ fn validate_recording(recording: &PiperRecording, limits: RecordingLimits) -> Re
     Ok(())
 }
 
-fn validate_metadata_string(name: &str, value: &str, limits: RecordingLimits) -> Result<()> {
+pub(super) fn validate_metadata_string(
+    name: &str,
+    value: &str,
+    limits: RecordingLimits,
+) -> Result<()> {
     if value.len() > limits.max_metadata_string_bytes {
         bail!(
             "metadata {name} is {} bytes, limit is {}",
@@ -22,6 +22,12 @@ pub struct SafetyConfig {
     /// E-Stop 设置
     #[serde(rename = "estop")]
     pub estop: EStopSettings,
+
+    /// 碰撞检测设置
+    ///
+    /// 旧配置文件缺省此段时使用默认阈值。
+    #[serde(rename = "collision", default)]
+    pub collision: CollisionSettings,
 }
 
 impl SafetyConfig {
@@ -31,6 +37,7 @@ impl SafetyConfig {
             limits: SafetyLimits::default(),
             confirmation: ConfirmationSettings::default(),
             estop: EStopSettings::default(),
+            collision: CollisionSettings::default(),
         }
     }
 
@@ -49,6 +56,8 @@ impl SafetyConfig {
     /// joints_min = [-3.14, -1.57, -1.57, -1.57, -1.57, -3.14]
     /// joints_max = [3.14, 1.57, 1.57, 1.57, 1.57, 3.14]
     /// max_step_angle = 30.0
+    /// max_torque_nm = [8.0, 8.0, 8.0, 3.0, 3.0, 3.0]
+    /// max_velocity_rad_s = [3.0, 3.0, 3.0, 3.0, 3.0, 3.0]
     ///
     /// [confirmation]
     /// threshold_degrees = 10.0
@@ -57,6 +66,11 @@ impl SafetyConfig {
     /// [estop]
     /// enabled = true
     /// timeout_ms = 50
+    ///
+    /// [collision]
+    /// max_jerk_rad_s3 = 2000.0
+    /// max_torque_rate_nm_s = 200.0
+    /// debounce_samples = 3
     /// ```
     pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, anyhow::Error> {
         let content =
@@ -113,6 +127,17 @@ impl SafetyConfig {
     }
 }
 
+/// 超出安全限制时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SafetyLimitMode {
+    /// 将超限值限制到边界后继续下发（默认）
+    #[default]
+    Clamp,
+    /// 拒绝整批命令并返回错误
+    Reject,
+}
+
 /// 安全限制
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SafetyLimits {
@@ -130,6 +155,97 @@ pub struct SafetyLimits {
 
     /// 单步最大角度（度）
     pub max_step_angle: f64,
+
+    /// 各关节最大力矩（N·m，绝对值）
+    ///
+    /// 旧配置文件缺省此字段时使用 MIT 协议的力矩量程。
+    #[serde(default = "default_max_torque_nm")]
+    pub max_torque_nm: Vec<f64>,
+
+    /// 各关节最大速度（rad/s，绝对值）
+    #[serde(default = "default_max_velocity_rad_s")]
+    pub max_velocity_rad_s: Vec<f64>,
+
+    /// 超限处理方式
+    #[serde(default)]
+    pub mode: SafetyLimitMode,
+}
+
+fn default_max_torque_nm() -> Vec<f64> {
+    vec![8.0; 6]
+}
+
+fn default_max_velocity_rad_s() -> Vec<f64> {
+    vec![3.0; 6]
+}
+
+impl SafetyLimits {
+    /// 指定关节的位置范围 `(min, max)`（rad）
+    ///
+    /// 关节索引超出配置长度时返回 `None`（视为未配置）。
+    pub fn joint_position_limits(&self, joint_index: usize) -> Option<(f64, f64)> {
+        Some((
+            *self.joints_min.get(joint_index)?,
+            *self.joints_max.get(joint_index)?,
+        ))
+    }
+
+    /// 指定关节的力矩上限（N·m）
+    ///
+    /// 关节索引超出配置长度时返回 `None`（视为未配置）。
+    pub fn joint_torque_limit(&self, joint_index: usize) -> Option<f64> {
+        self.max_torque_nm.get(joint_index).copied()
+    }
+
+    /// 指定关节的速度上限（rad/s）
+    ///
+    /// 关节索引超出配置长度时返回 `None`（视为未配置）。
+    pub fn joint_velocity_limit(&self, joint_index: usize) -> Option<f64> {
+        self.max_velocity_rad_s.get(joint_index).copied()
+    }
+
+    /// 检查配置本身是否合法
+    ///
+    /// 力矩/速度上限必须是非负有限值，关节位置范围必须满足 `min <= max` 且均为有限值。
+    /// 配置多从 TOML 加载，应在进入控制循环之前调用，错误信息指明具体字段与关节。
+    pub fn validate(&self) -> Result<(), String> {
+        for (field, values) in [
+            ("max_torque_nm", &self.max_torque_nm),
+            ("max_velocity_rad_s", &self.max_velocity_rad_s),
+        ] {
+            for (index, value) in values.iter().enumerate() {
+                if !value.is_finite() || *value < 0.0 {
+                    return Err(format!(
+                        "{field}[{index}] must be a finite non-negative value, got {value}"
+                    ));
+                }
+            }
+        }
+        for (index, (min, max)) in self.joints_min.iter().zip(&self.joints_max).enumerate() {
+            if !min.is_finite() || !max.is_finite() {
+                return Err(format!(
+                    "joint {index} position range must be finite, got [{min}, {max}]"
+                ));
+            }
+            if min > max {
+                return Err(format!(
+                    "joint {index} position range is inverted: joints_min {min} > joints_max {max}"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// 检查关节力矩是否在限制内（未配置的关节视为不限制）
+    pub fn check_joint_torque(&self, joint_index: usize, torque: f64) -> bool {
+        self.joint_torque_limit(joint_index).is_none_or(|limit| torque.abs() <= limit)
+    }
+
+    /// 检查关节速度是否在限制内（未配置的关节视为不限制）
+    pub fn check_joint_velocity(&self, joint_index: usize, velocity: f64) -> bool {
+        self.joint_velocity_limit(joint_index)
+            .is_none_or(|limit| velocity.abs() <= limit)
+    }
 }
 
 impl Default for SafetyLimits {
@@ -155,6 +271,9 @@ impl Default for SafetyLimits {
                 std::f64::consts::PI,
             ],
             max_step_angle: 30.0, // 度
+            max_torque_nm: default_max_torque_nm(),
+            max_velocity_rad_s: default_max_velocity_rad_s(),
+            mode: SafetyLimitMode::default(),
         }
     }
 }
@@ -199,6 +318,52 @@ impl Default for EStopSettings {
     }
 }
 
+/// 碰撞检测设置
+///
+/// 由反馈推算的关节加加速度（jerk）或力矩变化率超过阈值时视为碰撞。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollisionSettings {
+    /// 关节加加速度阈值（rad/s³，绝对值）
+    pub max_jerk_rad_s3: f64,
+
+    /// 关节力矩变化率阈值（N·m/s，绝对值）
+    pub max_torque_rate_nm_s: f64,
+
+    /// 去抖样本数
+    ///
+    /// 超限需连续持续该数量的反馈样本才视为一次碰撞事件，单帧毛刺会被忽略。
+    pub debounce_samples: usize,
+}
+
+impl Default for CollisionSettings {
+    fn default() -> Self {
+        Self {
+            max_jerk_rad_s3: 2000.0,
+            max_torque_rate_nm_s: 200.0,
+            debounce_samples: 3,
+        }
+    }
+}
+
+impl CollisionSettings {
+    /// 检查配置本身是否合法
+    ///
+    /// 阈值必须是正的有限值：0 会把每个样本都判为碰撞，NaN 则永远不会触发。
+    pub fn validate(&self) -> Result<(), String> {
+        for (field, value) in [
+            ("max_jerk_rad_s3", self.max_jerk_rad_s3),
+            ("max_torque_rate_nm_s", self.max_torque_rate_nm_s),
+        ] {
+            if !value.is_finite() || value <= 0.0 {
+                return Err(format!(
+                    "{field} must be a finite positive value, got {value}"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -270,5 +435,71 @@ mod tests {
         assert_eq!(limits.max_step_angle, 30.0);
         assert_eq!(limits.joints_min.len(), 6);
         assert_eq!(limits.joints_max.len(), 6);
+        assert_eq!(limits.max_torque_nm.len(), 6);
+        assert_eq!(limits.max_velocity_rad_s.len(), 6);
+        assert_eq!(limits.mode, SafetyLimitMode::Clamp);
+        assert_eq!(limits.joint_position_limits(6), None);
+    }
+
+    #[test]
+    fn test_joint_torque_and_velocity_limits() {
+        let mut limits = SafetyLimits::default();
+        limits.max_torque_nm[2] = 2.5;
+
+        assert!(limits.check_joint_torque(2, -2.5));
+        assert!(!limits.check_joint_torque(2, 2.6));
+        assert!(limits.check_joint_torque(0, 8.0));
+        assert!(!limits.check_joint_velocity(1, -3.5));
+        // 未配置的关节不限制
+        assert!(limits.check_joint_torque(10, 100.0));
+    }
+
+    #[test]
+    fn test_safety_limits_validate_rejects_invalid_config() {
+        assert!(SafetyLimits::default().validate().is_ok());
+
+        let mut inverted = SafetyLimits::default();
+        inverted.joints_min[1] = 1.0;
+        inverted.joints_max[1] = -1.0;
+        assert!(inverted.validate().unwrap_err().contains("joint 1"));
+
+        let mut negative = SafetyLimits::default();
+        negative.max_torque_nm[3] = -1.0;
+        assert!(negative.validate().unwrap_err().contains("max_torque_nm[3]"));
+
+        let mut nan = SafetyLimits::default();
+        nan.max_velocity_rad_s[0] = f64::NAN;
+        assert!(nan.validate().unwrap_err().contains("max_velocity_rad_s[0]"));
+    }
+
+    #[test]
+    fn test_collision_settings_validate_rejects_invalid_thresholds() {
+        assert!(CollisionSettings::default().validate().is_ok());
+
+        let zero = CollisionSettings {
+            max_jerk_rad_s3: 0.0,
+            ..CollisionSettings::default()
+        };
+        assert!(zero.validate().unwrap_err().contains("max_jerk_rad_s3"));
+
+        let nan = CollisionSettings {
+            max_torque_rate_nm_s: f64::NAN,
+            ..CollisionSettings::default()
+        };
+        assert!(nan.validate().unwrap_err().contains("max_torque_rate_nm_s"));
+    }
+
+    #[test]
+    fn test_legacy_limits_toml_uses_per_joint_defaults() {
+        let toml = r#"
+max_velocity = 3.0
+max_acceleration = 10.0
+joints_min = [-3.14, -1.57, -1.57, -1.57, -1.57, -3.14]
+joints_max = [3.14, 1.57, 1.57, 1.57, 1.57, 3.14]
+max_step_angle = 30.0
+"#;
+        let limits: SafetyLimits = toml::from_str(toml).unwrap();
+        assert_eq!(limits.max_torque_nm, vec![8.0; 6]);
+        assert_eq!(limits.max_velocity_rad_s, vec![3.0; 6]);
     }
 }
@@ -7,6 +7,10 @@
 //! piper-tools = { workspace = true, features = ["statistics"] }
 //! ```
 
+use crate::recording::{PiperRecording, RecordedFrameDirection};
+use piper_protocol::feedback::{
+    JointDriverHighSpeedFeedback, JointFeedback12, JointFeedback34, JointFeedback56,
+};
 use piper_protocol::frame::{CanId, FrameError, PiperFrame};
 use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
 use std::collections::HashMap;
@@ -289,7 +293,7 @@ impl CanIdDistribution {
     /// 获取最常见的 CAN ID
     pub fn most_common(&self, limit: usize) -> Vec<(CanIdDistributionKey, u64)> {
         let mut items: Vec<_> = self.counts.iter().map(|(&k, &v)| (k, v)).collect();
-        items.sort_by(|a, b| b.1.cmp(&a.1));
+        items.sort_by_key(|b| std::cmp::Reverse(b.1));
         items.into_iter().take(limit).collect()
     }
 }
@@ -300,6 +304,612 @@ impl Default for CanIdDistribution {
     }
 }
 
+/// 默认断流判定倍数：帧间隔超过该 ID 中位间隔的 3 倍视为一次断流
+pub const DEFAULT_DROPOUT_FACTOR: f64 = 3.0;
+
+/// 单个 CAN ID 的帧时序统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanIdTimingStatistics {
+    /// CAN ID（区分标准帧/扩展帧）
+    pub id: CanIdDistributionKey,
+
+    /// 帧数
+    pub frame_count: u64,
+
+    /// 平均帧率（Hz，按该 ID 首末帧时间计算）
+    pub rate_hz: f64,
+
+    /// 最小帧间隔（微秒）
+    pub min_gap_us: u64,
+
+    /// 最大帧间隔（微秒）
+    pub max_gap_us: u64,
+
+    /// 平均帧间隔（微秒）
+    pub mean_gap_us: f64,
+
+    /// 中位帧间隔（微秒），断流判定基准
+    pub median_gap_us: u64,
+
+    /// 断流次数（间隔超过中位间隔 × 断流倍数）
+    pub dropouts: u64,
+
+    /// 断流期间估计缺失的帧数
+    pub missing_frames: u64,
+}
+
+/// 总线时序统计报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BusTimingReport {
+    /// 总帧数
+    pub total_frames: u64,
+
+    /// 首末帧跨度（微秒）
+    pub duration_us: u64,
+
+    /// 总帧率（帧/秒）
+    pub fps: f64,
+
+    /// 带宽使用（字节/秒，仅数据段）
+    pub bandwidth_bps: f64,
+
+    /// 时间戳回退的帧数（不参与间隔统计）
+    pub out_of_order_frames: u64,
+
+    /// 断流判定倍数
+    pub dropout_factor: f64,
+
+    /// 按 CAN ID 排序的统计
+    pub per_id: Vec<CanIdTimingStatistics>,
+}
+
+impl BusTimingReport {
+    /// 所有 ID 的断流次数之和
+    pub fn total_dropouts(&self) -> u64 {
+        self.per_id.iter().map(|stats| stats.dropouts).sum()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct CanIdTimingAccumulator {
+    frame_count: u64,
+    first_us: u64,
+    last_us: u64,
+    gaps_us: Vec<u64>,
+}
+
+/// 总线时序分析器
+///
+/// 逐帧累积每个 CAN ID 的帧间隔，`report()` 时计算帧率、间隔分布与断流。
+/// 断流以该 ID 的中位间隔为基准判定，不需要预先知道各反馈帧的周期。
+#[derive(Debug, Clone)]
+pub struct BusTimingAnalyzer {
+    per_id: HashMap<CanIdDistributionKey, CanIdTimingAccumulator>,
+    first_us: Option<u64>,
+    last_us: u64,
+    total_frames: u64,
+    total_bytes: u64,
+    out_of_order_frames: u64,
+    dropout_factor: f64,
+}
+
+impl BusTimingAnalyzer {
+    /// 创建分析器（断流倍数为 [`DEFAULT_DROPOUT_FACTOR`]）
+    pub fn new() -> Self {
+        Self {
+            per_id: HashMap::new(),
+            first_us: None,
+            last_us: 0,
+            total_frames: 0,
+            total_bytes: 0,
+            out_of_order_frames: 0,
+            dropout_factor: DEFAULT_DROPOUT_FACTOR,
+        }
+    }
+
+    /// 设置断流判定倍数（小于 1 时按 1 处理）
+    pub fn with_dropout_factor(mut self, factor: f64) -> Self {
+        self.dropout_factor = factor.max(1.0);
+        self
+    }
+
+    /// 添加一帧，时间戳取 `frame.timestamp_us()`
+    pub fn add_frame(&mut self, frame: &PiperFrame) {
+        self.add_frame_at(frame, frame.timestamp_us());
+    }
+
+    /// 添加一帧并指定时间戳（微秒），用于没有硬件时间戳的实时采集
+    pub fn add_frame_at(&mut self, frame: &PiperFrame, timestamp_us: u64) {
+        self.total_frames += 1;
+        self.total_bytes += frame.data().len() as u64;
+        let first_us = *self.first_us.get_or_insert(timestamp_us);
+        self.first_us = Some(first_us.min(timestamp_us));
+        self.last_us = self.last_us.max(timestamp_us);
+
+        let entry = self.per_id.entry(CanIdDistributionKey::from_frame(frame)).or_default();
+        if entry.frame_count == 0 {
+            entry.first_us = timestamp_us;
+        } else if timestamp_us < entry.last_us {
+            self.out_of_order_frames += 1;
+            entry.frame_count += 1;
+            return;
+        } else {
+            entry.gaps_us.push(timestamp_us - entry.last_us);
+        }
+        entry.frame_count += 1;
+        entry.last_us = timestamp_us;
+    }
+
+    /// 生成统计报告
+    pub fn report(&self) -> BusTimingReport {
+        let duration_us = self.first_us.map_or(0, |first| self.last_us.saturating_sub(first));
+        let mut per_id: Vec<_> = self
+            .per_id
+            .iter()
+            .map(|(&id, acc)| Self::id_statistics(id, acc, self.dropout_factor))
+            .collect();
+        per_id.sort_by_key(|stats| (stats.id.is_extended(), stats.id.raw_id()));
+
+        BusTimingReport {
+            total_frames: self.total_frames,
+            duration_us,
+            fps: CanBusStatistics::calculate_fps(self.total_frames, duration_us),
+            bandwidth_bps: CanBusStatistics::calculate_bandwidth(self.total_bytes, duration_us),
+            out_of_order_frames: self.out_of_order_frames,
+            dropout_factor: self.dropout_factor,
+            per_id,
+        }
+    }
+
+    fn id_statistics(
+        id: CanIdDistributionKey,
+        acc: &CanIdTimingAccumulator,
+        dropout_factor: f64,
+    ) -> CanIdTimingStatistics {
+        let span_us = acc.last_us.saturating_sub(acc.first_us);
+        let gap_count = acc.gaps_us.len() as u64;
+        let rate_hz = CanBusStatistics::calculate_fps(gap_count, span_us);
+
+        let mut sorted = acc.gaps_us.clone();
+        sorted.sort_unstable();
+        let median_gap_us = sorted.get(sorted.len() / 2).copied().unwrap_or(0);
+        let threshold_us = median_gap_us as f64 * dropout_factor;
+
+        let (mut dropouts, mut missing_frames) = (0, 0);
+        for &gap in sorted.iter().rev().take_while(|&&gap| gap as f64 > threshold_us) {
+            let Some(periods) = gap.checked_div(median_gap_us) else {
+                break;
+            };
+            dropouts += 1;
+            missing_frames += periods.saturating_sub(1);
+        }
+
+        CanIdTimingStatistics {
+            id,
+            frame_count: acc.frame_count,
+            rate_hz,
+            min_gap_us: sorted.first().copied().unwrap_or(0),
+            max_gap_us: sorted.last().copied().unwrap_or(0),
+            mean_gap_us: if gap_count == 0 {
+                0.0
+            } else {
+                sorted.iter().sum::<u64>() as f64 / gap_count as f64
+            },
+            median_gap_us,
+            dropouts,
+            missing_frames,
+        }
+    }
+}
+
+impl Default for BusTimingAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 单个关节的电流/力矩统计
+///
+/// 力矩由 0x251-0x256 高速反馈电流按 `JointDriverHighSpeedFeedback` 的力矩系数换算。
+/// 占空比与超阈值持续时间按零阶保持计算：每个采样值保持到该关节的下一帧。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JointEffortStatistics {
+    /// 关节序号（1-6）
+    pub joint_index: u8,
+
+    /// 样本数
+    pub sample_count: u64,
+
+    /// 最小电流（A）
+    pub min_current_a: f64,
+
+    /// 最大电流（A）
+    pub max_current_a: f64,
+
+    /// 平均电流（A）
+    pub mean_current_a: f64,
+
+    /// 电流均方根（A），用于评估电机发热
+    pub rms_current_a: f64,
+
+    /// 最小力矩（N·m）
+    pub min_torque_nm: f64,
+
+    /// 最大力矩（N·m）
+    pub max_torque_nm: f64,
+
+    /// 平均力矩（N·m）
+    pub mean_torque_nm: f64,
+
+    /// 力矩均方根（N·m）
+    pub rms_torque_nm: f64,
+
+    /// |力矩| 超过阈值的时间占比（0.0-1.0）
+    pub duty_cycle: f64,
+
+    /// |力矩| 连续超过阈值的最长持续时间（微秒）
+    pub longest_over_threshold_us: u64,
+
+    /// |力矩| 峰值（N·m）
+    pub peak_abs_torque_nm: f64,
+
+    /// |力矩| 峰值首次出现的时间戳（微秒）
+    pub peak_timestamp_us: u64,
+}
+
+/// 关节电流/力矩统计报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JointEffortReport {
+    /// 占空比判定的力矩阈值（N·m，按绝对值比较）
+    pub torque_threshold_nm: f64,
+
+    /// 有高速反馈的关节，按关节序号排序
+    pub joints: Vec<JointEffortStatistics>,
+}
+
+impl JointEffortReport {
+    /// 按关节序号（1-6）查找
+    pub fn joint(&self, joint_index: u8) -> Option<&JointEffortStatistics> {
+        self.joints.iter().find(|stats| stats.joint_index == joint_index)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct JointEffortAccumulator {
+    sample_count: u64,
+    min_current_a: f64,
+    max_current_a: f64,
+    sum_current_a: f64,
+    sum_sq_current_a: f64,
+    last_timestamp_us: u64,
+    last_over_threshold: bool,
+    span_us: u64,
+    over_threshold_us: u64,
+    current_run_us: u64,
+    longest_run_us: u64,
+    peak_current_a: f64,
+    peak_timestamp_us: u64,
+}
+
+/// 关节电流/力矩分析器
+///
+/// 逐帧累积 0x251-0x256 高速反馈，`report()` 时换算为每个关节的最小/最大/平均/RMS
+/// 电流与力矩、超阈值占空比和超阈值最长持续时间，用于核对运动是否满足电机热负载限制。
+/// 时间戳回退的帧只计入幅值统计，不计入时间统计。
+#[derive(Debug, Clone)]
+pub struct JointEffortAnalyzer {
+    torque_threshold_nm: f64,
+    per_joint: [JointEffortAccumulator; 6],
+}
+
+impl JointEffortAnalyzer {
+    /// 创建分析器，`torque_threshold_nm` 为占空比判定阈值（按绝对值比较）
+    pub fn new(torque_threshold_nm: f64) -> Self {
+        Self {
+            torque_threshold_nm: torque_threshold_nm.abs(),
+            per_joint: Default::default(),
+        }
+    }
+
+    /// 添加一帧（非高速反馈帧被忽略），时间戳取 `frame.timestamp_us()`
+    pub fn add_frame(&mut self, frame: &PiperFrame) {
+        let Ok(feedback) = JointDriverHighSpeedFeedback::try_from(*frame) else {
+            return;
+        };
+        let Some(acc) = self.per_joint.get_mut(usize::from(feedback.joint_index).wrapping_sub(1))
+        else {
+            return;
+        };
+
+        let timestamp_us = frame.timestamp_us();
+        let current_a = feedback.current();
+        let over_threshold = feedback.torque(None).abs() > self.torque_threshold_nm;
+
+        if acc.sample_count == 0 {
+            acc.min_current_a = current_a;
+            acc.max_current_a = current_a;
+            acc.last_timestamp_us = timestamp_us;
+        } else {
+            acc.min_current_a = acc.min_current_a.min(current_a);
+            acc.max_current_a = acc.max_current_a.max(current_a);
+            if timestamp_us >= acc.last_timestamp_us {
+                let held_us = timestamp_us - acc.last_timestamp_us;
+                acc.span_us += held_us;
+                if acc.last_over_threshold {
+                    acc.over_threshold_us += held_us;
+                    acc.current_run_us += held_us;
+                    acc.longest_run_us = acc.longest_run_us.max(acc.current_run_us);
+                }
+                acc.last_timestamp_us = timestamp_us;
+            }
+        }
+        if !over_threshold {
+            acc.current_run_us = 0;
+        }
+        if acc.sample_count == 0 || current_a.abs() > acc.peak_current_a.abs() {
+            acc.peak_current_a = current_a;
+            acc.peak_timestamp_us = timestamp_us;
+        }
+        acc.last_over_threshold = over_threshold;
+        acc.sample_count += 1;
+        acc.sum_current_a += current_a;
+        acc.sum_sq_current_a += current_a * current_a;
+    }
+
+    /// 生成统计报告
+    pub fn report(&self) -> JointEffortReport {
+        let joints = self
+            .per_joint
+            .iter()
+            .zip(1u8..)
+            .filter(|(acc, _)| acc.sample_count > 0)
+            .map(|(acc, joint_index)| Self::joint_statistics(joint_index, acc))
+            .collect();
+
+        JointEffortReport {
+            torque_threshold_nm: self.torque_threshold_nm,
+            joints,
+        }
+    }
+
+    fn joint_statistics(joint_index: u8, acc: &JointEffortAccumulator) -> JointEffortStatistics {
+        let coefficient = if joint_index <= 3 {
+            JointDriverHighSpeedFeedback::COEFFICIENT_1_3
+        } else {
+            JointDriverHighSpeedFeedback::COEFFICIENT_4_6
+        };
+        let samples = acc.sample_count as f64;
+        let mean_current_a = acc.sum_current_a / samples;
+        let rms_current_a = (acc.sum_sq_current_a / samples).sqrt();
+
+        JointEffortStatistics {
+            joint_index,
+            sample_count: acc.sample_count,
+            min_current_a: acc.min_current_a,
+            max_current_a: acc.max_current_a,
+            mean_current_a,
+            rms_current_a,
+            min_torque_nm: acc.min_current_a * coefficient,
+            max_torque_nm: acc.max_current_a * coefficient,
+            mean_torque_nm: mean_current_a * coefficient,
+            rms_torque_nm: rms_current_a * coefficient,
+            duty_cycle: if acc.span_us == 0 {
+                0.0
+            } else {
+                acc.over_threshold_us as f64 / acc.span_us as f64
+            },
+            longest_over_threshold_us: acc.longest_run_us,
+            peak_abs_torque_nm: acc.peak_current_a.abs() * coefficient,
+            peak_timestamp_us: acc.peak_timestamp_us,
+        }
+    }
+}
+
+impl PiperRecording {
+    /// 统计录制中每个关节的电流/力矩（只使用 RX 帧）
+    pub fn effort_statistics(&self, torque_threshold_nm: f64) -> JointEffortReport {
+        let mut analyzer = JointEffortAnalyzer::new(torque_threshold_nm);
+        for frame in
+            self.frames.iter().filter(|frame| frame.direction == RecordedFrameDirection::Rx)
+        {
+            analyzer.add_frame(&frame.frame);
+        }
+        analyzer.report()
+    }
+}
+
+/// 参与反馈断流检测的高频反馈 ID：末端位姿 0x2A2-0x2A4、关节位置 0x2A5-0x2A7、
+/// 高速驱动反馈 0x251-0x256
+const HIGH_RATE_FEEDBACK_IDS: [u32; 12] = [
+    0x2A2, 0x2A3, 0x2A4, 0x2A5, 0x2A6, 0x2A7, 0x251, 0x252, 0x253, 0x254, 0x255, 0x256,
+];
+
+/// 异常检测配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnomalyConfig {
+    /// 关节位置跳变判定的速度上限（rad/s）：相邻两帧位置差 / 时间差超过该值视为跳变
+    pub max_joint_velocity_rad_s: f64,
+
+    /// 高频反馈断流阈值（微秒）：同一反馈 ID 相邻两帧间隔超过该值视为断流
+    pub max_feedback_gap_us: u64,
+}
+
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        Self {
+            max_joint_velocity_rad_s: 5.0,
+            max_feedback_gap_us: 20_000, // 200Hz 反馈连续缺 3 帧
+        }
+    }
+}
+
+/// 录制中的异常
+///
+/// `frame_index` 为异常帧在 [`PiperRecording::frames`] 中的下标。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Anomaly {
+    /// 关节位置跳变：隐含速度超过 `max_joint_velocity_rad_s`
+    PositionJump {
+        frame_index: usize,
+        timestamp_us: u64,
+        /// 关节序号（1-6）
+        joint_index: u8,
+        /// 位置变化（rad）
+        delta_rad: f64,
+        /// 隐含速度（rad/s）
+        velocity_rad_s: f64,
+    },
+    /// 时间戳回退：RX 帧时间戳早于前一个 RX 帧
+    TimestampDiscontinuity {
+        frame_index: usize,
+        timestamp_us: u64,
+        previous_timestamp_us: u64,
+    },
+    /// 重复帧：与同一 ID 的上一帧数据和时间戳完全相同
+    DuplicateFrame {
+        frame_index: usize,
+        previous_frame_index: usize,
+        timestamp_us: u64,
+        id: CanIdDistributionKey,
+    },
+    /// 反馈断流：同一高频反馈 ID 相邻两帧间隔超过 `max_feedback_gap_us`
+    FeedbackGap {
+        /// 断流后第一帧的下标
+        frame_index: usize,
+        id: CanIdDistributionKey,
+        start_us: u64,
+        end_us: u64,
+    },
+}
+
+impl Anomaly {
+    /// 异常帧在录制中的下标
+    pub fn frame_index(&self) -> usize {
+        match self {
+            Self::PositionJump { frame_index, .. }
+            | Self::TimestampDiscontinuity { frame_index, .. }
+            | Self::DuplicateFrame { frame_index, .. }
+            | Self::FeedbackGap { frame_index, .. } => *frame_index,
+        }
+    }
+
+    /// 异常帧的时间戳（微秒）
+    pub fn timestamp_us(&self) -> u64 {
+        match self {
+            Self::PositionJump { timestamp_us, .. }
+            | Self::TimestampDiscontinuity { timestamp_us, .. }
+            | Self::DuplicateFrame { timestamp_us, .. } => *timestamp_us,
+            Self::FeedbackGap { end_us, .. } => *end_us,
+        }
+    }
+}
+
+/// 以默认配置检测录制中的异常，结果按帧下标排序
+pub fn detect_anomalies(recording: &PiperRecording) -> Vec<Anomaly> {
+    detect_anomalies_with_config(recording, &AnomalyConfig::default())
+}
+
+/// 检测录制中的异常（只检查 RX 帧），结果按帧下标排序
+///
+/// 时间相关的检测按每个 CAN ID 的录制顺序进行；时间戳回退的帧只报告
+/// `TimestampDiscontinuity`，不参与该 ID 的跳变/断流判定。
+pub fn detect_anomalies_with_config(
+    recording: &PiperRecording,
+    config: &AnomalyConfig,
+) -> Vec<Anomaly> {
+    let mut anomalies = Vec::new();
+    let mut previous_rx_timestamp_us = None;
+    let mut last_by_id: HashMap<CanIdDistributionKey, (usize, PiperFrame)> = HashMap::new();
+
+    for (frame_index, recorded) in recording.frames.iter().enumerate() {
+        if recorded.direction != RecordedFrameDirection::Rx {
+            continue;
+        }
+        let frame = recorded.frame;
+        let timestamp_us = frame.timestamp_us();
+
+        if let Some(previous_timestamp_us) = previous_rx_timestamp_us
+            && timestamp_us < previous_timestamp_us
+        {
+            anomalies.push(Anomaly::TimestampDiscontinuity {
+                frame_index,
+                timestamp_us,
+                previous_timestamp_us,
+            });
+        }
+        previous_rx_timestamp_us = Some(timestamp_us);
+
+        let id = CanIdDistributionKey::from_frame(&frame);
+        let Some((previous_frame_index, previous)) = last_by_id.insert(id, (frame_index, frame))
+        else {
+            continue;
+        };
+        let previous_timestamp_us = previous.timestamp_us();
+        if timestamp_us == previous_timestamp_us && frame.data() == previous.data() {
+            anomalies.push(Anomaly::DuplicateFrame {
+                frame_index,
+                previous_frame_index,
+                timestamp_us,
+                id,
+            });
+            continue;
+        }
+        if timestamp_us <= previous_timestamp_us {
+            continue;
+        }
+
+        let gap_us = timestamp_us - previous_timestamp_us;
+        if gap_us > config.max_feedback_gap_us
+            && id.is_standard()
+            && HIGH_RATE_FEEDBACK_IDS.contains(&id.raw_id())
+        {
+            anomalies.push(Anomaly::FeedbackGap {
+                frame_index,
+                id,
+                start_us: previous_timestamp_us,
+                end_us: timestamp_us,
+            });
+        }
+
+        if let (Some((joint_index, positions)), Some((_, previous_positions))) = (
+            joint_feedback_positions(&frame),
+            joint_feedback_positions(&previous),
+        ) {
+            let dt_s = gap_us as f64 / 1_000_000.0;
+            for (offset, (position, previous_position)) in
+                positions.into_iter().zip(previous_positions).enumerate()
+            {
+                let delta_rad = position - previous_position;
+                let velocity_rad_s = delta_rad.abs() / dt_s;
+                if velocity_rad_s > config.max_joint_velocity_rad_s {
+                    anomalies.push(Anomaly::PositionJump {
+                        frame_index,
+                        timestamp_us,
+                        joint_index: joint_index + offset as u8,
+                        delta_rad,
+                        velocity_rad_s,
+                    });
+                }
+            }
+        }
+    }
+
+    anomalies
+}
+
+/// 解析 0x2A5-0x2A7 关节位置反馈：返回 (首个关节序号, 两个关节位置 rad)
+fn joint_feedback_positions(frame: &PiperFrame) -> Option<(u8, [f64; 2])> {
+    if let Ok(feedback) = JointFeedback12::try_from(*frame) {
+        Some((1, [feedback.j1_rad(), feedback.j2_rad()]))
+    } else if let Ok(feedback) = JointFeedback34::try_from(*frame) {
+        Some((3, [feedback.j3_rad(), feedback.j4_rad()]))
+    } else if let Ok(feedback) = JointFeedback56::try_from(*frame) {
+        Some((5, [feedback.j5_rad(), feedback.j6_rad()]))
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -448,6 +1058,222 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bus_timing_analyzer_reports_gaps_and_dropouts() {
+        let mut analyzer = BusTimingAnalyzer::new();
+        let fast = PiperFrame::new_standard(0x2A5, [0; 8]).unwrap();
+        let slow = PiperFrame::new_standard(0x2A1, [0; 8]).unwrap();
+
+        // 0x2A5：2ms 周期，其中 t=20ms→30ms 断流（缺 4 帧）
+        let mut t = 0;
+        while t <= 40_000 {
+            if !(20_000 < t && t < 30_000) {
+                analyzer.add_frame_at(&fast, t);
+            }
+            t += 2_000;
+        }
+        // 0x2A1：10ms 周期，无断流
+        for t in (0..=40_000).step_by(10_000) {
+            analyzer.add_frame_at(&slow, t);
+        }
+
+        let report = analyzer.report();
+        assert_eq!(report.duration_us, 40_000);
+        assert_eq!(report.total_frames, 17 + 5);
+        assert_eq!(report.total_dropouts(), 1);
+
+        let slow_stats = &report.per_id[0];
+        assert_eq!(
+            slow_stats.id,
+            CanIdDistributionKey::standard(0x2A1).unwrap()
+        );
+        assert_eq!(slow_stats.rate_hz, 100.0);
+        assert_eq!(
+            (slow_stats.min_gap_us, slow_stats.max_gap_us),
+            (10_000, 10_000)
+        );
+        assert_eq!(slow_stats.dropouts, 0);
+
+        let fast_stats = &report.per_id[1];
+        assert_eq!(fast_stats.frame_count, 17);
+        assert_eq!(fast_stats.median_gap_us, 2_000);
+        assert_eq!(fast_stats.max_gap_us, 10_000);
+        assert_eq!(fast_stats.dropouts, 1);
+        assert_eq!(fast_stats.missing_frames, 4);
+    }
+
+    #[test]
+    fn bus_timing_analyzer_counts_out_of_order_frames() {
+        let mut analyzer = BusTimingAnalyzer::new().with_dropout_factor(0.5);
+        let frame = PiperFrame::new_standard(0x100, [1]).unwrap();
+        analyzer.add_frame_at(&frame, 1_000);
+        analyzer.add_frame_at(&frame, 900);
+        analyzer.add_frame_at(&frame, 2_000);
+
+        let report = analyzer.report();
+        assert_eq!(report.dropout_factor, 1.0);
+        assert_eq!(report.out_of_order_frames, 1);
+        assert_eq!(report.per_id[0].frame_count, 3);
+        assert_eq!(report.per_id[0].min_gap_us, 1_000);
+        assert!(BusTimingAnalyzer::default().report().per_id.is_empty());
+    }
+
+    fn high_speed_frame(joint_index: u32, current_ma: i16, timestamp_us: u64) -> PiperFrame {
+        let mut data = [0u8; 8];
+        data[2..4].copy_from_slice(&current_ma.to_be_bytes());
+        PiperFrame::new_standard(0x250 + joint_index, data)
+            .unwrap()
+            .with_timestamp_us(timestamp_us)
+    }
+
+    #[test]
+    fn joint_effort_analyzer_reports_magnitudes_duty_cycle_and_hold_time() {
+        let mut analyzer = JointEffortAnalyzer::new(2.0);
+        // J1：每 1ms 一帧，电流 1A, 3A, 3A, -4A, 1A（力矩系数 1.18125）
+        for (step, current_ma) in [1_000, 3_000, 3_000, -4_000, 1_000].into_iter().enumerate() {
+            analyzer.add_frame(&high_speed_frame(1, current_ma, step as u64 * 1_000));
+        }
+        // J5：始终低于阈值
+        analyzer.add_frame(&high_speed_frame(5, 500, 0));
+        analyzer.add_frame(&high_speed_frame(5, 500, 1_000));
+        // 非高速反馈帧被忽略
+        analyzer.add_frame(&PiperFrame::new_standard(0x2A5, [0; 8]).unwrap());
+
+        let report = analyzer.report();
+        assert_eq!(report.joints.len(), 2);
+        assert!(report.joint(2).is_none());
+
+        let j1 = report.joint(1).unwrap();
+        let coefficient = JointDriverHighSpeedFeedback::COEFFICIENT_1_3;
+        assert_eq!(j1.sample_count, 5);
+        assert_eq!((j1.min_current_a, j1.max_current_a), (-4.0, 3.0));
+        assert!((j1.mean_current_a - 0.8).abs() < 1e-9);
+        assert!((j1.rms_current_a - 7.2_f64.sqrt()).abs() < 1e-9);
+        assert!((j1.max_torque_nm - 3.0 * coefficient).abs() < 1e-9);
+        assert!((j1.rms_torque_nm - 7.2_f64.sqrt() * coefficient).abs() < 1e-9);
+        // 3A/3A/-4A 三个采样各保持 1ms，共 4ms 跨度
+        assert!((j1.duty_cycle - 0.75).abs() < 1e-9);
+        assert_eq!(j1.longest_over_threshold_us, 3_000);
+        assert!((j1.peak_abs_torque_nm - 4.0 * coefficient).abs() < 1e-9);
+        assert_eq!(j1.peak_timestamp_us, 3_000);
+
+        let j5 = report.joint(5).unwrap();
+        assert_eq!(j5.duty_cycle, 0.0);
+        assert_eq!(j5.longest_over_threshold_us, 0);
+        assert!(
+            (j5.max_torque_nm - 0.5 * JointDriverHighSpeedFeedback::COEFFICIENT_4_6).abs() < 1e-9
+        );
+    }
+
+    #[test]
+    fn effort_statistics_ignores_tx_frames() {
+        use crate::recording::{RecordingMetadata, TimestampedFrame};
+
+        let mut recording = PiperRecording::new(RecordingMetadata::new("can0".to_string(), 1));
+        recording.add_frame(TimestampedFrame::new(
+            high_speed_frame(2, 1_000, 0),
+            RecordedFrameDirection::Rx,
+            None,
+        ));
+        recording.add_frame(TimestampedFrame::new(
+            high_speed_frame(2, 9_000, 1_000),
+            RecordedFrameDirection::Tx,
+            None,
+        ));
+
+        let report = recording.effort_statistics(5.0);
+        assert_eq!(report.joint(2).unwrap().sample_count, 1);
+        assert_eq!(report.joint(2).unwrap().max_current_a, 1.0);
+    }
+
+    fn recorded_rx(
+        id: u32,
+        data: [u8; 8],
+        timestamp_us: u64,
+    ) -> crate::recording::TimestampedFrame {
+        crate::recording::TimestampedFrame::new(
+            PiperFrame::new_standard(id, data).unwrap().with_timestamp_us(timestamp_us),
+            RecordedFrameDirection::Rx,
+            None,
+        )
+    }
+
+    fn joint_feedback_data(first_deg: f64, second_deg: f64) -> [u8; 8] {
+        let mut data = [0u8; 8];
+        data[0..4].copy_from_slice(&((first_deg * 1000.0) as i32).to_be_bytes());
+        data[4..8].copy_from_slice(&((second_deg * 1000.0) as i32).to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn detect_anomalies_reports_jumps_discontinuities_duplicates_and_gaps() {
+        use crate::recording::RecordingMetadata;
+
+        let mut recording = PiperRecording::new(RecordingMetadata::new("can0".to_string(), 1));
+        // 0: 正常
+        recording.add_frame(recorded_rx(0x2A5, joint_feedback_data(0.0, 0.0), 0));
+        // 1: 5ms 内 J2 从 0° 跳到 10°（约 35 rad/s）
+        recording.add_frame(recorded_rx(0x2A5, joint_feedback_data(0.1, 10.0), 5_000));
+        // 2: 与上一帧完全相同
+        recording.add_frame(recorded_rx(0x2A5, joint_feedback_data(0.1, 10.0), 5_000));
+        // 3: 时间戳回退（0x2A6 首帧，不参与 ID 内判定）
+        recording.add_frame(recorded_rx(0x2A6, joint_feedback_data(0.0, 0.0), 4_000));
+        // 4: 0x2A5 断流 45ms，位置缓慢变化
+        recording.add_frame(recorded_rx(0x2A5, joint_feedback_data(0.2, 10.0), 50_000));
+        // TX 帧不参与检测
+        recording.add_frame(crate::recording::TimestampedFrame::new(
+            PiperFrame::new_standard(0x2A5, [0; 8]).unwrap(),
+            RecordedFrameDirection::Tx,
+            None,
+        ));
+
+        let anomalies = detect_anomalies(&recording);
+        assert_eq!(anomalies.len(), 4, "{anomalies:?}");
+
+        let Anomaly::PositionJump {
+            frame_index,
+            joint_index,
+            delta_rad,
+            velocity_rad_s,
+            ..
+        } = anomalies[0]
+        else {
+            panic!("expected position jump, got {:?}", anomalies[0]);
+        };
+        assert_eq!((frame_index, joint_index), (1, 2));
+        assert!((delta_rad - 10.0_f64.to_radians()).abs() < 1e-9);
+        assert!((velocity_rad_s - 10.0_f64.to_radians() / 0.005).abs() < 1e-6);
+
+        assert_eq!(
+            anomalies[1],
+            Anomaly::DuplicateFrame {
+                frame_index: 2,
+                previous_frame_index: 1,
+                timestamp_us: 5_000,
+                id: CanIdDistributionKey::standard(0x2A5).unwrap(),
+            }
+        );
+        assert_eq!(
+            anomalies[2],
+            Anomaly::TimestampDiscontinuity {
+                frame_index: 3,
+                timestamp_us: 4_000,
+                previous_timestamp_us: 5_000,
+            }
+        );
+        assert_eq!(
+            anomalies[3],
+            Anomaly::FeedbackGap {
+                frame_index: 4,
+                id: CanIdDistributionKey::standard(0x2A5).unwrap(),
+                start_us: 5_000,
+                end_us: 50_000,
+            }
+        );
+        assert_eq!(anomalies[3].timestamp_us(), 50_000);
+        assert!(anomalies.windows(2).all(|pair| pair[0].frame_index() <= pair[1].frame_index()));
+    }
+
     #[test]
     fn can_id_distribution_json_roundtrip_uses_string_keys() {
         use piper_protocol::frame::CanId;
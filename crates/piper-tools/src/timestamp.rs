@@ -104,6 +104,48 @@ pub fn detect_timestamp_source() -> TimestampSource {
     }
 }
 
+/// 会话相对时间戳归一化
+///
+/// 不同后端的 `PiperFrame::timestamp_us` 不在同一时间轴上：SocketCAN 为 Unix 纪元微秒，
+/// GS-USB 为设备计数器。归一化器按来源分别锚定：某来源的首帧对齐到当前会话时间
+/// （首帧整体为 0），之后按该来源自身的增量推进；输出单调不减。
+///
+/// 帧须按接收顺序输入。未标注来源的帧按 [`detect_timestamp_source`] 处理。
+#[derive(Debug, Clone, Default)]
+pub struct TimestampNormalizer {
+    /// 每个来源的锚点：(该来源首帧原始时间戳, 对应的会话相对时间)
+    anchors: [Option<(u64, u64)>; 3],
+    /// 最近一次输出的会话相对时间
+    last_us: u64,
+}
+
+impl TimestampNormalizer {
+    /// 创建新的归一化器（会话从第一帧开始）
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 将原始时间戳转换为相对会话起点的单调时间（微秒）
+    pub fn normalize(&mut self, timestamp_us: u64, source: Option<TimestampSource>) -> u64 {
+        let source = source.unwrap_or_else(detect_timestamp_source);
+        let (first_us, anchor_us) =
+            *self.anchors[source.index()].get_or_insert((timestamp_us, self.last_us));
+        let relative_us = anchor_us.saturating_add(timestamp_us.saturating_sub(first_us));
+        self.last_us = self.last_us.max(relative_us);
+        self.last_us
+    }
+}
+
+impl TimestampSource {
+    fn index(self) -> usize {
+        match self {
+            TimestampSource::Hardware => 0,
+            TimestampSource::Kernel => 1,
+            TimestampSource::Userspace => 2,
+        }
+    }
+}
+
 /// 获取当前时间（微秒）
 fn current_time_us() -> u64 {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -138,6 +180,55 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_normalizer_aligns_sources_to_session_start() {
+        let mut normalizer = TimestampNormalizer::new();
+        // SocketCAN：Unix 纪元微秒
+        assert_eq!(
+            normalizer.normalize(1_700_000_000_000_000, Some(TimestampSource::Kernel)),
+            0
+        );
+        assert_eq!(
+            normalizer.normalize(1_700_000_000_000_500, Some(TimestampSource::Kernel)),
+            500
+        );
+        // GS-USB：设备计数器，首帧锚定到当前会话时间
+        assert_eq!(
+            normalizer.normalize(42, Some(TimestampSource::Hardware)),
+            500
+        );
+        assert_eq!(
+            normalizer.normalize(1_042, Some(TimestampSource::Hardware)),
+            1_500
+        );
+        assert_eq!(
+            normalizer.normalize(1_700_000_000_001_700, Some(TimestampSource::Kernel)),
+            1_700
+        );
+    }
+
+    #[test]
+    fn test_normalizer_output_is_monotonic() {
+        let mut normalizer = TimestampNormalizer::new();
+        assert_eq!(
+            normalizer.normalize(10_000, Some(TimestampSource::Userspace)),
+            0
+        );
+        assert_eq!(
+            normalizer.normalize(12_000, Some(TimestampSource::Userspace)),
+            2_000
+        );
+        // 时间戳回退时保持不减
+        assert_eq!(
+            normalizer.normalize(11_000, Some(TimestampSource::Userspace)),
+            2_000
+        );
+        assert_eq!(
+            normalizer.normalize(5, Some(TimestampSource::Userspace)),
+            2_000
+        );
+    }
+
     #[test]
     fn test_timestamp_source_option_bincode_roundtrip_supports_none() {
         use bincode::Options;